@@ -0,0 +1,4 @@
+-- 512 = 0x200 = 0b10_0000_0000
+-- ENROLL_TOTP
+-- user 0 signed in but has to set up two-factor authentication first
+update "session" set scope_mask = 512 where token = 'Uv9rrKftNlHV0w2cbCHhf7wmtt5wQq8V';
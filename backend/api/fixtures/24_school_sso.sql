@@ -0,0 +1,18 @@
+insert into school_name (school_name_id, name, verified)
+values ('e5a0b04c-2e9f-11ee-9e3b-8bf2d1c8f4a1', 'Test School', true),
+       ('e5a0b2a4-2e9f-11ee-9e3b-9b1f6f5b1a22', 'Other School', true);
+
+insert into account (account_id, account_type)
+values ('f0e6b5b8-2e9f-11ee-9e3b-3f8a3c2e5b11', 1),
+       ('f0e6b7e8-2e9f-11ee-9e3b-6b2b0f9d7c33', 1);
+
+insert into school (school_id, school_name_id, email, account_id)
+values ('0a1f6b3e-2ea0-11ee-9e3b-1b7d2c4e6f01', 'e5a0b04c-2e9f-11ee-9e3b-8bf2d1c8f4a1', 'admin@school.edu', 'f0e6b5b8-2e9f-11ee-9e3b-3f8a3c2e5b11'),
+       ('0a1f6d96-2ea0-11ee-9e3b-4f3a8e9b2c02', 'e5a0b2a4-2e9f-11ee-9e3b-9b1f6f5b1a22', 'admin@other.edu', 'f0e6b7e8-2e9f-11ee-9e3b-6b2b0f9d7c33');
+
+insert into school_sso (school_id, issuer, client_id, client_secret, verification_token)
+values ('0a1f6b3e-2ea0-11ee-9e3b-1b7d2c4e6f01', 'https://login.school.edu', 'jigzi', 'secret', 'test-token');
+
+insert into school_sso_domain (school_id, domain, verified_at)
+values ('0a1f6b3e-2ea0-11ee-9e3b-1b7d2c4e6f01', 'school.edu', '2023-07-17 09:35:12+00'),
+       ('0a1f6b3e-2ea0-11ee-9e3b-1b7d2c4e6f01', 'staff.school.edu', null);
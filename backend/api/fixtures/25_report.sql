@@ -0,0 +1,7 @@
+insert into jig_report (id, jig_id, report_type, reporter_id, created_at)
+values ('6c5b0f4e-2f3a-11ee-9e3b-2b9d4e1f7a01', '0cc084bc-7c83-11eb-9f77-e3218dffb008', 0,
+        '1f241e1b-b537-493f-a230-075cb16315be', '2023-07-18 08:12:45+00');
+
+insert into resource_report (id, resource_id, report_type, reporter_id, created_at)
+values ('6c5b1232-2f3a-11ee-9e3b-5f1c8a3d6b02', 'd8067526-1518-11ed-87fa-ebaf880b6d9c', 0,
+        '1f241e1b-b537-493f-a230-075cb16315be', '2023-07-18 08:12:45+00');
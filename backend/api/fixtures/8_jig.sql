@@ -61,8 +61,12 @@ values ('d4cad43c-1dd5-11ec-8426-83d4a42e3ac9', '286b828c-1dd9-11ec-8426-571b03b
 
 insert into jig_player_session (index, jig_id, created_at, expires_at, direction, display_score, track_assessments,
                                 drag_assist)
-values (1234, '0cc084bc-7c83-11eb-9f77-e3218dffb008', now(), now() - interval '5 minutes', 0, true, true, true),
-       (1235, '0cc084bc-7c83-11eb-9f77-e3218dffb008', now(), now() + interval '5 minutes', 0, true, true, true);;
+values (1234, '0cc084bc-7c83-11eb-9f77-e3218dffb008', now(), now() + interval '5 minutes', 0, true, true, true),
+       (1235, '0cc084bc-7c83-11eb-9f77-e3218dffb008', now(), now() - interval '5 minutes', 0, true, true, true);;
+
+insert into jig_player_session_item (session_index, "index", jig_id)
+values (1234, 0, '0cc084bc-7c83-11eb-9f77-e3218dffb008'),
+       (1235, 0, '0cc084bc-7c83-11eb-9f77-e3218dffb008');
 
 insert into jig_play_count (jig_id)
 select id
@@ -0,0 +1,26 @@
+alter table subscription
+    add column if not exists trial_end timestamptz
+;
+
+create table billing_notification_preference
+(
+    user_id              uuid primary key not null references "user" (id) on delete cascade,
+    trial_ending         bool             not null default true,
+    payment_failed       bool             not null default true,
+    subscription_expired bool             not null default true,
+    created_at           timestamptz      not null default now(),
+    updated_at           timestamptz
+)
+;
+
+create table billing_notification
+(
+    subscription_id uuid        not null references subscription (subscription_id) on delete cascade,
+    user_id         uuid        not null references "user" (id) on delete cascade,
+    kind            int2        not null,
+    -- e.g. the invoice ID for failed payments so that each failed invoice is notified once
+    reference       text        not null default '',
+    sent_at         timestamptz not null default now(),
+    primary key (subscription_id, user_id, kind, reference)
+)
+;
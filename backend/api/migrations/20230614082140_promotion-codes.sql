@@ -0,0 +1,30 @@
+create table promotion_code
+(
+    promotion_code_id  uuid primary key not null default uuid_generate_v1mc(),
+    code               citext           not null unique,
+    stripe_coupon_id   text             not null,
+    percent_off        int2 check (percent_off between 1 and 100),
+    amount_off         int8 check (amount_off > 0),
+    -- 0: once, 1: repeating, 2: forever
+    duration           int2             not null,
+    duration_in_months int4 check (duration_in_months > 0),
+    max_redemptions    int4 check (max_redemptions > 0),
+    plan_ids           uuid[]           not null default '{}',
+    expires_at         timestamptz,
+    active             bool             not null default true,
+    created_at         timestamptz      not null default now(),
+    updated_at         timestamptz,
+    check ((percent_off is null) <> (amount_off is null)),
+    check ((duration = 1) = (duration_in_months is not null))
+)
+;
+
+create table promotion_code_redemption
+(
+    promotion_code_id uuid        not null references promotion_code (promotion_code_id) on delete cascade,
+    account_id        uuid        not null references "account" (account_id) on delete cascade,
+    subscription_id   uuid references subscription (subscription_id) on delete set null,
+    redeemed_at       timestamptz not null default now(),
+    primary key (promotion_code_id, account_id)
+)
+;
@@ -0,0 +1,22 @@
+alter table jig_report
+    add column status          int2 not null default 0,
+    add column assignee_id     uuid references "user" (id) on delete set null,
+    add column action          int2,
+    add column resolution_note text,
+    add column resolved_by     uuid references "user" (id) on delete set null,
+    add column resolved_at     timestamptz,
+    add column updated_at      timestamptz
+;
+
+alter table resource_report
+    add column status          int2 not null default 0,
+    add column assignee_id     uuid references "user" (id) on delete set null,
+    add column action          int2,
+    add column resolution_note text,
+    add column resolved_by     uuid references "user" (id) on delete set null,
+    add column resolved_at     timestamptz,
+    add column updated_at      timestamptz
+;
+
+create index jig_report_status_idx on jig_report (status, created_at);
+create index resource_report_status_idx on resource_report (status, created_at);
@@ -0,0 +1,156 @@
+--
+-- Curation workflow: curator assignment and SLA timestamps for jigs and resources, and curation
+-- data for playlists and courses.
+--
+
+alter table jig_curation_data
+    add column assignee_id       uuid references "user" (id) on delete set null,
+    add column created_at        timestamptz not null default now(),
+    add column status_updated_at timestamptz not null default now(),
+    add column assigned_at       timestamptz,
+    add column completed_at      timestamptz;
+
+update jig_curation_data
+set created_at        = jig_data.created_at,
+    status_updated_at = coalesce(jig_curation_data.updated_at, jig_data.created_at),
+    completed_at      = case when curation_status = 3 then jig_curation_data.updated_at end
+from jig
+         inner join jig_data on jig_data.id = jig.live_id
+where jig.id = jig_curation_data.jig_id;
+
+create index jig_curation_data_status_idx on jig_curation_data (curation_status, status_updated_at);
+
+alter table resource_curation_data
+    add column assignee_id       uuid references "user" (id) on delete set null,
+    add column created_at        timestamptz not null default now(),
+    add column status_updated_at timestamptz not null default now(),
+    add column assigned_at       timestamptz,
+    add column completed_at      timestamptz;
+
+update resource_curation_data
+set created_at        = resource_data.created_at,
+    status_updated_at = coalesce(resource_curation_data.updated_at, resource_data.created_at),
+    completed_at      = case when curation_status = 3 then resource_curation_data.updated_at end
+from resource
+         inner join resource_data on resource_data.id = resource.live_id
+where resource.id = resource_curation_data.resource_id;
+
+create index resource_curation_data_status_idx on resource_curation_data (curation_status, status_updated_at);
+
+--
+-- playlist
+--
+create table playlist_curation_data
+(
+    playlist_id          uuid primary key references playlist (id) on delete cascade,
+    display_name         boolean     not null default false,
+    language             boolean     not null default false,
+    categories           boolean     not null default false,
+    description          boolean     not null default false,
+    age_ranges           boolean     not null default false,
+    affiliations         boolean     not null default false,
+    additional_resources boolean     not null default false,
+    curation_status      smallint    not null default 0,
+    assignee_id          uuid references "user" (id) on delete set null,
+    created_at           timestamptz not null default now(),
+    status_updated_at    timestamptz not null default now(),
+    assigned_at          timestamptz,
+    completed_at         timestamptz,
+    updated_at           timestamptz
+);
+
+create index playlist_curation_data_status_idx on playlist_curation_data (curation_status, status_updated_at);
+
+create table playlist_curation_comment
+(
+    id          uuid primary key     default uuid_generate_v1mc(),
+    playlist_id uuid        not null references playlist (id) on delete cascade,
+    comment     text        not null,
+    author_id   uuid        not null references "user" (id),
+    created_at  timestamptz not null default now()
+);
+
+insert into playlist_curation_data(playlist_id, created_at, status_updated_at)
+select playlist.id, playlist_data.created_at, playlist_data.created_at
+from playlist
+         inner join playlist_data on playlist_data.id = playlist.live_id;
+
+--
+-- function: playlist_curation_add()
+-- trigger: playlist_curation_add
+-- description: add playlist_id to playlist_curation_data once added to playlist
+--
+create function playlist_curation_add() returns trigger
+    language plpgsql
+as
+$$
+begin
+    insert into playlist_curation_data(playlist_id)
+    values (NEW.id);
+    return NEW;
+end;
+$$;
+
+create trigger playlist_curation_add
+    after insert
+    on playlist
+    for each row
+execute procedure playlist_curation_add();
+
+--
+-- course
+--
+create table course_curation_data
+(
+    course_id            uuid primary key references course (id) on delete cascade,
+    display_name         boolean     not null default false,
+    language             boolean     not null default false,
+    categories           boolean     not null default false,
+    description          boolean     not null default false,
+    additional_resources boolean     not null default false,
+    curation_status      smallint    not null default 0,
+    assignee_id          uuid references "user" (id) on delete set null,
+    created_at           timestamptz not null default now(),
+    status_updated_at    timestamptz not null default now(),
+    assigned_at          timestamptz,
+    completed_at         timestamptz,
+    updated_at           timestamptz
+);
+
+create index course_curation_data_status_idx on course_curation_data (curation_status, status_updated_at);
+
+create table course_curation_comment
+(
+    id         uuid primary key     default uuid_generate_v1mc(),
+    course_id  uuid        not null references course (id) on delete cascade,
+    comment    text        not null,
+    author_id  uuid        not null references "user" (id),
+    created_at timestamptz not null default now()
+);
+
+insert into course_curation_data(course_id, created_at, status_updated_at)
+select course.id, course_data.created_at, course_data.created_at
+from course
+         inner join course_data on course_data.id = course.live_id;
+
+--
+-- function: course_curation_add()
+-- trigger: course_curation_add
+-- description: add course_id to course_curation_data once added to course
+--
+create function course_curation_add() returns trigger
+    language plpgsql
+as
+$$
+begin
+    insert into course_curation_data(course_id)
+    values (NEW.id);
+    return NEW;
+end;
+$$;
+
+create trigger course_curation_add
+    after insert
+    on course
+    for each row
+execute procedure course_curation_add();
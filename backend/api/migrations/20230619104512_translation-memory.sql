@@ -0,0 +1,43 @@
+--
+-- Translation memory: caches translations of source text per target language so that unchanged
+-- text isn't sent to Google Translate again.
+--
+create table translation_memory
+(
+    source_text     text        not null,
+    target_language text        not null,
+    translated_text text        not null,
+    created_at      timestamptz not null default now()
+);
+
+-- descriptions can exceed the btree row size, so index by hash
+create unique index translation_memory_source_idx on translation_memory (md5(source_text), target_language);
+
+--
+-- Glossary of terms which must not be translated, or must use fixed renderings.
+--
+create table translation_glossary
+(
+    id         uuid primary key     default uuid_generate_v1mc(),
+    term       text        not null unique check (term <> ''),
+    -- fixed renderings keyed by target language
+    renderings jsonb       not null default '{}',
+    created_at timestamptz not null default now(),
+    updated_at timestamptz
+);
+
+--
+-- Admin overrides of translated descriptions. These are kept on the asset rather than its data so
+-- that they survive publishing, and are applied over every automatic translation.
+--
+alter table jig
+    add column translated_description_override jsonb not null default '{}';
+
+alter table resource
+    add column translated_description_override jsonb not null default '{}';
+
+alter table playlist
+    add column translated_description_override jsonb not null default '{}';
+
+alter table course
+    add column translated_description_override jsonb not null default '{}';
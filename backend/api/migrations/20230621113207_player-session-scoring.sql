@@ -0,0 +1,23 @@
+--
+-- Server-side scoring for player session instances. Instances are now kept after being completed
+-- so that their results can be shown to the player and to the creator of the session.
+--
+
+alter table jig_player_session
+    add column creator_id uuid references "user" (id) on delete set null;
+
+alter table jig_player_session_instance
+    add column created_at   timestamptz not null default now(),
+    add column completed_at timestamptz;
+
+create table jig_player_session_instance_module
+(
+    instance_id uuid        not null references jig_player_session_instance (id) on delete cascade,
+    -- live module ids change when the jig is published again, so this isn't a foreign key
+    module_id   uuid        not null,
+    points      integer     not null default 0 check (points >= 0),
+    completed   boolean     not null default false,
+    created_at  timestamptz not null default now(),
+    updated_at  timestamptz,
+    primary key (instance_id, module_id)
+);
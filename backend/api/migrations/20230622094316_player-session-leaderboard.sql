@@ -0,0 +1,5 @@
+--
+-- Leaderboards for player sessions: players choose a display name when joining a session.
+--
+alter table jig_player_session_instance
+    add column display_name text check (char_length(display_name) between 1 and 50);
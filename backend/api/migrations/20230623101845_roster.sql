@@ -0,0 +1,32 @@
+--
+-- Class rosters: teachers list their students by nickname so that students can pick their name
+-- when joining a player session, without any personal data being collected.
+--
+create table roster
+(
+    id           uuid primary key     default uuid_generate_v1mc(),
+    creator_id   uuid        not null references "user" (id) on delete cascade,
+    display_name text        not null check (char_length(display_name) between 1 and 50),
+    created_at   timestamptz not null default now(),
+    updated_at   timestamptz
+);
+
+create index on roster (creator_id);
+
+create table roster_student
+(
+    id               uuid primary key     default uuid_generate_v1mc(),
+    roster_id        uuid        not null references roster (id) on delete cascade,
+    nickname         text        not null check (char_length(nickname) between 1 and 50),
+    -- index of the picture the student picks to confirm their name
+    picture_password smallint,
+    created_at       timestamptz not null default now(),
+    unique (roster_id, nickname)
+);
+
+alter table jig_player_session
+    add column roster_id uuid references roster (id) on delete set null;
+
+-- results are kept under the name the student joined with if they are removed from the roster
+alter table jig_player_session_instance
+    add column student_id uuid references roster_student (id) on delete set null;
@@ -0,0 +1,33 @@
+--
+-- Assignments: player sessions made up of several JIGs, which students can only play while they
+-- are open.
+--
+alter table jig_player_session
+    add column playlist_id  uuid references playlist (id) on delete set null,
+    add column opens_at     timestamptz,
+    add column closes_at    timestamptz,
+    add column max_attempts smallint check (max_attempts > 0),
+    add constraint jig_player_session_window_check check (opens_at < closes_at);
+
+create table jig_player_session_item
+(
+    session_index     int      not null references jig_player_session (index) on delete cascade,
+    "index"           smallint not null,
+    jig_id            uuid     not null references jig (id) on delete cascade,
+    -- overrides of the session's settings, either all or none are set
+    direction         smallint,
+    display_score     boolean,
+    track_assessments boolean,
+    drag_assist       boolean,
+    primary key (session_index, "index"),
+    check (num_nulls(direction, display_score, track_assessments, drag_assist) in (0, 4))
+);
+
+create index on jig_player_session_item (jig_id);
+
+-- existing sessions are assignments of a single JIG
+insert into jig_player_session_item (session_index, "index", jig_id)
+select index, 0, jig_id
+from jig_player_session;
+
+create index on jig_player_session (creator_id);
@@ -0,0 +1,6 @@
+-- Expired player sessions are purged by a scheduled job instead.
+drop trigger purge_expired_jig_player_session on jig_player_session;
+
+drop function expired_jig_player_session();
+
+create index on jig_player_session (expires_at);
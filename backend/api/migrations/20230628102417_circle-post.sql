@@ -0,0 +1,37 @@
+--
+-- Discussions in circles. Posts without a parent start a thread, the others reply to one.
+--
+create table circle_post
+(
+    id         uuid primary key     default uuid_generate_v1mc(),
+    circle_id  uuid        not null references circle (id) on delete cascade,
+    author_id  uuid        not null references "user" (id) on delete cascade,
+    parent_id  uuid references circle_post (id) on delete cascade,
+    body       text        not null check (char_length(body) between 1 and 5000),
+    created_at timestamptz not null default now(),
+    updated_at timestamptz
+);
+
+create index on circle_post (circle_id, created_at desc) where parent_id is null;
+create index on circle_post (parent_id, created_at);
+
+create table circle_post_asset
+(
+    post_id     uuid     not null references circle_post (id) on delete cascade,
+    "index"     smallint not null,
+    jig_id      uuid references jig (id) on delete cascade,
+    playlist_id uuid references playlist (id) on delete cascade,
+    resource_id uuid references resource (id) on delete cascade,
+    course_id   uuid references course (id) on delete cascade,
+    primary key (post_id, "index"),
+    check (num_nonnulls(jig_id, playlist_id, resource_id, course_id) = 1)
+);
+
+create table circle_post_reaction
+(
+    post_id    uuid        not null references circle_post (id) on delete cascade,
+    user_id    uuid        not null references "user" (id) on delete cascade,
+    reaction   smallint    not null,
+    created_at timestamptz not null default now(),
+    primary key (post_id, user_id)
+);
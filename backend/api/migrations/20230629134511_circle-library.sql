@@ -0,0 +1,28 @@
+--
+-- Shared libraries of circles, into which members pin assets.
+--
+create table circle_library_item
+(
+    id          uuid primary key     default uuid_generate_v1mc(),
+    circle_id   uuid        not null references circle (id) on delete cascade,
+    pinned_by   uuid        not null references "user" (id) on delete cascade,
+    jig_id      uuid references jig (id) on delete cascade,
+    playlist_id uuid references playlist (id) on delete cascade,
+    resource_id uuid references resource (id) on delete cascade,
+    course_id   uuid references course (id) on delete cascade,
+    note        text        not null default '' check (char_length(note) <= 1000),
+    pinned_at   timestamptz not null default now(),
+    updated_at  timestamptz,
+    check (num_nonnulls(jig_id, playlist_id, resource_id, course_id) = 1)
+);
+
+create index on circle_library_item (circle_id, pinned_at desc);
+
+-- an asset can only be pinned once into the same library
+create unique index circle_library_item_jig_key on circle_library_item (circle_id, jig_id) where jig_id is not null;
+create unique index circle_library_item_playlist_key on circle_library_item (circle_id, playlist_id) where playlist_id is not null;
+create unique index circle_library_item_resource_key on circle_library_item (circle_id, resource_id) where resource_id is not null;
+create unique index circle_library_item_course_key on circle_library_item (circle_id, course_id) where course_id is not null;
+
+-- look up the circles a jig is pinned into when indexing it
+create index on circle_library_item (jig_id) where jig_id is not null;
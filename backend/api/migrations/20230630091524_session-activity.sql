@@ -0,0 +1,11 @@
+--
+-- Lets users see where they're signed in and sign out remotely.
+-- `token` is secret, so sessions get a separate id to refer to them by.
+--
+alter table session
+    add column id           uuid not null default uuid_generate_v1mc() unique,
+    add column last_used_at timestamptz,
+    add column user_agent   text,
+    add column ip_hint      text;
+
+create index on session (user_id);
@@ -0,0 +1,30 @@
+--
+-- Two-factor authentication with codes from an authenticator app.
+--
+create table user_totp
+(
+    user_id         uuid primary key references "user" (id) on delete cascade,
+    secret          bytea       not null,
+    -- null until the user confirmed a code, i.e. finished setting up their authenticator app.
+    enabled_at      timestamptz,
+    -- the time step of the last accepted code, so that a code can't be used twice.
+    last_used_step  bigint,
+    created_at      timestamptz not null default now()
+);
+
+create table user_totp_recovery_code
+(
+    user_id    uuid        not null references "user" (id) on delete cascade,
+    code_hash  bytea       not null,
+    used_at    timestamptz,
+    primary key (user_id, code_hash)
+);
+
+-- wrong codes entered for a session which is waiting for a two-factor code.
+alter table session
+    add column failed_attempts int2 not null default 0;
+
+-- `SessionMask::GENERAL` now includes `ENROLL_TOTP`.
+update session
+set scope_mask = scope_mask | 512
+where scope_mask & 1 = 1;
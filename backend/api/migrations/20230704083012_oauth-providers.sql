@@ -0,0 +1,19 @@
+--
+-- Signing in with Microsoft and Apple, in addition to Google.
+--
+create table "user_auth_microsoft"
+(
+    user_id      uuid primary key references "user" (id) on delete cascade,
+    -- `{tenant id}:{object id}`
+    microsoft_id text unique not null,
+    created_at   timestamptz not null default now(),
+    updated_at   timestamptz
+);
+
+create table "user_auth_apple"
+(
+    user_id    uuid primary key references "user" (id) on delete cascade,
+    apple_id   text unique not null,
+    created_at timestamptz not null default now(),
+    updated_at timestamptz
+);
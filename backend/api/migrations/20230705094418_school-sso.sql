@@ -0,0 +1,31 @@
+--
+-- Single sign-on for school accounts, through the school's OpenID Connect identity provider.
+--
+create table "school_sso"
+(
+    school_id     uuid primary key references "school" (school_id) on delete cascade,
+    issuer        text        not null,
+    client_id     text        not null,
+    client_secret text        not null,
+    enabled       bool        not null default true,
+    created_at    timestamptz not null default now(),
+    updated_at    timestamptz
+);
+
+-- which school's identity provider signs in an email
+create table "school_sso_domain"
+(
+    school_id uuid   not null references "school_sso" (school_id) on delete cascade,
+    domain    citext not null unique
+);
+
+create index school_sso_domain_school_idx on school_sso_domain (school_id);
+
+create table "user_auth_sso"
+(
+    user_id    uuid primary key references "user" (id) on delete cascade,
+    -- `{school id}:{subject}`
+    sso_id     text unique not null,
+    created_at timestamptz not null default now(),
+    updated_at timestamptz
+);
@@ -0,0 +1,13 @@
+--
+-- Token buckets for rate limiting, shared between instances of the api.
+--
+-- Unlogged, as losing the buckets (e.g. on a crash) only resets the limits.
+create unlogged table "rate_limit_bucket"
+(
+    -- hash of the rule and who the requests are counted for (e.g. an IP address)
+    key        text primary key,
+    tokens     float8      not null,
+    updated_at timestamptz not null
+);
+
+create index rate_limit_bucket_updated_at_idx on rate_limit_bucket (updated_at);
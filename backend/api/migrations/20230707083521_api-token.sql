@@ -0,0 +1,19 @@
+--
+-- Personal API tokens, for using the API from scripts without copying a session cookie.
+-- Only a hash of the token is stored, it's shown to the user once when it's created.
+--
+create table user_api_token
+(
+    id           uuid primary key     default uuid_generate_v1mc(),
+    user_id      uuid        not null references "user" (id) on delete cascade,
+    name         text        not null,
+    token_hash   bytea       not null unique,
+    -- `UserScope`s the token can be used for, each one of the user's scopes when it was created.
+    scopes       int2[]      not null default '{}',
+    read_only    bool        not null,
+    created_at   timestamptz not null default now(),
+    last_used_at timestamptz,
+    expires_at   timestamptz
+);
+
+create index on user_api_token (user_id);
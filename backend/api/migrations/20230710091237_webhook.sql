@@ -0,0 +1,43 @@
+--
+-- Outbound webhooks, which are sent events about a user's assets and account.
+--
+create table webhook
+(
+    id         uuid primary key     default uuid_generate_v1mc(),
+    user_id    uuid        not null references "user" (id) on delete cascade,
+    url        text        not null,
+    -- HMAC key deliveries are signed with.
+    secret     text        not null,
+    -- `WebhookEvent`s the webhook is subscribed to.
+    events     int2[]      not null,
+    -- receives every user's events, only admins can set this.
+    all_users  bool        not null default false,
+    enabled    bool        not null default true,
+    created_at timestamptz not null default now(),
+    updated_at timestamptz
+);
+
+create index on webhook (user_id);
+
+--
+-- Delivery log, and queue for deliveries which haven't succeeded yet.
+--
+create table webhook_delivery
+(
+    id              uuid primary key     default uuid_generate_v1mc(),
+    webhook_id      uuid        not null references webhook (id) on delete cascade,
+    event           int2        not null,
+    data            jsonb       not null,
+    -- `WebhookDeliveryStatus`
+    status          int2        not null default 0,
+    attempts        int2        not null default 0,
+    response_status int2,
+    error           text,
+    created_at      timestamptz not null default now(),
+    last_attempt_at timestamptz,
+    -- null once the delivery succeeded or was given up on.
+    next_attempt_at timestamptz          default now()
+);
+
+create index on webhook_delivery (webhook_id, created_at);
+create index on webhook_delivery (next_attempt_at) where next_attempt_at is not null;
@@ -0,0 +1,29 @@
+--
+-- In-app notifications.
+--
+create table notification
+(
+    id         uuid primary key     default uuid_generate_v1mc(),
+    user_id    uuid        not null references "user" (id) on delete cascade,
+    -- `NotificationKind`
+    kind       int2        not null,
+    data       jsonb       not null default '{}',
+    created_at timestamptz not null default now(),
+    read_at    timestamptz
+);
+
+create index on notification (user_id, created_at desc);
+create index on notification (user_id) where read_at is null;
+
+create table notification_preference
+(
+    user_id              uuid primary key not null references "user" (id) on delete cascade,
+    new_follower         bool             not null default true,
+    asset_liked          bool             not null default true,
+    curation_comment     bool             not null default true,
+    report_resolved      bool             not null default true,
+    circle_invite        bool             not null default true,
+    subscription_changed bool             not null default true,
+    created_at           timestamptz      not null default now(),
+    updated_at           timestamptz
+);
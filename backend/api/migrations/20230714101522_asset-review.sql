@@ -0,0 +1,33 @@
+--
+-- Star ratings and reviews of live, public assets, with replies by the author of the asset.
+--
+create table asset_review
+(
+    id          uuid primary key     default uuid_generate_v1mc(),
+    user_id     uuid        not null references "user" (id) on delete cascade,
+    jig_id      uuid references jig (id) on delete cascade,
+    resource_id uuid references resource (id) on delete cascade,
+    playlist_id uuid references playlist (id) on delete cascade,
+    rating      int2        not null check (rating between 1 and 5),
+    text        text        not null default '' check (char_length(text) <= 1000),
+    reply       text check (char_length(reply) <= 1000),
+    replied_at  timestamptz,
+    created_at  timestamptz not null default now(),
+    updated_at  timestamptz,
+    check (num_nonnulls(jig_id, resource_id, playlist_id) = 1)
+);
+
+-- a user can only review an asset once
+create unique index asset_review_jig_key on asset_review (jig_id, user_id) where jig_id is not null;
+create unique index asset_review_resource_key on asset_review (resource_id, user_id) where resource_id is not null;
+create unique index asset_review_playlist_key on asset_review (playlist_id, user_id) where playlist_id is not null;
+
+create table asset_review_report
+(
+    review_id   uuid        not null references asset_review (id) on delete cascade,
+    reporter_id uuid        not null references "user" (id) on delete cascade,
+    -- `ReviewReportType`
+    report_type int2        not null,
+    created_at  timestamptz not null default now(),
+    primary key (review_id, reporter_id)
+);
@@ -0,0 +1,21 @@
+--
+-- Schools have to prove that they own an email domain, with a DNS TXT record, before their single sign-on
+-- signs in its emails.
+--
+alter table school_sso
+    add column verification_token text;
+
+update school_sso
+set verification_token = md5(random()::text || school_id::text);
+
+alter table school_sso
+    alter column verification_token set not null;
+
+-- domains which were already set up have to be verified as well.
+alter table school_sso_domain
+    add column verified_at timestamptz,
+    drop constraint school_sso_domain_domain_key,
+    add unique (school_id, domain);
+
+-- any school can add a domain, but only one can verify it
+create unique index school_sso_domain_verified_idx on school_sso_domain (domain) where verified_at is not null;
@@ -0,0 +1,12 @@
+--
+-- A user only has one invite to a circle until they join it, however often they're invited.
+--
+delete
+from notification
+where kind = 4
+  and id not in (select distinct on (user_id, data ->> 'circleId') id
+                 from notification
+                 where kind = 4
+                 order by user_id, data ->> 'circleId', created_at desc);
+
+create unique index notification_circle_invite_idx on notification (user_id, (data ->> 'circleId')) where kind = 4;
@@ -0,0 +1,11 @@
+--
+-- Version of the translation glossary, bumped whenever a term is added, updated or removed.
+-- Translations are only saved to the translation memory if they were made with the current version.
+--
+create table translation_glossary_version
+(
+    version bigint not null
+);
+
+insert into translation_glossary_version (version)
+values (0);
@@ -0,0 +1,42 @@
+-- Public content listed in the sitemap, shared by the sitemap index and its pages.
+-- Resources have no page of their own so they aren't listed, but their authors' profiles are.
+create view sitemap_entry as
+with asset as (
+    select 'jig' as kind, jig.id, jig.author_id, coalesce(jig_data.updated_at, jig_data.created_at) as last_edited
+    from jig
+             inner join jig_data on jig_data.id = jig.live_id
+             inner join jig_admin_data on jig_admin_data.jig_id = jig.id
+    where jig.published_at is not null
+      and jig_data.privacy_level = 0
+      and not jig_admin_data.blocked
+    union all
+    select 'resource', resource.id, resource.author_id, coalesce(resource_data.updated_at, resource_data.created_at)
+    from resource
+             inner join resource_data on resource_data.id = resource.live_id
+             inner join resource_admin_data on resource_admin_data.resource_id = resource.id
+    where resource.published_at is not null
+      and resource_data.privacy_level = 0
+      and not resource_admin_data.blocked
+    union all
+    select 'playlist', playlist.id, playlist.author_id, coalesce(playlist_data.updated_at, playlist_data.created_at)
+    from playlist
+             inner join playlist_data on playlist_data.id = playlist.live_id
+    where playlist.published_at is not null
+      and playlist_data.privacy_level = 0
+    union all
+    select 'course', course.id, course.author_id, coalesce(course_data.updated_at, course_data.created_at)
+    from course
+             inner join course_data on course_data.id = course.live_id
+    where course.published_at is not null
+      and course_data.privacy_level = 0
+)
+select kind, id, last_edited
+from asset
+where kind <> 'resource'
+union all
+select 'circle', circle.id, coalesce(circle.updated_at, circle.created_at)
+from circle
+union all
+select 'user', user_profile.user_id, coalesce(user_profile.updated_at, user_profile.created_at)
+from user_profile
+where user_profile.user_id in (select author_id from asset);
@@ -0,0 +1,15 @@
+-- Hidden reviews aren't listed and don't count towards the rating of the asset. A review is hidden
+-- once enough reports of it are pending, until a curator dismisses them, or when it's blocked.
+alter table asset_review
+    add column hidden bool not null default false
+;
+
+alter table asset_review_report
+    add column status          int2 not null default 0,
+    add column action          int2,
+    add column resolution_note text,
+    add column resolved_by     uuid references "user" (id) on delete set null,
+    add column resolved_at     timestamptz
+;
+
+create index asset_review_report_status_idx on asset_review_report (status, created_at);
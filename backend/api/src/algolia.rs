@@ -14,7 +14,7 @@ use tracing::{instrument, Instrument};
 
 use shared::{
     domain::{
-        asset::PrivacyLevel,
+        asset::{PrivacyLevel, SearchSortBy},
         category::CategoryId,
         circle::CircleId,
         course::CourseId,
@@ -41,6 +41,11 @@ const HAS_AUTHOR_TAG: &'static str = "hasAuthor";
 const HAS_RATING_TAG: &'static str = "isRated";
 const HAS_NO_RATING_TAG: &'static str = "isNotRated";
 
+/// Suffix of the replica of the jig and resource indices which ranks by `average_rating` descending.
+///
+/// The replicas are declared by the index migrations.
+const RATING_REPLICA_SUFFIX: &'static str = "_rating_desc";
+
 #[derive(Serialize)]
 struct BatchJig<'a> {
     name: &'a str,
@@ -62,12 +67,15 @@ struct BatchJig<'a> {
     other_keywords: &'a str,
     translated_keywords: &'a str,
     rating: Option<i16>,
+    average_rating: Option<f32>,
+    review_count: &'a i64,
     likes: &'a i64,
     plays: &'a i64,
     published_at: Option<DateTime<Utc>>,
     translated_name: &'a Vec<String>,
     translated_description: &'a Vec<String>,
     blocked: &'a bool,
+    circles: &'a [Uuid],
 }
 
 #[derive(Serialize)]
@@ -91,6 +99,8 @@ struct BatchResource<'a> {
     other_keywords: &'a str,
     translated_keywords: &'a str,
     rating: Option<i16>,
+    average_rating: Option<f32>,
+    review_count: &'a i64,
     likes: &'a i64,
     views: &'a i64,
     published_at: Option<DateTime<Utc>>,
@@ -140,6 +150,8 @@ struct BatchPlaylist<'a> {
     tags: Vec<&'static str>,
     other_keywords: &'a str,
     translated_keywords: &'a str,
+    average_rating: Option<f32>,
+    review_count: &'a i64,
     likes: &'a i64,
     plays: &'a i64,
     published_at: Option<DateTime<Utc>>,
@@ -253,6 +265,7 @@ pub struct Manager {
     pub public_user_index: String,
     pub user_index: String,
     pub course_index: String,
+    pub(crate) settings_client: migration::SettingsClient,
 }
 
 impl Manager {
@@ -308,7 +321,8 @@ impl Manager {
         };
 
         Ok(Some(Self {
-            inner: Inner::new(AppId::new(app_id), ApiKey(key))?,
+            inner: Inner::new(AppId::new(app_id.clone()), ApiKey(key.clone()))?,
+            settings_client: migration::SettingsClient::new(app_id, key),
             media_index,
             jig_index,
             resource_index,
@@ -418,10 +432,22 @@ impl Manager {
                     migration::media_index(&mut txn, &self.inner, &self.media_index).await?
                 }
                 migration::JIG_INDEX => {
-                    migration::jig_index(&mut txn, &self.inner, &self.jig_index).await?
+                    migration::jig_index(
+                        &mut txn,
+                        &self.inner,
+                        &self.settings_client,
+                        &self.jig_index,
+                    )
+                    .await?
                 }
                 migration::RESOURCE_INDEX => {
-                    migration::resource_index(&mut txn, &self.inner, &self.resource_index).await?
+                    migration::resource_index(
+                        &mut txn,
+                        &self.inner,
+                        &self.settings_client,
+                        &self.resource_index,
+                    )
+                    .await?
                 }
                 migration::PLAYLIST_INDEX => {
                     migration::playlist_index(&mut txn, &self.inner, &self.playlist_index).await?
@@ -598,6 +624,8 @@ select jig.id,
         from user_profile
         where user_profile.user_id = jig.author_id)                                                                 as "author_name",
         rating                                                                                                      as "rating",
+        (select avg(rating)::real from asset_review where not asset_review.hidden and asset_review.jig_id = jig.id)                             as "average_rating",
+        (select count(*) from asset_review where not asset_review.hidden and asset_review.jig_id = jig.id)                                      as "review_count!",
         liked_count                                                                                                 as "likes!",
         (
             select play_count
@@ -605,7 +633,10 @@ select jig.id,
             where jpc.jig_id = jig.id
         )                                                                                                           as "plays!",
         published_at                                                                                                as "published_at",
-        blocked                                                                                                     as "blocked!"
+        blocked                                                                                                     as "blocked!",
+        array((select circle_id
+               from circle_library_item
+               where jig_id = jig.id))                                                                              as "circles!"
 from jig
          inner join jig_data on live_id = jig_data.id
          inner join jig_admin_data "jad" on jad.jig_id = jig.id
@@ -663,12 +694,15 @@ limit 100 for no key update skip locked;
                 other_keywords: &row.other_keywords,
                 translated_keywords: &row.translated_keywords,
                 rating: row.rating,
+                average_rating: row.average_rating,
+                review_count: &row.review_count,
                 likes: &row.likes,
                 plays: &row.plays,
                 published_at: row.published_at,
                 translated_name: &translation_name,
                 translated_description: &translation_description,
-                blocked: &row.blocked
+                blocked: &row.blocked,
+                circles: &row.circles,
             })
             .expect("failed to serialize BatchJig to json")
             {
@@ -761,6 +795,8 @@ select resource.id,
         from user_profile
         where user_profile.user_id = resource.author_id)                                                            as "author_name",
         rating                                                                                                      as "rating",
+        (select avg(rating)::real from asset_review where not asset_review.hidden and asset_review.resource_id = resource.id)                   as "average_rating",
+        (select count(*) from asset_review where not asset_review.hidden and asset_review.resource_id = resource.id)                            as "review_count!",
         likes                                                                                                       as "likes!",
         views                                                                                                       as "views!",
         published_at                                                                                                as "published_at",
@@ -822,6 +858,8 @@ limit 100 for no key update skip locked;
                 other_keywords: &row.other_keywords,
                 translated_keywords: &row.translated_keywords,
                 rating: row.rating,
+                average_rating: row.average_rating,
+                review_count: &row.review_count,
                 likes: &row.likes,
                 views: &row.views,
                 published_at: row.published_at,
@@ -1065,6 +1103,8 @@ select playlist.id,
        (select given_name || ' '::text || family_name
         from user_profile
         where user_profile.user_id = playlist.author_id)                                                       as "author_name",
+        (select avg(rating)::real from asset_review where not asset_review.hidden and asset_review.playlist_id = playlist.id)                   as "average_rating",
+        (select count(*) from asset_review where not asset_review.hidden and asset_review.playlist_id = playlist.id)                            as "review_count!",
         likes                                                                                                       as "likes!",
         plays                                                                                                       as "plays!",
         published_at                                                                                                as "published_at"
@@ -1117,6 +1157,8 @@ limit 100 for no key update skip locked;
                 tags,
                 other_keywords: &row.other_keywords,
                 translated_keywords: &row.translated_keywords,
+                average_rating: row.average_rating,
+                review_count: &row.review_count,
                 likes: &row.likes,
                 plays: &row.plays,
                 published_at: row.published_at,
@@ -1988,6 +2030,8 @@ impl Client {
         page_limit: u32,
         blocked: Option<bool>,
         is_rated: Option<bool>,
+        circles: &[CircleId],
+        sort_by: Option<SearchSortBy>,
     ) -> anyhow::Result<Option<(Vec<Uuid>, u32, u64)>> {
         let mut and_filters = algolia::filter::AndFilter { filters: vec![] };
 
@@ -2077,11 +2121,17 @@ impl Client {
         filters_for_ids_or(&mut and_filters.filters, "affiliations", affiliations);
         filters_for_ids_or(&mut and_filters.filters, "resource_types", resource_types);
         filters_for_ids_or(&mut and_filters.filters, "categories", categories);
+        filters_for_ids_or(&mut and_filters.filters, "circles", circles);
+
+        let index = match sort_by {
+            Some(SearchSortBy::Rating) => format!("{}{}", self.jig_index, RATING_REPLICA_SUFFIX),
+            Some(SearchSortBy::Relevance) | None => self.jig_index.clone(),
+        };
 
         let results: SearchResponse = self
             .inner
             .search(
-                &self.jig_index,
+                &index,
                 SearchQuery::<'_, String, AndFilter> {
                     query: Some(query),
                     page,
@@ -2125,6 +2175,7 @@ impl Client {
         page_limit: u32,
         blocked: Option<bool>,
         is_rated: Option<bool>,
+        sort_by: Option<SearchSortBy>,
     ) -> anyhow::Result<Option<(Vec<Uuid>, u32, u64)>> {
         let mut and_filters = algolia::filter::AndFilter { filters: vec![] };
 
@@ -2215,10 +2266,17 @@ impl Client {
         filters_for_ids_or(&mut and_filters.filters, "resource_types", resource_types);
         filters_for_ids_or(&mut and_filters.filters, "categories", categories);
 
+        let index = match sort_by {
+            Some(SearchSortBy::Rating) => {
+                format!("{}{}", self.resource_index, RATING_REPLICA_SUFFIX)
+            }
+            Some(SearchSortBy::Relevance) | None => self.resource_index.clone(),
+        };
+
         let results: SearchResponse = self
             .inner
             .search(
-                &self.resource_index,
+                &index,
                 SearchQuery::<'_, String, AndFilter> {
                     query: Some(query),
                     page,
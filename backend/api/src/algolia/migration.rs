@@ -3,6 +3,7 @@ use algolia::{
     request::SetSettings,
 };
 
+use serde_json::json;
 use sqlx::PgConnection;
 
 use hashfn::hashfn;
@@ -16,6 +17,79 @@ pub const USER_INDEX: &str = "user_index";
 pub const RESOURCE_INDEX: &str = "resource_index";
 pub const COURSE_INDEX: &str = "course_index";
 
+/// Algolia's settings REST API, for the settings which `SetSettings` doesn't cover (replicas and ranking).
+#[derive(Clone)]
+pub(crate) struct SettingsClient {
+    app_id: String,
+    key: String,
+    client: reqwest::Client,
+}
+
+impl SettingsClient {
+    pub(crate) fn new(app_id: String, key: String) -> Self {
+        Self {
+            app_id,
+            key,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    async fn set_settings(&self, index: &str, settings: serde_json::Value) -> anyhow::Result<()> {
+        self.client
+            .put(&format!(
+                "https://{}.algolia.net/1/indexes/{}/settings",
+                self.app_id, index
+            ))
+            .header("X-Algolia-Application-Id", &self.app_id)
+            .header("X-Algolia-API-Key", &self.key)
+            .json(&settings)
+            .send()
+            .await?
+            .error_for_status()?;
+
+        Ok(())
+    }
+}
+
+/// Declares the replica of the index which ranks by `average_rating` descending, and gives it the index's settings.
+///
+/// Settings aren't forwarded to replicas, so this has to run whenever the index's settings change.
+async fn rating_replica(
+    client: &super::Inner,
+    settings_client: &SettingsClient,
+    index: &str,
+    settings: &SetSettings,
+) -> anyhow::Result<()> {
+    let replica = format!("{}{}", index, super::RATING_REPLICA_SUFFIX);
+
+    settings_client
+        .set_settings(index, json!({ "replicas": [replica] }))
+        .await?;
+
+    client.set_settings(&replica, settings).await?;
+
+    settings_client
+        .set_settings(
+            &replica,
+            json!({
+                "ranking": [
+                    "desc(average_rating)",
+                    "typo",
+                    "geo",
+                    "words",
+                    "filters",
+                    "proximity",
+                    "attribute",
+                    "exact",
+                    "custom",
+                ]
+            }),
+        )
+        .await?;
+
+    Ok(())
+}
+
 #[hashfn(MEDIA_HASH)]
 pub(crate) async fn media_index(
     txn: &mut PgConnection,
@@ -53,6 +127,7 @@ pub(crate) async fn media_index(
 pub(crate) async fn jig_index(
     txn: &mut PgConnection,
     client: &super::Inner,
+    settings_client: &SettingsClient,
     jig_index: &str,
 ) -> anyhow::Result<()> {
     let settings = SetSettings {
@@ -82,11 +157,14 @@ pub(crate) async fn jig_index(
             FacetAttribute::filter_only(Attribute("resource_types".to_owned())),
             FacetAttribute::filter_only(Attribute("language".to_owned())),
             FacetAttribute::filter_only(Attribute("rating".to_owned())),
+            FacetAttribute::filter_only(Attribute("circles".to_owned())),
         ]),
     };
 
     client.set_settings(jig_index, &settings).await?;
 
+    rating_replica(client, settings_client, jig_index, &settings).await?;
+
     sqlx::query!(r#"update algolia_index_settings set updated_at = now(), index_hash = $1 where index_name = $2"#, JIG_HASH, JIG_INDEX).execute(txn).await?;
 
     Ok(())
@@ -242,6 +320,7 @@ pub(crate) async fn public_user_index(
 pub(crate) async fn resource_index(
     txn: &mut PgConnection,
     client: &super::Inner,
+    settings_client: &SettingsClient,
     resource_index: &str,
 ) -> anyhow::Result<()> {
     let settings = SetSettings {
@@ -276,6 +355,8 @@ pub(crate) async fn resource_index(
 
     client.set_settings(resource_index, &settings).await?;
 
+    rating_replica(client, settings_client, resource_index, &settings).await?;
+
     sqlx::query!(r#"update algolia_index_settings set updated_at = now(), index_hash = $1 where index_name = $2"#, RESOURCE_HASH, RESOURCE_INDEX).execute(txn).await?;
 
     Ok(())
@@ -0,0 +1,194 @@
+//! Types and decoding for authenticating users through Sign in with Apple
+
+use anyhow::{bail, Context};
+use chrono::Utc;
+use http::StatusCode;
+use ji_core::settings::AppleOAuth;
+use jsonwebtoken as jwt;
+use serde::{Deserialize, Serialize};
+use shared::{api::endpoints::PathPart, config::RemoteTarget, domain::session::OAuthUrlKind};
+
+use crate::{error, jwk};
+
+const ISSUER: &str = "https://appleid.apple.com";
+
+const TOKEN_URL: &str = "https://appleid.apple.com/auth/token";
+
+const KEYS_URL: &str = "https://appleid.apple.com/auth/keys";
+
+pub const SCOPE: &str = "name email";
+
+/// Apple posts the code to the API, as it uses `form_post` when asking for the user's email.
+pub const CALLBACK_PATH: &str = "/v1/session/oauth/apple/callback/{kind}";
+
+const CLIENT_SECRET_VALID_SECS: i64 = 5 * 60;
+
+#[derive(Deserialize, Debug)]
+pub struct TokenResponse {
+    pub id_token: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct TokenErrorResponse {
+    error: String,
+}
+
+/// The form Apple posts to [`CALLBACK_PATH`].
+#[derive(Deserialize, Debug)]
+pub struct CallbackForm {
+    #[serde(default)]
+    pub code: Option<String>,
+
+    /// Set if the user cancelled, or something else went wrong.
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct IdentityClaims {
+    /// should be [`ISSUER`]
+    #[serde(rename = "iss")]
+    pub issuer: String,
+
+    /// The Apple id of the user
+    #[serde(rename = "sub")]
+    pub apple_id: String,
+
+    /// The user's email, which may be a private relay address
+    #[serde(default)]
+    pub email: Option<String>,
+
+    /// If the user's email is verified.
+    #[serde(default, deserialize_with = "jwk::bool_or_string")]
+    pub email_verified: bool,
+}
+
+impl IdentityClaims {
+    fn check_issuer(&self) -> anyhow::Result<()> {
+        if self.issuer != ISSUER {
+            bail!("unexpected issuer: {}", self.issuer);
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Serialize)]
+struct ClientSecretClaims<'a> {
+    iss: &'a str,
+    iat: i64,
+    exp: i64,
+    aud: &'a str,
+    sub: &'a str,
+}
+
+pub fn callback_url(remote_target: RemoteTarget, url_kind: OAuthUrlKind) -> String {
+    format!(
+        "{}{}",
+        remote_target.api_url(),
+        CALLBACK_PATH.replace("{kind}", &url_kind.get_path_string())
+    )
+}
+
+/// Apple doesn't use static client secrets, instead they're signed with the private key.
+fn client_secret(config: &AppleOAuth) -> anyhow::Result<String> {
+    let now = Utc::now().timestamp();
+
+    let mut header = jwt::Header::new(jwt::Algorithm::ES256);
+    header.kid = Some(config.key_id.clone());
+
+    let claims = ClientSecretClaims {
+        iss: &config.team_id,
+        iat: now,
+        exp: now + CLIENT_SECRET_VALID_SECS,
+        aud: ISSUER,
+        sub: &config.client,
+    };
+
+    let key = jwt::EncodingKey::from_ec_pem(config.private_key.as_bytes())
+        .context("invalid Apple private key")?;
+
+    Ok(jwt::encode(&header, &claims, &key)?)
+}
+
+pub async fn convert_oauth_code(
+    config: &AppleOAuth,
+    code: &str,
+    redirect_url: &str,
+) -> Result<TokenResponse, error::OAuth> {
+    let client_secret = client_secret(config)?;
+
+    let resp: reqwest::Response = reqwest::Client::new()
+        .post(TOKEN_URL)
+        .form(&[
+            ("code", code),
+            ("grant_type", "authorization_code"),
+            ("client_id", &config.client),
+            ("client_secret", &client_secret),
+            ("redirect_uri", redirect_url),
+        ])
+        .send()
+        .await?;
+
+    match resp.status() {
+        StatusCode::OK => Ok(resp.json::<TokenResponse>().await?),
+        _ => {
+            let err = resp.json::<TokenErrorResponse>().await.with_context(|| {
+                anyhow::anyhow!("Failed to decode {}", stringify!(TokenErrorResponse))
+            })?;
+
+            match err.error.as_str() {
+                "invalid_grant" => Err(error::OAuth::InvalidCode),
+                _ => Err(anyhow::anyhow!(err.error).into()),
+            }
+        }
+    }
+}
+
+pub async fn verify_id_token(
+    config: &AppleOAuth,
+    id_token: &str,
+) -> anyhow::Result<IdentityClaims> {
+    let claims: IdentityClaims = jwk::verify_remote(KEYS_URL, id_token, &config.client).await?;
+
+    claims.check_issuer()?;
+
+    Ok(claims)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{IdentityClaims, ISSUER};
+    use crate::jwk::tests::{decode, sign};
+
+    #[test]
+    fn verify_claims() {
+        // Apple sends `email_verified` as a string
+        let claims: IdentityClaims = decode(&sign(json!({
+            "iss": ISSUER,
+            "sub": "001234.abcdef.1234",
+            "email": "abcdef@privaterelay.appleid.com",
+            "email_verified": "true",
+        })))
+        .expect("token should be valid");
+
+        assert!(claims.check_issuer().is_ok());
+        assert!(claims.email_verified);
+        assert_eq!(claims.apple_id, "001234.abcdef.1234");
+    }
+
+    #[test]
+    fn verify_claims_other_issuer() {
+        let claims: IdentityClaims = decode(&sign(json!({
+            "iss": "https://accounts.google.com",
+            "sub": "001234.abcdef.1234",
+            "email_verified": false,
+        })))
+        .expect("token should be valid");
+
+        assert!(claims.check_issuer().is_err());
+        assert!(!claims.email_verified);
+    }
+}
@@ -1,20 +1,28 @@
 pub(crate) mod account;
 pub(crate) mod animation;
 pub(crate) mod audio;
-pub(crate) mod billing;
+pub mod billing;
 pub(crate) mod category;
 pub(crate) mod circle;
 pub(crate) mod course;
+pub(crate) mod curation;
 pub(crate) mod image;
-pub(crate) mod jig;
+pub mod jig;
 pub(crate) mod locale;
 pub(crate) mod media;
 pub(crate) mod meta;
+pub(crate) mod notification;
 pub(crate) mod pdf;
 pub(crate) mod playlist;
-pub(crate) mod resource;
+pub(crate) mod rate_limit;
+pub mod resource;
+pub mod review;
+pub(crate) mod roster;
 pub(crate) mod session;
+pub(crate) mod sitemap;
+pub(crate) mod translation;
 pub(crate) mod user;
+pub mod webhook;
 
 use anyhow::Context;
 use ji_core::config::DB_POOL_CONNECTIONS;
@@ -2,7 +2,8 @@ use crate::db;
 use shared::domain::admin::SearchSchoolNamesParams;
 use shared::domain::billing::{
     Account, AccountId, AccountType, AccountUser, CustomerId, PaymentMethod, School, SchoolId,
-    SchoolName, SchoolNameId, SubscriptionStatus, SubscriptionTier, UserAccountSummary,
+    SchoolName, SchoolNameId, SchoolSsoConfig, SchoolSsoDomain, SubscriptionStatus,
+    SubscriptionTier, UserAccountSummary,
 };
 use shared::domain::image::ImageId;
 use shared::domain::user::UserId;
@@ -664,3 +665,267 @@ pub async fn user_account_membership(
         }
     }))
 }
+
+/// A school's single sign-on, with what's needed to sign teachers in with it.
+#[derive(Debug)]
+pub struct SchoolSso {
+    pub school_id: SchoolId,
+    pub account_id: AccountId,
+    pub issuer: String,
+    pub client_id: String,
+    pub client_secret: String,
+    /// Only the verified email domains.
+    pub email_domains: Vec<String>,
+}
+
+/// Prefix of the DNS TXT record which proves that a school owns an email domain.
+const SSO_VERIFICATION_RECORD_PREFIX: &str = "jigzi-sso-verification=";
+
+#[instrument(skip(pool))]
+pub async fn get_school_sso(
+    pool: &PgPool,
+    school_id: &SchoolId,
+) -> sqlx::Result<Option<SchoolSsoConfig>> {
+    let sso = sqlx::query!(
+        // language=SQL
+        r#"
+select issuer,
+       client_id,
+       verification_token,
+       enabled,
+       created_at,
+       updated_at
+from school_sso
+where school_id = $1
+"#,
+        school_id as &SchoolId
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    let sso = match sso {
+        Some(sso) => sso,
+        None => return Ok(None),
+    };
+
+    let email_domains = sqlx::query_as!(
+        SchoolSsoDomain,
+        // language=SQL
+        r#"
+select domain::text as "domain!",
+       verified_at is not null as "verified!"
+from school_sso_domain
+where school_id = $1
+order by domain
+"#,
+        school_id as &SchoolId
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(Some(SchoolSsoConfig {
+        issuer: sso.issuer,
+        client_id: sso.client_id,
+        email_domains,
+        verification_record: format!(
+            "{}{}",
+            SSO_VERIFICATION_RECORD_PREFIX, sso.verification_token
+        ),
+        enabled: sso.enabled,
+        created_at: sso.created_at,
+        updated_at: sso.updated_at,
+    }))
+}
+
+/// Gets the school's single sign-on, if it's enabled.
+#[instrument(skip(pool))]
+pub async fn get_enabled_school_sso(
+    pool: &PgPool,
+    school_id: &SchoolId,
+) -> sqlx::Result<Option<SchoolSso>> {
+    sqlx::query_as!(
+        SchoolSso,
+        // language=SQL
+        r#"
+select
+    school_sso.school_id as "school_id!: SchoolId",
+    account_id as "account_id!: AccountId",
+    issuer,
+    client_id,
+    client_secret,
+    array(
+        select domain::text
+        from school_sso_domain
+        where school_id = school_sso.school_id
+          and verified_at is not null
+    ) as "email_domains!"
+from school_sso
+inner join school using (school_id)
+where school_id = $1 and enabled
+"#,
+        school_id as &SchoolId
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+/// Finds the school whose enabled single sign-on signs in emails of the (verified) domain.
+#[instrument(skip(pool))]
+pub async fn find_school_sso_by_domain(
+    pool: &PgPool,
+    domain: &str,
+) -> sqlx::Result<Option<SchoolSso>> {
+    let school_id = sqlx::query_scalar!(
+        // language=SQL
+        r#"
+select school_id as "school_id!: SchoolId"
+from school_sso_domain
+where domain = $1::text::citext
+  and verified_at is not null
+"#,
+        domain,
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    match school_id {
+        Some(school_id) => get_enabled_school_sso(pool, &school_id).await,
+        None => Ok(None),
+    }
+}
+
+/// Whether another school already verified one of the domains.
+#[instrument(skip(pool))]
+pub async fn sso_domains_verified_elsewhere(
+    pool: &PgPool,
+    school_id: &SchoolId,
+    email_domains: &[String],
+) -> sqlx::Result<bool> {
+    sqlx::query_scalar!(
+        // language=SQL
+        r#"
+select exists(
+    select 1
+    from school_sso_domain
+    where domain = any($2::text[]::citext[])
+      and verified_at is not null
+      and school_id <> $1
+) as "exists!"
+"#,
+        school_id as &SchoolId,
+        email_domains,
+    )
+    .fetch_one(pool)
+    .await
+}
+
+/// Sets up or updates the school's single sign-on.
+///
+/// `client_secret` is required when setting it up, and keeps the current secret if `None` when updating.
+/// `verification_token` is only used when setting it up.
+///
+/// Domains which the school already had stay verified, new ones have to be verified with [`verify_school_sso_domains`].
+#[instrument(skip(pool, client_secret, verification_token))]
+pub async fn upsert_school_sso(
+    pool: &PgPool,
+    school_id: &SchoolId,
+    issuer: &str,
+    client_id: &str,
+    client_secret: Option<&str>,
+    verification_token: &str,
+    email_domains: &[String],
+    enabled: bool,
+) -> sqlx::Result<()> {
+    let mut txn = pool.begin().await?;
+
+    sqlx::query!(
+        // language=SQL
+        r#"
+insert into school_sso (school_id, issuer, client_id, client_secret, verification_token, enabled)
+values ($1, $2, $3, $4, $5, $6)
+on conflict (school_id) do update
+set issuer        = $2,
+    client_id     = $3,
+    client_secret = coalesce($4, school_sso.client_secret),
+    enabled       = $6,
+    updated_at    = now()
+"#,
+        school_id as &SchoolId,
+        issuer,
+        client_id,
+        client_secret,
+        verification_token,
+        enabled,
+    )
+    .execute(&mut txn)
+    .await?;
+
+    sqlx::query!(
+        // language=SQL
+        r#"
+delete from school_sso_domain
+where school_id = $1
+  and domain <> all($2::text[]::citext[])
+"#,
+        school_id as &SchoolId,
+        email_domains,
+    )
+    .execute(&mut txn)
+    .await?;
+
+    sqlx::query!(
+        // language=SQL
+        r#"
+insert into school_sso_domain (school_id, domain)
+select $1, domain from unnest($2::text[]) as domain
+on conflict (school_id, domain) do nothing
+"#,
+        school_id as &SchoolId,
+        email_domains,
+    )
+    .execute(&mut txn)
+    .await?;
+
+    txn.commit().await?;
+
+    Ok(())
+}
+
+/// Marks the school's single sign-on domains as verified.
+#[instrument(skip(pool))]
+pub async fn verify_school_sso_domains(
+    pool: &PgPool,
+    school_id: &SchoolId,
+    email_domains: &[String],
+) -> sqlx::Result<()> {
+    sqlx::query!(
+        // language=SQL
+        r#"
+update school_sso_domain
+set verified_at = now()
+where school_id = $1
+  and domain = any($2::text[]::citext[])
+  and verified_at is null
+"#,
+        school_id as &SchoolId,
+        email_domains,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Returns `false` if the school didn't have single sign-on.
+#[instrument(skip(pool))]
+pub async fn delete_school_sso(pool: &PgPool, school_id: &SchoolId) -> sqlx::Result<bool> {
+    let res = sqlx::query!(
+        // language=SQL
+        r#"delete from school_sso where school_id = $1"#,
+        school_id as &SchoolId,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(res.rows_affected() > 0)
+}
@@ -1,10 +1,13 @@
 use chrono::{DateTime, Utc};
 use shared::domain::billing::{
-    AccountId, AccountLimit, AmountInCents, BillingInterval, CreateSubscriptionRecord,
-    CreateUpdateSubscriptionPlanRequest, PlanId, StripeInvoiceId, StripePriceId, StripeProductId,
-    StripeSubscriptionId, Subscription, SubscriptionId, SubscriptionPlan, SubscriptionStatus,
-    SubscriptionTier, SubscriptionType, TrialPeriod, UpdateSubscriptionRecord,
+    AccountId, AccountLimit, AmountInCents, BillingInterval, BillingNotificationKind,
+    BillingNotificationPreferences, CreatePromotionCodeRequest, CreateSubscriptionRecord,
+    CreateUpdateSubscriptionPlanRequest, Discount, DiscountDuration, PlanId, PromotionCode,
+    PromotionCodeId, StripeInvoiceId, StripePriceId, StripeProductId, StripeSubscriptionId,
+    Subscription, SubscriptionId, SubscriptionPlan, SubscriptionStatus, SubscriptionTier,
+    SubscriptionType, TrialPeriod, UpdateSubscriptionRecord,
 };
+use shared::domain::user::UserId;
 use sqlx::PgPool;
 use tracing::{instrument, Instrument};
 
@@ -127,10 +130,11 @@ insert into subscription
         current_period_end,
         account_id,
         latest_invoice_id,
-        amount_due
+        amount_due,
+        trial_end
     )
 values
-    ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+    ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
 returning subscription_id as "id!: SubscriptionId"
 "#,
         subscription.stripe_subscription_id as StripeSubscriptionId,
@@ -144,6 +148,7 @@ returning subscription_id as "id!: SubscriptionId"
             .latest_invoice_id
             .map(|invoice_id| invoice_id.inner()),
         subscription.amount_due_in_cents.map(|due| due.inner()),
+        subscription.trial_end,
     )
     .fetch_one(pool)
     .await
@@ -164,7 +169,8 @@ set
     status = coalesce($3, status),
     current_period_end = coalesce($4, current_period_end),
     updated_at = now(),
-    latest_invoice_id = $5
+    latest_invoice_id = $5,
+    trial_end = coalesce($6, trial_end)
 where stripe_subscription_id = $1
 "#,
         subscription.stripe_subscription_id as StripeSubscriptionId,
@@ -174,6 +180,7 @@ where stripe_subscription_id = $1
         subscription
             .latest_invoice_id
             .map(|invoice_id| invoice_id.inner()),
+        subscription.trial_end,
     )
     .execute(pool)
     .await?;
@@ -181,6 +188,25 @@ where stripe_subscription_id = $1
     Ok(())
 }
 
+#[instrument(skip(pool))]
+pub async fn get_subscription_status(
+    pool: &PgPool,
+    subscription_id: &StripeSubscriptionId,
+) -> sqlx::Result<Option<SubscriptionStatus>> {
+    sqlx::query!(
+        //language=SQL
+        r#"
+select status as "status!: SubscriptionStatus"
+from subscription
+where stripe_subscription_id = $1
+"#,
+        subscription_id as &StripeSubscriptionId,
+    )
+    .fetch_optional(pool)
+    .await
+    .map(|row| row.map(|row| row.status))
+}
+
 #[instrument(skip(pool))]
 pub async fn set_subscription_amount_due(
     pool: &PgPool,
@@ -220,6 +246,7 @@ select
     auto_renew,
     status as "status!: SubscriptionStatus",
     current_period_end as "current_period_end!: DateTime<Utc>",
+    trial_end as "trial_end?: DateTime<Utc>",
     account_id as "account_id!: AccountId",
     latest_invoice_id as "latest_invoice_id?: StripeInvoiceId",
     amount_due as "amount_due_in_cents?: AmountInCents",
@@ -241,6 +268,7 @@ where subscription_id = $1
         auto_renew: row.auto_renew,
         status: row.status,
         current_period_end: row.current_period_end,
+        trial_end: row.trial_end,
         account_id: row.account_id,
         latest_invoice_id: row.latest_invoice_id,
         amount_due_in_cents: row.amount_due_in_cents,
@@ -267,6 +295,7 @@ select
     auto_renew,
     status as "status!: SubscriptionStatus",
     current_period_end as "current_period_end!: DateTime<Utc>",
+    trial_end as "trial_end?: DateTime<Utc>",
     account_id as "account_id!: AccountId",
     latest_invoice_id as "latest_invoice_id?: StripeInvoiceId",
     amount_due as "amount_due_in_cents?: AmountInCents",
@@ -290,6 +319,7 @@ limit 1
         auto_renew: row.auto_renew,
         status: row.status,
         current_period_end: row.current_period_end,
+        trial_end: row.trial_end,
         account_id: row.account_id,
         latest_invoice_id: row.latest_invoice_id,
         amount_due_in_cents: row.amount_due_in_cents,
@@ -313,3 +343,481 @@ pub async fn get_stripe_subscription_id_with_invoice_id(
     .fetch_optional(pool)
     .await
 }
+
+/// An account administrator who should be notified about a subscription.
+#[derive(Debug)]
+pub struct BillingNotificationRecipient {
+    pub subscription_id: SubscriptionId,
+    pub user_id: UserId,
+    pub email: String,
+    pub given_name: String,
+    /// Used to de-duplicate notifications, i.e. the invoice ID for failed payments.
+    pub reference: String,
+    pub preferences: BillingNotificationPreferences,
+}
+
+/// Fetch the administrators of accounts whose latest subscription requires a `kind` notification
+/// which hasn't been sent yet, along with their notification preferences.
+///
+/// * [`TrialEnding`](BillingNotificationKind::TrialEnding) - the trial ends within `trial_ending_days`;
+/// * [`PaymentFailed`](BillingNotificationKind::PaymentFailed) - the subscription is active and has an amount due;
+/// * [`SubscriptionExpired`](BillingNotificationKind::SubscriptionExpired) - the current period has
+///   ended and the subscription isn't renewing.
+#[instrument(skip(pool))]
+pub async fn get_pending_billing_notifications(
+    pool: &PgPool,
+    kind: BillingNotificationKind,
+    trial_ending_days: i32,
+) -> sqlx::Result<Vec<BillingNotificationRecipient>> {
+    let rows = sqlx::query!(
+        // language=SQL
+        r#"
+with latest_subscription as (
+    select distinct on (account_id) *
+    from subscription
+    order by account_id, created_at desc
+), candidate as (
+    select
+        subscription_id,
+        account_id,
+        case
+            when $1 = 1 then coalesce(latest_invoice_id, '')
+            else ''
+        end as reference
+    from latest_subscription
+    where
+        ($1 = 0 and status = 1 and trial_end between now() and now() + make_interval(days => $2))
+        or ($1 = 1 and status = 1 and amount_due > 0)
+        or ($1 = 2 and current_period_end < now() and not auto_renew)
+)
+select
+    candidate.subscription_id as "subscription_id!: SubscriptionId",
+    user_account.user_id as "user_id!: UserId",
+    user_email.email::text as "email!",
+    user_profile.given_name as "given_name!",
+    candidate.reference as "reference!",
+    coalesce(billing_notification_preference.trial_ending, true) as "trial_ending!",
+    coalesce(billing_notification_preference.payment_failed, true) as "payment_failed!",
+    coalesce(billing_notification_preference.subscription_expired, true) as "subscription_expired!"
+from candidate
+inner join user_account on user_account.account_id = candidate.account_id and user_account.admin
+inner join user_email using (user_id)
+inner join user_profile using (user_id)
+left join billing_notification_preference using (user_id)
+where not exists (
+    select 1 from billing_notification
+    where billing_notification.subscription_id = candidate.subscription_id
+      and billing_notification.user_id = user_account.user_id
+      and billing_notification.kind = $1
+      and billing_notification.reference = candidate.reference
+)
+"#,
+        kind as i16,
+        trial_ending_days,
+    )
+    .fetch_all(pool)
+    .instrument(tracing::info_span!("get pending billing notifications"))
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| BillingNotificationRecipient {
+            subscription_id: row.subscription_id,
+            user_id: row.user_id,
+            email: row.email,
+            given_name: row.given_name,
+            reference: row.reference,
+            preferences: BillingNotificationPreferences {
+                trial_ending: row.trial_ending,
+                payment_failed: row.payment_failed,
+                subscription_expired: row.subscription_expired,
+            },
+        })
+        .collect())
+}
+
+/// Record that a billing notification was sent, or skipped because the user opted out, so that it
+/// isn't sent again.
+#[instrument(skip(pool))]
+pub async fn mark_billing_notification_sent(
+    pool: &PgPool,
+    recipient: &BillingNotificationRecipient,
+    kind: BillingNotificationKind,
+) -> sqlx::Result<()> {
+    sqlx::query!(
+        // language=SQL
+        r#"
+insert into billing_notification (subscription_id, user_id, kind, reference)
+values ($1, $2, $3, $4)
+on conflict do nothing
+"#,
+        recipient.subscription_id as SubscriptionId,
+        recipient.user_id as UserId,
+        kind as i16,
+        recipient.reference,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+#[instrument(skip(pool))]
+pub async fn get_billing_notification_preferences(
+    pool: &PgPool,
+    user_id: UserId,
+) -> sqlx::Result<BillingNotificationPreferences> {
+    let preferences = sqlx::query_as!(
+        BillingNotificationPreferences,
+        // language=SQL
+        r#"
+select
+    trial_ending,
+    payment_failed,
+    subscription_expired
+from billing_notification_preference
+where user_id = $1
+"#,
+        user_id as UserId,
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(preferences.unwrap_or_default())
+}
+
+#[instrument(skip(pool))]
+pub async fn upsert_billing_notification_preferences(
+    pool: &PgPool,
+    user_id: UserId,
+    preferences: BillingNotificationPreferences,
+) -> sqlx::Result<()> {
+    sqlx::query!(
+        // language=SQL
+        r#"
+insert into billing_notification_preference
+    (user_id, trial_ending, payment_failed, subscription_expired)
+values
+    ($1, $2, $3, $4)
+on conflict (user_id) do update
+set
+    trial_ending = $2,
+    payment_failed = $3,
+    subscription_expired = $4,
+    updated_at = now()
+"#,
+        user_id as UserId,
+        preferences.trial_ending,
+        preferences.payment_failed,
+        preferences.subscription_expired,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Stripe coupon IDs are stored alongside the promotion code but never exposed in the API.
+pub struct PromotionCodeRecord {
+    pub promotion_code: PromotionCode,
+    pub stripe_coupon_id: String,
+}
+
+struct PromotionCodeRow {
+    promotion_code_id: PromotionCodeId,
+    code: String,
+    stripe_coupon_id: String,
+    percent_off: Option<i16>,
+    amount_off: Option<i64>,
+    duration: i16,
+    duration_in_months: Option<i32>,
+    max_redemptions: Option<i32>,
+    redemption_count: i64,
+    plan_ids: Vec<uuid::Uuid>,
+    expires_at: Option<DateTime<Utc>>,
+    active: bool,
+    created_at: DateTime<Utc>,
+    updated_at: Option<DateTime<Utc>>,
+}
+
+impl From<PromotionCodeRow> for PromotionCodeRecord {
+    fn from(row: PromotionCodeRow) -> Self {
+        let discount = match (row.percent_off, row.amount_off) {
+            (Some(percent_off), _) => Discount::PercentOff(percent_off as u8),
+            (None, amount_off) => Discount::AmountOff(amount_off.unwrap_or_default()),
+        };
+
+        let duration = match (row.duration, row.duration_in_months) {
+            (1, Some(months)) => DiscountDuration::Repeating(months as u32),
+            (2, _) => DiscountDuration::Forever,
+            _ => DiscountDuration::Once,
+        };
+
+        Self {
+            promotion_code: PromotionCode {
+                id: row.promotion_code_id,
+                code: row.code,
+                discount,
+                duration,
+                max_redemptions: row.max_redemptions.map(|max| max as u32),
+                redemption_count: row.redemption_count as u32,
+                plan_ids: row.plan_ids.into_iter().map(PlanId).collect(),
+                expires_at: row.expires_at,
+                active: row.active,
+                created_at: row.created_at,
+                updated_at: row.updated_at,
+            },
+            stripe_coupon_id: row.stripe_coupon_id,
+        }
+    }
+}
+
+#[instrument(skip(pool))]
+pub async fn create_promotion_code(
+    pool: &PgPool,
+    req: &CreatePromotionCodeRequest,
+    stripe_coupon_id: &str,
+) -> sqlx::Result<PromotionCodeId> {
+    let (percent_off, amount_off) = match req.discount {
+        Discount::PercentOff(percent_off) => (Some(percent_off as i16), None),
+        Discount::AmountOff(amount_off) => (None, Some(amount_off)),
+    };
+
+    let (duration, duration_in_months): (i16, Option<i32>) = match req.duration {
+        DiscountDuration::Once => (0, None),
+        DiscountDuration::Repeating(months) => (1, Some(months as i32)),
+        DiscountDuration::Forever => (2, None),
+    };
+
+    let plan_ids: Vec<uuid::Uuid> = req.plan_ids.iter().map(|plan_id| plan_id.0).collect();
+
+    sqlx::query!(
+        // language=SQL
+        r#"
+insert into promotion_code
+    (code, stripe_coupon_id, percent_off, amount_off, duration, duration_in_months, max_redemptions, plan_ids, expires_at)
+values
+    ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+returning promotion_code_id as "id!: PromotionCodeId"
+"#,
+        &req.code,
+        stripe_coupon_id,
+        percent_off,
+        amount_off,
+        duration,
+        duration_in_months,
+        req.max_redemptions.map(|max| max as i32),
+        &plan_ids,
+        req.expires_at,
+    )
+    .fetch_one(pool)
+    .await
+    .map(|res| res.id)
+}
+
+#[instrument(skip(pool))]
+pub async fn get_promotion_codes(pool: &PgPool) -> sqlx::Result<Vec<PromotionCode>> {
+    let rows = sqlx::query_as!(
+        PromotionCodeRow,
+        // language=SQL
+        r#"
+select
+    promotion_code_id as "promotion_code_id!: PromotionCodeId",
+    code::text as "code!",
+    stripe_coupon_id,
+    percent_off,
+    amount_off,
+    duration,
+    duration_in_months,
+    max_redemptions,
+    (select count(*) from promotion_code_redemption where promotion_code_redemption.promotion_code_id = promotion_code.promotion_code_id) as "redemption_count!",
+    plan_ids,
+    expires_at,
+    active,
+    created_at,
+    updated_at
+from promotion_code
+order by created_at desc
+"#
+    )
+    .fetch_all(pool)
+    .instrument(tracing::info_span!("get promotion codes"))
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| PromotionCodeRecord::from(row).promotion_code)
+        .collect())
+}
+
+#[instrument(skip(pool))]
+pub async fn get_promotion_code_by_code(
+    pool: &PgPool,
+    code: &str,
+) -> sqlx::Result<Option<PromotionCodeRecord>> {
+    let row = sqlx::query_as!(
+        PromotionCodeRow,
+        // language=SQL
+        r#"
+select
+    promotion_code_id as "promotion_code_id!: PromotionCodeId",
+    code::text as "code!",
+    stripe_coupon_id,
+    percent_off,
+    amount_off,
+    duration,
+    duration_in_months,
+    max_redemptions,
+    (select count(*) from promotion_code_redemption where promotion_code_redemption.promotion_code_id = promotion_code.promotion_code_id) as "redemption_count!",
+    plan_ids,
+    expires_at,
+    active,
+    created_at,
+    updated_at
+from promotion_code
+where code = $1::text::citext
+"#,
+        code,
+    )
+    .fetch_optional(pool)
+    .instrument(tracing::info_span!("get promotion code"))
+    .await?;
+
+    Ok(row.map(PromotionCodeRecord::from))
+}
+
+/// Returns `false` if the promotion code doesn't exist.
+#[instrument(skip(pool))]
+pub async fn update_promotion_code(
+    pool: &PgPool,
+    id: PromotionCodeId,
+    active: Option<bool>,
+) -> sqlx::Result<bool> {
+    let res = sqlx::query!(
+        // language=SQL
+        r#"
+update promotion_code
+set
+    active = coalesce($2, active),
+    updated_at = now()
+where promotion_code_id = $1
+"#,
+        id as PromotionCodeId,
+        active,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(res.rows_affected() > 0)
+}
+
+#[instrument(skip(pool))]
+pub async fn has_redeemed_promotion_code(
+    pool: &PgPool,
+    id: PromotionCodeId,
+    account_id: AccountId,
+) -> sqlx::Result<bool> {
+    sqlx::query_scalar!(
+        // language=SQL
+        r#"
+select exists(
+    select 1 from promotion_code_redemption where promotion_code_id = $1 and account_id = $2
+) as "exists!"
+"#,
+        id as PromotionCodeId,
+        account_id as AccountId,
+    )
+    .fetch_one(pool)
+    .await
+}
+
+/// Reserve a redemption of a promotion code for an account, before the discounted subscription
+/// is created.
+///
+/// Returns `false` if the code has no redemptions left, or the account already redeemed it.
+#[instrument(skip(pool))]
+pub async fn reserve_promotion_code(
+    pool: &PgPool,
+    id: PromotionCodeId,
+    account_id: AccountId,
+) -> sqlx::Result<bool> {
+    let mut txn = pool.begin().await?;
+
+    // lock the code so that concurrent redemptions can't exceed `max_redemptions`
+    let max_redemptions = sqlx::query_scalar!(
+        // language=SQL
+        r#"
+select max_redemptions from promotion_code where promotion_code_id = $1 for update
+"#,
+        id as PromotionCodeId,
+    )
+    .fetch_one(&mut txn)
+    .await?;
+
+    let res = sqlx::query!(
+        // language=SQL
+        r#"
+insert into promotion_code_redemption (promotion_code_id, account_id)
+select $1::uuid, $2::uuid
+where $3::int4 is null
+   or (select count(*) from promotion_code_redemption where promotion_code_id = $1) < $3
+on conflict do nothing
+"#,
+        id as PromotionCodeId,
+        account_id as AccountId,
+        max_redemptions,
+    )
+    .execute(&mut txn)
+    .await?;
+
+    txn.commit().await?;
+
+    Ok(res.rows_affected() > 0)
+}
+
+/// Release a reserved redemption if the subscription couldn't be created.
+#[instrument(skip(pool))]
+pub async fn release_promotion_code(
+    pool: &PgPool,
+    id: PromotionCodeId,
+    account_id: AccountId,
+) -> sqlx::Result<()> {
+    sqlx::query!(
+        // language=SQL
+        r#"
+delete from promotion_code_redemption
+where promotion_code_id = $1 and account_id = $2 and subscription_id is null
+"#,
+        id as PromotionCodeId,
+        account_id as AccountId,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Link a reserved redemption to the subscription it was redeemed for.
+#[instrument(skip(pool))]
+pub async fn redeem_promotion_code(
+    pool: &PgPool,
+    id: PromotionCodeId,
+    account_id: AccountId,
+    subscription_id: SubscriptionId,
+) -> sqlx::Result<()> {
+    sqlx::query!(
+        // language=SQL
+        r#"
+update promotion_code_redemption
+set subscription_id = $3
+where promotion_code_id = $1 and account_id = $2
+"#,
+        id as PromotionCodeId,
+        account_id as AccountId,
+        subscription_id as SubscriptionId,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
@@ -1,6 +1,8 @@
+use serde_json::json;
 use shared::domain::{
     circle::{Circle, CircleId, OrderBy},
     image::ImageId,
+    notification::NotificationKind,
     user::{UserId, UserScope},
 };
 
@@ -9,6 +11,9 @@ use uuid::Uuid;
 
 use crate::error;
 
+pub(crate) mod library;
+pub(crate) mod post;
+
 pub async fn create(
     conn: &mut PgConnection,
     display_name: &str,
@@ -105,6 +110,22 @@ where id = $1 and $2 is distinct from image"#,
 pub async fn delete(db: &PgPool, id: CircleId) -> sqlx::Result<()> {
     let mut conn = db.begin().await?;
 
+    // re-index the jigs pinned into the circle's library, so they can't be found by the circle anymore
+    sqlx::query!(
+        //language=SQL
+        r#"
+update jig_data
+set last_synced_at = null
+where id in (select live_id
+             from jig
+                      inner join circle_library_item on jig_id = jig.id
+             where circle_id = $1)
+"#,
+        id.0
+    )
+    .execute(&mut conn)
+    .await?;
+
     sqlx::query!("delete from circle where id = $1", id.0)
         .execute(&mut conn)
         .await?;
@@ -146,6 +167,8 @@ where id = $1
 }
 
 pub async fn join_circle(db: &PgPool, user_id: UserId, id: CircleId) -> anyhow::Result<()> {
+    let mut txn = db.begin().await?;
+
     sqlx::query!(
         r#"
 insert into circle_member(id, user_id) values($1, $2)
@@ -153,13 +176,70 @@ insert into circle_member(id, user_id) values($1, $2)
         id.0,
         user_id.0
     )
-    .execute(db)
+    .execute(&mut txn)
     .await
     .map_err(|_| anyhow::anyhow!("User is already a member of this circle"))?;
 
+    // the invite isn't pending anymore, so they can be invited again if they leave
+    sqlx::query!(
+        //language=SQL
+        r#"
+delete from notification
+where user_id = $2
+  and kind = $3
+  and data ->> 'circleId' = $1::text
+"#,
+        id.0,
+        user_id.0,
+        NotificationKind::CircleInvite as i16,
+    )
+    .execute(&mut txn)
+    .await?;
+
+    txn.commit().await?;
+
     Ok(())
 }
 
+/// Notifies `invitee_id` that they were invited to the circle.
+///
+/// Returns `false` if they're already a member. A user with a pending invite to the circle (whether
+/// they read it or not) isn't notified again, until they join it.
+pub async fn invite(
+    db: &PgPool,
+    id: CircleId,
+    inviter_id: UserId,
+    invitee_id: UserId,
+) -> sqlx::Result<bool> {
+    let mut txn = db.begin().await?;
+
+    let member = sqlx::query_scalar!(
+        //language=SQL
+        r#"select exists(select 1 from circle_member where id = $1 and user_id = $2) as "member!""#,
+        id.0,
+        invitee_id.0,
+    )
+    .fetch_one(&mut txn)
+    .await?;
+
+    if member {
+        return Ok(false);
+    }
+
+    // pending invites are deduplicated by `notification_circle_invite_idx`
+    super::notification::create(
+        &mut txn,
+        &[invitee_id.0],
+        NotificationKind::CircleInvite,
+        &json!({ "circleId": id, "invitedBy": inviter_id }),
+    )
+    .await?;
+
+    txn.commit().await?;
+
+    Ok(true)
+}
+
 pub async fn removed_circle_member(
     db: &PgPool,
     user_id: UserId,
@@ -374,6 +454,51 @@ select exists (
     Ok(())
 }
 
+/// How a user takes part in a circle.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Membership {
+    /// Not a member of the circle.
+    Outsider,
+    /// A member of the circle.
+    Member,
+    /// The creator of the circle, or an admin, who moderate it.
+    Moderator,
+}
+
+/// Gets the membership of a user in a circle, or `None` if the circle doesn't exist.
+pub async fn membership(
+    db: &PgPool,
+    user_id: UserId,
+    circle_id: CircleId,
+) -> sqlx::Result<Option<Membership>> {
+    let membership = sqlx::query!(
+        //language=SQL
+        r#"
+select creator_id = $2                                                         as "creator!",
+       exists(select 1 from circle_member where id = $1 and user_id = $2)      as "member!",
+       exists(select 1 from user_scope where user_id = $2 and scope = any($3)) as "admin!"
+from circle
+where id = $1
+"#,
+        circle_id.0,
+        user_id.0,
+        &[UserScope::Admin as i16, UserScope::AdminAsset as i16][..],
+    )
+    .fetch_optional(db)
+    .await?
+    .map(|row| {
+        if row.creator || row.admin {
+            Membership::Moderator
+        } else if row.member {
+            Membership::Member
+        } else {
+            Membership::Outsider
+        }
+    });
+
+    Ok(membership)
+}
+
 pub async fn filtered_count(
     db: &PgPool,
     users: Vec<UserId>,
@@ -0,0 +1,269 @@
+use shared::domain::{
+    asset::{AssetId, AssetType},
+    circle::{
+        library::{CircleLibraryItem, CircleLibraryItemId},
+        CircleId,
+    },
+    course::CourseId,
+    jig::JigId,
+    playlist::PlaylistId,
+    resource::ResourceId,
+    user::UserId,
+};
+use sqlx::{postgres::PgDatabaseError, PgConnection, PgPool};
+use uuid::Uuid;
+
+use super::Membership;
+use crate::error;
+
+/// Checks that the user can use the library of a circle, i.e. is a member or created it, and
+/// returns whether they moderate it.
+pub async fn authz(
+    db: &PgPool,
+    user_id: UserId,
+    circle_id: CircleId,
+) -> Result<bool, error::CircleLibrary> {
+    match super::membership(db, user_id, circle_id).await? {
+        None => Err(error::CircleLibrary::ResourceNotFound),
+        Some(Membership::Outsider) => Err(error::CircleLibrary::Forbidden),
+        Some(membership) => Ok(membership == Membership::Moderator),
+    }
+}
+
+fn handle_pin_err(err: sqlx::Error) -> error::CircleLibrary {
+    match err {
+        sqlx::Error::Database(err) => {
+            let constraint = err.downcast_ref::<PgDatabaseError>().constraint();
+
+            match constraint {
+                Some(constraint) if constraint.ends_with("_fkey") => {
+                    error::CircleLibrary::ResourceNotFound
+                }
+                Some(constraint) if constraint.ends_with("_key") => {
+                    error::CircleLibrary::AlreadyPinned
+                }
+                _ => sqlx::Error::Database(err).into(),
+            }
+        }
+        e => e.into(),
+    }
+}
+
+/// Marks a jig to be re-indexed, so that searching by circle picks up changes to libraries.
+async fn mark_jig_unsynced(txn: &mut PgConnection, jig_id: Uuid) -> sqlx::Result<()> {
+    sqlx::query!(
+        //language=SQL
+        r#"
+update jig_data
+set last_synced_at = null
+where id = (select live_id from jig where jig.id = $1)
+"#,
+        jig_id,
+    )
+    .execute(&mut *txn)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn pin(
+    db: &PgPool,
+    circle_id: CircleId,
+    user_id: UserId,
+    asset_id: AssetId,
+    note: &str,
+) -> Result<CircleLibraryItemId, error::CircleLibrary> {
+    let mut txn = db.begin().await?;
+
+    let (jig_id, playlist_id, resource_id, course_id) = match asset_id {
+        AssetId::JigId(id) => (Some(id.0), None, None, None),
+        AssetId::PlaylistId(id) => (None, Some(id.0), None, None),
+        AssetId::ResourceId(id) => (None, None, Some(id.0), None),
+        AssetId::CourseId(id) => (None, None, None, Some(id.0)),
+    };
+
+    let id = sqlx::query!(
+        //language=SQL
+        r#"
+insert into circle_library_item (circle_id, pinned_by, jig_id, playlist_id, resource_id, course_id, note)
+values ($1, $2, $3, $4, $5, $6, $7)
+returning id as "id!: CircleLibraryItemId"
+"#,
+        circle_id.0,
+        user_id.0,
+        jig_id,
+        playlist_id,
+        resource_id,
+        course_id,
+        note,
+    )
+    .fetch_one(&mut txn)
+    .await
+    .map_err(handle_pin_err)?
+    .id;
+
+    if let Some(jig_id) = jig_id {
+        mark_jig_unsynced(&mut txn, jig_id).await?;
+    }
+
+    txn.commit().await?;
+
+    Ok(id)
+}
+
+/// Lists the items of the library of a circle, most recently pinned first.
+///
+/// Returns the items along with the total number of items.
+pub async fn list(
+    db: &PgPool,
+    circle_id: CircleId,
+    asset_type: Option<AssetType>,
+    page: u32,
+    page_limit: u32,
+) -> sqlx::Result<(Vec<CircleLibraryItem>, u64)> {
+    let asset_type = asset_type.map(|asset_type| asset_type.to_string());
+
+    let total_count = sqlx::query!(
+        //language=SQL
+        r#"
+select count(*) as "count!"
+from circle_library_item
+where circle_id = $1
+  and ($2::text is null
+    or ($2 = 'jig' and jig_id is not null)
+    or ($2 = 'playlist' and playlist_id is not null)
+    or ($2 = 'resource' and resource_id is not null)
+    or ($2 = 'course' and course_id is not null))
+"#,
+        circle_id.0,
+        asset_type,
+    )
+    .fetch_one(db)
+    .await?
+    .count;
+
+    let rows = sqlx::query!(
+        //language=SQL
+        r#"
+select id                                   as "id!: CircleLibraryItemId",
+       jig_id                               as "jig_id?: JigId",
+       playlist_id                          as "playlist_id?: PlaylistId",
+       resource_id                          as "resource_id?: ResourceId",
+       course_id                            as "course_id?: CourseId",
+       note,
+       pinned_by                            as "pinned_by!: UserId",
+       (select given_name || ' '::text || family_name
+        from user_profile
+        where user_profile.user_id = pinned_by) as "pinned_by_name",
+       pinned_at,
+       updated_at
+from circle_library_item
+where circle_id = $1
+  and ($2::text is null
+    or ($2 = 'jig' and jig_id is not null)
+    or ($2 = 'playlist' and playlist_id is not null)
+    or ($2 = 'resource' and resource_id is not null)
+    or ($2 = 'course' and course_id is not null))
+order by pinned_at desc
+offset $3 limit $4
+"#,
+        circle_id.0,
+        asset_type,
+        (page as i64) * (page_limit as i64),
+        page_limit as i64,
+    )
+    .fetch_all(db)
+    .await?;
+
+    let items = rows
+        .into_iter()
+        .filter_map(|row| {
+            let asset_id = match (row.jig_id, row.playlist_id, row.resource_id, row.course_id) {
+                (Some(id), _, _, _) => AssetId::JigId(id),
+                (_, Some(id), _, _) => AssetId::PlaylistId(id),
+                (_, _, Some(id), _) => AssetId::ResourceId(id),
+                (_, _, _, Some(id)) => AssetId::CourseId(id),
+                // the check constraint makes sure exactly one is set
+                (None, None, None, None) => return None,
+            };
+
+            Some(CircleLibraryItem {
+                id: row.id,
+                asset_id,
+                note: row.note,
+                pinned_by: row.pinned_by,
+                pinned_by_name: row.pinned_by_name,
+                pinned_at: row.pinned_at,
+                updated_at: row.updated_at,
+            })
+        })
+        .collect();
+
+    Ok((items, total_count as u64))
+}
+
+/// Gets the member who pinned an item into the library of a circle, if the item exists.
+pub async fn get_pinned_by(
+    db: &PgPool,
+    circle_id: CircleId,
+    id: CircleLibraryItemId,
+) -> sqlx::Result<Option<UserId>> {
+    let pinned_by = sqlx::query!(
+        //language=SQL
+        r#"
+select pinned_by as "pinned_by!: UserId"
+from circle_library_item
+where id = $1
+  and circle_id = $2
+"#,
+        id.0,
+        circle_id.0,
+    )
+    .fetch_optional(db)
+    .await?
+    .map(|row| row.pinned_by);
+
+    Ok(pinned_by)
+}
+
+pub async fn update(db: &PgPool, id: CircleLibraryItemId, note: &str) -> sqlx::Result<()> {
+    sqlx::query!(
+        //language=SQL
+        r#"
+update circle_library_item
+set note       = $2,
+    updated_at = now()
+where id = $1
+"#,
+        id.0,
+        note,
+    )
+    .execute(db)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn unpin(db: &PgPool, id: CircleLibraryItemId) -> sqlx::Result<()> {
+    let mut txn = db.begin().await?;
+
+    let jig_id = sqlx::query!(
+        //language=SQL
+        r#"
+delete from circle_library_item where id = $1
+returning jig_id
+"#,
+        id.0,
+    )
+    .fetch_optional(&mut txn)
+    .await?
+    .and_then(|row| row.jig_id);
+
+    if let Some(jig_id) = jig_id {
+        mark_jig_unsynced(&mut txn, jig_id).await?;
+    }
+
+    txn.commit().await?;
+
+    Ok(())
+}
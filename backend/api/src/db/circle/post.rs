@@ -0,0 +1,430 @@
+use std::collections::HashMap;
+
+use shared::domain::{
+    asset::AssetId,
+    circle::{
+        post::{CirclePost, CirclePostId, CirclePostReaction, CirclePostReactionCount},
+        CircleId,
+    },
+    course::CourseId,
+    jig::JigId,
+    playlist::PlaylistId,
+    resource::ResourceId,
+    user::UserId,
+};
+use sqlx::{postgres::PgDatabaseError, PgConnection, PgPool};
+use uuid::Uuid;
+
+use super::Membership;
+use crate::error;
+
+/// Checks that the user can take part in the discussions of a circle, i.e. is a member or created
+/// it, and returns whether they moderate it.
+pub async fn authz(
+    db: &PgPool,
+    user_id: UserId,
+    circle_id: CircleId,
+) -> Result<bool, error::CirclePost> {
+    match super::membership(db, user_id, circle_id).await? {
+        None => Err(error::CirclePost::ResourceNotFound),
+        Some(Membership::Outsider) => Err(error::CirclePost::Forbidden),
+        Some(membership) => Ok(membership == Membership::Moderator),
+    }
+}
+
+fn handle_asset_err(err: sqlx::Error) -> error::CirclePost {
+    match err {
+        sqlx::Error::Database(err)
+            if err
+                .downcast_ref::<PgDatabaseError>()
+                .constraint()
+                .map_or(false, |constraint| constraint.ends_with("_fkey")) =>
+        {
+            error::CirclePost::ResourceNotFound
+        }
+        e => e.into(),
+    }
+}
+
+async fn insert_assets(
+    txn: &mut PgConnection,
+    post_id: CirclePostId,
+    assets: &[AssetId],
+) -> Result<(), error::CirclePost> {
+    for (index, asset) in assets.iter().enumerate() {
+        let (jig_id, playlist_id, resource_id, course_id) = match asset {
+            AssetId::JigId(id) => (Some(id.0), None, None, None),
+            AssetId::PlaylistId(id) => (None, Some(id.0), None, None),
+            AssetId::ResourceId(id) => (None, None, Some(id.0), None),
+            AssetId::CourseId(id) => (None, None, None, Some(id.0)),
+        };
+
+        sqlx::query!(
+            //language=SQL
+            r#"
+insert into circle_post_asset (post_id, "index", jig_id, playlist_id, resource_id, course_id)
+values ($1, $2, $3, $4, $5, $6)
+"#,
+            post_id.0,
+            index as i16,
+            jig_id,
+            playlist_id,
+            resource_id,
+            course_id,
+        )
+        .execute(&mut *txn)
+        .await
+        .map_err(handle_asset_err)?;
+    }
+
+    Ok(())
+}
+
+pub async fn create(
+    db: &PgPool,
+    circle_id: CircleId,
+    author_id: UserId,
+    body: &str,
+    assets: &[AssetId],
+    parent_id: Option<CirclePostId>,
+) -> Result<CirclePostId, error::CirclePost> {
+    let mut txn = db.begin().await?;
+
+    if let Some(parent_id) = parent_id {
+        // replies can only be made to threads of the same circle
+        let is_thread = sqlx::query!(
+            //language=SQL
+            r#"
+select exists(select 1 from circle_post where id = $1 and circle_id = $2 and parent_id is null) as "exists!"
+"#,
+            parent_id.0,
+            circle_id.0,
+        )
+        .fetch_one(&mut txn)
+        .await?
+        .exists;
+
+        if !is_thread {
+            return Err(error::CirclePost::InvalidParent);
+        }
+    }
+
+    let id = sqlx::query!(
+        //language=SQL
+        r#"
+insert into circle_post (circle_id, author_id, parent_id, body)
+values ($1, $2, $3, $4)
+returning id as "id!: CirclePostId"
+"#,
+        circle_id.0,
+        author_id.0,
+        parent_id.map(|id| id.0),
+        body,
+    )
+    .fetch_one(&mut txn)
+    .await?
+    .id;
+
+    insert_assets(&mut txn, id, assets).await?;
+
+    txn.commit().await?;
+
+    Ok(id)
+}
+
+/// Lists the threads of a circle newest first, or the replies to `parent_id` oldest first.
+///
+/// Returns the posts along with the total number of posts.
+pub async fn list(
+    db: &PgPool,
+    circle_id: CircleId,
+    parent_id: Option<CirclePostId>,
+    user_id: UserId,
+    page: u32,
+    page_limit: u32,
+) -> Result<(Vec<CirclePost>, u64), error::CirclePost> {
+    if let Some(parent_id) = parent_id {
+        let is_thread = sqlx::query!(
+            //language=SQL
+            r#"
+select exists(select 1 from circle_post where id = $1 and circle_id = $2 and parent_id is null) as "exists!"
+"#,
+            parent_id.0,
+            circle_id.0,
+        )
+        .fetch_one(db)
+        .await?
+        .exists;
+
+        if !is_thread {
+            return Err(error::CirclePost::ResourceNotFound);
+        }
+    }
+
+    let total_count = sqlx::query!(
+        //language=SQL
+        r#"
+select count(*) as "count!"
+from circle_post
+where circle_id = $1
+  and parent_id is not distinct from $2
+"#,
+        circle_id.0,
+        parent_id.map(|id| id.0),
+    )
+    .fetch_one(db)
+    .await?
+    .count;
+
+    let rows = sqlx::query!(
+        //language=SQL
+        r#"
+select id                                   as "id!: CirclePostId",
+       parent_id                            as "parent_id?: CirclePostId",
+       author_id                            as "author_id!: UserId",
+       (select given_name || ' '::text || family_name
+        from user_profile
+        where user_profile.user_id = author_id) as "author_name",
+       body,
+       (select count(*) from circle_post "reply" where reply.parent_id = circle_post.id) as "reply_count!",
+       (select reaction
+        from circle_post_reaction
+        where post_id = circle_post.id
+          and user_id = $3)                 as "my_reaction?: CirclePostReaction",
+       created_at,
+       updated_at
+from circle_post
+where circle_id = $1
+  and parent_id is not distinct from $2
+-- threads are newest first, replies oldest first
+order by (case when $2::uuid is null then created_at end) desc, created_at
+offset $4 limit $5
+"#,
+        circle_id.0,
+        parent_id.map(|id| id.0),
+        user_id.0,
+        (page as i64) * (page_limit as i64),
+        page_limit as i64,
+    )
+    .fetch_all(db)
+    .await?;
+
+    let ids: Vec<_> = rows.iter().map(|row| row.id.0).collect();
+
+    let mut assets = list_assets(db, &ids).await?;
+    let mut reactions = list_reactions(db, &ids).await?;
+
+    let posts = rows
+        .into_iter()
+        .map(|row| CirclePost {
+            id: row.id,
+            parent_id: row.parent_id,
+            author_id: row.author_id,
+            author_name: row.author_name,
+            body: row.body,
+            assets: assets.remove(&row.id).unwrap_or_default(),
+            reply_count: row.reply_count as u32,
+            reactions: reactions.remove(&row.id).unwrap_or_default(),
+            my_reaction: row.my_reaction,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        })
+        .collect();
+
+    Ok((posts, total_count as u64))
+}
+
+async fn list_assets(
+    db: &PgPool,
+    post_ids: &[Uuid],
+) -> sqlx::Result<HashMap<CirclePostId, Vec<AssetId>>> {
+    let rows = sqlx::query!(
+        //language=SQL
+        r#"
+select post_id                              as "post_id!: CirclePostId",
+       jig_id                               as "jig_id?: JigId",
+       playlist_id                          as "playlist_id?: PlaylistId",
+       resource_id                          as "resource_id?: ResourceId",
+       course_id                            as "course_id?: CourseId"
+from circle_post_asset
+where post_id = any($1)
+order by post_id, "index"
+"#,
+        post_ids,
+    )
+    .fetch_all(db)
+    .await?;
+
+    let mut assets: HashMap<CirclePostId, Vec<AssetId>> = HashMap::new();
+
+    for row in rows {
+        let asset = match (row.jig_id, row.playlist_id, row.resource_id, row.course_id) {
+            (Some(id), _, _, _) => AssetId::JigId(id),
+            (_, Some(id), _, _) => AssetId::PlaylistId(id),
+            (_, _, Some(id), _) => AssetId::ResourceId(id),
+            (_, _, _, Some(id)) => AssetId::CourseId(id),
+            // the check constraint makes sure exactly one is set
+            (None, None, None, None) => continue,
+        };
+
+        assets.entry(row.post_id).or_default().push(asset);
+    }
+
+    Ok(assets)
+}
+
+async fn list_reactions(
+    db: &PgPool,
+    post_ids: &[Uuid],
+) -> sqlx::Result<HashMap<CirclePostId, Vec<CirclePostReactionCount>>> {
+    let rows = sqlx::query!(
+        //language=SQL
+        r#"
+select post_id                              as "post_id!: CirclePostId",
+       reaction                             as "reaction!: CirclePostReaction",
+       count(*)                             as "count!"
+from circle_post_reaction
+where post_id = any($1)
+group by post_id, reaction
+order by post_id, count(*) desc, reaction
+"#,
+        post_ids,
+    )
+    .fetch_all(db)
+    .await?;
+
+    let mut reactions: HashMap<CirclePostId, Vec<CirclePostReactionCount>> = HashMap::new();
+
+    for row in rows {
+        reactions
+            .entry(row.post_id)
+            .or_default()
+            .push(CirclePostReactionCount {
+                reaction: row.reaction,
+                count: row.count as u32,
+            });
+    }
+
+    Ok(reactions)
+}
+
+/// Gets the author of a post in a circle, if the post exists.
+pub async fn get_author(
+    db: &PgPool,
+    circle_id: CircleId,
+    id: CirclePostId,
+) -> sqlx::Result<Option<UserId>> {
+    let author_id = sqlx::query!(
+        //language=SQL
+        r#"
+select author_id as "author_id!: UserId"
+from circle_post
+where id = $1
+  and circle_id = $2
+"#,
+        id.0,
+        circle_id.0,
+    )
+    .fetch_optional(db)
+    .await?
+    .map(|row| row.author_id);
+
+    Ok(author_id)
+}
+
+pub async fn update(
+    db: &PgPool,
+    id: CirclePostId,
+    body: Option<&str>,
+    assets: Option<&[AssetId]>,
+) -> Result<(), error::CirclePost> {
+    let mut txn = db.begin().await?;
+
+    sqlx::query!(
+        //language=SQL
+        r#"
+update circle_post
+set body       = coalesce($2, body),
+    updated_at = now()
+where id = $1
+"#,
+        id.0,
+        body,
+    )
+    .execute(&mut txn)
+    .await?;
+
+    if let Some(assets) = assets {
+        sqlx::query!(
+            //language=SQL
+            r#"
+delete from circle_post_asset where post_id = $1
+"#,
+            id.0,
+        )
+        .execute(&mut txn)
+        .await?;
+
+        insert_assets(&mut txn, id, assets).await?;
+    }
+
+    txn.commit().await?;
+
+    Ok(())
+}
+
+pub async fn delete(db: &PgPool, id: CirclePostId) -> sqlx::Result<()> {
+    sqlx::query!(
+        //language=SQL
+        r#"
+delete from circle_post where id = $1
+"#,
+        id.0,
+    )
+    .execute(db)
+    .await?;
+
+    Ok(())
+}
+
+/// Sets the reaction of a user to a post, removing it if `reaction` is `None`.
+pub async fn react(
+    db: &PgPool,
+    id: CirclePostId,
+    user_id: UserId,
+    reaction: Option<CirclePostReaction>,
+) -> sqlx::Result<()> {
+    match reaction {
+        Some(reaction) => {
+            sqlx::query!(
+                //language=SQL
+                r#"
+insert into circle_post_reaction (post_id, user_id, reaction)
+values ($1, $2, $3)
+on conflict (post_id, user_id) do update
+    set reaction   = excluded.reaction,
+        created_at = now()
+"#,
+                id.0,
+                user_id.0,
+                reaction as i16,
+            )
+            .execute(db)
+            .await?;
+        }
+        None => {
+            sqlx::query!(
+                //language=SQL
+                r#"
+delete from circle_post_reaction where post_id = $1 and user_id = $2
+"#,
+                id.0,
+                user_id.0,
+            )
+            .execute(db)
+            .await?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,180 @@
+use std::convert::TryFrom;
+
+use anyhow::anyhow;
+use shared::domain::{
+    admin::{CurationQueueItem, CurationStatusCounts},
+    asset::{AssetType, CurationStatus},
+    user::{UserId, UserScope},
+};
+use sqlx::PgPool;
+
+/// Whether the user is able to curate assets, i.e. has the `Admin` or `AdminAsset` scope.
+///
+/// Curators publish through the regular publish endpoints while curating, so publishing as a
+/// curator shouldn't put the asset back in the curation queue.
+pub async fn is_curator(pool: &PgPool, user_id: UserId) -> sqlx::Result<bool> {
+    is_curator_with_token_scopes(pool, user_id, None).await
+}
+
+/// Like [`is_curator`], but only counts the user's scopes which are in `token_scopes`, if any
+/// (see [`SessionClaims::token_scopes`](crate::token::SessionClaims::token_scopes)).
+pub async fn is_curator_with_token_scopes(
+    pool: &PgPool,
+    user_id: UserId,
+    token_scopes: Option<&[UserScope]>,
+) -> sqlx::Result<bool> {
+    let scopes: Vec<_> = [UserScope::Admin, UserScope::AdminAsset]
+        .into_iter()
+        .filter(|scope| token_scopes.map_or(true, |token_scopes| token_scopes.contains(scope)))
+        .collect();
+
+    if scopes.is_empty() {
+        return Ok(false);
+    }
+
+    super::user::has_scopes(pool, user_id, &scopes).await
+}
+
+/// Lists curation work across all asset types, oldest status change first.
+///
+/// If `status` is `None`, only assets which haven't completed curation are listed.
+pub async fn list_queue(
+    pool: &PgPool,
+    asset_type: Option<AssetType>,
+    status: Option<CurationStatus>,
+    assignee_id: Option<UserId>,
+    page: i32,
+    page_limit: u32,
+) -> anyhow::Result<Vec<CurationQueueItem>> {
+    let rows = sqlx::query!(
+        //language=SQL
+        r#"
+with queue as (
+    select 'jig' as asset_type, jig_id as id, jig_data.display_name, curation_status, assignee_id,
+           cd.created_at, status_updated_at, assigned_at, completed_at
+    from jig_curation_data "cd"
+             inner join jig on jig.id = cd.jig_id
+             inner join jig_data on jig_data.id = jig.live_id
+    union all
+    select 'resource', resource_id, resource_data.display_name, curation_status, assignee_id,
+           cd.created_at, status_updated_at, assigned_at, completed_at
+    from resource_curation_data "cd"
+             inner join resource on resource.id = cd.resource_id
+             inner join resource_data on resource_data.id = resource.live_id
+    union all
+    select 'playlist', playlist_id, playlist_data.display_name, curation_status, assignee_id,
+           cd.created_at, status_updated_at, assigned_at, completed_at
+    from playlist_curation_data "cd"
+             inner join playlist on playlist.id = cd.playlist_id
+             inner join playlist_data on playlist_data.id = playlist.live_id
+    union all
+    select 'course', course_id, course_data.display_name, curation_status, assignee_id,
+           cd.created_at, status_updated_at, assigned_at, completed_at
+    from course_curation_data "cd"
+             inner join course on course.id = cd.course_id
+             inner join course_data on course_data.id = course.live_id
+)
+select asset_type                           as "asset_type!",
+       id                                   as "id!",
+       display_name                         as "display_name!",
+       curation_status                      as "curation_status!: CurationStatus",
+       assignee_id                          as "assignee_id?: UserId",
+       created_at                           as "created_at!",
+       status_updated_at                    as "status_updated_at!",
+       assigned_at,
+       completed_at
+from queue
+where (asset_type = $1 or $1 is null)
+  and (curation_status = $2 or ($2 is null and curation_status <> $3))
+  and (assignee_id = $4 or $4 is null)
+order by status_updated_at asc
+offset $5
+limit $6
+"#,
+        asset_type.map(|it| it.as_str()),
+        status.map(|it| it as i16),
+        CurationStatus::Done as i16,
+        assignee_id.map(|it| it.0),
+        (page * page_limit as i32) as i64,
+        page_limit as i64,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    rows.into_iter()
+        .map(|row| {
+            let asset_type = AssetType::try_from(row.asset_type.as_str())
+                .map_err(|_| anyhow!("unknown asset type {}", row.asset_type))?;
+
+            Ok(CurationQueueItem {
+                asset_id: asset_type.to_asset_id(row.id),
+                display_name: row.display_name,
+                curation_status: row.curation_status,
+                assignee_id: row.assignee_id,
+                created_at: row.created_at,
+                status_updated_at: row.status_updated_at,
+                assigned_at: row.assigned_at,
+                completed_at: row.completed_at,
+            })
+        })
+        .collect()
+}
+
+/// Counts assets in each curation status, across all asset types.
+pub async fn status_counts(
+    pool: &PgPool,
+    asset_type: Option<AssetType>,
+    assignee_id: Option<UserId>,
+) -> anyhow::Result<CurationStatusCounts> {
+    let rows = sqlx::query!(
+        //language=SQL
+        r#"
+with queue as (
+    select 'jig' as asset_type, curation_status, assignee_id
+    from jig_curation_data "cd"
+             inner join jig on jig.id = cd.jig_id
+             inner join jig_data on jig_data.id = jig.live_id
+    union all
+    select 'resource', curation_status, assignee_id
+    from resource_curation_data "cd"
+             inner join resource on resource.id = cd.resource_id
+             inner join resource_data on resource_data.id = resource.live_id
+    union all
+    select 'playlist', curation_status, assignee_id
+    from playlist_curation_data "cd"
+             inner join playlist on playlist.id = cd.playlist_id
+             inner join playlist_data on playlist_data.id = playlist.live_id
+    union all
+    select 'course', curation_status, assignee_id
+    from course_curation_data "cd"
+             inner join course on course.id = cd.course_id
+             inner join course_data on course_data.id = course.live_id
+)
+select curation_status                      as "curation_status!: CurationStatus",
+       count(*)                             as "count!"
+from queue
+where (asset_type = $1 or $1 is null)
+  and (assignee_id = $2 or $2 is null)
+group by curation_status
+"#,
+        asset_type.map(|it| it.as_str()),
+        assignee_id.map(|it| it.0),
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut counts = CurationStatusCounts::default();
+
+    for row in rows {
+        let count = row.count as u64;
+
+        match row.curation_status {
+            CurationStatus::New => counts.new = count,
+            CurationStatus::NewVersion => counts.new_version = count,
+            CurationStatus::InProgress => counts.in_progress = count,
+            CurationStatus::Done => counts.done = count,
+        }
+    }
+
+    Ok(counts)
+}
@@ -1,9 +1,9 @@
 use crate::translate::translate_text;
 use anyhow::Context;
-use serde_json::value::Value;
+use serde_json::{json, value::Value};
 use shared::domain::{
     additional_resource::{AdditionalResource, AdditionalResourceId as AddId, ResourceContent},
-    asset::{DraftOrLive, OrderBy, PrivacyLevel},
+    asset::{AssetId, DraftOrLive, OrderBy, PrivacyLevel},
     category::CategoryId,
     jig::{
         AudioBackground, AudioEffects, AudioFeedbackNegative, AudioFeedbackPositive, JigAdminData,
@@ -11,6 +11,7 @@ use shared::domain::{
     },
     meta::{AffiliationId, AgeRangeId, ResourceTypeId as TypeId},
     module::{body::ThemeId, LiteModule, ModuleId, ModuleKind},
+    notification::NotificationKind,
     playlist::{PlaylistData, PlaylistId, PlaylistResponse},
     user::{UserId, UserScope},
 };
@@ -25,7 +26,7 @@ pub(crate) mod additional_resource;
 pub(crate) mod curation;
 pub(crate) mod module;
 pub(crate) mod player;
-pub(crate) mod report;
+pub mod report;
 
 pub async fn create(
     pool: &PgPool,
@@ -211,6 +212,8 @@ select cte.jig_id                                          as "jig_id: JigId",
         play_count,
         live_up_to_date,
         exists(select 1 from jig_like where jig_id = $1 and user_id = $3)    as "is_liked!",
+        (select avg(rating)::real from asset_review where not asset_review.hidden and asset_review.jig_id = cte.jig_id)  as "average_rating",
+        (select count(*) from asset_review where not asset_review.hidden and asset_review.jig_id = cte.jig_id)        as "review_count!",
         locked,
         other_keywords,
         translated_keywords,
@@ -256,6 +259,8 @@ from jig_data
         author_name: row.author_name,
         likes: row.liked_count,
         plays: row.play_count,
+        average_rating: row.average_rating,
+        review_count: row.review_count,
         live_up_to_date: row.live_up_to_date,
         is_liked: row.is_liked,
         jig_data: JigData {
@@ -352,6 +357,8 @@ select jig.id                                       as "id!: JigId",
        liked_count                              as "liked_count!",
        live_up_to_date                          as "live_up_to_date!",
        exists(select 1 from jig_like where jig_id = jig.id and user_id = $2) as "is_liked!",
+       (select avg(rating)::real from asset_review where not asset_review.hidden and asset_review.jig_id = jig.id) as "average_rating",
+       (select count(*) from asset_review where not asset_review.hidden and asset_review.jig_id = jig.id) as "review_count!",
        (
            select play_count
            from jig_play_count
@@ -442,6 +449,8 @@ order by ord asc
             author_name: jig_row.author_name,
             likes: jig_row.liked_count,
             plays: jig_row.play_count,
+            average_rating: jig_row.average_rating,
+            review_count: jig_row.review_count,
             live_up_to_date: jig_row.live_up_to_date,
             is_liked: jig_row.is_liked,
             jig_data: JigData {
@@ -583,6 +592,8 @@ select jig.id                                              as "jig_id: JigId",
     liked_count,
     live_up_to_date,
     exists(select 1 from jig_like where jig_id = jig.id and user_id = $9) as "is_liked!",
+    (select avg(rating)::real from asset_review where not asset_review.hidden and asset_review.jig_id = jig.id) as "average_rating",
+    (select count(*) from asset_review where not asset_review.hidden and asset_review.jig_id = jig.id) as "review_count!",
     (
          select play_count
          from jig_play_count
@@ -666,6 +677,8 @@ limit $8
             author_name: jig_data_row.author_name,
             likes: jig_data_row.liked_count,
             plays: jig_data_row.play_count,
+            average_rating: jig_data_row.average_rating,
+            review_count: jig_data_row.review_count,
             live_up_to_date: jig_data_row.live_up_to_date,
             is_liked: jig_data_row.is_liked,
             jig_data: JigData {
@@ -1510,6 +1523,16 @@ values ($1, $2)
     .await
     .map_err(|_| anyhow::anyhow!("Cannot like a jig more than once"))?;
 
+    if let Some(author_id) = jig.author_id {
+        super::notification::create(
+            &mut txn,
+            &[author_id.0],
+            NotificationKind::AssetLiked,
+            &json!({ "assetId": AssetId::JigId(id), "likedBy": user_id }),
+        )
+        .await?;
+    }
+
     txn.commit().await?;
 
     Ok(())
@@ -1596,6 +1619,8 @@ select playlist.id
     plays,
     live_up_to_date,
     exists(select 1 from playlist_like where playlist_id = playlist.id and user_id = $2)    as "is_liked!",
+    (select avg(rating)::real from asset_review where not asset_review.hidden and asset_review.playlist_id = playlist.id) as "average_rating",
+    (select count(*) from asset_review where not asset_review.hidden and asset_review.playlist_id = playlist.id) as "review_count!",
     display_name                                                                  as "display_name!",
     updated_at,
     language                                                                      as "language!",
@@ -1655,6 +1680,8 @@ order by coalesce(updated_at, created_at) desc
             author_name: playlist_data_row.author_name,
             likes: playlist_data_row.likes,
             plays: playlist_data_row.plays,
+            average_rating: playlist_data_row.average_rating,
+            review_count: playlist_data_row.review_count,
             live_up_to_date: playlist_data_row.live_up_to_date,
             is_liked: playlist_data_row.is_liked,
             playlist_data: PlaylistData {
@@ -1733,11 +1760,28 @@ select exists(select 1 from user_scope where user_id = $1) as "authed!"
 }
 
 pub async fn authz(db: &PgPool, user_id: UserId, jig_id: Option<JigId>) -> Result<(), error::Auth> {
+    authz_with_token_scopes(db, user_id, None, jig_id).await
+}
+
+/// Like [`authz`], but only counts the user's scopes which are in `token_scopes`, if any
+/// (see [`SessionClaims::token_scopes`](crate::token::SessionClaims::token_scopes)).
+pub async fn authz_with_token_scopes(
+    db: &PgPool,
+    user_id: UserId,
+    token_scopes: Option<&[UserScope]>,
+    jig_id: Option<JigId>,
+) -> Result<(), error::Auth> {
+    let token_scopes: Option<Vec<i16>> =
+        token_scopes.map(|scopes| scopes.iter().map(|scope| *scope as i16).collect());
+
     let authed = match jig_id {
         None => {
             sqlx::query!(
                 r#"
-select exists(select 1 from user_scope where user_id = $1 and scope = any($2)) as "authed!"
+select exists(
+    select 1 from user_scope
+    where user_id = $1 and scope = any($2) and ($3::int2[] is null or scope = any($3))
+) as "authed!"
 "#,
                 user_id.0,
                 &[
@@ -1745,6 +1789,7 @@ select exists(select 1 from user_scope where user_id = $1 and scope = any($2)) a
                     UserScope::AdminAsset as i16,
                     UserScope::ManageSelfAsset as i16,
                 ][..],
+                token_scopes.as_deref(),
             )
             .fetch_one(db)
             .await?
@@ -1755,16 +1800,21 @@ select exists(select 1 from user_scope where user_id = $1 and scope = any($2)) a
                 //language=SQL
                 r#"
 select exists (
-    select 1 from user_scope where user_id = $1 and scope = any($2)
+    select 1 from user_scope
+    where user_id = $1 and scope = any($2) and ($5::int2[] is null or scope = any($5))
 ) or (
-    exists (select 1 from user_scope where user_id = $1 and scope = $3) and
+    exists (
+        select 1 from user_scope
+        where user_id = $1 and scope = $3 and ($5::int2[] is null or scope = any($5))
+    ) and
     not exists (select 1 from jig where jig.id = $4 and jig.author_id <> $1)
 ) as "authed!"
 "#,
                 user_id.0,
                 &[UserScope::Admin as i16, UserScope::AdminAsset as i16,][..],
                 UserScope::ManageSelfAsset as i16,
-                id.0
+                id.0,
+                token_scopes.as_deref(),
             )
             .fetch_one(db)
             .await?
@@ -1,16 +1,21 @@
 use chrono::{DateTime, Utc};
-use shared::domain::jig::{
-    curation::{
-        CommentId, JigCurationComment, JigCurationCommentResponse, JigCurationData,
-        JigCurationFieldsDone, JigCurationStatus,
+use serde_json::json;
+use shared::domain::{
+    jig::{
+        curation::{
+            CommentId, JigCurationComment, JigCurationCommentResponse, JigCurationData,
+            JigCurationFieldsDone, JigCurationStatus,
+        },
+        report::JigReport,
+        JigId,
     },
-    report::JigReport,
-    JigId,
+    notification::NotificationKind,
+    user::UserId,
 };
-use sqlx::PgPool;
+use sqlx::{PgConnection, PgPool};
 use uuid::Uuid;
 
-use crate::error;
+use crate::{db, error};
 
 pub async fn update(
     pool: &PgPool,
@@ -23,9 +28,34 @@ pub async fn update(
     description: Option<bool>,
     additional_resources: Option<bool>,
     curation_status: Option<JigCurationStatus>,
-) -> anyhow::Result<(), error::Auth> {
+    assignee_id: Option<Option<UserId>>,
+) -> anyhow::Result<(), error::Curation> {
     let mut txn = pool.begin().await?;
 
+    let current_status = sqlx::query!(
+        //language=SQL
+        r#"
+select curation_status as "curation_status!: JigCurationStatus"
+from jig_curation_data
+where jig_id = $1
+for update
+"#,
+        jig_id.0,
+    )
+    .fetch_optional(&mut txn)
+    .await?
+    .ok_or(error::Curation::ResourceNotFound)?
+    .curation_status;
+
+    if let Some(curation_status) = curation_status {
+        if !current_status.can_transition_to(curation_status) {
+            return Err(error::Curation::InvalidTransition(
+                current_status,
+                curation_status,
+            ));
+        }
+    }
+
     if let Some(display_name) = display_name {
         sqlx::query!(
             //language=SQL
@@ -136,12 +166,31 @@ where jig_id = $1 and $2 is distinct from categories
         sqlx::query!(
             //language=SQL
             r#"
-            update jig_curation_data
-            set curation_status = $2
-            where jig_id = $1 and $2 is distinct from curation_status
-             "#,
+update jig_curation_data
+set curation_status   = $2,
+    status_updated_at = now(),
+    completed_at      = case when $2 = $3 then now() else completed_at end
+where jig_id = $1 and $2 is distinct from curation_status
+"#,
             jig_id.0,
             curation_status as i16,
+            JigCurationStatus::Done as i16,
+        )
+        .execute(&mut txn)
+        .await?;
+    }
+
+    if let Some(assignee_id) = assignee_id {
+        sqlx::query!(
+            //language=SQL
+            r#"
+update jig_curation_data
+set assignee_id = $2,
+    assigned_at = case when $2::uuid is null then null else now() end
+where jig_id = $1 and $2 is distinct from assignee_id
+"#,
+            jig_id.0,
+            assignee_id.map(|it| it.0),
         )
         .execute(&mut txn)
         .await?;
@@ -176,6 +225,11 @@ select jig_id                               as "jig_id!: JigId",
        affiliations,
        additional_resources,
        curation_status                          as "curation_status!: JigCurationStatus",
+       assignee_id                              as "assignee_id?: UserId",
+       created_at,
+       status_updated_at,
+       assigned_at,
+       completed_at,
        array(
             select row (jcc.id, jcc.jig_id, comment, created_at, author_id)
             from jig_curation_comment  "jcc"
@@ -194,7 +248,12 @@ select jig_id                               as "jig_id!: JigId",
                             from user_email
                             where user_email.user_id = reporter_id
                         ),
-                        created_at                                                                         
+                        created_at,
+                        status,
+                        assignee_id,
+                        action,
+                        resolution_note,
+                        resolved_at
             )
            from jig_report "jr"
            where jcd.jig_id = jr.jig_id
@@ -210,6 +269,11 @@ where jig_id = $1
     .map(|row| JigCurationData {
         jig_id: row.jig_id,
         curation_status: row.curation_status,
+        assignee_id: row.assignee_id,
+        created_at: row.created_at,
+        status_updated_at: row.status_updated_at,
+        assigned_at: row.assigned_at,
+        completed_at: row.completed_at,
         fields_done: JigCurationFieldsDone {
             display_name: row.display_name,
             language: row.language,
@@ -239,8 +303,10 @@ pub async fn create_comment(
     value: String,
     author_id: Uuid,
 ) -> anyhow::Result<CommentId> {
+    let mut txn = pool.begin().await?;
+
     // Checks if Audio and Image IDs exists
-    sqlx::query!(
+    let id = sqlx::query!(
         r#"
 insert into jig_curation_comment (jig_id, comment, author_id)
 values ($1, $2, $3)
@@ -250,10 +316,28 @@ returning id as "id!: CommentId"
         value,
         author_id
     )
-    .fetch_one(pool)
-    .await
-    .map(|it| it.id)
-    .map_err(Into::into)
+    .fetch_one(&mut txn)
+    .await?
+    .id;
+
+    // curators can be the author of the jig they're commenting on
+    let jig_author_id = db::webhook::jig_author(&mut txn, jig_id)
+        .await?
+        .filter(|jig_author_id| jig_author_id.0 != author_id);
+
+    if let Some(jig_author_id) = jig_author_id {
+        db::notification::create(
+            &mut txn,
+            &[jig_author_id.0],
+            NotificationKind::CurationComment,
+            &json!({ "jigId": jig_id, "commentId": id }),
+        )
+        .await?;
+    }
+
+    txn.commit().await?;
+
+    Ok(id)
 }
 
 pub async fn get_comment(
@@ -293,3 +377,23 @@ where id = $1 and jig_id = $2
 
     Ok(comment)
 }
+
+/// Moves a curated JIG back into the curation queue after it has been republished.
+pub async fn mark_new_version(txn: &mut PgConnection, jig_id: JigId) -> sqlx::Result<()> {
+    sqlx::query!(
+        //language=SQL
+        r#"
+update jig_curation_data
+set curation_status   = $2,
+    status_updated_at = now()
+where jig_id = $1 and curation_status = $3
+"#,
+        jig_id.0,
+        JigCurationStatus::NewVersion as i16,
+        JigCurationStatus::Done as i16,
+    )
+    .execute(txn)
+    .await?;
+
+    Ok(())
+}
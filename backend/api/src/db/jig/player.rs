@@ -1,81 +1,199 @@
 use chrono::{DateTime, Duration, Utc};
 use rand::{rngs::ThreadRng, Rng};
+use serde_json::json;
 use shared::config::{JIG_PLAYER_SESSION_CODE_MAX, JIG_PLAYER_SESSION_VALID_DURATION_SECS};
-use shared::domain::jig::{
-    player::{JigPlayerSession, JigPlayerSessionIndex, JigPlayerSettings},
-    JigId, TextDirection,
+use shared::domain::{
+    jig::{
+        player::{
+            assignment::{JigPlayerAssignment, JigPlayerAssignmentCreateRequest},
+            instance::{
+                PlayerSessionInstanceId, PlayerSessionInstanceModuleResult,
+                PlayerSessionInstanceResult, PlayerSessionInstanceRosterStudent,
+                PlayerSessionInstanceStudent,
+            },
+            JigPlayerSession, JigPlayerSessionIndex, JigPlayerSessionItem,
+            JigPlayerSessionLeaderboardEntry, JigPlayerSettings,
+        },
+        JigId, TextDirection,
+    },
+    module::ModuleId,
+    playlist::PlaylistId,
+    roster::{PicturePassword, RosterId, RosterStudentId},
+    user::UserId,
+    webhook::WebhookEvent,
 };
-use sqlx::{error::DatabaseError, postgres::PgDatabaseError, PgPool};
+use sqlx::{postgres::PgDatabaseError, PgConnection, PgPool};
+use std::collections::HashMap;
 use uuid::Uuid;
 
 use crate::{
     error,
     extractor::{IPAddress, UserAgent},
+    webhook,
 };
 
 pub async fn create(
     db: &PgPool,
     jig_id: JigId,
+    creator_id: UserId,
     settings: &JigPlayerSettings,
+    roster_id: Option<RosterId>,
+    valid_for: Duration,
 ) -> Result<(JigPlayerSessionIndex, DateTime<Utc>), error::JigCode> {
+    let req = JigPlayerAssignmentCreateRequest {
+        items: vec![JigPlayerSessionItem {
+            jig_id,
+            settings: None,
+        }],
+        settings: settings.clone(),
+        roster_id,
+        ..Default::default()
+    };
+
+    let assignment = create_assignment(db, creator_id, &req, valid_for).await?;
+
+    Ok((assignment.index, assignment.expires_at))
+}
+
+/// Creates a player session for the JIGs of an assignment. The items of `req` must already be
+/// resolved from its playlist, if any.
+///
+/// The session stays valid for `valid_for` after the assignment closes.
+pub async fn create_assignment(
+    db: &PgPool,
+    creator_id: UserId,
+    req: &JigPlayerAssignmentCreateRequest,
+    valid_for: Duration,
+) -> Result<JigPlayerAssignment, error::JigCode> {
+    let first_jig_id = req
+        .items
+        .first()
+        .ok_or(error::JigCode::InvalidAssignment)?
+        .jig_id;
+
+    let mut txn = db.begin().await?;
+
+    if let Some(roster_id) = req.roster_id {
+        let owned = sqlx::query!(
+            //language=SQL
+            r#"
+select exists(select 1 from roster where id = $1 and creator_id = $2) as "exists!"
+"#,
+            roster_id.0,
+            creator_id.0,
+        )
+        .fetch_one(&mut txn)
+        .await?
+        .exists;
+
+        if !owned {
+            return Err(error::JigCode::ResourceNotFound);
+        }
+    }
+
     let mut generator = rand::thread_rng();
 
     let mut index = generate_random_code(&mut generator);
 
-    let expires_at = Utc::now() + Duration::seconds(JIG_PLAYER_SESSION_VALID_DURATION_SECS as i64);
+    // results stay available for a while after the assignment closes
+    let expires_at = req
+        .closes_at
+        .or(req.opens_at)
+        .map_or_else(Utc::now, |at| at.max(Utc::now()))
+        + valid_for;
 
     // retry as many times as there are possible codes
     // NOTE: this is NOT guaranteed to successfully insert if there
+    let mut inserted = false;
     for _ in 0..JIG_PLAYER_SESSION_CODE_MAX * 2 {
         log::debug!("Try insert with index {}", index);
-        match sqlx::query!(
+        let res = sqlx::query!(
             //language=SQL
             r#"
-insert into jig_player_session (jig_id, index, direction, display_score, track_assessments, drag_assist, expires_at)
-values ($1, $2, $3, $4, $5, $6, $7)
-
+insert into jig_player_session (jig_id, index, direction, display_score, track_assessments, drag_assist, expires_at,
+                                creator_id, roster_id, playlist_id, opens_at, closes_at, max_attempts)
+values ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+on conflict (index) do nothing
 "#,
-            jig_id.0,
+            first_jig_id.0,
             index,
-            settings.direction as i16,
-            settings.display_score,
-            settings.track_assessments,
-            settings.drag_assist,
+            req.settings.direction as i16,
+            req.settings.display_score,
+            req.settings.track_assessments,
+            req.settings.drag_assist,
             expires_at,
+            creator_id.0,
+            req.roster_id.map(|id| id.0),
+            req.playlist_id.map(|id| id.0),
+            req.opens_at,
+            req.closes_at,
+            req.max_attempts.map(|attempts| attempts.min(i16::MAX as u16) as i16),
         )
-        .execute(db)
+        .execute(&mut txn)
         .await
-        {
-            Ok(_) => { // insert successful
-                return Ok((JigPlayerSessionIndex(index), expires_at));
-            },
-            Err(err) => match err {
-                sqlx::Error::Database(db_err) => {
-                    session_create_error_or_continue(db_err)?;
-                    // did not return error on previous line, retry with new index
-                    index = generate_random_code(&mut generator);
-                },
-                err => return Err(anyhow::anyhow!("sqlx error: {:?}", err).into()),
-            },
+        .map_err(session_create_error)?;
+
+        if res.rows_affected() > 0 {
+            inserted = true;
+            break;
         }
+
+        // code is taken, retry insert with a new code
+        index = generate_random_code(&mut generator);
+    }
+
+    if !inserted {
+        return Err(
+            anyhow::anyhow!("Maximum retries reached for creating a new jig session").into(),
+        );
+    }
+
+    for (item_index, item) in req.items.iter().enumerate() {
+        let settings = item.settings.as_ref();
+
+        sqlx::query!(
+            //language=SQL
+            r#"
+insert into jig_player_session_item (session_index, "index", jig_id, direction, display_score, track_assessments, drag_assist)
+values ($1, $2, $3, $4, $5, $6, $7)
+"#,
+            index,
+            item_index as i16,
+            item.jig_id.0,
+            settings.map(|settings| settings.direction as i16),
+            settings.map(|settings| settings.display_score),
+            settings.map(|settings| settings.track_assessments),
+            settings.map(|settings| settings.drag_assist),
+        )
+        .execute(&mut txn)
+        .await
+        .map_err(session_create_error)?;
     }
 
-    Err(anyhow::anyhow!("Maximum retries reached for creating a new jig session").into())
+    let assignment = get_assignment(&mut txn, JigPlayerSessionIndex(index))
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("Created jig session is missing"))?;
+
+    txn.commit().await?;
+
+    Ok(assignment)
 }
 
-fn session_create_error_or_continue(db_err: Box<dyn DatabaseError>) -> Result<(), error::JigCode> {
-    let constraint = db_err.downcast_ref::<PgDatabaseError>().constraint();
+fn session_create_error(err: sqlx::Error) -> error::JigCode {
+    let db_err = match err {
+        sqlx::Error::Database(db_err) => db_err,
+        err => return anyhow::anyhow!("sqlx error: {:?}", err).into(),
+    };
 
-    match constraint {
-        Some("jig_player_session_pkey") => {
-            // same code but different jig. retry insert with a new code
-            Ok(())
+    match db_err.downcast_ref::<PgDatabaseError>().constraint() {
+        Some("jig_player_session_jig_id_fkey")
+        | Some("jig_player_session_item_jig_id_fkey")
+        | Some("jig_player_session_playlist_id_fkey") => {
+            // no jig or playlist with this id exists
+            error::JigCode::ResourceNotFound
         }
-        Some("jig_player_session_jig_id_fkey") => {
-            // no jig with this id exists
-            Err(error::JigCode::ResourceNotFound)
-        }
-        db_err => Err(anyhow::anyhow!("{}", db_err.unwrap_or("unknown database error")).into()),
+        Some("jig_player_session_window_check") => error::JigCode::InvalidAssignment,
+        db_err => anyhow::anyhow!("{}", db_err.unwrap_or("unknown database error")).into(),
     }
 }
 
@@ -85,6 +203,249 @@ fn generate_random_code(generator: &mut ThreadRng) -> i32 {
     generator.gen_range(0..JIG_PLAYER_SESSION_CODE_MAX)
 }
 
+/// Lists the JIGs of a published playlist, in order, or `None` if the playlist does not exist.
+pub async fn list_playlist_items(
+    db: &PgPool,
+    playlist_id: PlaylistId,
+) -> sqlx::Result<Option<Vec<JigPlayerSessionItem>>> {
+    let exists = sqlx::query!(
+        //language=SQL
+        r#"
+select exists(select 1 from playlist where id = $1 and live_id is not null) as "exists!"
+"#,
+        playlist_id.0,
+    )
+    .fetch_one(db)
+    .await?
+    .exists;
+
+    if !exists {
+        return Ok(None);
+    }
+
+    let items = sqlx::query!(
+        //language=SQL
+        r#"
+select jig_id as "jig_id!: JigId"
+from playlist_data_jig
+         inner join playlist on playlist.live_id = playlist_data_jig.playlist_data_id
+where playlist.id = $1
+order by "index"
+"#,
+        playlist_id.0,
+    )
+    .fetch_all(db)
+    .await?
+    .into_iter()
+    .map(|row| JigPlayerSessionItem {
+        jig_id: row.jig_id,
+        settings: None,
+    })
+    .collect();
+
+    Ok(Some(items))
+}
+
+async fn list_items(
+    txn: &mut PgConnection,
+    indexes: &[i32],
+) -> sqlx::Result<HashMap<i32, Vec<JigPlayerSessionItem>>> {
+    let rows = sqlx::query!(
+        //language=SQL
+        r#"
+select session_index,
+       jig_id                               as "jig_id!: JigId",
+       direction                            as "direction?: TextDirection",
+       display_score,
+       track_assessments,
+       drag_assist
+from jig_player_session_item
+where session_index = any($1)
+order by session_index, "index"
+"#,
+        indexes,
+    )
+    .fetch_all(&mut *txn)
+    .await?;
+
+    let mut items: HashMap<i32, Vec<JigPlayerSessionItem>> = HashMap::new();
+
+    for row in rows {
+        let settings = match (
+            row.direction,
+            row.display_score,
+            row.track_assessments,
+            row.drag_assist,
+        ) {
+            (Some(direction), Some(display_score), Some(track_assessments), Some(drag_assist)) => {
+                Some(JigPlayerSettings {
+                    direction,
+                    display_score,
+                    track_assessments,
+                    drag_assist,
+                })
+            }
+            _ => None,
+        };
+
+        items
+            .entry(row.session_index)
+            .or_default()
+            .push(JigPlayerSessionItem {
+                jig_id: row.jig_id,
+                settings,
+            });
+    }
+
+    Ok(items)
+}
+
+async fn get_assignment(
+    txn: &mut PgConnection,
+    index: JigPlayerSessionIndex,
+) -> sqlx::Result<Option<JigPlayerAssignment>> {
+    let row = sqlx::query!(
+        //language=SQL
+        r#"
+select direction                            as "direction: TextDirection",
+       display_score,
+       track_assessments,
+       drag_assist,
+       playlist_id                          as "playlist_id?: PlaylistId",
+       opens_at,
+       closes_at,
+       max_attempts,
+       roster_id                            as "roster_id?: RosterId",
+       created_at,
+       expires_at
+from jig_player_session
+where index = $1
+"#,
+        index.0,
+    )
+    .fetch_optional(&mut *txn)
+    .await?;
+
+    let row = match row {
+        Some(row) => row,
+        None => return Ok(None),
+    };
+
+    let items = list_items(&mut *txn, &[index.0])
+        .await?
+        .remove(&index.0)
+        .unwrap_or_default();
+
+    Ok(Some(JigPlayerAssignment {
+        index,
+        items,
+        playlist_id: row.playlist_id,
+        settings: JigPlayerSettings {
+            direction: row.direction,
+            display_score: row.display_score,
+            track_assessments: row.track_assessments,
+            drag_assist: row.drag_assist,
+        },
+        opens_at: row.opens_at,
+        closes_at: row.closes_at,
+        max_attempts: row.max_attempts.map(|attempts| attempts as u16),
+        roster_id: row.roster_id,
+        created_at: row.created_at,
+        expires_at: row.expires_at,
+    }))
+}
+
+/// Lists the assignments created by a user, newest first.
+pub async fn list_assignments(
+    db: &PgPool,
+    creator_id: UserId,
+) -> sqlx::Result<Vec<JigPlayerAssignment>> {
+    let mut txn = db.begin().await?;
+
+    let rows = sqlx::query!(
+        //language=SQL
+        r#"
+select index,
+       direction                            as "direction: TextDirection",
+       display_score,
+       track_assessments,
+       drag_assist,
+       playlist_id                          as "playlist_id?: PlaylistId",
+       opens_at,
+       closes_at,
+       max_attempts,
+       roster_id                            as "roster_id?: RosterId",
+       created_at,
+       expires_at
+from jig_player_session
+where creator_id = $1
+order by created_at desc
+"#,
+        creator_id.0,
+    )
+    .fetch_all(&mut txn)
+    .await?;
+
+    let indexes: Vec<_> = rows.iter().map(|row| row.index).collect();
+
+    let mut items = list_items(&mut txn, &indexes).await?;
+
+    txn.commit().await?;
+
+    let assignments = rows
+        .into_iter()
+        .map(|row| JigPlayerAssignment {
+            index: JigPlayerSessionIndex(row.index),
+            items: items.remove(&row.index).unwrap_or_default(),
+            playlist_id: row.playlist_id,
+            settings: JigPlayerSettings {
+                direction: row.direction,
+                display_score: row.display_score,
+                track_assessments: row.track_assessments,
+                drag_assist: row.drag_assist,
+            },
+            opens_at: row.opens_at,
+            closes_at: row.closes_at,
+            max_attempts: row.max_attempts.map(|attempts| attempts as u16),
+            roster_id: row.roster_id,
+            created_at: row.created_at,
+            expires_at: row.expires_at,
+        })
+        .collect();
+
+    Ok(assignments)
+}
+
+/// Closes an assignment now, unless it already closed.
+pub async fn close_assignment(
+    db: &PgPool,
+    index: JigPlayerSessionIndex,
+) -> Result<JigPlayerAssignment, error::JigCode> {
+    let mut txn = db.begin().await?;
+
+    sqlx::query!(
+        //language=SQL
+        r#"
+update jig_player_session
+-- an assignment closed before it opened is never opened
+set opens_at  = case when opens_at >= now() then null else opens_at end,
+    closes_at = least(coalesce(closes_at, now()), now())
+where index = $1
+"#,
+        index.0,
+    )
+    .execute(&mut txn)
+    .await?;
+
+    let assignment = get_assignment(&mut txn, index)
+        .await?
+        .ok_or(error::JigCode::ResourceNotFound)?;
+
+    txn.commit().await?;
+
+    Ok(assignment)
+}
+
 pub async fn list_sessions(db: &PgPool, jig_id: JigId) -> sqlx::Result<Vec<JigPlayerSession>> {
     let sessions = sqlx::query!(
         //language=SQL
@@ -94,7 +455,8 @@ select index     as "index!: i32",
        display_score,
        track_assessments,
        drag_assist,
-       expires_at as "expires_at: DateTime<Utc>"
+       expires_at as "expires_at: DateTime<Utc>",
+       roster_id as "roster_id?: RosterId"
 from jig_player_session
 where jig_id = $1
 "#,
@@ -112,6 +474,7 @@ where jig_id = $1
             drag_assist: it.drag_assist,
         },
         expires_at: it.expires_at,
+        roster_id: it.roster_id,
     })
     .collect();
 
@@ -122,9 +485,11 @@ where jig_id = $1
 pub async fn create_session_instance(
     db: &PgPool,
     session_index: JigPlayerSessionIndex,
+    display_name: Option<&str>,
+    student: Option<&PlayerSessionInstanceStudent>,
     ip_address: IPAddress,
     user_agent: UserAgent,
-) -> Result<(JigId, JigPlayerSettings, Uuid), error::JigCode> {
+) -> Result<(JigId, JigPlayerSettings, Vec<JigPlayerSessionItem>, Uuid), error::JigCode> {
     let mut txn = db.begin().await?;
 
     let session_info = sqlx::query!(
@@ -134,9 +499,13 @@ pub async fn create_session_instance(
                direction as "direction: TextDirection", 
                display_score, 
                track_assessments, 
-               drag_assist
+               drag_assist,
+               roster_id as "roster_id?: RosterId",
+               max_attempts,
+               coalesce(opens_at > now(), false) as "not_open!",
+               coalesce(closes_at <= now(), false) as "closed!"
         from jig_player_session
-        where index=$1
+        where index=$1 and expires_at > now()
         "#,
         session_index.0
     )
@@ -144,15 +513,83 @@ pub async fn create_session_instance(
     .await?
     .ok_or(error::JigCode::ResourceNotFound)?;
 
+    if session_info.not_open {
+        return Err(error::JigCode::AssignmentNotOpen);
+    }
+
+    if session_info.closed {
+        return Err(error::JigCode::AssignmentClosed);
+    }
+
+    // students of sessions with a roster join under their nickname
+    let (student_id, display_name) = match (session_info.roster_id, student) {
+        (None, None) => (None, display_name.map(ToOwned::to_owned)),
+        (Some(roster_id), Some(student)) => {
+            let roster_student = sqlx::query!(
+                //language=SQL
+                r#"
+select nickname,
+       picture_password as "picture_password?: PicturePassword"
+from roster_student
+where id = $1
+  and roster_id = $2
+"#,
+                student.id.0,
+                roster_id.0,
+            )
+            .fetch_optional(&mut txn)
+            .await?
+            .ok_or(error::JigCode::InvalidStudent)?;
+
+            if roster_student.picture_password.is_some()
+                && roster_student.picture_password != student.picture_password
+            {
+                return Err(error::JigCode::IncorrectPicturePassword);
+            }
+
+            (Some(student.id), Some(roster_student.nickname))
+        }
+        _ => return Err(error::JigCode::InvalidStudent),
+    };
+
+    if let Some(max_attempts) = session_info.max_attempts {
+        // students are counted by their name on the roster, otherwise by device
+        let attempts = sqlx::query!(
+            //language=SQL
+            r#"
+select count(*) as "count!"
+from jig_player_session_instance
+where session_index = $1
+  and case
+          when $2::uuid is not null then student_id = $2
+          else ip_address = $3 and user_agent = $4
+    end
+"#,
+            session_index.0,
+            student_id.map(|id| id.0),
+            ip_address.0,
+            user_agent.0,
+        )
+        .fetch_one(&mut txn)
+        .await?
+        .count;
+
+        if attempts >= max_attempts as i64 {
+            return Err(error::JigCode::AttemptLimitReached);
+        }
+    }
+
     // insert into the jig_player_session_instance table returning the instance_id
     let instance_id = sqlx::query!(
         //language=SQL
         r#"
-        insert into jig_player_session_instance (session_index, ip_address, user_agent)
-        values ($1, $2, $3)
+        insert into jig_player_session_instance (session_index, display_name, student_id, ip_address, user_agent)
+        values ($1, $2, $3, $4, $5)
         returning id as "id: Uuid"
         "#,
         session_index.0,
+        display_name,
+        student_id.map(|id| id.0),
         ip_address.0,
         user_agent.0
     )
@@ -160,41 +597,57 @@ pub async fn create_session_instance(
     .await?
     .id;
 
+    let settings = JigPlayerSettings {
+        direction: session_info.direction,
+        display_score: session_info.display_score,
+        track_assessments: session_info.track_assessments,
+        drag_assist: session_info.drag_assist,
+    };
+
+    // the player is given the settings each JIG is played with
+    let items = list_items(&mut txn, &[session_index.0])
+        .await?
+        .remove(&session_index.0)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|item| JigPlayerSessionItem {
+            jig_id: item.jig_id,
+            settings: item.settings.or_else(|| Some(settings.clone())),
+        })
+        .collect();
+
     txn.commit().await?;
 
-    Ok((
-        session_info.jig_id,
-        JigPlayerSettings {
-            direction: session_info.direction,
-            display_score: session_info.display_score,
-            track_assessments: session_info.track_assessments,
-            drag_assist: session_info.drag_assist,
-        },
-        instance_id,
-    ))
+    Ok((session_info.jig_id, settings, items, instance_id))
 }
 
-/// Completes a jig player session for a player and updates play count
+/// Completes a jig player session instance for a player and updates the play count of its JIGs.
+///
+/// Completing an instance again only returns its results.
+///
+/// Also returns whether a webhook delivery was queued, see [`webhook::queue`].
 pub async fn complete_session_instance(
     db: &PgPool,
     ip_address: IPAddress,
     user_agent: UserAgent,
     instance_id: Uuid,
-) -> Result<(), error::JigCode> {
+) -> Result<(PlayerSessionInstanceResult, bool), error::JigCode> {
     let mut txn = db.begin().await?;
 
-    let resp = sqlx::query!(
+    let instance = sqlx::query!(
         //language=SQL
         r#"
-delete
+select ip_address,
+       user_agent,
+       completed_at,
+       session_index,
+       creator_id as "creator_id: UserId",
+       expires_at <= now()                  as "expired!"
 from jig_player_session_instance
+         inner join jig_player_session on jig_player_session.index = session_index
 where id = $1
-returning ip_address, user_agent, (
-    select jig_id
-    from jig_player_session_instance
-             join jig_player_session on session_index = index
-) as "jig_id!: JigId"
-        "#,
+for update of jig_player_session_instance
+"#,
         instance_id,
     )
     .fetch_optional(&mut txn)
@@ -202,23 +655,489 @@ returning ip_address, user_agent, (
     .ok_or(error::JigCode::ResourceNotFound)?;
 
     // FIXME
-    if (resp.user_agent).ne(&user_agent.0) | (resp.ip_address).ne(&ip_address.0) {
+    if (instance.user_agent).ne(&user_agent.0) | (instance.ip_address).ne(&ip_address.0) {
         return Err(error::JigCode::ResourceNotFound);
     }
 
-    sqlx::query!(
-        //language=SQL
-        r#"
+    if instance.expired {
+        return Err(error::JigCode::SessionExpired);
+    }
+
+    if instance.completed_at.is_none() {
+        sqlx::query!(
+            //language=SQL
+            r#"
+update jig_player_session_instance
+set completed_at = now()
+where id = $1
+"#,
+            instance_id,
+        )
+        .execute(&mut txn)
+        .await?;
+
+        sqlx::query!(
+            //language=SQL
+            r#"
         update jig_play_count
         set play_count = play_count + 1
-        where jig_id = $1
+        where jig_id in (select jig_id from jig_player_session_item where session_index = $1)
         "#,
-        resp.jig_id.0,
+            instance.session_index,
+        )
+        .execute(&mut txn)
+        .await?;
+    }
+
+    let result = get_instance_result(&mut txn, instance_id).await?;
+
+    let queued = if instance.completed_at.is_none() {
+        webhook::queue(
+            &mut txn,
+            WebhookEvent::PlayerSessionCompleted,
+            instance.creator_id,
+            json!({
+                "sessionIndex": JigPlayerSessionIndex(instance.session_index),
+                "result": result,
+            }),
+        )
+        .await?
+    } else {
+        false
+    };
+
+    txn.commit().await?;
+
+    Ok((result, queued))
+}
+
+/// Saves the outcome of a module played in a jig player session instance, replacing any earlier
+/// outcome of the same module.
+pub async fn report_points(
+    db: &PgPool,
+    ip_address: IPAddress,
+    user_agent: UserAgent,
+    instance_id: Uuid,
+    module: &PlayerSessionInstanceModuleResult,
+) -> Result<PlayerSessionInstanceResult, error::JigCode> {
+    let mut txn = db.begin().await?;
+
+    let instance = sqlx::query!(
+        //language=SQL
+        r#"
+select ip_address,
+       user_agent,
+       completed_at,
+       exists(select 1
+              from jig_player_session_item
+                       inner join jig on jig.id = jig_player_session_item.jig_id
+                       inner join jig_data_module on jig_data_module.jig_data_id = jig.live_id
+              where jig_player_session_item.session_index = jig_player_session_instance.session_index
+                and jig_data_module.id = $2)  as "valid_module!",
+       expires_at <= now()                  as "expired!"
+from jig_player_session_instance
+         inner join jig_player_session on jig_player_session.index = session_index
+where id = $1
+for update of jig_player_session_instance
+"#,
+        instance_id,
+        module.module_id.0,
     )
-    .execute(db)
+    .fetch_optional(&mut txn)
+    .await?
+    .ok_or(error::JigCode::ResourceNotFound)?;
+
+    // points can only be reported by the client which started the instance
+    if instance.user_agent != user_agent.0 || instance.ip_address != ip_address.0 {
+        return Err(error::JigCode::ResourceNotFound);
+    }
+
+    if instance.expired {
+        return Err(error::JigCode::SessionExpired);
+    }
+
+    if instance.completed_at.is_some() {
+        return Err(error::JigCode::InstanceCompleted);
+    }
+
+    if !instance.valid_module {
+        return Err(error::JigCode::InvalidModule);
+    }
+
+    sqlx::query!(
+        //language=SQL
+        r#"
+insert into jig_player_session_instance_module (instance_id, module_id, points, completed)
+values ($1, $2, $3, $4)
+on conflict (instance_id, module_id) do update
+    -- reports can arrive out of order, and points within a module never decrease
+    set points     = greatest(jig_player_session_instance_module.points, excluded.points),
+        completed  = jig_player_session_instance_module.completed or excluded.completed,
+        updated_at = now()
+"#,
+        instance_id,
+        module.module_id.0,
+        module.points.min(i32::MAX as u32) as i32,
+        module.completed,
+    )
+    .execute(&mut txn)
     .await?;
 
+    let result = get_instance_result(&mut txn, instance_id).await?;
+
     txn.commit().await?;
 
+    Ok(result)
+}
+
+async fn get_instance_result(
+    txn: &mut PgConnection,
+    instance_id: Uuid,
+) -> sqlx::Result<PlayerSessionInstanceResult> {
+    let instance = sqlx::query!(
+        //language=SQL
+        r#"
+select display_name,
+       student_id                           as "student_id?: RosterStudentId",
+       created_at,
+       completed_at
+from jig_player_session_instance
+where id = $1
+"#,
+        instance_id,
+    )
+    .fetch_one(&mut *txn)
+    .await?;
+
+    let modules: Vec<_> = sqlx::query!(
+        //language=SQL
+        r#"
+select module_id                            as "module_id!: ModuleId",
+       points,
+       completed
+from jig_player_session_instance_module
+where instance_id = $1
+order by created_at
+"#,
+        instance_id,
+    )
+    .fetch_all(&mut *txn)
+    .await?
+    .into_iter()
+    .map(|row| PlayerSessionInstanceModuleResult {
+        module_id: row.module_id,
+        points: row.points as u32,
+        completed: row.completed,
+    })
+    .collect();
+
+    Ok(PlayerSessionInstanceResult {
+        id: PlayerSessionInstanceId(instance_id),
+        display_name: instance.display_name,
+        student_id: instance.student_id,
+        points: modules.iter().map(|module| module.points).sum(),
+        modules,
+        created_at: instance.created_at,
+        completed_at: instance.completed_at,
+    })
+}
+
+/// Extends a jig player session which hasn't expired yet, returning when it now expires.
+pub async fn extend(
+    db: &PgPool,
+    index: JigPlayerSessionIndex,
+    duration: Duration,
+) -> Result<DateTime<Utc>, error::JigCode> {
+    let max_expires_at =
+        Utc::now() + Duration::seconds(JIG_PLAYER_SESSION_VALID_DURATION_SECS as i64);
+
+    let expires_at = sqlx::query!(
+        //language=SQL
+        r#"
+update jig_player_session
+set expires_at = greatest(expires_at, least(expires_at + make_interval(secs => $2), $3))
+where index = $1
+  and expires_at > now()
+returning expires_at
+"#,
+        index.0,
+        duration.num_seconds() as f64,
+        max_expires_at,
+    )
+    .fetch_optional(db)
+    .await?
+    .ok_or(error::JigCode::ResourceNotFound)?
+    .expires_at;
+
+    Ok(expires_at)
+}
+
+/// Expires a jig player session now. Its instances can no longer report points or be completed.
+pub async fn revoke(db: &PgPool, index: JigPlayerSessionIndex) -> Result<(), error::JigCode> {
+    let revoked = sqlx::query!(
+        //language=SQL
+        r#"
+update jig_player_session
+set expires_at = now()
+where index = $1
+  and expires_at > now()
+"#,
+        index.0,
+    )
+    .execute(db)
+    .await?
+    .rows_affected();
+
+    if revoked == 0 {
+        return Err(error::JigCode::ResourceNotFound);
+    }
+
     Ok(())
 }
+
+/// Deletes the jig player sessions which have expired, along with their instances.
+pub async fn delete_expired(db: &PgPool) -> sqlx::Result<u64> {
+    let deleted = sqlx::query!(
+        //language=SQL
+        r#"
+delete from jig_player_session where expires_at <= now()
+"#,
+    )
+    .execute(db)
+    .await?
+    .rows_affected();
+
+    Ok(deleted)
+}
+
+/// Gets the creator of a jig player session, if it exists.
+pub async fn get_session_creator(
+    db: &PgPool,
+    index: JigPlayerSessionIndex,
+) -> Result<Option<UserId>, error::JigCode> {
+    let session = sqlx::query!(
+        //language=SQL
+        r#"
+select creator_id as "creator_id?: UserId"
+from jig_player_session
+where index = $1
+"#,
+        index.0,
+    )
+    .fetch_optional(db)
+    .await?
+    .ok_or(error::JigCode::ResourceNotFound)?;
+
+    Ok(session.creator_id)
+}
+
+/// Lists the results of every instance of a jig player session, oldest first.
+pub async fn list_instance_results(
+    db: &PgPool,
+    index: JigPlayerSessionIndex,
+) -> sqlx::Result<Vec<PlayerSessionInstanceResult>> {
+    let instances = sqlx::query!(
+        //language=SQL
+        r#"
+select id                                   as "id!: PlayerSessionInstanceId",
+       display_name,
+       student_id                           as "student_id?: RosterStudentId",
+       created_at,
+       completed_at
+from jig_player_session_instance
+where session_index = $1
+order by created_at
+"#,
+        index.0,
+    )
+    .fetch_all(db)
+    .await?;
+
+    let mut modules: HashMap<PlayerSessionInstanceId, Vec<PlayerSessionInstanceModuleResult>> =
+        HashMap::new();
+
+    let module_rows = sqlx::query!(
+        //language=SQL
+        r#"
+select instance_id                          as "instance_id!: PlayerSessionInstanceId",
+       module_id                            as "module_id!: ModuleId",
+       points,
+       completed
+from jig_player_session_instance_module "jpsim"
+         inner join jig_player_session_instance "jpsi" on jpsi.id = jpsim.instance_id
+where jpsi.session_index = $1
+order by jpsim.created_at
+"#,
+        index.0,
+    )
+    .fetch_all(db)
+    .await?;
+
+    for row in module_rows {
+        modules
+            .entry(row.instance_id)
+            .or_default()
+            .push(PlayerSessionInstanceModuleResult {
+                module_id: row.module_id,
+                points: row.points as u32,
+                completed: row.completed,
+            });
+    }
+
+    let results = instances
+        .into_iter()
+        .map(|instance| {
+            let modules = modules.remove(&instance.id).unwrap_or_default();
+
+            PlayerSessionInstanceResult {
+                id: instance.id,
+                display_name: instance.display_name,
+                student_id: instance.student_id,
+                points: modules.iter().map(|module| module.points).sum(),
+                modules,
+                created_at: instance.created_at,
+                completed_at: instance.completed_at,
+            }
+        })
+        .collect();
+
+    Ok(results)
+}
+
+/// Ranks the instances of a jig player session, returning nothing if none changed after `since`.
+///
+/// Returns the entries, the total number of instances and the cursor for the next request.
+pub async fn leaderboard(
+    db: &PgPool,
+    index: JigPlayerSessionIndex,
+    since: Option<DateTime<Utc>>,
+) -> sqlx::Result<(Vec<JigPlayerSessionLeaderboardEntry>, u32, DateTime<Utc>)> {
+    let mut txn = db.begin().await?;
+
+    // Changes committed by transactions which started before this one can carry an earlier
+    // timestamp than the cursor, so consecutive polls overlap slightly.
+    let cursor = sqlx::query!(
+        //language=SQL
+        r#"
+select now() - interval '5 seconds' as "cursor!"
+"#,
+    )
+    .fetch_one(&mut txn)
+    .await?
+    .cursor;
+
+    let total_count = sqlx::query!(
+        //language=SQL
+        r#"
+select count(*) as "count!"
+from jig_player_session_instance
+where session_index = $1
+"#,
+        index.0,
+    )
+    .fetch_one(&mut txn)
+    .await?
+    .count;
+
+    let entries = sqlx::query!(
+        //language=SQL
+        r#"
+with stats as (
+    select jpsi.id,
+           jpsi.display_name,
+           jpsi.created_at,
+           jpsi.completed_at,
+           coalesce(sum(jpsim.points), 0)                                                 as points,
+           greatest(jpsi.created_at, jpsi.completed_at, max(jpsim.created_at), max(jpsim.updated_at)) as updated_at
+    from jig_player_session_instance "jpsi"
+             left join jig_player_session_instance_module "jpsim" on jpsim.instance_id = jpsi.id
+    where jpsi.session_index = $1
+    group by jpsi.id
+),
+     ranked as (
+         select *,
+                rank() over (order by points desc, completed_at - created_at nulls last) as rank
+         from stats
+     )
+select id                                   as "id!: PlayerSessionInstanceId",
+       display_name,
+       points                               as "points!",
+       completed_at,
+       updated_at                           as "updated_at!",
+       rank                                 as "rank!"
+from ranked
+-- a change to one entry can change the rank of any other, so the whole ranking is returned
+where $2::timestamptz is null
+   or exists(select 1 from stats where updated_at > $2)
+order by rank, created_at
+"#,
+        index.0,
+        since,
+    )
+    .fetch_all(&mut txn)
+    .await?
+    .into_iter()
+    .map(|row| JigPlayerSessionLeaderboardEntry {
+        rank: row.rank as u32,
+        instance_id: row.id,
+        display_name: row.display_name,
+        points: row.points as u32,
+        completed_at: row.completed_at,
+        updated_at: row.updated_at,
+    })
+    .collect();
+
+    txn.commit().await?;
+
+    Ok((entries, total_count as u32, cursor))
+}
+
+/// Lists the students who can join a jig player session, or `None` if the session has no roster.
+pub async fn list_roster_students(
+    db: &PgPool,
+    index: JigPlayerSessionIndex,
+) -> Result<Option<Vec<PlayerSessionInstanceRosterStudent>>, error::JigCode> {
+    let roster_id = sqlx::query!(
+        //language=SQL
+        r#"
+select roster_id as "roster_id?: RosterId"
+from jig_player_session
+where index = $1
+  and expires_at > now()
+"#,
+        index.0,
+    )
+    .fetch_optional(db)
+    .await?
+    .ok_or(error::JigCode::ResourceNotFound)?
+    .roster_id;
+
+    let roster_id = match roster_id {
+        Some(roster_id) => roster_id,
+        None => return Ok(None),
+    };
+
+    let students = sqlx::query!(
+        //language=SQL
+        r#"
+select id                                   as "id!: RosterStudentId",
+       nickname,
+       picture_password is not null         as "has_picture_password!"
+from roster_student
+where roster_id = $1
+order by lower(nickname)
+"#,
+        roster_id.0,
+    )
+    .fetch_all(db)
+    .await?
+    .into_iter()
+    .map(|row| PlayerSessionInstanceRosterStudent {
+        id: row.id,
+        nickname: row.nickname,
+        has_picture_password: row.has_picture_password,
+    })
+    .collect();
+
+    Ok(Some(students))
+}
@@ -1,7 +1,11 @@
 use crate::error;
-use shared::domain::jig::{
-    report::{JigReport, JigReportEmail, JigReportType, ReportId},
-    JigId,
+use shared::domain::{
+    asset::{ReportAction, ReportStatus},
+    jig::{
+        report::{JigReport, JigReportEmail, JigReportType, ReportId},
+        JigId,
+    },
+    user::{UserId, UserScope},
 };
 use sqlx::{PgConnection, PgPool};
 use uuid::Uuid;
@@ -68,7 +72,12 @@ select id                                   as "id!: ReportId",
             select email::text
             from user_email
             where user_email.user_id = reporter_id
-        )                                       as "email?"
+        )                                       as "email?",
+       status                               as "status!: ReportStatus",
+       assignee_id                          as "assignee_id?: UserId",
+       action                               as "action?: ReportAction",
+       resolution_note,
+       resolved_at
 from jig_report
 where id = $1 and jig_id = $2
 "#,
@@ -85,6 +94,11 @@ where id = $1 and jig_id = $2
         reporter_name: row.name,
         reporter_email: row.email,
         created_at: row.created_at,
+        status: row.status,
+        assignee_id: row.assignee_id,
+        action: row.action,
+        resolution_note: row.resolution_note,
+        resolved_at: row.resolved_at,
     });
 
     Ok(report)
@@ -156,3 +170,261 @@ select exists (
 
     Ok(())
 }
+
+pub async fn list_reports(
+    pool: &PgPool,
+    report_type: Option<JigReportType>,
+    status: Option<ReportStatus>,
+    assignee_id: Option<UserId>,
+    page: i32,
+    page_limit: u32,
+) -> Result<Vec<JigReport>, error::ReportError> {
+    let reports = sqlx::query!(
+        //language=SQL
+        r#"
+select id                                   as "id!: ReportId",
+       jig_id                               as "jig_id!: JigId",
+       report_type                          as "report_type!: JigReportType",
+       created_at,
+       reporter_id                          as "reporter_id?: Uuid",
+       (
+            select given_name || ' '::text || family_name
+            from user_profile
+            where user_profile.user_id = reporter_id
+        )                                       as "name?",
+        (
+            select email::text
+            from user_email
+            where user_email.user_id = reporter_id
+        )                                       as "email?",
+       status                               as "status!: ReportStatus",
+       assignee_id                          as "assignee_id?: UserId",
+       action                               as "action?: ReportAction",
+       resolution_note,
+       resolved_at
+from jig_report
+where (report_type = $1 or $1 is null)
+  and (status = $2 or $2 is null)
+  and (assignee_id = $3 or $3 is null)
+order by created_at asc
+offset $4
+limit $5
+"#,
+        report_type.map(|it| it as i16),
+        status.map(|it| it as i16),
+        assignee_id.map(|it| it.0),
+        (page * page_limit as i32) as i64,
+        page_limit as i64,
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|row| JigReport {
+        id: row.id,
+        jig_id: row.jig_id,
+        report_type: row.report_type,
+        reporter_id: row.reporter_id,
+        reporter_name: row.name,
+        reporter_email: row.email,
+        created_at: row.created_at,
+        status: row.status,
+        assignee_id: row.assignee_id,
+        action: row.action,
+        resolution_note: row.resolution_note,
+        resolved_at: row.resolved_at,
+    })
+    .collect();
+
+    Ok(reports)
+}
+
+pub async fn filtered_count(
+    pool: &PgPool,
+    report_type: Option<JigReportType>,
+    status: Option<ReportStatus>,
+    assignee_id: Option<UserId>,
+) -> Result<u64, error::ReportError> {
+    let count = sqlx::query!(
+        //language=SQL
+        r#"
+select count(*) as "count!"
+from jig_report
+where (report_type = $1 or $1 is null)
+  and (status = $2 or $2 is null)
+  and (assignee_id = $3 or $3 is null)
+"#,
+        report_type.map(|it| it as i16),
+        status.map(|it| it as i16),
+        assignee_id.map(|it| it.0),
+    )
+    .fetch_one(pool)
+    .await?
+    .count;
+
+    Ok(count as u64)
+}
+
+/// Assign a report to a curator, or unassign it. Returns `false` if the report is already resolved.
+pub async fn assign_report(
+    pool: &PgPool,
+    jig_id: JigId,
+    report_id: ReportId,
+    assignee_id: Option<UserId>,
+) -> Result<bool, error::ReportError> {
+    let res = sqlx::query!(
+        //language=SQL
+        r#"
+update jig_report
+set assignee_id = $3,
+    status = case when $3::uuid is null then $4::int2 else $5::int2 end,
+    updated_at = now()
+where id = $1 and jig_id = $2 and status <> $6
+"#,
+        report_id.0,
+        jig_id.0,
+        assignee_id.map(|it| it.0),
+        ReportStatus::Pending as i16,
+        ReportStatus::InReview as i16,
+        ReportStatus::Resolved as i16,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(res.rows_affected() > 0)
+}
+
+/// Mark a report as resolved and, when the action is [`ReportAction::Block`], block the jig in the
+/// same transaction. Returns `false` if the report is already resolved.
+pub async fn resolve_report(
+    pool: &PgPool,
+    jig_id: JigId,
+    report_id: ReportId,
+    action: ReportAction,
+    note: Option<&str>,
+    resolved_by: UserId,
+) -> Result<bool, error::ReportError> {
+    let mut txn = pool.begin().await?;
+
+    let res = sqlx::query!(
+        //language=SQL
+        r#"
+update jig_report
+set status = $3,
+    action = $4,
+    resolution_note = $5,
+    resolved_by = $6,
+    resolved_at = now(),
+    updated_at = now()
+where id = $1 and jig_id = $2 and status <> $3
+"#,
+        report_id.0,
+        jig_id.0,
+        ReportStatus::Resolved as i16,
+        action as i16,
+        note,
+        resolved_by.0,
+    )
+    .execute(&mut txn)
+    .await?;
+
+    if res.rows_affected() == 0 {
+        return Ok(false);
+    }
+
+    if action == ReportAction::Block {
+        sqlx::query!(
+            //language=SQL
+            r#"
+update jig_admin_data
+set blocked = true
+where jig_id = $1
+"#,
+            jig_id.0,
+        )
+        .execute(&mut txn)
+        .await?;
+
+        sqlx::query!(
+            //language=SQL
+            r#"
+update jig_data
+set updated_at = now()
+from jig
+where jig.id = $1 and jig_data.id = jig.live_id
+"#,
+            jig_id.0,
+        )
+        .execute(&mut txn)
+        .await?;
+    }
+
+    txn.commit().await?;
+
+    Ok(true)
+}
+
+/// Whether the user is able to curate reports, i.e. has the `Admin` or `AdminAsset` scope.
+pub async fn is_curator(pool: &PgPool, user_id: UserId) -> Result<bool, error::ReportError> {
+    let authed = sqlx::query!(
+        //language=SQL
+        r#"
+select exists(select 1 from user_scope where user_id = $1 and scope = any($2)) as "authed!"
+"#,
+        user_id.0,
+        &[UserScope::Admin as i16, UserScope::AdminAsset as i16][..],
+    )
+    .fetch_one(pool)
+    .await?
+    .authed;
+
+    Ok(authed)
+}
+
+/// Contact details of the reporter and the author of the reported jig.
+pub struct ReportContacts {
+    pub display_name: String,
+    pub reporter_id: Option<Uuid>,
+    pub reporter_email: Option<String>,
+    pub author_email: Option<String>,
+    pub author_name: Option<String>,
+}
+
+pub async fn get_report_contacts(
+    pool: &PgPool,
+    jig_id: JigId,
+    report_id: ReportId,
+) -> Result<Option<ReportContacts>, error::ReportError> {
+    let contacts = sqlx::query_as!(
+        ReportContacts,
+        //language=SQL
+        r#"
+select jig_data.display_name                     as "display_name!",
+       reporter_id                               as "reporter_id?",
+       (
+            select email::text
+            from user_email
+            where user_email.user_id = reporter_id
+        )                                       as "reporter_email?",
+       (
+            select email::text
+            from user_email
+            where user_email.user_id = jig.creator_id
+        )                                       as "author_email?",
+       (
+            select given_name
+            from user_profile
+            where user_profile.user_id = jig.creator_id
+        )                                       as "author_name?"
+from jig_report
+    inner join jig on jig.id = jig_report.jig_id
+    inner join jig_data on jig_data.id = jig.live_id
+where jig_report.id = $1 and jig_report.jig_id = $2
+"#,
+        report_id.0,
+        jig_id.0
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(contacts)
+}
@@ -0,0 +1,186 @@
+use shared::domain::{
+    notification::{Notification, NotificationId, NotificationKind, NotificationPreferences},
+    user::UserId,
+};
+use sqlx::{Executor, PgPool, Postgres};
+use tracing::instrument;
+use uuid::Uuid;
+
+/// Notifies each of `user_ids`, unless they've disabled notifications of `kind`.
+///
+/// Users who already have a notification which can't be repeated (e.g. an invite to the same circle)
+/// aren't notified again.
+#[instrument(skip(executor, data))]
+pub async fn create<'c, E: Executor<'c, Database = Postgres>>(
+    executor: E,
+    user_ids: &[Uuid],
+    kind: NotificationKind,
+    data: &serde_json::Value,
+) -> sqlx::Result<()> {
+    sqlx::query!(
+        //language=SQL
+        r#"
+insert into notification (user_id, kind, data)
+select user_id, $2, $3
+from unnest($1::uuid[]) as recipient(user_id)
+         left join notification_preference using (user_id)
+where case $2::int2
+          when 0 then coalesce(notification_preference.new_follower, true)
+          when 1 then coalesce(notification_preference.asset_liked, true)
+          when 2 then coalesce(notification_preference.curation_comment, true)
+          when 3 then coalesce(notification_preference.report_resolved, true)
+          when 4 then coalesce(notification_preference.circle_invite, true)
+          when 5 then coalesce(notification_preference.subscription_changed, true)
+          -- fails the insert, so that a new kind can't be added without its preference
+          else cast('unknown notification kind ' || $2::int2 as bool)
+          end
+on conflict do nothing
+"#,
+        user_ids,
+        kind as i16,
+        data,
+    )
+    .execute(executor)
+    .await?;
+
+    Ok(())
+}
+
+#[instrument(skip(db))]
+pub async fn list(
+    db: &PgPool,
+    user_id: UserId,
+    unread_only: bool,
+    page: u32,
+    page_limit: u32,
+) -> sqlx::Result<Vec<Notification>> {
+    sqlx::query_as!(
+        Notification,
+        //language=SQL
+        r#"
+select id   as "id: NotificationId",
+       kind as "kind: NotificationKind",
+       data,
+       created_at,
+       read_at
+from notification
+where user_id = $1
+  and (read_at is null or not $2)
+order by created_at desc
+offset $3 limit $4
+"#,
+        user_id.0,
+        unread_only,
+        page as i64 * page_limit as i64,
+        page_limit as i64,
+    )
+    .fetch_all(db)
+    .await
+}
+
+#[instrument(skip(db))]
+pub async fn count(db: &PgPool, user_id: UserId, unread_only: bool) -> sqlx::Result<u64> {
+    sqlx::query!(
+        //language=SQL
+        r#"
+select count(*) as "count!"
+from notification
+where user_id = $1
+  and (read_at is null or not $2)
+"#,
+        user_id.0,
+        unread_only,
+    )
+    .fetch_one(db)
+    .await
+    .map(|it| it.count as u64)
+}
+
+/// Marks the user's notifications as read, or all of them if `ids` is `None`.
+#[instrument(skip(db))]
+pub async fn mark_read(
+    db: &PgPool,
+    user_id: UserId,
+    ids: Option<&[NotificationId]>,
+) -> sqlx::Result<()> {
+    let ids: Option<Vec<Uuid>> = ids.map(|ids| ids.iter().map(|id| id.0).collect());
+
+    sqlx::query!(
+        //language=SQL
+        r#"
+update notification
+set read_at = now()
+where user_id = $1
+  and read_at is null
+  and (id = any ($2) or $2 is null)
+"#,
+        user_id.0,
+        ids.as_deref(),
+    )
+    .execute(db)
+    .await?;
+
+    Ok(())
+}
+
+#[instrument(skip(db))]
+pub async fn get_preferences(
+    db: &PgPool,
+    user_id: UserId,
+) -> sqlx::Result<NotificationPreferences> {
+    let preferences = sqlx::query_as!(
+        NotificationPreferences,
+        //language=SQL
+        r#"
+select new_follower,
+       asset_liked,
+       curation_comment,
+       report_resolved,
+       circle_invite,
+       subscription_changed
+from notification_preference
+where user_id = $1
+"#,
+        user_id.0,
+    )
+    .fetch_optional(db)
+    .await?;
+
+    Ok(preferences.unwrap_or_default())
+}
+
+#[instrument(skip(db))]
+pub async fn upsert_preferences(
+    db: &PgPool,
+    user_id: UserId,
+    preferences: NotificationPreferences,
+) -> sqlx::Result<()> {
+    sqlx::query!(
+        //language=SQL
+        r#"
+insert into notification_preference
+    (user_id, new_follower, asset_liked, curation_comment, report_resolved, circle_invite,
+     subscription_changed)
+values ($1, $2, $3, $4, $5, $6, $7)
+on conflict (user_id) do update
+    set new_follower         = $2,
+        asset_liked          = $3,
+        curation_comment     = $4,
+        report_resolved      = $5,
+        circle_invite        = $6,
+        subscription_changed = $7,
+        updated_at           = now()
+"#,
+        user_id.0,
+        preferences.new_follower,
+        preferences.asset_liked,
+        preferences.curation_comment,
+        preferences.report_resolved,
+        preferences.circle_invite,
+        preferences.subscription_changed,
+    )
+    .execute(db)
+    .await?;
+
+    Ok(())
+}
@@ -1,13 +1,14 @@
 use crate::translate::translate_text;
 use anyhow::Context;
-use serde_json::value::Value;
+use serde_json::{json, value::Value};
 use shared::domain::{
     additional_resource::{AdditionalResource, AdditionalResourceId as AddId, ResourceContent},
-    asset::{DraftOrLive, PrivacyLevel},
+    asset::{AssetId, DraftOrLive, PrivacyLevel},
     category::CategoryId,
     jig::JigId,
     meta::{AffiliationId, AgeRangeId, ResourceTypeId as TypeId},
     module::{LiteModule, ModuleId, ModuleKind},
+    notification::NotificationKind,
     playlist::{PlaylistData, PlaylistId, PlaylistResponse},
     user::{UserId, UserScope},
 };
@@ -19,6 +20,7 @@ use uuid::Uuid;
 use crate::error;
 
 pub(crate) mod additional_resource;
+pub(crate) mod curation;
 pub(crate) mod module;
 
 pub async fn create(
@@ -168,6 +170,8 @@ select cte.playlist_id                                          as "playlist_id:
        other_keywords,
        translated_keywords,
        exists(select 1 from playlist_like where playlist_id = $1 and user_id = $3) as "is_liked!",
+       (select avg(rating)::real from asset_review where not asset_review.hidden and asset_review.playlist_id = cte.playlist_id) as "average_rating",
+       (select count(*) from asset_review where not asset_review.hidden and asset_review.playlist_id = cte.playlist_id)       as "review_count!",
        (
             select row(playlist_data_module.id, kind, is_complete)
             from playlist_data_module
@@ -211,6 +215,8 @@ from playlist_data
         author_name: row.author_name,
         likes: row.likes,
         plays: row.plays,
+        average_rating: row.average_rating,
+        review_count: row.review_count,
         live_up_to_date: row.live_up_to_date,
         is_liked: row.is_liked,
         playlist_data: PlaylistData {
@@ -276,7 +282,9 @@ select playlist.id                                       as "id!: PlaylistId",
        likes                                    as "likes!",
        plays                                    as "plays!",
        live_up_to_date                          as "live_up_to_date!",
-       exists(select 1 from playlist_like where playlist_id = playlist.id and user_id = $2) as "is_liked!"
+       exists(select 1 from playlist_like where playlist_id = playlist.id and user_id = $2) as "is_liked!",
+       (select avg(rating)::real from asset_review where not asset_review.hidden and asset_review.playlist_id = playlist.id) as "average_rating",
+       (select count(*) from asset_review where not asset_review.hidden and asset_review.playlist_id = playlist.id) as "review_count!"
 from playlist
 inner join unnest($1::uuid[])
     with ordinality t(id, ord) using (id)
@@ -352,6 +360,8 @@ order by ord asc
             author_name: playlist_row.author_name,
             likes: playlist_row.likes,
             plays: playlist_row.plays,
+            average_rating: playlist_row.average_rating,
+            review_count: playlist_row.review_count,
             live_up_to_date: playlist_row.live_up_to_date,
             is_liked: playlist_row.is_liked,
             playlist_data: PlaylistData {
@@ -461,6 +471,8 @@ select playlist.id
     plays,
     live_up_to_date,
     exists(select 1 from playlist_like where playlist_id = playlist.id and user_id = $7)    as "is_liked!",
+    (select avg(rating)::real from asset_review where not asset_review.hidden and asset_review.playlist_id = playlist.id) as "average_rating",
+    (select count(*) from asset_review where not asset_review.hidden and asset_review.playlist_id = playlist.id) as "review_count!",
     display_name                                                                  as "display_name!",
     updated_at,
     language                                                                      as "language!",
@@ -530,6 +542,8 @@ limit $6
             author_name: playlist_data_row.author_name,
             likes: playlist_data_row.likes,
             plays: playlist_data_row.plays,
+            average_rating: playlist_data_row.average_rating,
+            review_count: playlist_data_row.review_count,
             live_up_to_date: playlist_data_row.live_up_to_date,
             is_liked: playlist_data_row.is_liked,
             playlist_data: PlaylistData {
@@ -1148,6 +1162,16 @@ values ($1, $2)
     .await
     .map_err(|_| anyhow::anyhow!("Cannot like a playlist more than once"))?;
 
+    if let Some(author_id) = playlist.author_id {
+        super::notification::create(
+            &mut txn,
+            &[author_id.0],
+            NotificationKind::AssetLiked,
+            &json!({ "assetId": AssetId::PlaylistId(id), "likedBy": user_id }),
+        )
+        .await?;
+    }
+
     txn.commit().await?;
 
     Ok(())
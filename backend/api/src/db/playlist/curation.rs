@@ -0,0 +1,255 @@
+use shared::domain::{
+    playlist::{
+        curation::{
+            CommentId, PlaylistCurationComment, PlaylistCurationCommentResponse,
+            PlaylistCurationData, PlaylistCurationFieldsDone, PlaylistCurationStatus,
+        },
+        PlaylistId,
+    },
+    user::UserId,
+};
+use sqlx::{PgConnection, PgPool};
+
+use crate::error;
+
+pub async fn update(
+    pool: &PgPool,
+    playlist_id: PlaylistId,
+    display_name: Option<bool>,
+    language: Option<bool>,
+    categories: Option<bool>,
+    description: Option<bool>,
+    age_ranges: Option<bool>,
+    affiliations: Option<bool>,
+    additional_resources: Option<bool>,
+    curation_status: Option<PlaylistCurationStatus>,
+    assignee_id: Option<Option<UserId>>,
+) -> anyhow::Result<(), error::Curation> {
+    let mut txn = pool.begin().await?;
+
+    let current_status = sqlx::query!(
+        //language=SQL
+        r#"
+select curation_status as "curation_status!: PlaylistCurationStatus"
+from playlist_curation_data
+where playlist_id = $1
+for update
+"#,
+        playlist_id.0,
+    )
+    .fetch_optional(&mut txn)
+    .await?
+    .ok_or(error::Curation::ResourceNotFound)?
+    .curation_status;
+
+    if let Some(curation_status) = curation_status {
+        if !current_status.can_transition_to(curation_status) {
+            return Err(error::Curation::InvalidTransition(
+                current_status,
+                curation_status,
+            ));
+        }
+    }
+
+    sqlx::query!(
+        //language=SQL
+        r#"
+update playlist_curation_data
+set display_name         = coalesce($2, display_name),
+    language             = coalesce($3, language),
+    categories           = coalesce($4, categories),
+    description          = coalesce($5, description),
+    age_ranges           = coalesce($6, age_ranges),
+    affiliations         = coalesce($7, affiliations),
+    additional_resources = coalesce($8, additional_resources),
+    updated_at           = now()
+where playlist_id = $1
+"#,
+        playlist_id.0,
+        display_name,
+        language,
+        categories,
+        description,
+        age_ranges,
+        affiliations,
+        additional_resources,
+    )
+    .execute(&mut txn)
+    .await?;
+
+    if let Some(curation_status) = curation_status {
+        sqlx::query!(
+            //language=SQL
+            r#"
+update playlist_curation_data
+set curation_status   = $2,
+    status_updated_at = now(),
+    completed_at      = case when $2 = $3 then now() else completed_at end
+where playlist_id = $1 and $2 is distinct from curation_status
+"#,
+            playlist_id.0,
+            curation_status as i16,
+            PlaylistCurationStatus::Done as i16,
+        )
+        .execute(&mut txn)
+        .await?;
+    }
+
+    if let Some(assignee_id) = assignee_id {
+        sqlx::query!(
+            //language=SQL
+            r#"
+update playlist_curation_data
+set assignee_id = $2,
+    assigned_at = case when $2::uuid is null then null else now() end
+where playlist_id = $1 and $2 is distinct from assignee_id
+"#,
+            playlist_id.0,
+            assignee_id.map(|it| it.0),
+        )
+        .execute(&mut txn)
+        .await?;
+    }
+
+    txn.commit().await?;
+
+    Ok(())
+}
+
+pub async fn get_curation(
+    pool: &PgPool,
+    playlist_id: PlaylistId,
+) -> anyhow::Result<Option<PlaylistCurationData>> {
+    let curation = sqlx::query!(
+        //language=SQL
+        r#"
+select playlist_id                             as "playlist_id!: PlaylistId",
+       display_name,
+       language,
+       categories,
+       description,
+       age_ranges,
+       affiliations,
+       additional_resources,
+       curation_status                    as "curation_status!: PlaylistCurationStatus",
+       assignee_id                        as "assignee_id?: UserId",
+       created_at,
+       status_updated_at,
+       assigned_at,
+       completed_at,
+       array(
+            select row (cc.id, cc.playlist_id, comment, created_at, author_id)
+            from playlist_curation_comment "cc"
+            where cd.playlist_id = cc.playlist_id
+            order by created_at desc
+       )                                  as "comments!: Vec<(PlaylistCurationComment)>"
+from playlist_curation_data "cd"
+where playlist_id = $1
+"#,
+        playlist_id.0,
+    )
+    .fetch_optional(pool)
+    .await?
+    .map(|row| PlaylistCurationData {
+        playlist_id: row.playlist_id,
+        curation_status: row.curation_status,
+        assignee_id: row.assignee_id,
+        created_at: row.created_at,
+        status_updated_at: row.status_updated_at,
+        assigned_at: row.assigned_at,
+        completed_at: row.completed_at,
+        fields_done: PlaylistCurationFieldsDone {
+            display_name: row.display_name,
+            language: row.language,
+            categories: row.categories,
+            description: row.description,
+            age_ranges: row.age_ranges,
+            affiliations: row.affiliations,
+            additional_resources: row.additional_resources,
+        },
+        comments: row.comments,
+    });
+
+    Ok(curation)
+}
+
+pub async fn create_comment(
+    pool: &PgPool,
+    playlist_id: PlaylistId,
+    value: String,
+    author_id: UserId,
+) -> anyhow::Result<CommentId> {
+    sqlx::query!(
+        //language=SQL
+        r#"
+insert into playlist_curation_comment (playlist_id, comment, author_id)
+values ($1, $2, $3)
+returning id as "id!: CommentId"
+        "#,
+        playlist_id.0,
+        value,
+        author_id.0
+    )
+    .fetch_one(pool)
+    .await
+    .map(|it| it.id)
+    .map_err(Into::into)
+}
+
+pub async fn get_comment(
+    pool: &PgPool,
+    playlist_id: PlaylistId,
+    comment_id: CommentId,
+) -> anyhow::Result<Option<PlaylistCurationCommentResponse>> {
+    let comment = sqlx::query!(
+        //language=SQL
+        r#"
+select id                                   as "id!: CommentId",
+       playlist_id                               as "playlist_id!: PlaylistId",
+       comment,
+       created_at,
+       author_id                            as "author_id!: UserId",
+       (
+            select given_name || ' '::text || family_name
+            from user_profile
+            where user_profile.user_id = author_id
+        )                                   as "author_name!"
+from playlist_curation_comment
+where id = $1 and playlist_id = $2
+"#,
+        comment_id.0,
+        playlist_id.0
+    )
+    .fetch_optional(pool)
+    .await?
+    .map(|row| PlaylistCurationCommentResponse {
+        id: row.id,
+        playlist_id: row.playlist_id,
+        value: row.comment,
+        created_at: Some(row.created_at),
+        author_id: row.author_id,
+        author_name: row.author_name,
+    });
+
+    Ok(comment)
+}
+
+/// Moves a curated Playlist back into the curation queue after it has been republished.
+pub async fn mark_new_version(txn: &mut PgConnection, playlist_id: PlaylistId) -> sqlx::Result<()> {
+    sqlx::query!(
+        //language=SQL
+        r#"
+update playlist_curation_data
+set curation_status   = $2,
+    status_updated_at = now()
+where playlist_id = $1 and curation_status = $3
+"#,
+        playlist_id.0,
+        PlaylistCurationStatus::NewVersion as i16,
+        PlaylistCurationStatus::Done as i16,
+    )
+    .execute(txn)
+    .await?;
+
+    Ok(())
+}
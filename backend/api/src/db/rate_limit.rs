@@ -0,0 +1,61 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use tracing::instrument;
+
+/// Takes a token from the bucket, refilling it with `refill_per_second` tokens per second since
+/// it was last updated, up to `capacity`. Missing buckets are full.
+///
+/// Returns the tokens left, or `None` if the bucket is empty, in which case it's left unchanged.
+///
+/// The refill and the decrement are a single statement, so that concurrent requests (possibly on
+/// other instances) can't both take the last token, not even from a new bucket.
+#[instrument(skip(db))]
+pub async fn take_token(
+    db: &PgPool,
+    key: &str,
+    capacity: f64,
+    refill_per_second: f64,
+    now: DateTime<Utc>,
+) -> sqlx::Result<Option<f64>> {
+    sqlx::query_scalar!(
+        r#"
+insert into rate_limit_bucket (key, tokens, updated_at)
+values ($1, $2 - 1, $4)
+on conflict (key) do update
+set tokens     = least($2, rate_limit_bucket.tokens + greatest(extract(epoch from $4 - rate_limit_bucket.updated_at)::float8, 0) * $3) - 1,
+    updated_at = $4
+where least($2, rate_limit_bucket.tokens + greatest(extract(epoch from $4 - rate_limit_bucket.updated_at)::float8, 0) * $3) >= 1
+returning tokens
+"#,
+        key,
+        capacity,
+        refill_per_second,
+        now
+    )
+    .fetch_optional(db)
+    .await
+}
+
+/// The bucket's tokens and when they were last updated.
+pub async fn get_bucket(db: &PgPool, key: &str) -> sqlx::Result<Option<(f64, DateTime<Utc>)>> {
+    let bucket = sqlx::query!(
+        r#"select tokens, updated_at from rate_limit_bucket where key = $1"#,
+        key
+    )
+    .fetch_optional(db)
+    .await?
+    .map(|it| (it.tokens, it.updated_at));
+
+    Ok(bucket)
+}
+
+/// Deletes buckets which haven't been used for a day, which are full again for every rule.
+pub async fn delete_stale(db: &PgPool) -> sqlx::Result<u64> {
+    let res = sqlx::query!(
+        r#"delete from rate_limit_bucket where updated_at < now() - interval '1 day'"#
+    )
+    .execute(db)
+    .await?;
+
+    Ok(res.rows_affected())
+}
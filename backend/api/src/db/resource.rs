@@ -1,12 +1,13 @@
 use crate::translate::translate_text;
 use anyhow::Context;
-use serde_json::value::Value;
+use serde_json::{json, value::Value};
 use shared::domain::{
     additional_resource::{AdditionalResource, AdditionalResourceId as AddId, ResourceContent},
-    asset::{DraftOrLive, OrderBy, PrivacyLevel},
+    asset::{AssetId, DraftOrLive, OrderBy, PrivacyLevel},
     category::CategoryId,
     meta::{AffiliationId, AgeRangeId, ResourceTypeId as TypeId},
     module::{LiteModule, ModuleId, ModuleKind},
+    notification::NotificationKind,
     resource::{ResourceAdminData, ResourceData, ResourceId, ResourceRating, ResourceResponse},
     user::{UserId, UserScope},
 };
@@ -20,7 +21,7 @@ use crate::error;
 pub(crate) mod additional_resource;
 pub(crate) mod curation;
 pub(crate) mod module;
-pub(crate) mod report;
+pub mod report;
 
 pub async fn create(
     pool: &PgPool,
@@ -183,6 +184,8 @@ select cte.resource_id                                          as "resource_id:
         rating                                               as "rating?: ResourceRating",
         blocked                                              as "blocked",
         exists(select 1 from resource_like where resource_id = $1 and user_id = $3)  as "is_liked!",
+        (select avg(rating)::real from asset_review where not asset_review.hidden and asset_review.resource_id = cte.resource_id) as "average_rating",
+        (select count(*) from asset_review where not asset_review.hidden and asset_review.resource_id = cte.resource_id)       as "review_count!",
         curated,
         (
                 select row(resource_data_module.id, kind, is_complete)
@@ -220,6 +223,8 @@ from resource_data
         author_name: row.author_name,
         likes: row.likes,
         views: row.views,
+        average_rating: row.average_rating,
+        review_count: row.review_count,
         live_up_to_date: row.live_up_to_date,
         is_liked: row.is_liked,
         resource_data: ResourceData {
@@ -294,6 +299,8 @@ select resource.id                                       as "id!: ResourceId",
        live_up_to_date                          as "live_up_to_date!",
        rating                                   as "rating?: ResourceRating",
        exists(select 1 from resource_like where resource_id = resource.id and user_id = $2) as "is_liked!",
+       (select avg(rating)::real from asset_review where not asset_review.hidden and asset_review.resource_id = resource.id) as "average_rating",
+       (select count(*) from asset_review where not asset_review.hidden and asset_review.resource_id = resource.id) as "review_count!",
        blocked                                  as "blocked!",
        curated                                  as "curated!"
 from resource
@@ -369,6 +376,8 @@ order by ord asc
             author_name: resource_row.author_name,
             likes: resource_row.likes,
             views: resource_row.views,
+            average_rating: resource_row.average_rating,
+            review_count: resource_row.review_count,
             live_up_to_date: resource_row.live_up_to_date,
             is_liked: resource_row.is_liked,
             resource_data: ResourceData {
@@ -489,6 +498,8 @@ select resource.id                                              as "resource_id:
     views,
     live_up_to_date,
     exists(select 1 from resource_like where resource_id = resource.id and user_id = $9)                         as "is_liked!",
+    (select avg(rating)::real from asset_review where not asset_review.hidden and asset_review.resource_id = resource.id) as "average_rating",
+    (select count(*) from asset_review where not asset_review.hidden and asset_review.resource_id = resource.id) as "review_count!",
    display_name                                                                  as "display_name!",
    language                                                                      as "language!",
    description                                                                   as "description!",
@@ -558,6 +569,8 @@ limit $8
             author_name: resource_data_row.author_name,
             likes: resource_data_row.likes,
             views: resource_data_row.views,
+            average_rating: resource_data_row.average_rating,
+            review_count: resource_data_row.review_count,
             live_up_to_date: resource_data_row.live_up_to_date,
             is_liked: resource_data_row.is_liked,
             resource_data: ResourceData {
@@ -1193,6 +1206,16 @@ values ($1, $2)
     .await
     .map_err(|_| anyhow::anyhow!("Cannot like a resource more than once"))?;
 
+    if let Some(author_id) = resource.author_id {
+        super::notification::create(
+            &mut txn,
+            &[author_id.0],
+            NotificationKind::AssetLiked,
+            &json!({ "assetId": AssetId::ResourceId(id), "likedBy": user_id }),
+        )
+        .await?;
+    }
+
     txn.commit().await?;
 
     Ok(())
@@ -1,4 +1,6 @@
+use serde_json::json;
 use shared::domain::{
+    notification::NotificationKind,
     resource::{
         curation::{
             CommentId as CommId, ResourceCurationComment, ResourceCurationCommentResponse,
@@ -9,10 +11,10 @@ use shared::domain::{
     },
     user::UserId,
 };
-use sqlx::PgPool;
+use sqlx::{PgConnection, PgPool};
 use uuid::Uuid;
 
-use crate::error;
+use crate::{db, error};
 
 pub async fn update(
     pool: &PgPool,
@@ -25,9 +27,34 @@ pub async fn update(
     description: Option<bool>,
     additional_resources: Option<bool>,
     curation_status: Option<ResourceCurationStatus>,
-) -> anyhow::Result<(), error::Auth> {
+    assignee_id: Option<Option<UserId>>,
+) -> anyhow::Result<(), error::Curation> {
     let mut txn = pool.begin().await?;
 
+    let current_status = sqlx::query!(
+        //language=SQL
+        r#"
+select curation_status as "curation_status!: ResourceCurationStatus"
+from resource_curation_data
+where resource_id = $1
+for update
+"#,
+        resource_id.0,
+    )
+    .fetch_optional(&mut txn)
+    .await?
+    .ok_or(error::Curation::ResourceNotFound)?
+    .curation_status;
+
+    if let Some(curation_status) = curation_status {
+        if !current_status.can_transition_to(curation_status) {
+            return Err(error::Curation::InvalidTransition(
+                current_status,
+                curation_status,
+            ));
+        }
+    }
+
     if let Some(display_name) = display_name {
         sqlx::query!(
             //language=SQL
@@ -138,12 +165,31 @@ where resource_id = $1 and $2 is distinct from categories
         sqlx::query!(
             //language=SQL
             r#"
-            update resource_curation_data
-            set curation_status = $2
-            where resource_id = $1 and $2 is distinct from curation_status
-             "#,
+update resource_curation_data
+set curation_status   = $2,
+    status_updated_at = now(),
+    completed_at      = case when $2 = $3 then now() else completed_at end
+where resource_id = $1 and $2 is distinct from curation_status
+"#,
             resource_id.0,
             curation_status as i16,
+            ResourceCurationStatus::Done as i16,
+        )
+        .execute(&mut txn)
+        .await?;
+    }
+
+    if let Some(assignee_id) = assignee_id {
+        sqlx::query!(
+            //language=SQL
+            r#"
+update resource_curation_data
+set assignee_id = $2,
+    assigned_at = case when $2::uuid is null then null else now() end
+where resource_id = $1 and $2 is distinct from assignee_id
+"#,
+            resource_id.0,
+            assignee_id.map(|it| it.0),
         )
         .execute(&mut txn)
         .await?;
@@ -181,6 +227,11 @@ select resource_id                               as "resource_id!: ResourceId",
        affiliations,
        additional_resources,
        curation_status                          as "curation_status!: ResourceCurationStatus",
+       assignee_id                              as "assignee_id?: UserId",
+       created_at,
+       status_updated_at,
+       assigned_at,
+       completed_at,
        array(
             select row (rcc.id, rcc.resource_id, comment, created_at, author_id)
             from resource_curation_comment  "rcc"
@@ -188,7 +239,7 @@ select resource_id                               as "resource_id!: ResourceId",
             order by created_at desc
        )                                         as "comments!: Vec<(ResourceCurationComment)>",
        array(
-           select row (rr.id, rr.resource_id, report_type, reporter_id, created_at,      
+           select row (rr.id, rr.resource_id, report_type, reporter_id,
                         (
                         select given_name || ' '::text || family_name
                         from user_profile
@@ -198,7 +249,13 @@ select resource_id                               as "resource_id!: ResourceId",
                             select email::text
                             from user_email
                             where user_email.user_id = reporter_id
-                        )                                                                       
+                        ),
+                        created_at,
+                        status,
+                        assignee_id,
+                        action,
+                        resolution_note,
+                        resolved_at
             )
            from resource_report "rr"
            where rcd.resource_id = rr.resource_id
@@ -214,6 +271,11 @@ where resource_id = $1
     .map(|row| ResourceCurationData {
         resource_id: row.resource_id,
         curation_status: row.curation_status,
+        assignee_id: row.assignee_id,
+        created_at: row.created_at,
+        status_updated_at: row.status_updated_at,
+        assigned_at: row.assigned_at,
+        completed_at: row.completed_at,
         fields_done: ResourceCurationFieldsDone {
             display_name: row.display_name,
             language: row.language,
@@ -236,8 +298,10 @@ pub async fn create_comment(
     value: String,
     author_id: Uuid,
 ) -> anyhow::Result<CommId> {
+    let mut txn = pool.begin().await?;
+
     // Checks if Audio and Image IDs exists
-    sqlx::query!(
+    let id = sqlx::query!(
         r#"
 insert into resource_curation_comment (resource_id, comment, author_id)
 values ($1, $2, $3)
@@ -247,10 +311,28 @@ returning id as "id!: CommId"
         value,
         author_id
     )
-    .fetch_one(pool)
-    .await
-    .map(|it| it.id)
-    .map_err(Into::into)
+    .fetch_one(&mut txn)
+    .await?
+    .id;
+
+    // curators can be the author of the resource they're commenting on
+    let resource_author_id = db::webhook::resource_author(&mut txn, resource_id)
+        .await?
+        .filter(|resource_author_id| resource_author_id.0 != author_id);
+
+    if let Some(resource_author_id) = resource_author_id {
+        db::notification::create(
+            &mut txn,
+            &[resource_author_id.0],
+            NotificationKind::CurationComment,
+            &json!({ "resourceId": resource_id, "commentId": id }),
+        )
+        .await?;
+    }
+
+    txn.commit().await?;
+
+    Ok(id)
 }
 
 pub async fn get_comment(
@@ -290,3 +372,23 @@ where id = $1 and resource_id = $2
 
     Ok(comment)
 }
+
+/// Moves a curated Resource back into the curation queue after it has been republished.
+pub async fn mark_new_version(txn: &mut PgConnection, resource_id: ResourceId) -> sqlx::Result<()> {
+    sqlx::query!(
+        //language=SQL
+        r#"
+update resource_curation_data
+set curation_status   = $2,
+    status_updated_at = now()
+where resource_id = $1 and curation_status = $3
+"#,
+        resource_id.0,
+        ResourceCurationStatus::NewVersion as i16,
+        ResourceCurationStatus::Done as i16,
+    )
+    .execute(txn)
+    .await?;
+
+    Ok(())
+}
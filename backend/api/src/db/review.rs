@@ -0,0 +1,594 @@
+use shared::domain::{
+    asset::{AssetId, ReportAction, ReportStatus},
+    jig::JigId,
+    playlist::PlaylistId,
+    resource::ResourceId,
+    review::{Review, ReviewId, ReviewReport, ReviewReportType, REVIEW_REPORT_HIDE_THRESHOLD},
+    user::UserId,
+};
+use sqlx::{postgres::PgDatabaseError, PgConnection, PgPool};
+use uuid::Uuid;
+
+use crate::error;
+
+/// The `(jig_id, resource_id, playlist_id)` columns of a review, exactly one of which is set.
+pub type AssetColumns = (Option<Uuid>, Option<Uuid>, Option<Uuid>);
+
+/// Splits an asset id into the columns of a review.
+///
+/// Returns `None` for courses, which can't be reviewed.
+pub fn asset_columns(asset_id: AssetId) -> Option<AssetColumns> {
+    match asset_id {
+        AssetId::JigId(id) => Some((Some(id.0), None, None)),
+        AssetId::ResourceId(id) => Some((None, Some(id.0), None)),
+        AssetId::PlaylistId(id) => Some((None, None, Some(id.0))),
+        AssetId::CourseId(_) => None,
+    }
+}
+
+/// Gets the author of an asset, if the asset is live, public and not blocked.
+///
+/// Returns `None` if the asset can't be reviewed.
+pub async fn reviewable_asset_author(
+    db: &PgPool,
+    asset_id: AssetId,
+) -> sqlx::Result<Option<Option<UserId>>> {
+    let author_id = match asset_id {
+        AssetId::JigId(id) => sqlx::query!(
+            //language=SQL
+            r#"
+select author_id as "author_id: UserId"
+from jig
+         inner join jig_data on live_id = jig_data.id
+         left join jig_admin_data "admin" on admin.jig_id = jig.id
+where jig.id = $1
+  and published_at is not null
+  and privacy_level = 0
+  and blocked is not true
+"#,
+            id.0,
+        )
+        .fetch_optional(db)
+        .await?
+        .map(|row| row.author_id),
+        AssetId::ResourceId(id) => sqlx::query!(
+            //language=SQL
+            r#"
+select author_id as "author_id: UserId"
+from resource
+         inner join resource_data on live_id = resource_data.id
+         left join resource_admin_data "admin" on admin.resource_id = resource.id
+where resource.id = $1
+  and published_at is not null
+  and privacy_level = 0
+  and blocked is not true
+"#,
+            id.0,
+        )
+        .fetch_optional(db)
+        .await?
+        .map(|row| row.author_id),
+        AssetId::PlaylistId(id) => sqlx::query!(
+            //language=SQL
+            r#"
+select author_id as "author_id: UserId"
+from playlist
+         inner join playlist_data on live_id = playlist_data.id
+where playlist.id = $1
+  and published_at is not null
+  and privacy_level = 0
+"#,
+            id.0,
+        )
+        .fetch_optional(db)
+        .await?
+        .map(|row| row.author_id),
+        AssetId::CourseId(_) => None,
+    };
+
+    Ok(author_id)
+}
+
+/// Marks a reviewed jig, resource or playlist to be re-indexed, so that sorting by rating picks up the change.
+///
+/// Unlike `null`, `-infinity` keeps the live data visible to browsing until it is re-indexed.
+async fn mark_asset_unsynced(
+    txn: &mut PgConnection,
+    jig_id: Option<Uuid>,
+    resource_id: Option<Uuid>,
+    playlist_id: Option<Uuid>,
+) -> sqlx::Result<()> {
+    if let Some(jig_id) = jig_id {
+        sqlx::query!(
+            //language=SQL
+            r#"
+update jig_data
+set last_synced_at = '-infinity'
+where id = (select live_id from jig where jig.id = $1)
+  and last_synced_at is not null
+"#,
+            jig_id,
+        )
+        .execute(&mut *txn)
+        .await?;
+    }
+
+    if let Some(resource_id) = resource_id {
+        sqlx::query!(
+            //language=SQL
+            r#"
+update resource_data
+set last_synced_at = '-infinity'
+where id = (select live_id from resource where resource.id = $1)
+  and last_synced_at is not null
+"#,
+            resource_id,
+        )
+        .execute(&mut *txn)
+        .await?;
+    }
+
+    if let Some(playlist_id) = playlist_id {
+        sqlx::query!(
+            //language=SQL
+            r#"
+update playlist_data
+set last_synced_at = '-infinity'
+where id = (select live_id from playlist where playlist.id = $1)
+  and last_synced_at is not null
+"#,
+            playlist_id,
+        )
+        .execute(&mut *txn)
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Reviews an asset, or edits the user's existing review of it.
+pub async fn upsert(
+    db: &PgPool,
+    user_id: UserId,
+    (jig_id, resource_id, playlist_id): AssetColumns,
+    rating: i16,
+    text: &str,
+) -> sqlx::Result<ReviewId> {
+    let mut txn = db.begin().await?;
+
+    // skips the insert if the user already reviewed the asset, even if the review was only just
+    // written by a concurrent request, so that it can be updated instead.
+    let inserted = sqlx::query!(
+        //language=SQL
+        r#"
+insert into asset_review (user_id, jig_id, resource_id, playlist_id, rating, text)
+values ($1, $2, $3, $4, $5, $6)
+on conflict do nothing
+returning id as "id!: ReviewId"
+"#,
+        user_id.0,
+        jig_id,
+        resource_id,
+        playlist_id,
+        rating,
+        text,
+    )
+    .fetch_optional(&mut txn)
+    .await?;
+
+    let id = match inserted {
+        Some(row) => row.id,
+        None => {
+            sqlx::query!(
+                //language=SQL
+                r#"
+update asset_review
+set rating     = $5,
+    text       = $6,
+    updated_at = now()
+where user_id = $1
+  and (jig_id = $2 or resource_id = $3 or playlist_id = $4)
+returning id as "id!: ReviewId"
+"#,
+                user_id.0,
+                jig_id,
+                resource_id,
+                playlist_id,
+                rating,
+                text,
+            )
+            .fetch_one(&mut txn)
+            .await?
+            .id
+        }
+    };
+
+    mark_asset_unsynced(&mut txn, jig_id, resource_id, playlist_id).await?;
+
+    txn.commit().await?;
+
+    Ok(id)
+}
+
+/// Lists the reviews of an asset which aren't hidden, most recent first.
+///
+/// Returns the reviews along with the total number of reviews and the average rating.
+pub async fn list(
+    db: &PgPool,
+    (jig_id, resource_id, playlist_id): AssetColumns,
+    page: u32,
+    page_limit: u32,
+) -> sqlx::Result<(Vec<Review>, u64, Option<f32>)> {
+    let summary = sqlx::query!(
+        //language=SQL
+        r#"
+select count(*)          as "count!",
+       avg(rating)::real as "average_rating"
+from asset_review
+where (jig_id = $1 or resource_id = $2 or playlist_id = $3)
+  and not hidden
+"#,
+        jig_id,
+        resource_id,
+        playlist_id,
+    )
+    .fetch_one(db)
+    .await?;
+
+    let rows = sqlx::query!(
+        //language=SQL
+        r#"
+select id                                   as "id!: ReviewId",
+       jig_id                               as "jig_id?: JigId",
+       resource_id                          as "resource_id?: ResourceId",
+       playlist_id                          as "playlist_id?: PlaylistId",
+       user_id                              as "user_id!: UserId",
+       (select given_name || ' '::text || family_name
+        from user_profile
+        where user_profile.user_id = asset_review.user_id) as "author_name",
+       rating,
+       text,
+       reply,
+       replied_at,
+       created_at,
+       updated_at
+from asset_review
+where (jig_id = $1 or resource_id = $2 or playlist_id = $3)
+  and not hidden
+order by created_at desc
+offset $4 limit $5
+"#,
+        jig_id,
+        resource_id,
+        playlist_id,
+        (page as i64) * (page_limit as i64),
+        page_limit as i64,
+    )
+    .fetch_all(db)
+    .await?;
+
+    let reviews = rows
+        .into_iter()
+        .filter_map(|row| {
+            let asset_id = review_asset_id(row.jig_id, row.resource_id, row.playlist_id)?;
+
+            Some(Review {
+                id: row.id,
+                asset_id,
+                author_id: row.user_id,
+                author_name: row.author_name,
+                rating: row.rating as u8,
+                text: row.text,
+                reply: row.reply,
+                replied_at: row.replied_at,
+                created_at: row.created_at,
+                updated_at: row.updated_at,
+            })
+        })
+        .collect();
+
+    Ok((reviews, summary.count as u64, summary.average_rating))
+}
+
+fn review_asset_id(
+    jig_id: Option<JigId>,
+    resource_id: Option<ResourceId>,
+    playlist_id: Option<PlaylistId>,
+) -> Option<AssetId> {
+    match (jig_id, resource_id, playlist_id) {
+        (Some(id), _, _) => Some(id.into()),
+        (_, Some(id), _) => Some(id.into()),
+        (_, _, Some(id)) => Some(id.into()),
+        // the check constraint makes sure exactly one is set
+        (None, None, None) => None,
+    }
+}
+
+/// Gets the user who wrote a review and the author of the reviewed asset, if the review exists.
+pub async fn get_authors(
+    db: &PgPool,
+    id: ReviewId,
+) -> sqlx::Result<Option<(UserId, Option<UserId>)>> {
+    let authors = sqlx::query!(
+        //language=SQL
+        r#"
+select asset_review.user_id                                                as "user_id!: UserId",
+       coalesce(jig.author_id, resource.author_id, playlist.author_id) as "asset_author_id: UserId"
+from asset_review
+         left join jig on jig.id = asset_review.jig_id
+         left join resource on resource.id = asset_review.resource_id
+         left join playlist on playlist.id = asset_review.playlist_id
+where asset_review.id = $1
+"#,
+        id.0,
+    )
+    .fetch_optional(db)
+    .await?
+    .map(|row| (row.user_id, row.asset_author_id));
+
+    Ok(authors)
+}
+
+pub async fn delete(db: &PgPool, id: ReviewId) -> sqlx::Result<()> {
+    let mut txn = db.begin().await?;
+
+    let row = sqlx::query!(
+        //language=SQL
+        r#"
+delete from asset_review where id = $1
+returning jig_id, resource_id, playlist_id
+"#,
+        id.0,
+    )
+    .fetch_optional(&mut txn)
+    .await?;
+
+    if let Some(row) = row {
+        mark_asset_unsynced(&mut txn, row.jig_id, row.resource_id, row.playlist_id).await?;
+    }
+
+    txn.commit().await?;
+
+    Ok(())
+}
+
+/// Sets the reply of the author of the asset to a review, or removes it if `reply` is `None`.
+pub async fn reply(db: &PgPool, id: ReviewId, reply: Option<&str>) -> sqlx::Result<()> {
+    sqlx::query!(
+        //language=SQL
+        r#"
+update asset_review
+set reply      = $2,
+    replied_at = case when $2::text is null then null else now() end
+where id = $1
+"#,
+        id.0,
+        reply,
+    )
+    .execute(db)
+    .await?;
+
+    Ok(())
+}
+
+/// Reports a review, hiding it if it has [`REVIEW_REPORT_HIDE_THRESHOLD`] pending reports.
+pub async fn report(
+    db: &PgPool,
+    id: ReviewId,
+    reporter_id: UserId,
+    report_type: ReviewReportType,
+) -> Result<(), error::Review> {
+    let mut txn = db.begin().await?;
+
+    sqlx::query!(
+        //language=SQL
+        r#"
+insert into asset_review_report (review_id, reporter_id, report_type)
+values ($1, $2, $3)
+"#,
+        id.0,
+        reporter_id.0,
+        report_type as i16,
+    )
+    .execute(&mut txn)
+    .await
+    .map_err(|err| match err {
+        sqlx::Error::Database(err) => {
+            let constraint = err.downcast_ref::<PgDatabaseError>().constraint();
+
+            match constraint {
+                Some(constraint) if constraint.ends_with("_fkey") => {
+                    error::Review::ResourceNotFound
+                }
+                Some(constraint) if constraint.ends_with("_pkey") => error::Review::AlreadyReported,
+                _ => sqlx::Error::Database(err).into(),
+            }
+        }
+        e => e.into(),
+    })?;
+
+    let hidden = sqlx::query!(
+        //language=SQL
+        r#"
+update asset_review
+set hidden = true
+where id = $1
+  and not hidden
+  and (select count(*)
+       from asset_review_report
+       where review_id = $1
+         and status <> $2) >= $3
+returning jig_id, resource_id, playlist_id
+"#,
+        id.0,
+        ReportStatus::Resolved as i16,
+        REVIEW_REPORT_HIDE_THRESHOLD as i64,
+    )
+    .fetch_optional(&mut txn)
+    .await?;
+
+    if let Some(row) = hidden {
+        mark_asset_unsynced(&mut txn, row.jig_id, row.resource_id, row.playlist_id).await?;
+    }
+
+    txn.commit().await?;
+
+    Ok(())
+}
+
+/// Lists reports of reviews in the moderation queue, oldest first.
+pub async fn list_reports(
+    db: &PgPool,
+    status: Option<ReportStatus>,
+    page: u32,
+    page_limit: u32,
+) -> sqlx::Result<Vec<ReviewReport>> {
+    let rows = sqlx::query!(
+        //language=SQL
+        r#"
+select asset_review.id                      as "id!: ReviewId",
+       jig_id                               as "jig_id?: JigId",
+       resource_id                          as "resource_id?: ResourceId",
+       playlist_id                          as "playlist_id?: PlaylistId",
+       user_id                              as "user_id!: UserId",
+       (select given_name || ' '::text || family_name
+        from user_profile
+        where user_profile.user_id = asset_review.user_id) as "author_name",
+       rating,
+       text,
+       reply,
+       replied_at,
+       asset_review.created_at,
+       updated_at,
+       hidden,
+       reporter_id                          as "reporter_id!: UserId",
+       report_type                          as "report_type!: ReviewReportType",
+       report.created_at                    as "reported_at",
+       status                               as "status!: ReportStatus",
+       action                               as "action?: ReportAction",
+       resolution_note,
+       resolved_at
+from asset_review_report "report"
+         inner join asset_review on asset_review.id = report.review_id
+where (status = $1 or $1 is null)
+order by report.created_at
+offset $2 limit $3
+"#,
+        status.map(|it| it as i16),
+        page as i64 * page_limit as i64,
+        page_limit as i64,
+    )
+    .fetch_all(db)
+    .await?;
+
+    let reports = rows
+        .into_iter()
+        .filter_map(|row| {
+            let asset_id = review_asset_id(row.jig_id, row.resource_id, row.playlist_id)?;
+
+            Some(ReviewReport {
+                review: Review {
+                    id: row.id,
+                    asset_id,
+                    author_id: row.user_id,
+                    author_name: row.author_name,
+                    rating: row.rating as u8,
+                    text: row.text,
+                    reply: row.reply,
+                    replied_at: row.replied_at,
+                    created_at: row.created_at,
+                    updated_at: row.updated_at,
+                },
+                hidden: row.hidden,
+                reporter_id: row.reporter_id,
+                report_type: row.report_type,
+                created_at: row.reported_at,
+                status: row.status,
+                action: row.action,
+                resolution_note: row.resolution_note,
+                resolved_at: row.resolved_at,
+            })
+        })
+        .collect();
+
+    Ok(reports)
+}
+
+pub async fn filtered_report_count(db: &PgPool, status: Option<ReportStatus>) -> sqlx::Result<u64> {
+    let count = sqlx::query!(
+        //language=SQL
+        r#"
+select count(*) as "count!"
+from asset_review_report
+where (status = $1 or $1 is null)
+"#,
+        status.map(|it| it as i16),
+    )
+    .fetch_one(db)
+    .await?
+    .count;
+
+    Ok(count as u64)
+}
+
+/// Resolves the open reports of a review, hiding the review if it's blocked and showing it again
+/// otherwise.
+///
+/// Returns `false` if the review has no open reports.
+pub async fn resolve_reports(
+    db: &PgPool,
+    id: ReviewId,
+    action: ReportAction,
+    note: Option<&str>,
+    resolved_by: UserId,
+) -> sqlx::Result<bool> {
+    let mut txn = db.begin().await?;
+
+    let res = sqlx::query!(
+        //language=SQL
+        r#"
+update asset_review_report
+set status          = $2,
+    action          = $3,
+    resolution_note = $4,
+    resolved_by     = $5,
+    resolved_at     = now()
+where review_id = $1
+  and status <> $2
+"#,
+        id.0,
+        ReportStatus::Resolved as i16,
+        action as i16,
+        note,
+        resolved_by.0,
+    )
+    .execute(&mut txn)
+    .await?;
+
+    if res.rows_affected() == 0 {
+        return Ok(false);
+    }
+
+    let changed = sqlx::query!(
+        //language=SQL
+        r#"
+update asset_review
+set hidden = $2
+where id = $1
+  and hidden <> $2
+returning jig_id, resource_id, playlist_id
+"#,
+        id.0,
+        action == ReportAction::Block,
+    )
+    .fetch_optional(&mut txn)
+    .await?;
+
+    if let Some(row) = changed {
+        mark_asset_unsynced(&mut txn, row.jig_id, row.resource_id, row.playlist_id).await?;
+    }
+
+    txn.commit().await?;
+
+    Ok(true)
+}
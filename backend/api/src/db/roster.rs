@@ -0,0 +1,342 @@
+use std::collections::HashMap;
+
+use shared::domain::{
+    roster::{
+        PicturePassword, Roster, RosterId, RosterStudent, RosterStudentCreateRequest,
+        RosterStudentId,
+    },
+    user::UserId,
+};
+use sqlx::{postgres::PgDatabaseError, PgConnection, PgPool};
+use uuid::Uuid;
+
+use crate::error;
+
+fn handle_nickname_err(err: sqlx::Error, nickname: &str) -> error::Roster {
+    match err {
+        sqlx::Error::Database(err)
+            if err.downcast_ref::<PgDatabaseError>().constraint()
+                == Some("roster_student_roster_id_nickname_key") =>
+        {
+            error::Roster::NicknameExists(nickname.to_owned())
+        }
+        e => e.into(),
+    }
+}
+
+pub async fn create(
+    db: &PgPool,
+    creator_id: UserId,
+    display_name: &str,
+    students: &[RosterStudentCreateRequest],
+) -> Result<RosterId, error::Roster> {
+    let mut txn = db.begin().await?;
+
+    let id = sqlx::query!(
+        //language=SQL
+        r#"
+insert into roster (creator_id, display_name)
+values ($1, $2)
+returning id as "id!: RosterId"
+"#,
+        creator_id.0,
+        display_name,
+    )
+    .fetch_one(&mut txn)
+    .await?
+    .id;
+
+    for student in students {
+        insert_student(&mut txn, id, student).await?;
+    }
+
+    txn.commit().await?;
+
+    Ok(id)
+}
+
+async fn insert_student(
+    txn: &mut PgConnection,
+    roster_id: RosterId,
+    student: &RosterStudentCreateRequest,
+) -> Result<RosterStudentId, error::Roster> {
+    sqlx::query!(
+        //language=SQL
+        r#"
+insert into roster_student (roster_id, nickname, picture_password)
+values ($1, $2, $3)
+returning id as "id!: RosterStudentId"
+"#,
+        roster_id.0,
+        student.nickname,
+        student.picture_password.map(|picture| picture as i16),
+    )
+    .fetch_one(&mut *txn)
+    .await
+    .map(|row| row.id)
+    .map_err(|err| handle_nickname_err(err, &student.nickname))
+}
+
+/// Lists the rosters created by a user, along with their students.
+pub async fn list(db: &PgPool, creator_id: UserId) -> sqlx::Result<Vec<Roster>> {
+    let rows = sqlx::query!(
+        //language=SQL
+        r#"
+select id                                   as "id!: RosterId",
+       display_name,
+       created_at,
+       updated_at
+from roster
+where creator_id = $1
+order by display_name
+"#,
+        creator_id.0,
+    )
+    .fetch_all(db)
+    .await?;
+
+    let ids: Vec<_> = rows.iter().map(|row| row.id.0).collect();
+
+    let mut students = list_students(db, &ids).await?;
+
+    let rosters = rows
+        .into_iter()
+        .map(|row| Roster {
+            id: row.id,
+            display_name: row.display_name,
+            students: students.remove(&row.id).unwrap_or_default(),
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        })
+        .collect();
+
+    Ok(rosters)
+}
+
+/// Gets a roster, if it exists and was created by `creator_id`.
+pub async fn get_one(
+    db: &PgPool,
+    creator_id: UserId,
+    id: RosterId,
+) -> sqlx::Result<Option<Roster>> {
+    let row = sqlx::query!(
+        //language=SQL
+        r#"
+select display_name, created_at, updated_at
+from roster
+where id = $1
+  and creator_id = $2
+"#,
+        id.0,
+        creator_id.0,
+    )
+    .fetch_optional(db)
+    .await?;
+
+    let row = match row {
+        Some(row) => row,
+        None => return Ok(None),
+    };
+
+    let students = list_students(db, &[id.0])
+        .await?
+        .remove(&id)
+        .unwrap_or_default();
+
+    Ok(Some(Roster {
+        id,
+        display_name: row.display_name,
+        students,
+        created_at: row.created_at,
+        updated_at: row.updated_at,
+    }))
+}
+
+async fn list_students(
+    db: &PgPool,
+    roster_ids: &[Uuid],
+) -> sqlx::Result<HashMap<RosterId, Vec<RosterStudent>>> {
+    let rows = sqlx::query!(
+        //language=SQL
+        r#"
+select roster_id                            as "roster_id!: RosterId",
+       id                                   as "id!: RosterStudentId",
+       nickname,
+       picture_password                     as "picture_password?: PicturePassword"
+from roster_student
+where roster_id = any($1)
+order by lower(nickname)
+"#,
+        roster_ids,
+    )
+    .fetch_all(db)
+    .await?;
+
+    let mut students: HashMap<RosterId, Vec<RosterStudent>> = HashMap::new();
+
+    for row in rows {
+        students
+            .entry(row.roster_id)
+            .or_default()
+            .push(RosterStudent {
+                id: row.id,
+                nickname: row.nickname,
+                picture_password: row.picture_password,
+            });
+    }
+
+    Ok(students)
+}
+
+/// Checks that a roster exists and was created by `creator_id`.
+pub async fn authz(db: &PgPool, creator_id: UserId, id: RosterId) -> Result<(), error::Roster> {
+    let exists = sqlx::query!(
+        //language=SQL
+        r#"
+select exists(select 1 from roster where id = $1 and creator_id = $2) as "exists!"
+"#,
+        id.0,
+        creator_id.0,
+    )
+    .fetch_one(db)
+    .await?
+    .exists;
+
+    if !exists {
+        return Err(error::Roster::ResourceNotFound);
+    }
+
+    Ok(())
+}
+
+pub async fn update(db: &PgPool, id: RosterId, display_name: Option<&str>) -> sqlx::Result<()> {
+    sqlx::query!(
+        //language=SQL
+        r#"
+update roster
+set display_name = coalesce($2, display_name),
+    updated_at   = now()
+where id = $1
+"#,
+        id.0,
+        display_name,
+    )
+    .execute(db)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn delete(db: &PgPool, id: RosterId) -> sqlx::Result<()> {
+    sqlx::query!(
+        //language=SQL
+        r#"
+delete from roster where id = $1
+"#,
+        id.0,
+    )
+    .execute(db)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn create_student(
+    db: &PgPool,
+    roster_id: RosterId,
+    student: &RosterStudentCreateRequest,
+) -> Result<RosterStudentId, error::Roster> {
+    let mut txn = db.begin().await?;
+
+    let id = insert_student(&mut txn, roster_id, student).await?;
+
+    touch(&mut txn, roster_id).await?;
+
+    txn.commit().await?;
+
+    Ok(id)
+}
+
+pub async fn update_student(
+    db: &PgPool,
+    roster_id: RosterId,
+    id: RosterStudentId,
+    nickname: Option<&str>,
+    picture_password: Option<Option<PicturePassword>>,
+) -> Result<(), error::Roster> {
+    let mut txn = db.begin().await?;
+
+    let updated = sqlx::query!(
+        //language=SQL
+        r#"
+update roster_student
+set nickname         = coalesce($3, nickname),
+    picture_password = case when $4 then $5 else picture_password end
+where id = $1
+  and roster_id = $2
+"#,
+        id.0,
+        roster_id.0,
+        nickname,
+        picture_password.is_some(),
+        picture_password.flatten().map(|picture| picture as i16),
+    )
+    .execute(&mut txn)
+    .await
+    .map_err(|err| handle_nickname_err(err, nickname.unwrap_or_default()))?
+    .rows_affected();
+
+    if updated == 0 {
+        return Err(error::Roster::ResourceNotFound);
+    }
+
+    touch(&mut txn, roster_id).await?;
+
+    txn.commit().await?;
+
+    Ok(())
+}
+
+pub async fn delete_student(
+    db: &PgPool,
+    roster_id: RosterId,
+    id: RosterStudentId,
+) -> Result<(), error::Roster> {
+    let mut txn = db.begin().await?;
+
+    let deleted = sqlx::query!(
+        //language=SQL
+        r#"
+delete from roster_student where id = $1 and roster_id = $2
+"#,
+        id.0,
+        roster_id.0,
+    )
+    .execute(&mut txn)
+    .await?
+    .rows_affected();
+
+    if deleted == 0 {
+        return Err(error::Roster::ResourceNotFound);
+    }
+
+    touch(&mut txn, roster_id).await?;
+
+    txn.commit().await?;
+
+    Ok(())
+}
+
+async fn touch(txn: &mut PgConnection, id: RosterId) -> sqlx::Result<()> {
+    sqlx::query!(
+        //language=SQL
+        r#"
+update roster set updated_at = now() where id = $1
+"#,
+        id.0,
+    )
+    .execute(&mut *txn)
+    .await?;
+
+    Ok(())
+}
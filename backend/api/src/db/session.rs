@@ -1,6 +1,9 @@
 use chrono::{DateTime, Utc};
-use shared::domain::user::UserId;
-use sqlx::PgConnection;
+use shared::domain::{
+    session::{ActiveSession, SessionId},
+    user::UserId,
+};
+use sqlx::{PgConnection, PgPool};
 use tracing::instrument;
 use uuid::Uuid;
 
@@ -80,3 +83,135 @@ pub async fn delete(txn: &mut PgConnection, token: &str) -> sqlx::Result<()> {
 
     Ok(())
 }
+
+/// Records a wrong two-factor code entered for a session, returning how many wrong codes were entered for it.
+#[instrument(skip_all)]
+pub async fn record_failed_attempt(db: &PgPool, token: &str) -> sqlx::Result<i16> {
+    let failed_attempts = sqlx::query!(
+        r#"
+update session
+set failed_attempts = failed_attempts + 1
+where token = $1
+returning failed_attempts
+"#,
+        token,
+    )
+    .fetch_optional(db)
+    .await?
+    .map_or(0, |row| row.failed_attempts);
+
+    Ok(failed_attempts)
+}
+
+/// Records that a session was used, at most once a minute unless the client changed.
+#[instrument(skip_all)]
+pub async fn touch(
+    db: &PgPool,
+    token: &str,
+    user_agent: Option<&str>,
+    ip_hint: Option<&str>,
+) -> sqlx::Result<()> {
+    sqlx::query!(
+        //language=SQL
+        r#"
+update session
+set last_used_at = now(),
+    user_agent   = $2,
+    ip_hint      = $3
+where token = $1
+  and (last_used_at is null
+    or last_used_at < now() - interval '1 minute'
+    or user_agent is distinct from $2
+    or ip_hint is distinct from $3)
+"#,
+        token,
+        user_agent,
+        ip_hint,
+    )
+    .execute(db)
+    .await?;
+
+    Ok(())
+}
+
+/// Lists the unexpired sessions a user signed in with, most recently created first.
+///
+/// Sessions for one time actions (like verifying an email) and impersonation sessions are left out.
+#[instrument(skip(db, current_token))]
+pub async fn list_active(
+    db: &PgPool,
+    user_id: UserId,
+    current_token: &str,
+) -> sqlx::Result<Vec<ActiveSession>> {
+    sqlx::query_as!(
+        ActiveSession,
+        //language=SQL
+        r#"
+select id                as "id!: SessionId",
+       created_at,
+       last_used_at,
+       expires_at,
+       user_agent,
+       ip_hint,
+       scope_mask,
+       token = $2        as "current!"
+from session
+where user_id = $1
+  and impersonator_id is null
+  and (scope_mask & $3) <> 0
+  and expires_at < now() is not true
+order by created_at desc
+"#,
+        user_id.0,
+        current_token,
+        SessionMask::GENERAL_API.bits(),
+    )
+    .fetch_all(db)
+    .await
+}
+
+/// Signs out one of the sessions listed by [`list_active`], returning whether it existed.
+#[instrument(skip(db))]
+pub async fn revoke(db: &PgPool, user_id: UserId, id: SessionId) -> sqlx::Result<bool> {
+    let res = sqlx::query!(
+        //language=SQL
+        r#"
+delete
+from session
+where id = $1
+  and user_id = $2
+  and impersonator_id is null
+  and (scope_mask & $3) <> 0
+"#,
+        id.0,
+        user_id.0,
+        SessionMask::GENERAL_API.bits(),
+    )
+    .execute(db)
+    .await?;
+
+    Ok(res.rows_affected() > 0)
+}
+
+/// Signs out all the sessions listed by [`list_active`] except for `current_token`.
+#[instrument(skip(db, current_token))]
+pub async fn revoke_others(db: &PgPool, user_id: UserId, current_token: &str) -> sqlx::Result<()> {
+    sqlx::query!(
+        //language=SQL
+        r#"
+delete
+from session
+where user_id = $1
+  and token <> $2
+  and impersonator_id is null
+  and (scope_mask & $3) <> 0
+"#,
+        user_id.0,
+        current_token,
+        SessionMask::GENERAL_API.bits(),
+    )
+    .execute(db)
+    .await?;
+
+    Ok(())
+}
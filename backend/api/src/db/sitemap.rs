@@ -0,0 +1,85 @@
+use anyhow::anyhow;
+use shared::domain::sitemap::{SitemapEntry, SitemapKind, SitemapSummary, SITEMAP_PAGE_SIZE};
+use sqlx::PgPool;
+
+// All public content is listed: JIGs, playlists and courses which are published, public and not
+// blocked, every circle and the profiles of users who authored any of that content (resources
+// included). See the `sitemap_entry` view.
+
+/// Summarizes the sitemap pages of each kind of public content.
+pub async fn summaries(pool: &PgPool) -> anyhow::Result<Vec<SitemapSummary>> {
+    let rows = sqlx::query!(
+        //language=SQL
+        r#"
+select kind                                 as "kind!",
+       count(*)                             as "count!",
+       max(last_edited)                     as "last_edited"
+from sitemap_entry
+group by kind
+"#,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut summaries: Vec<SitemapSummary> = SitemapKind::ALL
+        .iter()
+        .map(|kind| SitemapSummary {
+            kind: *kind,
+            pages: 0,
+            last_edited: None,
+        })
+        .collect();
+
+    for row in rows {
+        let kind = sitemap_kind(&row.kind)?;
+        let count = row.count as u32;
+
+        if let Some(summary) = summaries.iter_mut().find(|summary| summary.kind == kind) {
+            summary.pages = count / SITEMAP_PAGE_SIZE + (count % SITEMAP_PAGE_SIZE != 0) as u32;
+            summary.last_edited = row.last_edited;
+        }
+    }
+
+    Ok(summaries)
+}
+
+/// Lists a page of public content of one kind, ordered by id so that pages stay stable.
+pub async fn list(
+    pool: &PgPool,
+    kind: SitemapKind,
+    page: u32,
+) -> anyhow::Result<Vec<SitemapEntry>> {
+    let entries = sqlx::query!(
+        //language=SQL
+        r#"
+select id                                   as "id!",
+       last_edited
+from sitemap_entry
+where kind = $1
+order by id
+offset $2
+limit $3
+"#,
+        kind.as_str(),
+        page as i64 * SITEMAP_PAGE_SIZE as i64,
+        SITEMAP_PAGE_SIZE as i64,
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|row| SitemapEntry {
+        id: row.id,
+        last_edited: row.last_edited,
+    })
+    .collect();
+
+    Ok(entries)
+}
+
+fn sitemap_kind(kind: &str) -> anyhow::Result<SitemapKind> {
+    SitemapKind::ALL
+        .iter()
+        .copied()
+        .find(|it| it.as_str() == kind)
+        .ok_or_else(|| anyhow!("unknown sitemap kind {}", kind))
+}
@@ -0,0 +1,364 @@
+use std::collections::HashMap;
+
+use serde_json::json;
+use shared::domain::{
+    admin::{GlossaryTerm, GlossaryTermId},
+    asset::AssetType,
+};
+use sqlx::{types::Json, PgConnection, PgPool};
+use uuid::Uuid;
+
+use crate::error;
+
+/// Gets every cached translation of `source_text`, keyed by target language.
+pub async fn get_translation_memory(
+    pool: &PgPool,
+    source_text: &str,
+) -> sqlx::Result<HashMap<String, String>> {
+    let translations = sqlx::query!(
+        //language=SQL
+        r#"
+select target_language, translated_text
+from translation_memory
+where md5(source_text) = md5($1) and source_text = $1
+"#,
+        source_text,
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|row| (row.target_language, row.translated_text))
+    .collect();
+
+    Ok(translations)
+}
+
+/// Saves translations of `source_text` made with version `glossary_version` of the glossary.
+///
+/// Nothing is saved if the glossary has changed since, as the translations may use outdated terms.
+pub async fn save_translation_memory(
+    pool: &PgPool,
+    source_text: &str,
+    glossary_version: i64,
+    translations: &HashMap<String, String>,
+) -> sqlx::Result<()> {
+    let (languages, texts): (Vec<_>, Vec<_>) = translations
+        .iter()
+        .map(|(language, text)| (language.clone(), text.clone()))
+        .unzip();
+
+    sqlx::query!(
+        //language=SQL
+        r#"
+insert into translation_memory (source_text, target_language, translated_text)
+select $1, target_language, translated_text
+from unnest($2::text[], $3::text[]) as t(target_language, translated_text)
+-- the lock makes glossary changes wait for the save, so that they can drop what it saved
+where exists(select 1 from translation_glossary_version where version = $4 for share)
+on conflict ((md5(source_text)), target_language)
+    do update set translated_text = excluded.translated_text,
+                  created_at      = now()
+"#,
+        source_text,
+        &languages[..],
+        &texts[..],
+        glossary_version,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Bumps the glossary version and drops cached translations of any text containing one of `terms`,
+/// so that they are translated again with the current glossary.
+///
+/// Translations still being made with the previous version of the glossary won't be saved.
+async fn glossary_changed(txn: &mut PgConnection, terms: &[&str]) -> sqlx::Result<()> {
+    sqlx::query!(
+        //language=SQL
+        r#"
+update translation_glossary_version
+set version = version + 1
+"#,
+    )
+    .execute(&mut *txn)
+    .await?;
+
+    sqlx::query!(
+        //language=SQL
+        r#"
+delete from translation_memory
+where exists(select 1 from unnest($1::text[]) as term where strpos(source_text, term) > 0)
+"#,
+        terms,
+    )
+    .execute(&mut *txn)
+    .await?;
+
+    Ok(())
+}
+
+/// The glossary along with its version.
+pub struct Glossary {
+    pub terms: Vec<GlossaryTerm>,
+    pub version: i64,
+}
+
+/// Gets the glossary to translate with.
+///
+/// The version is read before the terms, so that translations made with terms which change in the
+/// meantime aren't saved.
+pub async fn get_glossary(pool: &PgPool) -> sqlx::Result<Glossary> {
+    let version = sqlx::query_scalar!(
+        //language=SQL
+        r#"
+select version from translation_glossary_version
+"#,
+    )
+    .fetch_one(pool)
+    .await?;
+
+    let terms = list_glossary_terms(pool).await?;
+
+    Ok(Glossary { terms, version })
+}
+
+pub async fn list_glossary_terms(pool: &PgPool) -> sqlx::Result<Vec<GlossaryTerm>> {
+    sqlx::query!(
+        //language=SQL
+        r#"
+select id                                   as "id!: GlossaryTermId",
+       term,
+       renderings                           as "renderings!: Json<HashMap<String, String>>",
+       created_at,
+       updated_at
+from translation_glossary
+order by term
+"#,
+    )
+    .fetch_all(pool)
+    .await
+    .map(|rows| {
+        rows.into_iter()
+            .map(|row| GlossaryTerm {
+                id: row.id,
+                term: row.term,
+                renderings: row.renderings.0,
+                created_at: row.created_at,
+                updated_at: row.updated_at,
+            })
+            .collect()
+    })
+}
+
+pub async fn create_glossary_term(
+    pool: &PgPool,
+    term: &str,
+    renderings: &HashMap<String, String>,
+) -> Result<GlossaryTermId, error::Translation> {
+    let mut txn = pool.begin().await?;
+
+    let id = sqlx::query!(
+        //language=SQL
+        r#"
+insert into translation_glossary (term, renderings)
+values ($1, $2)
+on conflict (term) do nothing
+returning id as "id!: GlossaryTermId"
+"#,
+        term,
+        json!(renderings),
+    )
+    .fetch_optional(&mut txn)
+    .await?
+    .ok_or_else(|| error::Translation::TermExists(term.to_owned()))?
+    .id;
+
+    glossary_changed(&mut txn, &[term]).await?;
+
+    txn.commit().await?;
+
+    Ok(id)
+}
+
+pub async fn update_glossary_term(
+    pool: &PgPool,
+    id: GlossaryTermId,
+    term: Option<&str>,
+    renderings: Option<&HashMap<String, String>>,
+) -> Result<GlossaryTerm, error::Translation> {
+    let mut txn = pool.begin().await?;
+
+    let previous_term = sqlx::query!(
+        //language=SQL
+        r#"
+select term from translation_glossary where id = $1 for update
+"#,
+        id.0,
+    )
+    .fetch_optional(&mut txn)
+    .await?
+    .ok_or(error::Translation::ResourceNotFound)?
+    .term;
+
+    if let Some(term) = term {
+        let exists = sqlx::query!(
+            //language=SQL
+            r#"
+select exists(select 1 from translation_glossary where term = $1 and id <> $2) as "exists!"
+"#,
+            term,
+            id.0,
+        )
+        .fetch_one(&mut txn)
+        .await?
+        .exists;
+
+        if exists {
+            return Err(error::Translation::TermExists(term.to_owned()));
+        }
+    }
+
+    let row = sqlx::query!(
+        //language=SQL
+        r#"
+update translation_glossary
+set term       = coalesce($2, term),
+    renderings = coalesce($3, renderings),
+    updated_at = now()
+where id = $1
+returning term,
+          renderings                        as "renderings!: Json<HashMap<String, String>>",
+          created_at,
+          updated_at
+"#,
+        id.0,
+        term,
+        renderings.map(|it| json!(it)),
+    )
+    .fetch_one(&mut txn)
+    .await?;
+
+    glossary_changed(&mut txn, &[&previous_term, &row.term]).await?;
+
+    txn.commit().await?;
+
+    Ok(GlossaryTerm {
+        id,
+        term: row.term,
+        renderings: row.renderings.0,
+        created_at: row.created_at,
+        updated_at: row.updated_at,
+    })
+}
+
+pub async fn delete_glossary_term(
+    pool: &PgPool,
+    id: GlossaryTermId,
+) -> Result<(), error::Translation> {
+    let mut txn = pool.begin().await?;
+
+    let term = sqlx::query!(
+        //language=SQL
+        r#"
+delete from translation_glossary where id = $1 returning term
+"#,
+        id.0,
+    )
+    .fetch_optional(&mut txn)
+    .await?
+    .ok_or(error::Translation::ResourceNotFound)?
+    .term;
+
+    glossary_changed(&mut txn, &[&term]).await?;
+
+    txn.commit().await?;
+
+    Ok(())
+}
+
+/// Stores admin overrides of an asset's translated description and applies them to its live data.
+///
+/// Languages whose override is removed are translated again by the translation job. Courses aren't
+/// translated automatically, so removed overrides are simply dropped from their description.
+pub async fn override_translated_description(
+    pool: &PgPool,
+    asset_type: AssetType,
+    id: Uuid,
+    translations: &HashMap<String, Option<String>>,
+) -> Result<(), error::Translation> {
+    let table = asset_type.as_str();
+
+    let removed: Vec<String> = translations
+        .iter()
+        .filter(|(_, text)| text.is_none())
+        .map(|(language, _)| language.clone())
+        .collect();
+
+    let mut txn = pool.begin().await?;
+
+    let live_id: Option<(Uuid,)> = sqlx::query_as(&format!(
+        r#"
+update {0}
+set translated_description_override = jsonb_strip_nulls(translated_description_override || $2)
+where id = $1
+returning live_id
+"#,
+        table
+    ))
+    .bind(id)
+    .bind(json!(translations))
+    .fetch_optional(&mut txn)
+    .await?;
+
+    let (live_id,) = live_id.ok_or(error::Translation::ResourceNotFound)?;
+
+    sqlx::query(&format!(
+        "update {0}_data set translated_description = translated_description - $2::text[], last_synced_at = null where id = $1",
+        table
+    ))
+    .bind(live_id)
+    .bind(&removed)
+    .execute(&mut txn)
+    .await?;
+
+    if !removed.is_empty() && !asset_type.is_course() {
+        // the translation job fills in the languages missing from the description
+        sqlx::query(&format!(
+            "update {0} set description_translate_status = null where id = $1",
+            table
+        ))
+        .bind(id)
+        .execute(&mut txn)
+        .await?;
+    }
+
+    apply_translation_overrides(&mut txn, asset_type, id).await?;
+
+    txn.commit().await?;
+
+    Ok(())
+}
+
+/// Applies an asset's translated description overrides to its live data, e.g. after publishing.
+pub async fn apply_translation_overrides(
+    txn: &mut PgConnection,
+    asset_type: AssetType,
+    id: Uuid,
+) -> sqlx::Result<()> {
+    sqlx::query(&format!(
+        r#"
+update {0}_data
+set translated_description = translated_description || {0}.translated_description_override
+from {0}
+where {0}.id = $1 and {0}_data.id = {0}.live_id and {0}.translated_description_override <> '{{}}'
+"#,
+        asset_type.as_str()
+    ))
+    .bind(id)
+    .execute(&mut *txn)
+    .await?;
+
+    Ok(())
+}
@@ -18,7 +18,10 @@ use std::{convert::TryFrom, fmt::Debug, str::FromStr};
 use tracing::{instrument, Instrument};
 use uuid::Uuid;
 
+pub(crate) mod api_token;
+pub(crate) mod oauth;
 pub(crate) mod public_user;
+pub(crate) mod totp;
 
 use super::{nul_if_empty, recycle_metadata};
 use crate::error;
@@ -51,7 +54,10 @@ select
     given_name,
     family_name,
     profile_image_id       as "profile_image?: ImageId",
-    (select case when exists(select * from user_auth_google where user_id = $1) = true then 1 else 0 end)     as "is_oauth!: bool",
+    (exists(select 1 from user_auth_google where user_id = $1)
+        or exists(select 1 from user_auth_microsoft where user_id = $1)
+        or exists(select 1 from user_auth_apple where user_id = $1)
+        or exists(select 1 from user_auth_sso where user_id = $1)) as "is_oauth!: bool",
     languages_spoken         as "languages_spoken!: Vec<String>",
     language_app,
     language_emails,
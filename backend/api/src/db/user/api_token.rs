@@ -0,0 +1,145 @@
+use chrono::{DateTime, Utc};
+use shared::domain::user::{ApiToken, ApiTokenId, UserId, UserScope};
+use sqlx::PgPool;
+use tracing::instrument;
+
+use crate::token;
+
+/// An unexpired API token, as used to authorize a request.
+pub struct ValidApiToken {
+    pub id: ApiTokenId,
+    pub user_id: UserId,
+    pub scopes: Vec<UserScope>,
+    pub read_only: bool,
+}
+
+fn scopes_from_db(scopes: Vec<i16>) -> Vec<UserScope> {
+    // scopes which no longer exist don't grant anything
+    scopes
+        .into_iter()
+        .filter_map(|scope| UserScope::try_from(scope).ok())
+        .collect()
+}
+
+#[instrument(skip(db, token_hash))]
+pub async fn create(
+    db: &PgPool,
+    user_id: UserId,
+    name: &str,
+    token_hash: &[u8],
+    scopes: &[UserScope],
+    read_only: bool,
+    expires_at: Option<DateTime<Utc>>,
+) -> sqlx::Result<ApiTokenId> {
+    let scopes: Vec<_> = scopes.iter().map(|scope| *scope as i16).collect();
+
+    sqlx::query!(
+        r#"
+insert into user_api_token (user_id, name, token_hash, scopes, read_only, expires_at)
+values ($1, $2, $3, $4, $5, $6)
+returning id as "id: ApiTokenId"
+"#,
+        user_id.0,
+        name,
+        token_hash,
+        &scopes[..],
+        read_only,
+        expires_at,
+    )
+    .fetch_one(db)
+    .await
+    .map(|it| it.id)
+}
+
+/// Lists the user's tokens, including expired ones, most recently created first.
+#[instrument(skip(db))]
+pub async fn list(db: &PgPool, user_id: UserId) -> sqlx::Result<Vec<ApiToken>> {
+    let tokens = sqlx::query!(
+        r#"
+select id as "id: ApiTokenId",
+       name,
+       scopes,
+       read_only,
+       created_at,
+       last_used_at,
+       expires_at
+from user_api_token
+where user_id = $1
+order by created_at desc
+"#,
+        user_id.0,
+    )
+    .fetch_all(db)
+    .await?
+    .into_iter()
+    .map(|it| ApiToken {
+        id: it.id,
+        name: it.name,
+        scopes: scopes_from_db(it.scopes),
+        read_only: it.read_only,
+        created_at: it.created_at,
+        last_used_at: it.last_used_at,
+        expires_at: it.expires_at,
+    })
+    .collect();
+
+    Ok(tokens)
+}
+
+/// Deletes one of the user's tokens, returning whether it existed.
+#[instrument(skip(db))]
+pub async fn revoke(db: &PgPool, user_id: UserId, id: ApiTokenId) -> sqlx::Result<bool> {
+    let res = sqlx::query!(
+        "delete from user_api_token where id = $1 and user_id = $2",
+        id.0,
+        user_id.0,
+    )
+    .execute(db)
+    .await?;
+
+    Ok(res.rows_affected() > 0)
+}
+
+/// Finds the unexpired token with the given secret, and records that it was used.
+#[instrument(skip_all)]
+pub async fn authenticate(db: &PgPool, token: &str) -> sqlx::Result<Option<ValidApiToken>> {
+    let row = sqlx::query!(
+        r#"
+select id as "id: ApiTokenId",
+       user_id as "user_id: UserId",
+       scopes,
+       read_only
+from user_api_token
+where token_hash = $1
+  and expires_at < now() is not true
+"#,
+        &token::hash_api_token(token)[..],
+    )
+    .fetch_optional(db)
+    .await?;
+
+    let row = match row {
+        Some(row) => row,
+        None => return Ok(None),
+    };
+
+    // at most once a minute, like sessions
+    sqlx::query!(
+        r#"
+update user_api_token
+set last_used_at = now()
+where id = $1
+  and (last_used_at is null or last_used_at < now() - interval '1 minute')
+"#,
+        row.id.0,
+    )
+    .execute(db)
+    .await?;
+
+    Ok(Some(ValidApiToken {
+        id: row.id,
+        user_id: row.user_id,
+        scopes: scopes_from_db(row.scopes),
+        read_only: row.read_only,
+    }))
+}
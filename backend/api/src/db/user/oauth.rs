@@ -0,0 +1,131 @@
+use shared::domain::{session::OAuthProvider, user::UserId};
+use sqlx::PgConnection;
+use tracing::instrument;
+
+/// Finds the user who signs in with the given account of an OAuth provider.
+#[instrument(skip(txn))]
+pub async fn find(
+    txn: &mut PgConnection,
+    provider: OAuthProvider,
+    provider_id: &str,
+) -> sqlx::Result<Option<UserId>> {
+    let user_id = match provider {
+        OAuthProvider::Google => sqlx::query!(
+            r#"select user_id as "user_id: UserId" from user_auth_google where google_id = $1"#,
+            provider_id
+        )
+        .fetch_optional(txn)
+        .await?
+        .map(|it| it.user_id),
+
+        OAuthProvider::Microsoft => sqlx::query!(
+            r#"select user_id as "user_id: UserId" from user_auth_microsoft where microsoft_id = $1"#,
+            provider_id
+        )
+        .fetch_optional(txn)
+        .await?
+        .map(|it| it.user_id),
+
+        OAuthProvider::Apple => sqlx::query!(
+            r#"select user_id as "user_id: UserId" from user_auth_apple where apple_id = $1"#,
+            provider_id
+        )
+        .fetch_optional(txn)
+        .await?
+        .map(|it| it.user_id),
+
+        OAuthProvider::Sso => sqlx::query!(
+            r#"select user_id as "user_id: UserId" from user_auth_sso where sso_id = $1"#,
+            provider_id
+        )
+        .fetch_optional(txn)
+        .await?
+        .map(|it| it.user_id),
+    };
+
+    Ok(user_id)
+}
+
+/// Lets the user sign in with the given account of an OAuth provider.
+///
+/// A user can only link one account of each provider.
+#[instrument(skip(txn))]
+pub async fn link(
+    txn: &mut PgConnection,
+    user_id: UserId,
+    provider: OAuthProvider,
+    provider_id: &str,
+) -> sqlx::Result<()> {
+    match provider {
+        OAuthProvider::Google => {
+            sqlx::query!(
+                "insert into user_auth_google (user_id, google_id) values ($1, $2)",
+                user_id.0,
+                provider_id
+            )
+            .execute(txn)
+            .await?
+        }
+
+        OAuthProvider::Microsoft => {
+            sqlx::query!(
+                "insert into user_auth_microsoft (user_id, microsoft_id) values ($1, $2)",
+                user_id.0,
+                provider_id
+            )
+            .execute(txn)
+            .await?
+        }
+
+        OAuthProvider::Apple => {
+            sqlx::query!(
+                "insert into user_auth_apple (user_id, apple_id) values ($1, $2)",
+                user_id.0,
+                provider_id
+            )
+            .execute(txn)
+            .await?
+        }
+
+        OAuthProvider::Sso => {
+            sqlx::query!(
+                "insert into user_auth_sso (user_id, sso_id) values ($1, $2)",
+                user_id.0,
+                provider_id
+            )
+            .execute(txn)
+            .await?
+        }
+    };
+
+    Ok(())
+}
+
+/// Gets a provider the user can sign in with, if they don't have a password.
+#[instrument(skip(txn))]
+pub async fn get_provider(
+    txn: &mut PgConnection,
+    user_id: UserId,
+) -> sqlx::Result<Option<OAuthProvider>> {
+    let row = sqlx::query!(
+        r#"
+select exists(select 1 from user_auth_google where user_id = $1)    as "google!",
+       exists(select 1 from user_auth_microsoft where user_id = $1) as "microsoft!",
+       exists(select 1 from user_auth_apple where user_id = $1)     as "apple!",
+       exists(select 1 from user_auth_sso where user_id = $1)       as "sso!"
+"#,
+        user_id.0
+    )
+    .fetch_one(txn)
+    .await?;
+
+    let provider = match (row.google, row.microsoft, row.apple, row.sso) {
+        (true, _, _, _) => Some(OAuthProvider::Google),
+        (_, true, _, _) => Some(OAuthProvider::Microsoft),
+        (_, _, true, _) => Some(OAuthProvider::Apple),
+        (_, _, _, true) => Some(OAuthProvider::Sso),
+        _ => None,
+    };
+
+    Ok(provider)
+}
@@ -1,17 +1,25 @@
-use crate::{error, extractor::TokenUser};
+use crate::{db, error, extractor::TokenUser};
 // use serde_json::value::Value;
+use chrono::{DateTime, TimeZone, Utc};
+use serde_json::json;
 use shared::domain::{
     additional_resource::{AdditionalResource, AdditionalResourceId as AddId, ResourceContent},
-    asset::UserOrMe,
+    asset::{AssetId, UserOrMe},
     circle::CircleId,
+    course::CourseId,
     image::ImageId,
+    jig::JigId,
     meta::ResourceTypeId as TypeId,
+    notification::NotificationKind,
+    playlist::PlaylistId,
+    resource::ResourceId,
     user::{
-        public_user::{OrderBy, PublicUser},
+        public_user::{OrderBy, PublicUser, UserFeedActivity, UserFeedItem},
         UserBadge, UserId,
     },
 };
 use sqlx::PgPool;
+use std::{fmt, str::FromStr};
 use uuid::Uuid;
 
 use super::get_location;
@@ -301,6 +309,14 @@ pub async fn follow(
     .await
     .map_err(|_| anyhow::anyhow!("User already follows target user"))?;
 
+    db::notification::create(
+        &mut txn,
+        &[user_id.0],
+        NotificationKind::NewFollower,
+        &json!({ "followerId": follower_id }),
+    )
+    .await?;
+
     txn.commit().await?;
 
     Ok(())
@@ -766,6 +782,164 @@ pub async fn total_following_count(db: &PgPool, follower_id: UserId) -> sqlx::Re
     Ok(total_following.count as u64)
 }
 
+/// Position in the activity feed, i.e. the last item of a page.
+///
+/// Items are ordered by all of these, since many can happen at the same time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeedCursor {
+    created_at: DateTime<Utc>,
+    kind: i16,
+    id: Uuid,
+    user_id: Uuid,
+}
+
+impl fmt::Display for FeedCursor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}.{}.{}.{}",
+            self.created_at.timestamp_micros(),
+            self.kind,
+            self.id.simple(),
+            self.user_id.simple()
+        )
+    }
+}
+
+impl FromStr for FeedCursor {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split('.');
+        let mut next = || {
+            parts
+                .next()
+                .ok_or_else(|| anyhow::anyhow!("Invalid feed cursor"))
+        };
+
+        let micros: i64 = next()?.parse()?;
+        let created_at = Utc
+            .timestamp_opt(
+                micros.div_euclid(1_000_000),
+                (micros.rem_euclid(1_000_000) * 1000) as u32,
+            )
+            .single()
+            .ok_or_else(|| anyhow::anyhow!("Invalid feed cursor"))?;
+
+        Ok(Self {
+            created_at,
+            kind: next()?.parse()?,
+            id: next()?.parse()?,
+            user_id: next()?.parse()?,
+        })
+    }
+}
+
+/// Activity of the users `follower_id` follows, after `cursor`, most recent first.
+///
+/// Only public assets which haven't been blocked are included.
+///
+/// Also returns the cursor of the next page, if there is one.
+pub async fn feed(
+    db: &PgPool,
+    follower_id: UserId,
+    cursor: Option<FeedCursor>,
+    limit: u32,
+) -> sqlx::Result<(Vec<UserFeedItem>, Option<FeedCursor>)> {
+    let mut rows = sqlx::query!(
+        //language=SQL
+        r#"
+with followed as (select user_id from user_follow where follower_id = $1)
+select kind       as "kind!: i16",
+       id         as "id!",
+       user_id    as "user_id!",
+       created_at as "created_at!"
+from (select 0::int2 as kind, jig.id, jig.author_id as user_id, jig.published_at as created_at
+      from jig
+               inner join jig_data on jig_data.id = jig.live_id
+               left join jig_admin_data on jig_admin_data.jig_id = jig.id
+      where jig.author_id in (select user_id from followed)
+        and jig.published_at is not null
+        and jig_data.privacy_level = 0
+        and not coalesce(jig_admin_data.blocked, false)
+      union all
+      select 1::int2, resource.id, resource.author_id, resource.published_at
+      from resource
+               inner join resource_data on resource_data.id = resource.live_id
+               left join resource_admin_data on resource_admin_data.resource_id = resource.id
+      where resource.author_id in (select user_id from followed)
+        and resource.published_at is not null
+        and resource_data.privacy_level = 0
+        and not coalesce(resource_admin_data.blocked, false)
+      union all
+      select 2::int2, playlist.id, playlist.author_id, playlist.published_at
+      from playlist
+               inner join playlist_data on playlist_data.id = playlist.live_id
+      where playlist.author_id in (select user_id from followed)
+        and playlist.published_at is not null
+        and playlist_data.privacy_level = 0
+      union all
+      select 3::int2, course.id, course.author_id, course.published_at
+      from course
+               inner join course_data on course_data.id = course.live_id
+      where course.author_id in (select user_id from followed)
+        and course.published_at is not null
+        and course_data.privacy_level = 0
+      union all
+      select 4::int2, circle_member.id, circle_member.user_id, circle_member.joined_at
+      from circle_member
+      where circle_member.user_id in (select user_id from followed)) feed
+where $2::timestamptz is null
+   or (created_at, kind, id, user_id) < ($2, $3, $4, $5)
+order by created_at desc, kind desc, id desc, user_id desc
+limit $6
+"#,
+        follower_id.0,
+        cursor.map(|it| it.created_at),
+        cursor.map(|it| it.kind),
+        cursor.map(|it| it.id),
+        cursor.map(|it| it.user_id),
+        // one more than requested to know whether there's another page
+        limit as i64 + 1,
+    )
+    .fetch_all(db)
+    .await?;
+
+    let next_cursor = if rows.len() > limit as usize {
+        rows.truncate(limit as usize);
+        rows.last().map(|row| FeedCursor {
+            created_at: row.created_at,
+            kind: row.kind,
+            id: row.id,
+            user_id: row.user_id,
+        })
+    } else {
+        None
+    };
+
+    let items = rows
+        .into_iter()
+        .filter_map(|row| {
+            let activity = match row.kind {
+                0 => UserFeedActivity::Published(AssetId::JigId(JigId(row.id))),
+                1 => UserFeedActivity::Published(AssetId::ResourceId(ResourceId(row.id))),
+                2 => UserFeedActivity::Published(AssetId::PlaylistId(PlaylistId(row.id))),
+                3 => UserFeedActivity::Published(AssetId::CourseId(CourseId(row.id))),
+                4 => UserFeedActivity::JoinedCircle(CircleId(row.id)),
+                _ => return None,
+            };
+
+            Some(UserFeedItem {
+                user_id: UserId(row.user_id),
+                activity,
+                created_at: row.created_at,
+            })
+        })
+        .collect();
+
+    Ok((items, next_cursor))
+}
+
 fn filters_for_ids_or<T: Into<Uuid> + Copy>(ids: &[T]) -> Vec<Uuid> {
     let mut vect: Vec<Uuid> = vec![];
     for id in ids.iter().copied() {
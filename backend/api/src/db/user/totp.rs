@@ -0,0 +1,199 @@
+use chrono::{DateTime, Utc};
+use shared::domain::user::{UserId, UserScope};
+use sqlx::{PgConnection, PgPool};
+use tracing::instrument;
+
+use crate::totp;
+
+pub struct UserTotp {
+    pub secret: Vec<u8>,
+    pub enabled: bool,
+}
+
+#[instrument(skip(db))]
+pub async fn get(db: &PgPool, user_id: UserId) -> sqlx::Result<Option<UserTotp>> {
+    sqlx::query_as!(
+        UserTotp,
+        r#"
+select secret, enabled_at is not null as "enabled!"
+from user_totp
+where user_id = $1
+"#,
+        user_id.0,
+    )
+    .fetch_optional(db)
+    .await
+}
+
+#[instrument(skip(db))]
+pub async fn is_enabled(db: &PgPool, user_id: UserId) -> sqlx::Result<bool> {
+    let enabled = sqlx::query!(
+        r#"
+select exists(select 1 from user_totp where user_id = $1 and enabled_at is not null) as "enabled!"
+"#,
+        user_id.0,
+    )
+    .fetch_one(db)
+    .await?
+    .enabled;
+
+    Ok(enabled)
+}
+
+/// Two-factor authentication is mandatory for admins.
+#[instrument(skip(db))]
+pub async fn is_required(db: &PgPool, user_id: UserId) -> sqlx::Result<bool> {
+    super::has_scopes(db, user_id, &[UserScope::Admin, UserScope::AdminAsset]).await
+}
+
+/// Sets the secret of a user who hasn't enabled two-factor authentication yet.
+///
+/// Returns `false` if two-factor authentication is already enabled.
+#[instrument(skip(db, secret))]
+pub async fn set_secret(db: &PgPool, user_id: UserId, secret: &[u8]) -> sqlx::Result<bool> {
+    let res = sqlx::query!(
+        r#"
+insert into user_totp (user_id, secret)
+values ($1, $2)
+on conflict (user_id) do update
+set secret     = $2,
+    created_at = now()
+where user_totp.enabled_at is null
+"#,
+        user_id.0,
+        secret,
+    )
+    .execute(db)
+    .await?;
+
+    Ok(res.rows_affected() > 0)
+}
+
+#[instrument(skip(txn))]
+pub async fn enable(txn: &mut PgConnection, user_id: UserId) -> sqlx::Result<()> {
+    sqlx::query!(
+        "update user_totp set enabled_at = now() where user_id = $1",
+        user_id.0,
+    )
+    .execute(txn)
+    .await?;
+
+    Ok(())
+}
+
+#[instrument(skip(db))]
+pub async fn disable(db: &PgPool, user_id: UserId) -> sqlx::Result<()> {
+    let mut txn = db.begin().await?;
+
+    sqlx::query!(
+        "delete from user_totp_recovery_code where user_id = $1",
+        user_id.0
+    )
+    .execute(&mut txn)
+    .await?;
+
+    sqlx::query!("delete from user_totp where user_id = $1", user_id.0)
+        .execute(&mut txn)
+        .await?;
+
+    txn.commit().await?;
+
+    Ok(())
+}
+
+/// Checks a code from the user's authenticator app, or one of their unused recovery codes.
+///
+/// Codes from the authenticator app can only be used once, and recovery codes are marked as used.
+#[instrument(skip(txn, secret, code))]
+pub async fn check_code(
+    txn: &mut PgConnection,
+    user_id: UserId,
+    secret: &[u8],
+    code: &str,
+    now: DateTime<Utc>,
+) -> sqlx::Result<bool> {
+    if let Some(step) = totp::verify(secret, code, now) {
+        let res = sqlx::query!(
+            r#"
+update user_totp
+set last_used_step = $2
+where user_id = $1
+  and (last_used_step is null or last_used_step < $2)
+"#,
+            user_id.0,
+            step,
+        )
+        .execute(&mut *txn)
+        .await?;
+
+        return Ok(res.rows_affected() > 0);
+    }
+
+    let res = sqlx::query!(
+        r#"
+update user_totp_recovery_code
+set used_at = now()
+where user_id = $1
+  and code_hash = $2
+  and used_at is null
+"#,
+        user_id.0,
+        &totp::hash_recovery_code(code),
+    )
+    .execute(&mut *txn)
+    .await?;
+
+    Ok(res.rows_affected() > 0)
+}
+
+/// Replaces all of the user's recovery codes.
+#[instrument(skip(txn, codes))]
+pub async fn replace_recovery_codes(
+    txn: &mut PgConnection,
+    user_id: UserId,
+    codes: &[String],
+) -> sqlx::Result<()> {
+    sqlx::query!(
+        "delete from user_totp_recovery_code where user_id = $1",
+        user_id.0
+    )
+    .execute(&mut *txn)
+    .await?;
+
+    let hashes: Vec<Vec<u8>> = codes
+        .iter()
+        .map(|code| totp::hash_recovery_code(code))
+        .collect();
+
+    sqlx::query!(
+        r#"
+insert into user_totp_recovery_code (user_id, code_hash)
+select $1, code_hash
+from unnest($2::bytea[]) as code_hash
+"#,
+        user_id.0,
+        &hashes[..],
+    )
+    .execute(&mut *txn)
+    .await?;
+
+    Ok(())
+}
+
+#[instrument(skip(db))]
+pub async fn recovery_codes_left(db: &PgPool, user_id: UserId) -> sqlx::Result<u32> {
+    let count = sqlx::query!(
+        r#"
+select count(*) as "count!"
+from user_totp_recovery_code
+where user_id = $1
+  and used_at is null
+"#,
+        user_id.0,
+    )
+    .fetch_one(db)
+    .await?
+    .count;
+
+    Ok(count as u32)
+}
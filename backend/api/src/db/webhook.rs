@@ -0,0 +1,381 @@
+use chrono::{DateTime, Utc};
+use shared::domain::{
+    billing::{AccountId, StripeSubscriptionId, SubscriptionStatus},
+    jig::JigId,
+    resource::ResourceId,
+    user::{UserId, UserScope},
+    webhook::{
+        Webhook, WebhookDelivery, WebhookDeliveryId, WebhookDeliveryStatus, WebhookEvent, WebhookId,
+    },
+};
+use sqlx::{PgConnection, PgPool};
+use tracing::instrument;
+use uuid::Uuid;
+
+fn events_to_db(events: &[WebhookEvent]) -> Vec<i16> {
+    events.iter().map(|event| *event as i16).collect()
+}
+
+fn events_from_db(events: Vec<i16>) -> Vec<WebhookEvent> {
+    events
+        .into_iter()
+        .filter_map(|event| WebhookEvent::try_from(event).ok())
+        .collect()
+}
+
+#[instrument(skip(db, secret))]
+pub async fn create(
+    db: &PgPool,
+    user_id: UserId,
+    url: &str,
+    secret: &str,
+    events: &[WebhookEvent],
+    all_users: bool,
+) -> sqlx::Result<WebhookId> {
+    sqlx::query!(
+        //language=SQL
+        r#"
+insert into webhook (user_id, url, secret, events, all_users)
+values ($1, $2, $3, $4, $5)
+returning id as "id: WebhookId"
+"#,
+        user_id.0,
+        url,
+        secret,
+        &events_to_db(events)[..],
+        all_users,
+    )
+    .fetch_one(db)
+    .await
+    .map(|it| it.id)
+}
+
+#[instrument(skip(db))]
+pub async fn list(db: &PgPool, user_id: UserId) -> sqlx::Result<Vec<Webhook>> {
+    let webhooks = sqlx::query!(
+        //language=SQL
+        r#"
+select id as "id: WebhookId",
+       url,
+       events,
+       all_users,
+       enabled,
+       created_at,
+       updated_at
+from webhook
+where user_id = $1
+order by created_at desc
+"#,
+        user_id.0,
+    )
+    .fetch_all(db)
+    .await?
+    .into_iter()
+    .map(|it| Webhook {
+        id: it.id,
+        url: it.url,
+        events: events_from_db(it.events),
+        all_users: it.all_users,
+        enabled: it.enabled,
+        created_at: it.created_at,
+        updated_at: it.updated_at,
+    })
+    .collect();
+
+    Ok(webhooks)
+}
+
+/// Returns whether the webhook exists and belongs to the user.
+#[instrument(skip(db))]
+pub async fn exists(db: &PgPool, user_id: UserId, id: WebhookId) -> sqlx::Result<bool> {
+    sqlx::query!(
+        //language=SQL
+        r#"select exists(select 1 from webhook where id = $1 and user_id = $2) as "exists!""#,
+        id.0,
+        user_id.0,
+    )
+    .fetch_one(db)
+    .await
+    .map(|it| it.exists)
+}
+
+/// Updates the fields which are `Some`, returning whether the webhook exists and belongs to the user.
+#[instrument(skip(db))]
+pub async fn update(
+    db: &PgPool,
+    user_id: UserId,
+    id: WebhookId,
+    url: Option<&str>,
+    events: Option<&[WebhookEvent]>,
+    enabled: Option<bool>,
+) -> sqlx::Result<bool> {
+    let res = sqlx::query!(
+        //language=SQL
+        r#"
+update webhook
+set url        = coalesce($3, url),
+    events     = coalesce($4, events),
+    enabled    = coalesce($5, enabled),
+    updated_at = now()
+where id = $1
+  and user_id = $2
+"#,
+        id.0,
+        user_id.0,
+        url,
+        events.map(events_to_db) as Option<Vec<i16>>,
+        enabled,
+    )
+    .execute(db)
+    .await?;
+
+    Ok(res.rows_affected() > 0)
+}
+
+#[instrument(skip(db))]
+pub async fn delete(db: &PgPool, user_id: UserId, id: WebhookId) -> sqlx::Result<bool> {
+    let res = sqlx::query!(
+        //language=SQL
+        "delete from webhook where id = $1 and user_id = $2",
+        id.0,
+        user_id.0,
+    )
+    .execute(db)
+    .await?;
+
+    Ok(res.rows_affected() > 0)
+}
+
+#[instrument(skip(db))]
+pub async fn list_deliveries(db: &PgPool, id: WebhookId) -> sqlx::Result<Vec<WebhookDelivery>> {
+    let deliveries = sqlx::query!(
+        //language=SQL
+        r#"
+select id as "id: WebhookDeliveryId",
+       event as "event: WebhookEvent",
+       status as "status: WebhookDeliveryStatus",
+       attempts,
+       response_status,
+       error,
+       created_at,
+       last_attempt_at,
+       next_attempt_at
+from webhook_delivery
+where webhook_id = $1
+order by created_at desc
+limit 100
+"#,
+        id.0,
+    )
+    .fetch_all(db)
+    .await?
+    .into_iter()
+    .map(|it| WebhookDelivery {
+        id: it.id,
+        event: it.event,
+        status: it.status,
+        attempts: it.attempts as u32,
+        response_status: it.response_status.map(|status| status as u16),
+        error: it.error,
+        created_at: it.created_at,
+        last_attempt_at: it.last_attempt_at,
+        next_attempt_at: it.next_attempt_at,
+    })
+    .collect();
+
+    Ok(deliveries)
+}
+
+/// Queues a delivery of the event to each enabled webhook subscribed to it, which is either owned
+/// by one of `user_ids` or receives every user's events.
+///
+/// Webhooks only receive every user's events while their owner is an admin, so they stop as soon
+/// as the owner loses the scope.
+///
+/// Returns whether any deliveries were queued.
+#[instrument(skip(txn, data))]
+pub async fn enqueue(
+    txn: &mut PgConnection,
+    event: WebhookEvent,
+    user_ids: &[Uuid],
+    data: &serde_json::Value,
+) -> sqlx::Result<bool> {
+    let res = sqlx::query!(
+        //language=SQL
+        r#"
+insert into webhook_delivery (webhook_id, event, data)
+select id, $1, $3
+from webhook
+where enabled
+  and $1 = any (events)
+  and (user_id = any ($2)
+    or (all_users and exists(select 1 from user_scope where user_scope.user_id = webhook.user_id and scope = $4)))
+"#,
+        event as i16,
+        user_ids,
+        data,
+        UserScope::Admin as i16,
+    )
+    .execute(txn)
+    .await?;
+
+    Ok(res.rows_affected() > 0)
+}
+
+/// The author of a JIG, who is sent events about it.
+pub async fn jig_author(txn: &mut PgConnection, id: JigId) -> sqlx::Result<Option<UserId>> {
+    sqlx::query!(
+        //language=SQL
+        r#"select author_id as "author_id: UserId" from jig where id = $1"#,
+        id.0,
+    )
+    .fetch_optional(txn)
+    .await
+    .map(|it| it.and_then(|it| it.author_id))
+}
+
+/// The author of a resource, who is sent events about it.
+pub async fn resource_author(
+    txn: &mut PgConnection,
+    id: ResourceId,
+) -> sqlx::Result<Option<UserId>> {
+    sqlx::query!(
+        //language=SQL
+        r#"select author_id as "author_id: UserId" from resource where id = $1"#,
+        id.0,
+    )
+    .fetch_optional(txn)
+    .await
+    .map(|it| it.and_then(|it| it.author_id))
+}
+
+/// The account a subscription belongs to, the subscription's status, and the account's admins,
+/// who are sent events about it.
+pub async fn subscription_account_admins(
+    txn: &mut PgConnection,
+    stripe_subscription_id: &StripeSubscriptionId,
+) -> sqlx::Result<Option<(AccountId, SubscriptionStatus, Vec<UserId>)>> {
+    let row = sqlx::query!(
+        //language=SQL
+        r#"
+select account_id as "account_id!: AccountId",
+       status     as "status!: SubscriptionStatus",
+       array(select user_id
+             from user_account
+             where user_account.account_id = subscription.account_id
+               and admin) as "admin_ids!"
+from subscription
+where stripe_subscription_id = $1
+"#,
+        stripe_subscription_id as &StripeSubscriptionId,
+    )
+    .fetch_optional(txn)
+    .await?;
+
+    Ok(row.map(|row| {
+        (
+            row.account_id,
+            row.status,
+            row.admin_ids.into_iter().map(UserId).collect(),
+        )
+    }))
+}
+
+/// A delivery which is due to be attempted.
+pub struct DueDelivery {
+    pub id: WebhookDeliveryId,
+    pub url: String,
+    pub secret: String,
+    pub event: WebhookEvent,
+    pub data: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+    pub attempts: i16,
+}
+
+/// Claims up to `limit` due deliveries of enabled webhooks.
+///
+/// Claimed deliveries aren't due again for `lease_secs`, so that other instances don't attempt them at the same time.
+#[instrument(skip(db))]
+pub async fn claim_due(db: &PgPool, limit: i64, lease_secs: f64) -> sqlx::Result<Vec<DueDelivery>> {
+    sqlx::query_as!(
+        DueDelivery,
+        //language=SQL
+        r#"
+update webhook_delivery
+set next_attempt_at = now() + $2::float8 * interval '1 second'
+from webhook
+where webhook.id = webhook_delivery.webhook_id
+  and webhook_delivery.id in (select webhook_delivery.id
+                              from webhook_delivery
+                                       inner join webhook on webhook.id = webhook_delivery.webhook_id
+                              where next_attempt_at <= now()
+                                and webhook.enabled
+                              order by next_attempt_at
+                              limit $1 for update of webhook_delivery skip locked)
+returning webhook_delivery.id as "id: WebhookDeliveryId",
+          webhook.url,
+          webhook.secret,
+          webhook_delivery.event as "event: WebhookEvent",
+          webhook_delivery.data,
+          webhook_delivery.created_at,
+          webhook_delivery.attempts
+"#,
+        limit,
+        lease_secs,
+    )
+    .fetch_all(db)
+    .await
+}
+
+/// Records the outcome of an attempt.
+///
+/// The delivery is retried at `next_attempt_at` if it's `Pending`.
+#[instrument(skip(db))]
+pub async fn record_attempt(
+    db: &PgPool,
+    id: WebhookDeliveryId,
+    status: WebhookDeliveryStatus,
+    response_status: Option<u16>,
+    error: Option<&str>,
+    next_attempt_at: Option<DateTime<Utc>>,
+) -> sqlx::Result<()> {
+    sqlx::query!(
+        //language=SQL
+        r#"
+update webhook_delivery
+set status          = $2,
+    attempts        = attempts + 1,
+    response_status = $3,
+    error           = $4,
+    last_attempt_at = now(),
+    next_attempt_at = $5
+where id = $1
+"#,
+        id.0,
+        status as i16,
+        response_status.map(|status| status as i16),
+        error,
+        next_attempt_at,
+    )
+    .execute(db)
+    .await?;
+
+    Ok(())
+}
+
+/// Deletes deliveries older than 30 days, so that the log doesn't grow forever.
+pub async fn delete_old_deliveries(db: &PgPool) -> sqlx::Result<u64> {
+    let res = sqlx::query!(
+        //language=SQL
+        r#"
+delete
+from webhook_delivery
+where created_at < now() - interval '30 days'
+  and next_attempt_at is null
+"#
+    )
+    .execute(db)
+    .await?;
+
+    Ok(res.rows_affected())
+}
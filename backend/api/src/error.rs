@@ -10,6 +10,13 @@ use actix_web::{
     error::{JsonPayloadError, PathError, QueryPayloadError},
     HttpResponse, ResponseError,
 };
+use shared::config::JIG_PLAYER_SESSION_DISPLAY_NAME_MAX_LEN;
+use shared::domain::circle::{
+    library::CIRCLE_LIBRARY_NOTE_MAX_LEN,
+    post::{CIRCLE_POST_MAX_ASSETS, CIRCLE_POST_MAX_LEN},
+};
+use shared::domain::review::{REVIEW_RATING_MAX, REVIEW_RATING_MIN, REVIEW_TEXT_MAX_LEN};
+use shared::domain::roster::ROSTER_NAME_MAX_LEN;
 use shared::error::{ApiError, EmptyError, MetadataNotFound};
 use stripe::StripeError;
 
@@ -28,8 +35,10 @@ pub use user::{
 
 pub mod event_arc;
 pub use event_arc::EventArc;
+use shared::domain::asset::CurationStatus;
 use shared::domain::billing::{AccountType, SchoolNameId, SubscriptionType};
 use shared::domain::meta::MetaKind;
+use shared::domain::user::UserScope;
 
 /// Represents an error returned by the api.
 // mostly used in this module
@@ -171,6 +180,8 @@ pub enum ServiceKind {
     GoogleCloudStorage,
     GoogleCloudEventArc,
     GoogleOAuth,
+    MicrosoftOAuth,
+    AppleOAuth,
     GoogleCloudAccessKeyStore,
     Mail,
     FirebaseCloudMessaging,
@@ -187,6 +198,8 @@ impl ServiceKind {
             Self::GoogleCloudStorage => "Google Cloud Storage",
             Self::GoogleCloudEventArc => "Google Cloud EventArc",
             Self::GoogleOAuth => "Google OAuth",
+            Self::MicrosoftOAuth => "Microsoft OAuth",
+            Self::AppleOAuth => "Sign in with Apple",
             Self::GoogleCloudAccessKeyStore => "Google Cloud Access Key Store",
             Self::Mail => "Sendgrid Mail",
             Self::FirebaseCloudMessaging => "Firebase Cloud Messaging",
@@ -242,6 +255,31 @@ impl Into<actix_web::Error> for Service {
     }
 }
 
+/// The client made too many requests (see [`crate::rate_limit`]), and has to wait before retrying.
+#[derive(Debug)]
+pub struct TooManyRequests {
+    pub retry_after: std::time::Duration,
+}
+
+impl Into<actix_web::Error> for TooManyRequests {
+    fn into(self) -> actix_web::Error {
+        // `Retry-After` is in whole seconds, don't let clients retry too early.
+        let retry_after = self.retry_after.as_secs_f64().ceil().max(1.0) as u64;
+
+        let resp = HttpResponse::TooManyRequests()
+            .insert_header((
+                actix_web::http::header::RETRY_AFTER,
+                retry_after.to_string(),
+            ))
+            .json(BasicError::with_message(
+                http::StatusCode::TOO_MANY_REQUESTS,
+                format!("Too many requests, try again in {} seconds", retry_after),
+            ));
+
+        actix_web::error::InternalError::from_response("", resp).into()
+    }
+}
+
 #[derive(Debug)]
 pub enum ServiceSession {
     InternalServerError(anyhow::Error),
@@ -718,6 +756,9 @@ pub enum ReportError {
     InternalServerError(anyhow::Error),
     ResourceNotFound,
     SendEmailFail,
+    AlreadyResolved,
+    InvalidAssignee,
+    MissingNote,
 }
 
 impl<T: Into<anyhow::Error>> From<T> for ReportError {
@@ -741,6 +782,58 @@ impl Into<actix_web::Error> for ReportError {
                 "Failed to report to email".to_owned(),
             )
             .into(),
+            Self::AlreadyResolved => BasicError::with_message(
+                http::StatusCode::BAD_REQUEST,
+                "Report has already been resolved".to_owned(),
+            )
+            .into(),
+            Self::InvalidAssignee => BasicError::with_message(
+                http::StatusCode::BAD_REQUEST,
+                "Reports can only be assigned to curators".to_owned(),
+            )
+            .into(),
+            Self::MissingNote => BasicError::with_message(
+                http::StatusCode::BAD_REQUEST,
+                "A note is required when contacting the author".to_owned(),
+            )
+            .into(),
+        }
+    }
+}
+
+pub enum Curation {
+    InternalServerError(anyhow::Error),
+    ResourceNotFound,
+    InvalidTransition(CurationStatus, CurationStatus),
+    InvalidAssignee,
+}
+
+impl<T: Into<anyhow::Error>> From<T> for Curation {
+    fn from(e: T) -> Self {
+        Self::InternalServerError(e.into())
+    }
+}
+
+impl Into<actix_web::Error> for Curation {
+    fn into(self) -> actix_web::Error {
+        match self {
+            Self::InternalServerError(e) => ise(e),
+
+            Self::ResourceNotFound => BasicError::with_message(
+                http::StatusCode::NOT_FOUND,
+                "Resource Not Found".to_owned(),
+            )
+            .into(),
+            Self::InvalidTransition(from, to) => BasicError::with_message(
+                http::StatusCode::BAD_REQUEST,
+                format!("Curation status can't change from {:?} to {:?}", from, to),
+            )
+            .into(),
+            Self::InvalidAssignee => BasicError::with_message(
+                http::StatusCode::BAD_REQUEST,
+                "Curation can only be assigned to admins".to_owned(),
+            )
+            .into(),
         }
     }
 }
@@ -751,6 +844,17 @@ pub enum JigCode {
     Conflict,
     AllCodesUsed,
     Forbidden,
+    InstanceCompleted,
+    InvalidModule,
+    InvalidDisplayName,
+    InvalidStudent,
+    IncorrectPicturePassword,
+    InvalidAssignment,
+    AssignmentNotOpen,
+    AssignmentClosed,
+    AttemptLimitReached,
+    InvalidDuration,
+    SessionExpired,
 }
 
 impl<T: Into<anyhow::Error>> From<T> for JigCode {
@@ -787,6 +891,76 @@ impl Into<actix_web::Error> for JigCode {
                 "User does not have permissions for this jig".to_owned(),
             )
             .into(),
+
+            Self::InstanceCompleted => BasicError::with_message(
+                http::StatusCode::CONFLICT,
+                "This player session instance has already been completed".to_owned(),
+            )
+            .into(),
+
+            Self::InvalidModule => BasicError::with_message(
+                http::StatusCode::BAD_REQUEST,
+                "Module is not part of this jig".to_owned(),
+            )
+            .into(),
+
+            Self::InvalidDisplayName => BasicError::with_message(
+                http::StatusCode::BAD_REQUEST,
+                format!(
+                    "Display name must be between 1 and {} characters long",
+                    JIG_PLAYER_SESSION_DISPLAY_NAME_MAX_LEN
+                ),
+            )
+            .into(),
+
+            Self::InvalidStudent => BasicError::with_message(
+                http::StatusCode::BAD_REQUEST,
+                "Pick a student from the session's roster".to_owned(),
+            )
+            .into(),
+
+            Self::IncorrectPicturePassword => BasicError::with_message(
+                http::StatusCode::FORBIDDEN,
+                "Incorrect picture password".to_owned(),
+            )
+            .into(),
+
+            Self::InvalidAssignment => BasicError::with_message(
+                http::StatusCode::BAD_REQUEST,
+                "An assignment needs either JIGs or a playlist, and must close after it opens"
+                    .to_owned(),
+            )
+            .into(),
+
+            Self::AssignmentNotOpen => BasicError::with_message(
+                http::StatusCode::FORBIDDEN,
+                "This assignment isn't open yet".to_owned(),
+            )
+            .into(),
+
+            Self::AssignmentClosed => BasicError::with_message(
+                http::StatusCode::FORBIDDEN,
+                "This assignment is closed".to_owned(),
+            )
+            .into(),
+
+            Self::AttemptLimitReached => BasicError::with_message(
+                http::StatusCode::FORBIDDEN,
+                "No attempts left for this assignment".to_owned(),
+            )
+            .into(),
+
+            Self::InvalidDuration => BasicError::with_message(
+                http::StatusCode::BAD_REQUEST,
+                "Duration must be at least one second and at most a year".to_owned(),
+            )
+            .into(),
+
+            Self::SessionExpired => BasicError::with_message(
+                http::StatusCode::GONE,
+                "This player session has expired".to_owned(),
+            )
+            .into(),
         }
     }
 }
@@ -810,6 +984,9 @@ pub enum Billing {
     SubscriptionExists,
     SchoolNotFound,
     IncorrectPlanType(AccountType, SubscriptionType),
+    InvalidPromotionCode,
+    PromotionCodeRedeemed,
+    PromotionCodeExists,
 }
 
 impl<T: Into<anyhow::Error>> From<T> for Billing {
@@ -845,6 +1022,21 @@ impl Into<actix_web::Error> for Billing {
                 format!("Expected {expected}, found {found}"),
             )
             .into(),
+            Self::InvalidPromotionCode => BasicError::with_message(
+                http::StatusCode::BAD_REQUEST,
+                "Promotion code is not valid for this plan".into(),
+            )
+            .into(),
+            Self::PromotionCodeRedeemed => BasicError::with_message(
+                http::StatusCode::BAD_REQUEST,
+                "Promotion code has already been redeemed by this account".into(),
+            )
+            .into(),
+            Self::PromotionCodeExists => BasicError::with_message(
+                http::StatusCode::CONFLICT,
+                "Promotion code already exists".into(),
+            )
+            .into(),
         }
     }
 }
@@ -862,6 +1054,10 @@ pub enum Account {
     SchoolExists(SchoolNameId),
     NotFound(String),
     Forbidden,
+    /// The single sign-on configuration isn't valid.
+    InvalidSso(String),
+    /// Another school already signs in emails of the domain.
+    SsoDomainExists,
 }
 
 impl<T: Into<anyhow::Error>> From<T> for Account {
@@ -893,6 +1089,374 @@ impl Into<actix_web::Error> for Account {
                 BasicError::with_message(http::StatusCode::NOT_FOUND, message).into()
             }
             Self::Forbidden => BasicError::new(http::StatusCode::FORBIDDEN).into(),
+            Self::InvalidSso(message) => {
+                BasicError::with_message(http::StatusCode::BAD_REQUEST, message).into()
+            }
+            Self::SsoDomainExists => BasicError::with_message(
+                http::StatusCode::CONFLICT,
+                "Another school already uses one of the email domains".to_owned(),
+            )
+            .into(),
+        }
+    }
+}
+
+pub enum Translation {
+    InternalServerError(anyhow::Error),
+    ResourceNotFound,
+    TermExists(String),
+    UnsupportedLanguage(String),
+}
+
+impl<T: Into<anyhow::Error>> From<T> for Translation {
+    fn from(e: T) -> Self {
+        Self::InternalServerError(e.into())
+    }
+}
+
+impl Into<actix_web::Error> for Translation {
+    fn into(self) -> actix_web::Error {
+        match self {
+            Self::InternalServerError(e) => ise(e),
+            Self::ResourceNotFound => BasicError::with_message(
+                http::StatusCode::NOT_FOUND,
+                "Resource Not Found".to_owned(),
+            )
+            .into(),
+            Self::TermExists(term) => BasicError::with_message(
+                http::StatusCode::CONFLICT,
+                format!("Glossary term {term} already exists"),
+            )
+            .into(),
+            Self::UnsupportedLanguage(language) => BasicError::with_message(
+                http::StatusCode::BAD_REQUEST,
+                format!("Language {language} is not supported for translation"),
+            )
+            .into(),
+        }
+    }
+}
+
+pub enum Roster {
+    InternalServerError(anyhow::Error),
+    ResourceNotFound,
+    InvalidName,
+    NicknameExists(String),
+}
+
+impl<T: Into<anyhow::Error>> From<T> for Roster {
+    fn from(e: T) -> Self {
+        Self::InternalServerError(e.into())
+    }
+}
+
+impl Into<actix_web::Error> for Roster {
+    fn into(self) -> actix_web::Error {
+        match self {
+            Self::InternalServerError(e) => ise(e),
+            Self::ResourceNotFound => {
+                BasicError::with_message(http::StatusCode::NOT_FOUND, "Roster not found".to_owned())
+                    .into()
+            }
+            Self::InvalidName => BasicError::with_message(
+                http::StatusCode::BAD_REQUEST,
+                format!(
+                    "Names must be between 1 and {} characters long",
+                    ROSTER_NAME_MAX_LEN
+                ),
+            )
+            .into(),
+            Self::NicknameExists(nickname) => BasicError::with_message(
+                http::StatusCode::BAD_REQUEST,
+                format!("{nickname} is already on this roster"),
+            )
+            .into(),
+        }
+    }
+}
+
+pub enum CirclePost {
+    InternalServerError(anyhow::Error),
+    ResourceNotFound,
+    Forbidden,
+    InvalidBody,
+    TooManyAssets,
+    InvalidParent,
+}
+
+impl<T: Into<anyhow::Error>> From<T> for CirclePost {
+    fn from(e: T) -> Self {
+        Self::InternalServerError(e.into())
+    }
+}
+
+impl Into<actix_web::Error> for CirclePost {
+    fn into(self) -> actix_web::Error {
+        match self {
+            Self::InternalServerError(e) => ise(e),
+            Self::ResourceNotFound => BasicError::with_message(
+                http::StatusCode::NOT_FOUND,
+                "Resource not found".to_owned(),
+            )
+            .into(),
+            Self::Forbidden => BasicError::new(http::StatusCode::FORBIDDEN).into(),
+            Self::InvalidBody => BasicError::with_message(
+                http::StatusCode::BAD_REQUEST,
+                format!(
+                    "Posts must be between 1 and {} characters long",
+                    CIRCLE_POST_MAX_LEN
+                ),
+            )
+            .into(),
+            Self::TooManyAssets => BasicError::with_message(
+                http::StatusCode::BAD_REQUEST,
+                format!(
+                    "At most {} assets can be attached to a post",
+                    CIRCLE_POST_MAX_ASSETS
+                ),
+            )
+            .into(),
+            Self::InvalidParent => BasicError::with_message(
+                http::StatusCode::BAD_REQUEST,
+                "Replies can only be made to threads of the same circle".to_owned(),
+            )
+            .into(),
+        }
+    }
+}
+
+pub enum CircleLibrary {
+    InternalServerError(anyhow::Error),
+    ResourceNotFound,
+    Forbidden,
+    InvalidNote,
+    AlreadyPinned,
+}
+
+impl<T: Into<anyhow::Error>> From<T> for CircleLibrary {
+    fn from(e: T) -> Self {
+        Self::InternalServerError(e.into())
+    }
+}
+
+impl Into<actix_web::Error> for CircleLibrary {
+    fn into(self) -> actix_web::Error {
+        match self {
+            Self::InternalServerError(e) => ise(e),
+            Self::ResourceNotFound => BasicError::with_message(
+                http::StatusCode::NOT_FOUND,
+                "Resource not found".to_owned(),
+            )
+            .into(),
+            Self::Forbidden => BasicError::new(http::StatusCode::FORBIDDEN).into(),
+            Self::InvalidNote => BasicError::with_message(
+                http::StatusCode::BAD_REQUEST,
+                format!(
+                    "Notes can be at most {} characters long",
+                    CIRCLE_LIBRARY_NOTE_MAX_LEN
+                ),
+            )
+            .into(),
+            Self::AlreadyPinned => BasicError::with_message(
+                http::StatusCode::CONFLICT,
+                "Asset is already pinned into the library".to_owned(),
+            )
+            .into(),
+        }
+    }
+}
+
+pub enum Review {
+    InternalServerError(anyhow::Error),
+    ResourceNotFound,
+    Forbidden,
+    NotReviewable,
+    InvalidRating,
+    InvalidText,
+    AlreadyReported,
+    UnsupportedAction,
+}
+
+impl<T: Into<anyhow::Error>> From<T> for Review {
+    fn from(e: T) -> Self {
+        Self::InternalServerError(e.into())
+    }
+}
+
+impl Into<actix_web::Error> for Review {
+    fn into(self) -> actix_web::Error {
+        match self {
+            Self::InternalServerError(e) => ise(e),
+            Self::ResourceNotFound => BasicError::with_message(
+                http::StatusCode::NOT_FOUND,
+                "Resource not found".to_owned(),
+            )
+            .into(),
+            Self::Forbidden => BasicError::new(http::StatusCode::FORBIDDEN).into(),
+            Self::NotReviewable => BasicError::with_message(
+                http::StatusCode::BAD_REQUEST,
+                "Only JIGs, resources and playlists can be reviewed".to_owned(),
+            )
+            .into(),
+            Self::InvalidRating => BasicError::with_message(
+                http::StatusCode::BAD_REQUEST,
+                format!(
+                    "Ratings must be between {} and {}",
+                    REVIEW_RATING_MIN, REVIEW_RATING_MAX
+                ),
+            )
+            .into(),
+            Self::InvalidText => BasicError::with_message(
+                http::StatusCode::BAD_REQUEST,
+                format!(
+                    "Reviews and replies can be at most {} characters long",
+                    REVIEW_TEXT_MAX_LEN
+                ),
+            )
+            .into(),
+            Self::AlreadyReported => BasicError::with_message(
+                http::StatusCode::CONFLICT,
+                "Review was already reported".to_owned(),
+            )
+            .into(),
+            Self::UnsupportedAction => BasicError::with_message(
+                http::StatusCode::BAD_REQUEST,
+                "Reviewers can't be contacted, dismiss or block the review instead".to_owned(),
+            )
+            .into(),
+        }
+    }
+}
+
+pub enum Totp {
+    InternalServerError(anyhow::Error),
+    NotFound,
+    AlreadyEnabled,
+    Required,
+    InvalidCode,
+    Unauthorized,
+}
+
+impl<T: Into<anyhow::Error>> From<T> for Totp {
+    fn from(e: T) -> Self {
+        Self::InternalServerError(e.into())
+    }
+}
+
+impl Into<actix_web::Error> for Totp {
+    fn into(self) -> actix_web::Error {
+        match self {
+            Self::InternalServerError(e) => ise(e),
+            Self::NotFound => BasicError::with_message(
+                http::StatusCode::NOT_FOUND,
+                "Two-factor authentication is not set up".to_owned(),
+            )
+            .into(),
+            Self::AlreadyEnabled => BasicError::with_message(
+                http::StatusCode::CONFLICT,
+                "Two-factor authentication is already enabled".to_owned(),
+            )
+            .into(),
+            Self::Required => BasicError::with_message(
+                http::StatusCode::FORBIDDEN,
+                "Two-factor authentication is mandatory for admins".to_owned(),
+            )
+            .into(),
+            Self::InvalidCode => {
+                BasicError::with_message(http::StatusCode::BAD_REQUEST, "Invalid code".to_owned())
+                    .into()
+            }
+            Self::Unauthorized => BasicError::new(http::StatusCode::UNAUTHORIZED).into(),
+        }
+    }
+}
+
+pub enum ApiToken {
+    InternalServerError(anyhow::Error),
+    NotFound,
+    EmptyName,
+    AlreadyExpired,
+    ScopeNotAllowed(UserScope),
+    CreatedWithApiToken,
+}
+
+impl<T: Into<anyhow::Error>> From<T> for ApiToken {
+    fn from(e: T) -> Self {
+        Self::InternalServerError(e.into())
+    }
+}
+
+impl Into<actix_web::Error> for ApiToken {
+    fn into(self) -> actix_web::Error {
+        match self {
+            Self::InternalServerError(e) => ise(e),
+            Self::NotFound => BasicError::with_message(
+                http::StatusCode::NOT_FOUND,
+                "API token not found".to_owned(),
+            )
+            .into(),
+            Self::EmptyName => BasicError::with_message(
+                http::StatusCode::BAD_REQUEST,
+                "API token needs a name".to_owned(),
+            )
+            .into(),
+            Self::AlreadyExpired => BasicError::with_message(
+                http::StatusCode::BAD_REQUEST,
+                "API token would already be expired".to_owned(),
+            )
+            .into(),
+            Self::ScopeNotAllowed(scope) => BasicError::with_message(
+                http::StatusCode::FORBIDDEN,
+                format!("User doesn't have the {:?} scope", scope),
+            )
+            .into(),
+            Self::CreatedWithApiToken => BasicError::with_message(
+                http::StatusCode::FORBIDDEN,
+                "API tokens can't be created with an API token".to_owned(),
+            )
+            .into(),
+        }
+    }
+}
+
+pub enum Webhook {
+    InternalServerError(anyhow::Error),
+    ResourceNotFound,
+    InvalidUrl,
+    NoEvents,
+    Forbidden,
+}
+
+impl<T: Into<anyhow::Error>> From<T> for Webhook {
+    fn from(e: T) -> Self {
+        Self::InternalServerError(e.into())
+    }
+}
+
+impl Into<actix_web::Error> for Webhook {
+    fn into(self) -> actix_web::Error {
+        match self {
+            Self::InternalServerError(e) => ise(e),
+            Self::ResourceNotFound => BasicError::with_message(
+                http::StatusCode::NOT_FOUND,
+                "Webhook not found".to_owned(),
+            )
+            .into(),
+            Self::InvalidUrl => BasicError::with_message(
+                http::StatusCode::BAD_REQUEST,
+                "Webhook URL must be a valid https URL on the public internet".to_owned(),
+            )
+            .into(),
+            Self::NoEvents => BasicError::with_message(
+                http::StatusCode::BAD_REQUEST,
+                "Webhook must subscribe to at least one event".to_owned(),
+            )
+            .into(),
+            Self::Forbidden => BasicError::with_message(
+                http::StatusCode::FORBIDDEN,
+                "Only admins can receive every user's events".to_owned(),
+            )
+            .into(),
         }
     }
 }
@@ -6,6 +6,14 @@ use super::BasicError;
 pub enum OAuth {
     InternalServerError(anyhow::Error),
     Google(GoogleOAuth),
+    /// The provider isn't configured.
+    Disabled(super::ServiceKind),
+    /// The provider didn't accept the code (other than Google).
+    InvalidCode,
+    /// The provider didn't verify the email.
+    UnverifiedEmail,
+    /// There's no (enabled) single sign-on for the school, or the email's domain.
+    SsoNotFound,
     Conflict,
 }
 
@@ -26,9 +34,24 @@ impl Into<actix_web::Error> for OAuth {
         match self {
             Self::InternalServerError(err) => super::ise(err),
             Self::Google(it) => it.into(),
+            Self::Disabled(kind) => kind.into(),
+            Self::InvalidCode => {
+                BasicError::with_message(http::StatusCode::UNAUTHORIZED, "Invalid Code".to_owned())
+                    .into()
+            }
+            Self::UnverifiedEmail => BasicError::with_message(
+                http::StatusCode::UNAUTHORIZED,
+                "Email isn't verified".to_owned(),
+            )
+            .into(),
+            Self::SsoNotFound => BasicError::with_message(
+                http::StatusCode::NOT_FOUND,
+                "Single sign-on isn't set up for this school".to_owned(),
+            )
+            .into(),
             Self::Conflict => BasicError::with_message(
                 http::StatusCode::CONFLICT,
-                "User with same email exists, but they haven't enabled oauth".to_owned(),
+                "User with same email exists, but they can't sign in with this account".to_owned(),
             )
             .into(),
         }
@@ -42,7 +65,6 @@ pub enum GoogleOAuth {
     Disabled,
     InvalidCode,
     RedirectUriMismatch,
-    UnverifiedEmail,
 }
 
 impl From<google_oauth::TokenErrorResponse> for GoogleOAuth {
@@ -94,12 +116,6 @@ impl Into<actix_web::Error> for GoogleOAuth {
                 "Redirect URI Mismatch".to_owned(),
             )
             .into(),
-
-            GoogleOAuth::UnverifiedEmail => BasicError::with_message(
-                http::StatusCode::UNAUTHORIZED,
-                "Email isn't verified".to_owned(),
-            )
-            .into(),
         }
     }
 }
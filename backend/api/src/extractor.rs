@@ -2,7 +2,7 @@ use crate::{
     domain::RegistrationStatus,
     error::BasicError,
     more_futures::ReadyOrNot,
-    token::{check_login_token, SessionClaims, SessionMask},
+    token::{check_api_token, check_login_token, SessionClaims, SessionClient, SessionMask},
 };
 
 use actix_http::Payload;
@@ -17,16 +17,17 @@ use argon2::{
     password_hash::{Encoding, SaltString},
     Argon2, PasswordHasher, PasswordVerifier,
 };
-use futures::future::{self, ready, FutureExt};
+use futures::future::{self, ready, FutureExt, TryFutureExt};
 use http::StatusCode;
 use ji_core::settings::RuntimeSettings;
 use rand::thread_rng;
 use shared::domain::{
     session::{SessionTokenQuery, AUTH_COOKIE_NAME, CSRF_HEADER_NAME},
-    user::{UserId, UserScope},
+    user::{UserId, UserScope, API_TOKEN_PREFIX},
 };
 use sqlx::postgres::PgPool;
 use std::{borrow::Cow, marker::PhantomData};
+use uuid::Uuid;
 
 fn token_from_query(query_string: &str) -> Option<String> {
     serde_urlencoded::from_str::<SessionTokenQuery>(query_string)
@@ -53,6 +54,15 @@ fn token_from_header(headers: &HeaderMap) -> Option<String> {
         .map_or_else(|_| None, parse_for_token)
 }
 
+/// Personal API tokens can only be passed in the `Authorization` header, and a token in the query takes precedence.
+fn api_token_from_request(req: &HttpRequest) -> Option<String> {
+    if token_from_query(req.query_string()).is_some() {
+        return None;
+    }
+
+    token_from_header(req.headers()).filter(|token| token.starts_with(API_TOKEN_PREFIX))
+}
+
 fn csrf_header(headers: &HeaderMap) -> Option<String> {
     headers
         .get(CSRF_HEADER_NAME)?
@@ -91,6 +101,7 @@ async fn claims_for_scope(
     token_string: &str,
     csrf: Option<&str>,
     user_scope: UserScope,
+    client: &SessionClient,
 ) -> Result<SessionClaims, actix_web::Error> {
     let claims = check_login_token(
         &db,
@@ -98,9 +109,20 @@ async fn claims_for_scope(
         csrf,
         &settings.token_secret,
         SessionMask::GENERAL_API,
+        client,
     )
     .await?;
 
+    check_user_scope(db, claims.user_id, user_scope).await?;
+
+    Ok(claims)
+}
+
+async fn check_user_scope(
+    db: &PgPool,
+    user_id: Uuid,
+    user_scope: UserScope,
+) -> Result<(), actix_web::Error> {
     let has_scope = sqlx::query!(
         r#"select exists(
             select 1
@@ -109,7 +131,7 @@ async fn claims_for_scope(
                 user_id = $1 and
                 (scope = $2 or scope = $3)
         ) as "exists!""#,
-        claims.user_id,
+        user_id,
         user_scope as i16,
         UserScope::Admin as i16
     )
@@ -125,9 +147,10 @@ async fn claims_for_scope(
         return Err(BasicError::new(StatusCode::FORBIDDEN).into());
     }
 
-    Ok(claims)
+    Ok(())
 }
 
+/// The signed in user. Personal API tokens are refused, see [`TokenUserOrApiToken`].
 #[repr(transparent)]
 pub struct TokenUser(pub SessionClaims);
 
@@ -153,44 +176,99 @@ impl FromRequest for TokenUser {
         req: &actix_web::HttpRequest,
         _payload: &mut actix_web::dev::Payload,
     ) -> Self::Future {
-        let settings: &Data<RuntimeSettings> = req.app_data().expect("Settings??");
-        let settings = Data::clone(settings);
+        if api_token_from_request(req).is_some() {
+            return futures::future::err(
+                BasicError::with_message(
+                    StatusCode::FORBIDDEN,
+                    "API tokens can't be used for this endpoint".to_owned(),
+                )
+                .into(),
+            )
+            .into();
+        }
 
-        let db: &Data<PgPool> = req.app_data().expect("Missing `Data` for db?");
-        let db = db.as_ref().clone();
+        token_user_claims(req).map_ok(Self).boxed().into()
+    }
+}
 
-        let token =
-            token_from_query(req.query_string()).or_else(|| token_from_header(req.headers()));
+/// Like [`TokenUser`], but the request can also be authorized with a personal API token.
+///
+/// Only use it where the user's scopes are limited to the token's (see [`SessionClaims::token_scopes`]),
+/// otherwise a token grants everything the user can do.
+#[repr(transparent)]
+pub struct TokenUserOrApiToken(pub SessionClaims);
 
-        let (token_string, csrf) = match token {
-            Some(token_string) => (token_string, None),
-            None => {
-                let cookie = req.cookie(AUTH_COOKIE_NAME);
-                let csrf = csrf_header(req.headers());
+impl TokenUserOrApiToken {
+    pub fn user_id(&self) -> UserId {
+        UserId(self.0.user_id)
+    }
+}
 
-                match check_cookie_csrf(cookie, csrf.map(Cow::Owned)) {
-                    Ok((cookie, csrf)) => (cookie.value().to_owned(), Some(csrf.into_owned())),
-                    Err(e) => return futures::future::err(e.into()).into(),
-                }
+impl FromRequest for TokenUserOrApiToken {
+    type Error = actix_web::Error;
+    type Future = ReadyOrNot<'static, Result<Self, Self::Error>>;
+    fn from_request(
+        req: &actix_web::HttpRequest,
+        _payload: &mut actix_web::dev::Payload,
+    ) -> Self::Future {
+        if let Some(api_token) = api_token_from_request(req) {
+            let db: &Data<PgPool> = req.app_data().expect("Missing `Data` for db?");
+            let db = db.as_ref().clone();
+            let method = req.method().clone();
+
+            return async move {
+                let claims = check_api_token(&db, &api_token, None, &method).await?;
+
+                Ok(Self(claims))
             }
-        };
+            .boxed()
+            .into();
+        }
 
-        async move {
-            let csrf = csrf;
-            let claims = check_login_token(
-                &db,
-                &token_string,
-                csrf.as_deref(),
-                &settings.token_secret,
-                SessionMask::GENERAL_API,
-            )
-            .await?;
+        token_user_claims(req).map_ok(Self).boxed().into()
+    }
+}
+
+/// Checks the session of a [`TokenUser`].
+fn token_user_claims(
+    req: &actix_web::HttpRequest,
+) -> future::BoxFuture<'static, Result<SessionClaims, actix_web::Error>> {
+    let settings: &Data<RuntimeSettings> = req.app_data().expect("Settings??");
+    let settings = Data::clone(settings);
+
+    let db: &Data<PgPool> = req.app_data().expect("Missing `Data` for db?");
+    let db = db.as_ref().clone();
+
+    let token = token_from_query(req.query_string()).or_else(|| token_from_header(req.headers()));
+
+    let (token_string, csrf) = match token {
+        Some(token_string) => (token_string, None),
+        None => {
+            let cookie = req.cookie(AUTH_COOKIE_NAME);
+            let csrf = csrf_header(req.headers());
 
-            Ok(Self(claims))
+            match check_cookie_csrf(cookie, csrf.map(Cow::Owned)) {
+                Ok((cookie, csrf)) => (cookie.value().to_owned(), Some(csrf.into_owned())),
+                Err(e) => return async move { Err(e.into()) }.boxed(),
+            }
         }
-        .boxed()
-        .into()
+    };
+
+    let client = SessionClient::from_request(req);
+
+    async move {
+        let csrf = csrf;
+        check_login_token(
+            &db,
+            &token_string,
+            csrf.as_deref(),
+            &settings.token_secret,
+            SessionMask::GENERAL_API,
+            &client,
+        )
+        .await
     }
+    .boxed()
 }
 
 // fixme: replace with const-generics once stable
@@ -272,6 +350,23 @@ impl<S: Scope> FromRequest for TokenUserWithScope<S> {
         let db: &Data<PgPool> = req.app_data().expect("Missing `Data` for db?");
         let db = db.as_ref().clone();
 
+        if let Some(api_token) = api_token_from_request(req) {
+            let method = req.method().clone();
+
+            return async move {
+                let claims = check_api_token(&db, &api_token, Some(S::scope()), &method).await?;
+
+                check_user_scope(&db, claims.user_id, S::scope()).await?;
+
+                Ok(Self {
+                    claims,
+                    _phantom: PhantomData,
+                })
+            }
+            .boxed()
+            .into();
+        }
+
         let token =
             token_from_query(req.query_string()).or_else(|| token_from_header(req.headers()));
 
@@ -288,10 +383,18 @@ impl<S: Scope> FromRequest for TokenUserWithScope<S> {
             }
         };
 
+        let client = SessionClient::from_request(req);
+
         async move {
-            let claims =
-                claims_for_scope(&db, &settings, &token_string, csrf.as_deref(), S::scope())
-                    .await?;
+            let claims = claims_for_scope(
+                &db,
+                &settings,
+                &token_string,
+                csrf.as_deref(),
+                S::scope(),
+                &client,
+            )
+            .await?;
 
             Ok(Self {
                 claims,
@@ -341,8 +444,11 @@ impl<S: Scope> FromRequest for TokenUserNoCsrfWithScope<S> {
             }
         };
 
+        let client = SessionClient::from_request(req);
+
         async move {
-            let claims = claims_for_scope(&db, &settings, &token_string, None, S::scope()).await?;
+            let claims =
+                claims_for_scope(&db, &settings, &token_string, None, S::scope(), &client).await?;
 
             Ok(Self {
                 claims,
@@ -373,6 +479,21 @@ impl SessionMaskRequirement for SessionVerifyEmail {
     const REQUIREMENTS: SessionMask = SessionMask::VERIFY_EMAIL;
 }
 
+pub struct SessionVerifyTotp;
+impl SessionMaskRequirement for SessionVerifyTotp {
+    const REQUIREMENTS: SessionMask = SessionMask::VERIFY_TOTP;
+}
+
+pub struct SessionEnrollTotp;
+impl SessionMaskRequirement for SessionEnrollTotp {
+    const REQUIREMENTS: SessionMask = SessionMask::ENROLL_TOTP;
+}
+
+pub struct SessionGeneral;
+impl SessionMaskRequirement for SessionGeneral {
+    const REQUIREMENTS: SessionMask = SessionMask::GENERAL_API;
+}
+
 pub struct SessionAny;
 impl SessionMaskRequirement for SessionAny {
     const REQUIREMENTS: SessionMask = SessionMask::empty();
@@ -418,6 +539,8 @@ impl<S: SessionMaskRequirement> FromRequest for TokenSessionOf<S> {
             }
         };
 
+        let client = SessionClient::from_request(req);
+
         async move {
             let csrf = csrf;
             let claims = check_login_token(
@@ -426,6 +549,7 @@ impl<S: SessionMaskRequirement> FromRequest for TokenSessionOf<S> {
                 csrf.as_deref(),
                 &settings.token_secret,
                 S::REQUIREMENTS,
+                &client,
             )
             .await?;
 
@@ -22,6 +22,7 @@ use tracing_actix_web::{root_span, DefaultRootSpanBuilder, RootSpanBuilder, Trac
 
 use crate::{
     error::{BasicError, ConfigError},
+    rate_limit::RateLimiter,
     service::{self, mail, s3, upload::cleaner, ServiceData},
     translate,
 };
@@ -177,6 +178,9 @@ pub fn build(
     let media_upload_cleaner = media_upload_cleaner.map(ServiceData::new);
     let google_translate = google_translate.map(ServiceData::new);
 
+    // shared between workers, so that requests to all of them are counted together.
+    let rate_limiter = RateLimiter::from_env(pool.clone()).map(Data::new);
+
     let server = actix_web::HttpServer::new(move || {
         let app = actix_web::App::new()
             .app_data(Data::new(pool.clone()))
@@ -226,6 +230,11 @@ pub fn build(
             None => app,
         };
 
+        let app = match rate_limiter.clone() {
+            Some(rate_limiter) => app.app_data(rate_limiter),
+            None => app,
+        };
+
         let enable_tracing_logs = env_bool("ENABLE_TRACING_LOGS");
 
         app.app_data(Data::from(jwk_verifier.clone()))
@@ -250,6 +259,11 @@ pub fn build(
                 "google_cloud_oauth",
                 "https://accounts.google.com/o/oauth2/v2/auth",
             )
+            .external_resource(
+                "microsoft_oauth",
+                "https://login.microsoftonline.com/common/oauth2/v2.0/authorize",
+            )
+            .external_resource("apple_oauth", "https://appleid.apple.com/auth/authorize")
             .default_service(actix_web::web::to(default_route))
             .configure(endpoints::user::configure)
             .configure(endpoints::account::configure)
@@ -267,7 +281,9 @@ pub fn build(
             .configure(endpoints::resource::curation::configure)
             .configure(endpoints::resource::report::configure)
             .configure(endpoints::playlist::configure)
+            .configure(endpoints::playlist::curation::configure)
             .configure(endpoints::course::configure)
+            .configure(endpoints::course::curation::configure)
             .configure(endpoints::course::unit::configure)
             .configure(endpoints::admin::configure)
             .configure(endpoints::animation::configure)
@@ -278,6 +294,13 @@ pub fn build(
             .configure(endpoints::scheduler::configure)
             .configure(endpoints::pdf::configure)
             .configure(endpoints::circle::configure)
+            .configure(endpoints::circle::library::configure)
+            .configure(endpoints::circle::post::configure)
+            .configure(endpoints::sitemap::configure)
+            .configure(endpoints::roster::configure)
+            .configure(endpoints::webhook::configure)
+            .configure(endpoints::notification::configure)
+            .configure(endpoints::review::configure)
             .route("/", method(http::Method::GET).to(no_content_response))
     });
 
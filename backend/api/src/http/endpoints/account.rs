@@ -1,12 +1,22 @@
+use crate::db::account::AccountMember;
 use crate::extractor::TokenUser;
-use crate::{db, error};
+use crate::{db, error, oidc};
 use actix_web::web::{Data, Json, Path, ServiceConfig};
-use shared::api::endpoints::account::{GetSchoolAccount, GetSchoolNames};
+use actix_web::HttpResponse;
+use hickory_resolver::{error::ResolveErrorKind, TokioAsyncResolver};
+use ji_core::settings::RuntimeSettings;
+use rand::Rng;
+use shared::api::endpoints::account::{
+    DeleteSchoolSso, GetSchoolAccount, GetSchoolNames, GetSchoolSso, UpsertSchoolSso,
+    VerifySchoolSsoDomains,
+};
 use shared::api::{endpoints::account::CreateSchoolAccount, ApiEndpoint, PathParts};
 use shared::domain::billing::{
     CreateSchoolAccountRequest, GetSchoolAccountResponse, SchoolId, SchoolNameRequest,
+    UpsertSchoolSsoRequest,
 };
-use shared::domain::user::UserScope;
+use shared::domain::user::{UserId, UserScope};
+use sqlx::postgres::PgDatabaseError;
 use sqlx::PgPool;
 use tracing::instrument;
 
@@ -102,6 +112,254 @@ async fn get_school_account(
     }
 }
 
+/// Only the school account's admins, and Jigzi admins, can manage its single sign-on.
+async fn authorize_school_admin(
+    db: &PgPool,
+    user_id: UserId,
+    school_id: &SchoolId,
+) -> Result<(), error::Account> {
+    let school = db::account::get_school_account_by_id(db, school_id)
+        .await?
+        .ok_or(error::Account::NotFound("School not found".into()))?;
+
+    if db::user::has_scopes(db, user_id, &[UserScope::Admin]).await? {
+        return Ok(());
+    }
+
+    match db::account::user_account_membership(db, &user_id, &school.account_id).await? {
+        Some(AccountMember::Admin) => Ok(()),
+        _ => Err(error::Account::Forbidden),
+    }
+}
+
+/// Email domains anyone can get an address at, which can't be used for single sign-on.
+const PUBLIC_EMAIL_DOMAINS: &[&str] = &[
+    "gmail.com",
+    "googlemail.com",
+    "outlook.com",
+    "hotmail.com",
+    "live.com",
+    "msn.com",
+    "yahoo.com",
+    "icloud.com",
+    "me.com",
+    "mac.com",
+    "aol.com",
+    "gmx.com",
+    "gmx.net",
+    "proton.me",
+    "protonmail.com",
+    "mail.com",
+    "yandex.com",
+    "zoho.com",
+];
+
+#[instrument(skip_all)]
+async fn get_school_sso(
+    auth: TokenUser,
+    db: Data<PgPool>,
+    path: Path<SchoolId>,
+) -> Result<Json<<GetSchoolSso as ApiEndpoint>::Res>, error::Account> {
+    let school_id = path.into_inner();
+
+    authorize_school_admin(db.as_ref(), auth.user_id(), &school_id).await?;
+
+    let sso = db::account::get_school_sso(db.as_ref(), &school_id)
+        .await?
+        .ok_or(error::Account::NotFound(
+            "Single sign-on isn't set up".into(),
+        ))?;
+
+    Ok(Json(sso))
+}
+
+#[instrument(skip_all)]
+async fn upsert_school_sso(
+    settings: Data<RuntimeSettings>,
+    auth: TokenUser,
+    db: Data<PgPool>,
+    path: Path<SchoolId>,
+    req: Json<<UpsertSchoolSso as ApiEndpoint>::Req>,
+) -> Result<HttpResponse, error::Account> {
+    let school_id = path.into_inner();
+
+    authorize_school_admin(db.as_ref(), auth.user_id(), &school_id).await?;
+
+    let req: UpsertSchoolSsoRequest = req.into_inner();
+
+    if req.client_secret.is_none()
+        && db::account::get_school_sso(db.as_ref(), &school_id)
+            .await?
+            .is_none()
+    {
+        return Err(error::Account::InvalidSso(
+            "A client secret is required".into(),
+        ));
+    }
+
+    let email_domains: Vec<String> = req
+        .email_domains
+        .iter()
+        .map(|domain| domain.trim().trim_start_matches('@').to_lowercase())
+        .filter(|domain| !domain.is_empty())
+        .collect();
+
+    if email_domains.is_empty() || email_domains.iter().any(|domain| !domain.contains('.')) {
+        return Err(error::Account::InvalidSso(
+            "At least one valid email domain is required".into(),
+        ));
+    }
+
+    if let Some(domain) = email_domains
+        .iter()
+        .find(|domain| PUBLIC_EMAIL_DOMAINS.contains(&domain.as_str()))
+    {
+        return Err(error::Account::InvalidSso(format!(
+            "{} is a public email domain",
+            domain
+        )));
+    }
+
+    if db::account::sso_domains_verified_elsewhere(db.as_ref(), &school_id, &email_domains).await? {
+        return Err(error::Account::SsoDomainExists);
+    }
+
+    let issuer = req.issuer.trim().trim_end_matches('/');
+
+    // make sure that teachers will actually be able to sign in
+    if let Err(e) = oidc::discover(issuer, settings.is_local()).await {
+        log::info!(
+            "Failed to discover the identity provider at {}: {:?}",
+            issuer,
+            e
+        );
+
+        return Err(error::Account::InvalidSso(
+            "Couldn't get the identity provider's configuration from the issuer".into(),
+        ));
+    }
+
+    db::account::upsert_school_sso(
+        db.as_ref(),
+        &school_id,
+        issuer,
+        req.client_id.trim(),
+        req.client_secret.as_deref(),
+        &generate_verification_token(),
+        &email_domains,
+        req.enabled,
+    )
+    .await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+fn generate_verification_token() -> String {
+    let mut bytes = [0_u8; 24];
+    rand::thread_rng().fill(&mut bytes[..]);
+    base64::encode_config(&bytes, base64::URL_SAFE_NO_PAD)
+}
+
+/// Gets the domain's TXT records, with the strings of each record joined together.
+async fn txt_records(domain: &str) -> anyhow::Result<Vec<String>> {
+    let resolver = TokioAsyncResolver::tokio_from_system_conf()?;
+
+    let lookup = match resolver.txt_lookup(domain).await {
+        Ok(lookup) => lookup,
+        Err(e) if matches!(e.kind(), ResolveErrorKind::NoRecordsFound { .. }) => return Ok(vec![]),
+        Err(e) => return Err(e.into()),
+    };
+
+    Ok(lookup
+        .iter()
+        .map(|txt| {
+            txt.iter()
+                .map(|data| String::from_utf8_lossy(data))
+                .collect::<String>()
+        })
+        .collect())
+}
+
+#[instrument(skip_all)]
+async fn verify_school_sso_domains(
+    auth: TokenUser,
+    db: Data<PgPool>,
+    path: Path<SchoolId>,
+) -> Result<Json<<VerifySchoolSsoDomains as ApiEndpoint>::Res>, error::Account> {
+    let school_id = path.into_inner();
+
+    authorize_school_admin(db.as_ref(), auth.user_id(), &school_id).await?;
+
+    let sso = db::account::get_school_sso(db.as_ref(), &school_id)
+        .await?
+        .ok_or(error::Account::NotFound(
+            "Single sign-on isn't set up".into(),
+        ))?;
+
+    let mut verified = Vec::new();
+
+    for domain in sso.email_domains.iter().filter(|domain| !domain.verified) {
+        match txt_records(&domain.domain).await {
+            Ok(records) if records.contains(&sso.verification_record) => {
+                verified.push(domain.domain.clone());
+            }
+            Ok(_) => {}
+            Err(e) => log::info!(
+                "Failed to look up the TXT records of {}: {:?}",
+                domain.domain,
+                e
+            ),
+        }
+    }
+
+    if verified.is_empty() {
+        return Ok(Json(sso));
+    }
+
+    db::account::verify_school_sso_domains(db.as_ref(), &school_id, &verified)
+        .await
+        .map_err(handle_sso_domain_error)?;
+
+    let sso = db::account::get_school_sso(db.as_ref(), &school_id)
+        .await?
+        .ok_or(error::Account::NotFound(
+            "Single sign-on isn't set up".into(),
+        ))?;
+
+    Ok(Json(sso))
+}
+
+fn handle_sso_domain_error(e: sqlx::Error) -> error::Account {
+    let db_err = match &e {
+        sqlx::Error::Database(e) => e.downcast_ref::<PgDatabaseError>(),
+        _ => return e.into(),
+    };
+
+    match db_err.constraint() {
+        Some("school_sso_domain_verified_idx") => error::Account::SsoDomainExists,
+        _ => e.into(),
+    }
+}
+
+#[instrument(skip_all)]
+async fn delete_school_sso(
+    auth: TokenUser,
+    db: Data<PgPool>,
+    path: Path<SchoolId>,
+) -> Result<HttpResponse, error::Account> {
+    let school_id = path.into_inner();
+
+    authorize_school_admin(db.as_ref(), auth.user_id(), &school_id).await?;
+
+    if !db::account::delete_school_sso(db.as_ref(), &school_id).await? {
+        return Err(error::Account::NotFound(
+            "Single sign-on isn't set up".into(),
+        ));
+    }
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
 pub fn configure(cfg: &mut ServiceConfig) {
     cfg.route(
         <CreateSchoolAccount as ApiEndpoint>::Path::PATH,
@@ -116,5 +374,23 @@ pub fn configure(cfg: &mut ServiceConfig) {
     .route(
         <GetSchoolAccount as ApiEndpoint>::Path::PATH,
         GetSchoolAccount::METHOD.route().to(get_school_account),
+    )
+    .route(
+        <GetSchoolSso as ApiEndpoint>::Path::PATH,
+        GetSchoolSso::METHOD.route().to(get_school_sso),
+    )
+    .route(
+        <UpsertSchoolSso as ApiEndpoint>::Path::PATH,
+        UpsertSchoolSso::METHOD.route().to(upsert_school_sso),
+    )
+    .route(
+        <VerifySchoolSsoDomains as ApiEndpoint>::Path::PATH,
+        VerifySchoolSsoDomains::METHOD
+            .route()
+            .to(verify_school_sso_domains),
+    )
+    .route(
+        <DeleteSchoolSso as ApiEndpoint>::Path::PATH,
+        DeleteSchoolSso::METHOD.route().to(delete_school_sso),
     );
 }
@@ -11,13 +11,20 @@ use ji_core::settings::RuntimeSettings;
 use serde::ser::Serialize;
 use serde_derive::Deserialize;
 use shared::api::endpoints::admin::{
-    ImportSchoolNames, InviteUsers, SearchSchoolNames, VerifySchoolName,
+    CreateGlossaryTerm, CreatePromotionCode, CurationDashboard, DeleteGlossaryTerm,
+    ImportSchoolNames, InviteUsers, ListGlossaryTerms, ListPromotionCodes,
+    OverrideTranslatedDescription, SearchSchoolNames, UpdateGlossaryTerm, UpdatePromotionCode,
+    VerifySchoolName,
 };
 use shared::domain::admin::{
-    InviteFailedReason, InviteSchoolUserFailure, InviteSchoolUsersResponse,
-    SearchSchoolNamesResponse,
+    CurationDashboardResponse, GlossaryTermId, InviteFailedReason, InviteSchoolUserFailure,
+    InviteSchoolUsersResponse, ListGlossaryTermsResponse, SearchSchoolNamesResponse,
+};
+use shared::domain::asset::{AssetType, CurationStatus};
+use shared::domain::billing::{
+    AccountType, Discount, DiscountDuration, ListPromotionCodesResponse, PromotionCodeId, SchoolId,
+    SubscriptionTier,
 };
-use shared::domain::billing::{AccountType, SchoolId, SubscriptionTier};
 use shared::{
     api::{
         endpoints::admin::{self, CreateUpdateSubscriptionPlan},
@@ -27,14 +34,17 @@ use shared::{
         admin::{ExportDataRequest, ExportType},
         session::NewSessionResponse,
         user::UserId,
+        CreateResponse,
     },
 };
 use sqlx::PgPool;
 
 use crate::{
     db, error,
-    extractor::{ScopeAdmin, TokenUserNoCsrfWithScope, TokenUserWithScope},
+    extractor::{ScopeAdmin, ScopeAdminJig, TokenUserNoCsrfWithScope, TokenUserWithScope},
+    http::endpoints::{billing::create_stripe_client, jig::page_limit},
     token::{create_auth_token, SessionMask},
+    translate::LANGUAGES,
 };
 
 /// Impersonate another user
@@ -151,6 +161,123 @@ async fn create_or_update_subscription_plan(
     Ok(HttpResponse::Created().finish())
 }
 
+/// Create a promotion code. A Stripe coupon is created with the same discount, duration and
+/// redemption limit, restricted to the products of the promotion codes plans.
+async fn create_promotion_code(
+    _auth: TokenUserWithScope<ScopeAdmin>,
+    db: Data<PgPool>,
+    settings: Data<RuntimeSettings>,
+    req: Json<<CreatePromotionCode as ApiEndpoint>::Req>,
+) -> Result<
+    (
+        Json<<CreatePromotionCode as ApiEndpoint>::Res>,
+        http::StatusCode,
+    ),
+    error::Billing,
+> {
+    let req = req.into_inner();
+
+    if db::billing::get_promotion_code_by_code(&db, &req.code)
+        .await?
+        .is_some()
+    {
+        return Err(error::Billing::PromotionCodeExists);
+    }
+
+    let currency = match req.discount {
+        Discount::PercentOff(percent) if percent == 0 || percent > 100 => {
+            return Err(error::Billing::BadRequest)
+        }
+        Discount::PercentOff(_) => None,
+        Discount::AmountOff(amount) if amount <= 0 => return Err(error::Billing::BadRequest),
+        Discount::AmountOff(_) => Some(
+            req.currency
+                .as_deref()
+                .and_then(|currency| currency.parse::<stripe::Currency>().ok())
+                .ok_or(error::Billing::BadRequest)?,
+        ),
+    };
+
+    let mut product_ids = Vec::with_capacity(req.plan_ids.len());
+    for plan_id in &req.plan_ids {
+        let plan = db::billing::get_subscription_plan_by_id(&db, *plan_id)
+            .await?
+            .ok_or(error::Billing::NotFound)?;
+
+        product_ids.push(plan.product_id);
+    }
+
+    let client = create_stripe_client(&settings)?;
+
+    let coupon = {
+        let mut params = stripe::CreateCoupon::new();
+        params.name = Some(&req.code);
+        params.max_redemptions = req.max_redemptions.map(i64::from);
+        params.redeem_by = req.expires_at.map(|expires_at| expires_at.timestamp());
+
+        match req.discount {
+            Discount::PercentOff(percent) => params.percent_off = Some(f64::from(percent)),
+            Discount::AmountOff(amount) => {
+                params.amount_off = Some(amount);
+                params.currency = currency;
+            }
+        }
+
+        match req.duration {
+            DiscountDuration::Once => params.duration = Some(stripe::CouponDuration::Once),
+            DiscountDuration::Repeating(months) => {
+                params.duration = Some(stripe::CouponDuration::Repeating);
+                params.duration_in_months = Some(i64::from(months));
+            }
+            DiscountDuration::Forever => params.duration = Some(stripe::CouponDuration::Forever),
+        }
+
+        if !product_ids.is_empty() {
+            params.applies_to = Some(stripe::CreateCouponAppliesTo {
+                products: Some(product_ids.into_iter().map(String::from).collect()),
+            });
+        }
+
+        stripe::Coupon::create(&client, params)
+            .await
+            .map_err(error::Billing::Stripe)?
+    };
+
+    let id = db::billing::create_promotion_code(&db, &req, coupon.id.as_str()).await?;
+
+    let promotion_code = db::billing::get_promotion_code_by_code(&db, &req.code)
+        .await?
+        .filter(|record| record.promotion_code.id == id)
+        .ok_or(error::Billing::NotFound)?
+        .promotion_code;
+
+    Ok((Json(promotion_code), http::StatusCode::CREATED))
+}
+
+async fn list_promotion_codes(
+    _auth: TokenUserWithScope<ScopeAdmin>,
+    db: Data<PgPool>,
+) -> Result<Json<<ListPromotionCodes as ApiEndpoint>::Res>, error::Billing> {
+    let promotion_codes = db::billing::get_promotion_codes(&db).await?;
+
+    Ok(Json(ListPromotionCodesResponse { promotion_codes }))
+}
+
+async fn update_promotion_code(
+    _auth: TokenUserWithScope<ScopeAdmin>,
+    db: Data<PgPool>,
+    path: Path<PromotionCodeId>,
+    req: Json<<UpdatePromotionCode as ApiEndpoint>::Req>,
+) -> Result<HttpResponse, error::Billing> {
+    let req = req.into_inner();
+
+    if !db::billing::update_promotion_code(&db, path.into_inner(), req.active).await? {
+        return Err(error::Billing::NotFound);
+    }
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
 async fn search_school_names(
     _auth: TokenUserWithScope<ScopeAdmin>,
     db: Data<PgPool>,
@@ -303,6 +430,146 @@ async fn invite_school_users(
     ))
 }
 
+/// List curation work across all asset types, oldest status change first.
+async fn curation_dashboard(
+    _auth: TokenUserWithScope<ScopeAdminJig>,
+    db: Data<PgPool>,
+    query: Option<Query<<CurationDashboard as ApiEndpoint>::Req>>,
+) -> Result<Json<<CurationDashboard as ApiEndpoint>::Res>, error::Server> {
+    let query = query.map_or_else(Default::default, Query::into_inner);
+
+    let page_limit = page_limit(query.page_limit).await?;
+
+    let items = db::curation::list_queue(
+        &db,
+        query.asset_type,
+        query.status,
+        query.assignee_id,
+        query.page.unwrap_or(0) as i32,
+        page_limit,
+    );
+
+    let status_counts = db::curation::status_counts(&db, query.asset_type, query.assignee_id);
+
+    let (items, status_counts) = try_join!(items, status_counts)?;
+
+    let total_count = match query.status {
+        Some(CurationStatus::New) => status_counts.new,
+        Some(CurationStatus::NewVersion) => status_counts.new_version,
+        Some(CurationStatus::InProgress) => status_counts.in_progress,
+        Some(CurationStatus::Done) => status_counts.done,
+        None => status_counts.new + status_counts.new_version + status_counts.in_progress,
+    };
+
+    let pages = (total_count / (page_limit as u64)
+        + (total_count % (page_limit as u64) != 0) as u64) as u32;
+
+    Ok(Json(CurationDashboardResponse {
+        items,
+        pages,
+        total_count,
+        status_counts,
+    }))
+}
+
+/// Checks that every language code is one that descriptions are translated into.
+fn validate_translation_languages<'a>(
+    languages: impl IntoIterator<Item = &'a String>,
+) -> Result<(), error::Translation> {
+    for language in languages {
+        if !LANGUAGES.contains(&language.as_str()) {
+            return Err(error::Translation::UnsupportedLanguage(language.clone()));
+        }
+    }
+
+    Ok(())
+}
+
+async fn list_glossary_terms(
+    _auth: TokenUserWithScope<ScopeAdmin>,
+    db: Data<PgPool>,
+) -> Result<Json<<ListGlossaryTerms as ApiEndpoint>::Res>, error::Server> {
+    let terms = db::translation::list_glossary_terms(&db).await?;
+
+    Ok(Json(ListGlossaryTermsResponse { terms }))
+}
+
+async fn create_glossary_term(
+    _auth: TokenUserWithScope<ScopeAdmin>,
+    db: Data<PgPool>,
+    req: Json<<CreateGlossaryTerm as ApiEndpoint>::Req>,
+) -> Result<
+    (
+        Json<<CreateGlossaryTerm as ApiEndpoint>::Res>,
+        http::StatusCode,
+    ),
+    error::Translation,
+> {
+    let req = req.into_inner();
+
+    validate_translation_languages(req.renderings.keys())?;
+
+    let id = db::translation::create_glossary_term(&db, req.term.trim(), &req.renderings).await?;
+
+    Ok((Json(CreateResponse { id }), http::StatusCode::CREATED))
+}
+
+async fn update_glossary_term(
+    _auth: TokenUserWithScope<ScopeAdmin>,
+    db: Data<PgPool>,
+    path: Path<GlossaryTermId>,
+    req: Json<<UpdateGlossaryTerm as ApiEndpoint>::Req>,
+) -> Result<Json<<UpdateGlossaryTerm as ApiEndpoint>::Res>, error::Translation> {
+    let req = req.into_inner();
+
+    if let Some(renderings) = &req.renderings {
+        validate_translation_languages(renderings.keys())?;
+    }
+
+    let term = db::translation::update_glossary_term(
+        &db,
+        path.into_inner(),
+        req.term.as_deref().map(str::trim),
+        req.renderings.as_ref(),
+    )
+    .await?;
+
+    Ok(Json(term))
+}
+
+async fn delete_glossary_term(
+    _auth: TokenUserWithScope<ScopeAdmin>,
+    db: Data<PgPool>,
+    path: Path<GlossaryTermId>,
+) -> Result<HttpResponse, error::Translation> {
+    db::translation::delete_glossary_term(&db, path.into_inner()).await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// Override entries of an asset's translated description, which the translation job won't overwrite.
+async fn override_translated_description(
+    _auth: TokenUserWithScope<ScopeAdmin>,
+    db: Data<PgPool>,
+    req: Json<<OverrideTranslatedDescription as ApiEndpoint>::Req>,
+) -> Result<HttpResponse, error::Translation> {
+    let req = req.into_inner();
+
+    validate_translation_languages(req.translations.keys())?;
+
+    let asset_type = AssetType::from(&req.asset_id);
+
+    db::translation::override_translated_description(
+        &db,
+        asset_type,
+        *req.asset_id.uuid(),
+        &req.translations,
+    )
+    .await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
 pub fn configure(cfg: &mut ServiceConfig) {
     cfg.route(
         <admin::Impersonate as ApiEndpoint>::Path::PATH,
@@ -335,5 +602,47 @@ pub fn configure(cfg: &mut ServiceConfig) {
     .route(
         <InviteUsers as ApiEndpoint>::Path::PATH,
         InviteUsers::METHOD.route().to(invite_school_users),
+    )
+    .route(
+        <CreatePromotionCode as ApiEndpoint>::Path::PATH,
+        CreatePromotionCode::METHOD
+            .route()
+            .to(create_promotion_code),
+    )
+    .route(
+        <ListPromotionCodes as ApiEndpoint>::Path::PATH,
+        ListPromotionCodes::METHOD.route().to(list_promotion_codes),
+    )
+    .route(
+        <UpdatePromotionCode as ApiEndpoint>::Path::PATH,
+        UpdatePromotionCode::METHOD
+            .route()
+            .to(update_promotion_code),
+    )
+    .route(
+        <CurationDashboard as ApiEndpoint>::Path::PATH,
+        CurationDashboard::METHOD.route().to(curation_dashboard),
+    )
+    .route(
+        <ListGlossaryTerms as ApiEndpoint>::Path::PATH,
+        ListGlossaryTerms::METHOD.route().to(list_glossary_terms),
+    )
+    .route(
+        <CreateGlossaryTerm as ApiEndpoint>::Path::PATH,
+        CreateGlossaryTerm::METHOD.route().to(create_glossary_term),
+    )
+    .route(
+        <UpdateGlossaryTerm as ApiEndpoint>::Path::PATH,
+        UpdateGlossaryTerm::METHOD.route().to(update_glossary_term),
+    )
+    .route(
+        <DeleteGlossaryTerm as ApiEndpoint>::Path::PATH,
+        DeleteGlossaryTerm::METHOD.route().to(delete_glossary_term),
+    )
+    .route(
+        <OverrideTranslatedDescription as ApiEndpoint>::Path::PATH,
+        OverrideTranslatedDescription::METHOD
+            .route()
+            .to(override_translated_description),
     );
 }
@@ -1,20 +1,27 @@
 use actix_web::{
-    web::{self, Data, Json, ServiceConfig},
+    web::{self, Data, Json, Query, ServiceConfig},
     HttpRequest, HttpResponse,
 };
 use anyhow::anyhow;
 use chrono::{TimeZone, Utc};
 use ji_core::settings::RuntimeSettings;
-use shared::api::endpoints::billing::{CreateSetupIntent, GetSubscriptionPlans};
+use serde_json::json;
+use shared::api::endpoints::billing::{
+    CreateSetupIntent, GetBillingNotificationPreferences, GetSubscriptionPlans,
+    UpdateBillingNotificationPreferences, ValidatePromotionCode,
+};
 use shared::domain::billing::{
-    Account, AccountType, AmountInCents, CreateSubscriptionRecord, StripeInvoiceId,
-    StripeSubscriptionId, SubscriptionStatus, SubscriptionType, UpdateSubscriptionRecord,
+    Account, AccountId, AccountType, AmountInCents, CreateSubscriptionRecord, PlanId,
+    StripeInvoiceId, StripeSubscriptionId, SubscriptionPlanDetailsResponse, SubscriptionStatus,
+    SubscriptionType, UpdateSubscriptionRecord, ValidatePromotionCodeResponse,
 };
 use shared::{
     api::{endpoints::billing::CreateSubscription, ApiEndpoint, Method, PathParts},
     domain::{
         billing::{CreateSubscriptionResponse, CustomerId, PaymentMethod, SubscriptionPlan},
+        notification::NotificationKind,
         user::UserProfile,
+        webhook::WebhookEvent,
     },
 };
 use sqlx::PgPool;
@@ -27,7 +34,7 @@ use stripe::{
 };
 use tracing::instrument;
 
-use crate::{db, error, extractor::TokenUser};
+use crate::{db, db::billing::PromotionCodeRecord, error, extractor::TokenUser};
 
 /// Create a new subscription for an authenticated user.
 ///
@@ -37,6 +44,8 @@ use crate::{db, error, extractor::TokenUser};
 ///   - If a user has _not_ had a previous subscription, they will receive trial days.
 ///   - If a user does not receive trial days then a Payment Intent will be the response so that the client can confirm it.
 /// - Trial subscriptions will be canceled if payment could not be collected. I.e. if they don't have a payment method.
+/// - If a promotion code is passed, its Stripe coupon is applied to the subscription and the
+///   redemption is recorded against the account.
 #[instrument(skip_all)]
 async fn create_subscription(
     auth: TokenUser,
@@ -76,6 +85,19 @@ async fn create_subscription(
         ));
     }
 
+    let promotion_code = match &req.promotion_code {
+        Some(code) => Some(
+            get_redeemable_promotion_code(
+                db.as_ref(),
+                code,
+                &plan.plan_id,
+                Some(account.account_id),
+            )
+            .await?,
+        ),
+        None => None,
+    };
+
     let stripe_customer_id = stripe::CustomerId::from(account.stripe_customer_id.unwrap().clone());
 
     if let Some(setup_intent_id) = &req.setup_intent_id {
@@ -106,95 +128,210 @@ async fn create_subscription(
             .map_err(error::Billing::Stripe)?;
     }
 
-    // Create a Stripe subscription
-    let stripe_subscription = {
-        let mut params = CreateStripeSubscription::new(stripe_customer_id);
-        params.items = Some(vec![CreateSubscriptionItems {
-            price: Some(plan.price_id.into()),
-            ..Default::default()
-        }]);
-
-        // This will mark the subscription as incomplete until the payment intent has been
-        // confirmed.
-        params.payment_behavior = Some(stripe::SubscriptionPaymentBehavior::AllowIncomplete);
-        params.expand = &["latest_invoice.payment_intent"];
-
-        // If the user hasn't previously had a subscription, then we can set their trial period.
-        if account.subscription.is_none() {
-            if let Some(trial_period) = plan.trial_period {
-                params.trial_period_days = Some(trial_period.inner() as u32);
-                params.trial_settings = Some(stripe::CreateSubscriptionTrialSettings {
-                    end_behavior: stripe::CreateSubscriptionTrialSettingsEndBehavior {
-                        missing_payment_method: stripe::CreateSubscriptionTrialSettingsEndBehaviorMissingPaymentMethod::Cancel,
-                    },
-                });
-            }
+    let coupon_id = match &promotion_code {
+        Some(promotion_code) => Some(
+            stripe::CouponId::from_str(&promotion_code.stripe_coupon_id)
+                .map_err(|_| anyhow!("Invalid coupon ID"))?,
+        ),
+        None => None,
+    };
+
+    // Reserve the promotion code before the discounted subscription is created in Stripe, so
+    // that concurrent requests can't redeem it more times than allowed.
+    if let Some(promotion_code) = &promotion_code {
+        if !db::billing::reserve_promotion_code(
+            db.as_ref(),
+            promotion_code.promotion_code.id,
+            account.account_id,
+        )
+        .await?
+        {
+            return Err(error::Billing::InvalidPromotionCode);
         }
+    }
 
-        stripe::Subscription::create(&client, params)
-            .await
-            .map_err(error::Billing::Stripe)?
-    };
+    // Release the reserved redemption if anything fails after it, so that it isn't used up by a
+    // subscription which was never created.
+    let result = async {
+        // Create a Stripe subscription
+        let stripe_subscription = {
+            let mut params = CreateStripeSubscription::new(stripe_customer_id);
+            params.items = Some(vec![CreateSubscriptionItems {
+                price: Some(plan.price_id.into()),
+                ..Default::default()
+            }]);
+
+            // This will mark the subscription as incomplete until the payment intent has been
+            // confirmed.
+            params.payment_behavior = Some(stripe::SubscriptionPaymentBehavior::AllowIncomplete);
+            params.expand = &["latest_invoice.payment_intent"];
+
+            params.coupon = coupon_id;
+
+            // If the user hasn't previously had a subscription, then we can set their trial period.
+            if account.subscription.is_none() {
+                if let Some(trial_period) = plan.trial_period {
+                    params.trial_period_days = Some(trial_period.inner() as u32);
+                    params.trial_settings = Some(stripe::CreateSubscriptionTrialSettings {
+                        end_behavior: stripe::CreateSubscriptionTrialSettingsEndBehavior {
+                            missing_payment_method: stripe::CreateSubscriptionTrialSettingsEndBehaviorMissingPaymentMethod::Cancel,
+                        },
+                    });
+                }
+            }
+
+            stripe::Subscription::create(&client, params)
+                .await
+                .map_err(error::Billing::Stripe)?
+        };
 
-    let stripe_subscription_id: StripeSubscriptionId = stripe_subscription.id.into();
+        let stripe_subscription_id: StripeSubscriptionId = stripe_subscription.id.into();
 
-    let latest_invoice_id = stripe_subscription
-        .latest_invoice
-        .as_ref()
-        .map(|invoice| StripeInvoiceId::from(&invoice.id()));
-
-    let amount_due_in_cents = match stripe_subscription.latest_invoice.as_ref() {
-        Some(invoice) => invoice
-            .as_object()
-            .unwrap()
-            .amount_remaining
-            .map(AmountInCents::new),
-        None => None,
-    };
+        let latest_invoice_id = stripe_subscription
+            .latest_invoice
+            .as_ref()
+            .map(|invoice| StripeInvoiceId::from(&invoice.id()));
 
-    // Fetch the latest invoice so that we can retrieve the client secret. This is useful if the
-    // user doesn't get a trial, and needs to add a payment method so that the subscription can be
-    // completed.
-    let create_response = match stripe_subscription.latest_invoice {
-        Some(invoice) => {
-            invoice
+        let amount_due_in_cents = match stripe_subscription.latest_invoice.as_ref() {
+            Some(invoice) => invoice
                 .as_object()
                 .unwrap()
-                .payment_intent
-                .as_ref()
-                .map(|payment_intent| CreateSubscriptionResponse {
-                    subscription_id: stripe_subscription_id.clone(),
-                    client_secret: payment_intent
-                        .as_object()
-                        .unwrap()
-                        .client_secret
-                        .as_ref()
-                        .unwrap()
-                        .clone(),
-                })
+                .amount_remaining
+                .map(AmountInCents::new),
+            None => None,
+        };
+
+        // Fetch the latest invoice so that we can retrieve the client secret. This is useful if the
+        // user doesn't get a trial, and needs to add a payment method so that the subscription can be
+        // completed.
+        let create_response = match stripe_subscription.latest_invoice {
+            Some(invoice) => {
+                invoice
+                    .as_object()
+                    .unwrap()
+                    .payment_intent
+                    .as_ref()
+                    .map(|payment_intent| CreateSubscriptionResponse {
+                        subscription_id: stripe_subscription_id.clone(),
+                        client_secret: payment_intent
+                            .as_object()
+                            .unwrap()
+                            .client_secret
+                            .as_ref()
+                            .unwrap()
+                            .clone(),
+                    })
+            }
+            None => None,
+        };
+
+        // Create subscription in database
+        let subscription = CreateSubscriptionRecord {
+            stripe_subscription_id,
+            subscription_plan_id: plan.plan_id,
+            tier: plan.subscription_tier,
+            auto_renew: true,           // TODO is this always true initially?
+            status: Default::default(), // This will be updated in the webhook
+            current_period_end: Utc
+                .timestamp_opt(stripe_subscription.current_period_end, 0)
+                .latest()
+                .ok_or(anyhow::anyhow!("Invalid timestamp"))?,
+            trial_end: stripe_subscription
+                .trial_end
+                .and_then(|trial_end| Utc.timestamp_opt(trial_end, 0).latest()),
+            account_id: account.account_id,
+            latest_invoice_id,
+            amount_due_in_cents,
+        };
+
+        let subscription_id = db::billing::create_subscription(db.as_ref(), subscription).await?;
+
+        if let Some(promotion_code) = &promotion_code {
+            db::billing::redeem_promotion_code(
+                db.as_ref(),
+                promotion_code.promotion_code.id,
+                account.account_id,
+                subscription_id,
+            )
+            .await?;
         }
-        None => None,
-    };
 
-    // Create subscription in database
-    let subscription = CreateSubscriptionRecord {
-        stripe_subscription_id,
-        subscription_plan_id: plan.plan_id,
-        tier: plan.subscription_tier,
-        auto_renew: true,           // TODO is this always true initially?
-        status: Default::default(), // This will be updated in the webhook
-        current_period_end: Utc
-            .timestamp_opt(stripe_subscription.current_period_end, 0)
-            .latest()
-            .ok_or(anyhow::anyhow!("Invalid timestamp"))?,
-        account_id: account.account_id,
-        latest_invoice_id,
-        amount_due_in_cents,
-    };
 
-    db::billing::create_subscription(db.as_ref(), subscription).await?;
+        Ok::<_, error::Billing>(create_response)
+    }
+    .await;
 
-    Ok((Json(create_response), http::StatusCode::CREATED))
+    if result.is_err() {
+        if let Some(promotion_code) = &promotion_code {
+            db::billing::release_promotion_code(
+                db.as_ref(),
+                promotion_code.promotion_code.id,
+                account.account_id,
+            )
+            .await?;
+        }
+    }
+
+    Ok((Json(result?), http::StatusCode::CREATED))
+}
+
+/// Fetch a promotion code and make sure that it can be redeemed for the plan. If an account is
+/// passed, the code must not have been redeemed by that account before.
+#[instrument(skip(db))]
+async fn get_redeemable_promotion_code(
+    db: &PgPool,
+    code: &str,
+    plan_id: &PlanId,
+    account_id: Option<AccountId>,
+) -> Result<PromotionCodeRecord, error::Billing> {
+    let record = db::billing::get_promotion_code_by_code(db, code)
+        .await?
+        .ok_or(error::Billing::NotFound)?;
+
+    if !record.promotion_code.is_redeemable(plan_id) {
+        return Err(error::Billing::InvalidPromotionCode);
+    }
+
+    if let Some(account_id) = account_id {
+        if db::billing::has_redeemed_promotion_code(db, record.promotion_code.id, account_id)
+            .await?
+        {
+            return Err(error::Billing::PromotionCodeRedeemed);
+        }
+    }
+
+    Ok(record)
+}
+
+/// Validate a promotion code for a plan and preview the discounted price.
+#[instrument(skip_all)]
+async fn validate_promotion_code(
+    auth: TokenUser,
+    db: Data<PgPool>,
+    query: Query<<ValidatePromotionCode as ApiEndpoint>::Req>,
+) -> Result<Json<<ValidatePromotionCode as ApiEndpoint>::Res>, error::Billing> {
+    let query = query.into_inner();
+
+    let plan = db::billing::get_subscription_plan_by_id(&db, query.plan_id)
+        .await?
+        .ok_or(error::Billing::NotFound)?;
+
+    let account_id = db::account::get_account_by_user_id(db.as_ref(), &auth.user_id())
+        .await?
+        .map(|account| account.account_id);
+
+    let record =
+        get_redeemable_promotion_code(db.as_ref(), &query.code, &plan.plan_id, account_id).await?;
+
+    let plan = SubscriptionPlanDetailsResponse::from(plan);
+    let promotion_code = record.promotion_code;
+
+    Ok(Json(ValidatePromotionCodeResponse {
+        discounted_amount_in_cents: promotion_code.discount.apply(&plan.amount_in_cents),
+        plan,
+        discount: promotion_code.discount,
+        duration: promotion_code.duration,
+    }))
 }
 
 #[instrument(skip_all)]
@@ -246,7 +383,7 @@ async fn create_setup_intent(
 }
 
 #[instrument(skip_all)]
-fn create_stripe_client(settings: &RuntimeSettings) -> Result<Client, error::Billing> {
+pub(crate) fn create_stripe_client(settings: &RuntimeSettings) -> Result<Client, error::Billing> {
     let secret = settings
         .stripe_secret_key
         .as_ref()
@@ -393,8 +530,21 @@ async fn webhook(
                         // Note: this will handle invoice changes on subscriptions as well since a
                         // subscription is updated when an invoice is paid/unpaid/etc.
                         let update_subscription = UpdateSubscriptionRecord::try_from(subscription)?;
+                        let subscription_id = update_subscription.stripe_subscription_id.clone();
+
+                        let previous_status =
+                            db::billing::get_subscription_status(db.as_ref(), &subscription_id)
+                                .await?;
 
                         db::billing::save_subscription(db.as_ref(), update_subscription).await?;
+
+                        notify_subscription_changed(
+                            db.as_ref(),
+                            &subscription_id,
+                            previous_status,
+                            settings.is_local(),
+                        )
+                        .await?;
                     }
                     EventObject::Invoice(invoice) => {
                         let _span = tracing::info_span!("invoice event");
@@ -430,6 +580,48 @@ async fn webhook(
     Ok(HttpResponse::Ok().finish())
 }
 
+/// Queue a webhook delivery to the admins of the account a subscription belongs to, and notify them
+/// if its status changed from `previous_status`.
+async fn notify_subscription_changed(
+    db: &PgPool,
+    subscription_id: &StripeSubscriptionId,
+    previous_status: Option<SubscriptionStatus>,
+    local: bool,
+) -> anyhow::Result<()> {
+    let mut txn = db.begin().await?;
+
+    let (account_id, status, admin_ids) =
+        match db::webhook::subscription_account_admins(&mut txn, subscription_id).await? {
+            Some(subscription) => subscription,
+            None => return Ok(()),
+        };
+
+    let data = json!({ "accountId": account_id, "status": status });
+
+    // Stripe sends an event for every change, e.g. to the latest invoice, which users don't need
+    // to be notified of
+    if previous_status.as_ref() != Some(&status) {
+        db::notification::create(
+            &mut txn,
+            &admin_ids.iter().map(|id| id.0).collect::<Vec<_>>(),
+            NotificationKind::SubscriptionChanged,
+            &data,
+        )
+        .await?;
+    }
+
+    let queued =
+        crate::webhook::queue(&mut txn, WebhookEvent::SubscriptionChanged, admin_ids, data).await?;
+
+    txn.commit().await?;
+
+    if queued {
+        crate::webhook::spawn_delivery(db.clone(), local);
+    }
+
+    Ok(())
+}
+
 /// Save a payment method for a customer. This will overwrite the existing payment method
 /// if there is one. If `payment_method` is `None`, then the customer's payment method
 /// will be removed.
@@ -484,6 +676,36 @@ pub async fn get_subscription_plans(
     Ok(Json(plans.try_into()?))
 }
 
+/// Get the billing notification email preferences for the current user.
+#[instrument(skip_all)]
+async fn get_billing_notification_preferences(
+    auth: TokenUser,
+    db: Data<PgPool>,
+) -> Result<Json<<GetBillingNotificationPreferences as ApiEndpoint>::Res>, error::Billing> {
+    let preferences =
+        db::billing::get_billing_notification_preferences(db.as_ref(), auth.user_id()).await?;
+
+    Ok(Json(preferences))
+}
+
+/// Update the billing notification email preferences for the current user, i.e. unsubscribe from
+/// trial ending, failed payment or expired subscription emails.
+#[instrument(skip_all)]
+async fn update_billing_notification_preferences(
+    auth: TokenUser,
+    db: Data<PgPool>,
+    req: Json<<UpdateBillingNotificationPreferences as ApiEndpoint>::Req>,
+) -> Result<HttpResponse, error::Billing> {
+    db::billing::upsert_billing_notification_preferences(
+        db.as_ref(),
+        auth.user_id(),
+        req.into_inner(),
+    )
+    .await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
 pub fn configure(cfg: &mut ServiceConfig) {
     cfg.route(
         <CreateSubscription as ApiEndpoint>::Path::PATH,
@@ -499,5 +721,23 @@ pub fn configure(cfg: &mut ServiceConfig) {
         GetSubscriptionPlans::METHOD
             .route()
             .to(get_subscription_plans),
+    )
+    .route(
+        <GetBillingNotificationPreferences as ApiEndpoint>::Path::PATH,
+        GetBillingNotificationPreferences::METHOD
+            .route()
+            .to(get_billing_notification_preferences),
+    )
+    .route(
+        <UpdateBillingNotificationPreferences as ApiEndpoint>::Path::PATH,
+        UpdateBillingNotificationPreferences::METHOD
+            .route()
+            .to(update_billing_notification_preferences),
+    )
+    .route(
+        <ValidatePromotionCode as ApiEndpoint>::Path::PATH,
+        ValidatePromotionCode::METHOD
+            .route()
+            .to(validate_promotion_code),
     );
 }
@@ -15,13 +15,17 @@ use shared::{
 use sqlx::PgPool;
 
 use crate::{
-    db::{self},
+    db::{self, circle::Membership},
     error::{self, ServiceKind},
     extractor::TokenUser,
     http::endpoints::jig::page_limit,
+    rate_limit::{self, RateLimit},
     service::ServiceData,
 };
 
+pub mod library;
+pub mod post;
+
 /// Create an Circle.
 async fn create(
     db: Data<PgPool>,
@@ -158,6 +162,32 @@ async fn remove_member(
     Ok(HttpResponse::NoContent().finish())
 }
 
+/// Invite a user to a circle.
+async fn invite(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    path: Path<(CircleId, UserId)>,
+) -> Result<HttpResponse, error::NotFound> {
+    let (circle_id, invitee_id) = path.into_inner();
+    let user_id = claims.user_id();
+
+    match db::circle::membership(&db, user_id, circle_id).await? {
+        None => return Err(error::NotFound::ResourceNotFound),
+        Some(Membership::Outsider) => return Err(error::NotFound::Forbidden),
+        Some(_) => {}
+    }
+
+    if !db::user::exists(&db, invitee_id).await? {
+        return Err(error::NotFound::ResourceNotFound);
+    }
+
+    if !db::circle::invite(&db, circle_id, user_id, invitee_id).await? {
+        return Err(error::NotFound::BadRequest);
+    }
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
 /// Search for Circles.
 async fn search(
     db: Data<PgPool>,
@@ -356,6 +386,13 @@ pub fn configure(cfg: &mut ServiceConfig) {
         <circle::RemoveMember as ApiEndpoint>::Path::PATH,
         circle::RemoveMember::METHOD.route().to(remove_member),
     )
+    .route(
+        <circle::Invite as ApiEndpoint>::Path::PATH,
+        circle::Invite::METHOD
+            .route()
+            .to(invite)
+            .wrap(RateLimit::new(&rate_limit::CIRCLE_INVITE)),
+    )
     .route(
         <circle::LeaveCircle as ApiEndpoint>::Path::PATH,
         circle::LeaveCircle::METHOD.route().to(leave),
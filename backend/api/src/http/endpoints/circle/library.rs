@@ -0,0 +1,153 @@
+use actix_web::{
+    web::{Data, Json, Path, Query, ServiceConfig},
+    HttpResponse,
+};
+use shared::{
+    api::{endpoints::circle::library, ApiEndpoint, PathParts},
+    domain::{
+        circle::{
+            library::{
+                CircleLibraryItemId, CircleLibraryListResponse, CIRCLE_LIBRARY_NOTE_MAX_LEN,
+            },
+            CircleId,
+        },
+        CreateResponse,
+    },
+};
+use sqlx::PgPool;
+
+use crate::{db, error, extractor::TokenUser, http::endpoints::jig::page_limit};
+
+/// Trims the note of a library item, checking that it isn't too long.
+fn validate_note(note: &str) -> Result<&str, error::CircleLibrary> {
+    let note = note.trim();
+
+    if note.chars().count() > CIRCLE_LIBRARY_NOTE_MAX_LEN {
+        return Err(error::CircleLibrary::InvalidNote);
+    }
+
+    Ok(note)
+}
+
+/// Pin an asset into the library of a circle.
+async fn pin(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    path: Path<CircleId>,
+    req: Json<<library::Pin as ApiEndpoint>::Req>,
+) -> Result<HttpResponse, error::CircleLibrary> {
+    let circle_id = path.into_inner();
+    let user_id = claims.user_id();
+    let req = req.into_inner();
+
+    db::circle::library::authz(&db, user_id, circle_id).await?;
+
+    let note = validate_note(&req.note)?;
+
+    let id = db::circle::library::pin(&db, circle_id, user_id, req.asset_id, note).await?;
+
+    Ok(HttpResponse::Created().json(CreateResponse { id }))
+}
+
+/// Browse the library of a circle.
+async fn list(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    path: Path<CircleId>,
+    query: Option<Query<<library::List as ApiEndpoint>::Req>>,
+) -> Result<Json<<library::List as ApiEndpoint>::Res>, error::CircleLibrary> {
+    let circle_id = path.into_inner();
+    let query = query.map_or_else(Default::default, Query::into_inner);
+
+    db::circle::library::authz(&db, claims.user_id(), circle_id).await?;
+
+    let page_limit = page_limit(query.page_limit).await?;
+
+    let (items, total_count) = db::circle::library::list(
+        &db,
+        circle_id,
+        query.asset_type,
+        query.page.unwrap_or(0),
+        page_limit,
+    )
+    .await?;
+
+    let pages = (total_count / (page_limit as u64)
+        + (total_count % (page_limit as u64) != 0) as u64) as u32;
+
+    Ok(Json(CircleLibraryListResponse {
+        items,
+        pages,
+        total_item_count: total_count,
+    }))
+}
+
+/// Edit the note of a library item.
+async fn update(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    path: Path<(CircleId, CircleLibraryItemId)>,
+    req: Json<<library::Update as ApiEndpoint>::Req>,
+) -> Result<HttpResponse, error::CircleLibrary> {
+    let (circle_id, id) = path.into_inner();
+    let user_id = claims.user_id();
+
+    db::circle::library::authz(&db, user_id, circle_id).await?;
+
+    let pinned_by = db::circle::library::get_pinned_by(&db, circle_id, id)
+        .await?
+        .ok_or(error::CircleLibrary::ResourceNotFound)?;
+
+    if pinned_by != user_id {
+        return Err(error::CircleLibrary::Forbidden);
+    }
+
+    let note = validate_note(&req.note)?;
+
+    db::circle::library::update(&db, id, note).await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// Remove an asset from the library of a circle.
+async fn unpin(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    path: Path<(CircleId, CircleLibraryItemId)>,
+) -> Result<HttpResponse, error::CircleLibrary> {
+    let (circle_id, id) = path.into_inner();
+    let user_id = claims.user_id();
+
+    let moderator = db::circle::library::authz(&db, user_id, circle_id).await?;
+
+    let pinned_by = db::circle::library::get_pinned_by(&db, circle_id, id)
+        .await?
+        .ok_or(error::CircleLibrary::ResourceNotFound)?;
+
+    if pinned_by != user_id && !moderator {
+        return Err(error::CircleLibrary::Forbidden);
+    }
+
+    db::circle::library::unpin(&db, id).await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+pub fn configure(cfg: &mut ServiceConfig) {
+    cfg.route(
+        <library::Pin as ApiEndpoint>::Path::PATH,
+        library::Pin::METHOD.route().to(pin),
+    )
+    .route(
+        <library::List as ApiEndpoint>::Path::PATH,
+        library::List::METHOD.route().to(list),
+    )
+    .route(
+        <library::Update as ApiEndpoint>::Path::PATH,
+        library::Update::METHOD.route().to(update),
+    )
+    .route(
+        <library::Unpin as ApiEndpoint>::Path::PATH,
+        library::Unpin::METHOD.route().to(unpin),
+    );
+}
@@ -0,0 +1,225 @@
+use actix_web::{
+    web::{Data, Json, Path, Query, ServiceConfig},
+    HttpResponse,
+};
+use shared::{
+    api::{endpoints::circle::post, ApiEndpoint, PathParts},
+    domain::{
+        asset::AssetId,
+        circle::{
+            post::{
+                CirclePostId, CirclePostListQuery, CirclePostListResponse, CIRCLE_POST_MAX_ASSETS,
+                CIRCLE_POST_MAX_LEN,
+            },
+            CircleId,
+        },
+        CreateResponse,
+    },
+};
+use sqlx::PgPool;
+
+use crate::{db, error, extractor::TokenUser, http::endpoints::jig::page_limit};
+
+/// Trims the text of a post, checking that it isn't empty or too long.
+fn validate_body(body: &str) -> Result<&str, error::CirclePost> {
+    let body = body.trim();
+
+    if body.is_empty() || body.chars().count() > CIRCLE_POST_MAX_LEN {
+        return Err(error::CirclePost::InvalidBody);
+    }
+
+    Ok(body)
+}
+
+fn validate_assets(assets: &[AssetId]) -> Result<&[AssetId], error::CirclePost> {
+    if assets.len() > CIRCLE_POST_MAX_ASSETS {
+        return Err(error::CirclePost::TooManyAssets);
+    }
+
+    Ok(assets)
+}
+
+/// Write a post in a circle.
+async fn create(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    path: Path<CircleId>,
+    req: Json<<post::Create as ApiEndpoint>::Req>,
+) -> Result<HttpResponse, error::CirclePost> {
+    let circle_id = path.into_inner();
+    let user_id = claims.user_id();
+    let req = req.into_inner();
+
+    db::circle::post::authz(&db, user_id, circle_id).await?;
+
+    let body = validate_body(&req.body)?;
+    let assets = validate_assets(&req.assets)?;
+
+    let id = db::circle::post::create(&db, circle_id, user_id, body, assets, req.parent_id).await?;
+
+    Ok(HttpResponse::Created().json(CreateResponse { id }))
+}
+
+async fn list_posts(
+    db: &PgPool,
+    claims: TokenUser,
+    circle_id: CircleId,
+    parent_id: Option<CirclePostId>,
+    query: CirclePostListQuery,
+) -> Result<CirclePostListResponse, error::CirclePost> {
+    let user_id = claims.user_id();
+
+    db::circle::post::authz(db, user_id, circle_id).await?;
+
+    let page_limit = page_limit(query.page_limit).await?;
+
+    let (posts, total_count) = db::circle::post::list(
+        db,
+        circle_id,
+        parent_id,
+        user_id,
+        query.page.unwrap_or(0),
+        page_limit,
+    )
+    .await?;
+
+    let pages = (total_count / (page_limit as u64)
+        + (total_count % (page_limit as u64) != 0) as u64) as u32;
+
+    Ok(CirclePostListResponse {
+        posts,
+        pages,
+        total_post_count: total_count,
+    })
+}
+
+/// List the threads of a circle.
+async fn list(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    path: Path<CircleId>,
+    query: Option<Query<<post::List as ApiEndpoint>::Req>>,
+) -> Result<Json<<post::List as ApiEndpoint>::Res>, error::CirclePost> {
+    let query = query.map_or_else(Default::default, Query::into_inner);
+
+    let res = list_posts(&db, claims, path.into_inner(), None, query).await?;
+
+    Ok(Json(res))
+}
+
+/// List the replies to a thread.
+async fn replies(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    path: Path<(CircleId, CirclePostId)>,
+    query: Option<Query<<post::Replies as ApiEndpoint>::Req>>,
+) -> Result<Json<<post::Replies as ApiEndpoint>::Res>, error::CirclePost> {
+    let (circle_id, id) = path.into_inner();
+    let query = query.map_or_else(Default::default, Query::into_inner);
+
+    let res = list_posts(&db, claims, circle_id, Some(id), query).await?;
+
+    Ok(Json(res))
+}
+
+/// Edit a post.
+async fn update(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    path: Path<(CircleId, CirclePostId)>,
+    req: Option<Json<<post::Update as ApiEndpoint>::Req>>,
+) -> Result<HttpResponse, error::CirclePost> {
+    let (circle_id, id) = path.into_inner();
+    let user_id = claims.user_id();
+    let req = req.map_or_else(Default::default, Json::into_inner);
+
+    db::circle::post::authz(&db, user_id, circle_id).await?;
+
+    let author_id = db::circle::post::get_author(&db, circle_id, id)
+        .await?
+        .ok_or(error::CirclePost::ResourceNotFound)?;
+
+    // moderators can delete posts, but not put words in other members' mouths
+    if author_id != user_id {
+        return Err(error::CirclePost::Forbidden);
+    }
+
+    let body = req.body.as_deref().map(validate_body).transpose()?;
+    let assets = req.assets.as_deref().map(validate_assets).transpose()?;
+
+    db::circle::post::update(&db, id, body, assets).await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// Delete a post.
+async fn delete(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    path: Path<(CircleId, CirclePostId)>,
+) -> Result<HttpResponse, error::CirclePost> {
+    let (circle_id, id) = path.into_inner();
+    let user_id = claims.user_id();
+
+    let moderator = db::circle::post::authz(&db, user_id, circle_id).await?;
+
+    let author_id = db::circle::post::get_author(&db, circle_id, id)
+        .await?
+        .ok_or(error::CirclePost::ResourceNotFound)?;
+
+    if author_id != user_id && !moderator {
+        return Err(error::CirclePost::Forbidden);
+    }
+
+    db::circle::post::delete(&db, id).await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// React to a post.
+async fn react(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    path: Path<(CircleId, CirclePostId)>,
+    req: Json<<post::React as ApiEndpoint>::Req>,
+) -> Result<HttpResponse, error::CirclePost> {
+    let (circle_id, id) = path.into_inner();
+    let user_id = claims.user_id();
+
+    db::circle::post::authz(&db, user_id, circle_id).await?;
+
+    db::circle::post::get_author(&db, circle_id, id)
+        .await?
+        .ok_or(error::CirclePost::ResourceNotFound)?;
+
+    db::circle::post::react(&db, id, user_id, req.into_inner().reaction).await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+pub fn configure(cfg: &mut ServiceConfig) {
+    cfg.route(
+        <post::Create as ApiEndpoint>::Path::PATH,
+        post::Create::METHOD.route().to(create),
+    )
+    .route(
+        <post::List as ApiEndpoint>::Path::PATH,
+        post::List::METHOD.route().to(list),
+    )
+    .route(
+        <post::Replies as ApiEndpoint>::Path::PATH,
+        post::Replies::METHOD.route().to(replies),
+    )
+    .route(
+        <post::Update as ApiEndpoint>::Path::PATH,
+        post::Update::METHOD.route().to(update),
+    )
+    .route(
+        <post::Delete as ApiEndpoint>::Path::PATH,
+        post::Delete::METHOD.route().to(delete),
+    )
+    .route(
+        <post::React as ApiEndpoint>::Path::PATH,
+        post::React::METHOD.route().to(react),
+    );
+}
@@ -8,7 +8,7 @@ use shared::domain::user::UserScope;
 use shared::{
     api::{endpoints::course, ApiEndpoint, PathParts},
     domain::{
-        asset::{DraftOrLive, PrivacyLevel, UserOrMe},
+        asset::{AssetType, DraftOrLive, PrivacyLevel, UserOrMe},
         course::{CourseBrowseResponse, CourseCreateRequest, CourseId, CourseSearchResponse},
         user::UserId,
         CreateResponse,
@@ -25,6 +25,7 @@ use crate::{
     service::ServiceData,
 };
 
+pub mod curation;
 pub mod unit;
 
 pub const DEFAULT_PAGE_LIMIT: u32 = 20;
@@ -241,6 +242,13 @@ delete from course_data where id = $1
     .execute(&mut *txn)
     .await?;
 
+    // the new live data is cloned from the draft, which doesn't have the admin overrides applied
+    db::translation::apply_translation_overrides(&mut txn, AssetType::Course, course_id.0).await?;
+
+    if !db::curation::is_curator(&db, user_id).await? {
+        db::course::curation::mark_new_version(&mut txn, course_id).await?;
+    }
+
     txn.commit().await?;
 
     Ok(HttpResponse::NoContent().finish())
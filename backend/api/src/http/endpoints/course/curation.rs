@@ -0,0 +1,128 @@
+use actix_web::{
+    web::{Data, Json, Path, ServiceConfig},
+    HttpResponse,
+};
+use shared::{
+    api::{endpoints::course::curation, ApiEndpoint, PathParts},
+    domain::{
+        course::{curation::CommentId, CourseId},
+        user::UserId,
+        CreateResponse,
+    },
+};
+use sqlx::PgPool;
+
+use crate::{
+    db, error,
+    extractor::{ScopeAdminJig, TokenUserWithScope},
+};
+
+/// Update curation details for a Course.
+async fn update_curation(
+    db: Data<PgPool>,
+    _auth: TokenUserWithScope<ScopeAdminJig>,
+    path: Path<CourseId>,
+    req: Json<<curation::UpdateCuration as ApiEndpoint>::Req>,
+) -> Result<HttpResponse, error::Curation> {
+    let course_id = path.into_inner();
+
+    let req = req.into_inner();
+
+    if let Some(Some(assignee_id)) = req.assignee_id {
+        if !db::curation::is_curator(&db, assignee_id).await? {
+            return Err(error::Curation::InvalidAssignee);
+        }
+    }
+
+    db::course::curation::update(
+        &*db,
+        course_id,
+        req.display_name,
+        req.categories,
+        req.language,
+        req.description,
+        req.additional_resources,
+        req.curation_status,
+        req.assignee_id,
+    )
+    .await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// Get curation details for a Course
+async fn get_curation(
+    db: Data<PgPool>,
+    auth: TokenUserWithScope<ScopeAdminJig>,
+    path: Path<CourseId>,
+) -> Result<Json<<curation::GetCuration as ApiEndpoint>::Res>, error::NotFound> {
+    let course_id = path.into_inner();
+    let admin_id = UserId(auth.claims.user_id);
+
+    db::course::authz(&*db, admin_id, Some(course_id)).await?;
+
+    let curation = db::course::curation::get_curation(&db, course_id)
+        .await?
+        .ok_or(error::NotFound::ResourceNotFound)?;
+
+    Ok(Json(curation))
+}
+
+/// Create a comment for course curation.
+async fn create_comment(
+    db: Data<PgPool>,
+    auth: TokenUserWithScope<ScopeAdminJig>,
+    path: Path<CourseId>,
+    req: Json<<curation::CreateComment as ApiEndpoint>::Req>,
+) -> Result<
+    (
+        Json<<curation::CreateComment as ApiEndpoint>::Res>,
+        http::StatusCode,
+    ),
+    error::Auth,
+> {
+    let course_id = path.into_inner();
+    let admin_id = UserId(auth.claims.user_id);
+
+    db::course::authz(&*db, admin_id, Some(course_id)).await?;
+
+    let req = req.into_inner();
+
+    let id = db::course::curation::create_comment(&*db, course_id, req.value, admin_id).await?;
+
+    Ok((Json(CreateResponse { id }), http::StatusCode::CREATED))
+}
+
+/// Get comment details for a course curation
+async fn get_comment(
+    db: Data<PgPool>,
+    _auth: TokenUserWithScope<ScopeAdminJig>,
+    path: Path<(CourseId, CommentId)>,
+) -> Result<Json<<curation::GetComment as ApiEndpoint>::Res>, error::NotFound> {
+    let (course_id, comment_id) = path.into_inner();
+
+    let comment = db::course::curation::get_comment(&db, course_id, comment_id)
+        .await?
+        .ok_or(error::NotFound::ResourceNotFound)?;
+
+    Ok(Json(comment))
+}
+
+pub fn configure(cfg: &mut ServiceConfig) {
+    cfg.route(
+        <curation::UpdateCuration as ApiEndpoint>::Path::PATH,
+        curation::UpdateCuration::METHOD.route().to(update_curation),
+    )
+    .route(
+        <curation::GetCuration as ApiEndpoint>::Path::PATH,
+        curation::GetCuration::METHOD.route().to(get_curation),
+    )
+    .route(
+        <curation::CreateComment as ApiEndpoint>::Path::PATH,
+        curation::CreateComment::METHOD.route().to(create_comment),
+    )
+    .route(
+        <curation::GetComment as ApiEndpoint>::Path::PATH,
+        curation::GetComment::METHOD.route().to(get_comment),
+    );
+}
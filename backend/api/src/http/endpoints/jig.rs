@@ -4,16 +4,18 @@ use actix_web::{
 };
 use futures::try_join;
 use ji_core::settings::RuntimeSettings;
+use serde_json::json;
 use shared::domain::user::UserScope;
 use shared::{
     api::{endpoints::jig, ApiEndpoint, PathParts},
     domain::{
-        asset::{DraftOrLive, PrivacyLevel, UserOrMe},
+        asset::{AssetType, DraftOrLive, PrivacyLevel, UserOrMe},
         jig::{
             GetJigPlaylistsResponse, JigBrowseResponse, JigCountResponse, JigCreateRequest, JigId,
             JigLikedResponse, JigSearchResponse,
         },
         user::UserId,
+        webhook::WebhookEvent,
         CreateResponse,
     },
 };
@@ -24,8 +26,10 @@ use uuid::Uuid;
 use crate::{
     db::{self, jig::CreateJigError},
     error::{self, ServiceKind},
-    extractor::{get_user_id, ScopeAdmin, TokenUser, TokenUserWithScope},
+    extractor::{get_user_id, ScopeAdmin, TokenUser, TokenUserOrApiToken, TokenUserWithScope},
+    rate_limit::{self, RateLimit},
     service::ServiceData,
+    webhook,
 };
 
 pub mod curation;
@@ -38,7 +42,7 @@ const MAX_PAGE_LIMIT: u32 = 100;
 /// Create a jig.
 async fn create(
     db: Data<PgPool>,
-    auth: TokenUser,
+    auth: TokenUserOrApiToken,
     req: Option<Json<<jig::Create as ApiEndpoint>::Req>>,
 ) -> Result<
     (
@@ -51,7 +55,7 @@ async fn create(
 
     let creator_id = auth.user_id();
 
-    db::jig::authz(db, creator_id, None).await?;
+    db::jig::authz_with_token_scopes(db, creator_id, auth.0.token_scopes(), None).await?;
 
     let req = req.map_or_else(JigCreateRequest::default, Json::into_inner);
 
@@ -116,7 +120,7 @@ async fn get_draft(
 async fn update_draft(
     db: Data<PgPool>,
     settings: Data<RuntimeSettings>,
-    claims: TokenUser,
+    claims: TokenUserOrApiToken,
     req: Option<Json<<jig::UpdateDraftData as ApiEndpoint>::Req>>,
     path: web::Path<JigId>,
 ) -> Result<HttpResponse, error::UpdateWithMetadata> {
@@ -124,7 +128,7 @@ async fn update_draft(
     let api_key = &settings.google_api_key;
     let user_id = claims.user_id();
 
-    db::jig::authz(&*db, user_id, Some(id)).await?;
+    db::jig::authz_with_token_scopes(&*db, user_id, claims.0.token_scopes(), Some(id)).await?;
 
     let req = req.map_or_else(Default::default, Json::into_inner);
 
@@ -254,14 +258,15 @@ fn filters_for_ids_or<T: Into<Uuid> + Copy>(ids: &[T]) -> Vec<Uuid> {
 /// Copies the contents of the draft jig data to live
 pub(super) async fn publish_draft_to_live(
     db: Data<PgPool>,
-    claims: TokenUser,
+    settings: Data<RuntimeSettings>,
+    claims: TokenUserOrApiToken,
     jig_id: Path<JigId>,
 ) -> Result<HttpResponse, error::CloneDraft> {
     let jig_id = jig_id.into_inner();
 
     let user_id = claims.user_id();
 
-    db::jig::authz(&*db, user_id, Some(jig_id)).await?;
+    db::jig::authz_with_token_scopes(&*db, user_id, claims.0.token_scopes(), Some(jig_id)).await?;
 
     let mut txn = db.begin().await?;
 
@@ -313,10 +318,30 @@ delete from jig_data where id = $1
     .execute(&mut *txn)
     .await?;
 
+    // the new live data is cloned from the draft, which doesn't have the admin overrides applied
+    db::translation::apply_translation_overrides(&mut txn, AssetType::Jig, jig_id.0).await?;
+
+    if !db::curation::is_curator_with_token_scopes(&db, user_id, claims.0.token_scopes()).await? {
+        db::jig::curation::mark_new_version(&mut txn, jig_id).await?;
+    }
+
     log::info!("AOSIJDOAIJSD");
 
+    let author_id = db::webhook::jig_author(&mut txn, jig_id).await?;
+    let queued = webhook::queue(
+        &mut txn,
+        WebhookEvent::JigPublished,
+        author_id,
+        json!({ "jigId": jig_id }),
+    )
+    .await?;
+
     txn.commit().await?;
 
+    if queued {
+        webhook::spawn_delivery(db.as_ref().clone(), settings.is_local());
+    }
+
     Ok(HttpResponse::NoContent().finish())
 }
 
@@ -375,6 +400,8 @@ async fn search(
             page_limit,
             blocked,
             query.is_rated,
+            &query.circles,
+            query.sort_by,
         )
         .await?
         .ok_or_else(|| error::Service::DisabledService(ServiceKind::Algolia))?;
@@ -626,15 +653,66 @@ pub fn configure(cfg: &mut ServiceConfig) {
         <jig::player::Create as ApiEndpoint>::Path::PATH,
         jig::player::Create::METHOD.route().to(player::create),
     )
+    .route(
+        <jig::player::assignment::Create as ApiEndpoint>::Path::PATH,
+        jig::player::assignment::Create::METHOD
+            .route()
+            .to(player::create_assignment),
+    )
+    .route(
+        <jig::player::assignment::List as ApiEndpoint>::Path::PATH,
+        jig::player::assignment::List::METHOD
+            .route()
+            .to(player::list_assignments),
+    )
+    .route(
+        <jig::player::assignment::Close as ApiEndpoint>::Path::PATH,
+        jig::player::assignment::Close::METHOD
+            .route()
+            .to(player::close_assignment),
+    )
     .route(
         <jig::player::List as ApiEndpoint>::Path::PATH,
         jig::player::List::METHOD.route().to(player::list),
     )
+    .route(
+        <jig::player::Instances as ApiEndpoint>::Path::PATH,
+        jig::player::Instances::METHOD
+            .route()
+            .to(player::list_instances),
+    )
+    .route(
+        <jig::player::Extend as ApiEndpoint>::Path::PATH,
+        jig::player::Extend::METHOD.route().to(player::extend),
+    )
+    .route(
+        <jig::player::Revoke as ApiEndpoint>::Path::PATH,
+        jig::player::Revoke::METHOD.route().to(player::revoke),
+    )
+    .route(
+        <jig::player::Leaderboard as ApiEndpoint>::Path::PATH,
+        jig::player::Leaderboard::METHOD
+            .route()
+            .to(player::leaderboard),
+    )
+    .route(
+        <jig::player::instance::Roster as ApiEndpoint>::Path::PATH,
+        jig::player::instance::Roster::METHOD
+            .route()
+            .to(player::instance::list_roster_students),
+    )
     .route(
         <jig::player::instance::Create as ApiEndpoint>::Path::PATH,
         jig::player::instance::Create::METHOD
             .route()
-            .to(player::instance::create_session_instance),
+            .to(player::instance::create_session_instance)
+            .wrap(RateLimit::new(&rate_limit::PLAYER_SESSION_INSTANCE)),
+    )
+    .route(
+        <jig::player::instance::Points as ApiEndpoint>::Path::PATH,
+        jig::player::instance::Points::METHOD
+            .route()
+            .to(player::instance::report_points),
     )
     .route(
         <jig::player::instance::Complete as ApiEndpoint>::Path::PATH,
@@ -2,11 +2,14 @@ use actix_web::{
     web::{Data, Json, Path, ServiceConfig},
     HttpResponse,
 };
+use ji_core::settings::RuntimeSettings;
+use serde_json::json;
 use shared::{
     api::{endpoints::jig::curation, ApiEndpoint, PathParts},
     domain::{
         jig::{curation::CommentId, JigId},
         user::UserId,
+        webhook::WebhookEvent,
         CreateResponse,
     },
 };
@@ -14,20 +17,30 @@ use sqlx::PgPool;
 
 use crate::{
     db, error,
-    extractor::{ScopeAdmin, TokenUserWithScope},
+    extractor::{ScopeAdminJig, TokenUserWithScope},
+    webhook,
 };
 
 /// Update curation details for a Jig.
 async fn update_curation(
     db: Data<PgPool>,
-    _auth: TokenUserWithScope<ScopeAdmin>,
+    settings: Data<RuntimeSettings>,
+    _auth: TokenUserWithScope<ScopeAdminJig>,
     path: Path<JigId>,
     req: Json<<curation::UpdateCuration as ApiEndpoint>::Req>,
-) -> Result<HttpResponse, error::Auth> {
+) -> Result<HttpResponse, error::Curation> {
     let jig_id = path.into_inner();
 
     let req = req.into_inner();
 
+    if let Some(Some(assignee_id)) = req.assignee_id {
+        if !db::curation::is_curator(&db, assignee_id).await? {
+            return Err(error::Curation::InvalidAssignee);
+        }
+    }
+
+    let curation_status = req.curation_status;
+
     db::jig::curation::update(
         &*db,
         jig_id,
@@ -39,16 +52,36 @@ async fn update_curation(
         req.description,
         req.additional_resources,
         req.curation_status,
+        req.assignee_id,
     )
     .await?;
 
+    if let Some(curation_status) = curation_status {
+        let mut txn = db.begin().await?;
+
+        let author_id = db::webhook::jig_author(&mut txn, jig_id).await?;
+        let queued = webhook::queue(
+            &mut txn,
+            WebhookEvent::CurationStatusChanged,
+            author_id,
+            json!({ "jigId": jig_id, "curationStatus": curation_status }),
+        )
+        .await?;
+
+        txn.commit().await?;
+
+        if queued {
+            webhook::spawn_delivery(db.as_ref().clone(), settings.is_local());
+        }
+    }
+
     Ok(HttpResponse::NoContent().finish())
 }
 
 /// Get curation details for a Jig
 async fn get_curation(
     db: Data<PgPool>,
-    auth: TokenUserWithScope<ScopeAdmin>,
+    auth: TokenUserWithScope<ScopeAdminJig>,
     path: Path<JigId>,
 ) -> Result<Json<<curation::GetCuration as ApiEndpoint>::Res>, error::NotFound> {
     let jig_id = path.into_inner();
@@ -66,7 +99,7 @@ async fn get_curation(
 /// Create a comment for jig curation.
 async fn create_comment(
     db: Data<PgPool>,
-    auth: TokenUserWithScope<ScopeAdmin>,
+    auth: TokenUserWithScope<ScopeAdminJig>,
     path: Path<JigId>,
     req: Json<<curation::CreateComment as ApiEndpoint>::Req>,
 ) -> Result<
@@ -92,7 +125,7 @@ async fn create_comment(
 /// Get comment details for a jig curation
 async fn get_comment(
     db: Data<PgPool>,
-    _auth: TokenUserWithScope<ScopeAdmin>,
+    _auth: TokenUserWithScope<ScopeAdminJig>,
     path: Path<(JigId, CommentId)>,
 ) -> Result<Json<<curation::GetComment as ApiEndpoint>::Res>, error::NotFound> {
     let (jig_id, comment_id) = path.into_inner();
@@ -1,18 +1,37 @@
 use actix_web::{
-    web::{self, Data, Json},
+    web::{self, Data, Json, Query},
     HttpResponse,
 };
+use chrono::{Duration, Utc};
 use shared::{
     api::{endpoints::jig::player, ApiEndpoint},
-    domain::jig::{
-        player::{JigPlayCountResponse, JigPlayerSession, JigPlayerSessionListResponse},
-        JigId,
+    config::JIG_PLAYER_SESSION_VALID_DURATION_SECS,
+    domain::{
+        jig::{
+            player::{
+                assignment::JigPlayerAssignmentListResponse,
+                instance::JigPlayerSessionInstancesResponse, JigPlayCountResponse,
+                JigPlayerSession, JigPlayerSessionExtendResponse, JigPlayerSessionIndex,
+                JigPlayerSessionLeaderboardResponse, JigPlayerSessionListResponse,
+            },
+            JigId,
+        },
+        user::UserScope,
     },
 };
 use sqlx::PgPool;
 
 use crate::{db, error, extractor::TokenUser};
 
+/// Checks that a session duration is neither zero nor longer than sessions can be valid for.
+fn session_duration(secs: u32) -> Result<Duration, error::JigCode> {
+    if secs == 0 || secs > JIG_PLAYER_SESSION_VALID_DURATION_SECS {
+        return Err(error::JigCode::InvalidDuration);
+    }
+
+    Ok(Duration::seconds(secs as i64))
+}
+
 /// Create a jig player session for the author, if one does not exist already.
 pub async fn create(
     db: Data<PgPool>,
@@ -24,15 +43,128 @@ pub async fn create(
 
     db::jig::is_logged_in(&*db, user_id).await?;
 
-    let (index, expires_at) = db::jig::player::create(&db, req.jig_id, &req.settings).await?;
+    let valid_for = session_duration(
+        req.duration
+            .unwrap_or(JIG_PLAYER_SESSION_VALID_DURATION_SECS),
+    )?;
+
+    let (index, expires_at) = db::jig::player::create(
+        &db,
+        req.jig_id,
+        user_id,
+        &req.settings,
+        req.roster_id,
+        valid_for,
+    )
+    .await?;
 
     Ok(HttpResponse::Created().json(JigPlayerSession {
         index,
         settings: req.settings,
         expires_at,
+        roster_id: req.roster_id,
     }))
 }
 
+/// Create an assignment, copying the JIGs of its playlist if it has one.
+pub async fn create_assignment(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    req: Json<<player::assignment::Create as ApiEndpoint>::Req>,
+) -> Result<HttpResponse, error::JigCode> {
+    let mut req = req.into_inner();
+    let user_id = claims.user_id();
+
+    db::jig::is_logged_in(&*db, user_id).await?;
+
+    let has_items = !req.items.is_empty();
+
+    match (has_items, req.playlist_id) {
+        (true, None) => {}
+        (false, Some(playlist_id)) => {
+            req.items = db::jig::player::list_playlist_items(&db, playlist_id)
+                .await?
+                .ok_or(error::JigCode::ResourceNotFound)?;
+        }
+        _ => return Err(error::JigCode::InvalidAssignment),
+    }
+
+    let closed = req.closes_at.map_or(false, |closes_at| {
+        closes_at <= Utc::now() || req.opens_at.map_or(false, |opens_at| closes_at <= opens_at)
+    });
+
+    if req.items.is_empty() || closed || req.max_attempts == Some(0) {
+        return Err(error::JigCode::InvalidAssignment);
+    }
+
+    let valid_for = session_duration(
+        req.duration
+            .unwrap_or(JIG_PLAYER_SESSION_VALID_DURATION_SECS),
+    )?;
+
+    let assignment = db::jig::player::create_assignment(&db, user_id, &req, valid_for).await?;
+
+    Ok(HttpResponse::Created().json(assignment))
+}
+
+/// List the assignments created by the user.
+pub async fn list_assignments(
+    db: Data<PgPool>,
+    claims: TokenUser,
+) -> Result<Json<<player::assignment::List as ApiEndpoint>::Res>, error::JigCode> {
+    let assignments = db::jig::player::list_assignments(&db, claims.user_id()).await?;
+
+    Ok(Json(JigPlayerAssignmentListResponse { assignments }))
+}
+
+/// Close an assignment now.
+pub async fn close_assignment(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    path: web::Path<JigPlayerSessionIndex>,
+) -> Result<Json<<player::assignment::Close as ApiEndpoint>::Res>, error::JigCode> {
+    let index = path.into_inner();
+
+    authorize_session_creator(&db, claims, index).await?;
+
+    let assignment = db::jig::player::close_assignment(&db, index).await?;
+
+    Ok(Json(assignment))
+}
+
+/// Extend a jig player session.
+pub async fn extend(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    path: web::Path<JigPlayerSessionIndex>,
+    req: Json<<player::Extend as ApiEndpoint>::Req>,
+) -> Result<Json<<player::Extend as ApiEndpoint>::Res>, error::JigCode> {
+    let index = path.into_inner();
+
+    let duration = session_duration(req.into_inner().duration)?;
+
+    authorize_session_creator(&db, claims, index).await?;
+
+    let expires_at = db::jig::player::extend(&db, index, duration).await?;
+
+    Ok(Json(JigPlayerSessionExtendResponse { expires_at }))
+}
+
+/// Revoke a jig player session.
+pub async fn revoke(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    path: web::Path<JigPlayerSessionIndex>,
+) -> Result<HttpResponse, error::JigCode> {
+    let index = path.into_inner();
+
+    authorize_session_creator(&db, claims, index).await?;
+
+    db::jig::player::revoke(&db, index).await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
 /// Fetch a jig player session code from it's jig if it exists.
 pub async fn list(
     db: Data<PgPool>,
@@ -46,6 +178,62 @@ pub async fn list(
     Ok(Json(JigPlayerSessionListResponse { sessions }))
 }
 
+/// Checks that the user created the jig player session, or is an admin.
+async fn authorize_session_creator(
+    db: &PgPool,
+    claims: TokenUser,
+    index: JigPlayerSessionIndex,
+) -> Result<(), error::JigCode> {
+    let user_id = claims.user_id();
+
+    let creator_id = db::jig::player::get_session_creator(db, index).await?;
+
+    if creator_id != Some(user_id)
+        && !db::user::has_scopes(db, user_id, &[UserScope::Admin]).await?
+    {
+        return Err(error::JigCode::Forbidden);
+    }
+
+    Ok(())
+}
+
+/// List the results of every instance of a jig player session.
+pub async fn list_instances(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    path: web::Path<JigPlayerSessionIndex>,
+) -> Result<Json<<player::Instances as ApiEndpoint>::Res>, error::JigCode> {
+    let index = path.into_inner();
+
+    authorize_session_creator(&db, claims, index).await?;
+
+    let instances = db::jig::player::list_instance_results(&db, index).await?;
+
+    Ok(Json(JigPlayerSessionInstancesResponse { instances }))
+}
+
+/// Get the leaderboard of a jig player session.
+pub async fn leaderboard(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    path: web::Path<JigPlayerSessionIndex>,
+    query: Option<Query<<player::Leaderboard as ApiEndpoint>::Req>>,
+) -> Result<Json<<player::Leaderboard as ApiEndpoint>::Res>, error::JigCode> {
+    let index = path.into_inner();
+    let query = query.map_or_else(Default::default, Query::into_inner);
+
+    authorize_session_creator(&db, claims, index).await?;
+
+    let (entries, total_count, cursor) =
+        db::jig::player::leaderboard(&db, index, query.since).await?;
+
+    Ok(Json(JigPlayerSessionLeaderboardResponse {
+        entries,
+        total_count,
+        cursor,
+    }))
+}
+
 /// Post an increase in the number of times a jig was played
 pub async fn get_play_count(
     db: Data<PgPool>,
@@ -57,16 +245,17 @@ pub async fn get_play_count(
 }
 
 pub mod instance {
-    use actix_web::{
-        web::{Data, Json},
-        HttpResponse,
-    };
+    use actix_web::web::{self, Data, Json};
     use chrono::{Duration, Utc};
     use ji_core::settings::RuntimeSettings;
     use serde::Deserialize;
     use shared::{
         api::{endpoints::jig::player, ApiEndpoint},
-        domain::jig::player::instance::PlayerSessionInstanceResponse,
+        config::JIG_PLAYER_SESSION_DISPLAY_NAME_MAX_LEN,
+        domain::jig::player::{
+            instance::{PlayerSessionInstanceResponse, PlayerSessionInstanceRosterResponse},
+            JigPlayerSessionIndex,
+        },
     };
     use sqlx::PgPool;
 
@@ -74,6 +263,7 @@ pub mod instance {
         db, error,
         extractor::{IPAddress, UserAgent},
         token::{create_player_session_instance_token, validate_token},
+        webhook,
     };
     use uuid::Uuid;
 
@@ -93,14 +283,30 @@ pub mod instance {
     > {
         let req = req.into_inner();
 
-        let resp =
-            db::jig::player::create_session_instance(&*db, req.index, ip_address, user_agent)
-                .await?;
+        let display_name = req.display_name.as_deref().map(str::trim);
+
+        if let Some(display_name) = display_name {
+            if display_name.is_empty()
+                || display_name.chars().count() > JIG_PLAYER_SESSION_DISPLAY_NAME_MAX_LEN
+            {
+                return Err(error::JigCode::InvalidDisplayName);
+            }
+        }
+
+        let resp = db::jig::player::create_session_instance(
+            &*db,
+            req.index,
+            display_name,
+            req.student.as_ref(),
+            ip_address,
+            user_agent,
+        )
+        .await?;
 
         let token: String = create_player_session_instance_token(
             &settings.token_secret,
             Duration::weeks(2),
-            &resp.2,
+            &resp.3,
             Utc::now(),
         )?;
 
@@ -108,36 +314,77 @@ pub mod instance {
             Json(PlayerSessionInstanceResponse {
                 jig_id: resp.0,
                 settings: resp.1,
+                items: resp.2,
                 token,
             }),
             actix_web::http::StatusCode::CREATED,
         ))
     }
 
+    /// List the students who can join a jig player session
+    pub async fn list_roster_students(
+        db: Data<PgPool>,
+        path: web::Path<JigPlayerSessionIndex>,
+    ) -> Result<Json<<player::instance::Roster as ApiEndpoint>::Res>, error::JigCode> {
+        let students = db::jig::player::list_roster_students(&db, path.into_inner()).await?;
+
+        Ok(Json(PlayerSessionInstanceRosterResponse { students }))
+    }
+
     #[derive(Deserialize)]
     struct InstanceToken {
         /// The instance this token is for.
         pub sub: Uuid,
     }
 
-    /// Create a jig player session for someone who's not the author, if one doesn't already exist
+    fn instance_id(settings: &RuntimeSettings, token: &str) -> Result<Uuid, error::JigCode> {
+        let token = validate_token(token, None, &settings.token_secret)
+            .map_err(|_| error::JigCode::Forbidden)?;
+
+        let instance_token: InstanceToken = serde_json::from_value(token)?;
+
+        Ok(instance_token.sub)
+    }
+
+    /// Report the points scored in a module of a jig player session instance
+    pub async fn report_points(
+        settings: Data<RuntimeSettings>,
+        db: Data<PgPool>,
+        ip_address: IPAddress,
+        user_agent: UserAgent,
+        req: Json<<player::instance::Points as ApiEndpoint>::Req>,
+    ) -> Result<Json<<player::instance::Points as ApiEndpoint>::Res>, error::JigCode> {
+        let req = req.into_inner();
+
+        let instance_id = instance_id(&settings, &req.token)?;
+
+        let result =
+            db::jig::player::report_points(&db, ip_address, user_agent, instance_id, &req.module)
+                .await?;
+
+        Ok(Json(result))
+    }
+
+    /// Complete a jig player session instance, returning its results
     pub async fn complete_session_instance(
         settings: Data<RuntimeSettings>,
         db: Data<PgPool>,
         ip_address: IPAddress,
         user_agent: UserAgent,
         req: Json<<player::instance::Complete as ApiEndpoint>::Req>,
-    ) -> Result<HttpResponse, error::JigCode> {
+    ) -> Result<Json<<player::instance::Complete as ApiEndpoint>::Res>, error::JigCode> {
         let req = req.into_inner();
 
-        let token = validate_token(&req.token, None, &settings.token_secret)
-            .map_err(|_| error::JigCode::Forbidden)?;
+        let instance_id = instance_id(&settings, &req.token)?;
 
-        let instance_token: InstanceToken = serde_json::from_value(token)?;
+        let (result, queued) =
+            db::jig::player::complete_session_instance(&db, ip_address, user_agent, instance_id)
+                .await?;
 
-        db::jig::player::complete_session_instance(&db, ip_address, user_agent, instance_token.sub)
-            .await?;
+        if queued {
+            webhook::spawn_delivery(db.as_ref().clone(), settings.is_local());
+        }
 
-        Ok(HttpResponse::NoContent().finish())
+        Ok(Json(result))
     }
 }
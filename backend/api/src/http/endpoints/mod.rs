@@ -13,10 +13,15 @@ pub mod locale;
 pub mod media;
 pub mod meta;
 pub mod module;
+pub mod notification;
 pub mod pdf;
 pub mod playlist;
 pub mod resource;
+pub mod review;
+pub mod roster;
 pub mod scheduler;
 pub mod search;
 pub mod session;
+pub mod sitemap;
 pub mod user;
+pub mod webhook;
@@ -0,0 +1,121 @@
+use actix_web::{
+    web::{Data, Json, Query, ServiceConfig},
+    HttpResponse,
+};
+use futures::try_join;
+use shared::{
+    api::{endpoints::notification, ApiEndpoint, PathParts},
+    domain::notification::{NotificationListResponse, NotificationUnreadCountResponse},
+};
+use sqlx::PgPool;
+
+use crate::{db, error, extractor::TokenUser};
+
+const DEFAULT_PAGE_LIMIT: u32 = 20;
+const MAX_PAGE_LIMIT: u32 = 100;
+
+/// List the notifications of the requesting user.
+async fn list(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    query: Option<Query<<notification::List as ApiEndpoint>::Req>>,
+) -> Result<Json<<notification::List as ApiEndpoint>::Res>, error::NotFound> {
+    let query = query.map_or_else(Default::default, Query::into_inner);
+    let user_id = claims.user_id();
+
+    let page_limit = query.page_limit.unwrap_or(DEFAULT_PAGE_LIMIT);
+    if page_limit == 0 || page_limit > MAX_PAGE_LIMIT {
+        return Err(error::NotFound::BadRequest);
+    }
+
+    let (notifications, total_count) = try_join!(
+        db::notification::list(
+            &db,
+            user_id,
+            query.unread_only,
+            query.page.unwrap_or(0),
+            page_limit,
+        ),
+        db::notification::count(&db, user_id, query.unread_only),
+    )?;
+
+    let pages = (total_count / (page_limit as u64)
+        + (total_count % (page_limit as u64) != 0) as u64) as u32;
+
+    Ok(Json(NotificationListResponse {
+        notifications,
+        pages,
+        total_count,
+    }))
+}
+
+/// Mark notifications of the requesting user as read.
+async fn mark_read(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    req: Option<Json<<notification::MarkRead as ApiEndpoint>::Req>>,
+) -> Result<HttpResponse, error::Server> {
+    let req = req.map_or_else(Default::default, Json::into_inner);
+
+    db::notification::mark_read(&db, claims.user_id(), req.ids.as_deref()).await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// Count the unread notifications of the requesting user.
+async fn unread_count(
+    db: Data<PgPool>,
+    claims: TokenUser,
+) -> Result<Json<<notification::UnreadCount as ApiEndpoint>::Res>, error::Server> {
+    let count = db::notification::count(&db, claims.user_id(), true).await?;
+
+    Ok(Json(NotificationUnreadCountResponse { count }))
+}
+
+/// Get the notification preferences of the requesting user.
+async fn get_preferences(
+    db: Data<PgPool>,
+    claims: TokenUser,
+) -> Result<Json<<notification::GetPreferences as ApiEndpoint>::Res>, error::Server> {
+    let preferences = db::notification::get_preferences(&db, claims.user_id()).await?;
+
+    Ok(Json(preferences))
+}
+
+/// Update the notification preferences of the requesting user.
+async fn update_preferences(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    req: Json<<notification::UpdatePreferences as ApiEndpoint>::Req>,
+) -> Result<HttpResponse, error::Server> {
+    db::notification::upsert_preferences(&db, claims.user_id(), req.into_inner()).await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+pub fn configure(cfg: &mut ServiceConfig) {
+    cfg.route(
+        <notification::List as ApiEndpoint>::Path::PATH,
+        notification::List::METHOD.route().to(list),
+    )
+    .route(
+        <notification::MarkRead as ApiEndpoint>::Path::PATH,
+        notification::MarkRead::METHOD.route().to(mark_read),
+    )
+    .route(
+        <notification::UnreadCount as ApiEndpoint>::Path::PATH,
+        notification::UnreadCount::METHOD.route().to(unread_count),
+    )
+    .route(
+        <notification::GetPreferences as ApiEndpoint>::Path::PATH,
+        notification::GetPreferences::METHOD
+            .route()
+            .to(get_preferences),
+    )
+    .route(
+        <notification::UpdatePreferences as ApiEndpoint>::Path::PATH,
+        notification::UpdatePreferences::METHOD
+            .route()
+            .to(update_preferences),
+    );
+}
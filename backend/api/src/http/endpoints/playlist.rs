@@ -8,7 +8,7 @@ use shared::domain::user::UserScope;
 use shared::{
     api::{endpoints::playlist, ApiEndpoint, PathParts},
     domain::{
-        asset::{DraftOrLive, PrivacyLevel, UserOrMe},
+        asset::{AssetType, DraftOrLive, PrivacyLevel, UserOrMe},
         playlist::{
             PlaylistBrowseResponse, PlaylistCreateRequest, PlaylistId, PlaylistLikedResponse,
             PlaylistSearchResponse,
@@ -28,6 +28,8 @@ use crate::{
     service::ServiceData,
 };
 
+pub mod curation;
+
 pub const DEFAULT_PAGE_LIMIT: u32 = 20;
 pub const MAX_PAGE_LIMIT: u32 = 100;
 
@@ -255,6 +257,14 @@ delete from playlist_data where id = $1
     .execute(&mut *txn)
     .await?;
 
+    // the new live data is cloned from the draft, which doesn't have the admin overrides applied
+    db::translation::apply_translation_overrides(&mut txn, AssetType::Playlist, playlist_id.0)
+        .await?;
+
+    if !db::curation::is_curator(&db, user_id).await? {
+        db::playlist::curation::mark_new_version(&mut txn, playlist_id).await?;
+    }
+
     txn.commit().await?;
 
     Ok(HttpResponse::NoContent().finish())
@@ -0,0 +1,130 @@
+use actix_web::{
+    web::{Data, Json, Path, ServiceConfig},
+    HttpResponse,
+};
+use shared::{
+    api::{endpoints::playlist::curation, ApiEndpoint, PathParts},
+    domain::{
+        playlist::{curation::CommentId, PlaylistId},
+        user::UserId,
+        CreateResponse,
+    },
+};
+use sqlx::PgPool;
+
+use crate::{
+    db, error,
+    extractor::{ScopeAdminJig, TokenUserWithScope},
+};
+
+/// Update curation details for a Playlist.
+async fn update_curation(
+    db: Data<PgPool>,
+    _auth: TokenUserWithScope<ScopeAdminJig>,
+    path: Path<PlaylistId>,
+    req: Json<<curation::UpdateCuration as ApiEndpoint>::Req>,
+) -> Result<HttpResponse, error::Curation> {
+    let playlist_id = path.into_inner();
+
+    let req = req.into_inner();
+
+    if let Some(Some(assignee_id)) = req.assignee_id {
+        if !db::curation::is_curator(&db, assignee_id).await? {
+            return Err(error::Curation::InvalidAssignee);
+        }
+    }
+
+    db::playlist::curation::update(
+        &*db,
+        playlist_id,
+        req.display_name,
+        req.categories,
+        req.age_ranges,
+        req.affiliations,
+        req.language,
+        req.description,
+        req.additional_resources,
+        req.curation_status,
+        req.assignee_id,
+    )
+    .await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// Get curation details for a Playlist
+async fn get_curation(
+    db: Data<PgPool>,
+    auth: TokenUserWithScope<ScopeAdminJig>,
+    path: Path<PlaylistId>,
+) -> Result<Json<<curation::GetCuration as ApiEndpoint>::Res>, error::NotFound> {
+    let playlist_id = path.into_inner();
+    let admin_id = UserId(auth.claims.user_id);
+
+    db::playlist::authz(&*db, admin_id, Some(playlist_id)).await?;
+
+    let curation = db::playlist::curation::get_curation(&db, playlist_id)
+        .await?
+        .ok_or(error::NotFound::ResourceNotFound)?;
+
+    Ok(Json(curation))
+}
+
+/// Create a comment for playlist curation.
+async fn create_comment(
+    db: Data<PgPool>,
+    auth: TokenUserWithScope<ScopeAdminJig>,
+    path: Path<PlaylistId>,
+    req: Json<<curation::CreateComment as ApiEndpoint>::Req>,
+) -> Result<
+    (
+        Json<<curation::CreateComment as ApiEndpoint>::Res>,
+        http::StatusCode,
+    ),
+    error::Auth,
+> {
+    let playlist_id = path.into_inner();
+    let admin_id = UserId(auth.claims.user_id);
+
+    db::playlist::authz(&*db, admin_id, Some(playlist_id)).await?;
+
+    let req = req.into_inner();
+
+    let id = db::playlist::curation::create_comment(&*db, playlist_id, req.value, admin_id).await?;
+
+    Ok((Json(CreateResponse { id }), http::StatusCode::CREATED))
+}
+
+/// Get comment details for a playlist curation
+async fn get_comment(
+    db: Data<PgPool>,
+    _auth: TokenUserWithScope<ScopeAdminJig>,
+    path: Path<(PlaylistId, CommentId)>,
+) -> Result<Json<<curation::GetComment as ApiEndpoint>::Res>, error::NotFound> {
+    let (playlist_id, comment_id) = path.into_inner();
+
+    let comment = db::playlist::curation::get_comment(&db, playlist_id, comment_id)
+        .await?
+        .ok_or(error::NotFound::ResourceNotFound)?;
+
+    Ok(Json(comment))
+}
+
+pub fn configure(cfg: &mut ServiceConfig) {
+    cfg.route(
+        <curation::UpdateCuration as ApiEndpoint>::Path::PATH,
+        curation::UpdateCuration::METHOD.route().to(update_curation),
+    )
+    .route(
+        <curation::GetCuration as ApiEndpoint>::Path::PATH,
+        curation::GetCuration::METHOD.route().to(get_curation),
+    )
+    .route(
+        <curation::CreateComment as ApiEndpoint>::Path::PATH,
+        curation::CreateComment::METHOD.route().to(create_comment),
+    )
+    .route(
+        <curation::GetComment as ApiEndpoint>::Path::PATH,
+        curation::GetComment::METHOD.route().to(get_comment),
+    );
+}
@@ -4,16 +4,18 @@ use actix_web::{
 };
 use futures::try_join;
 use ji_core::settings::RuntimeSettings;
+use serde_json::json;
 use shared::domain::user::UserScope;
 use shared::{
     api::{endpoints::resource, ApiEndpoint, PathParts},
     domain::{
-        asset::{DraftOrLive, PrivacyLevel, UserOrMe},
+        asset::{AssetType, DraftOrLive, PrivacyLevel, UserOrMe},
         resource::{
             ResourceBrowseResponse, ResourceCountResponse, ResourceCreateRequest, ResourceId,
             ResourceLikedResponse, ResourceSearchResponse,
         },
         user::UserId,
+        webhook::WebhookEvent,
         CreateResponse,
     },
 };
@@ -26,6 +28,7 @@ use crate::{
     error::{self, ServiceKind},
     extractor::{get_user_id, ScopeAdmin, TokenUser, TokenUserWithScope},
     service::ServiceData,
+    webhook,
 };
 
 pub mod curation;
@@ -234,6 +237,7 @@ fn filters_for_ids_or<T: Into<Uuid> + Copy>(ids: &[T]) -> Vec<Uuid> {
 /// Copies the contents of the draft resource data to live
 pub(super) async fn publish_draft_to_live(
     db: Data<PgPool>,
+    settings: Data<RuntimeSettings>,
     claims: TokenUser,
     resource_id: Path<ResourceId>,
 ) -> Result<HttpResponse, error::CloneDraft> {
@@ -271,10 +275,31 @@ delete from resource_data where id = $1
     .execute(&mut *txn)
     .await?;
 
+    // the new live data is cloned from the draft, which doesn't have the admin overrides applied
+    db::translation::apply_translation_overrides(&mut txn, AssetType::Resource, resource_id.0)
+        .await?;
+
+    if !db::curation::is_curator(&db, user_id).await? {
+        db::resource::curation::mark_new_version(&mut txn, resource_id).await?;
+    }
+
     log::info!("AOSIJDOAIJSD");
 
+    let author_id = db::webhook::resource_author(&mut txn, resource_id).await?;
+    let queued = webhook::queue(
+        &mut txn,
+        WebhookEvent::ResourcePublished,
+        author_id,
+        json!({ "resourceId": resource_id }),
+    )
+    .await?;
+
     txn.commit().await?;
 
+    if queued {
+        webhook::spawn_delivery(db.as_ref().clone(), settings.is_local());
+    }
+
     Ok(HttpResponse::NoContent().finish())
 }
 
@@ -333,6 +358,7 @@ async fn search(
             page_limit,
             blocked,
             query.is_rated,
+            query.sort_by,
         )
         .await?
         .ok_or_else(|| error::Service::DisabledService(ServiceKind::Algolia))?;
@@ -2,11 +2,14 @@ use actix_web::{
     web::{Data, Json, Path, ServiceConfig},
     HttpResponse,
 };
+use ji_core::settings::RuntimeSettings;
+use serde_json::json;
 use shared::{
     api::{endpoints::resource::curation, ApiEndpoint, PathParts},
     domain::{
         resource::{curation::CommentId, ResourceId},
         user::UserId,
+        webhook::WebhookEvent,
         CreateResponse,
     },
 };
@@ -14,20 +17,30 @@ use sqlx::PgPool;
 
 use crate::{
     db, error,
-    extractor::{ScopeAdmin, TokenUserWithScope},
+    extractor::{ScopeAdminJig, TokenUserWithScope},
+    webhook,
 };
 
 /// Update curation details for a Resource.
 async fn update_curation(
     db: Data<PgPool>,
-    _auth: TokenUserWithScope<ScopeAdmin>,
+    settings: Data<RuntimeSettings>,
+    _auth: TokenUserWithScope<ScopeAdminJig>,
     path: Path<ResourceId>,
     req: Json<<curation::UpdateCuration as ApiEndpoint>::Req>,
-) -> Result<HttpResponse, error::Auth> {
+) -> Result<HttpResponse, error::Curation> {
     let resource_id = path.into_inner();
 
     let req = req.into_inner();
 
+    if let Some(Some(assignee_id)) = req.assignee_id {
+        if !db::curation::is_curator(&db, assignee_id).await? {
+            return Err(error::Curation::InvalidAssignee);
+        }
+    }
+
+    let curation_status = req.curation_status;
+
     db::resource::curation::update(
         &*db,
         resource_id,
@@ -39,16 +52,36 @@ async fn update_curation(
         req.description,
         req.additional_resources,
         req.curation_status,
+        req.assignee_id,
     )
     .await?;
 
+    if let Some(curation_status) = curation_status {
+        let mut txn = db.begin().await?;
+
+        let author_id = db::webhook::resource_author(&mut txn, resource_id).await?;
+        let queued = webhook::queue(
+            &mut txn,
+            WebhookEvent::CurationStatusChanged,
+            author_id,
+            json!({ "resourceId": resource_id, "curationStatus": curation_status }),
+        )
+        .await?;
+
+        txn.commit().await?;
+
+        if queued {
+            webhook::spawn_delivery(db.as_ref().clone(), settings.is_local());
+        }
+    }
+
     Ok(HttpResponse::NoContent().finish())
 }
 
 /// Get curation details for a Resource
 async fn get_curation(
     db: Data<PgPool>,
-    auth: TokenUserWithScope<ScopeAdmin>,
+    auth: TokenUserWithScope<ScopeAdminJig>,
     path: Path<ResourceId>,
 ) -> Result<Json<<curation::GetCuration as ApiEndpoint>::Res>, error::NotFound> {
     let resource_id = path.into_inner();
@@ -66,7 +99,7 @@ async fn get_curation(
 /// Create a comment for resource curation.
 async fn create_comment(
     db: Data<PgPool>,
-    auth: TokenUserWithScope<ScopeAdmin>,
+    auth: TokenUserWithScope<ScopeAdminJig>,
     path: Path<ResourceId>,
     req: Json<<curation::CreateComment as ApiEndpoint>::Req>,
 ) -> Result<
@@ -93,7 +126,7 @@ async fn create_comment(
 /// Get comment details for a resource curation
 async fn get_comment(
     db: Data<PgPool>,
-    _auth: TokenUserWithScope<ScopeAdmin>,
+    _auth: TokenUserWithScope<ScopeAdminJig>,
     path: Path<(ResourceId, CommentId)>,
 ) -> Result<Json<<curation::GetComment as ApiEndpoint>::Res>, error::NotFound> {
     let (resource_id, comment_id) = path.into_inner();
@@ -1,10 +1,21 @@
-use actix_web::web::{Data, Json, Path, ServiceConfig};
+use actix_web::{
+    web::{Data, Json, Path, Query, ServiceConfig},
+    HttpResponse,
+};
+use futures::try_join;
 use ji_core::settings::RuntimeSettings;
 use sendgrid::v3::Email;
+use serde_json::json;
 use shared::{
     api::{endpoints::resource::report, ApiEndpoint, PathParts},
     domain::{
-        resource::{report::ReportId, report::ResourceReportEmail, ResourceId},
+        asset::ReportAction,
+        notification::NotificationKind,
+        resource::{
+            report::{ListResourceReportsResponse, ReportId, ResourceReportEmail},
+            ResourceId,
+        },
+        user::UserId,
         CreateResponse,
     },
 };
@@ -13,7 +24,9 @@ use sqlx::PgPool;
 use crate::{
     db::{self},
     error,
-    extractor::{ScopeAdmin, TokenUser, TokenUserWithScope},
+    extractor::{ScopeAdminJig, TokenUser, TokenUserWithScope},
+    http::endpoints::jig::page_limit,
+    rate_limit::{self, RateLimit},
     service::{mail, ServiceData},
 };
 use uuid::Uuid;
@@ -62,7 +75,7 @@ async fn create(
 /// Get report details for a resource
 async fn get(
     db: Data<PgPool>,
-    _auth: TokenUserWithScope<ScopeAdmin>,
+    _auth: TokenUserWithScope<ScopeAdminJig>,
     path: Path<(ResourceId, ReportId)>,
 ) -> Result<Json<<report::Get as ApiEndpoint>::Res>, error::ReportError> {
     let (resource_id, report_id) = path.into_inner();
@@ -89,13 +102,165 @@ async fn send_report(
     Ok(())
 }
 
+/// List reports in the moderation queue, oldest first
+async fn list(
+    db: Data<PgPool>,
+    _auth: TokenUserWithScope<ScopeAdminJig>,
+    query: Option<Query<<report::List as ApiEndpoint>::Req>>,
+) -> Result<Json<<report::List as ApiEndpoint>::Res>, error::ReportError> {
+    let query = query.map_or_else(Default::default, Query::into_inner);
+
+    let page_limit = page_limit(query.page_limit).await?;
+
+    let reports = db::resource::report::list_reports(
+        &db,
+        query.report_type,
+        query.status,
+        query.assignee_id,
+        query.page.unwrap_or(0) as i32,
+        page_limit,
+    );
+
+    let total_count = db::resource::report::filtered_count(
+        &db,
+        query.report_type,
+        query.status,
+        query.assignee_id,
+    );
+
+    let (reports, total_count) = try_join!(reports, total_count)?;
+
+    let pages = (total_count / (page_limit as u64)
+        + (total_count % (page_limit as u64) != 0) as u64) as u32;
+
+    Ok(Json(ListResourceReportsResponse {
+        reports,
+        pages,
+        total_report_count: total_count,
+    }))
+}
+
+/// Assign a report to a curator
+async fn assign(
+    db: Data<PgPool>,
+    _auth: TokenUserWithScope<ScopeAdminJig>,
+    path: Path<(ResourceId, ReportId)>,
+    req: Json<<report::Assign as ApiEndpoint>::Req>,
+) -> Result<HttpResponse, error::ReportError> {
+    let (resource_id, report_id) = path.into_inner();
+    let req = req.into_inner();
+
+    db::resource::report::get_report(&db, resource_id, report_id)
+        .await?
+        .ok_or(error::ReportError::ResourceNotFound)?;
+
+    if let Some(assignee_id) = req.assignee_id {
+        if !db::resource::report::is_curator(&db, assignee_id).await? {
+            return Err(error::ReportError::InvalidAssignee);
+        }
+    }
+
+    if !db::resource::report::assign_report(&db, resource_id, report_id, req.assignee_id).await? {
+        return Err(error::ReportError::AlreadyResolved);
+    }
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// Resolve a report, take the requested action and let the reporter know the outcome
+async fn resolve(
+    db: Data<PgPool>,
+    auth: TokenUserWithScope<ScopeAdminJig>,
+    mail: ServiceData<mail::Client>,
+    path: Path<(ResourceId, ReportId)>,
+    req: Json<<report::Resolve as ApiEndpoint>::Req>,
+) -> Result<HttpResponse, error::ReportError> {
+    let (resource_id, report_id) = path.into_inner();
+    let req = req.into_inner();
+
+    let note = req
+        .note
+        .as_deref()
+        .map(str::trim)
+        .filter(|it| !it.is_empty());
+
+    if req.action == ReportAction::ContactAuthor && note.is_none() {
+        return Err(error::ReportError::MissingNote);
+    }
+
+    let contacts = db::resource::report::get_report_contacts(&db, resource_id, report_id)
+        .await?
+        .ok_or(error::ReportError::ResourceNotFound)?;
+
+    if !db::resource::report::resolve_report(
+        &db,
+        resource_id,
+        report_id,
+        req.action,
+        note,
+        UserId(auth.claims.user_id),
+    )
+    .await?
+    {
+        return Err(error::ReportError::AlreadyResolved);
+    }
+
+    match req.action {
+        // blocking happens in the same transaction as resolving the report
+        ReportAction::Dismiss | ReportAction::Block => {}
+        ReportAction::ContactAuthor => {
+            if let (Some(email), Some(note)) = (contacts.author_email, note) {
+                mail.send_report_author_email(
+                    Email::new(email),
+                    &contacts.display_name,
+                    contacts.author_name.as_deref().unwrap_or_default(),
+                    note,
+                )
+                .await?;
+            }
+        }
+    }
+
+    if let Some(reporter_id) = contacts.reporter_id {
+        db::notification::create(
+            db.as_ref(),
+            &[reporter_id],
+            NotificationKind::ReportResolved,
+            &json!({ "resourceId": resource_id, "reportId": report_id, "action": req.action }),
+        )
+        .await?;
+    }
+
+    if let Some(email) = contacts.reporter_email {
+        mail.send_report_resolved_email(Email::new(email), &contacts.display_name, req.action)
+            .await?;
+    }
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
 pub fn configure(cfg: &mut ServiceConfig) {
     cfg.route(
         <report::Create as ApiEndpoint>::Path::PATH,
-        report::Create::METHOD.route().to(create),
+        report::Create::METHOD
+            .route()
+            .to(create)
+            .wrap(RateLimit::new(&rate_limit::REPORT)),
     )
     .route(
         <report::Get as ApiEndpoint>::Path::PATH,
         report::Get::METHOD.route().to(get),
+    )
+    .route(
+        <report::List as ApiEndpoint>::Path::PATH,
+        report::List::METHOD.route().to(list),
+    )
+    .route(
+        <report::Assign as ApiEndpoint>::Path::PATH,
+        report::Assign::METHOD.route().to(assign),
+    )
+    .route(
+        <report::Resolve as ApiEndpoint>::Path::PATH,
+        report::Resolve::METHOD.route().to(resolve),
     );
 }
@@ -0,0 +1,276 @@
+use actix_web::{
+    web::{Data, Json, Path, Query, ServiceConfig},
+    HttpResponse,
+};
+use shared::{
+    api::{endpoints::review, ApiEndpoint, PathParts},
+    domain::{
+        asset::{AssetId, AssetType, ReportAction},
+        course::CourseId,
+        jig::JigId,
+        playlist::PlaylistId,
+        resource::ResourceId,
+        review::{
+            ReviewId, ReviewListResponse, ReviewReportListResponse, REVIEW_RATING_MAX,
+            REVIEW_RATING_MIN, REVIEW_TEXT_MAX_LEN,
+        },
+        user::{UserId, UserScope},
+        CreateResponse,
+    },
+};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    db, error,
+    extractor::{ScopeAdminJig, TokenUser, TokenUserWithScope},
+    http::endpoints::jig::page_limit,
+};
+
+/// Trims the text of a review or reply, checking that it isn't too long.
+fn validate_text(text: &str) -> Result<&str, error::Review> {
+    let text = text.trim();
+
+    if text.chars().count() > REVIEW_TEXT_MAX_LEN {
+        return Err(error::Review::InvalidText);
+    }
+
+    Ok(text)
+}
+
+fn asset_id(asset_type: AssetType, id: Uuid) -> AssetId {
+    match asset_type {
+        AssetType::Jig => JigId(id).into(),
+        AssetType::Resource => ResourceId(id).into(),
+        AssetType::Playlist => PlaylistId(id).into(),
+        AssetType::Course => CourseId(id).into(),
+    }
+}
+
+/// Gets the author of an asset, checking that it can be reviewed.
+async fn reviewable_asset(
+    db: &PgPool,
+    asset_id: AssetId,
+) -> Result<(db::review::AssetColumns, Option<UserId>), error::Review> {
+    let columns = db::review::asset_columns(asset_id).ok_or(error::Review::NotReviewable)?;
+
+    let author_id = db::review::reviewable_asset_author(db, asset_id)
+        .await?
+        .ok_or(error::Review::ResourceNotFound)?;
+
+    Ok((columns, author_id))
+}
+
+/// List the reviews of an asset.
+async fn list(
+    db: Data<PgPool>,
+    path: Path<(AssetType, Uuid)>,
+    query: Option<Query<<review::List as ApiEndpoint>::Req>>,
+) -> Result<Json<<review::List as ApiEndpoint>::Res>, error::Review> {
+    let (asset_type, id) = path.into_inner();
+    let query = query.map_or_else(Default::default, Query::into_inner);
+
+    let (columns, _) = reviewable_asset(&db, asset_id(asset_type, id)).await?;
+
+    let page_limit = page_limit(query.page_limit).await?;
+
+    let (reviews, total_count, average_rating) =
+        db::review::list(&db, columns, query.page.unwrap_or(0), page_limit).await?;
+
+    let pages = (total_count / (page_limit as u64)
+        + (total_count % (page_limit as u64) != 0) as u64) as u32;
+
+    Ok(Json(ReviewListResponse {
+        reviews,
+        pages,
+        total_review_count: total_count,
+        average_rating,
+    }))
+}
+
+/// Review an asset, or edit the review of the requesting user.
+async fn upsert(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    path: Path<(AssetType, Uuid)>,
+    req: Json<<review::Upsert as ApiEndpoint>::Req>,
+) -> Result<Json<<review::Upsert as ApiEndpoint>::Res>, error::Review> {
+    let (asset_type, id) = path.into_inner();
+    let user_id = claims.user_id();
+    let req = req.into_inner();
+
+    let (columns, author_id) = reviewable_asset(&db, asset_id(asset_type, id)).await?;
+
+    if author_id == Some(user_id) {
+        return Err(error::Review::Forbidden);
+    }
+
+    if !(REVIEW_RATING_MIN..=REVIEW_RATING_MAX).contains(&req.rating) {
+        return Err(error::Review::InvalidRating);
+    }
+
+    let text = validate_text(&req.text)?;
+
+    let id = db::review::upsert(&db, user_id, columns, req.rating as i16, text).await?;
+
+    Ok(Json(CreateResponse { id }))
+}
+
+/// Delete a review.
+async fn delete(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    path: Path<ReviewId>,
+) -> Result<HttpResponse, error::Review> {
+    let id = path.into_inner();
+    let user_id = claims.user_id();
+
+    let (reviewer_id, _) = db::review::get_authors(&db, id)
+        .await?
+        .ok_or(error::Review::ResourceNotFound)?;
+
+    if reviewer_id != user_id
+        && !db::user::has_scopes(&db, user_id, &[UserScope::Admin, UserScope::AdminAsset]).await?
+    {
+        return Err(error::Review::Forbidden);
+    }
+
+    db::review::delete(&db, id).await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// Reply to a review of one of the requesting user's assets.
+async fn reply(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    path: Path<ReviewId>,
+    req: Json<<review::Reply as ApiEndpoint>::Req>,
+) -> Result<HttpResponse, error::Review> {
+    let id = path.into_inner();
+
+    let (_, asset_author_id) = db::review::get_authors(&db, id)
+        .await?
+        .ok_or(error::Review::ResourceNotFound)?;
+
+    if asset_author_id != Some(claims.user_id()) {
+        return Err(error::Review::Forbidden);
+    }
+
+    let text = validate_text(&req.text)?;
+    let text = (!text.is_empty()).then(|| text);
+
+    db::review::reply(&db, id, text).await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// Report a review for abuse.
+async fn report(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    path: Path<ReviewId>,
+    req: Json<<review::Report as ApiEndpoint>::Req>,
+) -> Result<HttpResponse, error::Review> {
+    db::review::report(
+        &db,
+        path.into_inner(),
+        claims.user_id(),
+        req.into_inner().report_type,
+    )
+    .await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// List reports of reviews in the moderation queue, oldest first.
+async fn list_reports(
+    db: Data<PgPool>,
+    _auth: TokenUserWithScope<ScopeAdminJig>,
+    query: Option<Query<<review::ReportList as ApiEndpoint>::Req>>,
+) -> Result<Json<<review::ReportList as ApiEndpoint>::Res>, error::Review> {
+    let query = query.map_or_else(Default::default, Query::into_inner);
+
+    let page_limit = page_limit(query.page_limit).await?;
+
+    let reports =
+        db::review::list_reports(&db, query.status, query.page.unwrap_or(0), page_limit).await?;
+
+    let total_count = db::review::filtered_report_count(&db, query.status).await?;
+
+    let pages = (total_count / (page_limit as u64)
+        + (total_count % (page_limit as u64) != 0) as u64) as u32;
+
+    Ok(Json(ReviewReportListResponse {
+        reports,
+        pages,
+        total_report_count: total_count,
+    }))
+}
+
+/// Resolve the open reports of a review.
+async fn resolve_reports(
+    db: Data<PgPool>,
+    auth: TokenUserWithScope<ScopeAdminJig>,
+    path: Path<ReviewId>,
+    req: Json<<review::ReportResolve as ApiEndpoint>::Req>,
+) -> Result<HttpResponse, error::Review> {
+    let req = req.into_inner();
+
+    if req.action == ReportAction::ContactAuthor {
+        return Err(error::Review::UnsupportedAction);
+    }
+
+    let note = req
+        .note
+        .as_deref()
+        .map(str::trim)
+        .filter(|note| !note.is_empty());
+
+    let resolved = db::review::resolve_reports(
+        &db,
+        path.into_inner(),
+        req.action,
+        note,
+        UserId(auth.claims.user_id),
+    )
+    .await?;
+
+    if !resolved {
+        return Err(error::Review::ResourceNotFound);
+    }
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+pub fn configure(cfg: &mut ServiceConfig) {
+    // the moderation queue goes first, so that `admin` isn't taken for a review id
+    cfg.route(
+        <review::ReportList as ApiEndpoint>::Path::PATH,
+        review::ReportList::METHOD.route().to(list_reports),
+    )
+    .route(
+        <review::ReportResolve as ApiEndpoint>::Path::PATH,
+        review::ReportResolve::METHOD.route().to(resolve_reports),
+    )
+    .route(
+        <review::List as ApiEndpoint>::Path::PATH,
+        review::List::METHOD.route().to(list),
+    )
+    .route(
+        <review::Upsert as ApiEndpoint>::Path::PATH,
+        review::Upsert::METHOD.route().to(upsert),
+    )
+    .route(
+        <review::Delete as ApiEndpoint>::Path::PATH,
+        review::Delete::METHOD.route().to(delete),
+    )
+    .route(
+        <review::Reply as ApiEndpoint>::Path::PATH,
+        review::Reply::METHOD.route().to(reply),
+    )
+    .route(
+        <review::Report as ApiEndpoint>::Path::PATH,
+        review::Report::METHOD.route().to(report),
+    );
+}
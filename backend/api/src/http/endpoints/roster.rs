@@ -0,0 +1,202 @@
+use actix_web::{
+    web::{Data, Json, Path, ServiceConfig},
+    HttpResponse,
+};
+use shared::{
+    api::{endpoints::roster, ApiEndpoint, PathParts},
+    domain::{
+        roster::{
+            RosterId, RosterListResponse, RosterStudentCreateRequest, RosterStudentId,
+            ROSTER_NAME_MAX_LEN,
+        },
+        CreateResponse,
+    },
+};
+use sqlx::PgPool;
+
+use crate::{db, error, extractor::TokenUser};
+
+/// Trims a roster or student name, checking that it isn't empty or too long.
+fn validate_name(name: &str) -> Result<&str, error::Roster> {
+    let name = name.trim();
+
+    if name.is_empty() || name.chars().count() > ROSTER_NAME_MAX_LEN {
+        return Err(error::Roster::InvalidName);
+    }
+
+    Ok(name)
+}
+
+fn validate_student(
+    student: RosterStudentCreateRequest,
+) -> Result<RosterStudentCreateRequest, error::Roster> {
+    Ok(RosterStudentCreateRequest {
+        nickname: validate_name(&student.nickname)?.to_owned(),
+        picture_password: student.picture_password,
+    })
+}
+
+/// Create a roster.
+async fn create(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    req: Json<<roster::Create as ApiEndpoint>::Req>,
+) -> Result<HttpResponse, error::Roster> {
+    let req = req.into_inner();
+
+    let display_name = validate_name(&req.display_name)?;
+
+    let students = req
+        .students
+        .into_iter()
+        .map(validate_student)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let id = db::roster::create(&db, claims.user_id(), display_name, &students).await?;
+
+    Ok(HttpResponse::Created().json(CreateResponse { id }))
+}
+
+/// List the rosters of the requesting user.
+async fn list(
+    db: Data<PgPool>,
+    claims: TokenUser,
+) -> Result<Json<<roster::List as ApiEndpoint>::Res>, error::Roster> {
+    let rosters = db::roster::list(&db, claims.user_id()).await?;
+
+    Ok(Json(RosterListResponse { rosters }))
+}
+
+/// Get a roster.
+async fn get(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    path: Path<RosterId>,
+) -> Result<Json<<roster::Get as ApiEndpoint>::Res>, error::Roster> {
+    let roster = db::roster::get_one(&db, claims.user_id(), path.into_inner())
+        .await?
+        .ok_or(error::Roster::ResourceNotFound)?;
+
+    Ok(Json(roster))
+}
+
+/// Update a roster.
+async fn update(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    path: Path<RosterId>,
+    req: Option<Json<<roster::Update as ApiEndpoint>::Req>>,
+) -> Result<HttpResponse, error::Roster> {
+    let id = path.into_inner();
+    let req = req.map_or_else(Default::default, Json::into_inner);
+
+    db::roster::authz(&db, claims.user_id(), id).await?;
+
+    let display_name = req.display_name.as_deref().map(validate_name).transpose()?;
+
+    db::roster::update(&db, id, display_name).await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// Delete a roster.
+async fn delete(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    path: Path<RosterId>,
+) -> Result<HttpResponse, error::Roster> {
+    let id = path.into_inner();
+
+    db::roster::authz(&db, claims.user_id(), id).await?;
+
+    db::roster::delete(&db, id).await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// Add a student to a roster.
+async fn create_student(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    path: Path<RosterId>,
+    req: Json<<roster::student::Create as ApiEndpoint>::Req>,
+) -> Result<HttpResponse, error::Roster> {
+    let roster_id = path.into_inner();
+
+    db::roster::authz(&db, claims.user_id(), roster_id).await?;
+
+    let student = validate_student(req.into_inner())?;
+
+    let id = db::roster::create_student(&db, roster_id, &student).await?;
+
+    Ok(HttpResponse::Created().json(CreateResponse { id }))
+}
+
+/// Update a student on a roster.
+async fn update_student(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    path: Path<(RosterId, RosterStudentId)>,
+    req: Option<Json<<roster::student::Update as ApiEndpoint>::Req>>,
+) -> Result<HttpResponse, error::Roster> {
+    let (roster_id, id) = path.into_inner();
+    let req = req.map_or_else(Default::default, Json::into_inner);
+
+    db::roster::authz(&db, claims.user_id(), roster_id).await?;
+
+    let nickname = req.nickname.as_deref().map(validate_name).transpose()?;
+
+    db::roster::update_student(&db, roster_id, id, nickname, req.picture_password).await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// Remove a student from a roster.
+async fn delete_student(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    path: Path<(RosterId, RosterStudentId)>,
+) -> Result<HttpResponse, error::Roster> {
+    let (roster_id, id) = path.into_inner();
+
+    db::roster::authz(&db, claims.user_id(), roster_id).await?;
+
+    db::roster::delete_student(&db, roster_id, id).await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+pub fn configure(cfg: &mut ServiceConfig) {
+    cfg.route(
+        <roster::Create as ApiEndpoint>::Path::PATH,
+        roster::Create::METHOD.route().to(create),
+    )
+    .route(
+        <roster::List as ApiEndpoint>::Path::PATH,
+        roster::List::METHOD.route().to(list),
+    )
+    .route(
+        <roster::Get as ApiEndpoint>::Path::PATH,
+        roster::Get::METHOD.route().to(get),
+    )
+    .route(
+        <roster::Update as ApiEndpoint>::Path::PATH,
+        roster::Update::METHOD.route().to(update),
+    )
+    .route(
+        <roster::Delete as ApiEndpoint>::Path::PATH,
+        roster::Delete::METHOD.route().to(delete),
+    )
+    .route(
+        <roster::student::Create as ApiEndpoint>::Path::PATH,
+        roster::student::Create::METHOD.route().to(create_student),
+    )
+    .route(
+        <roster::student::Update as ApiEndpoint>::Path::PATH,
+        roster::student::Update::METHOD.route().to(update_student),
+    )
+    .route(
+        <roster::student::Delete as ApiEndpoint>::Path::PATH,
+        roster::student::Delete::METHOD.route().to(delete_student),
+    );
+}
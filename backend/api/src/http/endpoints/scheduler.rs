@@ -3,18 +3,23 @@ use actix_web::{
     HttpResponse,
 };
 use actix_web_httpauth::extractors::bearer::BearerAuth;
+use ji_core::settings::RuntimeSettings;
 use sqlx::PgPool;
 
-use crate::http::endpoints::scheduler::expired_emails::delete_expired_emails;
+use crate::http::endpoints::scheduler::{
+    billing_notifications::send_billing_notifications, expired_emails::delete_expired_emails,
+};
 use crate::{
     algolia::Manager,
-    error,
+    db, error,
     extractor::UserAgent,
     jwk::{IdentityClaims, JwkVerifier},
-    service::{upload::cleaner::UploadCleaner, ServiceData},
+    service::{mail, upload::cleaner::UploadCleaner, ServiceData},
     translate::GoogleTranslate,
+    webhook,
 };
 
+pub(crate) mod billing_notifications;
 pub(crate) mod expired_emails;
 
 async fn batch_update(
@@ -105,6 +110,109 @@ async fn clean_expired_emails(
     Ok(HttpResponse::Ok().finish())
 }
 
+async fn clean_expired_player_sessions(
+    bearer_auth: BearerAuth,
+    jwks: Data<JwkVerifier>,
+    db: Data<PgPool>,
+    user_agent: UserAgent,
+) -> Result<HttpResponse, error::ServiceSession> {
+    if user_agent
+        .0
+        .map_or(true, |it| it != "Google-Cloud-Scheduler")
+    {
+        return Err(error::ServiceSession::Unauthorized);
+    }
+
+    let _claims: IdentityClaims = jwks
+        .verify_iam_api_invoker_oauth(bearer_auth.token(), 3)
+        .await?;
+
+    let deleted = db::jig::player::delete_expired(&*db).await?;
+
+    log::info!("Deleted {} expired jig player sessions", deleted);
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+async fn clean_rate_limits(
+    bearer_auth: BearerAuth,
+    jwks: Data<JwkVerifier>,
+    db: Data<PgPool>,
+    user_agent: UserAgent,
+) -> Result<HttpResponse, error::ServiceSession> {
+    if user_agent
+        .0
+        .map_or(true, |it| it != "Google-Cloud-Scheduler")
+    {
+        return Err(error::ServiceSession::Unauthorized);
+    }
+
+    let _claims: IdentityClaims = jwks
+        .verify_iam_api_invoker_oauth(bearer_auth.token(), 3)
+        .await?;
+
+    let deleted = db::rate_limit::delete_stale(&*db).await?;
+
+    log::info!("Deleted {} stale rate limit buckets", deleted);
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+/// Retries failed webhook deliveries, and deletes old entries from the delivery log.
+async fn deliver_webhooks(
+    bearer_auth: BearerAuth,
+    jwks: Data<JwkVerifier>,
+    db: Data<PgPool>,
+    settings: Data<RuntimeSettings>,
+    user_agent: UserAgent,
+) -> Result<HttpResponse, error::ServiceSession> {
+    if user_agent
+        .0
+        .map_or(true, |it| it != "Google-Cloud-Scheduler")
+    {
+        return Err(error::ServiceSession::Unauthorized);
+    }
+
+    let _claims: IdentityClaims = jwks
+        .verify_iam_api_invoker_oauth(bearer_auth.token(), 3)
+        .await?;
+
+    let delivered = webhook::deliver_due(&*db, settings.is_local()).await?;
+    let deleted = db::webhook::delete_old_deliveries(&*db).await?;
+
+    log::info!(
+        "Delivered {} webhook events, deleted {} old deliveries",
+        delivered,
+        deleted
+    );
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+async fn billing_notifications(
+    bearer_auth: BearerAuth,
+    jwks: Data<JwkVerifier>,
+    db: Data<PgPool>,
+    mail: ServiceData<mail::Client>,
+    settings: Data<RuntimeSettings>,
+    user_agent: UserAgent,
+) -> Result<HttpResponse, error::ServiceSession> {
+    if user_agent
+        .0
+        .map_or(true, |it| it != "Google-Cloud-Scheduler")
+    {
+        return Err(error::ServiceSession::Unauthorized);
+    }
+
+    let _claims: IdentityClaims = jwks
+        .verify_iam_api_invoker_oauth(bearer_auth.token(), 3)
+        .await?;
+
+    send_billing_notifications(&*db, &mail, &settings.remote_target().pages_url()).await?;
+
+    Ok(HttpResponse::Ok().finish())
+}
+
 pub fn configure(cfg: &mut ServiceConfig) {
     cfg.route(
         "/v1/scheduler/update-algolia",
@@ -122,4 +230,20 @@ pub fn configure(cfg: &mut ServiceConfig) {
         "/v1/scheduler/expired-emails-clean",
         method(http::Method::POST).to(clean_expired_emails),
     );
+    cfg.route(
+        "/v1/scheduler/player-sessions-clean",
+        method(http::Method::POST).to(clean_expired_player_sessions),
+    );
+    cfg.route(
+        "/v1/scheduler/rate-limits-clean",
+        method(http::Method::POST).to(clean_rate_limits),
+    );
+    cfg.route(
+        "/v1/scheduler/webhooks-deliver",
+        method(http::Method::POST).to(deliver_webhooks),
+    );
+    cfg.route(
+        "/v1/scheduler/billing-notifications",
+        method(http::Method::POST).to(billing_notifications),
+    );
 }
@@ -0,0 +1,62 @@
+use sendgrid::v3::Email;
+use shared::domain::billing::BillingNotificationKind;
+use sqlx::PgPool;
+
+use crate::{db, service::mail};
+
+/// How many days before a trial ends that the account administrators are notified.
+const TRIAL_ENDING_NOTICE_DAYS: i32 = 3;
+
+/// Email account administrators about trials that are about to end, failed payments and expired
+/// subscriptions. Each notification is only sent once per subscription (and per invoice for failed
+/// payments), and users who have opted out of a notification kind are skipped for good.
+pub(crate) async fn send_billing_notifications(
+    db: &PgPool,
+    mail: &mail::Client,
+    pages_url: &str,
+) -> anyhow::Result<()> {
+    log::debug!("reached send billing notifications");
+
+    let settings_link = format!("{}/user/settings", pages_url);
+
+    for kind in [
+        BillingNotificationKind::TrialEnding,
+        BillingNotificationKind::PaymentFailed,
+        BillingNotificationKind::SubscriptionExpired,
+    ] {
+        let recipients =
+            db::billing::get_pending_billing_notifications(db, kind, TRIAL_ENDING_NOTICE_DAYS)
+                .await?;
+
+        for recipient in recipients {
+            if !recipient.preferences.is_enabled(kind) {
+                // Mark it as handled anyway, so that opting back in doesn't send a stale notice.
+                db::billing::mark_billing_notification_sent(db, &recipient, kind).await?;
+                continue;
+            }
+
+            if let Err(error) = mail
+                .send_billing_notification(
+                    Email::new(recipient.email.clone()),
+                    kind,
+                    recipient.given_name.clone(),
+                    settings_link.clone(),
+                )
+                .await
+            {
+                // Don't mark it as sent so that it is retried on the next run.
+                log::warn!(
+                    "Failed to send {} notification to user {}: {:?}",
+                    kind.as_str(),
+                    recipient.user_id,
+                    error
+                );
+                continue;
+            }
+
+            db::billing::mark_billing_notification_sent(db, &recipient, kind).await?;
+        }
+    }
+
+    Ok(())
+}
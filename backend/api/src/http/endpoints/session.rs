@@ -1,12 +1,18 @@
 use actix_web::{
-    web::{Data, ServiceConfig},
+    web::{Data, Json, Path, ServiceConfig},
     HttpResponse,
 };
 use chrono::{Duration, Utc};
 use ji_core::settings::RuntimeSettings;
 use shared::{
     api::{endpoints::session, ApiEndpoint, PathParts},
-    domain::session::{CreateSessionResponse, NewSessionResponse},
+    domain::{
+        session::{
+            CreateSessionResponse, ListActiveSessionsResponse, NewSessionResponse, SessionId,
+            VerifyTotpRequest,
+        },
+        user::UserId,
+    },
 };
 use sqlx::PgPool;
 
@@ -14,12 +20,35 @@ use crate::{
     db,
     domain::{NoContentClearAuth, RegistrationStatus},
     error,
-    extractor::{EmailBasicUser, SessionAny, TokenSessionOf},
+    extractor::{EmailBasicUser, SessionAny, SessionVerifyTotp, TokenSessionOf, TokenUser},
+    rate_limit::{self, RateLimit},
     token::{create_auth_token, SessionMask},
 };
 
 mod oauth;
 
+/// How long a user has to enter their two-factor code after signing in.
+const TWO_FACTOR_TTL_MINUTES: i64 = 10;
+
+/// Number of wrong two-factor codes after which a user has to sign in again.
+const MAX_FAILED_TOTP_ATTEMPTS: i16 = 5;
+
+/// Checks whether the user has to enter a two-factor code before getting a standard session,
+/// returning the mask for the session to issue in the meantime.
+///
+/// Users who have to but haven't set up two-factor authentication yet get a session for setting it up.
+async fn two_factor_mask(db: &PgPool, user_id: UserId) -> sqlx::Result<Option<SessionMask>> {
+    if db::user::totp::is_enabled(db, user_id).await? {
+        return Ok(Some(SessionMask::VERIFY_TOTP));
+    }
+
+    if db::user::totp::is_required(db, user_id).await? {
+        return Ok(Some(SessionMask::ENROLL_TOTP));
+    }
+
+    Ok(None)
+}
+
 /// Login with basic authorization.
 /// May return resources for *signing up* if the user doesn't have a profile.
 async fn create_session(
@@ -37,7 +66,10 @@ async fn create_session(
             SessionMask::PUT_PROFILE | SessionMask::DELETE_ACCOUNT,
             Utc::now() + Duration::hours(1),
         ),
-        RegistrationStatus::Complete => (SessionMask::GENERAL, Utc::now() + login_ttl),
+        RegistrationStatus::Complete => match two_factor_mask(&db, user.id).await? {
+            Some(mask) => (mask, Utc::now() + Duration::minutes(TWO_FACTOR_TTL_MINUTES)),
+            None => (SessionMask::GENERAL, Utc::now() + login_ttl),
+        },
     };
 
     let mut txn = db.begin().await?;
@@ -55,18 +87,85 @@ async fn create_session(
 
     let response = NewSessionResponse { csrf };
 
-    let response = if !mask.contains(SessionMask::GENERAL) {
+    let response = if mask.contains(SessionMask::GENERAL) {
+        CreateSessionResponse::Login(response)
+    } else if mask.intersects(SessionMask::VERIFY_TOTP | SessionMask::ENROLL_TOTP) {
+        CreateSessionResponse::TwoFactor {
+            response,
+            enrolled: mask.contains(SessionMask::VERIFY_TOTP),
+        }
+    } else {
         CreateSessionResponse::Register {
             response,
             oauth_profile: None,
         }
-    } else {
-        CreateSessionResponse::Login(response)
     };
 
     Ok(HttpResponse::Created().cookie(cookie).json(response))
 }
 
+/// Finish signing in with a two-factor code.
+async fn verify_totp(
+    db: Data<PgPool>,
+    settings: Data<RuntimeSettings>,
+    session: TokenSessionOf<SessionVerifyTotp>,
+    req: Json<VerifyTotpRequest>,
+) -> Result<HttpResponse, error::Totp> {
+    let user_id = UserId(session.claims.user_id);
+    let token = &session.claims.token;
+
+    let totp = db::user::totp::get(&db, user_id)
+        .await?
+        .filter(|totp| totp.enabled)
+        .ok_or(error::Totp::Unauthorized)?;
+
+    let mut txn = db.begin().await?;
+
+    let valid =
+        db::user::totp::check_code(&mut txn, user_id, &totp.secret, &req.code, Utc::now()).await?;
+
+    if !valid {
+        txn.rollback().await?;
+
+        let failed_attempts = db::session::record_failed_attempt(&db, token).await?;
+
+        if failed_attempts >= MAX_FAILED_TOTP_ATTEMPTS {
+            let mut conn = db.acquire().await?;
+            db::session::delete(&mut conn, token).await?;
+        }
+
+        return Err(error::Totp::Unauthorized);
+    }
+
+    let login_ttl = settings
+        .login_token_valid_duration
+        .unwrap_or(Duration::weeks(2));
+
+    db::session::delete(&mut txn, token).await?;
+
+    let session = db::session::create(
+        &mut txn,
+        user_id,
+        Some(&(Utc::now() + login_ttl)),
+        SessionMask::GENERAL,
+        None,
+    )
+    .await?;
+
+    let (csrf, cookie) = create_auth_token(
+        &settings.token_secret,
+        settings.is_local(),
+        login_ttl,
+        &session,
+    )?;
+
+    txn.commit().await?;
+
+    Ok(HttpResponse::Created()
+        .cookie(cookie)
+        .json(NewSessionResponse { csrf }))
+}
+
 /// Logout
 async fn delete_session(
     db: Data<PgPool>,
@@ -79,6 +178,38 @@ async fn delete_session(
     Ok(NoContentClearAuth)
 }
 
+/// List the sessions the user is signed in with.
+async fn list_active(
+    db: Data<PgPool>,
+    claims: TokenUser,
+) -> Result<Json<<session::ListActive as ApiEndpoint>::Res>, error::Server> {
+    let sessions = db::session::list_active(&db, claims.user_id(), &claims.0.token).await?;
+
+    Ok(Json(ListActiveSessionsResponse { sessions }))
+}
+
+/// Sign out one of the user's sessions.
+async fn revoke(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    path: Path<SessionId>,
+) -> Result<HttpResponse, error::NotFound> {
+    let revoked = db::session::revoke(&db, claims.user_id(), path.into_inner()).await?;
+
+    if !revoked {
+        return Err(error::NotFound::ResourceNotFound);
+    }
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// Sign out all of the user's sessions except the current one.
+async fn revoke_others(db: Data<PgPool>, claims: TokenUser) -> Result<HttpResponse, error::Server> {
+    db::session::revoke_others(&db, claims.user_id(), &claims.0.token).await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
 pub fn configure(cfg: &mut ServiceConfig) {
     cfg.route(
         <session::GetOAuthUrl as ApiEndpoint>::Path::PATH,
@@ -86,14 +217,44 @@ pub fn configure(cfg: &mut ServiceConfig) {
     )
     .route(
         <session::Create as ApiEndpoint>::Path::PATH,
-        session::Create::METHOD.route().to(create_session),
+        session::Create::METHOD
+            .route()
+            .to(create_session)
+            .wrap(RateLimit::new(&rate_limit::LOGIN)),
     )
     .route(
         <session::Delete as ApiEndpoint>::Path::PATH,
         session::Delete::METHOD.route().to(delete_session),
     )
+    .route(
+        <session::VerifyTotp as ApiEndpoint>::Path::PATH,
+        session::VerifyTotp::METHOD
+            .route()
+            .to(verify_totp)
+            .wrap(RateLimit::new(&rate_limit::LOGIN)),
+    )
     .route(
         <session::CreateOAuth as ApiEndpoint>::Path::PATH,
         session::CreateOAuth::METHOD.route().to(oauth::create),
+    )
+    .route(
+        <session::GetSsoUrl as ApiEndpoint>::Path::PATH,
+        session::GetSsoUrl::METHOD.route().to(oauth::get_sso_url),
+    )
+    .route(
+        crate::apple_oauth::CALLBACK_PATH,
+        actix_web::web::post().to(oauth::apple_callback),
+    )
+    .route(
+        <session::ListActive as ApiEndpoint>::Path::PATH,
+        session::ListActive::METHOD.route().to(list_active),
+    )
+    .route(
+        <session::Revoke as ApiEndpoint>::Path::PATH,
+        session::Revoke::METHOD.route().to(revoke),
+    )
+    .route(
+        <session::RevokeOthers as ApiEndpoint>::Path::PATH,
+        session::RevokeOthers::METHOD.route().to(revoke_others),
     );
 }
@@ -1,6 +1,7 @@
 use actix_web::{
     cookie::Cookie,
-    web::{Data, Json, Path},
+    http::header,
+    web::{Data, Form, Json, Path, Query},
     HttpRequest, HttpResponse,
 };
 use chrono::{Duration, Utc};
@@ -11,22 +12,39 @@ use ji_core::settings::{GoogleOAuth, RuntimeSettings};
 use shared::{
     config::RemoteTarget,
     domain::{
+        billing::{AccountId, SubscriptionTier},
         session::{
             CreateSessionOAuthRequest, CreateSessionResponse, GetOAuthUrlResponse,
-            GetOAuthUrlServiceKind, NewSessionResponse, OAuthUrlKind,
+            GetOAuthUrlServiceKind, GetSsoUrlQuery, NewSessionResponse, OAuthProvider,
+            OAuthUrlKind,
         },
         user::UserId,
     },
 };
 
 use crate::{
-    db, error,
+    apple_oauth, db, error,
     google_oauth::{self, oauth_url},
     jwk::{self, IdentityClaims},
+    microsoft_oauth, oidc,
     token::{create_auth_token, SessionMask},
 };
 use shared::domain::session::OAuthUserProfile;
 
+/// The user already signs in with another account of the same provider.
+fn handle_link_error(e: sqlx::Error) -> error::OAuth {
+    let db_err = match &e {
+        sqlx::Error::Database(e) => e.downcast_ref::<PgDatabaseError>(),
+        _ => return e.into(),
+    };
+
+    match db_err.constraint() {
+        Some(constraint) if constraint.ends_with("_pkey") => error::OAuth::Conflict,
+
+        _ => e.into(),
+    }
+}
+
 fn handle_user_email_error(e: sqlx::Error) -> error::OAuth {
     let db_err = match &e {
         sqlx::Error::Database(e) => e.downcast_ref::<PgDatabaseError>(),
@@ -47,37 +65,153 @@ pub async fn get_url(
 ) -> Result<Json<GetOAuthUrlResponse>, error::Service> {
     let (service_kind, url_kind) = path.into_inner();
 
-    match service_kind {
-        GetOAuthUrlServiceKind::Google => {}
-        it => return Err(anyhow::anyhow!("Unsupported OAuth service kind: {:?}", it).into()),
-    }
+    // todo: add / verify `state`, for now it's only used to tell the providers apart on the callback page.
+    let url = match service_kind {
+        GetOAuthUrlServiceKind::Google => {
+            let oauth_config =
+                config
+                    .google_oauth
+                    .as_ref()
+                    .ok_or(error::Service::DisabledService(
+                        error::ServiceKind::GoogleOAuth,
+                    ))?;
+
+            let route = oauth_url(config.remote_target(), url_kind);
+
+            let mut url: Url = req.url_for_static("google_cloud_oauth")?;
+
+            let url = url
+                .query_pairs_mut()
+                .append_pair("client_id", &oauth_config.client)
+                .append_pair("response_type", "code")
+                .append_pair("include_granted_scopes", "true")
+                .append_pair("redirect_uri", &route)
+                .append_pair("scope", "openid email")
+                .finish()
+                .to_string();
+
+            url
+        }
 
-    let oauth_config = config
-        .google_oauth
-        .as_ref()
-        .ok_or(error::Service::DisabledService(
-            error::ServiceKind::GoogleOAuth,
-        ))?;
+        GetOAuthUrlServiceKind::Microsoft => {
+            let oauth_config =
+                config
+                    .microsoft_oauth
+                    .as_ref()
+                    .ok_or(error::Service::DisabledService(
+                        error::ServiceKind::MicrosoftOAuth,
+                    ))?;
+
+            let route = oauth_url(config.remote_target(), url_kind);
+
+            let mut url: Url = req.url_for_static("microsoft_oauth")?;
+
+            let url = url
+                .query_pairs_mut()
+                .append_pair("client_id", &oauth_config.client)
+                .append_pair("response_type", "code")
+                .append_pair("response_mode", "query")
+                .append_pair("redirect_uri", &route)
+                .append_pair("scope", microsoft_oauth::SCOPE)
+                .append_pair("state", "microsoft")
+                .finish()
+                .to_string();
+
+            url
+        }
 
-    let route = oauth_url(config.remote_target(), url_kind);
+        GetOAuthUrlServiceKind::Apple => {
+            let oauth_config =
+                config
+                    .apple_oauth
+                    .as_ref()
+                    .ok_or(error::Service::DisabledService(
+                        error::ServiceKind::AppleOAuth,
+                    ))?;
+
+            let route = apple_oauth::callback_url(config.remote_target(), url_kind);
+
+            let mut url: Url = req.url_for_static("apple_oauth")?;
+
+            let url = url
+                .query_pairs_mut()
+                .append_pair("client_id", &oauth_config.client)
+                .append_pair("response_type", "code")
+                .append_pair("response_mode", "form_post")
+                .append_pair("redirect_uri", &route)
+                .append_pair("scope", apple_oauth::SCOPE)
+                .append_pair("state", "apple")
+                .finish()
+                .to_string();
+
+            url
+        }
 
-    let mut url: Url = req.url_for_static("google_cloud_oauth")?;
+        it => return Err(anyhow::anyhow!("Unsupported OAuth service kind: {:?}", it).into()),
+    };
 
-    // todo: add / verify `state`
+    Ok(Json(GetOAuthUrlResponse { url }))
+}
 
-    let url = url
-        .query_pairs_mut()
-        .append_pair("client_id", &oauth_config.client)
-        .append_pair("response_type", "code")
-        .append_pair("include_granted_scopes", "true")
-        .append_pair("redirect_uri", &route)
-        .append_pair("scope", "openid email")
-        .finish()
-        .to_string();
+/// Gets the authorization URL of the identity provider of the school the email belongs to.
+pub async fn get_sso_url(
+    db: Data<PgPool>,
+    settings: Data<RuntimeSettings>,
+    path: Path<OAuthUrlKind>,
+    query: Query<GetSsoUrlQuery>,
+) -> Result<Json<GetOAuthUrlResponse>, error::OAuth> {
+    let domain = query
+        .email
+        .trim()
+        .rsplit_once('@')
+        .map(|(_, domain)| domain)
+        .ok_or(error::OAuth::SsoNotFound)?;
+
+    let sso = db::account::find_school_sso_by_domain(&db, domain)
+        .await?
+        .ok_or(error::OAuth::SsoNotFound)?;
+
+    let discovery = oidc::discover(&sso.issuer, settings.is_local()).await?;
+
+    let route = oauth_url(settings.remote_target(), path.into_inner());
+
+    // todo: add / verify a nonce in `state`, for now it only tells the page which school to sign in with.
+    let url = oidc::authorization_url(
+        &discovery,
+        &sso.client_id,
+        &route,
+        &format!("sso:{}", sso.school_id),
+    )?;
 
     Ok(Json(GetOAuthUrlResponse { url }))
 }
 
+/// Receives the code from Apple, which posts it instead of redirecting to the page, and redirects
+/// to the page with it like the other providers.
+pub async fn apple_callback(
+    settings: Data<RuntimeSettings>,
+    path: Path<OAuthUrlKind>,
+    form: Form<apple_oauth::CallbackForm>,
+) -> Result<HttpResponse, error::Service> {
+    let form = form.into_inner();
+
+    let pages_url = settings.remote_target().pages_url();
+
+    let location = match form.code {
+        Some(code) if form.error.is_none() => Url::parse_with_params(
+            &oauth_url(settings.remote_target(), path.into_inner()),
+            &[("code", code.as_str()), ("state", "apple")],
+        )?
+        .to_string(),
+        // e.g. the user cancelled
+        _ => format!("{}/user/login", pages_url),
+    };
+
+    Ok(HttpResponse::SeeOther()
+        .insert_header((header::LOCATION, location))
+        .finish())
+}
+
 /// Login with OAuth
 /// May return resources for *signing up* if the user doesn't exist.
 pub async fn create(
@@ -86,7 +220,7 @@ pub async fn create(
     req: Json<CreateSessionOAuthRequest>,
     jwks: Data<jwk::JwkVerifier>,
 ) -> Result<HttpResponse, error::OAuth> {
-    let (response, cookie) = match req.into_inner() {
+    let identity = match req.into_inner() {
         CreateSessionOAuthRequest::Google {
             code,
             redirect_kind,
@@ -96,97 +230,302 @@ pub async fn create(
                 .as_ref()
                 .ok_or(error::GoogleOAuth::Disabled)?;
 
-            handle_google_oauth(
-                &db,
-                &config,
-                &settings.token_secret,
-                settings.is_local(),
+            google_identity(
+                config,
                 &jwks,
                 &code,
-                settings.login_token_valid_duration,
                 settings.remote_target(),
                 redirect_kind,
             )
             .await?
         }
 
+        CreateSessionOAuthRequest::Microsoft {
+            code,
+            redirect_kind,
+        } => {
+            let config = settings
+                .microsoft_oauth
+                .as_ref()
+                .ok_or(error::OAuth::Disabled(error::ServiceKind::MicrosoftOAuth))?;
+
+            let redirect_url = oauth_url(settings.remote_target(), redirect_kind);
+
+            let tokens = microsoft_oauth::convert_oauth_code(config, &code, &redirect_url).await?;
+
+            let claims = microsoft_oauth::verify_id_token(config, &tokens.id_token).await?;
+
+            OAuthIdentity {
+                provider: OAuthProvider::Microsoft,
+                id: claims.id(),
+                email_verified: claims.email_verified(),
+                email: claims.email,
+                name: claims.name,
+                profile_picture: None,
+                given_name: claims.given_name,
+                family_name: claims.family_name,
+                locale: None,
+                account_id: None,
+            }
+        }
+
+        CreateSessionOAuthRequest::Apple {
+            code,
+            redirect_kind,
+        } => {
+            let config = settings
+                .apple_oauth
+                .as_ref()
+                .ok_or(error::OAuth::Disabled(error::ServiceKind::AppleOAuth))?;
+
+            let redirect_url = apple_oauth::callback_url(settings.remote_target(), redirect_kind);
+
+            let tokens = apple_oauth::convert_oauth_code(config, &code, &redirect_url).await?;
+
+            let claims = apple_oauth::verify_id_token(config, &tokens.id_token).await?;
+
+            // Apple only shares the user's name with the callback the first time they sign in,
+            // so they enter it when registering instead.
+            OAuthIdentity {
+                provider: OAuthProvider::Apple,
+                id: claims.apple_id,
+                email_verified: claims.email.is_some() && claims.email_verified,
+                email: claims.email,
+                name: None,
+                profile_picture: None,
+                given_name: None,
+                family_name: None,
+                locale: None,
+                account_id: None,
+            }
+        }
+
+        CreateSessionOAuthRequest::Sso {
+            school_id,
+            code,
+            redirect_kind,
+        } => {
+            let sso = db::account::get_enabled_school_sso(&db, &school_id)
+                .await?
+                .ok_or(error::OAuth::SsoNotFound)?;
+
+            sso_identity(
+                &sso,
+                &code,
+                settings.remote_target(),
+                redirect_kind,
+                settings.is_local(),
+            )
+            .await?
+        }
+
         other => return Err(anyhow::anyhow!("Unsupported OAuth request kind: {:?}", other).into()),
     };
 
+    let (response, cookie) = sign_in(
+        &db,
+        identity,
+        &settings.token_secret,
+        settings.is_local(),
+        settings.login_token_valid_duration,
+    )
+    .await?;
+
     Ok(HttpResponse::Created().cookie(cookie).json(response))
 }
 
-async fn handle_google_oauth(
-    db: &PgPool,
+/// A user's account with an OAuth provider, as verified by the provider.
+struct OAuthIdentity {
+    provider: OAuthProvider,
+
+    /// The id of the user with the provider
+    id: String,
+
+    email: Option<String>,
+
+    /// If the provider verified that the user owns the email.
+    email_verified: bool,
+
+    name: Option<String>,
+    profile_picture: Option<String>,
+    given_name: Option<String>,
+    family_name: Option<String>,
+    locale: Option<String>,
+
+    /// The (school) account the user is added to, if they aren't a member yet.
+    account_id: Option<AccountId>,
+}
+
+async fn google_identity(
     config: &GoogleOAuth,
-    token_secret: &[u8; 32],
-    local_insecure: bool,
     jwks: &jwk::JwkVerifier,
     code: &str,
-    login_token_valid_duration: Option<Duration>,
     remote_target: RemoteTarget,
     redirect_kind: OAuthUrlKind,
-) -> Result<(CreateSessionResponse, Cookie<'static>), error::OAuth> {
+) -> Result<OAuthIdentity, error::OAuth> {
     let redirect_url = google_oauth::oauth_url(remote_target, redirect_kind);
 
     let tokens = google_oauth::convert_oauth_code(config, code, &redirect_url).await?;
 
     let claims: IdentityClaims = jwks.verify_google_user_oauth(&tokens.id_token, 3).await?;
 
+    Ok(OAuthIdentity {
+        provider: OAuthProvider::Google,
+        id: claims.google_id,
+        email: Some(claims.email),
+        email_verified: claims.email_verified,
+        name: claims.name,
+        profile_picture: claims.profile_picture,
+        given_name: claims.given_name,
+        family_name: claims.family_name,
+        locale: claims.locale,
+        account_id: None,
+    })
+}
+
+async fn sso_identity(
+    sso: &db::account::SchoolSso,
+    code: &str,
+    remote_target: RemoteTarget,
+    redirect_kind: OAuthUrlKind,
+    local: bool,
+) -> Result<OAuthIdentity, error::OAuth> {
+    let discovery = oidc::discover(&sso.issuer, local).await?;
+
+    let client = oidc::Client {
+        client_id: &sso.client_id,
+        client_secret: &sso.client_secret,
+    };
+
+    let redirect_url = oauth_url(remote_target, redirect_kind);
+
+    let tokens = oidc::convert_oauth_code(&discovery, &client, code, &redirect_url, local).await?;
+
+    let claims = oidc::verify_id_token(&discovery, &client, &tokens.id_token, local).await?;
+
+    // The school vouches for the emails of its own domains (unless the identity provider says otherwise),
+    // but not for any others.
+    let email_verified = claims.email_verified != Some(false)
+        && claims.email.as_deref().map_or(false, |email| {
+            email.rsplit_once('@').map_or(false, |(_, domain)| {
+                sso.email_domains
+                    .iter()
+                    .any(|it| it.eq_ignore_ascii_case(domain))
+            })
+        });
+
+    Ok(OAuthIdentity {
+        provider: OAuthProvider::Sso,
+        id: format!("{}:{}", sso.school_id, claims.subject),
+        email: claims.email,
+        email_verified,
+        name: claims.name,
+        profile_picture: claims.profile_picture,
+        given_name: claims.given_name,
+        family_name: claims.family_name,
+        locale: claims.locale,
+        account_id: Some(sso.account_id),
+    })
+}
+
+/// Signs in the user with the provider account, creating the user if needed.
+///
+/// If there's no user for the provider account yet, but there's one with the same (verified) email,
+/// the provider account is linked to that user.
+async fn sign_in(
+    db: &PgPool,
+    identity: OAuthIdentity,
+    token_secret: &[u8; 32],
+    local_insecure: bool,
+    login_token_valid_duration: Option<Duration>,
+) -> Result<(CreateSessionResponse, Cookie<'static>), error::OAuth> {
     let mut txn = db.begin().await?;
 
-    let google_auth = sqlx::query!(
-        "select user_id from user_auth_google where google_id = $1",
-        &claims.google_id
-    )
-    .fetch_optional(&mut txn)
-    .await?;
+    let mut user_id = db::user::oauth::find(&mut txn, identity.provider, &identity.id).await?;
+
+    if user_id.is_none() {
+        let email = match &identity.email {
+            Some(email) if identity.email_verified => email,
+            _ => return Err(error::OAuth::UnverifiedEmail),
+        };
+
+        // emails are only added to `user_email` once they're verified
+        let existing = sqlx::query!(
+            r#"select user_id as "user_id: UserId" from user_email where email = lower($1::text)"#,
+            email
+        )
+        .fetch_optional(&mut txn)
+        .await?;
+
+        if let Some(existing) = existing {
+            // a school's identity provider can only sign in the school's own members.
+            if let Some(account_id) = &identity.account_id {
+                let membership =
+                    db::account::user_account_membership(db, &existing.user_id, account_id).await?;
+
+                if membership.is_none() {
+                    return Err(error::OAuth::Conflict);
+                }
+            }
+
+            db::user::oauth::link(&mut txn, existing.user_id, identity.provider, &identity.id)
+                .await
+                .map_err(handle_link_error)?;
+
+            user_id = Some(existing.user_id);
+        }
+    }
 
-    let (user_id, mask) = match &google_auth {
-        Some(google_auth) => {
+    let (user_id, mask) = match user_id {
+        Some(user_id) => {
             // make sure that the user either has a profile, or can only *create* one.
             let check_profile = sqlx::query!(
                 r#"select exists(select 1 from user_profile where user_id = $1) as "exists!""#,
-                google_auth.user_id
+                user_id.0
             )
             .fetch_one(&mut txn)
             .await?;
 
             let mask = if check_profile.exists {
-                SessionMask::GENERAL
+                super::two_factor_mask(db, user_id)
+                    .await?
+                    .unwrap_or(SessionMask::GENERAL)
             } else {
                 SessionMask::PUT_PROFILE | SessionMask::DELETE_ACCOUNT
             };
 
-            (google_auth.user_id, mask)
+            (user_id, mask)
         }
         None => {
-            if !claims.email_verified {
-                return Err(error::OAuth::Google(error::GoogleOAuth::UnverifiedEmail));
-            }
-
             let id = sqlx::query!(r#"insert into "user" default values returning id"#)
                 .fetch_one(&mut txn)
                 .await?
                 .id;
 
-            sqlx::query!(
-                r"insert into user_auth_google (user_id, google_id) values ($1, $2)",
-                id,
-                &claims.google_id
-            )
-            .execute(&mut txn)
-            .await?;
+            db::user::oauth::link(&mut txn, UserId(id), identity.provider, &identity.id).await?;
 
             sqlx::query!(
                 "insert into user_email (user_id, email) values ($1, lower($2::text))",
                 id,
-                &claims.email
+                identity.email.as_deref()
             )
             .execute(&mut txn)
             .await
             .map_err(handle_user_email_error)?;
-            (id, SessionMask::PUT_PROFILE)
+
+            if let Some(account_id) = &identity.account_id {
+                db::account::associate_user_with_account(
+                    &mut txn,
+                    &UserId(id),
+                    account_id,
+                    &SubscriptionTier::Pro,
+                    false,
+                    true,
+                )
+                .await?;
+            }
+
+            (UserId(id), SessionMask::PUT_PROFILE)
         }
     };
 
@@ -195,12 +534,13 @@ async fn handle_google_oauth(
     let valid_until = Utc::now()
         + if mask.contains(SessionMask::PUT_PROFILE) {
             Duration::hours(1)
-        } else {
+        } else if mask.contains(SessionMask::GENERAL) {
             login_ttl
+        } else {
+            Duration::minutes(super::TWO_FACTOR_TTL_MINUTES)
         };
 
-    let session =
-        db::session::create(&mut txn, UserId(user_id), Some(&valid_until), mask, None).await?;
+    let session = db::session::create(&mut txn, user_id, Some(&valid_until), mask, None).await?;
 
     txn.commit().await?;
 
@@ -208,23 +548,187 @@ async fn handle_google_oauth(
 
     let response = NewSessionResponse { csrf };
 
-    let response = if !mask.contains(SessionMask::GENERAL) {
+    let response = if mask.contains(SessionMask::GENERAL) {
+        CreateSessionResponse::Login(response)
+    } else if mask.intersects(SessionMask::VERIFY_TOTP | SessionMask::ENROLL_TOTP) {
+        CreateSessionResponse::TwoFactor {
+            response,
+            enrolled: mask.contains(SessionMask::VERIFY_TOTP),
+        }
+    } else {
         let profile = OAuthUserProfile {
-            email: claims.email.clone(),
-            name: claims.name,
-            profile_picture: claims.profile_picture,
-            given_name: claims.given_name,
-            family_name: claims.family_name,
-            locale: claims.locale,
+            email: identity.email.unwrap_or_default(),
+            name: identity.name,
+            profile_picture: identity.profile_picture,
+            given_name: identity.given_name,
+            family_name: identity.family_name,
+            locale: identity.locale,
         };
 
         CreateSessionResponse::Register {
             response,
             oauth_profile: Some(profile),
         }
-    } else {
-        CreateSessionResponse::Login(response)
     };
 
     Ok((response, cookie))
 }
+
+#[cfg(test)]
+mod tests {
+    use shared::domain::session::{CreateSessionResponse, OAuthProvider};
+    use sqlx::{Executor, PgPool};
+
+    use super::{sign_in, OAuthIdentity};
+    use crate::{db, error};
+
+    fn identity(provider: OAuthProvider, email: &str, email_verified: bool) -> OAuthIdentity {
+        OAuthIdentity {
+            provider,
+            id: "provider-user".to_owned(),
+            email: Some(email.to_owned()),
+            email_verified,
+            name: Some("Test User".to_owned()),
+            profile_picture: None,
+            given_name: Some("Test".to_owned()),
+            family_name: Some("User".to_owned()),
+            locale: None,
+            account_id: None,
+        }
+    }
+
+    async fn user_count(pool: &PgPool) -> sqlx::Result<i64> {
+        sqlx::query_scalar(
+            // language=SQL
+            r#"select count(*) from "user""#,
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    #[sqlx::test]
+    async fn sign_in_creates_user(pool: PgPool) -> sqlx::Result<()> {
+        for provider in [OAuthProvider::Microsoft, OAuthProvider::Apple] {
+            let email = format!("{:?}@example.com", provider);
+
+            let (response, _) = sign_in(
+                &pool,
+                identity(provider, &email, true),
+                &[0; 32],
+                true,
+                None,
+            )
+            .await
+            .expect("user should be created");
+
+            assert!(matches!(
+                response,
+                CreateSessionResponse::Register {
+                    oauth_profile: Some(_),
+                    ..
+                }
+            ));
+
+            let linked =
+                db::user::oauth::find(&mut *pool.acquire().await?, provider, "provider-user")
+                    .await?;
+
+            let email_owner: Option<uuid::Uuid> = sqlx::query_scalar(
+                // language=SQL
+                r#"select user_id from user_email where email = lower($1)"#,
+            )
+            .bind(&email)
+            .fetch_optional(&pool)
+            .await?;
+
+            assert_eq!(linked.map(|it| it.0), email_owner);
+            assert!(linked.is_some());
+        }
+
+        assert_eq!(user_count(&pool).await?, 2);
+
+        // signing in again doesn't create another user
+        sign_in(
+            &pool,
+            identity(OAuthProvider::Microsoft, "Microsoft@example.com", true),
+            &[0; 32],
+            true,
+            None,
+        )
+        .await
+        .expect("user should sign in");
+
+        assert_eq!(user_count(&pool).await?, 2);
+
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn sign_in_links_existing_user(pool: PgPool) -> sqlx::Result<()> {
+        pool.execute(include_str!("../../../../fixtures/1_user.sql"))
+            .await?;
+
+        let users = user_count(&pool).await?;
+
+        // the user is an admin, so they have to set up two-factor authentication first
+        let (response, _) = sign_in(
+            &pool,
+            identity(OAuthProvider::Microsoft, "TEST@test.test", true),
+            &[0; 32],
+            true,
+            None,
+        )
+        .await
+        .expect("user should sign in");
+
+        assert!(matches!(
+            response,
+            CreateSessionResponse::TwoFactor {
+                enrolled: false,
+                ..
+            }
+        ));
+
+        assert_eq!(user_count(&pool).await?, users);
+
+        let linked = db::user::oauth::find(
+            &mut *pool.acquire().await?,
+            OAuthProvider::Microsoft,
+            "provider-user",
+        )
+        .await?;
+
+        assert_eq!(
+            linked.map(|it| it.0.to_string()),
+            Some("1f241e1b-b537-493f-a230-075cb16315be".to_owned())
+        );
+
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn sign_in_unverified_email(pool: PgPool) -> sqlx::Result<()> {
+        pool.execute(include_str!("../../../../fixtures/1_user.sql"))
+            .await?;
+
+        let users = user_count(&pool).await?;
+
+        // anyone could claim to own the email, so neither link nor create a user
+        for email in ["test@test.test", "new@example.com"] {
+            let res = sign_in(
+                &pool,
+                identity(OAuthProvider::Microsoft, email, false),
+                &[0; 32],
+                true,
+                None,
+            )
+            .await;
+
+            assert!(matches!(res, Err(error::OAuth::UnverifiedEmail)));
+        }
+
+        assert_eq!(user_count(&pool).await?, users);
+
+        Ok(())
+    }
+}
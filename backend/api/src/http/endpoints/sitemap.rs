@@ -0,0 +1,43 @@
+use actix_web::web::{Data, Json, Path, ServiceConfig};
+use shared::{
+    api::{endpoints::sitemap, ApiEndpoint, PathParts},
+    domain::sitemap::{SitemapIndexResponse, SitemapKind, SitemapPageResponse},
+};
+use sqlx::PgPool;
+use tracing::instrument;
+
+use crate::{db, error};
+
+/// Get the number of sitemap pages for each kind of public content.
+#[instrument(skip(db))]
+async fn index(
+    db: Data<PgPool>,
+) -> Result<Json<<sitemap::Index as ApiEndpoint>::Res>, error::Server> {
+    let sitemaps = db::sitemap::summaries(&db).await?;
+
+    Ok(Json(SitemapIndexResponse { sitemaps }))
+}
+
+/// Get a page of sitemap entries for a kind of public content.
+#[instrument(skip(db))]
+async fn page(
+    db: Data<PgPool>,
+    path: Path<(SitemapKind, u32)>,
+) -> Result<Json<<sitemap::Page as ApiEndpoint>::Res>, error::Server> {
+    let (kind, page) = path.into_inner();
+
+    let entries = db::sitemap::list(&db, kind, page).await?;
+
+    Ok(Json(SitemapPageResponse { entries }))
+}
+
+pub fn configure(cfg: &mut ServiceConfig) {
+    cfg.route(
+        <sitemap::Index as ApiEndpoint>::Path::PATH,
+        sitemap::Index::METHOD.route().to(index),
+    )
+    .route(
+        <sitemap::Page as ApiEndpoint>::Path::PATH,
+        sitemap::Page::METHOD.route().to(page),
+    );
+}
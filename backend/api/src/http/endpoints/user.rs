@@ -15,11 +15,12 @@ use shared::{
     api::endpoints::{
         user::{
             self, Browse, BrowseFollowers, BrowseFollowing, BrowsePlaylists, BrowsePublicUser,
-            BrowseResources, BrowseUserJigs, ChangePassword, Create, CreateColor, CreateFont,
-            CreateProfile, Delete, DeleteColor, DeleteFont, Follow, GetColors, GetFonts,
-            GetPublicUser, PatchProfile, PatchProfileAdminData, Profile, ResetEmail, ResetPassword,
-            Search, SearchUser, Unfollow, UpdateColor, UpdateFont, UserLookup, VerifyEmail,
-            VerifyResetEmail,
+            BrowseResources, BrowseUserJigs, ChangePassword, ConfirmTotp, Create, CreateApiToken,
+            CreateColor, CreateFont, CreateProfile, Delete, DeleteColor, DeleteFont, DisableTotp,
+            EnrollTotp, Feed, Follow, GetColors, GetFonts, GetPublicUser, GetTotp, ListApiTokens,
+            PatchProfile, PatchProfileAdminData, Profile, RegenerateRecoveryCodes, ResetEmail,
+            ResetPassword, RevokeApiToken, Search, SearchUser, Unfollow, UpdateColor, UpdateFont,
+            UserLookup, VerifyEmail, VerifyResetEmail,
         },
         ApiEndpoint, PathParts,
     },
@@ -41,7 +42,11 @@ use crate::{
     db::{self, user::upsert_profile},
     domain::NoContentClearAuth,
     error::{self, ServiceKind},
-    extractor::{ScopeAdmin, SessionCreateProfile, SessionDelete, TokenSessionOf, TokenUser},
+    extractor::{
+        ScopeAdmin, SessionCreateProfile, SessionDelete, TokenSessionOf, TokenUser,
+        TokenUserOrApiToken,
+    },
+    rate_limit::{self, RateLimit},
     service::{mail, s3, ServiceData},
     token::{create_auth_token, SessionMask},
 };
@@ -52,9 +57,11 @@ use crate::{
 
 use super::jig::page_limit;
 
+mod api_token;
 mod color;
 mod font;
 pub mod public_user;
+mod totp;
 
 #[instrument(skip(txn, email_address, mail))]
 async fn send_verification_email(
@@ -141,7 +148,11 @@ async fn send_password_email(
         mail.send_password_reset(template, Email::new(email_address), email_link, first_name)
             .await?;
     } else {
-        mail.send_oauth_password_reset(Email::new(email_address), OAuthProvider::Google)
+        let provider = db::user::oauth::get_provider(&mut *txn, user_id)
+            .await?
+            .unwrap_or(OAuthProvider::Google);
+
+        mail.send_oauth_password_reset(Email::new(email_address), provider)
             .await?;
     }
 
@@ -856,7 +867,7 @@ async fn patch_profile_admin_data(
 #[instrument(skip_all)]
 async fn get_profile(
     db: Data<PgPool>,
-    claims: TokenUser,
+    claims: TokenUserOrApiToken,
 ) -> Result<Json<<Profile as ApiEndpoint>::Res>, error::UserNotFound> {
     // todo: figure out how to do `<Profile as ApiEndpoint>::Err`
     let user_id = claims.user_id();
@@ -1104,7 +1115,10 @@ pub fn configure(cfg: &mut ServiceConfig) {
     )
     .route(
         <Create as ApiEndpoint>::Path::PATH,
-        Create::METHOD.route().to(create_user),
+        Create::METHOD
+            .route()
+            .to(create_user)
+            .wrap(RateLimit::new(&rate_limit::SIGNUP_VERIFICATION)),
     )
     .route(
         <Browse as ApiEndpoint>::Path::PATH,
@@ -1116,11 +1130,17 @@ pub fn configure(cfg: &mut ServiceConfig) {
     )
     .route(
         <VerifyEmail as ApiEndpoint>::Path::PATH,
-        VerifyEmail::METHOD.route().to(verify_email),
+        VerifyEmail::METHOD
+            .route()
+            .to(verify_email)
+            .wrap(RateLimit::new(&rate_limit::SIGNUP_VERIFICATION)),
     )
     .route(
         <ResetPassword as ApiEndpoint>::Path::PATH,
-        ResetPassword::METHOD.route().to(reset_password),
+        ResetPassword::METHOD
+            .route()
+            .to(reset_password)
+            .wrap(RateLimit::new(&rate_limit::PASSWORD_RESET)),
     )
     .route(
         <ChangePassword as ApiEndpoint>::Path::PATH,
@@ -1180,6 +1200,40 @@ pub fn configure(cfg: &mut ServiceConfig) {
         <DeleteFont as ApiEndpoint>::Path::PATH,
         DeleteFont::METHOD.route().to(font::delete),
     )
+    .route(
+        <GetTotp as ApiEndpoint>::Path::PATH,
+        GetTotp::METHOD.route().to(totp::get),
+    )
+    .route(
+        <EnrollTotp as ApiEndpoint>::Path::PATH,
+        EnrollTotp::METHOD.route().to(totp::enroll),
+    )
+    .route(
+        <ConfirmTotp as ApiEndpoint>::Path::PATH,
+        ConfirmTotp::METHOD.route().to(totp::confirm),
+    )
+    .route(
+        <DisableTotp as ApiEndpoint>::Path::PATH,
+        DisableTotp::METHOD.route().to(totp::disable),
+    )
+    .route(
+        <RegenerateRecoveryCodes as ApiEndpoint>::Path::PATH,
+        RegenerateRecoveryCodes::METHOD
+            .route()
+            .to(totp::regenerate_recovery_codes),
+    )
+    .route(
+        <CreateApiToken as ApiEndpoint>::Path::PATH,
+        CreateApiToken::METHOD.route().to(api_token::create),
+    )
+    .route(
+        <ListApiTokens as ApiEndpoint>::Path::PATH,
+        ListApiTokens::METHOD.route().to(api_token::list),
+    )
+    .route(
+        <RevokeApiToken as ApiEndpoint>::Path::PATH,
+        RevokeApiToken::METHOD.route().to(api_token::revoke),
+    )
     .route(
         <Search as ApiEndpoint>::Path::PATH,
         Search::METHOD.route().to(public_user::search),
@@ -1222,6 +1276,10 @@ pub fn configure(cfg: &mut ServiceConfig) {
             .route()
             .to(public_user::browse_user_followings),
     )
+    .route(
+        <Feed as ApiEndpoint>::Path::PATH,
+        Feed::METHOD.route().to(public_user::feed),
+    )
     .route(
         <Follow as ApiEndpoint>::Path::PATH,
         Follow::METHOD.route().to(public_user::follow),
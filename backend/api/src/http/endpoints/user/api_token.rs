@@ -0,0 +1,79 @@
+use actix_web::{
+    web::{Data, Json, Path},
+    HttpResponse,
+};
+use chrono::Utc;
+use shared::{
+    api::endpoints::{user::ListApiTokens, ApiEndpoint},
+    domain::user::{
+        ApiTokenCreateRequest, ApiTokenCreateResponse, ApiTokenId, ApiTokenListResponse, UserScope,
+    },
+};
+use sqlx::PgPool;
+
+use crate::{db, error, extractor::TokenUser, token};
+
+pub async fn create(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    req: Json<ApiTokenCreateRequest>,
+) -> Result<HttpResponse, error::ApiToken> {
+    // otherwise a leaked token could be used to keep access after it's revoked
+    if claims.0.api_token.is_some() {
+        return Err(error::ApiToken::CreatedWithApiToken);
+    }
+
+    let user_id = claims.user_id();
+    let req = req.into_inner();
+
+    let name = req.name.trim();
+    if name.is_empty() {
+        return Err(error::ApiToken::EmptyName);
+    }
+
+    if matches!(req.expires_at, Some(expires_at) if expires_at <= Utc::now()) {
+        return Err(error::ApiToken::AlreadyExpired);
+    }
+
+    for scope in &req.scopes {
+        if !db::user::has_scopes(&db, user_id, &[*scope, UserScope::Admin]).await? {
+            return Err(error::ApiToken::ScopeNotAllowed(*scope));
+        }
+    }
+
+    let token = token::generate_api_token();
+
+    let id = db::user::api_token::create(
+        &db,
+        user_id,
+        name,
+        &token::hash_api_token(&token),
+        &req.scopes,
+        req.read_only,
+        req.expires_at,
+    )
+    .await?;
+
+    Ok(HttpResponse::Created().json(ApiTokenCreateResponse { id, token }))
+}
+
+pub async fn list(
+    db: Data<PgPool>,
+    claims: TokenUser,
+) -> Result<Json<<ListApiTokens as ApiEndpoint>::Res>, error::ApiToken> {
+    let tokens = db::user::api_token::list(&db, claims.user_id()).await?;
+
+    Ok(Json(ApiTokenListResponse { tokens }))
+}
+
+pub async fn revoke(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    path: Path<ApiTokenId>,
+) -> Result<HttpResponse, error::ApiToken> {
+    if !db::user::api_token::revoke(&db, claims.user_id(), path.into_inner()).await? {
+        return Err(error::ApiToken::NotFound);
+    }
+
+    Ok(HttpResponse::NoContent().finish())
+}
@@ -15,7 +15,7 @@ use shared::{
                 BrowsePublicUserFollowersResponse as BrowseFollowersResponse,
                 BrowsePublicUserFollowingResponse as BrowseFollowingsResponse,
                 BrowsePublicUserResourcesResponse as BrowseResourcesResponse,
-                BrowsePublicUserResponse, SearchPublicUserResponse,
+                BrowsePublicUserResponse, SearchPublicUserResponse, UserFeedResponse,
             },
             UserId,
         },
@@ -25,7 +25,7 @@ use shared::{
 use sqlx::PgPool;
 
 use crate::{
-    db,
+    db::{self, user::public_user::FeedCursor},
     error::{self, ServiceKind},
     extractor::{get_user_id, TokenUser},
     http::endpoints::playlist::{DEFAULT_PAGE_LIMIT, MAX_PAGE_LIMIT},
@@ -366,6 +366,33 @@ pub async fn browse_user_followings(
     }))
 }
 
+/// Activity of the users the requesting user follows
+pub async fn feed(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    query: Option<Query<<user::Feed as ApiEndpoint>::Req>>,
+) -> Result<Json<<user::Feed as ApiEndpoint>::Res>, error::NotFound> {
+    let query = query.map_or_else(Default::default, Query::into_inner);
+
+    let page_limit = page_limit(query.page_limit)
+        .await
+        .map_err(|_| error::NotFound::BadRequest)?;
+
+    let cursor = query
+        .cursor
+        .as_deref()
+        .map(str::parse::<FeedCursor>)
+        .transpose()
+        .map_err(|_| error::NotFound::BadRequest)?;
+
+    let (items, next_cursor) =
+        db::user::public_user::feed(&db, claims.user_id(), cursor, page_limit).await?;
+
+    let next_cursor = next_cursor.map(|cursor| cursor.to_string());
+
+    Ok(Json(UserFeedResponse { items, next_cursor }))
+}
+
 async fn page_limit(page_limit: Option<u32>) -> anyhow::Result<u32> {
     if let Some(limit) = page_limit {
         match limit > 0 && limit <= MAX_PAGE_LIMIT {
@@ -0,0 +1,187 @@
+use actix_web::{
+    web::{Data, Json},
+    Either, HttpResponse,
+};
+use chrono::{Duration, Utc};
+use ji_core::settings::RuntimeSettings;
+use shared::{
+    api::endpoints::{
+        user::{GetTotp, RegenerateRecoveryCodes},
+        ApiEndpoint,
+    },
+    domain::user::{
+        TotpCodeRequest, TotpConfirmResponse, TotpEnrollResponse, TotpRecoveryCodesResponse,
+        TotpStatusResponse, UserId,
+    },
+};
+use sqlx::PgPool;
+
+use crate::{
+    db, error,
+    extractor::{SessionEnrollTotp, SessionGeneral, TokenSessionOf, TokenUser},
+    token::{create_auth_token, SessionClaims, SessionMask},
+    totp,
+};
+
+/// Gets the user's two-factor secret, if they have enabled two-factor authentication.
+async fn enabled_secret(db: &PgPool, user_id: UserId) -> Result<Vec<u8>, error::Totp> {
+    db::user::totp::get(db, user_id)
+        .await?
+        .filter(|totp| totp.enabled)
+        .map(|totp| totp.secret)
+        .ok_or(error::Totp::NotFound)
+}
+
+/// Two-factor authentication can be set up from a standard session, or from the session users get when signing in
+/// while they still have to set it up.
+type EnrollSession = Either<TokenSessionOf<SessionEnrollTotp>, TokenSessionOf<SessionGeneral>>;
+
+fn enroll_claims(session: EnrollSession) -> SessionClaims {
+    match session {
+        Either::Left(session) => session.claims,
+        Either::Right(session) => session.claims,
+    }
+}
+
+pub async fn get(
+    db: Data<PgPool>,
+    claims: TokenUser,
+) -> Result<Json<<GetTotp as ApiEndpoint>::Res>, error::Server> {
+    let user_id = claims.user_id();
+
+    let enabled = db::user::totp::is_enabled(&db, user_id).await?;
+    let required = db::user::totp::is_required(&db, user_id).await?;
+    let recovery_codes_left = db::user::totp::recovery_codes_left(&db, user_id).await?;
+
+    Ok(Json(TotpStatusResponse {
+        enabled,
+        required,
+        recovery_codes_left,
+    }))
+}
+
+pub async fn enroll(db: Data<PgPool>, session: EnrollSession) -> Result<HttpResponse, error::Totp> {
+    let claims = enroll_claims(session);
+    let user_id = UserId(claims.user_id);
+
+    let secret = totp::generate_secret();
+
+    if !db::user::totp::set_secret(&db, user_id, &secret).await? {
+        return Err(error::Totp::AlreadyEnabled);
+    }
+
+    let email = db::user::get_email(&mut *db.acquire().await?, user_id).await?;
+
+    Ok(HttpResponse::Created().json(TotpEnrollResponse {
+        secret: totp::base32_encode(&secret),
+        provisioning_uri: totp::provisioning_uri(&secret, &email),
+    }))
+}
+
+pub async fn confirm(
+    db: Data<PgPool>,
+    settings: Data<RuntimeSettings>,
+    session: EnrollSession,
+    req: Json<TotpCodeRequest>,
+) -> Result<HttpResponse, error::Totp> {
+    let claims = enroll_claims(session);
+    let user_id = UserId(claims.user_id);
+
+    let totp = db::user::totp::get(&db, user_id)
+        .await?
+        .ok_or(error::Totp::NotFound)?;
+
+    if totp.enabled {
+        return Err(error::Totp::AlreadyEnabled);
+    }
+
+    let mut txn = db.begin().await?;
+
+    if !db::user::totp::check_code(&mut txn, user_id, &totp.secret, &req.code, Utc::now()).await? {
+        return Err(error::Totp::InvalidCode);
+    }
+
+    db::user::totp::enable(&mut txn, user_id).await?;
+
+    let recovery_codes = totp::generate_recovery_codes();
+    db::user::totp::replace_recovery_codes(&mut txn, user_id, &recovery_codes).await?;
+
+    // the session may have only been good for setting up two-factor authentication, so replace it with a standard one.
+    let login_ttl = settings
+        .login_token_valid_duration
+        .unwrap_or(Duration::weeks(2));
+
+    db::session::delete(&mut txn, &claims.token).await?;
+
+    let session = db::session::create(
+        &mut txn,
+        user_id,
+        Some(&(Utc::now() + login_ttl)),
+        SessionMask::GENERAL,
+        None,
+    )
+    .await?;
+
+    let (csrf, cookie) = create_auth_token(
+        &settings.token_secret,
+        settings.is_local(),
+        login_ttl,
+        &session,
+    )?;
+
+    txn.commit().await?;
+
+    Ok(HttpResponse::Ok().cookie(cookie).json(TotpConfirmResponse {
+        csrf,
+        recovery_codes,
+    }))
+}
+
+pub async fn disable(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    req: Json<TotpCodeRequest>,
+) -> Result<HttpResponse, error::Totp> {
+    let user_id = claims.user_id();
+
+    if db::user::totp::is_required(&db, user_id).await? {
+        return Err(error::Totp::Required);
+    }
+
+    let secret = enabled_secret(&db, user_id).await?;
+
+    let mut txn = db.begin().await?;
+
+    if !db::user::totp::check_code(&mut txn, user_id, &secret, &req.code, Utc::now()).await? {
+        return Err(error::Totp::InvalidCode);
+    }
+
+    txn.commit().await?;
+
+    db::user::totp::disable(&db, user_id).await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+pub async fn regenerate_recovery_codes(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    req: Json<TotpCodeRequest>,
+) -> Result<Json<<RegenerateRecoveryCodes as ApiEndpoint>::Res>, error::Totp> {
+    let user_id = claims.user_id();
+
+    let secret = enabled_secret(&db, user_id).await?;
+
+    let mut txn = db.begin().await?;
+
+    if !db::user::totp::check_code(&mut txn, user_id, &secret, &req.code, Utc::now()).await? {
+        return Err(error::Totp::InvalidCode);
+    }
+
+    let recovery_codes = totp::generate_recovery_codes();
+    db::user::totp::replace_recovery_codes(&mut txn, user_id, &recovery_codes).await?;
+
+    txn.commit().await?;
+
+    Ok(Json(TotpRecoveryCodesResponse { recovery_codes }))
+}
@@ -0,0 +1,171 @@
+use actix_web::{
+    web::{Data, Json, Path, ServiceConfig},
+    HttpResponse,
+};
+use ji_core::settings::RuntimeSettings;
+use shared::{
+    api::{endpoints::webhook, ApiEndpoint, PathParts},
+    domain::{
+        user::UserScope,
+        webhook::{
+            WebhookCreateResponse, WebhookDeliveryListResponse, WebhookEvent, WebhookId,
+            WebhookListResponse,
+        },
+    },
+};
+use sqlx::PgPool;
+use url::Url;
+
+use crate::{db, error, extractor::TokenUser, outbound};
+
+/// Checks that a webhook URL is `https` (or `http` when running locally), and doesn't resolve to
+/// an internal address, see [`outbound`].
+///
+/// Deliveries check the address again, as it could change after the webhook is registered.
+async fn validate_url(settings: &RuntimeSettings, url: &str) -> Result<String, error::Webhook> {
+    let url = Url::parse(url.trim()).map_err(|_| error::Webhook::InvalidUrl)?;
+
+    if let Err(e) = outbound::resolve(&url, settings.is_local()).await {
+        log::info!("Invalid webhook URL {}: {:?}", url, e);
+        return Err(error::Webhook::InvalidUrl);
+    }
+
+    Ok(url.into())
+}
+
+fn validate_events(events: &[WebhookEvent]) -> Result<Vec<WebhookEvent>, error::Webhook> {
+    let mut deduped: Vec<WebhookEvent> = Vec::with_capacity(events.len());
+
+    for event in events {
+        if !deduped.contains(event) {
+            deduped.push(*event);
+        }
+    }
+
+    if deduped.is_empty() {
+        return Err(error::Webhook::NoEvents);
+    }
+
+    Ok(deduped)
+}
+
+/// Register a webhook.
+async fn create(
+    db: Data<PgPool>,
+    settings: Data<RuntimeSettings>,
+    claims: TokenUser,
+    req: Json<<webhook::Create as ApiEndpoint>::Req>,
+) -> Result<HttpResponse, error::Webhook> {
+    let req = req.into_inner();
+    let user_id = claims.user_id();
+
+    let events = validate_events(&req.events)?;
+    let url = validate_url(&settings, &req.url).await?;
+
+    if req.all_users && !db::user::has_scopes(&db, user_id, &[UserScope::Admin]).await? {
+        return Err(error::Webhook::Forbidden);
+    }
+
+    let secret = crate::webhook::generate_secret();
+
+    let id = db::webhook::create(&db, user_id, &url, &secret, &events, req.all_users).await?;
+
+    Ok(HttpResponse::Created().json(WebhookCreateResponse { id, secret }))
+}
+
+/// List the webhooks of the requesting user.
+async fn list(
+    db: Data<PgPool>,
+    claims: TokenUser,
+) -> Result<Json<<webhook::List as ApiEndpoint>::Res>, error::Webhook> {
+    let webhooks = db::webhook::list(&db, claims.user_id()).await?;
+
+    Ok(Json(WebhookListResponse { webhooks }))
+}
+
+/// Update a webhook.
+async fn update(
+    db: Data<PgPool>,
+    settings: Data<RuntimeSettings>,
+    claims: TokenUser,
+    path: Path<WebhookId>,
+    req: Json<<webhook::Update as ApiEndpoint>::Req>,
+) -> Result<HttpResponse, error::Webhook> {
+    let req = req.into_inner();
+
+    let events = req.events.as_deref().map(validate_events).transpose()?;
+
+    let url = match req.url.as_deref() {
+        Some(url) => Some(validate_url(&settings, url).await?),
+        None => None,
+    };
+
+    let exists = db::webhook::update(
+        &db,
+        claims.user_id(),
+        path.into_inner(),
+        url.as_deref(),
+        events.as_deref(),
+        req.enabled,
+    )
+    .await?;
+
+    if !exists {
+        return Err(error::Webhook::ResourceNotFound);
+    }
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// Delete a webhook.
+async fn delete(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    path: Path<WebhookId>,
+) -> Result<HttpResponse, error::Webhook> {
+    if !db::webhook::delete(&db, claims.user_id(), path.into_inner()).await? {
+        return Err(error::Webhook::ResourceNotFound);
+    }
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// List the most recent deliveries to a webhook.
+async fn list_deliveries(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    path: Path<WebhookId>,
+) -> Result<Json<<webhook::ListDeliveries as ApiEndpoint>::Res>, error::Webhook> {
+    let id = path.into_inner();
+
+    if !db::webhook::exists(&db, claims.user_id(), id).await? {
+        return Err(error::Webhook::ResourceNotFound);
+    }
+
+    let deliveries = db::webhook::list_deliveries(&db, id).await?;
+
+    Ok(Json(WebhookDeliveryListResponse { deliveries }))
+}
+
+pub fn configure(cfg: &mut ServiceConfig) {
+    cfg.route(
+        <webhook::Create as ApiEndpoint>::Path::PATH,
+        webhook::Create::METHOD.route().to(create),
+    )
+    .route(
+        <webhook::List as ApiEndpoint>::Path::PATH,
+        webhook::List::METHOD.route().to(list),
+    )
+    .route(
+        <webhook::Update as ApiEndpoint>::Path::PATH,
+        webhook::Update::METHOD.route().to(update),
+    )
+    .route(
+        <webhook::Delete as ApiEndpoint>::Path::PATH,
+        webhook::Delete::METHOD.route().to(delete),
+    )
+    .route(
+        <webhook::ListDeliveries as ApiEndpoint>::Path::PATH,
+        webhook::ListDeliveries::METHOD.route().to(list_deliveries),
+    );
+}
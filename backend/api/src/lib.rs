@@ -31,6 +31,7 @@
 )]
 
 pub mod algolia;
+pub mod apple_oauth;
 pub mod db;
 mod domain;
 pub mod error;
@@ -41,10 +42,16 @@ mod image_ops;
 pub mod image_search;
 pub mod jwk;
 pub mod logger;
+pub mod microsoft_oauth;
 pub(crate) mod more_futures;
+pub mod oidc;
+pub mod outbound;
+pub mod rate_limit;
 pub mod service;
 pub mod token;
+pub mod totp;
 pub mod translate;
+pub mod webhook;
 
 // // todo: make this configurable?
 // const ARGON2_DEFAULT_PARAMS: argon2::Params = argon2::Params {
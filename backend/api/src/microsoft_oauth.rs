@@ -0,0 +1,202 @@
+//! Types and decoding for authenticating users through the Microsoft identity platform
+//!
+//! Uses the `common` endpoints, so that both work or school (e.g. Microsoft 365 education tenants)
+//! and personal Microsoft accounts can sign in.
+
+use anyhow::{bail, Context};
+use http::StatusCode;
+use ji_core::settings::MicrosoftOAuth;
+use serde::Deserialize;
+
+use crate::{error, jwk};
+
+const TOKEN_URL: &str = "https://login.microsoftonline.com/common/oauth2/v2.0/token";
+
+const KEYS_URL: &str = "https://login.microsoftonline.com/common/discovery/v2.0/keys";
+
+pub const SCOPE: &str = "openid email profile";
+
+/// The tenant personal Microsoft accounts belong to.
+const CONSUMER_TENANT_ID: &str = "9188040d-6c67-4c5b-b112-36a304b66dad";
+
+#[derive(Deserialize, Debug)]
+pub struct TokenResponse {
+    pub id_token: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct TokenErrorResponse {
+    error: String,
+    #[serde(default)]
+    error_description: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct IdentityClaims {
+    /// should be `https://login.microsoftonline.com/{tenant_id}/v2.0`
+    #[serde(rename = "iss")]
+    pub issuer: String,
+
+    /// The tenant (organization) the user belongs to
+    #[serde(rename = "tid")]
+    pub tenant_id: String,
+
+    /// The id of the user in their tenant
+    #[serde(rename = "oid")]
+    pub object_id: String,
+
+    /// The user's email, which isn't necessarily verified.
+    #[serde(default)]
+    pub email: Option<String>,
+
+    /// If the domain of the user's email is verified by the tenant.
+    ///
+    /// This is an optional claim which has to be enabled in the app registration.
+    /// It's never set for personal accounts, see [`IdentityClaims::email_verified`].
+    #[serde(rename = "xms_edov", default, deserialize_with = "jwk::bool_or_string")]
+    pub email_domain_verified: bool,
+
+    /// The user's name
+    #[serde(default)]
+    pub name: Option<String>,
+
+    /// The user's given / first name
+    #[serde(default)]
+    pub given_name: Option<String>,
+
+    /// The user's family / last name
+    #[serde(default)]
+    pub family_name: Option<String>,
+}
+
+impl IdentityClaims {
+    /// Identifies the user across apps, unlike `sub`, which is specific to this app.
+    #[must_use]
+    pub fn id(&self) -> String {
+        format!("{}:{}", self.tenant_id, self.object_id)
+    }
+
+    /// If the user owns their email.
+    ///
+    /// Microsoft verifies the emails of personal accounts, but work or school tenants can set any email
+    /// unless they verified its domain.
+    #[must_use]
+    pub fn email_verified(&self) -> bool {
+        self.email.is_some() && (self.tenant_id == CONSUMER_TENANT_ID || self.email_domain_verified)
+    }
+
+    /// Tokens from the `common` endpoints are issued by the user's tenant.
+    fn check_issuer(&self) -> anyhow::Result<()> {
+        let issuer = format!("https://login.microsoftonline.com/{}/v2.0", self.tenant_id);
+
+        if self.issuer != issuer {
+            bail!("unexpected issuer: {}", self.issuer);
+        }
+
+        Ok(())
+    }
+}
+
+pub async fn convert_oauth_code(
+    config: &MicrosoftOAuth,
+    code: &str,
+    redirect_url: &str,
+) -> Result<TokenResponse, error::OAuth> {
+    let resp: reqwest::Response = reqwest::Client::new()
+        .post(TOKEN_URL)
+        .form(&[
+            ("code", code),
+            ("grant_type", "authorization_code"),
+            ("client_id", &config.client),
+            ("client_secret", &config.secret),
+            ("redirect_uri", redirect_url),
+            ("scope", SCOPE),
+        ])
+        .send()
+        .await?;
+
+    match resp.status() {
+        StatusCode::OK => Ok(resp.json::<TokenResponse>().await?),
+        _ => {
+            let err = resp.json::<TokenErrorResponse>().await.with_context(|| {
+                anyhow::anyhow!("Failed to decode {}", stringify!(TokenErrorResponse))
+            })?;
+
+            match err.error.as_str() {
+                "invalid_grant" => Err(error::OAuth::InvalidCode),
+                _ => Err(anyhow::anyhow!("{}: {:?}", err.error, err.error_description).into()),
+            }
+        }
+    }
+}
+
+pub async fn verify_id_token(
+    config: &MicrosoftOAuth,
+    id_token: &str,
+) -> anyhow::Result<IdentityClaims> {
+    let claims: IdentityClaims = jwk::verify_remote(KEYS_URL, id_token, &config.client).await?;
+
+    claims.check_issuer()?;
+
+    Ok(claims)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{IdentityClaims, CONSUMER_TENANT_ID};
+    use crate::jwk::tests::{decode, sign};
+
+    const WORK_TENANT_ID: &str = "72f988bf-86f1-41af-91ab-2d7cd011db47";
+
+    fn identity_claims(tenant_id: &str, email_domain_verified: Option<&str>) -> IdentityClaims {
+        let mut claims = json!({
+            "iss": format!("https://login.microsoftonline.com/{}/v2.0", tenant_id),
+            "tid": tenant_id,
+            "oid": "00000000-0000-0000-66f3-3332eca7ea81",
+            "email": "test@example.com",
+        });
+
+        if let Some(verified) = email_domain_verified {
+            claims["xms_edov"] = json!(verified);
+        }
+
+        decode(&sign(claims)).expect("token should be valid")
+    }
+
+    #[test]
+    fn personal_account() {
+        let claims = identity_claims(CONSUMER_TENANT_ID, None);
+
+        assert!(claims.check_issuer().is_ok());
+        assert!(claims.email_verified());
+        assert_eq!(
+            claims.id(),
+            format!(
+                "{}:00000000-0000-0000-66f3-3332eca7ea81",
+                CONSUMER_TENANT_ID
+            )
+        );
+    }
+
+    #[test]
+    fn work_account() {
+        let claims = identity_claims(WORK_TENANT_ID, Some("true"));
+
+        assert!(claims.check_issuer().is_ok());
+        assert!(claims.email_verified());
+
+        // the tenant didn't verify the domain, so anyone could have set the email.
+        assert!(!identity_claims(WORK_TENANT_ID, Some("false")).email_verified());
+        assert!(!identity_claims(WORK_TENANT_ID, None).email_verified());
+    }
+
+    #[test]
+    fn other_tenant_issuer() {
+        let mut claims = identity_claims(WORK_TENANT_ID, Some("true"));
+        claims.tenant_id = CONSUMER_TENANT_ID.to_owned();
+
+        assert!(claims.check_issuer().is_err());
+    }
+}
@@ -0,0 +1,186 @@
+//! Signing in through a school's OpenID Connect identity provider (single sign-on)
+//!
+//! Unlike the other OAuth providers, the endpoints aren't known ahead of time, so they're
+//! discovered from the issuer. As schools enter the issuer, every request goes through [`outbound`].
+
+use anyhow::{bail, Context};
+use http::StatusCode;
+use serde::Deserialize;
+
+use crate::{error, jwk, outbound};
+
+pub const SCOPE: &str = "openid email profile";
+
+/// The parts of the identity provider's configuration we use, from
+/// `{issuer}/.well-known/openid-configuration`.
+#[derive(Deserialize, Debug)]
+pub struct Discovery {
+    pub issuer: String,
+    pub authorization_endpoint: String,
+    pub token_endpoint: String,
+    pub jwks_uri: String,
+}
+
+/// The client the school registered for us with its identity provider.
+#[derive(Debug)]
+pub struct Client<'a> {
+    pub client_id: &'a str,
+    pub client_secret: &'a str,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct TokenResponse {
+    pub id_token: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct TokenErrorResponse {
+    error: String,
+    #[serde(default)]
+    error_description: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct IdentityClaims {
+    #[serde(rename = "iss")]
+    pub issuer: String,
+
+    /// The id of the user with the identity provider
+    #[serde(rename = "sub")]
+    pub subject: String,
+
+    #[serde(default)]
+    pub email: Option<String>,
+
+    /// Not every identity provider sends this, as they manage the emails themselves.
+    #[serde(default, deserialize_with = "optional_bool_or_string")]
+    pub email_verified: Option<bool>,
+
+    #[serde(default)]
+    pub name: Option<String>,
+
+    #[serde(default)]
+    pub given_name: Option<String>,
+
+    #[serde(default)]
+    pub family_name: Option<String>,
+
+    #[serde(default, rename = "picture")]
+    pub profile_picture: Option<String>,
+
+    #[serde(default)]
+    pub locale: Option<String>,
+}
+
+fn optional_bool_or_string<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<bool>, D::Error> {
+    jwk::bool_or_string(deserializer).map(Some)
+}
+
+/// Gets the identity provider's configuration.
+///
+/// `local` allows identity providers on the local network, see [`outbound`].
+pub async fn discover(issuer: &str, local: bool) -> anyhow::Result<Discovery> {
+    let url = reqwest::Url::parse(&format!(
+        "{}/.well-known/openid-configuration",
+        issuer.trim_end_matches('/')
+    ))?;
+
+    let discovery: Discovery = outbound::client(&url, local)
+        .await?
+        .get(url.clone())
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await
+        .with_context(|| anyhow::anyhow!("Failed to decode {}", url))?;
+
+    // https://openid.net/specs/openid-connect-discovery-1_0.html#ProviderConfigurationValidation
+    if discovery.issuer.trim_end_matches('/') != issuer.trim_end_matches('/') {
+        bail!("unexpected issuer: {}", discovery.issuer);
+    }
+
+    Ok(discovery)
+}
+
+pub fn authorization_url(
+    discovery: &Discovery,
+    client_id: &str,
+    redirect_url: &str,
+    state: &str,
+) -> anyhow::Result<String> {
+    let url = reqwest::Url::parse_with_params(
+        &discovery.authorization_endpoint,
+        &[
+            ("client_id", client_id),
+            ("response_type", "code"),
+            ("redirect_uri", redirect_url),
+            ("scope", SCOPE),
+            ("state", state),
+        ],
+    )?;
+
+    Ok(url.to_string())
+}
+
+pub async fn convert_oauth_code(
+    discovery: &Discovery,
+    client: &Client<'_>,
+    code: &str,
+    redirect_url: &str,
+    local: bool,
+) -> Result<TokenResponse, error::OAuth> {
+    let token_url = reqwest::Url::parse(&discovery.token_endpoint)?;
+
+    let resp: reqwest::Response = outbound::client(&token_url, local)
+        .await?
+        .post(token_url)
+        .form(&[
+            ("code", code),
+            ("grant_type", "authorization_code"),
+            ("client_id", client.client_id),
+            ("client_secret", client.client_secret),
+            ("redirect_uri", redirect_url),
+        ])
+        .send()
+        .await?;
+
+    match resp.status() {
+        StatusCode::OK => Ok(resp.json::<TokenResponse>().await?),
+        _ => {
+            let err = resp.json::<TokenErrorResponse>().await.with_context(|| {
+                anyhow::anyhow!("Failed to decode {}", stringify!(TokenErrorResponse))
+            })?;
+
+            match err.error.as_str() {
+                "invalid_grant" => Err(error::OAuth::InvalidCode),
+                _ => Err(anyhow::anyhow!("{}: {:?}", err.error, err.error_description).into()),
+            }
+        }
+    }
+}
+
+pub async fn verify_id_token(
+    discovery: &Discovery,
+    client: &Client<'_>,
+    id_token: &str,
+    local: bool,
+) -> anyhow::Result<IdentityClaims> {
+    let jwks_url = reqwest::Url::parse(&discovery.jwks_uri)?;
+
+    let claims: IdentityClaims = jwk::verify_remote_with(
+        &outbound::client(&jwks_url, local).await?,
+        &discovery.jwks_uri,
+        id_token,
+        client.client_id,
+    )
+    .await?;
+
+    if claims.issuer != discovery.issuer {
+        bail!("unexpected issuer: {}", claims.issuer);
+    }
+
+    Ok(claims)
+}
@@ -0,0 +1,182 @@
+//! Requests to URLs we're given by users, like a school's identity provider or a webhook.
+//!
+//! Such URLs could point at internal services (e.g. the metadata server), so they have to be `https`,
+//! and their host is resolved up front to make sure it's on the public internet. The client then
+//! connects to the address which was checked, so that the host can't resolve to a different one
+//! by the time the request is sent.
+//!
+//! When running locally, plain `http` and local addresses are allowed, e.g. for testing.
+
+use std::{
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    time::Duration,
+};
+
+use anyhow::{anyhow, bail};
+use url::{Host, Url};
+
+/// How long such URLs have to respond.
+pub const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Whether the address isn't on the public internet, e.g. a private network, loopback,
+/// or the link local metadata server.
+#[must_use]
+pub fn is_internal(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_internal_v4(ip),
+        IpAddr::V6(ip) => {
+            if let Some(ip) = ip.to_ipv4_mapped() {
+                return is_internal_v4(ip);
+            }
+
+            let segments = ip.segments();
+
+            ip.is_loopback()
+                || ip.is_unspecified()
+                || ip.is_multicast()
+                // unique local addresses
+                || (segments[0] & 0xfe00) == 0xfc00
+                // link local addresses
+                || (segments[0] & 0xffc0) == 0xfe80
+                // NAT64, which could translate to an internal IPv4 address
+                || segments[..6] == [0x64, 0xff9b, 0, 0, 0, 0]
+        }
+    }
+}
+
+const fn is_internal_v4(ip: Ipv4Addr) -> bool {
+    let octets = ip.octets();
+
+    ip.is_private()
+        || ip.is_loopback()
+        || ip.is_link_local()
+        || ip.is_unspecified()
+        || ip.is_broadcast()
+        || ip.is_multicast()
+        || ip.is_documentation()
+        // "this network"
+        || octets[0] == 0
+        // shared address space (carrier-grade NAT)
+        || (octets[0] == 100 && (octets[1] & 0xc0) == 64)
+}
+
+/// Checks that the URL can be requested, returning the address to connect to if its host is a domain.
+pub async fn resolve(url: &Url, local: bool) -> anyhow::Result<Option<SocketAddr>> {
+    match (url.scheme(), local) {
+        ("https", _) | ("http", true) => {}
+        (scheme, _) => bail!("unsupported scheme: {}", scheme),
+    }
+
+    let port = url
+        .port_or_known_default()
+        .ok_or_else(|| anyhow!("missing port"))?;
+
+    let ip = match url.host() {
+        None => bail!("missing host"),
+        Some(Host::Ipv4(ip)) => IpAddr::V4(ip),
+        Some(Host::Ipv6(ip)) => IpAddr::V6(ip),
+        Some(Host::Domain(domain)) => {
+            let addrs: Vec<SocketAddr> =
+                tokio::time::timeout(TIMEOUT, tokio::net::lookup_host((domain, port)))
+                    .await??
+                    .collect();
+
+            if !local {
+                if let Some(addr) = addrs.iter().find(|addr| is_internal(addr.ip())) {
+                    bail!("{} resolves to an internal address: {}", domain, addr.ip());
+                }
+            }
+
+            return match addrs.first() {
+                Some(addr) => Ok(Some(*addr)),
+                None => bail!("{} doesn't resolve to any address", domain),
+            };
+        }
+    };
+
+    if !local && is_internal(ip) {
+        bail!("internal address: {}", ip);
+    }
+
+    Ok(None)
+}
+
+/// Builds a client for requesting the URL, which connects to the address its host was checked with.
+///
+/// Redirects aren't followed, as they could lead anywhere.
+pub async fn client(url: &Url, local: bool) -> anyhow::Result<reqwest::Client> {
+    let addr = resolve(url, local).await?;
+
+    let builder = reqwest::Client::builder()
+        .timeout(TIMEOUT)
+        .redirect(reqwest::redirect::Policy::none());
+
+    let builder = match (url.host_str(), addr) {
+        (Some(domain), Some(addr)) => builder.resolve(domain, addr),
+        _ => builder,
+    };
+
+    Ok(builder.build()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::IpAddr;
+
+    use url::Url;
+
+    use super::{is_internal, resolve};
+
+    fn ip(ip: &str) -> IpAddr {
+        ip.parse().expect("invalid ip")
+    }
+
+    #[test]
+    fn internal_addresses() {
+        for internal in [
+            "10.0.0.1",
+            "172.16.0.1",
+            "192.168.1.1",
+            "127.0.0.1",
+            "169.254.169.254",
+            "0.0.0.0",
+            "100.64.0.1",
+            "::1",
+            "::",
+            "fd00::1",
+            "fe80::1",
+            "::ffff:127.0.0.1",
+            "::ffff:169.254.169.254",
+            "64:ff9b::a00:1",
+        ] {
+            assert!(is_internal(ip(internal)), "{} should be internal", internal);
+        }
+
+        for public in [
+            "8.8.8.8",
+            "1.1.1.1",
+            "2606:4700:4700::1111",
+            "::ffff:8.8.8.8",
+        ] {
+            assert!(!is_internal(ip(public)), "{} should be public", public);
+        }
+    }
+
+    #[tokio::test]
+    async fn resolve_urls() {
+        let url = |url: &str| Url::parse(url).expect("invalid url");
+
+        assert!(resolve(&url("http://8.8.8.8"), false).await.is_err());
+        assert!(resolve(&url("https://169.254.169.254/latest"), false)
+            .await
+            .is_err());
+        assert!(resolve(&url("https://[::ffff:10.0.0.1]"), false)
+            .await
+            .is_err());
+        assert!(resolve(&url("https://localhost"), false).await.is_err());
+        assert!(resolve(&url("https://8.8.8.8"), false).await.is_ok());
+
+        // anything goes when running locally
+        assert!(resolve(&url("http://127.0.0.1:8080"), true).await.is_ok());
+    }
+}
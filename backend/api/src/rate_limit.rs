@@ -0,0 +1,586 @@
+//! Limits how often clients can call endpoints which are attractive to abuse, like signing in.
+//!
+//! Requests are counted in token buckets per IP address and, where the request carries
+//! credentials, per user (e.g. the email a client tries to sign in as). The buckets are kept in
+//! memory by default, or in Postgres so that they're shared between instances.
+
+use std::{
+    collections::HashMap,
+    future::{ready, Ready},
+    rc::Rc,
+    sync::Mutex,
+};
+
+use actix_web::{
+    dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
+    http::header::{self, Header},
+    web::Data,
+};
+use actix_web_httpauth::headers::authorization::{Authorization, Basic, Bearer};
+use chrono::{DateTime, Duration, Utc};
+use futures::future::LocalBoxFuture;
+use ji_core::env::{env_bool, keys};
+use sha2::Digest;
+use shared::domain::session::AUTH_COOKIE_NAME;
+use sqlx::PgPool;
+
+use crate::{db, error};
+
+/// How often the memory store removes the buckets which are full again.
+const MEMORY_EVICT_INTERVAL_SECONDS: i64 = 60;
+
+/// `requests` requests per `seconds` seconds, allowing all of them at once.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Quota {
+    pub requests: u32,
+    pub seconds: u32,
+}
+
+impl Quota {
+    #[must_use]
+    pub const fn new(requests: u32, seconds: u32) -> Self {
+        Self { requests, seconds }
+    }
+
+    /// Parses `{requests}/{seconds}`.
+    fn parse(s: &str) -> Option<Self> {
+        let (requests, seconds) = s.trim().split_once('/')?;
+
+        let quota = Self::new(requests.trim().parse().ok()?, seconds.trim().parse().ok()?);
+
+        (quota.requests > 0 && quota.seconds > 0).then(|| quota)
+    }
+
+    fn refill_per_second(&self) -> f64 {
+        f64::from(self.requests) / f64::from(self.seconds)
+    }
+
+    /// Takes a token from the bucket, returning the bucket's new state, and how long to wait
+    /// before retrying if it's empty.
+    fn take(
+        &self,
+        bucket: Option<(f64, DateTime<Utc>)>,
+        now: DateTime<Utc>,
+    ) -> (f64, DateTime<Utc>, Result<(), std::time::Duration>) {
+        let capacity = f64::from(self.requests);
+
+        let tokens = match bucket {
+            Some((tokens, updated_at)) => {
+                let elapsed = (now - updated_at).num_milliseconds().max(0) as f64 / 1000.0;
+
+                (tokens + elapsed * self.refill_per_second()).min(capacity)
+            }
+            None => capacity,
+        };
+
+        if tokens >= 1.0 {
+            (tokens - 1.0, now, Ok(()))
+        } else {
+            let wait = (1.0 - tokens) / self.refill_per_second();
+
+            (tokens, now, Err(std::time::Duration::from_secs_f64(wait)))
+        }
+    }
+
+    /// When a bucket with `tokens` left at `updated_at` is full again.
+    fn full_at(&self, tokens: f64, updated_at: DateTime<Utc>) -> DateTime<Utc> {
+        let missing = (f64::from(self.requests) - tokens).max(0.0);
+
+        updated_at
+            + Duration::milliseconds((missing / self.refill_per_second() * 1000.0).ceil() as i64)
+    }
+}
+
+/// What's limited, e.g. signing in.
+#[derive(Debug)]
+pub struct Rule {
+    /// Used to tell the buckets apart, and for overriding the quotas with
+    /// `RATE_LIMIT_{NAME}_IP` and `RATE_LIMIT_{NAME}_USER`.
+    pub name: &'static str,
+
+    /// Requests from the same IP address.
+    pub per_ip: Quota,
+
+    /// Requests with the same credentials.
+    pub per_user: Option<Quota>,
+}
+
+/// Signing in with a password, limited per user to slow down guessing a user's password.
+pub const LOGIN: Rule = Rule {
+    name: "login",
+    per_ip: Quota::new(30, 5 * 60),
+    per_user: Some(Quota::new(10, 5 * 60)),
+};
+
+/// Sending password reset emails.
+pub const PASSWORD_RESET: Rule = Rule {
+    name: "password_reset",
+    per_ip: Quota::new(5, 15 * 60),
+    per_user: None,
+};
+
+/// Signing up and (re)sending the verification email.
+pub const SIGNUP_VERIFICATION: Rule = Rule {
+    name: "signup_verification",
+    per_ip: Quota::new(10, 15 * 60),
+    per_user: Some(Quota::new(5, 15 * 60)),
+};
+
+/// Reporting jigs and resources.
+pub const REPORT: Rule = Rule {
+    name: "report",
+    per_ip: Quota::new(20, 60 * 60),
+    per_user: Some(Quota::new(10, 60 * 60)),
+};
+
+/// Inviting users to circles, which notifies them.
+pub const CIRCLE_INVITE: Rule = Rule {
+    name: "circle_invite",
+    per_ip: Quota::new(60, 60 * 60),
+    per_user: Some(Quota::new(30, 60 * 60)),
+};
+
+/// Starting to play a jig of a player session. A whole class often plays from behind the same
+/// IP address, so this only stops scripts.
+pub const PLAYER_SESSION_INSTANCE: Rule = Rule {
+    name: "player_session_instance",
+    per_ip: Quota::new(300, 60),
+    per_user: None,
+};
+
+#[derive(Debug)]
+struct MemoryBucket {
+    tokens: f64,
+    updated_at: DateTime<Utc>,
+    full_at: DateTime<Utc>,
+}
+
+/// Buckets kept in memory. A full bucket is the same as a missing one, so they're removed
+/// every [`MEMORY_EVICT_INTERVAL_SECONDS`].
+#[derive(Debug, Default)]
+struct MemoryBuckets {
+    buckets: HashMap<String, MemoryBucket>,
+    evicted_at: Option<DateTime<Utc>>,
+}
+
+impl MemoryBuckets {
+    fn take(
+        &mut self,
+        key: &str,
+        quota: Quota,
+        now: DateTime<Utc>,
+    ) -> Result<(), std::time::Duration> {
+        let evict = self.evicted_at.map_or(true, |evicted_at| {
+            now - evicted_at >= Duration::seconds(MEMORY_EVICT_INTERVAL_SECONDS)
+        });
+
+        if evict {
+            self.buckets.retain(|_, bucket| bucket.full_at > now);
+            self.evicted_at = Some(now);
+        }
+
+        let bucket = self
+            .buckets
+            .get(key)
+            .map(|bucket| (bucket.tokens, bucket.updated_at));
+
+        let (tokens, updated_at, res) = quota.take(bucket, now);
+
+        self.buckets.insert(
+            key.to_owned(),
+            MemoryBucket {
+                tokens,
+                updated_at,
+                full_at: quota.full_at(tokens, updated_at),
+            },
+        );
+
+        res
+    }
+}
+
+#[derive(Debug)]
+enum Store {
+    Memory(Mutex<MemoryBuckets>),
+    Postgres(PgPool),
+}
+
+/// Counts requests, shared by every route wrapped in [`RateLimit`].
+#[derive(Debug)]
+pub struct RateLimiter {
+    store: Store,
+    overrides: HashMap<String, Quota>,
+    /// How many proxies in front append to `X-Forwarded-For`.
+    trusted_proxy_hops: usize,
+}
+
+impl RateLimiter {
+    #[must_use]
+    pub fn memory() -> Self {
+        Self {
+            store: Store::Memory(Mutex::new(MemoryBuckets::default())),
+            overrides: HashMap::new(),
+            trusted_proxy_hops: 0,
+        }
+    }
+
+    #[must_use]
+    pub fn postgres(db: PgPool) -> Self {
+        Self {
+            store: Store::Postgres(db),
+            overrides: HashMap::new(),
+            trusted_proxy_hops: 0,
+        }
+    }
+
+    /// Creates the limiter configured by the environment, or `None` if rate limiting is disabled.
+    #[must_use]
+    pub fn from_env(db: PgPool) -> Option<Self> {
+        if env_bool(keys::rate_limit::DISABLE) {
+            log::info!("Rate limiting is disabled");
+            return None;
+        }
+
+        let limiter = match std::env::var(keys::rate_limit::STORE).as_deref() {
+            Ok("postgres") => Self::postgres(db),
+            Ok("memory") | Err(_) => Self::memory(),
+            Ok(other) => {
+                log::warn!("Unknown rate limit store `{}`, using memory", other);
+                Self::memory()
+            }
+        };
+
+        let overrides = std::env::vars()
+            .filter_map(|(key, value)| {
+                let name = key.strip_prefix(keys::rate_limit::QUOTA_PREFIX)?;
+
+                let quota = Quota::parse(&value);
+
+                if quota.is_none() && (name.ends_with("_IP") || name.ends_with("_USER")) {
+                    log::warn!("Invalid rate limit quota {}={}", key, value);
+                }
+
+                Some((name.to_lowercase(), quota?))
+            })
+            .collect();
+
+        let trusted_proxy_hops = match std::env::var(keys::rate_limit::TRUSTED_PROXY_HOPS) {
+            Ok(hops) => hops.trim().parse().unwrap_or_else(|_| {
+                log::warn!("Invalid trusted proxy hops `{}`, using 0", hops);
+                0
+            }),
+            Err(_) => 0,
+        };
+
+        Some(Self {
+            overrides,
+            trusted_proxy_hops,
+            ..limiter
+        })
+    }
+
+    fn quota(&self, rule: &Rule, kind: KeyKind) -> Option<Quota> {
+        let default = match kind {
+            KeyKind::Ip => Some(rule.per_ip),
+            KeyKind::User => rule.per_user,
+        };
+
+        let name = format!("{}_{}", rule.name, kind.as_str());
+
+        default.map(|default| self.overrides.get(&name).copied().unwrap_or(default))
+    }
+
+    /// Counts the request for each key, failing if any of them is out of requests.
+    pub async fn check(
+        &self,
+        rule: &Rule,
+        keys: &[(KeyKind, String)],
+    ) -> Result<(), error::TooManyRequests> {
+        let mut retry_after = None;
+
+        for (kind, value) in keys {
+            let quota = match self.quota(rule, *kind) {
+                Some(quota) => quota,
+                None => continue,
+            };
+
+            // don't keep IP addresses or emails around.
+            let key = base64::encode(sha2::Sha256::digest(
+                format!("{}:{}:{}", rule.name, kind.as_str(), value).as_bytes(),
+            ));
+
+            let res = match self.take(&key, quota).await {
+                Ok(res) => res,
+                Err(e) => {
+                    // rather let requests through than fail them while the database is unavailable
+                    log::warn!("Failed to count request for rate limiting: {:?}", e);
+                    Ok(())
+                }
+            };
+
+            if let Err(wait) = res {
+                retry_after = retry_after.max(Some(wait));
+            }
+        }
+
+        match retry_after {
+            Some(retry_after) => Err(error::TooManyRequests { retry_after }),
+            None => Ok(()),
+        }
+    }
+
+    async fn take(
+        &self,
+        key: &str,
+        quota: Quota,
+    ) -> anyhow::Result<Result<(), std::time::Duration>> {
+        let now = Utc::now();
+
+        match &self.store {
+            Store::Memory(buckets) => Ok(buckets.lock().unwrap().take(key, quota, now)),
+
+            Store::Postgres(db) => {
+                let taken = db::rate_limit::take_token(
+                    db,
+                    key,
+                    f64::from(quota.requests),
+                    quota.refill_per_second(),
+                    now,
+                )
+                .await?;
+
+                if taken.is_some() {
+                    return Ok(Ok(()));
+                }
+
+                // the bucket is empty, work out how long until there's a token again
+                let bucket = db::rate_limit::get_bucket(db, key).await?;
+
+                Ok(quota.take(bucket, now).2)
+            }
+        }
+    }
+}
+
+/// Who requests are counted for.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum KeyKind {
+    Ip,
+    User,
+}
+
+impl KeyKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Ip => "ip",
+            Self::User => "user",
+        }
+    }
+}
+
+/// The client's IP address.
+///
+/// Clients can send any `X-Forwarded-For` they like and every proxy appends the address it received
+/// the request from, so the client's address is the one `trusted_proxy_hops` entries from the end.
+/// Anything before it could be made up.
+fn client_ip(req: &ServiceRequest, trusted_proxy_hops: usize) -> Option<String> {
+    if trusted_proxy_hops > 0 {
+        let forwarded: Vec<&str> = req
+            .headers()
+            .get_all(header::X_FORWARDED_FOR)
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .map(str::trim)
+            .filter(|addr| !addr.is_empty())
+            .collect();
+
+        let addr = forwarded
+            .len()
+            .checked_sub(trusted_proxy_hops)
+            .and_then(|idx| forwarded.get(idx));
+
+        if let Some(addr) = addr {
+            return Some((*addr).to_owned());
+        }
+    }
+
+    req.peer_addr().map(|addr| addr.ip().to_string())
+}
+
+/// The IP address, and the credentials (if any) of the request.
+fn keys(req: &ServiceRequest, trusted_proxy_hops: usize) -> Vec<(KeyKind, String)> {
+    let mut keys = Vec::with_capacity(2);
+
+    if let Some(ip) = client_ip(req, trusted_proxy_hops) {
+        keys.push((KeyKind::Ip, ip));
+    }
+
+    // signing in with a password counts for the email, otherwise for the session.
+    let user = Authorization::<Basic>::parse(req)
+        .map(|it| it.into_scheme().user_id().to_lowercase())
+        .or_else(|_| {
+            Authorization::<Bearer>::parse(req).map(|it| it.into_scheme().token().to_owned())
+        })
+        .ok()
+        .or_else(|| {
+            req.request()
+                .cookie(AUTH_COOKIE_NAME)
+                .map(|it| it.value().to_owned())
+        });
+
+    if let Some(user) = user {
+        keys.push((KeyKind::User, user));
+    }
+
+    keys
+}
+
+/// Middleware that limits how often a route can be called, according to the [`Rule`].
+///
+/// Routes aren't limited if there's no [`RateLimiter`] in the app data (rate limiting is disabled).
+///
+/// ```ignore
+/// Create::METHOD
+///     .route()
+///     .to(create_session)
+///     .wrap(RateLimit::new(&rate_limit::LOGIN))
+/// ```
+pub struct RateLimit {
+    rule: &'static Rule,
+}
+
+impl RateLimit {
+    #[must_use]
+    pub fn new(rule: &'static Rule) -> Self {
+        Self { rule }
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for RateLimit
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = actix_web::Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = actix_web::Error;
+    type Transform = RateLimitMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(RateLimitMiddleware {
+            service: Rc::new(service),
+            rule: self.rule,
+        }))
+    }
+}
+
+pub struct RateLimitMiddleware<S> {
+    service: Rc<S>,
+    rule: &'static Rule,
+}
+
+impl<S, B> Service<ServiceRequest> for RateLimitMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = actix_web::Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = actix_web::Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = self.service.clone();
+        let rule = self.rule;
+
+        Box::pin(async move {
+            if let Some(limiter) = req.app_data::<Data<RateLimiter>>().cloned() {
+                let keys = keys(&req, limiter.trusted_proxy_hops);
+
+                limiter
+                    .check(rule, &keys)
+                    .await
+                    .map_err(Into::<actix_web::Error>::into)?;
+            }
+
+            service.call(req).await
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{client_ip, MemoryBuckets, Quota};
+    use actix_web::{http::header, test::TestRequest};
+    use chrono::{Duration, TimeZone, Utc};
+
+    #[test]
+    fn parse_quota() {
+        assert_eq!(Quota::parse("10/60"), Some(Quota::new(10, 60)));
+        assert_eq!(Quota::parse(" 5 / 300 "), Some(Quota::new(5, 300)));
+        assert_eq!(Quota::parse("0/60"), None);
+        assert_eq!(Quota::parse("10"), None);
+    }
+
+    #[test]
+    fn take_until_empty_then_refill() {
+        let quota = Quota::new(2, 60);
+        let now = Utc.timestamp(1_600_000_000, 0);
+
+        let (tokens, updated_at, res) = quota.take(None, now);
+        assert!(res.is_ok());
+
+        let (tokens, updated_at, res) = quota.take(Some((tokens, updated_at)), now);
+        assert!(res.is_ok());
+
+        let (tokens, updated_at, res) = quota.take(Some((tokens, updated_at)), now);
+        assert_eq!(res.unwrap_err().as_secs_f64().round(), 30.0);
+
+        // one token refills every 30 seconds
+        let later = now + Duration::seconds(30);
+        let (_, _, res) = quota.take(Some((tokens, updated_at)), later);
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn evict_full_memory_buckets() {
+        let quota = Quota::new(2, 60);
+        let now = Utc.timestamp(1_600_000_000, 0);
+
+        let mut buckets = MemoryBuckets::default();
+
+        assert!(buckets.take("a", quota, now).is_ok());
+        assert!(buckets.take("a", quota, now).is_ok());
+        assert!(buckets.take("b", quota, now).is_ok());
+
+        // "b" is full again after 30 seconds, but eviction only runs every minute
+        let later = now + Duration::seconds(45);
+        assert!(buckets.take("c", quota, later).is_ok());
+        assert_eq!(buckets.buckets.len(), 3);
+
+        // "a" needs a minute to refill both tokens
+        let later = now + Duration::seconds(60);
+        assert!(buckets.take("c", quota, later).is_ok());
+        assert!(!buckets.buckets.contains_key("a"));
+        assert!(!buckets.buckets.contains_key("b"));
+        assert!(buckets.buckets.contains_key("c"));
+    }
+
+    #[test]
+    fn client_ip_behind_proxies() {
+        let req = TestRequest::default()
+            .insert_header((header::X_FORWARDED_FOR, "1.1.1.1, 2.2.2.2"))
+            .insert_header((header::X_FORWARDED_FOR, "3.3.3.3"))
+            .peer_addr("10.0.0.1:1234".parse().unwrap())
+            .to_srv_request();
+
+        assert_eq!(client_ip(&req, 0).as_deref(), Some("10.0.0.1"));
+        assert_eq!(client_ip(&req, 1).as_deref(), Some("3.3.3.3"));
+        assert_eq!(client_ip(&req, 2).as_deref(), Some("2.2.2.2"));
+
+        // fewer addresses than proxies, the request didn't come through all of them
+        assert_eq!(client_ip(&req, 4).as_deref(), Some("10.0.0.1"));
+    }
+}
@@ -1,7 +1,8 @@
 use ji_core::settings::EmailClientSettings;
 use sendgrid::v3::{Content, Email, Message, Personalization, SGMap, Sender};
 use shared::domain::{
-    jig::report::JigReportEmail, resource::report::ResourceReportEmail, session::OAuthProvider,
+    asset::ReportAction, billing::BillingNotificationKind, jig::report::JigReportEmail,
+    resource::report::ResourceReportEmail, session::OAuthProvider,
 };
 use tracing::instrument;
 
@@ -232,6 +233,121 @@ Please try logging in with your {} account.
         Ok(())
     }
 
+    /// Let a reporter know the outcome of their report.
+    #[instrument(skip_all)]
+    pub async fn send_report_resolved_email(
+        &self,
+        to: Email,
+        display_name: &str,
+        action: ReportAction,
+    ) -> anyhow::Result<()> {
+        let subject = format!("Your report on '{}'", display_name);
+
+        let value = format!(
+            r#"Thank you for helping keep Jigzi safe.
+
+The content you reported, "{}", has been {}.
+"#,
+            display_name,
+            action.as_str(),
+        );
+
+        let message = Message::new(self.sender_email.clone())
+            .add_personalization(Personalization::new(to))
+            .set_subject(&subject)
+            .add_content(
+                Content::new()
+                    .set_content_type("text/plain")
+                    .set_value(value),
+            );
+
+        self.client.send(&message).await?;
+
+        Ok(())
+    }
+
+    /// Contact the author of reported content on behalf of a curator.
+    #[instrument(skip_all)]
+    pub async fn send_report_author_email(
+        &self,
+        to: Email,
+        display_name: &str,
+        first_name: &str,
+        note: &str,
+    ) -> anyhow::Result<()> {
+        let subject = format!("About your content '{}'", display_name);
+
+        let value = format!(
+            r#"Hi {},
+
+Your content "{}" was reported by a Jigzi user and reviewed by our team:
+
+{}
+"#,
+            first_name, display_name, note,
+        );
+
+        let message = Message::new(self.sender_email.clone())
+            .add_personalization(Personalization::new(to))
+            .set_subject(&subject)
+            .add_content(
+                Content::new()
+                    .set_content_type("text/plain")
+                    .set_value(value),
+            );
+
+        self.client.send(&message).await?;
+
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    pub async fn send_billing_notification(
+        &self,
+        to: Email,
+        kind: BillingNotificationKind,
+        first_name: String,
+        settings_link: String,
+    ) -> anyhow::Result<()> {
+        let (subject, body) = match kind {
+            BillingNotificationKind::TrialEnding => (
+                "Your Jigzi trial is ending soon",
+                "Your free trial is ending soon. Make sure you have a payment method on your account so that you don't lose access to your Pro features.",
+            ),
+            BillingNotificationKind::PaymentFailed => (
+                "Your Jigzi payment failed",
+                "We couldn't process the latest payment for your subscription. Please update your payment method so that your subscription stays active.",
+            ),
+            BillingNotificationKind::SubscriptionExpired => (
+                "Your Jigzi subscription has expired",
+                "Your subscription has expired. You can renew it at any time to regain access to your Pro features.",
+            ),
+        };
+
+        let value = format!(
+            r#"Hi {},
+
+{}
+
+Manage your subscription, or stop receiving these emails, from your account settings: {}
+"#,
+            first_name, body, settings_link,
+        );
+
+        let message = Message::new(self.sender_email.clone())
+            .add_personalization(Personalization::new(to))
+            .set_subject(subject)
+            .add_content(
+                Content::new()
+                    .set_content_type("text/plain")
+                    .set_value(value),
+            );
+
+        self.client.send(&message).await?;
+
+        Ok(())
+    }
+
     pub fn signup_verify_template(&self) -> Result<SignupVerifyTemplate<'_>, error::ServiceKind> {
         // todo: make the error more specific?
         self.signup_verify_template
@@ -1,21 +1,57 @@
-use actix_web::cookie::{Cookie, CookieBuilder, SameSite};
+use std::net::{IpAddr, Ipv6Addr, SocketAddr};
+
+use actix_web::{
+    cookie::{Cookie, CookieBuilder, SameSite},
+    http::{header, Method},
+    HttpRequest,
+};
 use chrono::{DateTime, Duration, Utc};
 use http::StatusCode;
 use paseto::{PasetoBuilder, TimeBackend};
 use rand::Rng;
 use serde_json::json;
-use shared::domain::{session::AUTH_COOKIE_NAME, user::UserScope};
+use sha2::Digest;
+use shared::domain::{
+    session::AUTH_COOKIE_NAME,
+    user::{ApiTokenId, UserScope, API_TOKEN_PREFIX},
+};
 use sqlx::PgPool;
 use tracing::instrument;
 use uuid::Uuid;
 
-use crate::error::{self, BasicError};
+use crate::{
+    db,
+    error::{self, BasicError},
+};
 
 const AUTHORIZED_FOOTER: &str = "authorized";
 
 pub struct SessionClaims {
     pub user_id: Uuid,
+    /// The session's token, or the id of the personal API token.
     pub token: String,
+    /// Set if the request was authorized with a personal API token instead of a session.
+    pub api_token: Option<ApiTokenClaims>,
+}
+
+impl SessionClaims {
+    /// The scopes the user's own scopes are limited to, if authorized with a personal API token.
+    ///
+    /// `None` if they aren't limited, e.g. for sessions or tokens created with [`UserScope::Admin`].
+    #[must_use]
+    pub fn token_scopes(&self) -> Option<&[UserScope]> {
+        self.api_token
+            .as_ref()
+            .filter(|token| !token.scopes.contains(&UserScope::Admin))
+            .map(|token| &token.scopes[..])
+    }
+}
+
+/// The personal API token a request was authorized with.
+pub struct ApiTokenClaims {
+    pub id: ApiTokenId,
+    /// The scopes the token was created with.
+    pub scopes: Vec<UserScope>,
 }
 
 /// The claims that are used as part of the user's token.
@@ -28,6 +64,51 @@ struct AuthorizedTokenClaims {
     csrf: String,
 }
 
+/// The client that makes a request authenticated by a session.
+pub struct SessionClient {
+    pub user_agent: Option<String>,
+    pub ip_hint: Option<String>,
+}
+
+impl SessionClient {
+    pub fn from_request(req: &HttpRequest) -> Self {
+        let user_agent = req
+            .headers()
+            .get(header::USER_AGENT)
+            .and_then(|it| it.to_str().ok())
+            .map(ToOwned::to_owned);
+
+        let ip_hint = req.connection_info().realip_remote_addr().and_then(ip_hint);
+
+        Self {
+            user_agent,
+            ip_hint,
+        }
+    }
+}
+
+/// Hides which host an address belongs to, only keeping its network (a `/24` for IPv4, a `/48` for IPv6).
+fn ip_hint(addr: &str) -> Option<String> {
+    let ip = addr
+        .parse::<SocketAddr>()
+        .map(|addr| addr.ip())
+        .or_else(|_| addr.parse::<IpAddr>())
+        .ok()?;
+
+    let hint = match ip {
+        IpAddr::V4(ip) => {
+            let [a, b, c, _] = ip.octets();
+            format!("{}.{}.{}.0/24", a, b, c)
+        }
+        IpAddr::V6(ip) => {
+            let [a, b, c, ..] = ip.segments();
+            format!("{}/48", Ipv6Addr::new(a, b, c, 0, 0, 0, 0, 0))
+        }
+    };
+
+    Some(hint)
+}
+
 bitflags::bitflags! {
     #[derive(sqlx::Type)]
     pub struct SessionMask: i16 {
@@ -39,6 +120,8 @@ bitflags::bitflags! {
         const CHANGE_EMAIL = 0b0000_0000_0010_0000;
         const REPORT_EMAIL = 0b0000_0000_0100_0000;
         const WELCOME_EMAIL = 0b0000_0000_1000_0000;
+        const VERIFY_TOTP = 0b0000_0001_0000_0000;
+        const ENROLL_TOTP = 0b0000_0010_0000_0000;
 
         const GENERAL = Self::GENERAL_API.bits | Self::DELETE_ACCOUNT.bits;
         const ONE_TIME = Self::CHANGE_PASSWORD.bits | Self::VERIFY_EMAIL.bits;
@@ -88,6 +171,7 @@ pub async fn check_login_token(
     csrf: Option<&str>,
     token_key: &[u8; 32],
     min_mask: SessionMask,
+    client: &SessionClient,
 ) -> Result<SessionClaims, actix_web::Error> {
     let token = validate_token(token_string, Some(AUTHORIZED_FOOTER), token_key)?;
 
@@ -129,11 +213,70 @@ where
             .await
             .map_err(Into::into)
             .map_err(error::ise)?;
+    } else {
+        db::session::touch(
+            db,
+            &claims.sub,
+            client.user_agent.as_deref(),
+            client.ip_hint.as_deref(),
+        )
+        .await
+        .map_err(Into::into)
+        .map_err(error::ise)?;
     }
 
     Ok(SessionClaims {
         user_id: session_info.user_id,
         token: claims.sub,
+        api_token: None,
+    })
+}
+
+/// Checks a personal API token.
+///
+/// A token can only be used where `required_scope` is needed if it was created with it (or [`UserScope::Admin`]),
+/// and read-only tokens can only be used for requests which don't change anything.
+///
+/// Note: this doesn't check that the user still has `required_scope`.
+pub async fn check_api_token(
+    db: &PgPool,
+    token_string: &str,
+    required_scope: Option<UserScope>,
+    method: &Method,
+) -> Result<SessionClaims, actix_web::Error> {
+    let token = db::user::api_token::authenticate(db, token_string)
+        .await
+        .map_err(Into::into)
+        .map_err(error::ise)?
+        .ok_or_else(|| {
+            BasicError::with_message(StatusCode::UNAUTHORIZED, "Invalid API token".to_owned())
+        })?;
+
+    if token.read_only && !method.is_safe() {
+        return Err(BasicError::with_message(
+            StatusCode::FORBIDDEN,
+            "API token is read-only".to_owned(),
+        )
+        .into());
+    }
+
+    if let Some(scope) = required_scope {
+        if !token.scopes.contains(&scope) && !token.scopes.contains(&UserScope::Admin) {
+            return Err(BasicError::with_message(
+                StatusCode::FORBIDDEN,
+                format!("API token doesn't have the {:?} scope", scope),
+            )
+            .into());
+        }
+    }
+
+    Ok(SessionClaims {
+        user_id: token.user_id.0,
+        token: token.id.to_string(),
+        api_token: Some(ApiTokenClaims {
+            id: token.id,
+            scopes: token.scopes,
+        }),
     })
 }
 
@@ -185,6 +328,24 @@ fn create_cookie(token: String, local_insecure: bool, ttl: time::Duration) -> Co
         .finish()
 }
 
+/// Generates the secret for a new personal API token.
+#[must_use]
+pub fn generate_api_token() -> String {
+    let mut bytes = [0_u8; 32];
+    rand::thread_rng().fill(&mut bytes[..]);
+    format!(
+        "{}{}",
+        API_TOKEN_PREFIX,
+        base64::encode_config(&bytes, base64::URL_SAFE_NO_PAD)
+    )
+}
+
+/// API tokens are random enough that they don't need a slow, salted hash.
+#[must_use]
+pub fn hash_api_token(token: &str) -> Vec<u8> {
+    sha2::Sha256::digest(token.as_bytes()).to_vec()
+}
+
 #[must_use]
 pub fn generate_csrf() -> String {
     let mut bytes = [0_u8; 32];
@@ -7,9 +7,11 @@ use serde_json::json;
 use sqlx::{PgConnection, PgPool};
 use std::collections::HashMap;
 
-use shared::domain::image::ImageId;
+use shared::domain::{admin::GlossaryTerm, image::ImageId};
 use uuid::Uuid;
 
+use crate::db;
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct TranslateTextRequest {
@@ -71,7 +73,7 @@ struct Translation {
     translated_text: String,
 }
 
-const LANGUAGES: &'static [&str] = &[
+pub(crate) const LANGUAGES: &'static [&str] = &[
     "en", "iw", "es", "pt", "ru", "fr", "nl", "sv", "ar", "de", "hu", "it", "yi",
 ];
 
@@ -225,9 +227,11 @@ limit 10 for no key update skip locked;
             return Ok(true);
         }
 
+        let glossary = db::translation::get_glossary(&self.db).await?;
+
         for t in descriptions {
             let descriptions: anyhow::Result<Option<HashMap<String, String>>> =
-                multi_translation(&t.text, &self.api_key).await;
+                multi_translation(&self.db, &glossary, &t.text, &self.api_key).await;
 
             match descriptions {
                 Ok(descriptions) => {
@@ -287,7 +291,7 @@ limit 10 for no key update skip locked;
 
         for t in names {
             let names: anyhow::Result<Option<HashMap<String, String>>> =
-                multi_translation(&t.text, &self.api_key).await;
+                multi_translation(&self.db, &glossary, &t.text, &self.api_key).await;
 
             match names {
                 Ok(names) => {
@@ -363,12 +367,13 @@ select jig_data.id ,
        description
 from jig_data
 inner join jig on live_id = jig_data.id
-where description <> '' and translated_description = '{}'
+where description <> '' and (translated_description = '{}' or not translated_description ?& $1)
 and published_at is not null
 and description_translate_status is null
 order by coalesce(updated_at, created_at) desc
 limit 30 for no key update skip locked;
- "#
+ "#,
+            LANGUAGES
         )
         .fetch(&mut txn)
         .map_ok(|row| JigTranslate {
@@ -404,16 +409,24 @@ limit 30 for no key update skip locked;
             return Ok(true);
         }
 
+        let glossary = db::translation::get_glossary(&self.db).await?;
+
         for t in descriptions {
             let res: Option<Option<HashMap<String, String>>> =
-                multi_translation(&t.text, &self.api_key).await.ok();
+                multi_translation(&self.db, &glossary, &t.text, &self.api_key)
+                    .await
+                    .ok();
 
             if let Some(res) = res {
                 if let Some(res) = res {
                     sqlx::query!(
                         r#"
                             update jig_data
-                            set translated_description = $2,
+                            set translated_description = $2 || coalesce((
+                                    select translated_description_override
+                                    from jig
+                                    where live_id = $1
+                                ), '{}'),
                                 last_synced_at = null
                             where id = $1
                             "#,
@@ -463,7 +476,9 @@ limit 30 for no key update skip locked;
 
         for t in display_names {
             let res: Option<Option<HashMap<String, String>>> =
-                multi_translation(&t.text, &self.api_key).await.ok();
+                multi_translation(&self.db, &glossary, &t.text, &self.api_key)
+                    .await
+                    .ok();
 
             if let Some(res) = res {
                 if let Some(res) = res {
@@ -537,12 +552,13 @@ select resource_data.id,
        description
 from resource_data
 inner join resource on live_id = resource_data.id
-where description <> '' and translated_description = '{}'
+where description <> '' and (translated_description = '{}' or not translated_description ?& $1)
 and published_at is not null
 and description_translate_status is null
 order by coalesce(updated_at, created_at) desc
 limit 10 for no key update skip locked;
- "#
+ "#,
+            LANGUAGES
         )
         .fetch(&mut txn)
         .map_ok(|row| ResourceTranslate {
@@ -578,16 +594,24 @@ limit 10 for no key update skip locked;
             return Ok(true);
         }
 
+        let glossary = db::translation::get_glossary(&self.db).await?;
+
         for t in descriptions {
             let res: Option<Option<HashMap<String, String>>> =
-                multi_translation(&t.text, &self.api_key).await.ok();
+                multi_translation(&self.db, &glossary, &t.text, &self.api_key)
+                    .await
+                    .ok();
 
             if let Some(res) = res {
                 if let Some(res) = res {
                     sqlx::query!(
                         r#"
                             update resource_data
-                            set translated_description = $2,
+                            set translated_description = $2 || coalesce((
+                                    select translated_description_override
+                                    from resource
+                                    where live_id = $1
+                                ), '{}'),
                                 last_synced_at = null
                             where id = $1
                             "#,
@@ -640,7 +664,9 @@ limit 10 for no key update skip locked;
 
         for t in display_names {
             let res: Option<Option<HashMap<String, String>>> =
-                multi_translation(&t.text, &self.api_key).await.ok();
+                multi_translation(&self.db, &glossary, &t.text, &self.api_key)
+                    .await
+                    .ok();
 
             if let Some(res) = res {
                 if let Some(res) = res {
@@ -720,12 +746,13 @@ select playlist_data.id,
 from playlist_data
 inner join playlist on live_id = playlist_data.id
 where description <> ''
-      and translated_description = '{}'
+      and (translated_description = '{}' or not translated_description ?& $1)
       and published_at is not null
       and description_translate_status is null
 order by coalesce(updated_at, created_at) desc
 limit 20 for no key update skip locked;
- "#
+ "#,
+            LANGUAGES
         )
         .fetch(&mut txn)
         .map_ok(|row| PlaylistTranslate {
@@ -762,16 +789,24 @@ limit 20 for no key update skip locked;
             return Ok(true);
         }
 
+        let glossary = db::translation::get_glossary(&self.db).await?;
+
         for t in descriptions {
             let res: Option<Option<HashMap<String, String>>> =
-                multi_translation(&t.text, &self.api_key).await.ok();
+                multi_translation(&self.db, &glossary, &t.text, &self.api_key)
+                    .await
+                    .ok();
 
             if let Some(res) = res {
                 if let Some(res) = res {
                     sqlx::query!(
                         r#"
                             update playlist_data
-                            set translated_description = $2,
+                            set translated_description = $2 || coalesce((
+                                    select translated_description_override
+                                    from playlist
+                                    where live_id = $1
+                                ), '{}'),
                                 last_synced_at = null
                             where id = $1
                             "#,
@@ -827,7 +862,9 @@ limit 20 for no key update skip locked;
 
         for t in names {
             let res: Option<Option<HashMap<String, String>>> =
-                multi_translation(&t.text, &self.api_key).await.ok();
+                multi_translation(&self.db, &glossary, &t.text, &self.api_key)
+                    .await
+                    .ok();
 
             if let Some(res) = res {
                 if let Some(res) = res {
@@ -898,6 +935,16 @@ pub async fn translate_text(
     target: &str,
     source: &str,
     api_key: &str,
+) -> anyhow::Result<Option<String>> {
+    translate(query, target, source, "text", api_key).await
+}
+
+async fn translate(
+    query: &str,
+    target: &str,
+    source: &str,
+    format: &str,
+    api_key: &str,
 ) -> anyhow::Result<Option<String>> {
     //https://cloud.google.com/translate/docs/languages
     //https://cloud.google.com/translate/docs/reference/rest/v2/translate
@@ -908,7 +955,7 @@ pub async fn translate_text(
             q: query.to_string(),
             target: target.to_string(),
             source: source.to_string(),
-            format: "text".to_string(),
+            format: format.to_string(),
         })
         .send()
         .await?
@@ -932,10 +979,130 @@ pub async fn translate_text(
     Ok(Some(translate))
 }
 
-pub async fn multi_translation(
+/// Translates `query`, keeping glossary terms untranslated or replacing them with their fixed
+/// rendering for `target`.
+async fn translate_with_glossary(
+    query: &str,
+    target: &str,
+    source: &str,
+    glossary: &[GlossaryTerm],
+    api_key: &str,
+) -> anyhow::Result<Option<String>> {
+    match protect_glossary_terms(query, target, glossary) {
+        Some(html) => Ok(translate(&html, target, source, "html", api_key)
+            .await?
+            .map(|translated| unprotect_glossary_terms(&translated))),
+        None => translate_text(query, target, source, api_key).await,
+    }
+}
+
+const NO_TRANSLATE_OPEN: &str = r#"<span translate="no">"#;
+const NO_TRANSLATE_CLOSE: &str = "</span>";
+
+/// Escapes `text` as HTML and wraps every whole-word glossary term in a `translate="no"` span,
+/// replaced by its rendering for `target` if it has one.
+///
+/// Returns `None` if `text` doesn't contain any glossary terms.
+fn protect_glossary_terms(text: &str, target: &str, glossary: &[GlossaryTerm]) -> Option<String> {
+    let is_word_char = |c: Option<char>| c.map_or(false, char::is_alphanumeric);
+
+    // longest terms first, so that a term containing another term wins
+    let mut terms: Vec<_> = glossary.iter().filter(|it| !it.term.is_empty()).collect();
+    terms.sort_by_key(|it| std::cmp::Reverse(it.term.len()));
+
+    let mut matches: Vec<(usize, usize, &str)> = Vec::new();
+
+    for glossary_term in terms {
+        let term = glossary_term.term.as_str();
+
+        for (start, _) in text.match_indices(term) {
+            let end = start + term.len();
+
+            if is_word_char(text[..start].chars().next_back())
+                || is_word_char(text[end..].chars().next())
+            {
+                continue;
+            }
+
+            if matches.iter().any(|(s, e, _)| start < *e && *s < end) {
+                continue;
+            }
+
+            let rendering = glossary_term
+                .renderings
+                .get(target)
+                .map(String::as_str)
+                .unwrap_or(term);
+
+            matches.push((start, end, rendering));
+        }
+    }
+
+    if matches.is_empty() {
+        return None;
+    }
+
+    matches.sort_by_key(|(start, _, _)| *start);
+
+    let mut html = String::with_capacity(text.len());
+    let mut position = 0;
+
+    for (start, end, rendering) in matches {
+        html.push_str(&escape_html(&text[position..start]));
+        html.push_str(NO_TRANSLATE_OPEN);
+        html.push_str(&escape_html(rendering));
+        html.push_str(NO_TRANSLATE_CLOSE);
+        position = end;
+    }
+
+    html.push_str(&escape_html(&text[position..]));
+
+    Some(html)
+}
+
+/// Reverses [`protect_glossary_terms`] on a translation returned in HTML format.
+fn unprotect_glossary_terms(html: &str) -> String {
+    unescape_html(
+        &html
+            .replace(NO_TRANSLATE_OPEN, "")
+            .replace(NO_TRANSLATE_CLOSE, ""),
+    )
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+fn unescape_html(html: &str) -> String {
+    html.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&#x27;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Translates `query` into every language in [`LANGUAGES`].
+///
+/// Translations are looked up in the translation memory first, and only missing languages are
+/// sent to Google Translate. New translations are only saved if the glossary hasn't changed since.
+pub(crate) async fn multi_translation(
+    db: &PgPool,
+    glossary: &db::translation::Glossary,
     query: &str,
     api_key: &str,
 ) -> anyhow::Result<Option<HashMap<String, String>>> {
+    let mut translation_list = db::translation::get_translation_memory(db, query).await?;
+
+    if LANGUAGES.iter().all(|l| translation_list.contains_key(*l)) {
+        translation_list.retain(|l, _| LANGUAGES.contains(&l.as_str()));
+        return Ok(Some(translation_list));
+    }
+
     //https://cloud.google.com/translate/docs/languages
     //https://cloud.google.com/translate/docs/reference/rest/v2/translate
     let res = reqwest::Client::new()
@@ -967,23 +1134,35 @@ pub async fn multi_translation(
 
     let src = &v[0][..2];
 
-    let mut translation_list = HashMap::new();
+    let mut new_translations = HashMap::new();
 
     for l in LANGUAGES {
+        if translation_list.contains_key(*l) {
+            continue;
+        }
+
         let text = if l != &src {
-            let text: Option<String> = translate_text(query, l, src, &api_key.to_owned()).await?;
+            let text: Option<String> =
+                translate_with_glossary(query, l, src, &glossary.terms, &api_key.to_owned())
+                    .await?;
             text
         } else {
             None
         };
 
         if let Some(text) = text {
-            translation_list.insert(l.to_owned().to_owned(), text);
+            new_translations.insert(l.to_owned().to_owned(), text);
         } else {
-            translation_list.insert(l.to_owned().to_owned(), query.to_owned());
+            new_translations.insert(l.to_owned().to_owned(), query.to_owned());
         };
     }
 
+    db::translation::save_translation_memory(db, query, glossary.version, &new_translations)
+        .await?;
+
+    translation_list.extend(new_translations);
+    translation_list.retain(|l, _| LANGUAGES.contains(&l.as_str()));
+
     Ok(Some(translation_list))
 }
 
@@ -1045,3 +1224,55 @@ async fn update_image_translation_status(
 
 //     Ok(())
 // }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use shared::domain::admin::GlossaryTermId;
+
+    fn term(term: &str, renderings: &[(&str, &str)]) -> GlossaryTerm {
+        GlossaryTerm {
+            id: GlossaryTermId(Uuid::nil()),
+            term: term.to_owned(),
+            renderings: renderings
+                .iter()
+                .map(|(l, r)| (l.to_string(), r.to_string()))
+                .collect(),
+            created_at: Utc::now(),
+            updated_at: None,
+        }
+    }
+
+    #[test]
+    fn protects_whole_word_glossary_terms() {
+        let glossary = [
+            term("Pesach", &[("en", "Passover")]),
+            term("Rosh Hashanah", &[]),
+            term("Rosh", &[]),
+        ];
+
+        let html = protect_glossary_terms("Pesach & Rosh Hashanah, not Pesachim", "en", &glossary)
+            .expect("terms should match");
+
+        assert_eq!(
+            html,
+            r#"<span translate="no">Passover</span> &amp; <span translate="no">Rosh Hashanah</span>, not Pesachim"#
+        );
+
+        assert_eq!(
+            unprotect_glossary_terms(&html),
+            "Passover & Rosh Hashanah, not Pesachim"
+        );
+    }
+
+    #[test]
+    fn skips_text_without_glossary_terms() {
+        let glossary = [term("Sukkot", &[])];
+
+        assert_eq!(
+            protect_glossary_terms("A lesson about the alef-bet", "he", &glossary),
+            None
+        );
+    }
+}
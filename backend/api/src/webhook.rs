@@ -0,0 +1,252 @@
+//! Outbound webhooks.
+//!
+//! Events are queued with [`queue`], in the same transaction as the change they're about, so that
+//! an event is only sent if the change is committed. Queued deliveries are sent by [`deliver_due`],
+//! which is spawned right after an event is queued, and run by the scheduler to retry failed deliveries.
+//!
+//! See [`shared::domain::webhook`] for how deliveries are signed.
+//!
+//! Webhook URLs are entered by users, so each delivery goes through [`outbound`], which checks the
+//! address the URL resolves to at the time it's sent.
+
+use chrono::{DateTime, Duration, Utc};
+use hmac::{Hmac, Mac};
+use rand::Rng;
+use sha2::Sha256;
+use shared::domain::{
+    user::UserId,
+    webhook::{
+        WebhookDeliveryStatus, WebhookEvent, WebhookPayload, WEBHOOK_EVENT_HEADER,
+        WEBHOOK_SIGNATURE_HEADER,
+    },
+};
+use sqlx::{PgConnection, PgPool};
+
+use crate::{
+    db::{self, webhook::DueDelivery},
+    outbound,
+};
+
+/// Prefix of webhook secrets.
+const SECRET_PREFIX: &str = "whsec_";
+
+/// Deliveries are given up on after this many attempts.
+const MAX_ATTEMPTS: i16 = 12;
+
+/// Longest time between two attempts of a delivery.
+const MAX_RETRY_DELAY_MINUTES: i64 = 6 * 60;
+
+/// How many deliveries are sent at once.
+const BATCH_SIZE: i64 = 20;
+
+/// Longest error message that's kept in the delivery log.
+const MAX_ERROR_LEN: usize = 500;
+
+/// Generates the secret for a new webhook.
+#[must_use]
+pub fn generate_secret() -> String {
+    let mut bytes = [0_u8; 32];
+    rand::thread_rng().fill(&mut bytes[..]);
+    format!(
+        "{}{}",
+        SECRET_PREFIX,
+        base64::encode_config(&bytes, base64::URL_SAFE_NO_PAD)
+    )
+}
+
+fn hmac_sha256_hex(key: &[u8], message: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC can take a key of any size");
+    mac.update(message);
+
+    mac.finalize()
+        .into_bytes()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// Value of the signature header for a delivery attempted at `timestamp` (unix time).
+#[must_use]
+pub fn signature(secret: &str, timestamp: i64, body: &[u8]) -> String {
+    let mut message = format!("{}.", timestamp).into_bytes();
+    message.extend_from_slice(body);
+
+    format!(
+        "t={},v1={}",
+        timestamp,
+        hmac_sha256_hex(secret.as_bytes(), &message)
+    )
+}
+
+/// When to retry a delivery which failed for the `attempts`th time, or `None` to give up.
+fn retry_at(attempts: i16, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    if attempts >= MAX_ATTEMPTS {
+        return None;
+    }
+
+    let minutes = 1_i64
+        .checked_shl((attempts - 1).max(0) as u32)
+        .unwrap_or(i64::MAX)
+        .min(MAX_RETRY_DELAY_MINUTES);
+
+    Some(now + Duration::minutes(minutes))
+}
+
+/// Queues a delivery of an event about `user_ids` (e.g. the author of a JIG) to their webhooks,
+/// and the webhooks of admins which receive every user's events.
+///
+/// Returns whether any deliveries were queued, in which case [`spawn_delivery`] should be called after committing.
+pub async fn queue(
+    txn: &mut PgConnection,
+    event: WebhookEvent,
+    user_ids: impl IntoIterator<Item = UserId>,
+    data: serde_json::Value,
+) -> sqlx::Result<bool> {
+    let user_ids: Vec<_> = user_ids.into_iter().map(|id| id.0).collect();
+
+    db::webhook::enqueue(txn, event, &user_ids, &data).await
+}
+
+/// Sends due deliveries in the background.
+///
+/// `local` allows URLs on the local network, see [`outbound`].
+pub fn spawn_delivery(db: PgPool, local: bool) {
+    tokio::spawn(async move {
+        if let Err(e) = deliver_due(&db, local).await {
+            log::warn!("Failed to deliver webhooks: {:?}", e);
+        }
+    });
+}
+
+/// Attempts a delivery, returning the status the URL responded with and an error if it wasn't successful.
+async fn attempt(delivery: &DueDelivery, local: bool) -> (Option<u16>, Result<(), String>) {
+    let url = match reqwest::Url::parse(&delivery.url) {
+        Ok(url) => url,
+        Err(e) => return (None, Err(e.to_string())),
+    };
+
+    // resolved for every attempt, as the URL's host could point somewhere else since it was registered
+    let client = match outbound::client(&url, local).await {
+        Ok(client) => client,
+        Err(e) => return (None, Err(e.to_string())),
+    };
+
+    let payload = WebhookPayload {
+        id: delivery.id,
+        event: delivery.event,
+        created_at: delivery.created_at,
+        data: delivery.data.clone(),
+    };
+
+    let body = match serde_json::to_vec(&payload) {
+        Ok(body) => body,
+        Err(e) => return (None, Err(e.to_string())),
+    };
+
+    let signature = signature(&delivery.secret, Utc::now().timestamp(), &body);
+
+    let event = serde_json::to_value(delivery.event)
+        .ok()
+        .and_then(|it| it.as_str().map(ToOwned::to_owned))
+        .unwrap_or_default();
+
+    let resp = client
+        .post(url)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .header(WEBHOOK_EVENT_HEADER, event)
+        .header(WEBHOOK_SIGNATURE_HEADER, signature)
+        .body(body)
+        .send()
+        .await;
+
+    match resp {
+        Ok(resp) if resp.status().is_success() => (Some(resp.status().as_u16()), Ok(())),
+        Ok(resp) => (
+            Some(resp.status().as_u16()),
+            Err(format!("Responded with {}", resp.status())),
+        ),
+        Err(e) => (None, Err(e.to_string())),
+    }
+}
+
+/// Sends every delivery which is due, returning how many were sent successfully.
+pub async fn deliver_due(db: &PgPool, local: bool) -> anyhow::Result<usize> {
+    let mut delivered = 0;
+
+    loop {
+        // claimed for longer than a batch can take, in case this instance stops halfway through
+        let due =
+            db::webhook::claim_due(db, BATCH_SIZE, (outbound::TIMEOUT * 6).as_secs_f64()).await?;
+
+        if due.is_empty() {
+            break;
+        }
+
+        let attempts = due.iter().map(|delivery| attempt(delivery, local));
+        let results = futures::future::join_all(attempts).await;
+
+        for (delivery, (response_status, result)) in due.iter().zip(results) {
+            let attempts = delivery.attempts + 1;
+
+            let (status, error, next_attempt_at) = match result {
+                Ok(()) => {
+                    delivered += 1;
+                    (WebhookDeliveryStatus::Delivered, None, None)
+                }
+                Err(error) => {
+                    let error: String = error.chars().take(MAX_ERROR_LEN).collect();
+
+                    match retry_at(attempts, Utc::now()) {
+                        Some(at) => (WebhookDeliveryStatus::Pending, Some(error), Some(at)),
+                        None => (WebhookDeliveryStatus::Failed, Some(error), None),
+                    }
+                }
+            };
+
+            db::webhook::record_attempt(
+                db,
+                delivery.id,
+                status,
+                response_status,
+                error.as_deref(),
+                next_attempt_at,
+            )
+            .await?;
+        }
+    }
+
+    Ok(delivered)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::{hmac_sha256_hex, retry_at, signature, MAX_ATTEMPTS};
+
+    #[test]
+    fn hmac_rfc_4231() {
+        assert_eq!(
+            hmac_sha256_hex(b"Jefe", b"what do ya want for nothing?"),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[test]
+    fn signature_format() {
+        let signature = signature("whsec_test", 1688980000, b"{}");
+
+        assert!(signature.starts_with("t=1688980000,v1="));
+        assert_eq!(signature.len(), "t=1688980000,v1=".len() + 64);
+    }
+
+    #[test]
+    fn backoff() {
+        let now = Utc.timestamp_opt(1688980000, 0).unwrap();
+
+        assert_eq!(retry_at(1, now), Some(now + chrono::Duration::minutes(1)));
+        assert_eq!(retry_at(4, now), Some(now + chrono::Duration::minutes(8)));
+        assert_eq!(retry_at(11, now), Some(now + chrono::Duration::hours(6)));
+        assert_eq!(retry_at(MAX_ATTEMPTS, now), None);
+    }
+}
@@ -0,0 +1,176 @@
+use http::StatusCode;
+use ji_cloud_api::db::billing;
+use macros::test_service;
+use serde_json::json;
+use shared::domain::{
+    billing::{BillingNotificationKind, BillingNotificationPreferences},
+    user::UserId,
+};
+use sqlx::{
+    postgres::{PgConnectOptions, PgPoolOptions},
+    PgPool,
+};
+use uuid::Uuid;
+
+use crate::{
+    fixture::Fixture,
+    helpers::{initialize_server_and_get_db, setup_service, LoginExt},
+};
+
+const USER_ID: UserId = UserId(Uuid::from_u128(0x1f241e1b_b537_493f_a230_075cb16315be));
+
+async fn notification_preferences(
+    client: &reqwest::Client,
+    port: u16,
+) -> anyhow::Result<BillingNotificationPreferences> {
+    let resp = client
+        .get(&format!(
+            "http://0.0.0.0:{}/v1/user/me/billing-notifications",
+            port
+        ))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    Ok(resp.json().await?)
+}
+
+#[test_service(setup = "setup_service", fixtures("Fixture::User"))]
+async fn billing_notification_preferences(port: u16) -> anyhow::Result<()> {
+    let client = reqwest::Client::new();
+
+    // every notification is enabled until the user opts out
+    let preferences = notification_preferences(&client, port).await?;
+
+    assert!(preferences.is_enabled(BillingNotificationKind::TrialEnding));
+    assert!(preferences.is_enabled(BillingNotificationKind::PaymentFailed));
+    assert!(preferences.is_enabled(BillingNotificationKind::SubscriptionExpired));
+
+    let resp = client
+        .put(&format!(
+            "http://0.0.0.0:{}/v1/user/me/billing-notifications",
+            port
+        ))
+        .json(&json!({
+            "trial_ending": true,
+            "payment_failed": true,
+            "subscription_expired": false,
+        }))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    let preferences = notification_preferences(&client, port).await?;
+
+    assert!(preferences.is_enabled(BillingNotificationKind::TrialEnding));
+    assert!(preferences.is_enabled(BillingNotificationKind::PaymentFailed));
+    assert!(!preferences.is_enabled(BillingNotificationKind::SubscriptionExpired));
+
+    Ok(())
+}
+
+/// Adds an account administered by the test user, with a subscription whose period ended
+/// yesterday while still marked active, like a subscription that lapsed without Stripe
+/// updating its status.
+async fn lapsed_subscription(
+    pool_opts: PgPoolOptions,
+    conn_opts: PgConnectOptions,
+    auto_renew: bool,
+) -> anyhow::Result<PgPool> {
+    let (_, db) = initialize_server_and_get_db(&[Fixture::User], &[], pool_opts, conn_opts).await;
+
+    sqlx::query(
+        // language=SQL
+        r#"
+with plan as (
+    insert into subscription_plan (product_id, price_id, subscription_tier, subscription_type, billing_interval, amount_in_cents)
+    values ('prod_test', 'price_test', 0, 0, 0, 1000)
+    returning plan_id
+), account as (
+    insert into account (account_type) values (0)
+    returning account_id
+), user_account as (
+    insert into user_account (user_id, account_id, admin)
+    select $1, account_id, true from account
+)
+insert into subscription (stripe_subscription_id, subscription_plan_id, subscription_tier, auto_renew, status, current_period_end, account_id)
+select 'sub_test', plan_id, 0, $2, 1, now() - interval '1 day', account_id
+from plan, account
+"#,
+    )
+    .bind(USER_ID.0)
+    .bind(auto_renew)
+    .execute(&db)
+    .await?;
+
+    Ok(db)
+}
+
+#[sqlx::test]
+async fn subscription_expired_notification(
+    pool_opts: PgPoolOptions,
+    conn_opts: PgConnectOptions,
+) -> anyhow::Result<()> {
+    let db = lapsed_subscription(pool_opts, conn_opts, false).await?;
+
+    let kind = BillingNotificationKind::SubscriptionExpired;
+
+    let recipients = billing::get_pending_billing_notifications(&db, kind, 3).await?;
+
+    assert_eq!(recipients.len(), 1);
+    assert_eq!(recipients[0].user_id, USER_ID);
+    assert!(recipients[0].preferences.is_enabled(kind));
+
+    billing::upsert_billing_notification_preferences(
+        &db,
+        USER_ID,
+        BillingNotificationPreferences {
+            subscription_expired: false,
+            ..Default::default()
+        },
+    )
+    .await?;
+
+    let recipients = billing::get_pending_billing_notifications(&db, kind, 3).await?;
+
+    assert!(!recipients[0].preferences.is_enabled(kind));
+
+    // skipped notices are marked as handled, so they aren't sent once the user opts back in
+    billing::mark_billing_notification_sent(&db, &recipients[0], kind).await?;
+
+    billing::upsert_billing_notification_preferences(
+        &db,
+        USER_ID,
+        BillingNotificationPreferences::default(),
+    )
+    .await?;
+
+    let recipients = billing::get_pending_billing_notifications(&db, kind, 3).await?;
+
+    assert!(recipients.is_empty());
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn renewing_subscription_not_expired(
+    pool_opts: PgPoolOptions,
+    conn_opts: PgConnectOptions,
+) -> anyhow::Result<()> {
+    let db = lapsed_subscription(pool_opts, conn_opts, true).await?;
+
+    let recipients = billing::get_pending_billing_notifications(
+        &db,
+        BillingNotificationKind::SubscriptionExpired,
+        3,
+    )
+    .await?;
+
+    assert!(recipients.is_empty());
+
+    Ok(())
+}
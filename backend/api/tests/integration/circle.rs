@@ -9,6 +9,9 @@ use crate::{
     helpers::{setup_service, LoginExt},
 };
 
+mod library;
+mod post;
+
 #[test_service(setup = "setup_service", fixtures("Fixture::User", "Fixture::Image"))]
 async fn create(port: u16) -> anyhow::Result<()> {
     let name = "create";
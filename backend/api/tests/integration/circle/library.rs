@@ -0,0 +1,138 @@
+use http::StatusCode;
+use macros::test_service;
+use serde_json::json;
+use shared::domain::{
+    circle::library::{CircleLibraryItemId, CircleLibraryListResponse},
+    CreateResponse,
+};
+use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+
+use crate::{
+    fixture::Fixture,
+    helpers::{setup_service, LoginExt},
+};
+
+#[test_service(
+    setup = "setup_service",
+    fixtures(
+        "Fixture::MetaKinds",
+        "Fixture::User",
+        "Fixture::Image",
+        "Fixture::Circle",
+        "Fixture::Jig",
+        "Fixture::Resource"
+    )
+)]
+async fn library_flow(port: u16) -> anyhow::Result<()> {
+    let name = "library_flow";
+
+    let client = reqwest::Client::new();
+
+    let circle_id = "57a1eaaa-f182-11ec-a96e-13f3929f5b22";
+
+    let resp = client
+        .post(&format!(
+            "http://0.0.0.0:{}/v1/circle/{}/library",
+            port, circle_id
+        ))
+        .json(&json!({
+            "assetId": { "jigId": "0cc084bc-7c83-11eb-9f77-e3218dffb008" },
+            "note": "  Great for the first week of Hanukkah  ",
+        }))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    let jig_item: CreateResponse<CircleLibraryItemId> = resp.json().await?;
+
+    let resp = client
+        .post(&format!(
+            "http://0.0.0.0:{}/v1/circle/{}/library",
+            port, circle_id
+        ))
+        .json(&json!({
+            "assetId": { "resourceId": "d8067526-1518-11ed-87fa-ebaf880b6d9c" },
+        }))
+        .login()
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    // an asset can only be pinned once
+    let resp = client
+        .post(&format!(
+            "http://0.0.0.0:{}/v1/circle/{}/library",
+            port, circle_id
+        ))
+        .json(&json!({
+            "assetId": { "jigId": "0cc084bc-7c83-11eb-9f77-e3218dffb008" },
+        }))
+        .login()
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::CONFLICT);
+
+    let resp = client
+        .patch(&format!(
+            "http://0.0.0.0:{}/v1/circle/{}/library/{}",
+            port, circle_id, jig_item.id.0
+        ))
+        .json(&json!({
+            "note": "Great for Hanukkah",
+        }))
+        .login()
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    let resp = client
+        .get(&format!(
+            "http://0.0.0.0:{}/v1/circle/{}/library",
+            port, circle_id
+        ))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let body: serde_json::Value = resp.json().await?;
+
+    insta::assert_json_snapshot!(format!("{}-1", name), body, {
+        ".items[].id" => "[id]",
+        ".items[].pinnedAt" => "[timestamp]",
+        ".items[1].updatedAt" => "[timestamp]",
+    });
+
+    let resp = client
+        .delete(&format!(
+            "http://0.0.0.0:{}/v1/circle/{}/library/{}",
+            port, circle_id, jig_item.id.0
+        ))
+        .login()
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    let resp = client
+        .get(&format!(
+            "http://0.0.0.0:{}/v1/circle/{}/library?assetType=jig",
+            port, circle_id
+        ))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let body: CircleLibraryListResponse = resp.json().await?;
+
+    assert_eq!(body.total_item_count, 0);
+
+    Ok(())
+}
@@ -0,0 +1,151 @@
+use http::StatusCode;
+use macros::test_service;
+use serde_json::json;
+use shared::domain::{circle::post::CirclePostId, CreateResponse};
+use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+
+use crate::{
+    fixture::Fixture,
+    helpers::{setup_service, LoginExt},
+};
+
+#[test_service(
+    setup = "setup_service",
+    fixtures(
+        "Fixture::MetaKinds",
+        "Fixture::User",
+        "Fixture::Image",
+        "Fixture::Circle",
+        "Fixture::Jig"
+    )
+)]
+async fn thread_flow(port: u16) -> anyhow::Result<()> {
+    let name = "thread_flow";
+
+    let client = reqwest::Client::new();
+
+    let circle_id = "57a1eaaa-f182-11ec-a96e-13f3929f5b22";
+
+    let resp = client
+        .post(&format!(
+            "http://0.0.0.0:{}/v1/circle/{}/post",
+            port, circle_id
+        ))
+        .json(&json!({
+            "body": "  Which JIGs do you use for Hanukkah?  ",
+            "assets": [{ "jigId": "0cc084bc-7c83-11eb-9f77-e3218dffb008" }],
+        }))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    let thread: CreateResponse<CirclePostId> = resp.json().await?;
+
+    let resp = client
+        .post(&format!(
+            "http://0.0.0.0:{}/v1/circle/{}/post",
+            port, circle_id
+        ))
+        .json(&json!({
+            "body": "This one!",
+            "parentId": thread.id,
+        }))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let reply: CreateResponse<CirclePostId> = resp.json().await?;
+
+    // threads only go one level deep
+    let resp = client
+        .post(&format!(
+            "http://0.0.0.0:{}/v1/circle/{}/post",
+            port, circle_id
+        ))
+        .json(&json!({
+            "body": "Thanks",
+            "parentId": reply.id,
+        }))
+        .login()
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let resp = client
+        .put(&format!(
+            "http://0.0.0.0:{}/v1/circle/{}/post/{}/reaction",
+            port, circle_id, thread.id.0
+        ))
+        .json(&json!({
+            "reaction": "insightful",
+        }))
+        .login()
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    let resp = client
+        .get(&format!(
+            "http://0.0.0.0:{}/v1/circle/{}/post",
+            port, circle_id
+        ))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let body: serde_json::Value = resp.json().await?;
+
+    insta::assert_json_snapshot!(format!("{}-1", name), body, {
+        ".posts[].id" => "[id]",
+        ".posts[].createdAt" => "[timestamp]",
+    });
+
+    let resp = client
+        .get(&format!(
+            "http://0.0.0.0:{}/v1/circle/{}/post/{}/replies",
+            port, circle_id, thread.id.0
+        ))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let body: serde_json::Value = resp.json().await?;
+
+    insta::assert_json_snapshot!(format!("{}-2", name), body, {
+        ".posts[].id" => "[id]",
+        ".posts[].parentId" => "[id]",
+        ".posts[].createdAt" => "[timestamp]",
+    });
+
+    let resp = client
+        .delete(&format!(
+            "http://0.0.0.0:{}/v1/circle/{}/post/{}",
+            port, circle_id, thread.id.0
+        ))
+        .login()
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    let resp = client
+        .get(&format!(
+            "http://0.0.0.0:{}/v1/circle/{}/post/{}/replies",
+            port, circle_id, thread.id.0
+        ))
+        .login()
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    Ok(())
+}
@@ -21,6 +21,9 @@ pub enum Fixture {
     Playlist,
     Resource,
     Course,
+    SessionEnrollTotp,
+    SchoolSso,
+    Report,
 }
 
 impl Fixture {
@@ -47,6 +50,9 @@ impl Fixture {
             Self::Playlist => include_str!("../../fixtures/20_playlist.sql"),
             Self::Resource => include_str!("../../fixtures/21_resource.sql"),
             Self::Course => include_str!("../../fixtures/22_course.sql"),
+            Self::SessionEnrollTotp => include_str!("../../fixtures/23_session_enroll_totp.sql"),
+            Self::SchoolSso => include_str!("../../fixtures/24_school_sso.sql"),
+            Self::Report => include_str!("../../fixtures/25_report.sql"),
         }
     }
 }
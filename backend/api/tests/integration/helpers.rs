@@ -120,6 +120,8 @@ pub async fn initialize_server_and_get_db(
         None,
         None,
         None,
+        None,
+        None,
         PASETO_KEY.clone(),
         None,
         None,
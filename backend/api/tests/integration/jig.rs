@@ -13,6 +13,7 @@ mod additional_resource;
 mod cover;
 mod module;
 mod player;
+mod report;
 
 #[test_service(setup = "setup_service", fixtures("Fixture::User"))]
 async fn create_default(port: u16) -> anyhow::Result<()> {
@@ -132,6 +132,33 @@ async fn session_instance_play_count_flow(port: u16) -> anyhow::Result<()> {
 
     insta::assert_json_snapshot!(format!("{}-1",name), body, {".**.token" => "[instance_token]"});
 
+    let resp = client
+        .post(&format!(
+            "http://0.0.0.0:{}/v1/jig/player/instance/points",
+            port
+        ))
+        .json(&serde_json::json!({
+            "token": token,
+            "module": {
+                "moduleId": "a6b248f8-1dd7-11ec-8426-975953035335",
+                "points": 3,
+                "completed": true,
+            },
+        }))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let body: serde_json::Value = resp.json().await?;
+
+    insta::assert_json_snapshot!(format!("{}-3", name), body, {
+        ".id" => "[id]",
+        ".createdAt" => "[timestamp]",
+    });
+
     let resp = client
         .post(&format!(
             "http://0.0.0.0:{}/v1/jig/player/instance/complete",
@@ -145,7 +172,34 @@ async fn session_instance_play_count_flow(port: u16) -> anyhow::Result<()> {
         .await?
         .error_for_status()?;
 
-    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let body: serde_json::Value = resp.json().await?;
+
+    insta::assert_json_snapshot!(format!("{}-4", name), body, {
+        ".id" => "[id]",
+        ".createdAt" => "[timestamp]",
+        ".completedAt" => "[timestamp]",
+    });
+
+    let resp = client
+        .post(&format!(
+            "http://0.0.0.0:{}/v1/jig/player/instance/points",
+            port
+        ))
+        .json(&serde_json::json!({
+            "token": token,
+            "module": {
+                "moduleId": "a6b248f8-1dd7-11ec-8426-975953035335",
+                "points": 5,
+                "completed": true,
+            },
+        }))
+        .login()
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::CONFLICT);
 
     let resp = client
         .get(&format!(
@@ -163,3 +217,409 @@ async fn session_instance_play_count_flow(port: u16) -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test_service(
+    setup = "setup_service",
+    fixtures("Fixture::MetaKinds", "Fixture::User", "Fixture::Jig")
+)]
+async fn report_points_other_client(port: u16) -> anyhow::Result<()> {
+    let client: reqwest::Client = reqwest::ClientBuilder::new()
+        .user_agent("mocked user agent")
+        .build()?;
+
+    let resp = client
+        .post(&format!("http://0.0.0.0:{}/v1/jig/player/instance", port))
+        .json(&serde_json::json!({
+            "index": 1234,
+        }))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let body: PlayerSessionInstanceResponse = resp.json().await?;
+
+    // the instance token was issued to a different user agent
+    let other_client: reqwest::Client = reqwest::ClientBuilder::new()
+        .user_agent("other user agent")
+        .build()?;
+
+    let resp = other_client
+        .post(&format!(
+            "http://0.0.0.0:{}/v1/jig/player/instance/points",
+            port
+        ))
+        .json(&serde_json::json!({
+            "token": body.token,
+            "module": {
+                "moduleId": "a6b248f8-1dd7-11ec-8426-975953035335",
+                "points": 3,
+                "completed": true,
+            },
+        }))
+        .login()
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    Ok(())
+}
+
+#[test_service(
+    setup = "setup_service",
+    fixtures("Fixture::MetaKinds", "Fixture::User", "Fixture::Jig")
+)]
+async fn leaderboard(port: u16) -> anyhow::Result<()> {
+    let name = "leaderboard";
+
+    let client = reqwest::Client::new();
+
+    let mut tokens = Vec::new();
+
+    for display_name in ["Alice", "Bob"] {
+        let resp = client
+            .post(&format!("http://0.0.0.0:{}/v1/jig/player/instance", port))
+            .json(&serde_json::json!({
+                "index": 1234,
+                "displayName": display_name,
+            }))
+            .send()
+            .await?
+            .error_for_status()?;
+
+        let body: PlayerSessionInstanceResponse = resp.json().await?;
+
+        tokens.push(body.token);
+    }
+
+    let resp = client
+        .post(&format!(
+            "http://0.0.0.0:{}/v1/jig/player/instance/points",
+            port
+        ))
+        .json(&serde_json::json!({
+            "token": tokens[1],
+            "module": {
+                "moduleId": "a6b248f8-1dd7-11ec-8426-975953035335",
+                "points": 2,
+                "completed": true,
+            },
+        }))
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let resp = client
+        .get(&format!(
+            "http://0.0.0.0:{}/v1/jig/player/1234/leaderboard",
+            port
+        ))
+        .login()
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let body: serde_json::Value = resp.json().await?;
+
+    let cursor = body["cursor"].as_str().unwrap().to_owned();
+
+    insta::assert_json_snapshot!(format!("{}-1", name), body, {
+        ".entries[].instanceId" => "[id]",
+        ".entries[].updatedAt" => "[timestamp]",
+        ".cursor" => "[timestamp]",
+    });
+
+    let resp = client
+        .get(&format!(
+            "http://0.0.0.0:{}/v1/jig/player/1234/leaderboard",
+            port
+        ))
+        .query(&[("since", cursor)])
+        .login()
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let body: serde_json::Value = resp.json().await?;
+
+    assert_eq!(body["totalCount"], 2);
+
+    let cursor = body["cursor"].as_str().unwrap().to_owned();
+
+    // Alice overtakes Bob, whose entry has to be returned with its new rank
+    let resp = client
+        .post(&format!(
+            "http://0.0.0.0:{}/v1/jig/player/instance/points",
+            port
+        ))
+        .json(&serde_json::json!({
+            "token": tokens[0],
+            "module": {
+                "moduleId": "a6b248f8-1dd7-11ec-8426-975953035335",
+                "points": 5,
+                "completed": true,
+            },
+        }))
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let resp = client
+        .get(&format!(
+            "http://0.0.0.0:{}/v1/jig/player/1234/leaderboard",
+            port
+        ))
+        .query(&[("since", cursor)])
+        .login()
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let body: serde_json::Value = resp.json().await?;
+
+    assert_eq!(body["entries"][0]["displayName"], "Alice");
+    assert_eq!(body["entries"][1]["displayName"], "Bob");
+    assert_eq!(body["entries"][1]["rank"], 2);
+
+    let resp = client
+        .post(&format!("http://0.0.0.0:{}/v1/jig/player/instance", port))
+        .json(&serde_json::json!({
+            "index": 1234,
+            "displayName": "   ",
+        }))
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
+
+#[test_service(
+    setup = "setup_service",
+    fixtures("Fixture::MetaKinds", "Fixture::User", "Fixture::Jig")
+)]
+async fn assignment(port: u16) -> anyhow::Result<()> {
+    let name = "assignment";
+
+    let client = reqwest::Client::new();
+
+    let resp = client
+        .post(&format!("http://0.0.0.0:{}/v1/jig/player/assignment", port))
+        .json(&serde_json::json!({
+            "items": [
+                { "jigId": "0cc084bc-7c83-11eb-9f77-e3218dffb008" },
+                {
+                    "jigId": "3a71522a-cd77-11eb-8dc1-af3e35f7c743",
+                    "settings": {
+                        "direction": "rtl",
+                        "displayScore": true,
+                    },
+                },
+            ],
+            "maxAttempts": 1,
+        }))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    let body: serde_json::Value = resp.json().await?;
+
+    let index = body["index"].as_i64().unwrap();
+
+    insta::assert_json_snapshot!(format!("{}-1", name), body, {
+        ".index" => "[index]",
+        ".createdAt" => "[timestamp]",
+        ".expiresAt" => "[timestamp]",
+    });
+
+    let resp = client
+        .post(&format!("http://0.0.0.0:{}/v1/jig/player/instance", port))
+        .json(&serde_json::json!({
+            "index": index,
+        }))
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let body: PlayerSessionInstanceResponse = resp.json().await?;
+
+    assert_eq!(body.items.len(), 2);
+
+    // only one attempt per device
+    let resp = client
+        .post(&format!("http://0.0.0.0:{}/v1/jig/player/instance", port))
+        .json(&serde_json::json!({
+            "index": index,
+        }))
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    let resp = client
+        .post(&format!(
+            "http://0.0.0.0:{}/v1/jig/player/assignment/{}/close",
+            port, index
+        ))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let body: serde_json::Value = resp.json().await?;
+
+    assert!(body["closesAt"].is_string());
+
+    let resp = client
+        .get(&format!("http://0.0.0.0:{}/v1/jig/player/assignment", port))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let body: serde_json::Value = resp.json().await?;
+
+    assert_eq!(body["assignments"].as_array().unwrap().len(), 1);
+
+    let resp = client
+        .post(&format!("http://0.0.0.0:{}/v1/jig/player/assignment", port))
+        .json(&serde_json::json!({
+            "items": [{ "jigId": "0cc084bc-7c83-11eb-9f77-e3218dffb008" }],
+            "playlistId": "db817a2c-f3ec-11ec-b8ef-37027ca902ea",
+        }))
+        .login()
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
+
+#[test_service(
+    setup = "setup_service",
+    fixtures("Fixture::MetaKinds", "Fixture::User", "Fixture::Jig")
+)]
+async fn session_lifetime(port: u16) -> anyhow::Result<()> {
+    let client = reqwest::Client::new();
+
+    // session 1235 has expired
+    let resp = client
+        .post(&format!("http://0.0.0.0:{}/v1/jig/player/instance", port))
+        .json(&serde_json::json!({
+            "index": 1235,
+        }))
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    let resp = client
+        .post(&format!(
+            "http://0.0.0.0:{}/v1/jig/player/1234/extend",
+            port
+        ))
+        .json(&serde_json::json!({
+            "duration": 3600,
+        }))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let body: serde_json::Value = resp.json().await?;
+
+    assert!(body["expiresAt"].is_string());
+
+    // sessions can't be valid for longer than a year
+    let resp = client
+        .post(&format!(
+            "http://0.0.0.0:{}/v1/jig/player/1234/extend",
+            port
+        ))
+        .json(&serde_json::json!({
+            "duration": 60 * 60 * 24 * 366,
+        }))
+        .login()
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let resp = client
+        .post(&format!("http://0.0.0.0:{}/v1/jig/player/instance", port))
+        .json(&serde_json::json!({
+            "index": 1234,
+        }))
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let body: PlayerSessionInstanceResponse = resp.json().await?;
+
+    let resp = client
+        .post(&format!(
+            "http://0.0.0.0:{}/v1/jig/player/1234/revoke",
+            port
+        ))
+        .login()
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    // outstanding tokens stop working
+    let resp = client
+        .post(&format!(
+            "http://0.0.0.0:{}/v1/jig/player/instance/points",
+            port
+        ))
+        .json(&serde_json::json!({
+            "token": body.token,
+            "module": {
+                "moduleId": "a6b248f8-1dd7-11ec-8426-975953035335",
+                "points": 1,
+                "completed": true,
+            },
+        }))
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::GONE);
+
+    let resp = client
+        .post(&format!("http://0.0.0.0:{}/v1/jig/player/instance", port))
+        .json(&serde_json::json!({
+            "index": 1234,
+        }))
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    let resp = client
+        .post(&format!(
+            "http://0.0.0.0:{}/v1/jig/player/1234/extend",
+            port
+        ))
+        .json(&serde_json::json!({
+            "duration": 3600,
+        }))
+        .login()
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    Ok(())
+}
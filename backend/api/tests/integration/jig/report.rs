@@ -0,0 +1,138 @@
+use http::StatusCode;
+use ji_cloud_api::db::jig::report;
+use macros::test_service;
+use serde_json::json;
+use shared::domain::{
+    asset::{ReportAction, ReportStatus},
+    jig::{
+        curation::JigCurationData,
+        report::{JigReport, ReportId},
+        JigId,
+    },
+    user::UserId,
+};
+use sqlx::{
+    postgres::{PgConnectOptions, PgPoolOptions},
+    PgPool,
+};
+use uuid::Uuid;
+
+use crate::{
+    fixture::Fixture,
+    helpers::{initialize_server_and_get_db, setup_service, LoginExt},
+};
+
+const USER_ID: UserId = UserId(Uuid::from_u128(0x1f241e1b_b537_493f_a230_075cb16315be));
+
+const JIG_ID: JigId = JigId(Uuid::from_u128(0x0cc084bc_7c83_11eb_9f77_e3218dffb008));
+
+const REPORT_ID: ReportId = ReportId(Uuid::from_u128(0x6c5b0f4e_2f3a_11ee_9e3b_2b9d4e1f7a01));
+
+#[test_service(
+    setup = "setup_service",
+    fixtures(
+        "Fixture::MetaKinds",
+        "Fixture::User",
+        "Fixture::Jig",
+        "Fixture::Report"
+    )
+)]
+async fn curation_with_report(port: u16) -> anyhow::Result<()> {
+    let client = reqwest::Client::new();
+
+    let resp = client
+        .get(&format!(
+            "http://0.0.0.0:{}/v1/jig/{}/curation",
+            port, JIG_ID.0
+        ))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let curation: JigCurationData = resp.json().await?;
+
+    assert_eq!(curation.reports.len(), 1);
+    assert_eq!(curation.reports[0].id, REPORT_ID);
+    assert_eq!(curation.reports[0].reporter_id, Some(USER_ID.0));
+    assert_eq!(curation.reports[0].status, ReportStatus::Pending);
+
+    let resp = client
+        .patch(&format!(
+            "http://0.0.0.0:{}/v1/jig/{}/report/{}/assign",
+            port, JIG_ID.0, REPORT_ID.0
+        ))
+        .json(&json!({ "assigneeId": USER_ID }))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    let resp = client
+        .get(&format!(
+            "http://0.0.0.0:{}/v1/jig/{}/report/{}",
+            port, JIG_ID.0, REPORT_ID.0
+        ))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let report: JigReport = resp.json().await?;
+
+    assert_eq!(report.status, ReportStatus::InReview);
+    assert_eq!(report.assignee_id, Some(USER_ID));
+
+    Ok(())
+}
+
+async fn is_blocked(db: &PgPool) -> sqlx::Result<bool> {
+    sqlx::query_scalar(
+        // language=SQL
+        r#"select blocked from jig_admin_data where jig_id = $1"#,
+    )
+    .bind(JIG_ID.0)
+    .fetch_one(db)
+    .await
+}
+
+#[sqlx::test]
+async fn resolve_with_block(
+    pool_opts: PgPoolOptions,
+    conn_opts: PgConnectOptions,
+) -> anyhow::Result<()> {
+    let (_, db) = initialize_server_and_get_db(
+        &[
+            Fixture::MetaKinds,
+            Fixture::User,
+            Fixture::Jig,
+            Fixture::Report,
+        ],
+        &[],
+        pool_opts,
+        conn_opts,
+    )
+    .await;
+
+    assert!(!is_blocked(&db).await?);
+
+    let resolved =
+        report::resolve_report(&db, JIG_ID, REPORT_ID, ReportAction::Block, None, USER_ID)
+            .await
+            .ok();
+
+    assert_eq!(resolved, Some(true));
+    assert!(is_blocked(&db).await?);
+
+    // a resolved report can't be resolved again
+    let resolved =
+        report::resolve_report(&db, JIG_ID, REPORT_ID, ReportAction::Dismiss, None, USER_ID)
+            .await
+            .ok();
+
+    assert_eq!(resolved, Some(false));
+
+    Ok(())
+}
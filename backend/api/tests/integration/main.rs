@@ -5,6 +5,7 @@ use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
 mod animation;
 mod audio;
 mod auth;
+mod billing;
 mod category;
 mod circle;
 mod course;
@@ -14,11 +15,18 @@ mod image;
 mod jig;
 mod locale;
 mod meta;
+mod notification;
 mod playlist;
 mod resource;
+mod review;
+mod roster;
+mod school_sso;
 mod service;
 mod session;
+mod share;
+mod sitemap;
 mod user;
+mod webhook;
 
 #[test_service(setup = "setup_service")]
 async fn pass(port: u16) -> anyhow::Result<()> {
@@ -0,0 +1,183 @@
+use http::StatusCode;
+use macros::test_service;
+use serde_json::json;
+use shared::domain::notification::{
+    NotificationKind, NotificationListResponse, NotificationPreferences,
+    NotificationUnreadCountResponse,
+};
+use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+
+use crate::{
+    fixture::Fixture,
+    helpers::{setup_service, LoginExt},
+};
+
+async fn unread_count(client: &reqwest::Client, port: u16) -> anyhow::Result<u64> {
+    let resp = client
+        .get(&format!(
+            "http://0.0.0.0:{}/v1/notification/unread-count",
+            port
+        ))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let body: NotificationUnreadCountResponse = resp.json().await?;
+
+    Ok(body.count)
+}
+
+#[test_service(
+    setup = "setup_service",
+    fixtures("Fixture::User", "Fixture::Image", "Fixture::Circle")
+)]
+async fn circle_invite_flow(port: u16) -> anyhow::Result<()> {
+    let client = reqwest::Client::new();
+
+    assert_eq!(unread_count(&client, port).await?, 0);
+
+    // the test user created this circle without joining it, and invites themselves twice
+    for _ in 0..2 {
+        let resp = client
+            .post(&format!(
+                "http://0.0.0.0:{}/v1/circle/57a1eaaa-f182-11ec-a96e-13f3929f5b22/invite/1f241e1b-b537-493f-a230-075cb16315be",
+                port
+            ))
+            .login()
+            .send()
+            .await?
+            .error_for_status()?;
+
+        assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+    }
+
+    // already a member
+    let resp = client
+        .post(&format!(
+            "http://0.0.0.0:{}/v1/circle/829606d0-f185-11ec-b9e4-5fadfd7252f6/invite/a641fd6e-e41b-11eb-8176-57df101c2201",
+            port
+        ))
+        .login()
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let resp = client
+        .get(&format!("http://0.0.0.0:{}/v1/notification", port))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let body: NotificationListResponse = resp.json().await?;
+
+    assert_eq!(body.total_count, 1);
+    assert_eq!(body.notifications[0].kind, NotificationKind::CircleInvite);
+    assert_eq!(
+        body.notifications[0].data["circleId"],
+        "57a1eaaa-f182-11ec-a96e-13f3929f5b22"
+    );
+    assert_eq!(body.notifications[0].read_at, None);
+
+    assert_eq!(unread_count(&client, port).await?, 1);
+
+    let resp = client
+        .post(&format!("http://0.0.0.0:{}/v1/notification/read", port))
+        .json(&json!({ "ids": [body.notifications[0].id] }))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    assert_eq!(unread_count(&client, port).await?, 0);
+
+    let resp = client
+        .get(&format!(
+            "http://0.0.0.0:{}/v1/notification?unreadOnly=true",
+            port
+        ))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let body: NotificationListResponse = resp.json().await?;
+
+    assert_eq!(body.total_count, 0);
+
+    Ok(())
+}
+
+#[test_service(
+    setup = "setup_service",
+    fixtures("Fixture::User", "Fixture::Image", "Fixture::Circle")
+)]
+async fn preferences(port: u16) -> anyhow::Result<()> {
+    let client = reqwest::Client::new();
+
+    let resp = client
+        .get(&format!(
+            "http://0.0.0.0:{}/v1/notification/preferences",
+            port
+        ))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let preferences: NotificationPreferences = resp.json().await?;
+
+    assert_eq!(preferences, NotificationPreferences::default());
+
+    let preferences = NotificationPreferences {
+        circle_invite: false,
+        ..Default::default()
+    };
+
+    let resp = client
+        .put(&format!(
+            "http://0.0.0.0:{}/v1/notification/preferences",
+            port
+        ))
+        .json(&preferences)
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    let resp = client
+        .get(&format!(
+            "http://0.0.0.0:{}/v1/notification/preferences",
+            port
+        ))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let body: NotificationPreferences = resp.json().await?;
+
+    assert_eq!(body, preferences);
+
+    let resp = client
+        .post(&format!(
+            "http://0.0.0.0:{}/v1/circle/a3126bec-f185-11ec-b9e4-5fa4e257b5a1/invite/1f241e1b-b537-493f-a230-075cb16315be",
+            port
+        ))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    assert_eq!(unread_count(&client, port).await?, 0);
+
+    Ok(())
+}
@@ -11,6 +11,7 @@ use crate::{
 
 mod cover;
 mod curation;
+mod report;
 
 #[test_service(setup = "setup_service", fixtures("Fixture::User"))]
 async fn create_default(port: u16) -> anyhow::Result<()> {
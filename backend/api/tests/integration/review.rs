@@ -0,0 +1,291 @@
+use http::StatusCode;
+use ji_cloud_api::db::review;
+use macros::test_service;
+use serde_json::json;
+use shared::domain::{
+    asset::{ReportAction, ReportStatus},
+    jig::{JigId, JigResponse},
+    review::{ReviewId, ReviewListResponse, ReviewReportType},
+    user::UserId,
+    CreateResponse,
+};
+use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+use uuid::Uuid;
+
+use crate::{
+    fixture::Fixture,
+    helpers::{initialize_server_and_get_db, setup_service, LoginExt},
+};
+
+const JIG_ID: &str = "0cc084bc-7c83-11eb-9f77-e3218dffb008";
+
+async fn list_reviews(client: &reqwest::Client, port: u16) -> anyhow::Result<ReviewListResponse> {
+    let resp = client
+        .get(&format!("http://0.0.0.0:{}/v1/jig/{}/review", port, JIG_ID))
+        .send()
+        .await?
+        .error_for_status()?;
+
+    Ok(resp.json().await?)
+}
+
+#[test_service(
+    setup = "setup_service",
+    fixtures("Fixture::MetaKinds", "Fixture::User", "Fixture::Jig")
+)]
+async fn review_flow(port: u16) -> anyhow::Result<()> {
+    let client = reqwest::Client::new();
+
+    // the test user authored the jig
+    let resp = client
+        .put(&format!("http://0.0.0.0:{}/v1/jig/{}/review", port, JIG_ID))
+        .json(&json!({ "rating": 4 }))
+        .login()
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    let resp = client
+        .post(&format!("http://0.0.0.0:{}/v1/jig/admin/transfer", port))
+        .json(&json!({
+            "from": "1f241e1b-b537-493f-a230-075cb16315be",
+            "to": "7b96a41c-e406-11eb-8176-efd86dd7f444",
+            "jigIds": [JIG_ID],
+        }))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    let resp = client
+        .put(&format!("http://0.0.0.0:{}/v1/jig/{}/review", port, JIG_ID))
+        .json(&json!({ "rating": 6 }))
+        .login()
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let resp = client
+        .put(&format!("http://0.0.0.0:{}/v1/jig/{}/review", port, JIG_ID))
+        .json(&json!({ "rating": 4, "text": "  My class loved it  " }))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let CreateResponse { id } = resp.json::<CreateResponse<ReviewId>>().await?;
+
+    // editing keeps the review
+    let resp = client
+        .put(&format!("http://0.0.0.0:{}/v1/jig/{}/review", port, JIG_ID))
+        .json(&json!({ "rating": 2, "text": "Too hard for my class" }))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.json::<CreateResponse<ReviewId>>().await?.id, id);
+
+    let body = list_reviews(&client, port).await?;
+
+    assert_eq!(body.total_review_count, 1);
+    assert_eq!(body.average_rating, Some(2.0));
+    assert_eq!(body.reviews[0].id, id);
+    assert_eq!(body.reviews[0].rating, 2);
+    assert_eq!(body.reviews[0].text, "Too hard for my class");
+    assert_eq!(body.reviews[0].reply, None);
+
+    let resp = client
+        .get(&format!("http://0.0.0.0:{}/v1/jig/{}/live", port, JIG_ID))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let jig: JigResponse = resp.json().await?;
+
+    assert_eq!(jig.average_rating, Some(2.0));
+    assert_eq!(jig.review_count, 1);
+
+    // only the author of the jig can reply
+    let resp = client
+        .put(&format!("http://0.0.0.0:{}/v1/review/{}/reply", port, id.0))
+        .json(&json!({ "text": "Thanks!" }))
+        .login()
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    let resp = client
+        .post(&format!(
+            "http://0.0.0.0:{}/v1/review/{}/report",
+            port, id.0
+        ))
+        .json(&json!({ "reportType": "offTopic" }))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    let resp = client
+        .post(&format!(
+            "http://0.0.0.0:{}/v1/review/{}/report",
+            port, id.0
+        ))
+        .json(&json!({ "reportType": "spam" }))
+        .login()
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::CONFLICT);
+
+    let resp = client
+        .delete(&format!("http://0.0.0.0:{}/v1/review/{}", port, id.0))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    let body = list_reviews(&client, port).await?;
+
+    assert_eq!(body.total_review_count, 0);
+    assert_eq!(body.average_rating, None);
+
+    Ok(())
+}
+
+#[test_service(
+    setup = "setup_service",
+    fixtures("Fixture::MetaKinds", "Fixture::User", "Fixture::Jig")
+)]
+async fn not_reviewable(port: u16) -> anyhow::Result<()> {
+    let client = reqwest::Client::new();
+
+    let resp = client
+        .get(&format!(
+            "http://0.0.0.0:{}/v1/course/{}/review",
+            port, "3a6a3660-f3ec-11ec-b8ef-c3a8d6c3a9b7"
+        ))
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    // unknown jig
+    let resp = client
+        .get(&format!(
+            "http://0.0.0.0:{}/v1/jig/{}/review",
+            port, "6f8ba5be-2a96-11ee-9dd5-3b4e5bd54e6e"
+        ))
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn report_moderation(
+    pool_opts: PgPoolOptions,
+    conn_opts: PgConnectOptions,
+) -> anyhow::Result<()> {
+    let (_, db) = initialize_server_and_get_db(
+        &[Fixture::MetaKinds, Fixture::User, Fixture::Jig],
+        &[],
+        pool_opts,
+        conn_opts,
+    )
+    .await;
+
+    let columns = review::asset_columns(JigId(Uuid::parse_str(JIG_ID)?).into()).unwrap();
+    let reviewer_id = UserId(Uuid::parse_str("7b96a41c-e406-11eb-8176-efd86dd7f444")?);
+    let curator_id = UserId(Uuid::parse_str("1f241e1b-b537-493f-a230-075cb16315be")?);
+
+    // concurrent first reviews of the same asset end up as one review
+    let (first, second) = tokio::join!(
+        review::upsert(&db, reviewer_id, columns, 1, "Buy my course"),
+        review::upsert(&db, reviewer_id, columns, 1, "Buy my course now"),
+    );
+
+    let id = first?;
+    assert_eq!(second?, id);
+
+    let reporters: Vec<Uuid> = sqlx::query_scalar(
+        r#"insert into "user" (id) select uuid_generate_v1mc() from generate_series(1, 4) returning id"#,
+    )
+    .fetch_all(&db)
+    .await?;
+
+    for reporter_id in &reporters[..2] {
+        assert!(
+            review::report(&db, id, UserId(*reporter_id), ReviewReportType::Spam)
+                .await
+                .is_ok()
+        );
+    }
+
+    assert_eq!(review::list(&db, columns, 0, 10).await?.1, 1);
+
+    // hidden once enough reports are pending
+    assert!(
+        review::report(&db, id, UserId(reporters[2]), ReviewReportType::Spam)
+            .await
+            .is_ok()
+    );
+
+    let (reviews, count, average_rating) = review::list(&db, columns, 0, 10).await?;
+    assert!(reviews.is_empty());
+    assert_eq!(count, 0);
+    assert_eq!(average_rating, None);
+
+    let reports = review::list_reports(&db, Some(ReportStatus::Pending), 0, 10).await?;
+    assert_eq!(reports.len(), 3);
+    assert!(reports
+        .iter()
+        .all(|report| report.hidden && report.review.id == id));
+
+    // dismissing shows the review again
+    assert!(review::resolve_reports(&db, id, ReportAction::Dismiss, None, curator_id).await?);
+    assert!(!review::resolve_reports(&db, id, ReportAction::Dismiss, None, curator_id).await?);
+
+    assert_eq!(review::list(&db, columns, 0, 10).await?.1, 1);
+    assert_eq!(
+        review::filtered_report_count(&db, Some(ReportStatus::Pending)).await?,
+        0
+    );
+    assert_eq!(
+        review::filtered_report_count(&db, Some(ReportStatus::Resolved)).await?,
+        3
+    );
+
+    // blocking hides it for good
+    assert!(
+        review::report(&db, id, UserId(reporters[3]), ReviewReportType::Offensive)
+            .await
+            .is_ok()
+    );
+    assert!(
+        review::resolve_reports(
+            &db,
+            id,
+            ReportAction::Block,
+            Some("Advertising"),
+            curator_id
+        )
+        .await?
+    );
+
+    assert_eq!(review::list(&db, columns, 0, 10).await?.1, 0);
+
+    Ok(())
+}
@@ -0,0 +1,158 @@
+use http::StatusCode;
+use macros::test_service;
+use serde_json::json;
+use shared::domain::{
+    jig::player::JigPlayerSession,
+    roster::{Roster, RosterId},
+    CreateResponse,
+};
+use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+
+use crate::{
+    fixture::Fixture,
+    helpers::{setup_service, LoginExt},
+};
+
+#[test_service(
+    setup = "setup_service",
+    fixtures("Fixture::MetaKinds", "Fixture::User", "Fixture::Jig")
+)]
+async fn roster_session_flow(port: u16) -> anyhow::Result<()> {
+    let name = "roster_session_flow";
+    let client = reqwest::Client::new();
+
+    let resp = client
+        .post(&format!("http://0.0.0.0:{}/v1/roster", port))
+        .json(&json!({
+            "displayName": "Class 3B",
+            "students": [
+                { "nickname": "Sam" },
+                { "nickname": "Ari", "picturePassword": "moon" },
+            ],
+        }))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    let body: CreateResponse<RosterId> = resp.json().await?;
+
+    let roster_id = body.id;
+
+    let resp = client
+        .get(&format!(
+            "http://0.0.0.0:{}/v1/roster/{}",
+            port, roster_id.0
+        ))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let roster: Roster = resp.json().await?;
+
+    insta::assert_json_snapshot!(format!("{}-1", name), roster, {
+        ".id" => "[id]",
+        ".students[].id" => "[id]",
+        ".createdAt" => "[timestamp]",
+    });
+
+    let resp = client
+        .post(&format!(
+            "http://0.0.0.0:{}/v1/roster/{}/student",
+            port, roster_id.0
+        ))
+        .json(&json!({ "nickname": "Sam" }))
+        .login()
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let resp = client
+        .post(&format!("http://0.0.0.0:{}/v1/jig/player", port))
+        .json(&json!({
+            "jigId": "0cc084bc-7c83-11eb-9f77-e3218dffb008",
+            "settings": {},
+            "rosterId": roster_id,
+        }))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    let session: JigPlayerSession = resp.json().await?;
+
+    let resp = client
+        .get(&format!(
+            "http://0.0.0.0:{}/v1/jig/player/{}/roster",
+            port, session.index.0
+        ))
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let body: serde_json::Value = resp.json().await?;
+
+    insta::assert_json_snapshot!(format!("{}-2", name), body, {
+        ".students[].id" => "[id]",
+    });
+
+    let ari = roster
+        .students
+        .iter()
+        .find(|student| student.nickname == "Ari")
+        .unwrap()
+        .id;
+
+    let resp = client
+        .post(&format!("http://0.0.0.0:{}/v1/jig/player/instance", port))
+        .json(&json!({ "index": session.index }))
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let resp = client
+        .post(&format!("http://0.0.0.0:{}/v1/jig/player/instance", port))
+        .json(&json!({
+            "index": session.index,
+            "student": { "id": ari, "picturePassword": "sun" },
+        }))
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    let resp = client
+        .post(&format!("http://0.0.0.0:{}/v1/jig/player/instance", port))
+        .json(&json!({
+            "index": session.index,
+            "student": { "id": ari, "picturePassword": "moon" },
+        }))
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    let resp = client
+        .get(&format!(
+            "http://0.0.0.0:{}/v1/jig/player/{}/instances",
+            port, session.index.0
+        ))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let body: serde_json::Value = resp.json().await?;
+
+    assert_eq!(body["instances"][0]["displayName"], "Ari");
+    assert_eq!(body["instances"][0]["studentId"], json!(ari));
+
+    Ok(())
+}
@@ -0,0 +1,219 @@
+use http::StatusCode;
+use macros::test_service;
+use serde_json::json;
+use shared::domain::{billing::SchoolSsoConfig, session::GetOAuthUrlResponse};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpListener,
+};
+
+use crate::{
+    fixture::Fixture,
+    helpers::{setup_service, LoginExt},
+};
+
+const SCHOOL_ID: &str = "0a1f6b3e-2ea0-11ee-9e3b-1b7d2c4e6f01";
+
+const OTHER_SCHOOL_ID: &str = "0a1f6d96-2ea0-11ee-9e3b-4f3a8e9b2c02";
+
+/// Serves the OpenID Connect discovery document of an identity provider at the listener's address,
+/// returning its issuer.
+async fn serve_discovery() -> anyhow::Result<String> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let issuer = format!("http://127.0.0.1:{}", listener.local_addr()?.port());
+
+    let body = json!({
+        "issuer": issuer,
+        "authorization_endpoint": format!("{}/authorize", issuer),
+        "token_endpoint": format!("{}/token", issuer),
+        "jwks_uri": format!("{}/keys", issuer),
+    })
+    .to_string();
+
+    tokio::spawn(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            let mut request = Vec::new();
+            let mut buf = [0_u8; 4096];
+
+            while !String::from_utf8_lossy(&request).contains("\r\n\r\n") {
+                match stream.read(&mut buf).await {
+                    Ok(0) | Err(_) => break,
+                    Ok(read) => request.extend_from_slice(&buf[..read]),
+                }
+            }
+
+            let response = format!(
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+
+            let _ = stream.write_all(response.as_bytes()).await;
+        }
+    });
+
+    Ok(issuer)
+}
+
+#[test_service(
+    setup = "setup_service",
+    fixtures("Fixture::User", "Fixture::SchoolSso")
+)]
+async fn upsert(port: u16) -> anyhow::Result<()> {
+    let issuer = serve_discovery().await?;
+
+    let client = reqwest::Client::new();
+
+    let resp = client
+        .put(&format!(
+            "http://0.0.0.0:{}/v1/school/{}/sso",
+            port, OTHER_SCHOOL_ID
+        ))
+        .json(&json!({
+            "issuer": issuer,
+            "client_id": "jigzi",
+            "client_secret": "secret",
+            "email_domains": ["@Other.edu"],
+        }))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    let resp = client
+        .get(&format!(
+            "http://0.0.0.0:{}/v1/school/{}/sso",
+            port, OTHER_SCHOOL_ID
+        ))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let sso: SchoolSsoConfig = resp.json().await?;
+
+    assert_eq!(sso.issuer, issuer);
+    assert_eq!(sso.email_domains.len(), 1);
+    assert_eq!(sso.email_domains[0].domain, "other.edu");
+    assert!(!sso.email_domains[0].verified);
+    assert!(sso
+        .verification_record
+        .starts_with("jigzi-sso-verification="));
+
+    Ok(())
+}
+
+#[test_service(
+    setup = "setup_service",
+    fixtures("Fixture::User", "Fixture::SchoolSso")
+)]
+async fn upsert_public_domain(port: u16) -> anyhow::Result<()> {
+    let issuer = serve_discovery().await?;
+
+    let client = reqwest::Client::new();
+
+    let resp = client
+        .put(&format!(
+            "http://0.0.0.0:{}/v1/school/{}/sso",
+            port, OTHER_SCHOOL_ID
+        ))
+        .json(&json!({
+            "issuer": issuer,
+            "client_id": "jigzi",
+            "client_secret": "secret",
+            "email_domains": ["gmail.com"],
+        }))
+        .login()
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
+
+#[test_service(
+    setup = "setup_service",
+    fixtures("Fixture::User", "Fixture::SchoolSso")
+)]
+async fn upsert_verified_elsewhere(port: u16) -> anyhow::Result<()> {
+    let issuer = serve_discovery().await?;
+
+    let client = reqwest::Client::new();
+
+    let resp = client
+        .put(&format!(
+            "http://0.0.0.0:{}/v1/school/{}/sso",
+            port, OTHER_SCHOOL_ID
+        ))
+        .json(&json!({
+            "issuer": issuer,
+            "client_id": "jigzi",
+            "client_secret": "secret",
+            "email_domains": ["other.edu", "school.edu"],
+        }))
+        .login()
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::CONFLICT);
+
+    Ok(())
+}
+
+#[test_service(
+    setup = "setup_service",
+    fixtures("Fixture::User", "Fixture::SchoolSso")
+)]
+async fn sso_url(port: u16) -> anyhow::Result<()> {
+    let issuer = serve_discovery().await?;
+
+    let client = reqwest::Client::new();
+
+    // the school's domains stay verified when it's updated
+    let resp = client
+        .put(&format!(
+            "http://0.0.0.0:{}/v1/school/{}/sso",
+            port, SCHOOL_ID
+        ))
+        .json(&json!({
+            "issuer": issuer,
+            "client_id": "jigzi",
+            "email_domains": ["school.edu", "staff.school.edu"],
+        }))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    let resp = client
+        .get(&format!("http://0.0.0.0:{}/v1/session/sso/url/login", port))
+        .query(&[("email", "teacher@School.edu")])
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let body: GetOAuthUrlResponse = resp.json().await?;
+
+    let url = url::Url::parse(&body.url)?;
+    let query: Vec<(String, String)> = url.query_pairs().into_owned().collect();
+
+    assert!(body.url.starts_with(&format!("{}/authorize?", issuer)));
+    assert!(query.contains(&("client_id".to_owned(), "jigzi".to_owned())));
+    assert!(query.contains(&("state".to_owned(), format!("sso:{}", SCHOOL_ID))));
+
+    // the domain isn't verified yet
+    let resp = client
+        .get(&format!("http://0.0.0.0:{}/v1/session/sso/url/login", port))
+        .query(&[("email", "teacher@staff.school.edu")])
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    Ok(())
+}
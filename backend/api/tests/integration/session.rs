@@ -1,8 +1,12 @@
 use http::StatusCode;
 use macros::test_service;
+use shared::domain::session::ListActiveSessionsResponse;
 use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
 
-use crate::{fixture::Fixture, helpers::setup_service};
+use crate::{
+    fixture::Fixture,
+    helpers::{setup_service, LoginExt},
+};
 
 #[test_service(setup = "setup_service", fixtures(""))]
 async fn create_401_no_auth(port: u16) -> anyhow::Result<()> {
@@ -53,3 +57,132 @@ async fn create_basic_bad_password(port: u16) -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test_service(setup = "setup_service", fixtures("Fixture::User"))]
+async fn create_basic_rate_limited(port: u16) -> anyhow::Result<()> {
+    let client = reqwest::Client::new();
+
+    // the quota per user is 10 attempts
+    for _ in 0..10 {
+        let resp = client
+            .post(&format!("http://0.0.0.0:{}/v1/session", port))
+            .basic_auth("test@test.test", Some("wrongPAssword"))
+            .send()
+            .await?;
+
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    let resp = client
+        .post(&format!("http://0.0.0.0:{}/v1/session", port))
+        .basic_auth("test@test.test", Some("password1"))
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert!(resp.headers().contains_key("retry-after"));
+
+    // other users can still sign in
+    let resp = client
+        .post(&format!("http://0.0.0.0:{}/v1/session", port))
+        .basic_auth("fooodoosfakeemail@testing238EE.test", Some("wrongPAssword"))
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+
+    Ok(())
+}
+
+// admins have to set up two-factor authentication when signing in, so use a user without scopes
+#[test_service(setup = "setup_service", fixtures("Fixture::UserNoPerms"))]
+async fn active_sessions(port: u16) -> anyhow::Result<()> {
+    let name = "active_sessions";
+
+    let client = reqwest::Client::new();
+
+    // sign in on another computer
+    let resp = client
+        .post(&format!("http://0.0.0.0:{}/v1/session", port))
+        .basic_auth("test@test.test", Some("password1"))
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    let resp = client
+        .get(&format!("http://0.0.0.0:{}/v1/session/active", port))
+        .header("User-Agent", "Mozilla/5.0 (X11; Linux x86_64)")
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let body: serde_json::Value = resp.json().await?;
+
+    insta::assert_json_snapshot!(format!("{}-1", name), body, {
+        ".sessions[].id" => "[id]",
+        ".sessions[].createdAt" => "[timestamp]",
+        ".sessions[0].expiresAt" => "[timestamp]",
+        ".sessions[1].lastUsedAt" => "[timestamp]",
+    });
+
+    let other_id = body["sessions"][0]["id"]
+        .as_str()
+        .expect("session id wasn't a string")
+        .to_owned();
+
+    let resp = client
+        .delete(&format!(
+            "http://0.0.0.0:{}/v1/session/active/{}",
+            port, other_id
+        ))
+        .login()
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    let resp = client
+        .delete(&format!(
+            "http://0.0.0.0:{}/v1/session/active/{}",
+            port, other_id
+        ))
+        .login()
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    // sign in twice more, then sign out everywhere else
+    for _ in 0..2 {
+        client
+            .post(&format!("http://0.0.0.0:{}/v1/session", port))
+            .basic_auth("test@test.test", Some("password1"))
+            .send()
+            .await?
+            .error_for_status()?;
+    }
+
+    let resp = client
+        .delete(&format!("http://0.0.0.0:{}/v1/session/active", port))
+        .login()
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    let body: ListActiveSessionsResponse = client
+        .get(&format!("http://0.0.0.0:{}/v1/session/active", port))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    assert_eq!(body.sessions.len(), 1);
+    assert!(body.sessions[0].current);
+
+    Ok(())
+}
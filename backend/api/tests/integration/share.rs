@@ -0,0 +1,155 @@
+use http::StatusCode;
+use macros::test_service;
+use serde_json::json;
+use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+
+use crate::{
+    fixture::Fixture,
+    helpers::{setup_service, LoginExt},
+};
+
+/// Fetches a live asset the way the pages server does for link previews, i.e. without logging in,
+/// and keeps the fields it renders.
+async fn share_preview(port: u16, asset_type: &str, id: &str) -> anyhow::Result<serde_json::Value> {
+    let body: serde_json::Value = reqwest::Client::new()
+        .get(&format!(
+            "http://0.0.0.0:{}/v1/{}/{}/live",
+            port, asset_type, id
+        ))
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    let data = &body[format!("{}Data", asset_type)];
+
+    // jigs are previewed with their first module, other assets with their cover
+    let cover = match asset_type {
+        "jig" => &data["modules"][0]["id"],
+        _ => &data["cover"]["id"],
+    };
+
+    Ok(json!({
+        "publishedAt": body["publishedAt"],
+        "authorName": body["authorName"],
+        "displayName": data["displayName"],
+        "description": data["description"],
+        "privacyLevel": data["privacyLevel"],
+        "cover": cover,
+    }))
+}
+
+#[test_service(
+    setup = "setup_service",
+    fixtures("Fixture::MetaKinds", "Fixture::User", "Fixture::Jig")
+)]
+async fn share_preview_jig(port: u16) -> anyhow::Result<()> {
+    let name = "share_preview_jig";
+
+    let body = share_preview(port, "jig", "0cc084bc-7c83-11eb-9f77-e3218dffb008").await?;
+
+    insta::assert_json_snapshot!(format!("{}", name), body);
+
+    Ok(())
+}
+
+#[test_service(
+    setup = "setup_service",
+    fixtures("Fixture::MetaKinds", "Fixture::User", "Fixture::Resource")
+)]
+async fn share_preview_resource(port: u16) -> anyhow::Result<()> {
+    let name = "share_preview_resource";
+
+    let body = share_preview(port, "resource", "d8067526-1518-11ed-87fa-ebaf880b6d9c").await?;
+
+    insta::assert_json_snapshot!(format!("{}", name), body);
+
+    Ok(())
+}
+
+#[test_service(
+    setup = "setup_service",
+    fixtures(
+        "Fixture::MetaKinds",
+        "Fixture::User",
+        "Fixture::Jig",
+        "Fixture::Playlist"
+    )
+)]
+async fn share_preview_playlist(port: u16) -> anyhow::Result<()> {
+    let name = "share_preview_playlist";
+
+    let body = share_preview(port, "playlist", "3a6a3660-f3ec-11ec-b8ef-071747fa2a0d").await?;
+
+    insta::assert_json_snapshot!(format!("{}", name), body);
+
+    Ok(())
+}
+
+#[test_service(
+    setup = "setup_service",
+    fixtures("Fixture::MetaKinds", "Fixture::User", "Fixture::Course")
+)]
+async fn share_preview_course(port: u16) -> anyhow::Result<()> {
+    let name = "share_preview_course";
+
+    let body = share_preview(port, "course", "f77222a6-906b-11ed-b4f6-2f6dfab2ea0a").await?;
+
+    insta::assert_json_snapshot!(format!("{}", name), body);
+
+    Ok(())
+}
+
+#[test_service(
+    setup = "setup_service",
+    fixtures(
+        "Fixture::MetaKinds",
+        "Fixture::User",
+        "Fixture::Jig",
+        "Fixture::Playlist"
+    )
+)]
+async fn oembed_unlisted_playlist(port: u16) -> anyhow::Result<()> {
+    let name = "oembed_unlisted_playlist";
+
+    let client = reqwest::Client::new();
+
+    let playlist_id = "3a6a3660-f3ec-11ec-b8ef-071747fa2a0d";
+
+    // oEmbed is only provided for public assets, which it tells apart by the live privacy level
+    let resp = client
+        .patch(&format!(
+            "http://0.0.0.0:{}/v1/playlist/{}",
+            port, playlist_id
+        ))
+        .json(&json!({ "privacyLevel": "unlisted" }))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    client
+        .put(&format!(
+            "http://0.0.0.0:{}/v1/playlist/{}/draft/publish",
+            port, playlist_id
+        ))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let body = share_preview(port, "playlist", playlist_id).await?;
+
+    insta::assert_json_snapshot!(
+        format!("{}", name),
+        body, {
+            ".publishedAt" => "[published_at]",
+            ".cover" => "[id]"
+        }
+    );
+
+    Ok(())
+}
@@ -0,0 +1,83 @@
+use macros::test_service;
+use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+
+use crate::{fixture::Fixture, helpers::setup_service};
+
+#[test_service(
+    setup = "setup_service",
+    fixtures(
+        "Fixture::MetaKinds",
+        "Fixture::User",
+        "Fixture::Image",
+        "Fixture::Jig",
+        "Fixture::Playlist",
+        "Fixture::Resource",
+        "Fixture::Course",
+        "Fixture::Circle"
+    )
+)]
+async fn sitemap_index(port: u16) -> anyhow::Result<()> {
+    let name = "sitemap_index";
+
+    let resp = reqwest::get(&format!("http://0.0.0.0:{}/v1/sitemap", port))
+        .await?
+        .error_for_status()?;
+
+    let body: serde_json::Value = resp.json().await?;
+
+    insta::assert_json_snapshot!(
+        format!("{}", name),
+        body, {
+            ".**.lastEdited" => "[last_edited]"
+        }
+    );
+
+    Ok(())
+}
+
+#[test_service(
+    setup = "setup_service",
+    fixtures(
+        "Fixture::MetaKinds",
+        "Fixture::User",
+        "Fixture::Jig",
+        "Fixture::Resource"
+    )
+)]
+async fn sitemap_page_resource(port: u16) -> anyhow::Result<()> {
+    // resources don't have a page of their own, so they aren't listed
+    let resp = reqwest::get(&format!("http://0.0.0.0:{}/v1/sitemap/resource/0", port)).await?;
+
+    assert_eq!(resp.status(), http::StatusCode::NOT_FOUND);
+
+    Ok(())
+}
+
+#[test_service(
+    setup = "setup_service",
+    fixtures(
+        "Fixture::MetaKinds",
+        "Fixture::User",
+        "Fixture::Jig",
+        "Fixture::Resource"
+    )
+)]
+async fn sitemap_page_user(port: u16) -> anyhow::Result<()> {
+    let name = "sitemap_page_user";
+
+    // only users who authored published public content are listed
+    let resp = reqwest::get(&format!("http://0.0.0.0:{}/v1/sitemap/user/0", port))
+        .await?
+        .error_for_status()?;
+
+    let body: serde_json::Value = resp.json().await?;
+
+    insta::assert_json_snapshot!(
+        format!("{}", name),
+        body, {
+            ".**.lastEdited" => "[last_edited]"
+        }
+    );
+
+    Ok(())
+}
@@ -8,9 +8,11 @@ use macros::test_service;
 use shared::domain::{meta::AffiliationId, user::PatchProfileRequest};
 use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
 
+mod api_token;
 mod color;
 mod font;
 mod public_user;
+mod totp;
 
 #[test_service(setup = "setup_service", fixtures("Fixture::User"))]
 async fn get_profile(port: u16) -> anyhow::Result<()> {
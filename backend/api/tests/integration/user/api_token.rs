@@ -0,0 +1,239 @@
+use http::StatusCode;
+use macros::test_service;
+use serde_json::json;
+
+use crate::{
+    fixture::Fixture,
+    helpers::{setup_service, LoginExt},
+};
+
+/// Creates an API token for the signed in user, returning its id and secret.
+async fn create_token(
+    client: &reqwest::Client,
+    port: u16,
+    body: serde_json::Value,
+) -> anyhow::Result<(String, String)> {
+    let resp = client
+        .post(&format!("http://0.0.0.0:{}/v1/user/me/api-token", port))
+        .login()
+        .json(&body)
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    let body: serde_json::Value = resp.json().await?;
+
+    let id = body["id"].as_str().expect("id wasn't a string").to_owned();
+    let token = body["token"]
+        .as_str()
+        .expect("token wasn't a string")
+        .to_owned();
+
+    assert!(token.starts_with("jpat_"));
+
+    Ok((id, token))
+}
+
+#[test_service(setup = "setup_service", fixtures("Fixture::User"))]
+async fn create_use_and_revoke(port: u16) -> anyhow::Result<()> {
+    let client = reqwest::Client::new();
+
+    let (id, token) = create_token(
+        &client,
+        port,
+        json!({ "name": "batch-user-jigs", "readOnly": false }),
+    )
+    .await?;
+
+    let resp = client
+        .get(&format!("http://0.0.0.0:{}/v1/user/me/profile", port))
+        .bearer_auth(&token)
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    // the user is an admin, but the token wasn't created with the scope
+    let resp = client
+        .get(&format!("http://0.0.0.0:{}/v1/user/browse", port))
+        .bearer_auth(&token)
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    let resp = client
+        .post(&format!("http://0.0.0.0:{}/v1/user/me/api-token", port))
+        .bearer_auth(&token)
+        .json(&json!({ "name": "another", "readOnly": false }))
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    let body: serde_json::Value = client
+        .get(&format!("http://0.0.0.0:{}/v1/user/me/api-token", port))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    let tokens = body["tokens"].as_array().expect("tokens wasn't an array");
+
+    assert_eq!(tokens.len(), 1);
+    assert_eq!(tokens[0]["id"], id.as_str());
+    assert_eq!(tokens[0]["name"], "batch-user-jigs");
+    assert!(!tokens[0]["lastUsedAt"].is_null());
+
+    let resp = client
+        .delete(&format!(
+            "http://0.0.0.0:{}/v1/user/me/api-token/{}",
+            port, id
+        ))
+        .login()
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    let resp = client
+        .get(&format!("http://0.0.0.0:{}/v1/user/me/profile", port))
+        .bearer_auth(&token)
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+
+    Ok(())
+}
+
+#[test_service(setup = "setup_service", fixtures("Fixture::User"))]
+async fn read_only_scoped(port: u16) -> anyhow::Result<()> {
+    let client = reqwest::Client::new();
+
+    let (_, token) = create_token(
+        &client,
+        port,
+        json!({ "name": "reports", "scopes": ["Admin"], "readOnly": true }),
+    )
+    .await?;
+
+    let resp = client
+        .get(&format!("http://0.0.0.0:{}/v1/user/browse", port))
+        .bearer_auth(&token)
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let resp = client
+        .patch(&format!("http://0.0.0.0:{}/v1/user/me/profile", port))
+        .bearer_auth(&token)
+        .json(&json!({ "username": "renamed" }))
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    Ok(())
+}
+
+#[test_service(setup = "setup_service", fixtures("Fixture::UserNoPerms"))]
+async fn create_invalid(port: u16) -> anyhow::Result<()> {
+    let client = reqwest::Client::new();
+
+    let resp = client
+        .post(&format!("http://0.0.0.0:{}/v1/user/me/api-token", port))
+        .login()
+        .json(&json!({ "name": " ", "readOnly": true }))
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    // the user doesn't have any scopes
+    let resp = client
+        .post(&format!("http://0.0.0.0:{}/v1/user/me/api-token", port))
+        .login()
+        .json(&json!({ "name": "categories", "scopes": ["ManageCategory"], "readOnly": false }))
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    Ok(())
+}
+
+#[test_service(
+    setup = "setup_service",
+    fixtures("Fixture::MetaKinds", "Fixture::User", "Fixture::Jig")
+)]
+async fn scopeless_jig_writes(port: u16) -> anyhow::Result<()> {
+    let client = reqwest::Client::new();
+
+    // the user is an admin, but the token wasn't created with any scopes
+    let (_, token) = create_token(
+        &client,
+        port,
+        json!({ "name": "batch-user-jigs", "readOnly": false }),
+    )
+    .await?;
+
+    let resp = client
+        .post(&format!("http://0.0.0.0:{}/v1/jig", port))
+        .bearer_auth(&token)
+        .json(&json!({}))
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    let resp = client
+        .patch(&format!(
+            "http://0.0.0.0:{}/v1/jig/0cc084bc-7c83-11eb-9f77-e3218dffb008",
+            port
+        ))
+        .bearer_auth(&token)
+        .json(&json!({ "displayName": "renamed" }))
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    // endpoints which don't limit the user's scopes to the token's don't take API tokens at all
+    let resp = client
+        .delete(&format!(
+            "http://0.0.0.0:{}/v1/jig/0cc084bc-7c83-11eb-9f77-e3218dffb008",
+            port
+        ))
+        .bearer_auth(&token)
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    let (_, token) = create_token(
+        &client,
+        port,
+        json!({ "name": "batch-user-jigs", "scopes": ["Admin"], "readOnly": false }),
+    )
+    .await?;
+
+    let resp = client
+        .patch(&format!(
+            "http://0.0.0.0:{}/v1/jig/0cc084bc-7c83-11eb-9f77-e3218dffb008",
+            port
+        ))
+        .bearer_auth(&token)
+        .json(&json!({ "displayName": "renamed" }))
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    Ok(())
+}
@@ -1,5 +1,6 @@
 use http::StatusCode;
 use macros::test_service;
+use shared::domain::user::public_user::{UserFeedActivity, UserFeedResponse};
 use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
 
 use crate::{
@@ -415,3 +416,73 @@ async fn follow_self_error(port: u16) -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test_service(
+    setup = "setup_service",
+    fixtures(
+        "Fixture::User",
+        "Fixture::Image",
+        "Fixture::PublicUser",
+        "Fixture::Circle"
+    )
+)]
+async fn feed(port: u16) -> anyhow::Result<()> {
+    let client = reqwest::Client::new();
+
+    let resp = client
+        .get(&format!(
+            "http://0.0.0.0:{}/v1/user/me/feed?pageLimit=3",
+            port
+        ))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let first: UserFeedResponse = resp.json().await?;
+
+    assert_eq!(first.items.len(), 3);
+
+    let cursor = first.next_cursor.expect("there should be a second page");
+
+    let resp = client
+        .get(&format!("http://0.0.0.0:{}/v1/user/me/feed", port))
+        .query(&[("pageLimit", "3"), ("cursor", &cursor)])
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let second: UserFeedResponse = resp.json().await?;
+
+    assert_eq!(second.next_cursor, None);
+
+    // the followed users joined 4 circles at the same time, none should be skipped or repeated
+    let mut joined: Vec<_> = first
+        .items
+        .iter()
+        .chain(&second.items)
+        .map(|item| match item.activity {
+            UserFeedActivity::JoinedCircle(circle_id) => (item.user_id.0, circle_id.0),
+            activity => panic!("unexpected activity {:?}", activity),
+        })
+        .collect();
+
+    joined.sort();
+    joined.dedup();
+
+    assert_eq!(joined.len(), 4);
+
+    let resp = client
+        .get(&format!(
+            "http://0.0.0.0:{}/v1/user/me/feed?cursor=nope",
+            port
+        ))
+        .login()
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
@@ -0,0 +1,284 @@
+use chrono::Utc;
+use http::StatusCode;
+use ji_cloud_api::totp;
+use macros::test_service;
+use serde_json::json;
+use shared::domain::session::AUTH_COOKIE_NAME;
+use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+
+use crate::{
+    fixture::Fixture,
+    helpers::{setup_service, LoginExt},
+};
+
+/// Signs in with basic auth, returning the response body and the session token.
+async fn sign_in(
+    client: &reqwest::Client,
+    port: u16,
+) -> anyhow::Result<(serde_json::Value, String)> {
+    let resp = client
+        .post(&format!("http://0.0.0.0:{}/v1/session", port))
+        .basic_auth("test@test.test", Some("password1"))
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    session_from(resp).await
+}
+
+async fn session_from(resp: reqwest::Response) -> anyhow::Result<(serde_json::Value, String)> {
+    let token = resp
+        .cookies()
+        .find(|cookie| cookie.name() == AUTH_COOKIE_NAME)
+        .expect("missing auth cookie")
+        .value()
+        .to_owned();
+
+    Ok((resp.json().await?, token))
+}
+
+#[test_service(setup = "setup_service", fixtures("Fixture::User"))]
+async fn enroll_and_sign_in(port: u16) -> anyhow::Result<()> {
+    let client = reqwest::Client::new();
+
+    // admins have to set up two-factor authentication before they get a standard session
+    let (body, token) = sign_in(&client, port).await?;
+
+    assert_eq!(body["twoFactor"]["enrolled"], false);
+
+    let resp = client
+        .get(&format!("http://0.0.0.0:{}/v1/user/me/profile", port))
+        .bearer_auth(&token)
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+
+    let body: serde_json::Value = client
+        .post(&format!("http://0.0.0.0:{}/v1/user/me/totp", port))
+        .bearer_auth(&token)
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    let secret = body["secret"].as_str().expect("secret wasn't a string");
+    let secret = totp::base32_decode(secret).expect("secret wasn't base32");
+
+    assert!(body["provisioningUri"]
+        .as_str()
+        .expect("provisioningUri wasn't a string")
+        .starts_with("otpauth://totp/Jigzi%3Atest%40test%2Etest?secret="));
+
+    let resp = client
+        .post(&format!("http://0.0.0.0:{}/v1/user/me/totp/confirm", port))
+        .bearer_auth(&token)
+        .json(&json!({ "code": "abcdef" }))
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let code = totp::generate(&secret, Utc::now());
+
+    let resp = client
+        .post(&format!("http://0.0.0.0:{}/v1/user/me/totp/confirm", port))
+        .bearer_auth(&token)
+        .json(&json!({ "code": code }))
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let (body, token) = session_from(resp).await?;
+
+    let recovery_codes = body["recoveryCodes"]
+        .as_array()
+        .expect("recoveryCodes wasn't an array");
+
+    assert_eq!(recovery_codes.len(), totp::RECOVERY_CODE_COUNT);
+
+    let body: serde_json::Value = client
+        .get(&format!("http://0.0.0.0:{}/v1/user/me/totp", port))
+        .bearer_auth(&token)
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    assert_eq!(
+        body,
+        json!({ "enabled": true, "required": true, "recoveryCodesLeft": 10 })
+    );
+
+    // codes can't be used twice
+    let resp = client
+        .post(&format!(
+            "http://0.0.0.0:{}/v1/user/me/totp/recovery-codes",
+            port
+        ))
+        .bearer_auth(&token)
+        .json(&json!({ "code": code }))
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    // it's mandatory for admins
+    let resp = client
+        .delete(&format!("http://0.0.0.0:{}/v1/user/me/totp", port))
+        .bearer_auth(&token)
+        .json(&json!({ "code": recovery_codes[0] }))
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    // signing in again asks for a code
+    let (body, pending_token) = sign_in(&client, port).await?;
+
+    assert_eq!(body["twoFactor"]["enrolled"], true);
+
+    let resp = client
+        .post(&format!("http://0.0.0.0:{}/v1/session/totp", port))
+        .bearer_auth(&pending_token)
+        .json(&json!({ "code": "abcde-fghij" }))
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+
+    let resp = client
+        .post(&format!("http://0.0.0.0:{}/v1/session/totp", port))
+        .bearer_auth(&pending_token)
+        .json(&json!({ "code": recovery_codes[1] }))
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    let (_, token) = session_from(resp).await?;
+
+    let body: serde_json::Value = client
+        .get(&format!("http://0.0.0.0:{}/v1/user/me/totp", port))
+        .bearer_auth(&token)
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    assert_eq!(body["recoveryCodesLeft"], 9);
+
+    // the pending session was replaced
+    let resp = client
+        .post(&format!("http://0.0.0.0:{}/v1/session/totp", port))
+        .bearer_auth(&pending_token)
+        .json(&json!({ "code": recovery_codes[2] }))
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+
+    Ok(())
+}
+
+#[test_service(setup = "setup_service", fixtures("Fixture::User"))]
+async fn enroll_with_standard_session(port: u16) -> anyhow::Result<()> {
+    let client = reqwest::Client::new();
+
+    let resp = client
+        .post(&format!("http://0.0.0.0:{}/v1/user/me/totp", port))
+        .login()
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    Ok(())
+}
+
+#[test_service(
+    setup = "setup_service",
+    fixtures("Fixture::User", "Fixture::SessionEnrollTotp")
+)]
+async fn enroll_with_pending_session(port: u16) -> anyhow::Result<()> {
+    let client = reqwest::Client::new();
+
+    // the session is only good for setting up two-factor authentication
+    let resp = client
+        .get(&format!("http://0.0.0.0:{}/v1/user/me/profile", port))
+        .login()
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+
+    let resp = client
+        .post(&format!("http://0.0.0.0:{}/v1/user/me/totp", port))
+        .login()
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    Ok(())
+}
+
+#[test_service(setup = "setup_service", fixtures("Fixture::User"))]
+async fn sign_in_attempts_are_limited(port: u16) -> anyhow::Result<()> {
+    let client = reqwest::Client::new();
+
+    let (_, token) = sign_in(&client, port).await?;
+
+    let body: serde_json::Value = client
+        .post(&format!("http://0.0.0.0:{}/v1/user/me/totp", port))
+        .bearer_auth(&token)
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    let secret = totp::base32_decode(body["secret"].as_str().expect("secret wasn't a string"))
+        .expect("secret wasn't base32");
+
+    client
+        .post(&format!("http://0.0.0.0:{}/v1/user/me/totp/confirm", port))
+        .bearer_auth(&token)
+        .json(&json!({ "code": totp::generate(&secret, Utc::now()) }))
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let (_, pending_token) = sign_in(&client, port).await?;
+
+    for _ in 0..5 {
+        let resp = client
+            .post(&format!("http://0.0.0.0:{}/v1/session/totp", port))
+            .bearer_auth(&pending_token)
+            .json(&json!({ "code": "abcdef" }))
+            .send()
+            .await?;
+
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    // the session is gone, so even a correct code doesn't help anymore
+    let resp = client
+        .post(&format!("http://0.0.0.0:{}/v1/session/totp", port))
+        .bearer_auth(&pending_token)
+        .json(
+            &json!({ "code": totp::generate(&secret, Utc::now() + chrono::Duration::seconds(30)) }),
+        )
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+
+    Ok(())
+}
@@ -0,0 +1,240 @@
+use std::time::Duration;
+
+use http::StatusCode;
+use ji_cloud_api::db::webhook;
+use macros::test_service;
+use serde_json::json;
+use shared::domain::webhook::{
+    WebhookCreateResponse, WebhookDeliveryListResponse, WebhookDeliveryStatus, WebhookEvent,
+    WebhookListResponse,
+};
+use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpListener,
+    sync::oneshot,
+};
+use uuid::Uuid;
+
+use crate::{
+    fixture::Fixture,
+    helpers::{initialize_server_and_get_db, setup_service, LoginExt},
+};
+
+/// Accepts a single request on `listener`, responds with `200 OK` and sends the raw request.
+async fn receive_one(listener: TcpListener, tx: oneshot::Sender<String>) -> anyhow::Result<()> {
+    let (mut stream, _) = listener.accept().await?;
+
+    let mut request = Vec::new();
+    let mut buf = [0_u8; 4096];
+
+    loop {
+        let read = stream.read(&mut buf).await?;
+        if read == 0 {
+            break;
+        }
+
+        request.extend_from_slice(&buf[..read]);
+
+        let text = String::from_utf8_lossy(&request);
+        if let Some(header_end) = text.find("\r\n\r\n") {
+            let content_length = text[..header_end]
+                .lines()
+                .find_map(|line| {
+                    let (name, value) = line.split_once(':')?;
+                    name.eq_ignore_ascii_case("content-length")
+                        .then(|| value.trim().parse::<usize>().ok())?
+                })
+                .unwrap_or_default();
+
+            if request.len() >= header_end + 4 + content_length {
+                break;
+            }
+        }
+    }
+
+    stream
+        .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\nconnection: close\r\n\r\n")
+        .await?;
+
+    let _ = tx.send(String::from_utf8_lossy(&request).into_owned());
+
+    Ok(())
+}
+
+#[test_service(
+    setup = "setup_service",
+    fixtures("Fixture::MetaKinds", "Fixture::User", "Fixture::Jig")
+)]
+async fn deliver_jig_published(port: u16) -> anyhow::Result<()> {
+    let client = reqwest::Client::new();
+
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let receiver_port = listener.local_addr()?.port();
+    let (tx, rx) = oneshot::channel();
+    tokio::spawn(receive_one(listener, tx));
+
+    let resp = client
+        .post(&format!("http://0.0.0.0:{}/v1/webhook", port))
+        .json(&json!({
+            "url": format!("http://127.0.0.1:{}/hook", receiver_port),
+            "events": ["jigPublished", "jigPublished"],
+        }))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    let created: WebhookCreateResponse = resp.json().await?;
+
+    assert!(created.secret.starts_with("whsec_"));
+
+    let resp = client
+        .get(&format!("http://0.0.0.0:{}/v1/webhook", port))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let list: WebhookListResponse = resp.json().await?;
+
+    assert_eq!(list.webhooks.len(), 1);
+    assert_eq!(list.webhooks[0].id, created.id);
+    assert_eq!(list.webhooks[0].events, vec![WebhookEvent::JigPublished]);
+
+    let resp = client
+        .post(&format!(
+            "http://0.0.0.0:{}/v1/jig/19becb2b-bff7-4c1b-bb2c-16f2e098d3d3/draft/publish",
+            port
+        ))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    let request = tokio::time::timeout(Duration::from_secs(10), rx).await??;
+    let request_lowercase = request.to_lowercase();
+
+    assert!(request.starts_with("POST /hook "));
+    assert!(request_lowercase.contains("x-jigzi-event: jigpublished"));
+    assert!(request_lowercase.contains("x-jigzi-signature: t="));
+    assert!(request.contains("19becb2b-bff7-4c1b-bb2c-16f2e098d3d3"));
+
+    // the delivery is recorded right after the response
+    tokio::time::sleep(Duration::from_millis(500)).await;
+
+    let resp = client
+        .get(&format!(
+            "http://0.0.0.0:{}/v1/webhook/{}/delivery",
+            port, created.id.0
+        ))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let deliveries: WebhookDeliveryListResponse = resp.json().await?;
+
+    assert_eq!(deliveries.deliveries.len(), 1);
+    assert_eq!(deliveries.deliveries[0].event, WebhookEvent::JigPublished);
+    assert_eq!(
+        deliveries.deliveries[0].status,
+        WebhookDeliveryStatus::Delivered
+    );
+    assert_eq!(deliveries.deliveries[0].attempts, 1);
+    assert_eq!(deliveries.deliveries[0].response_status, Some(200));
+
+    Ok(())
+}
+
+#[test_service(setup = "setup_service", fixtures("Fixture::User"))]
+async fn create_invalid(port: u16) -> anyhow::Result<()> {
+    let client = reqwest::Client::new();
+
+    let resp = client
+        .post(&format!("http://0.0.0.0:{}/v1/webhook", port))
+        .json(&json!({
+            "url": "not a url",
+            "events": ["jigPublished"],
+        }))
+        .login()
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let resp = client
+        .post(&format!("http://0.0.0.0:{}/v1/webhook", port))
+        .json(&json!({
+            "url": "https://example.com/hook",
+            "events": [],
+        }))
+        .login()
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn all_users_requires_admin(
+    pool_opts: PgPoolOptions,
+    conn_opts: PgConnectOptions,
+) -> anyhow::Result<()> {
+    let (_, db) = initialize_server_and_get_db(&[Fixture::User], &[], pool_opts, conn_opts).await;
+
+    let owner_id = Uuid::parse_str("1f241e1b-b537-493f-a230-075cb16315be")?;
+    let other_user_id = Uuid::new_v4();
+
+    sqlx::query(
+        // language=SQL
+        r#"
+insert into webhook (user_id, url, secret, events, all_users)
+values ($1, 'http://127.0.0.1/hook', 'whsec_test', $2, true)
+"#,
+    )
+    .bind(owner_id)
+    .bind(vec![WebhookEvent::JigPublished as i16])
+    .execute(&db)
+    .await?;
+
+    let data = json!({});
+
+    let mut txn = db.begin().await?;
+    let queued = webhook::enqueue(
+        &mut txn,
+        WebhookEvent::JigPublished,
+        &[other_user_id],
+        &data,
+    )
+    .await?;
+    txn.commit().await?;
+
+    assert!(queued);
+
+    // the owner is no longer an admin, so the webhook only receives their own events
+    sqlx::query("delete from user_scope where user_id = $1")
+        .bind(owner_id)
+        .execute(&db)
+        .await?;
+
+    let mut txn = db.begin().await?;
+    let queued = webhook::enqueue(
+        &mut txn,
+        WebhookEvent::JigPublished,
+        &[other_user_id],
+        &data,
+    )
+    .await?;
+    txn.commit().await?;
+
+    assert!(!queued);
+
+    Ok(())
+}
@@ -80,6 +80,26 @@ pub mod email {
     pub const EMAIL_WELCOME_JIGZI_TEMPLATE: &str = "EMAIL_WELCOME_JIGZI_TEMPLATE";
 }
 
+pub mod rate_limit {
+    /// Disable rate limiting (e.g. locally)
+    /// if specified in a way that maps to `true` (currently "true", "1", "y"), no requests will be limited.
+    pub const DISABLE: &str = "RATE_LIMIT_DISABLE";
+
+    /// Where requests are counted, either `memory` (the default, per instance) or `postgres`
+    /// (shared between instances).
+    pub const STORE: &str = "RATE_LIMIT_STORE";
+
+    /// How many proxies in front of the API append the address they received the request from to
+    /// `X-Forwarded-For`, e.g. `1` behind Cloud Run alone, or `2` behind a load balancer and Cloud
+    /// Run. The client's address is the one this many entries from the end.
+    /// Defaults to `0`, counting requests for the address they're received from.
+    pub const TRUSTED_PROXY_HOPS: &str = "RATE_LIMIT_TRUSTED_PROXY_HOPS";
+
+    /// Prefix for overriding the quotas of rules, in the form `{requests}/{seconds}`,
+    /// e.g. `RATE_LIMIT_LOGIN_IP=20/300` or `RATE_LIMIT_LOGIN_USER=10/300`.
+    pub const QUOTA_PREFIX: &str = "RATE_LIMIT_";
+}
+
 #[cfg(feature = "db")]
 pub mod db {
     pub const DATABASE_URL: &str = "DATABASE_URL";
@@ -188,6 +208,30 @@ pub const GOOGLE_OAUTH_CLIENT: &str = "GOOGLE_OAUTH_CLIENT";
 /// all related routes will return "501 - Not Implemented" and a warning will be emitted.
 pub const GOOGLE_OAUTH_SECRET: &str = "GOOGLE_OAUTH_SECRET";
 
+/// ID (application / client ID) of the Microsoft identity platform app registration.
+/// Is optional. If missing, signing in with Microsoft will be disabled,
+/// all related routes will return "501 - Not Implemented" and a warning will be emitted.
+pub const MICROSOFT_OAUTH_CLIENT: &str = "MICROSOFT_OAUTH_CLIENT";
+
+/// Client secret of the Microsoft identity platform app registration.
+/// Is optional. If missing, signing in with Microsoft will be disabled,
+/// all related routes will return "501 - Not Implemented" and a warning will be emitted.
+pub const MICROSOFT_OAUTH_SECRET: &str = "MICROSOFT_OAUTH_SECRET";
+
+/// Services ID used for Sign in with Apple.
+/// Is optional. If any of the Apple OAuth secrets are missing, signing in with Apple will be disabled,
+/// all related routes will return "501 - Not Implemented" and a warning will be emitted.
+pub const APPLE_OAUTH_CLIENT: &str = "APPLE_OAUTH_CLIENT";
+
+/// ID of the Apple developer team which owns [`APPLE_OAUTH_CLIENT`].
+pub const APPLE_OAUTH_TEAM_ID: &str = "APPLE_OAUTH_TEAM_ID";
+
+/// ID of the private key for Sign in with Apple.
+pub const APPLE_OAUTH_KEY_ID: &str = "APPLE_OAUTH_KEY_ID";
+
+/// PEM encoded private key for Sign in with Apple, used to sign client secrets.
+pub const APPLE_OAUTH_PRIVATE_KEY: &str = "APPLE_OAUTH_PRIVATE_KEY";
+
 /// Secret for the Google Translate APIs.
 /// Is optional. If missing, all Google Api related services will be disabled,
 /// all related routes will return "501 - Not Implemented" and a warning will be emitted.
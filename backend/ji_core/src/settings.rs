@@ -68,6 +68,57 @@ impl GoogleOAuth {
     }
 }
 
+/// Settings related to signing in with Microsoft.
+#[derive(Clone)]
+pub struct MicrosoftOAuth {
+    /// Application (client) ID of the app registration.
+    pub client: String,
+
+    /// Client Secret of the app registration.
+    pub secret: String,
+}
+
+impl MicrosoftOAuth {
+    fn from_parts(client: Option<String>, secret: Option<String>) -> Option<Self> {
+        Some(Self {
+            client: client?,
+            secret: secret?,
+        })
+    }
+}
+
+/// Settings related to Sign in with Apple.
+#[derive(Clone)]
+pub struct AppleOAuth {
+    /// Services ID.
+    pub client: String,
+
+    /// ID of the developer team which owns the services ID.
+    pub team_id: String,
+
+    /// ID of the private key.
+    pub key_id: String,
+
+    /// PEM encoded private key, used to sign client secrets.
+    pub private_key: String,
+}
+
+impl AppleOAuth {
+    fn from_parts(
+        client: Option<String>,
+        team_id: Option<String>,
+        key_id: Option<String>,
+        private_key: Option<String>,
+    ) -> Option<Self> {
+        Some(Self {
+            client: client?,
+            team_id: team_id?,
+            key_id: key_id?,
+            private_key: private_key?,
+        })
+    }
+}
+
 /// Settings that are accessed at runtime (as compared to startup time)
 #[derive(Clone)]
 pub struct RuntimeSettings {
@@ -95,6 +146,14 @@ pub struct RuntimeSettings {
     /// if missing / disabled, related routes will return `501 - Not Implemented`
     pub google_oauth: Option<GoogleOAuth>,
 
+    /// Settings for Microsoft OAuth
+    /// if missing / disabled, related routes will return `501 - Not Implemented`
+    pub microsoft_oauth: Option<MicrosoftOAuth>,
+
+    /// Settings for Sign in with Apple
+    /// if missing / disabled, related routes will return `501 - Not Implemented`
+    pub apple_oauth: Option<AppleOAuth>,
+
     /// Settings for google OAuth
     /// if missing / disabled, related routes will return `501 - Not Implemented`
     pub google_api_key: Option<String>,
@@ -124,6 +183,8 @@ impl RuntimeSettings {
         bing_search_key: Option<String>,
         google_api_key: Option<String>,
         google_oauth: Option<GoogleOAuth>,
+        microsoft_oauth: Option<MicrosoftOAuth>,
+        apple_oauth: Option<AppleOAuth>,
         token_secret: Box<[u8; 32]>,
         login_token_valid_duration: Option<chrono::Duration>,
         stripe_secret_key: Option<String>,
@@ -139,6 +200,8 @@ impl RuntimeSettings {
             remote_target,
             bing_search_key,
             google_oauth,
+            microsoft_oauth,
+            apple_oauth,
             google_api_key,
             token_secret,
             login_token_valid_duration,
@@ -152,6 +215,8 @@ impl RuntimeSettings {
         bing_search_key: Option<String>,
         google_api_key: Option<String>,
         google_oauth: Option<GoogleOAuth>,
+        microsoft_oauth: Option<MicrosoftOAuth>,
+        apple_oauth: Option<AppleOAuth>,
         token_secret: Box<[u8; 32]>,
         login_token_valid_duration: Option<chrono::Duration>,
         stripe_secret_key: Option<String>,
@@ -177,6 +242,8 @@ impl RuntimeSettings {
             remote_target,
             bing_search_key,
             google_oauth,
+            microsoft_oauth,
+            apple_oauth,
             google_api_key,
             token_secret,
             login_token_valid_duration,
@@ -237,10 +304,12 @@ pub struct AlgoliaSettings {
 
     /// The index to use for operations relating to jigs on the algolia client.
     /// If [`None`], indexing and searching will be disabled.
+    /// Sorting search results by rating uses its `{index}_rating_desc` replica, which must rank by `average_rating` descending.
     pub jig_index: Option<String>,
 
     /// The index to use for operations relating to Resource on the algolia client.
     /// If [`None`], indexing and searching will be disabled.
+    /// Needs a `{index}_rating_desc` replica, like [`Self::jig_index`].
     pub resource_index: Option<String>,
 
     /// The key to use for the *frontend* for the algolia client.
@@ -857,11 +926,28 @@ impl SettingsManager {
             self.get_varying_secret(keys::GOOGLE_OAUTH_SECRET).await?,
         );
 
+        let microsoft_oauth = MicrosoftOAuth::from_parts(
+            self.get_varying_secret(keys::MICROSOFT_OAUTH_CLIENT)
+                .await?,
+            self.get_varying_secret(keys::MICROSOFT_OAUTH_SECRET)
+                .await?,
+        );
+
+        let apple_oauth = AppleOAuth::from_parts(
+            self.get_varying_secret(keys::APPLE_OAUTH_CLIENT).await?,
+            self.get_varying_secret(keys::APPLE_OAUTH_TEAM_ID).await?,
+            self.get_varying_secret(keys::APPLE_OAUTH_KEY_ID).await?,
+            self.get_varying_secret(keys::APPLE_OAUTH_PRIVATE_KEY)
+                .await?,
+        );
+
         RuntimeSettings::with_env(
             self.remote_target,
             bing_search_key,
             google_api_key,
             google_oauth,
+            microsoft_oauth,
+            apple_oauth,
             token_secret,
             login_token_valid_duration,
             self.stripe_secret_key().await?,
@@ -1,5 +1,6 @@
 use crate::templates::{
-    direct::direct_template_no_auth, epoch::epoch_page, info::info_template, passthrough, spa,
+    direct::direct_template_no_auth, epoch::epoch_page, info::info_template, oembed::oembed,
+    passthrough, sitemap, spa,
 };
 use actix_web::{
     web::{self, ServiceConfig},
@@ -32,7 +33,7 @@ pub fn configure(config: &mut ServiceConfig) {
         // jig route is just to redirect old urls
         .route(
             "/jig/{page_kind}/{path:.*}",
-            web::get().to(spa::asset_template),
+            web::get().to(spa::jig_template),
         )
         .route("/legacy/play/{jig_id}", web::get().to(spa::legacy_template))
         .route(
@@ -51,6 +52,10 @@ pub fn configure(config: &mut ServiceConfig) {
         .route("/info", web::get().to(info_template))
         .route("/epoch", web::get().to(epoch_page))
         .route("/plans", web::get().to(redirect))
+        .route("/oembed", web::get().to(oembed))
+        .route("/robots.txt", web::get().to(sitemap::robots))
+        .route("/sitemap.xml", web::get().to(sitemap::sitemap_index))
+        .route("/sitemap/{kind}/{page}", web::get().to(sitemap::sitemap))
         .route(
             "/service-worker.js",
             web::get().to(passthrough::service_worker),
@@ -0,0 +1,183 @@
+// Open Graph / Twitter card metadata for shared asset links, so that link previews (WhatsApp,
+// Google Classroom, email, ...) show the asset instead of a blank page.
+
+use std::{error::Error, str::FromStr};
+
+use cached::proc_macro::cached;
+use reqwest::{StatusCode, Url};
+use serde::de::DeserializeOwned;
+use shared::{
+    api::endpoints::PathParts,
+    config::RemoteTarget,
+    domain::{
+        asset::{AssetId, AssetType, PrivacyLevel},
+        course::{CourseGetLivePath, CourseId, CourseResponse},
+        jig::{JigGetLivePath, JigId, JigResponse},
+        module::LiteModule,
+        playlist::{PlaylistGetLivePath, PlaylistId, PlaylistResponse},
+        resource::ResourceId,
+    },
+};
+
+#[derive(Clone, Debug)]
+pub struct AssetMeta {
+    pub title: String,
+    pub description: String,
+    pub author_name: Option<String>,
+    pub image: Option<String>,
+    pub url: String,
+    pub oembed_url: Option<String>,
+}
+
+/// Parses the asset id out of an `/asset/play/...` path, i.e. `jig/{jig_id}[/{module_id}]`.
+pub fn parse_asset_id(path: &str) -> Option<AssetId> {
+    let mut parts = path.split('/');
+
+    let asset_type = AssetType::try_from(parts.next()?).ok()?;
+    let id = parts.next()?;
+
+    let asset_id = match asset_type {
+        AssetType::Jig => JigId::from_str(id).ok()?.into(),
+        AssetType::Resource => ResourceId::from_str(id).ok()?.into(),
+        AssetType::Playlist => PlaylistId::from_str(id).ok()?.into(),
+        AssetType::Course => CourseId::from_str(id).ok()?.into(),
+    };
+
+    Some(asset_id)
+}
+
+/// Looks up the metadata of a live asset.
+///
+/// Returns `None` for assets which are missing, unpublished or not public, so that nothing about
+/// them ends up in the page.
+pub async fn asset_meta(remote_target: RemoteTarget, asset_id: AssetId) -> Option<AssetMeta> {
+    match get_asset_meta(remote_target, asset_id).await {
+        Ok(meta) => meta,
+        Err(e) => {
+            log::warn!("Error fetching metadata for {:?}: {:?}", asset_id, e);
+            None
+        }
+    }
+}
+
+#[cached(
+    time = 60,
+    result = true,
+    sync_writes = true,
+    key = "String",
+    convert = r#"{ format!("{:?}", asset_id) }"#
+)]
+async fn get_asset_meta(
+    remote_target: RemoteTarget,
+    asset_id: AssetId,
+) -> Result<Option<AssetMeta>, Box<dyn Error>> {
+    let meta = match asset_id {
+        AssetId::JigId(id) => get_live::<JigResponse>(remote_target, JigGetLivePath(id))
+            .await?
+            .filter(|jig| jig.published_at.is_some())
+            .filter(|jig| jig.jig_data.privacy_level == PrivacyLevel::Public)
+            .map(|jig| {
+                meta(
+                    remote_target,
+                    asset_id,
+                    jig.author_name,
+                    jig.jig_data.display_name,
+                    jig.jig_data.description,
+                    jig.jig_data.modules.first(),
+                )
+            }),
+        // resources don't have a page of their own, shared links point at their content
+        AssetId::ResourceId(_) => None,
+        AssetId::PlaylistId(id) => {
+            get_live::<PlaylistResponse>(remote_target, PlaylistGetLivePath(id))
+                .await?
+                .filter(|playlist| playlist.published_at.is_some())
+                .filter(|playlist| playlist.playlist_data.privacy_level == PrivacyLevel::Public)
+                .map(|playlist| {
+                    meta(
+                        remote_target,
+                        asset_id,
+                        playlist.author_name,
+                        playlist.playlist_data.display_name,
+                        playlist.playlist_data.description,
+                        playlist.playlist_data.cover.as_ref(),
+                    )
+                })
+        }
+        AssetId::CourseId(id) => get_live::<CourseResponse>(remote_target, CourseGetLivePath(id))
+            .await?
+            .filter(|course| course.published_at.is_some())
+            .filter(|course| course.course_data.privacy_level == PrivacyLevel::Public)
+            .map(|course| {
+                meta(
+                    remote_target,
+                    asset_id,
+                    course.author_name,
+                    course.course_data.display_name,
+                    course.course_data.description,
+                    course.course_data.cover.as_ref(),
+                )
+            }),
+    };
+
+    Ok(meta)
+}
+
+async fn get_live<T: DeserializeOwned>(
+    remote_target: RemoteTarget,
+    path: impl PathParts,
+) -> Result<Option<T>, reqwest::Error> {
+    let url = format!("{}{}", remote_target.api_url(), path.get_filled());
+
+    let res = reqwest::get(url).await?;
+
+    if res.status() == StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+
+    Ok(Some(res.error_for_status()?.json().await?))
+}
+
+fn meta(
+    remote_target: RemoteTarget,
+    asset_id: AssetId,
+    author_name: Option<String>,
+    title: String,
+    description: String,
+    cover: Option<&LiteModule>,
+) -> AssetMeta {
+    let image = cover.map(|module| {
+        format!(
+            "{}/screenshot/{}/{}/thumb.jpg",
+            remote_target.uploads_url(),
+            asset_id.uuid(),
+            module.id.0
+        )
+    });
+
+    let url = format!(
+        "{}/asset/play/{}/{}",
+        remote_target.pages_url(),
+        AssetType::from(&asset_id).as_str(),
+        asset_id.uuid()
+    );
+
+    let oembed_url = match asset_id {
+        AssetId::JigId(_) | AssetId::PlaylistId(_) => Url::parse_with_params(
+            &format!("{}/oembed", remote_target.pages_url()),
+            &[("url", url.as_str()), ("format", "json")],
+        )
+        .ok()
+        .map(String::from),
+        _ => None,
+    };
+
+    AssetMeta {
+        title,
+        description,
+        author_name,
+        image,
+        url,
+        oembed_url,
+    }
+}
@@ -1,5 +1,8 @@
 pub mod direct;
 pub mod epoch;
 pub mod info;
+pub mod meta;
+pub mod oembed;
 pub mod passthrough;
+pub mod sitemap;
 pub mod spa;
@@ -0,0 +1,165 @@
+// oEmbed provider (https://oembed.com) for public JIG and playlist links, so that LMSs and blog
+// platforms can embed them automatically.
+
+use actix_web::{
+    error::{ErrorBadRequest, ErrorInternalServerError, ErrorNotFound, ErrorNotImplemented},
+    web::{Data, Query},
+    HttpResponse,
+};
+use askama::Template;
+use ji_core::settings::RuntimeSettings;
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use shared::domain::asset::{AssetId, AssetType};
+
+use super::meta;
+
+// player size if the consumer doesn't limit it
+const DEFAULT_WIDTH: u32 = 960;
+const DEFAULT_HEIGHT: u32 = 540;
+
+// size of module screenshot thumbnails
+const THUMBNAIL_WIDTH: u32 = 299;
+const THUMBNAIL_HEIGHT: u32 = 168;
+
+// how long consumers may cache the response, in seconds
+const CACHE_AGE: u32 = 3600;
+
+enum OEmbedFormat {
+    Json,
+    Xml,
+}
+
+#[derive(Deserialize)]
+pub struct OEmbedQuery {
+    url: String,
+    maxwidth: Option<u32>,
+    maxheight: Option<u32>,
+    // unknown formats have to be answered with a 501, so this isn't deserialized as `OEmbedFormat`
+    format: Option<String>,
+}
+
+#[derive(Serialize, Template)]
+#[template(path = "oembed.xml")]
+struct OEmbed {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    version: &'static str,
+    title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    author_name: Option<String>,
+    provider_name: &'static str,
+    provider_url: String,
+    cache_age: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    thumbnail_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    thumbnail_width: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    thumbnail_height: Option<u32>,
+    html: String,
+    width: u32,
+    height: u32,
+}
+
+/// Parses the JIG or playlist id out of a shared Jigzi url, e.g. `https://jigzi.org/asset/play/jig/{jig_id}`.
+fn parse_asset_url(pages_url: &str, url: &str) -> Option<AssetId> {
+    let pages_url = Url::parse(pages_url).ok()?;
+    let url = Url::parse(url).ok()?;
+
+    let host = url.host_str()?;
+    if host.trim_start_matches("www.") != pages_url.host_str()? {
+        return None;
+    }
+
+    let path = url.path().trim_start_matches('/');
+    let asset_id = if let Some(path) = path.strip_prefix("asset/play/") {
+        meta::parse_asset_id(path)?
+    } else if let Some(path) = path.strip_prefix("jig/play/") {
+        meta::parse_asset_id(&format!("jig/{}", path))?
+    } else {
+        return None;
+    };
+
+    match asset_id {
+        AssetId::JigId(_) | AssetId::PlaylistId(_) => Some(asset_id),
+        _ => None,
+    }
+}
+
+/// Fits the player within the consumer's limits, keeping its aspect ratio.
+fn player_size(maxwidth: Option<u32>, maxheight: Option<u32>) -> (u32, u32) {
+    let width = maxwidth.unwrap_or(DEFAULT_WIDTH).min(DEFAULT_WIDTH);
+    let height = maxheight.unwrap_or(DEFAULT_HEIGHT).min(DEFAULT_HEIGHT);
+
+    let width = width.min(height * DEFAULT_WIDTH / DEFAULT_HEIGHT);
+
+    (width, width * DEFAULT_HEIGHT / DEFAULT_WIDTH)
+}
+
+pub async fn oembed(
+    settings: Data<RuntimeSettings>,
+    query: Query<OEmbedQuery>,
+) -> actix_web::Result<HttpResponse> {
+    let query = query.into_inner();
+    let remote_target = settings.remote_target();
+
+    let format = match query.format.as_deref() {
+        None | Some("json") => OEmbedFormat::Json,
+        Some("xml") => OEmbedFormat::Xml,
+        Some(_) => return Err(ErrorNotImplemented("unsupported format")),
+    };
+
+    let asset_id = parse_asset_url(&remote_target.pages_url(), &query.url)
+        .ok_or_else(|| ErrorNotFound("not a JIG or playlist url"))?;
+
+    // private and unpublished assets are reported as missing
+    let meta = meta::asset_meta(remote_target, asset_id)
+        .await
+        .ok_or_else(|| ErrorNotFound("not found"))?;
+
+    let (width, height) = player_size(query.maxwidth, query.maxheight);
+    if width == 0 || height == 0 {
+        return Err(ErrorBadRequest("maxwidth and maxheight are too small"));
+    }
+
+    let src = remote_target.spa_iframe(&format!(
+        "/asset/play/{}/{}",
+        AssetType::from(&asset_id).as_str(),
+        asset_id.uuid()
+    ));
+
+    let html = format!(
+        r#"<iframe src="{}" width="{}" height="{}" frameborder="0" allow="autoplay; fullscreen" allowfullscreen></iframe>"#,
+        src, width, height
+    );
+
+    let has_thumbnail = meta.image.is_some();
+
+    let oembed = OEmbed {
+        kind: "rich",
+        version: "1.0",
+        title: meta.title,
+        author_name: meta.author_name,
+        provider_name: "Jigzi",
+        provider_url: remote_target.pages_url(),
+        cache_age: CACHE_AGE,
+        thumbnail_url: meta.image,
+        thumbnail_width: has_thumbnail.then(|| THUMBNAIL_WIDTH),
+        thumbnail_height: has_thumbnail.then(|| THUMBNAIL_HEIGHT),
+        html,
+        width,
+        height,
+    };
+
+    match format {
+        OEmbedFormat::Json => Ok(HttpResponse::Ok().json(oembed)),
+        OEmbedFormat::Xml => {
+            let body = oembed.render().map_err(ErrorInternalServerError)?;
+
+            Ok(HttpResponse::Ok()
+                .content_type("text/xml; charset=utf-8")
+                .body(body))
+        }
+    }
+}
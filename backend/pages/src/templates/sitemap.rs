@@ -0,0 +1,198 @@
+// Sitemaps (https://www.sitemaps.org) and robots.txt, so that search engines can find the public
+// library.
+
+use std::error::Error;
+
+use actix_web::{
+    error::{ErrorInternalServerError, ErrorNotFound},
+    web::{Data, Path},
+    HttpResponse,
+};
+use askama::Template;
+use cached::proc_macro::cached;
+use ji_core::settings::RuntimeSettings;
+use shared::{
+    api::endpoints::PathParts,
+    config::RemoteTarget,
+    domain::sitemap::{
+        SitemapEntry, SitemapIndexPath, SitemapIndexResponse, SitemapKind, SitemapPagePath,
+        SitemapPageResponse,
+    },
+};
+
+// how long crawlers may cache sitemaps, in seconds. Matches how often they're regenerated.
+const SITEMAP_MAX_AGE: u64 = 60 * 60;
+
+struct SitemapLink {
+    loc: String,
+    lastmod: Option<String>,
+}
+
+#[derive(Template)]
+#[template(path = "sitemap_index.xml")]
+struct SitemapIndex {
+    sitemaps: Vec<SitemapLink>,
+}
+
+#[derive(Template)]
+#[template(path = "sitemap.xml")]
+struct Sitemap {
+    urls: Vec<SitemapLink>,
+}
+
+#[cached(
+    time = 3600,
+    result = true,
+    sync_writes = true,
+    key = "()",
+    convert = r#"{}"#
+)]
+async fn get_sitemap_index(remote_target: RemoteTarget) -> Result<String, Box<dyn Error>> {
+    let url = format!(
+        "{}{}",
+        remote_target.api_url(),
+        SitemapIndexPath().get_filled()
+    );
+    let index: SitemapIndexResponse = reqwest::get(url).await?.error_for_status()?.json().await?;
+
+    let sitemaps = index
+        .sitemaps
+        .into_iter()
+        .flat_map(|summary| {
+            let lastmod = summary.last_edited.map(|it| it.to_rfc3339());
+
+            (0..summary.pages).map(move |page| SitemapLink {
+                loc: format!(
+                    "{}/sitemap/{}/{}.xml",
+                    remote_target.pages_url(),
+                    summary.kind.as_str(),
+                    page
+                ),
+                lastmod: lastmod.clone(),
+            })
+        })
+        .collect();
+
+    Ok(SitemapIndex { sitemaps }.render()?)
+}
+
+#[cached(
+    time = 3600,
+    result = true,
+    sync_writes = true,
+    key = "(SitemapKind, u32)",
+    convert = r#"{ (kind, page) }"#
+)]
+async fn get_sitemap(
+    remote_target: RemoteTarget,
+    kind: SitemapKind,
+    page: u32,
+) -> Result<Option<String>, Box<dyn Error>> {
+    let url = format!(
+        "{}{}",
+        remote_target.api_url(),
+        SitemapPagePath(kind, page).get_filled()
+    );
+    let res: SitemapPageResponse = reqwest::get(url).await?.error_for_status()?.json().await?;
+
+    if res.entries.is_empty() {
+        return Ok(None);
+    }
+
+    let urls = res
+        .entries
+        .into_iter()
+        .map(|entry| SitemapLink {
+            loc: format!("{}{}", remote_target.pages_url(), page_path(kind, &entry)),
+            lastmod: entry.last_edited.map(|it| it.to_rfc3339()),
+        })
+        .collect();
+
+    Ok(Some(Sitemap { urls }.render()?))
+}
+
+fn page_path(kind: SitemapKind, entry: &SitemapEntry) -> String {
+    let id = entry.id;
+
+    match kind {
+        SitemapKind::Jig => format!("/asset/play/jig/{}", id),
+        SitemapKind::Playlist => format!("/asset/play/playlist/{}", id),
+        SitemapKind::Course => format!("/asset/play/course/{}", id),
+        SitemapKind::Circle => format!("/community/circles/{}", id),
+        SitemapKind::User => format!("/community/members/{}", id),
+    }
+}
+
+fn xml_response(body: String) -> HttpResponse {
+    HttpResponse::Ok()
+        .content_type("application/xml; charset=utf-8")
+        .insert_header((
+            "Cache-Control",
+            format!("public, max-age={}", SITEMAP_MAX_AGE),
+        ))
+        .body(body)
+}
+
+pub async fn sitemap_index(settings: Data<RuntimeSettings>) -> actix_web::Result<HttpResponse> {
+    let body = get_sitemap_index(settings.remote_target())
+        .await
+        .map_err(|e| {
+            log::error!("Error generating sitemap index: {:?}", e);
+            ErrorInternalServerError("")
+        })?;
+
+    Ok(xml_response(body))
+}
+
+pub async fn sitemap(
+    settings: Data<RuntimeSettings>,
+    path: Path<(SitemapKind, String)>,
+) -> actix_web::Result<HttpResponse> {
+    let (kind, page) = path.into_inner();
+
+    let page: u32 = page
+        .strip_suffix(".xml")
+        .and_then(|page| page.parse().ok())
+        .ok_or_else(|| ErrorNotFound("not found"))?;
+
+    let body = get_sitemap(settings.remote_target(), kind, page)
+        .await
+        .map_err(|e| {
+            log::error!(
+                "Error generating {} sitemap {}: {:?}",
+                kind.as_str(),
+                page,
+                e
+            );
+            ErrorInternalServerError("")
+        })?
+        .ok_or_else(|| ErrorNotFound("not found"))?;
+
+    Ok(xml_response(body))
+}
+
+pub async fn robots(settings: Data<RuntimeSettings>) -> actix_web::Result<HttpResponse> {
+    let remote_target = settings.remote_target();
+
+    // only the release site should be indexed
+    let body = match remote_target {
+        RemoteTarget::Release => format!(
+            "User-agent: *\n\
+             Disallow: /admin\n\
+             Disallow: /user\n\
+             Disallow: /asset/edit\n\
+             Disallow: /module\n\
+             Disallow: /dev\n\
+             Disallow: /kids\n\
+             Disallow: /legacy\n\
+             \n\
+             Sitemap: {}/sitemap.xml\n",
+            remote_target.pages_url()
+        ),
+        RemoteTarget::Local | RemoteTarget::Sandbox => "User-agent: *\nDisallow: /\n".to_string(),
+    };
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/plain; charset=utf-8")
+        .body(body))
+}
@@ -9,6 +9,8 @@ use std::borrow::Cow;
 
 use askama::Template;
 
+use super::meta::{self, AssetMeta};
+
 #[derive(Debug, Clone, PartialEq, Copy, Eq, serde::Deserialize)]
 #[serde(rename_all = "kebab-case")]
 pub enum ModuleAssetPageKind {
@@ -68,9 +70,18 @@ struct SpaPageInfo {
     local_dev: bool,
     include_hubspot: bool,
     is_release: bool,
+    meta: Option<AssetMeta>,
 }
 
 fn spa_template(settings: &RuntimeSettings, spa: SpaPage) -> actix_web::Result<HttpResponse> {
+    spa_template_with_meta(settings, spa, None)
+}
+
+fn spa_template_with_meta(
+    settings: &RuntimeSettings,
+    spa: SpaPage,
+    meta: Option<AssetMeta>,
+) -> actix_web::Result<HttpResponse> {
     let google_maps_url = match spa {
         // todo: `Cow::borrowed` ('static)
         SpaPage::User | SpaPage::Community => {
@@ -98,6 +109,7 @@ fn spa_template(settings: &RuntimeSettings, spa: SpaPage) -> actix_web::Result<H
             SpaPage::LegacyJig => false,
             _ => true,
         },
+        meta,
     };
 
     let info = info.render().map_err(ErrorInternalServerError)?;
@@ -131,8 +143,33 @@ pub async fn asset_template(
     settings: Data<RuntimeSettings>,
     path: Path<(ModuleAssetPageKind, String)>,
 ) -> actix_web::Result<HttpResponse> {
-    let (page_kind, _asset_kind) = path.into_inner();
-    spa_template(&settings, SpaPage::Asset(page_kind))
+    let (page_kind, path) = path.into_inner();
+    asset_template_with_meta(&settings, page_kind, &path).await
+}
+
+// old `/jig/{page_kind}/{jig_id}` urls, which the frontend redirects
+pub async fn jig_template(
+    settings: Data<RuntimeSettings>,
+    path: Path<(ModuleAssetPageKind, String)>,
+) -> actix_web::Result<HttpResponse> {
+    let (page_kind, path) = path.into_inner();
+    asset_template_with_meta(&settings, page_kind, &format!("jig/{}", path)).await
+}
+
+async fn asset_template_with_meta(
+    settings: &RuntimeSettings,
+    page_kind: ModuleAssetPageKind,
+    path: &str,
+) -> actix_web::Result<HttpResponse> {
+    // only shared (played) assets get a link preview
+    let meta = match (page_kind, meta::parse_asset_id(path)) {
+        (ModuleAssetPageKind::Play, Some(asset_id)) => {
+            meta::asset_meta(settings.remote_target(), asset_id).await
+        }
+        _ => None,
+    };
+
+    spa_template_with_meta(settings, SpaPage::Asset(page_kind), meta)
 }
 
 pub async fn legacy_template(
@@ -216,6 +216,8 @@ where
                         published_at: None,
                         likes: 0,
                         plays: 0,
+                        average_rating: None,
+                        review_count: 0,
                         live_up_to_date: false,
                         is_liked: false,
                         jig_data: JigData {
@@ -142,6 +142,8 @@ where
                         },
                         likes: 0,
                         plays: 0,
+                        average_rating: None,
+                        review_count: 0,
                     }))
                 } else {
                     let resp = match _self.opts.asset_id {
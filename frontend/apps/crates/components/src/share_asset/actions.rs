@@ -20,6 +20,8 @@ impl ShareAsset {
             let req = shared::domain::jig::player::JigPlayerSessionCreateRequest {
                 jig_id: state.asset.unwrap_jig().id,
                 settings: JigPlayerSettings::default(),
+                roster_id: None,
+                duration: None,
             };
 
             match jig::player::Create::api_with_auth(JigPlayerSessionCreatePath(), Some(req)).await {
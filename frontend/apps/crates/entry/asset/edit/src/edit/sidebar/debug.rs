@@ -72,5 +72,7 @@ pub fn get_jig() -> JigResponse {
         },
         likes: 0,
         plays: 0,
+        average_rating: None,
+        review_count: 0,
     }
 }
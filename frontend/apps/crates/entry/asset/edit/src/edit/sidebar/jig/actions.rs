@@ -82,6 +82,8 @@ pub fn get_player_settings(settings_state: Rc<SettingsState>) -> JigPlayerOption
         drag_assist,
         is_student: false,
         draft_or_live: DraftOrLive::Draft,
+        session_instance_token: None,
+        completes_session_instance: false,
     }
 }
 
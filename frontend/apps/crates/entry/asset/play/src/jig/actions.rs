@@ -12,7 +12,14 @@ use shared::{
     domain::{
         asset::DraftOrLive,
         jig::{
-            player::{ModuleConfig, PlayerNavigationHandler, Seconds},
+            player::{
+                instance::{
+                    PlayerSessionInstanceCompletePath, PlayerSessionInstanceCompleteRequest,
+                    PlayerSessionInstanceModuleResult, PlayerSessionInstancePointsPath,
+                    PlayerSessionInstancePointsRequest,
+                },
+                ModuleConfig, PlayerNavigationHandler, Seconds,
+            },
             AudioBackground, JigGetDraftPath, JigGetLivePath, JigLikedPath, JigPlayPath,
             TextDirection,
         },
@@ -98,6 +105,8 @@ pub fn navigate_forward(state: Rc<JigPlayer>) {
                 }))
             }
 
+            report_points(Rc::clone(&state), true, is_done);
+
             if !is_done {
                 // Only increment the played count when navigating to the _next_ module.
                 let mut played_modules = state.played_modules.borrow_mut();
@@ -111,6 +120,61 @@ pub fn navigate_forward(state: Rc<JigPlayer>) {
     }
 }
 
+/// Reports the points scored in the active module to the player session instance, if the JIG is
+/// played as part of one, and completes the instance once its last JIG is done.
+fn report_points(state: Rc<JigPlayer>, completed: bool, is_done: bool) {
+    let token = match &state.player_options.session_instance_token {
+        Some(token) => token.clone(),
+        None => return,
+    };
+
+    let completes_instance = state.player_options.completes_session_instance;
+
+    let module_id = state.active_module.get().and_then(|index| {
+        state
+            .jig
+            .lock_ref()
+            .as_ref()
+            .and_then(|jig| jig.jig_data.modules.get(index).map(|module| module.id))
+    });
+
+    let module = module_id.map(|module_id| PlayerSessionInstanceModuleResult {
+        module_id,
+        points: *state.module_points.borrow(),
+        completed,
+    });
+
+    spawn_local(async move {
+        // Failures are noted in the server logs, playing the JIG doesn't depend on them.
+        if let Some(module) = module {
+            let req = PlayerSessionInstancePointsRequest {
+                token: token.clone(),
+                module,
+            };
+
+            let _ = jig::player::instance::Points::api_no_auth(
+                PlayerSessionInstancePointsPath(),
+                Some(req),
+            )
+            .await;
+        }
+
+        if is_done && completes_instance {
+            let req = PlayerSessionInstanceCompleteRequest { token };
+
+            if let Ok(result) = jig::player::instance::Complete::api_no_auth(
+                PlayerSessionInstanceCompletePath(),
+                Some(req),
+            )
+            .await
+            {
+                // Show the score which was recorded for the class
+                state.points.set_neq(result.points);
+            }
+        }
+    });
+}
+
 pub fn navigate_back_or_handle(state: Rc<JigPlayer>) {
     match state.navigation_handler.get_cloned() {
         Some(PlayerNavigationHandler::Module) => {
@@ -148,6 +212,7 @@ pub fn navigate_from_keyboard_event(state: Rc<JigPlayer>, key_event: KeyEvent) {
 
 pub fn navigate_to_index(state: Rc<JigPlayer>, index: usize) {
     state.active_module.set(Some(index));
+    *state.module_points.borrow_mut() = 0;
     state.timer.set(None);
     state.done.set(false);
     set_paused(&state, false);
@@ -388,8 +453,10 @@ pub fn send_iframe_message(state: Rc<JigPlayer>, data: JigToModulePlayerMessage)
 pub fn on_iframe_message(state: Rc<JigPlayer>, message: ModuleToJigPlayerMessage) {
     match message {
         ModuleToJigPlayerMessage::AddPoints(amount) => {
-            let mut points = state.points.lock_mut();
-            *points += amount;
+            *state.points.lock_mut() += amount;
+            *state.module_points.borrow_mut() += amount;
+
+            report_points(state, false, false);
         }
         ModuleToJigPlayerMessage::Start(config) => {
             start_player(state, config);
@@ -30,6 +30,8 @@ pub struct JigPlayer {
     pub navigation_handler: Mutable<Option<PlayerNavigationHandler>>,
     pub timer: Mutable<Option<Timer>>,
     pub points: Mutable<u32>,
+    /// Points scored in the active module
+    pub module_points: RefCell<u32>,
     pub iframe: Rc<RefCell<Option<HtmlIFrameElement>>>,
     /// Whether this activity has started (via clicking Play button, or automatically).
     ///
@@ -73,6 +75,7 @@ impl JigPlayer {
             timer: Mutable::new(None),
             navigation_handler: Mutable::new(None),
             points: Mutable::new(0),
+            module_points: RefCell::new(0),
             iframe: Rc::new(RefCell::new(None)),
             started: Mutable::new(false),
             paused: Mutable::new(false),
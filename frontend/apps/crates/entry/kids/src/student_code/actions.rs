@@ -3,35 +3,134 @@ use std::rc::Rc;
 use dominator::clone;
 use shared::{
     api::endpoints::jig,
-    domain::jig::player::{
-        instance::{
-            PlayerSessionInstanceCreatePath, PlayerSessionInstanceCreateRequest,
-            PlayerSessionInstanceResponse,
+    domain::{
+        jig::player::{
+            instance::{
+                PlayerSessionInstanceCreatePath, PlayerSessionInstanceCreateRequest,
+                PlayerSessionInstanceResponse, PlayerSessionInstanceRosterPath,
+                PlayerSessionInstanceRosterStudent, PlayerSessionInstanceStudent,
+            },
+            JigPlayerSessionIndex,
         },
-        JigPlayerSessionIndex,
+        roster::PicturePassword,
     },
 };
 use utils::prelude::*;
 
-use super::state::State;
+use super::state::{SessionRoster, State};
 
 pub fn submit_code(state: Rc<State>, number: String) {
     state.loader.load(clone!(state => async move {
-        match code_to_jig_id(number).await {
-            Err(_) => {}
-            Ok(res) => {
-                state.error.set_neq(false);
-                state.play_jig.set(Some((res.jig_id, res.settings)));
-            }
+        let index = match number.parse::<i32>() {
+            Ok(number) => JigPlayerSessionIndex(number),
+            Err(_) => return,
+        };
+
+        let (result, status) = jig::player::instance::Roster::api_no_auth_status(
+            PlayerSessionInstanceRosterPath(index),
+            None,
+        )
+        .await;
+
+        match (status, result) {
+            (404, _) | (_, Err(_)) => {}
+            (_, Ok(res)) => match res.students {
+                // the student picks their name before joining
+                Some(students) => {
+                    state.error.set_neq(false);
+                    state.roster.set(Some(SessionRoster::new(index, students)));
+                }
+                None => {
+                    let display_name = state.display_name.get_cloned();
+                    let display_name = display_name.trim();
+                    let display_name = (!display_name.is_empty()).then(|| display_name.to_owned());
+
+                    if let Ok(res) = create_instance(index, display_name, None).await {
+                        state.error.set_neq(false);
+                        start_playing(&state, res);
+                    }
+                }
+            },
         };
+    }));
+}
+
+pub fn pick_student(
+    state: Rc<State>,
+    roster: Rc<SessionRoster>,
+    student: &PlayerSessionInstanceRosterStudent,
+) {
+    if student.has_picture_password {
+        roster.wrong_picture.set_neq(false);
+        roster.selected.set(Some(student.id));
+    } else {
+        join_as_student(
+            state,
+            roster,
+            PlayerSessionInstanceStudent {
+                id: student.id,
+                picture_password: None,
+            },
+        );
+    }
+}
+
+pub fn pick_picture(state: Rc<State>, roster: Rc<SessionRoster>, picture: PicturePassword) {
+    if let Some(id) = roster.selected.get() {
+        join_as_student(
+            state,
+            roster,
+            PlayerSessionInstanceStudent {
+                id,
+                picture_password: Some(picture),
+            },
+        );
+    }
+}
 
+fn join_as_student(
+    state: Rc<State>,
+    roster: Rc<SessionRoster>,
+    student: PlayerSessionInstanceStudent,
+) {
+    state.loader.load(clone!(state => async move {
+        match create_instance(roster.index, None, Some(student)).await {
+            Ok(res) => {
+                state.roster.set(None);
+                start_playing(&state, res);
+            }
+            Err(403) => roster.wrong_picture.set_neq(true),
+            Err(_) => {}
+        }
     }));
 }
 
-async fn code_to_jig_id(number: String) -> Result<PlayerSessionInstanceResponse, ()> {
-    let number = number.parse::<i32>().map_err(|_| ())?;
-    let index = JigPlayerSessionIndex(number);
-    let req = PlayerSessionInstanceCreateRequest { index };
+fn start_playing(state: &State, instance: PlayerSessionInstanceResponse) {
+    state.item_index.set(0);
+    state.play_jig.set(Some(instance));
+}
+
+/// Moves on to the next JIG of the session, or back to the code input after the last one.
+pub fn play_next_item(state: Rc<State>, item_count: usize) {
+    let next = state.item_index.get() + 1;
+
+    if next < item_count {
+        state.item_index.set(next);
+    } else {
+        state.play_jig.set(None);
+    }
+}
+
+async fn create_instance(
+    index: JigPlayerSessionIndex,
+    display_name: Option<String>,
+    student: Option<PlayerSessionInstanceStudent>,
+) -> Result<PlayerSessionInstanceResponse, u16> {
+    let req = PlayerSessionInstanceCreateRequest {
+        index,
+        display_name,
+        student,
+    };
 
     let (result, status) = jig::player::instance::Create::api_no_auth_status(
         PlayerSessionInstanceCreatePath(),
@@ -39,8 +138,5 @@ async fn code_to_jig_id(number: String) -> Result<PlayerSessionInstanceResponse,
     )
     .await;
 
-    match status {
-        404 => Err(()),
-        _ => result.map_err(|_| ()),
-    }
+    result.map_err(|_| status)
 }
@@ -1,16 +1,23 @@
 use std::rc::Rc;
 
 use components::player_popup::{PlayerPopup, PreviewPopupCallbacks};
-use dominator::{clone, html, Dom};
-use futures_signals::signal::SignalExt;
+use dominator::{clone, html, with_node, Dom};
+use futures_signals::{map_ref, signal::SignalExt};
+use shared::{config::JIG_PLAYER_SESSION_DISPLAY_NAME_MAX_LEN, domain::roster::PicturePassword};
 use utils::{asset::JigPlayerOptions, events};
+use web_sys::HtmlInputElement;
 
-use crate::student_code::actions::submit_code;
+use crate::student_code::actions::{pick_picture, pick_student, play_next_item, submit_code};
 
-use super::state::State;
+use super::state::{SessionRoster, State};
 
 const STR_TRY_AGAIN: &str = "Try again";
 const STR_HELP: &str = "Ask for help";
+const STR_NAME_LABEL: &str = "Your name";
+const STR_NAME_PLACEHOLDER: &str = "Type your name";
+const STR_PICK_NAME: &str = "Find your name";
+const STR_PICK_PICTURE: &str = "Pick your picture";
+const STR_WRONG_PICTURE: &str = "That's not your picture, try again";
 
 pub fn render(state: Rc<State>, code: Option<String>) -> Dom {
     if let Some(code) = code {
@@ -19,13 +26,25 @@ pub fn render(state: Rc<State>, code: Option<String>) -> Dom {
 
     html!("empty-fragment", {
         .child(html!("kids-student-code", {
-            .child(html!("kids-student-code-input", {
-                .prop("slot", "input")
-                .prop_signal("error", state.error.signal())
-                .event(clone!(state => move |evt: events::CustomInput| {
-                    submit_code(Rc::clone(&state), evt.value());
-                }))
-            }))
+            .child_signal(state.roster.signal_cloned().map(clone!(state => move |roster| {
+                // students of sessions with a roster pick their name instead
+                match roster {
+                    Some(_) => None,
+                    None => Some(render_name_input(Rc::clone(&state))),
+                }
+            })))
+            .child_signal(state.roster.signal_cloned().map(clone!(state => move |roster| {
+                Some(match roster {
+                    Some(roster) => render_roster(Rc::clone(&state), roster),
+                    None => html!("kids-student-code-input", {
+                        .prop("slot", "input")
+                        .prop_signal("error", state.error.signal())
+                        .event(clone!(state => move |evt: events::CustomInput| {
+                            submit_code(Rc::clone(&state), evt.value());
+                        }))
+                    }),
+                })
+            })))
             .child(html!("kids-student-code-jigzi", {
                 .prop("slot", "jigzi")
                 .prop_signal("mode", state.error.signal().map(|error| {
@@ -44,13 +63,29 @@ pub fn render(state: Rc<State>, code: Option<String>) -> Dom {
                 }))
             }))
         }))
-        .child_signal(state.play_jig.signal_cloned().map(clone!(state => move|play_jig| {
-            play_jig.map(|(jig_id, settings)| {
+        .child_signal(map_ref! {
+            let play_jig = state.play_jig.signal_cloned(),
+            let item_index = state.item_index.signal() => {
+                play_jig.clone().map(|instance| (instance, *item_index))
+            }
+        }.map(clone!(state => move |play_jig| {
+            play_jig.map(|(instance, item_index)| {
+                // sessions created before assignments don't list their JIGs
+                let item_count = instance.items.len().max(1);
+                let (jig_id, settings) = match instance.items.get(item_index) {
+                    Some(item) => (
+                        item.jig_id,
+                        item.settings.clone().unwrap_or_else(|| instance.settings.clone()),
+                    ),
+                    None => (instance.jig_id, instance.settings.clone()),
+                };
                 let close = clone!(state => move || {
-                    state.play_jig.set(None);
+                    play_next_item(Rc::clone(&state), item_count);
                 });
                 let mut player_options: JigPlayerOptions = settings.into();
                 player_options.is_student = true;
+                player_options.session_instance_token = Some(instance.token);
+                player_options.completes_session_instance = item_index + 1 == item_count;
                 PlayerPopup::new(
                     jig_id.into(),
                     None,
@@ -62,3 +97,88 @@ pub fn render(state: Rc<State>, code: Option<String>) -> Dom {
         })))
     })
 }
+
+fn render_name_input(state: Rc<State>) -> Dom {
+    html!("input-wrapper", {
+        .prop("slot", "name")
+        .prop("label", STR_NAME_LABEL)
+        .child(html!("input" => HtmlInputElement, {
+            .prop("placeholder", STR_NAME_PLACEHOLDER)
+            .prop("dir", "auto")
+            .prop("maxLength", JIG_PLAYER_SESSION_DISPLAY_NAME_MAX_LEN as i32)
+            .prop("value", state.display_name.get_cloned())
+            .with_node!(input => {
+                .event(clone!(state => move |_: events::Input| {
+                    state.display_name.set(input.value());
+                }))
+            })
+        }))
+    })
+}
+
+fn render_roster(state: Rc<State>, roster: Rc<SessionRoster>) -> Dom {
+    html!("div", {
+        .prop("slot", "input")
+        .style("display", "grid")
+        .style("justify-items", "center")
+        .style("row-gap", "24px")
+        .child_signal(roster.selected.signal().map(clone!(state, roster => move |selected| {
+            Some(match selected {
+                None => html!("div", {
+                    .style("display", "grid")
+                    .style("justify-items", "center")
+                    .style("row-gap", "24px")
+                    .child(html!("h2", {
+                        .text(STR_PICK_NAME)
+                    }))
+                    .child(html!("div", {
+                        .style("display", "flex")
+                        .style("flex-wrap", "wrap")
+                        .style("justify-content", "center")
+                        .style("gap", "16px")
+                        .style("max-width", "800px")
+                        .children(roster.students.iter().map(clone!(state, roster => move |student| {
+                            let student = student.clone();
+                            html!("button-rect", {
+                                .prop("color", "blue")
+                                .prop("kind", "outline")
+                                .text(&student.nickname)
+                                .event(clone!(state, roster, student => move |_: events::Click| {
+                                    pick_student(Rc::clone(&state), Rc::clone(&roster), &student);
+                                }))
+                            })
+                        })))
+                    }))
+                }),
+                Some(_) => html!("div", {
+                    .style("display", "grid")
+                    .style("justify-items", "center")
+                    .style("row-gap", "24px")
+                    .child(html!("h2", {
+                        .text_signal(roster.wrong_picture.signal().map(|wrong_picture| {
+                            match wrong_picture {
+                                true => STR_WRONG_PICTURE,
+                                false => STR_PICK_PICTURE,
+                            }
+                        }))
+                    }))
+                    .child(html!("div", {
+                        .style("display", "grid")
+                        .style("grid-template-columns", "repeat(4, 100px)")
+                        .style("gap", "16px")
+                        .children(PicturePassword::ALL.iter().map(clone!(state, roster => move |picture| {
+                            let picture = *picture;
+                            html!("button-empty", {
+                                .style("font-size", "64px")
+                                .text(picture.emoji())
+                                .event(clone!(state, roster => move |_: events::Click| {
+                                    pick_picture(Rc::clone(&state), Rc::clone(&roster), picture);
+                                }))
+                            })
+                        })))
+                    }))
+                }),
+            })
+        })))
+    })
+}
@@ -1,11 +1,25 @@
+use std::rc::Rc;
+
 use awsm_web::loaders::helpers::AsyncLoader;
 use futures_signals::signal::Mutable;
-use shared::domain::jig::{JigId, JigPlayerSettings};
+use shared::domain::{
+    jig::player::{
+        instance::{PlayerSessionInstanceResponse, PlayerSessionInstanceRosterStudent},
+        JigPlayerSessionIndex,
+    },
+    roster::RosterStudentId,
+};
 
 pub struct State {
     pub loader: AsyncLoader,
     pub error: Mutable<bool>,
-    pub play_jig: Mutable<Option<(JigId, JigPlayerSettings)>>,
+    /// Name shown on the class leaderboard
+    pub display_name: Mutable<String>,
+    /// Set when the entered code is for a session with a roster, for the student to pick their name
+    pub roster: Mutable<Option<Rc<SessionRoster>>>,
+    pub play_jig: Mutable<Option<PlayerSessionInstanceResponse>>,
+    /// Index of the JIG of the session being played
+    pub item_index: Mutable<usize>,
 }
 
 impl State {
@@ -13,7 +27,32 @@ impl State {
         Self {
             loader: AsyncLoader::new(),
             error: Mutable::new(false),
+            display_name: Mutable::new(String::new()),
+            roster: Mutable::new(None),
             play_jig: Mutable::new(None),
+            item_index: Mutable::new(0),
         }
     }
 }
+
+pub struct SessionRoster {
+    pub index: JigPlayerSessionIndex,
+    pub students: Vec<PlayerSessionInstanceRosterStudent>,
+    /// Student who picked their name and still has to pick their picture
+    pub selected: Mutable<Option<RosterStudentId>>,
+    pub wrong_picture: Mutable<bool>,
+}
+
+impl SessionRoster {
+    pub fn new(
+        index: JigPlayerSessionIndex,
+        students: Vec<PlayerSessionInstanceRosterStudent>,
+    ) -> Rc<Self> {
+        Rc::new(Self {
+            index,
+            students,
+            selected: Mutable::new(None),
+            wrong_picture: Mutable::new(false),
+        })
+    }
+}
@@ -1,10 +1,18 @@
 use super::state::*;
 use dominator::clone;
-use shared::{api::endpoints::session, domain::session::*};
+use shared::{
+    api::endpoints::{session, user},
+    domain::{
+        session::*,
+        user::{TotpCodeRequest, TotpConfirmPath, TotpEnrollPath},
+    },
+};
 use std::rc::Rc;
 use utils::{prelude::*, storage};
 
 const STR_INVALID_COMBINATION: &str = "Invalid email or password combination";
+const STR_INVALID_CODE: &str = "Invalid code";
+const STR_NO_SSO: &str = "Single sign-on isn't set up for this email";
 
 pub fn signin_email(state: Rc<LoginPage>) {
     state.tried_to_submit.set(true);
@@ -30,6 +38,10 @@ pub fn signin_email(state: Rc<LoginPage>) {
                         let route = Route::User(UserRoute::ContinueRegistration(oauth_profile)).to_string();
                         dominator::routing::go_to_url(&route);
                     }
+                    CreateSessionResponse::TwoFactor{response, enrolled} => {
+                        storage::save_csrf_token(&response.csrf);
+                        start_two_factor(Rc::clone(&state), enrolled);
+                    }
                 }
             },
             Err(_err) => {
@@ -40,12 +52,81 @@ pub fn signin_email(state: Rc<LoginPage>) {
     }));
 }
 
-pub fn signin_google(state: Rc<LoginPage>) {
-    state.loader.load(async {
-        crate::oauth::actions::redirect(GetOAuthUrlServiceKind::Google, OAuthUrlKind::Login).await;
+/// Asks for a code from the authenticator app, setting up two-factor authentication first if needed.
+pub fn start_two_factor(state: Rc<LoginPage>, enrolled: bool) {
+    if enrolled {
+        state.two_factor.set(Some(TwoFactor::Verify));
+        return;
+    }
+
+    state.loader.load(clone!(state => async move {
+        match user::EnrollTotp::api_with_auth(TotpEnrollPath(), None).await {
+            Ok(resp) => {
+                state.two_factor.set(Some(TwoFactor::Enroll {
+                    secret: resp.secret,
+                    provisioning_uri: resp.provisioning_uri,
+                }));
+            }
+            Err(_) => {
+                let _ = web_sys::window().unwrap_ji().alert_with_message(crate::strings::STR_NOT_AUTHORIZED);
+            }
+        }
+    }));
+}
+
+pub fn submit_code(state: Rc<LoginPage>) {
+    let code = state.code.borrow().trim().to_string();
+
+    let enrolled = match &*state.two_factor.lock_ref() {
+        Some(TwoFactor::Verify) => true,
+        Some(TwoFactor::Enroll { .. }) => false,
+        _ => return,
+    };
+
+    state.loader.load(clone!(state => async move {
+        if enrolled {
+            match session::VerifyTotp::api_with_auth(VerifyTotpPath(), Some(VerifyTotpRequest { code })).await {
+                Ok(resp) => do_success(&resp.csrf),
+                Err(_) => state.code_error.set(Some(STR_INVALID_CODE)),
+            }
+        } else {
+            match user::ConfirmTotp::api_with_auth(TotpConfirmPath(), Some(TotpCodeRequest { code })).await {
+                Ok(resp) => {
+                    state.code_error.set(None);
+                    state.two_factor.set(Some(TwoFactor::RecoveryCodes {
+                        csrf: resp.csrf,
+                        codes: resp.recovery_codes,
+                    }));
+                }
+                Err(_) => state.code_error.set(Some(STR_INVALID_CODE)),
+            }
+        }
+    }));
+}
+
+pub fn signin_oauth(state: Rc<LoginPage>, service_kind: GetOAuthUrlServiceKind) {
+    state.loader.load(async move {
+        crate::oauth::actions::redirect(service_kind, OAuthUrlKind::Login).await;
     });
 }
 
+/// Signs in with the single sign-on of the school the entered email belongs to.
+pub fn signin_sso(state: Rc<LoginPage>) {
+    state.tried_to_submit.set(true);
+
+    if !state.email.email_acceptable() {
+        return;
+    }
+
+    state.loader.load(clone!(state => async move {
+        let email = state.email.get_value();
+
+        if !crate::oauth::actions::redirect_sso(email, OAuthUrlKind::Login).await {
+            state.email.set_error(STR_NO_SSO);
+        }
+    }));
+}
+
 pub fn go_register(_state: Rc<LoginPage>) {
     let route: String = Route::User(UserRoute::Register(Default::default())).into();
     dominator::routing::go_to_url(&route);
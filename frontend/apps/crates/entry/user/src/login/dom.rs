@@ -1,16 +1,30 @@
 use crate::login::send_reset_link::{SendResetLink, SendResetLinkCallbacks};
 
-use super::{actions, state::LoginPage};
+use super::{
+    actions,
+    state::{LoginPage, TwoFactor},
+};
 use dominator::{clone, html, with_node, Dom};
 use futures_signals::signal::SignalExt;
+use shared::domain::session::GetOAuthUrlServiceKind;
 use std::rc::Rc;
 use web_sys::HtmlInputElement;
 
 use utils::events;
 
 const STR_GOOGLE_LABEL: &str = "Log in with Google";
+const STR_MICROSOFT_LABEL: &str = "Log in with Microsoft";
+const STR_APPLE_LABEL: &str = "Log in with Apple";
+const STR_SSO_LABEL: &str = "Log in with your school's single sign-on";
 const STR_BASIC_TRIED_OAUTH: &str =
     "Looks like you didn't sign up with Google, try logging in with a password";
+const STR_CODE_LABEL: &str = "Code from your authenticator app *";
+const STR_VERIFY_HINT: &str =
+    "Enter the code from your authenticator app, or one of your recovery codes.";
+const STR_ENROLL_HINT: &str =
+    "Two-factor authentication is required for your account. Add this key to your authenticator app, then enter the code it shows:";
+const STR_RECOVERY_CODES_HINT: &str =
+    "Save these recovery codes somewhere safe. Each can be used once to log in without your authenticator app.";
 
 impl LoginPage {
     pub fn render(self: &Rc<Self>) -> Dom {
@@ -33,7 +47,11 @@ impl LoginPage {
             .child(html!("window-loader-block", {
                 .prop_signal("visible", state.loader.is_loading())
             }))
+            .child_signal(state.two_factor.signal_cloned().map(clone!(state => move |two_factor| {
+                two_factor.map(|two_factor| state.render_two_factor(two_factor))
+            })))
             .child(html!("user-login", {
+                .visible_signal(state.two_factor.signal_ref(Option::is_none))
                 .apply_if(state.basic_tried_oauth, |dom| {
                     dom.child(html!("p", {
                         .prop("slot", "alert")
@@ -74,7 +92,32 @@ impl LoginPage {
                         .prop("slot", "google")
                         .prop("label", STR_GOOGLE_LABEL)
                         .event(clone!(state => move |_evt:events::Click| {
-                            actions::signin_google(state.clone())
+                            actions::signin_oauth(state.clone(), GetOAuthUrlServiceKind::Google)
+                        }))
+                    }),
+                    html!("button-oauth", {
+                        .prop("slot", "oauth")
+                        .prop("provider", "microsoft")
+                        .prop("label", STR_MICROSOFT_LABEL)
+                        .event(clone!(state => move |_evt:events::Click| {
+                            actions::signin_oauth(state.clone(), GetOAuthUrlServiceKind::Microsoft)
+                        }))
+                    }),
+                    html!("button-oauth", {
+                        .prop("slot", "oauth")
+                        .prop("provider", "apple")
+                        .prop("label", STR_APPLE_LABEL)
+                        .event(clone!(state => move |_evt:events::Click| {
+                            actions::signin_oauth(state.clone(), GetOAuthUrlServiceKind::Apple)
+                        }))
+                    }),
+                    html!("button-rect", {
+                        .prop("slot", "password-forgot")
+                        .prop("kind", "text")
+                        .prop("color", "blue")
+                        .text(STR_SSO_LABEL)
+                        .event(clone!(state => move |_evt:events::Click| {
+                            actions::signin_sso(state.clone())
                         }))
                     }),
                     html!("button-rect", {
@@ -109,4 +152,77 @@ impl LoginPage {
             }))
         })
     }
+
+    fn render_two_factor(self: &Rc<Self>, two_factor: TwoFactor) -> Dom {
+        let state = self;
+
+        match two_factor {
+            TwoFactor::RecoveryCodes { csrf, codes } => html!("div", {
+                .child(html!("p", {
+                    .text(STR_RECOVERY_CODES_HINT)
+                }))
+                .child(html!("ul", {
+                    .children(codes.iter().map(|code| {
+                        html!("li", {
+                            .child(html!("code", {
+                                .text(code)
+                            }))
+                        })
+                    }))
+                }))
+                .child(html!("button-rect-icon", {
+                    .prop("color", "red")
+                    .prop("size", "regular")
+                    .prop("iconAfter", "arrow")
+                    .text(crate::strings::STR_CONTINUE)
+                    .event(move |_evt:events::Click| {
+                        actions::do_success(&csrf)
+                    })
+                }))
+            }),
+            two_factor => html!("div", {
+                .apply(|dom| match &two_factor {
+                    TwoFactor::Enroll { secret, provisioning_uri } => dom.children(vec![
+                        html!("p", {
+                            .text(STR_ENROLL_HINT)
+                        }),
+                        html!("a", {
+                            .attr("href", provisioning_uri)
+                            .child(html!("code", {
+                                .text(secret)
+                            }))
+                        }),
+                    ]),
+                    _ => dom.child(html!("p", {
+                        .text(STR_VERIFY_HINT)
+                    })),
+                })
+                .child(html!("input-wrapper", {
+                    .prop("label", STR_CODE_LABEL)
+                    .prop_signal("hint", state.code_error.signal_cloned())
+                    .prop_signal("error", state.code_error.signal_ref(|err| {
+                        err.is_some()
+                    }))
+                    .child(html!("input" => HtmlInputElement, {
+                        .with_node!(elem => {
+                            .attr("autocomplete", "one-time-code")
+                            .attr("inputmode", "numeric")
+                            .event(clone!(state => move |_:events::Input| {
+                                *state.code.borrow_mut() = elem.value();
+                            }))
+                        })
+                    }))
+                }))
+                .child(html!("button-rect-icon", {
+                    .prop("color", "red")
+                    .prop("size", "regular")
+                    .prop("iconAfter", "arrow")
+                    .text(crate::strings::STR_CONTINUE)
+                    .event(clone!(state => move |_evt:events::Click| {
+                        actions::submit_code(state.clone())
+                    }))
+                }))
+            }),
+        }
+    }
 }
@@ -6,8 +6,22 @@ use futures_signals::{
 use std::{cell::RefCell, rc::Rc};
 use utils::{routes::LoginQuery, unwrap::UnwrapJiExt};
 
+use super::actions;
 use crate::email_handler::EmailHandler;
 
+#[derive(Clone)]
+pub enum TwoFactor {
+    /// The user has to enter a code from their authenticator app.
+    Verify,
+    /// The user has to set up two-factor authentication first, by adding the secret to their authenticator app.
+    Enroll {
+        secret: String,
+        provisioning_uri: String,
+    },
+    /// Two-factor authentication was set up, the recovery codes have to be shown before continuing.
+    RecoveryCodes { csrf: String, codes: Vec<String> },
+}
+
 pub struct LoginPage {
     pub loader: AsyncLoader,
     pub email: EmailHandler,
@@ -16,10 +30,13 @@ pub struct LoginPage {
     pub reset_password_popup: Mutable<bool>,
     pub tried_to_submit: Mutable<bool>,
     pub basic_tried_oauth: bool,
+    pub two_factor: Mutable<Option<TwoFactor>>,
+    pub code: RefCell<String>,
+    pub code_error: Mutable<Option<&'static str>>,
 }
 impl LoginPage {
     pub fn new(query: LoginQuery) -> Rc<Self> {
-        Rc::new(Self {
+        let state = Rc::new(Self {
             loader: AsyncLoader::new(),
             email: EmailHandler::new(),
             password: RefCell::new("".to_string()),
@@ -27,7 +44,16 @@ impl LoginPage {
             reset_password_popup: Mutable::new(false),
             tried_to_submit: Mutable::new(false),
             basic_tried_oauth: query.basic_tried_oauth,
-        })
+            two_factor: Mutable::new(None),
+            code: RefCell::new("".to_string()),
+            code_error: Mutable::new(None),
+        });
+
+        if let Some(enrolled) = query.two_factor {
+            actions::start_two_factor(Rc::clone(&state), enrolled);
+        }
+
+        state
     }
 
     pub fn show_email_error_signal(&self) -> impl Signal<Item = Option<&'static str>> {
@@ -17,12 +17,41 @@ pub async fn redirect(service_kind: GetOAuthUrlServiceKind, url_kind: OAuthUrlKi
     }
 }
 
+/// Returns `false` if the email's school doesn't have single sign-on.
+pub async fn redirect_sso(email: String, url_kind: OAuthUrlKind) -> bool {
+    match endpoints::session::GetSsoUrl::api_no_auth(
+        GetSsoUrlPath(url_kind),
+        Some(GetSsoUrlQuery { email }),
+    )
+    .await
+    {
+        Ok(resp) => {
+            let _ = web_sys::window().unwrap_ji().location().set_href(&resp.url);
+            true
+        }
+        Err(_) => false,
+    }
+}
+
 pub async fn finalize(data: OauthData, redirect_kind: OAuthUrlKind) {
     let req = match data {
         OauthData::Google(code) => CreateSessionOAuthRequest::Google {
             code,
             redirect_kind,
         },
+        OauthData::Microsoft(code) => CreateSessionOAuthRequest::Microsoft {
+            code,
+            redirect_kind,
+        },
+        OauthData::Apple(code) => CreateSessionOAuthRequest::Apple {
+            code,
+            redirect_kind,
+        },
+        OauthData::Sso(school_id, code) => CreateSessionOAuthRequest::Sso {
+            school_id,
+            code,
+            redirect_kind,
+        },
     };
 
     let (res, status) = endpoints::session::CreateOAuth::api_no_auth_with_credentials_status(
@@ -45,6 +74,12 @@ pub async fn finalize(data: OauthData, redirect_kind: OAuthUrlKind) {
                 let route = Route::User(UserRoute::ContinueRegistration(oauth_profile)).to_string();
                 dominator::routing::go_to_url(&route);
             }
+            CreateSessionResponse::TwoFactor { response, enrolled } => {
+                storage::save_csrf_token(&response.csrf);
+                let route =
+                    Route::User(UserRoute::Login(LoginQuery::two_factor(enrolled))).to_string();
+                dominator::routing::go_to_url(&route);
+            }
         },
         Err(_) => match status {
             409 => {
@@ -51,10 +51,9 @@ impl RegisterStart {
         }));
     }
 
-    pub fn register_google(self: &Rc<Self>) {
-        self.loader.load(async {
-            crate::oauth::actions::redirect(GetOAuthUrlServiceKind::Google, OAuthUrlKind::Register)
-                .await;
+    pub fn register_oauth(self: &Rc<Self>, service_kind: GetOAuthUrlServiceKind) {
+        self.loader.load(async move {
+            crate::oauth::actions::redirect(service_kind, OAuthUrlKind::Register).await;
         });
     }
 }
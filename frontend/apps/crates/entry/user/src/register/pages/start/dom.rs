@@ -1,6 +1,7 @@
 use super::state::RegisterStart;
 use dominator::{clone, html, with_node, Dom};
 use futures_signals::signal::SignalExt;
+use shared::domain::session::GetOAuthUrlServiceKind;
 use std::rc::Rc;
 use web_sys::HtmlInputElement;
 
@@ -8,6 +9,8 @@ use crate::{register::components::footer::Footer, strings};
 use utils::events;
 
 const STR_GOOGLE_LABEL: &str = "Sign up with Google";
+const STR_MICROSOFT_LABEL: &str = "Sign up with Microsoft";
+const STR_APPLE_LABEL: &str = "Sign up with Apple";
 const STR_REGISTER_FIRST: &str = "Please register before logging in";
 
 impl RegisterStart {
@@ -61,7 +64,23 @@ impl RegisterStart {
                         .prop("slot", "google")
                         .prop("label", STR_GOOGLE_LABEL)
                         .event(clone!(state => move |_evt:events::Click| {
-                            state.register_google()
+                            state.register_oauth(GetOAuthUrlServiceKind::Google)
+                        }))
+                    }),
+                    html!("button-oauth", {
+                        .prop("slot", "oauth")
+                        .prop("provider", "microsoft")
+                        .prop("label", STR_MICROSOFT_LABEL)
+                        .event(clone!(state => move |_evt:events::Click| {
+                            state.register_oauth(GetOAuthUrlServiceKind::Microsoft)
+                        }))
+                    }),
+                    html!("button-oauth", {
+                        .prop("slot", "oauth")
+                        .prop("provider", "apple")
+                        .prop("label", STR_APPLE_LABEL)
+                        .event(clone!(state => move |_evt:events::Click| {
+                            state.register_oauth(GetOAuthUrlServiceKind::Apple)
                         }))
                     }),
                     html!("button-rect-icon", {
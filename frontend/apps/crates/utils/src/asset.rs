@@ -182,6 +182,15 @@ pub struct JigPlayerOptions {
 
     #[serde(default)]
     pub draft_or_live: DraftOrLive,
+
+    /// Token of the player session instance which points are reported to, if any.
+    #[serde(default)]
+    pub session_instance_token: Option<String>,
+
+    /// Whether finishing the JIG completes the player session instance, i.e. it's the session's
+    /// last JIG.
+    #[serde(default)]
+    pub completes_session_instance: bool,
 }
 
 impl Default for JigPlayerOptions {
@@ -210,6 +219,8 @@ impl From<JigPlayerSettings> for JigPlayerOptions {
             drag_assist: settings.drag_assist,
             is_student: false,
             draft_or_live: DraftOrLive::Live,
+            session_instance_token: None,
+            completes_session_instance: false,
         }
     }
 }
@@ -169,6 +169,11 @@ pub struct LoginQuery {
     #[serde(default)]
     #[serde(skip_serializing_if = "std::ops::Not::not")]
     pub basic_tried_oauth: bool,
+
+    /// user signed in with OAuth and has to enter a two-factor code, `true` if they already set it up
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub two_factor: Option<bool>,
 }
 
 impl LoginQuery {
@@ -184,6 +189,12 @@ impl LoginQuery {
             ..Default::default()
         }
     }
+    pub fn two_factor(enrolled: bool) -> Self {
+        Self {
+            two_factor: Some(enrolled),
+            ..Default::default()
+        }
+    }
 }
 
 impl AdminRoute {
@@ -310,9 +321,30 @@ struct JsonQuery {
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub enum OauthData {
     Google(OauthCode),
+    Microsoft(OauthCode),
+    Apple(OauthCode),
+    Sso(SchoolId, OauthCode),
 }
 pub type OauthCode = String;
 
+impl OauthData {
+    /// The provider is passed back as the `state` query param, Google doesn't set it.
+    fn from_state(state: Option<&str>, code: OauthCode) -> Self {
+        match state {
+            Some("microsoft") => Self::Microsoft(code),
+            Some("apple") => Self::Apple(code),
+            Some(state) => match state
+                .strip_prefix("sso:")
+                .and_then(|school_id| school_id.parse().ok())
+            {
+                Some(school_id) => Self::Sso(school_id, code),
+                None => Self::Google(code),
+            },
+            None => Self::Google(code),
+        }
+    }
+}
+
 impl Display for Route {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let s: String = self.into();
@@ -398,7 +430,8 @@ impl Route {
 
             ["user", "register-oauth"] => {
                 if let Some(code) = params_map.get("code") {
-                    let data = OauthData::Google(code);
+                    let state = params_map.get("state");
+                    let data = OauthData::from_state(state.as_deref(), code);
                     Self::User(UserRoute::RegisterOauth(data))
                 } else {
                     Self::User(UserRoute::NoAuth)
@@ -406,7 +439,8 @@ impl Route {
             }
             ["user", "login-oauth"] => {
                 if let Some(code) = params_map.get("code") {
-                    let data = OauthData::Google(code);
+                    let state = params_map.get("state");
+                    let data = OauthData::from_state(state.as_deref(), code);
                     Self::User(UserRoute::LoginOauth(data))
                 } else {
                     Self::User(UserRoute::NoAuth)
@@ -40,6 +40,10 @@ export class _ extends LitElement {
                     color: var(--dark-blue-4);
                     text-align: center;
                 }
+                ::slotted(input-wrapper) {
+                    justify-self: center;
+                    width: 400px;
+                }
                 ::slotted(kids-student-code-input) {
                     justify-self: center;
                     width: fit-content;
@@ -64,6 +68,7 @@ export class _ extends LitElement {
                     <img-ui path="core/page-header/logo.svg"></img-ui>
                 </header>
                 <h1>${STR_TYPE_THE_CODE}</h1>
+                <slot name="name"></slot>
                 <slot name="input"></slot>
             </main>
             <div class="jigzi-wrapper">
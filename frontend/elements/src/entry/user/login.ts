@@ -33,6 +33,9 @@ export class _ extends LitElement {
                 ::slotted([slot="input"]) {
                     margin-top: 16px;
                 }
+                ::slotted([slot="oauth"]) {
+                    margin-top: 16px;
+                }
                 ::slotted([slot="password-forgot"]) {
                     text-align: end;
 /* <<<<<<< HEAD:frontend/elements/src/entry/user/login/pages/landing.ts
@@ -115,6 +118,7 @@ export class _ extends LitElement {
 
                 <div class="inside-wrapper">
                     <slot name="google"></slot>
+                    <slot name="oauth"></slot>
 
                     <or-divider></or-divider>
 
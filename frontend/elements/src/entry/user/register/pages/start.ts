@@ -32,6 +32,9 @@ export class _ extends LitElement {
                 ::slotted([slot="passwordreminder"]) {
                     text-align: end;
                 }
+                ::slotted([slot="oauth"]) {
+                    margin-top: 16px;
+                }
                 ::slotted([slot="submit"]) {
                     margin-top: 30px;
                     margin-bottom: 16px;
@@ -80,6 +83,7 @@ export class _ extends LitElement {
                 <h1>${STR_TITLE}</h1>
                 <div class="inside-wrapper">
                     <slot name="google"></slot>
+                    <slot name="oauth"></slot>
                     <or-divider slot="divider"></or-divider>
                     <form
                         @submit=${(evt: Event) => {
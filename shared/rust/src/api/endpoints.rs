@@ -84,6 +84,9 @@ pub mod course;
 /// Billing endpoints
 pub mod billing;
 
+/// Sitemap endpoints
+pub mod sitemap;
+
 /// Account endpoints
 pub mod account;
 
@@ -134,3 +137,15 @@ impl PathPart for i16 {
         self.to_string()
     }
 }
+
+/// Class roster endpoints
+pub mod roster;
+
+/// Outbound webhook endpoints
+pub mod webhook;
+
+/// In-app notification endpoints
+pub mod notification;
+
+/// Asset review endpoints
+pub mod review;
@@ -1,7 +1,8 @@
 use super::ApiEndpoint;
 use crate::domain::billing::{
     CreateSchoolAccountRequest, GetSchoolAccountPath, GetSchoolAccountResponse, SchoolAccountPath,
-    SchoolId, SchoolName, SchoolNamePath,
+    SchoolId, SchoolName, SchoolNamePath, SchoolSsoConfig, SchoolSsoPath, UpsertSchoolSsoRequest,
+    VerifySchoolSsoPath,
 };
 use crate::{api::Method, error::EmptyError};
 
@@ -37,3 +38,65 @@ impl ApiEndpoint for GetSchoolAccount {
 
 /// Update a school account
 pub struct UpdateSchoolAccount;
+
+/// Get a school's single sign-on configuration.
+///
+/// Only the school account's admins can see it.
+///
+/// # Errors
+/// * [`404 - NotFound`](http::StatusCode::NOT_FOUND) if the school doesn't exist, or doesn't have single sign-on.
+pub struct GetSchoolSso;
+impl ApiEndpoint for GetSchoolSso {
+    type Path = SchoolSsoPath;
+    type Req = ();
+    type Res = SchoolSsoConfig;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Get;
+}
+
+/// Set up or update single sign-on for a school.
+///
+/// Only the school account's admins can do this.
+///
+/// # Errors
+/// * [`400 - BadRequest`](http::StatusCode::BAD_REQUEST) if the identity provider's configuration
+/// can't be discovered from the issuer, the client secret is missing when setting it up, or one of the
+/// email domains is a public email domain.
+/// * [`409 - Conflict`](http::StatusCode::CONFLICT) if another school already verified one of the email domains.
+pub struct UpsertSchoolSso;
+impl ApiEndpoint for UpsertSchoolSso {
+    type Path = SchoolSsoPath;
+    type Req = UpsertSchoolSsoRequest;
+    type Res = ();
+    type Err = EmptyError;
+    const METHOD: Method = Method::Put;
+}
+
+/// Remove single sign-on from a school.
+///
+/// Teachers who signed in with it can still sign in with a password, by resetting it.
+pub struct DeleteSchoolSso;
+impl ApiEndpoint for DeleteSchoolSso {
+    type Path = SchoolSsoPath;
+    type Req = ();
+    type Res = ();
+    type Err = EmptyError;
+    const METHOD: Method = Method::Delete;
+}
+
+/// Verify the email domains of a school's single sign-on, by looking up their DNS TXT records.
+///
+/// Domains which don't have the school's [`verification_record`](SchoolSsoConfig::verification_record) yet
+/// stay unverified, so this can be retried once the records are added.
+///
+/// # Errors
+/// * [`404 - NotFound`](http::StatusCode::NOT_FOUND) if the school doesn't exist, or doesn't have single sign-on.
+/// * [`409 - Conflict`](http::StatusCode::CONFLICT) if another school already verified one of the email domains.
+pub struct VerifySchoolSsoDomains;
+impl ApiEndpoint for VerifySchoolSsoDomains {
+    type Path = VerifySchoolSsoPath;
+    type Req = ();
+    type Res = SchoolSsoConfig;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Post;
+}
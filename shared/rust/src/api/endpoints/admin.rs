@@ -1,15 +1,24 @@
 use super::ApiEndpoint;
 use crate::domain::admin::{
-    AdminSchoolNamesPath, AdminVerifySchoolNamePath, ImportSchoolNamesPath, InviteSchoolUsersPath,
-    InviteSchoolUsersRequest, InviteSchoolUsersResponse, SearchSchoolNamesParams,
-    SearchSchoolNamesResponse, VerifySchoolNameRequest,
+    AdminSchoolNamesPath, AdminVerifySchoolNamePath, CreateGlossaryTermRequest,
+    CurationDashboardPath, CurationDashboardQuery, CurationDashboardResponse,
+    DeleteGlossaryTermPath, GlossaryTerm, GlossaryTermId, GlossaryTermPath, ImportSchoolNamesPath,
+    InviteSchoolUsersPath, InviteSchoolUsersRequest, InviteSchoolUsersResponse,
+    ListGlossaryTermsResponse, OverrideTranslatedDescriptionPath,
+    OverrideTranslatedDescriptionRequest, SearchSchoolNamesParams, SearchSchoolNamesResponse,
+    UpdateGlossaryTermPath, UpdateGlossaryTermRequest, VerifySchoolNameRequest,
 };
 use crate::{
     api::Method,
     domain::{
         admin::{ExportDataPath, ExportDataRequest},
-        billing::{CreateUpdateSubscriptionPlanRequest, SubscriptionPlanPath},
+        billing::{
+            CreatePromotionCodeRequest, CreateUpdateSubscriptionPlanRequest,
+            ListPromotionCodesResponse, PromotionCode, PromotionCodePath, SubscriptionPlanPath,
+            UpdatePromotionCodePath, UpdatePromotionCodeRequest,
+        },
         session::{ImpersonatePath, NewSessionResponse},
+        CreateResponse,
     },
     error::{ApiError, EmptyError},
 };
@@ -83,3 +92,93 @@ impl ApiEndpoint for InviteUsers {
     type Err = EmptyError;
     const METHOD: Method = Method::Post;
 }
+
+/// Create a promotion code and its Stripe coupon
+pub struct CreatePromotionCode;
+impl ApiEndpoint for CreatePromotionCode {
+    type Path = PromotionCodePath;
+    type Req = CreatePromotionCodeRequest;
+    type Res = PromotionCode;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Post;
+}
+
+/// List promotion codes
+pub struct ListPromotionCodes;
+impl ApiEndpoint for ListPromotionCodes {
+    type Path = PromotionCodePath;
+    type Req = ();
+    type Res = ListPromotionCodesResponse;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Get;
+}
+
+/// Update whether a promotion code is active or when it expires
+pub struct UpdatePromotionCode;
+impl ApiEndpoint for UpdatePromotionCode {
+    type Path = UpdatePromotionCodePath;
+    type Req = UpdatePromotionCodeRequest;
+    type Res = ();
+    type Err = EmptyError;
+    const METHOD: Method = Method::Patch;
+}
+
+/// List curation work across JIGs, resources, playlists and courses, oldest status change first.
+pub struct CurationDashboard;
+impl ApiEndpoint for CurationDashboard {
+    type Path = CurationDashboardPath;
+    type Req = CurationDashboardQuery;
+    type Res = CurationDashboardResponse;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Get;
+}
+
+/// List the translation glossary.
+pub struct ListGlossaryTerms;
+impl ApiEndpoint for ListGlossaryTerms {
+    type Path = GlossaryTermPath;
+    type Req = ();
+    type Res = ListGlossaryTermsResponse;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Get;
+}
+
+/// Add a term to the translation glossary.
+pub struct CreateGlossaryTerm;
+impl ApiEndpoint for CreateGlossaryTerm {
+    type Path = GlossaryTermPath;
+    type Req = CreateGlossaryTermRequest;
+    type Res = CreateResponse<GlossaryTermId>;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Post;
+}
+
+/// Update a term in the translation glossary.
+pub struct UpdateGlossaryTerm;
+impl ApiEndpoint for UpdateGlossaryTerm {
+    type Path = UpdateGlossaryTermPath;
+    type Req = UpdateGlossaryTermRequest;
+    type Res = GlossaryTerm;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Patch;
+}
+
+/// Remove a term from the translation glossary.
+pub struct DeleteGlossaryTerm;
+impl ApiEndpoint for DeleteGlossaryTerm {
+    type Path = DeleteGlossaryTermPath;
+    type Req = ();
+    type Res = ();
+    type Err = EmptyError;
+    const METHOD: Method = Method::Delete;
+}
+
+/// Override entries of an asset's translated description.
+pub struct OverrideTranslatedDescription;
+impl ApiEndpoint for OverrideTranslatedDescription {
+    type Path = OverrideTranslatedDescriptionPath;
+    type Req = OverrideTranslatedDescriptionRequest;
+    type Res = ();
+    type Err = EmptyError;
+    const METHOD: Method = Method::Patch;
+}
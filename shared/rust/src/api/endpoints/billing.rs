@@ -1,6 +1,8 @@
 use super::ApiEndpoint;
 use crate::domain::billing::{
-    CreateSetupIntentPath, CreateSetupIntentRequest, SubscriptionPlansResponse,
+    BillingNotificationPreferences, BillingNotificationPreferencesPath, CreateSetupIntentPath,
+    CreateSetupIntentRequest, SubscriptionPlansResponse, ValidatePromotionCodePath,
+    ValidatePromotionCodeQuery, ValidatePromotionCodeResponse,
 };
 use crate::{
     api::Method,
@@ -40,3 +42,38 @@ impl ApiEndpoint for GetSubscriptionPlans {
     type Err = EmptyError;
     const METHOD: Method = Method::Get;
 }
+
+/// Get the current users billing notification email preferences
+pub struct GetBillingNotificationPreferences;
+impl ApiEndpoint for GetBillingNotificationPreferences {
+    type Path = BillingNotificationPreferencesPath;
+    type Req = ();
+    type Res = BillingNotificationPreferences;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Get;
+}
+
+/// Update the current users billing notification email preferences
+pub struct UpdateBillingNotificationPreferences;
+impl ApiEndpoint for UpdateBillingNotificationPreferences {
+    type Path = BillingNotificationPreferencesPath;
+    type Req = BillingNotificationPreferences;
+    type Res = ();
+    type Err = EmptyError;
+    const METHOD: Method = Method::Put;
+}
+
+/// Validate a promotion code for a plan and preview the discounted price
+///
+/// # Errors
+/// * [`NotFound`](http::StatusCode::NOT_FOUND) if the plan or the code does not exist.
+/// * [`BadRequest`](http::StatusCode::BAD_REQUEST) if the code can't be redeemed for the plan, or
+///   has already been redeemed by the users account.
+pub struct ValidatePromotionCode;
+impl ApiEndpoint for ValidatePromotionCode {
+    type Path = ValidatePromotionCodePath;
+    type Req = ValidatePromotionCodeQuery;
+    type Res = ValidatePromotionCodeResponse;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Get;
+}
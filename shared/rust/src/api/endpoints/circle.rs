@@ -4,9 +4,9 @@ use crate::{
         circle::{
             BrowseMembersResponse, Circle, CircleBrowseMembersPath, CircleBrowsePath,
             CircleBrowseQuery, CircleBrowseResponse, CircleCreatePath, CircleCreateRequest,
-            CircleDeletePath, CircleGetPath, CircleId, CircleRemoveMemberPath, CircleSearchPath,
-            CircleSearchQuery, CircleSearchResponse, CircleUpdateRequest, JoinCirclePath,
-            LeaveCirclePath, UpdateCirclePath,
+            CircleDeletePath, CircleGetPath, CircleId, CircleInvitePath, CircleRemoveMemberPath,
+            CircleSearchPath, CircleSearchQuery, CircleSearchResponse, CircleUpdateRequest,
+            JoinCirclePath, LeaveCirclePath, UpdateCirclePath,
         },
         CreateResponse,
     },
@@ -15,6 +15,12 @@ use crate::{
 
 use super::ApiEndpoint;
 
+/// Endpoints for the shared libraries of Circles.
+pub mod library;
+
+/// Endpoints for discussions in Circles.
+pub mod post;
+
 /// Create a Circle.
 ///
 /// # Authorization
@@ -138,6 +144,26 @@ impl ApiEndpoint for RemoveMember {
     const METHOD: Method = Method::Delete;
 }
 
+/// Invite a user to a Circle, which sends them a notification.
+///
+/// A user is only notified once until they join the Circle, however often they're invited.
+///
+/// # Authorization
+/// * Members of the Circle
+///
+/// # Errors
+/// * [`400 - BadRequest`](http::StatusCode::BAD_REQUEST) if the user is already a member.
+/// * [`403 - Forbidden`](http::StatusCode::FORBIDDEN) if the requesting user isn't a member.
+/// * [`404 - NotFound`](http::StatusCode::NOT_FOUND) if the Circle or user does not exist.
+pub struct Invite;
+impl ApiEndpoint for Invite {
+    type Req = ();
+    type Res = ();
+    type Path = CircleInvitePath;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Post;
+}
+
 /// Browse members of a Circle.
 pub struct BrowseMembers;
 impl ApiEndpoint for BrowseMembers {
@@ -0,0 +1,90 @@
+use crate::{
+    api::{ApiEndpoint, Method},
+    domain::{
+        circle::library::{
+            CircleLibraryItemId, CircleLibraryListPath, CircleLibraryListQuery,
+            CircleLibraryListResponse, CircleLibraryPinPath, CircleLibraryPinRequest,
+            CircleLibraryUnpinPath, CircleLibraryUpdatePath, CircleLibraryUpdateRequest,
+        },
+        CreateResponse,
+    },
+    error::EmptyError,
+};
+
+/// Pin a JIG, resource, playlist or course into the library of a Circle.
+///
+/// # Authorization
+/// * Members and the creator of the Circle
+///
+/// # Errors
+/// * [`400 - BadRequest`](http::StatusCode::BAD_REQUEST) if the note is too long.
+/// * [`401 - Unauthorized`](http::StatusCode::UNAUTHORIZED) if authorization is not valid.
+/// * [`403 - Forbidden`](http::StatusCode::FORBIDDEN) if the user isn't a member of the Circle.
+/// * [`404 - NotFound`](http::StatusCode::NOT_FOUND) if the Circle or the asset does not exist.
+/// * [`409 - Conflict`](http::StatusCode::CONFLICT) if the asset is already pinned into the library.
+pub struct Pin;
+impl ApiEndpoint for Pin {
+    type Req = CircleLibraryPinRequest;
+    type Res = CreateResponse<CircleLibraryItemId>;
+    type Path = CircleLibraryPinPath;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Post;
+}
+
+/// Browse the library of a Circle, most recently pinned first.
+///
+/// Pinned assets can be copied with the clone endpoint of their asset type.
+///
+/// # Authorization
+/// * Members and the creator of the Circle
+///
+/// # Errors
+/// * [`401 - Unauthorized`](http::StatusCode::UNAUTHORIZED) if authorization is not valid.
+/// * [`403 - Forbidden`](http::StatusCode::FORBIDDEN) if the user isn't a member of the Circle.
+/// * [`404 - NotFound`](http::StatusCode::NOT_FOUND) if the Circle does not exist.
+pub struct List;
+impl ApiEndpoint for List {
+    type Req = CircleLibraryListQuery;
+    type Res = CircleLibraryListResponse;
+    type Path = CircleLibraryListPath;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Get;
+}
+
+/// Edit the note of a library item.
+///
+/// # Authorization
+/// * The member who pinned the asset
+///
+/// # Errors
+/// * [`400 - BadRequest`](http::StatusCode::BAD_REQUEST) if the note is too long.
+/// * [`401 - Unauthorized`](http::StatusCode::UNAUTHORIZED) if authorization is not valid.
+/// * [`403 - Forbidden`](http::StatusCode::FORBIDDEN) if the user didn't pin the asset.
+/// * [`404 - NotFound`](http::StatusCode::NOT_FOUND) if the library item does not exist.
+pub struct Update;
+impl ApiEndpoint for Update {
+    type Req = CircleLibraryUpdateRequest;
+    type Res = ();
+    type Path = CircleLibraryUpdatePath;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Patch;
+}
+
+/// Remove an asset from the library of a Circle. The asset itself is left untouched.
+///
+/// # Authorization
+/// * The member who pinned the asset
+/// * The creator of the Circle, who moderates it, or an admin
+///
+/// # Errors
+/// * [`401 - Unauthorized`](http::StatusCode::UNAUTHORIZED) if authorization is not valid.
+/// * [`403 - Forbidden`](http::StatusCode::FORBIDDEN) if the user can't unpin the asset.
+/// * [`404 - NotFound`](http::StatusCode::NOT_FOUND) if the library item does not exist.
+pub struct Unpin;
+impl ApiEndpoint for Unpin {
+    type Req = ();
+    type Res = ();
+    type Path = CircleLibraryUnpinPath;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Delete;
+}
@@ -0,0 +1,125 @@
+use crate::{
+    api::{ApiEndpoint, Method},
+    domain::{
+        circle::post::{
+            CirclePostCreatePath, CirclePostCreateRequest, CirclePostDeletePath, CirclePostId,
+            CirclePostListPath, CirclePostListQuery, CirclePostListResponse, CirclePostReactPath,
+            CirclePostReactRequest, CirclePostRepliesPath, CirclePostUpdatePath,
+            CirclePostUpdateRequest,
+        },
+        CreateResponse,
+    },
+    error::EmptyError,
+};
+
+/// Write a post in a Circle, either starting a thread or replying to one.
+///
+/// # Authorization
+/// * Members and the creator of the Circle
+///
+/// # Errors
+/// * [`400 - BadRequest`](http::StatusCode::BAD_REQUEST) if the text is empty or too long, too many assets are
+///   attached, or the post replies to a reply or a thread of another Circle.
+/// * [`401 - Unauthorized`](http::StatusCode::UNAUTHORIZED) if authorization is not valid.
+/// * [`403 - Forbidden`](http::StatusCode::FORBIDDEN) if the user isn't a member of the Circle.
+/// * [`404 - NotFound`](http::StatusCode::NOT_FOUND) if the Circle or an attached asset does not exist.
+pub struct Create;
+impl ApiEndpoint for Create {
+    type Req = CirclePostCreateRequest;
+    type Res = CreateResponse<CirclePostId>;
+    type Path = CirclePostCreatePath;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Post;
+}
+
+/// List the threads of a Circle, newest first.
+///
+/// # Authorization
+/// * Members and the creator of the Circle
+///
+/// # Errors
+/// * [`401 - Unauthorized`](http::StatusCode::UNAUTHORIZED) if authorization is not valid.
+/// * [`403 - Forbidden`](http::StatusCode::FORBIDDEN) if the user isn't a member of the Circle.
+/// * [`404 - NotFound`](http::StatusCode::NOT_FOUND) if the Circle does not exist.
+pub struct List;
+impl ApiEndpoint for List {
+    type Req = CirclePostListQuery;
+    type Res = CirclePostListResponse;
+    type Path = CirclePostListPath;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Get;
+}
+
+/// List the replies to a thread, oldest first.
+///
+/// # Authorization
+/// * Members and the creator of the Circle
+///
+/// # Errors
+/// * [`401 - Unauthorized`](http::StatusCode::UNAUTHORIZED) if authorization is not valid.
+/// * [`403 - Forbidden`](http::StatusCode::FORBIDDEN) if the user isn't a member of the Circle.
+/// * [`404 - NotFound`](http::StatusCode::NOT_FOUND) if the Circle or thread does not exist.
+pub struct Replies;
+impl ApiEndpoint for Replies {
+    type Req = CirclePostListQuery;
+    type Res = CirclePostListResponse;
+    type Path = CirclePostRepliesPath;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Get;
+}
+
+/// Edit a post.
+///
+/// # Authorization
+/// * The author of the post
+///
+/// # Errors
+/// * [`400 - BadRequest`](http::StatusCode::BAD_REQUEST) if the text is empty or too long, or too many assets are attached.
+/// * [`401 - Unauthorized`](http::StatusCode::UNAUTHORIZED) if authorization is not valid.
+/// * [`403 - Forbidden`](http::StatusCode::FORBIDDEN) if the user didn't write the post.
+/// * [`404 - NotFound`](http::StatusCode::NOT_FOUND) if the post or an attached asset does not exist.
+pub struct Update;
+impl ApiEndpoint for Update {
+    type Req = CirclePostUpdateRequest;
+    type Res = ();
+    type Path = CirclePostUpdatePath;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Patch;
+}
+
+/// Delete a post, along with its replies.
+///
+/// # Authorization
+/// * The author of the post
+/// * The creator of the Circle, who moderates it, or an admin
+///
+/// # Errors
+/// * [`401 - Unauthorized`](http::StatusCode::UNAUTHORIZED) if authorization is not valid.
+/// * [`403 - Forbidden`](http::StatusCode::FORBIDDEN) if the user can't delete the post.
+/// * [`404 - NotFound`](http::StatusCode::NOT_FOUND) if the post does not exist.
+pub struct Delete;
+impl ApiEndpoint for Delete {
+    type Req = ();
+    type Res = ();
+    type Path = CirclePostDeletePath;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Delete;
+}
+
+/// React to a post, or remove the user's reaction.
+///
+/// # Authorization
+/// * Members and the creator of the Circle
+///
+/// # Errors
+/// * [`401 - Unauthorized`](http::StatusCode::UNAUTHORIZED) if authorization is not valid.
+/// * [`403 - Forbidden`](http::StatusCode::FORBIDDEN) if the user isn't a member of the Circle.
+/// * [`404 - NotFound`](http::StatusCode::NOT_FOUND) if the post does not exist.
+pub struct React;
+impl ApiEndpoint for React {
+    type Req = CirclePostReactRequest;
+    type Res = ();
+    type Path = CirclePostReactPath;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Put;
+}
@@ -14,6 +14,9 @@ use crate::{
     error::{EmptyError, MetadataNotFound},
 };
 
+/// Endpoints for course curation.
+pub mod curation;
+
 pub mod unit;
 
 use super::ApiEndpoint;
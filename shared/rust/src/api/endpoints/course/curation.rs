@@ -0,0 +1,58 @@
+//! routes for the course curation by admin
+
+use crate::{
+    api::Method,
+    domain::{
+        course::curation::{
+            CommentId, CourseCurationCommentCreatePath, CourseCurationCommentGetPath,
+            CourseCurationCommentRequest, CourseCurationCommentResponse, CourseCurationData,
+            CourseCurationPath, CourseCurationUpdatePath, CourseCurationUpdateRequest,
+        },
+        CreateResponse,
+    },
+    error::EmptyError,
+};
+
+use super::ApiEndpoint;
+
+/// Get a curation data by Course ID.
+pub struct GetCuration;
+impl ApiEndpoint for GetCuration {
+    type Req = ();
+    type Res = CourseCurationData;
+    type Path = CourseCurationPath;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Get;
+}
+
+/// Update a curation data by Course ID.
+///
+/// Status changes must follow [`CurationStatus::can_transition_to`](crate::domain::asset::CurationStatus::can_transition_to).
+pub struct UpdateCuration;
+impl ApiEndpoint for UpdateCuration {
+    type Req = CourseCurationUpdateRequest;
+    type Res = ();
+    type Path = CourseCurationUpdatePath;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Patch;
+}
+
+/// Submit a comment by Course ID.
+pub struct CreateComment;
+impl ApiEndpoint for CreateComment {
+    type Req = CourseCurationCommentRequest;
+    type Res = CreateResponse<CommentId>;
+    type Path = CourseCurationCommentCreatePath;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Post;
+}
+
+/// Get a comment by comment ID.
+pub struct GetComment;
+impl ApiEndpoint for GetComment {
+    type Req = ();
+    type Res = CourseCurationCommentResponse;
+    type Path = CourseCurationCommentGetPath;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Get;
+}
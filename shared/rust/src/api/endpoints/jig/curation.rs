@@ -25,6 +25,8 @@ impl ApiEndpoint for GetCuration {
 }
 
 /// Update a curation data by JIG ID.
+///
+/// Status changes must follow [`CurationStatus::can_transition_to`](crate::domain::asset::CurationStatus::can_transition_to).
 pub struct UpdateCuration;
 impl ApiEndpoint for UpdateCuration {
     type Req = JigCurationUpdateRequest;
@@ -1,9 +1,13 @@
 use crate::{
     api::{ApiEndpoint, Method},
     domain::jig::player::{
+        instance::{JigPlayerSessionInstancesPath, JigPlayerSessionInstancesResponse},
         JigPlayCountPath, JigPlayCountResponse, JigPlayerSessionCreatePath,
-        JigPlayerSessionCreateRequest, JigPlayerSessionCreateResponse, JigPlayerSessionListPath,
-        JigPlayerSessionListResponse,
+        JigPlayerSessionCreateRequest, JigPlayerSessionCreateResponse, JigPlayerSessionExtendPath,
+        JigPlayerSessionExtendRequest, JigPlayerSessionExtendResponse,
+        JigPlayerSessionLeaderboardPath, JigPlayerSessionLeaderboardQuery,
+        JigPlayerSessionLeaderboardResponse, JigPlayerSessionListPath,
+        JigPlayerSessionListResponse, JigPlayerSessionRevokePath,
     },
     error::EmptyError,
 };
@@ -14,13 +18,20 @@ use crate::{
 ///
 /// 1. Author/admin creates a player session using [`POST /v1/jig/player`](Create)
 ///     * This is represented by a *session code/index*
-/// 2. Unauthed user instantiates the player session. This creates an instance of a session. [`POST /v1/jig/player/session`](instance::Create) returns:
+///     * Sessions made up of several JIGs are created as assignments using [`POST /v1/jig/player/assignment`](assignment::Create)
+/// 2. If the session has a roster, the unauthed user lists its students and picks their name. [`GET /v1/jig/player/{index}/roster`](instance::Roster)
+/// 3. Unauthed user instantiates the player session. This creates an instance of a session. [`POST /v1/jig/player/session`](instance::Create) returns:
 ///     * A short lived token, which identifies the guest user and the session instance.
 ///     * The player session settings.
 ///     * `JigId` of the JIG on which the session was created.
-/// 3. Unauthed user posts short lived token to complete the instance. [`POST /v1/jig/player/session/complete`](instance::Complete)
+/// 4. Unauthed user posts the points scored in each module with the short lived token. [`POST /v1/jig/player/instance/points`](instance::Points)
+/// 5. Unauthed user posts short lived token to complete the instance. [`POST /v1/jig/player/session/complete`](instance::Complete)
 ///     * This increments the play count of the jig.
-///     * Deletes the completed instance from the DB.
+///     * Marks the instance as completed and returns its results.
+/// 6. The creator of the player session lists the results of every instance. [`GET /v1/jig/player/{index}/instances`](Instances)
+///     * Or polls the ranked results while the session is being played. [`GET /v1/jig/player/{index}/leaderboard`](Leaderboard)
+/// 7. The creator can extend the session, [`POST /v1/jig/player/{index}/extend`](Extend), or revoke it early. [`POST /v1/jig/player/{index}/revoke`](Revoke)
+///     * Expired sessions can't be joined, and their instance tokens stop working.
 ///
 /// The hierarchy here is Jig -> Player Session -> Session Instance, where each arrow is a one-to-many mapping.
 ///
@@ -28,7 +39,7 @@ use crate::{
 ///
 /// # Errors
 ///
-/// * [`400 - BadRequest`](http::StatusCode::BAD_REQUEST) if the request is malformed.
+/// * [`400 - BadRequest`](http::StatusCode::BAD_REQUEST) if the request is malformed, or the duration is zero or longer than sessions can be valid for.
 /// * [`401 - Unauthorized`](http::StatusCode::UNAUTHORIZED) if authorization is not valid.
 /// * [`403 - Forbidden`](http::StatusCode::FORBIDDEN) if the user does not have sufficient permission to perform the action.
 /// * ['404 - NotFound'](http::StatusCode::NOT_FOUND) if the jig does not exist.
@@ -79,6 +90,163 @@ impl ApiEndpoint for PlayCount {
     const METHOD: Method = Method::Get;
 }
 
+/// List the results of every instance of a player session.
+///
+/// # Authorization
+/// * The creator of the player session, or an admin.
+///
+/// # Errors
+///
+/// * [`401 - Unauthorized`](http::StatusCode::UNAUTHORIZED) if authorization is not valid.
+/// * [`403 - Forbidden`](http::StatusCode::FORBIDDEN) if the user did not create the player session.
+/// * ['404 - NotFound'](http::StatusCode::NOT_FOUND) if the player session does not exist.
+pub struct Instances;
+impl ApiEndpoint for Instances {
+    type Path = JigPlayerSessionInstancesPath;
+    type Req = ();
+    type Res = JigPlayerSessionInstancesResponse;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Get;
+}
+
+/// Leaderboard of a player session, for showing live during class.
+///
+/// Pass the returned `cursor` as `since` to only get the leaderboard when it changed since the
+/// previous request.
+///
+/// # Authorization
+/// * The creator of the player session, or an admin.
+///
+/// # Errors
+///
+/// * [`400 - BadRequest`](http::StatusCode::BAD_REQUEST) if the request is malformed.
+/// * [`401 - Unauthorized`](http::StatusCode::UNAUTHORIZED) if authorization is not valid.
+/// * [`403 - Forbidden`](http::StatusCode::FORBIDDEN) if the user did not create the player session.
+/// * ['404 - NotFound'](http::StatusCode::NOT_FOUND) if the player session does not exist.
+pub struct Leaderboard;
+impl ApiEndpoint for Leaderboard {
+    type Path = JigPlayerSessionLeaderboardPath;
+    type Req = JigPlayerSessionLeaderboardQuery;
+    type Res = JigPlayerSessionLeaderboardResponse;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Get;
+}
+
+/// Extend a player session, so that it stays valid for longer.
+///
+/// The session can be valid for at most
+/// [`JIG_PLAYER_SESSION_VALID_DURATION_SECS`](crate::config::JIG_PLAYER_SESSION_VALID_DURATION_SECS) from now.
+/// Sessions which are already valid for longer keep their expiry.
+///
+/// # Authorization
+/// * The creator of the player session, or an admin.
+///
+/// # Errors
+///
+/// * [`400 - BadRequest`](http::StatusCode::BAD_REQUEST) if the duration is zero or longer than sessions can be valid for.
+/// * [`401 - Unauthorized`](http::StatusCode::UNAUTHORIZED) if authorization is not valid.
+/// * [`403 - Forbidden`](http::StatusCode::FORBIDDEN) if the user did not create the player session.
+/// * ['404 - NotFound'](http::StatusCode::NOT_FOUND) if the player session does not exist or has expired.
+pub struct Extend;
+impl ApiEndpoint for Extend {
+    type Path = JigPlayerSessionExtendPath;
+    type Req = JigPlayerSessionExtendRequest;
+    type Res = JigPlayerSessionExtendResponse;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Post;
+}
+
+/// Revoke a player session, expiring it now.
+///
+/// Its code can no longer be used to join, and players who already joined can no longer report
+/// their points. Its results are kept until expired sessions are purged.
+///
+/// # Authorization
+/// * The creator of the player session, or an admin.
+///
+/// # Errors
+///
+/// * [`401 - Unauthorized`](http::StatusCode::UNAUTHORIZED) if authorization is not valid.
+/// * [`403 - Forbidden`](http::StatusCode::FORBIDDEN) if the user did not create the player session.
+/// * ['404 - NotFound'](http::StatusCode::NOT_FOUND) if the player session does not exist or has expired.
+pub struct Revoke;
+impl ApiEndpoint for Revoke {
+    type Path = JigPlayerSessionRevokePath;
+    type Req = ();
+    type Res = ();
+    type Err = EmptyError;
+    const METHOD: Method = Method::Post;
+}
+
+/// Endpoints for assignments, i.e. player sessions made up of several JIGs.
+pub mod assignment {
+    use crate::{
+        api::{ApiEndpoint, Method},
+        domain::jig::player::assignment::{
+            JigPlayerAssignment, JigPlayerAssignmentClosePath, JigPlayerAssignmentCreatePath,
+            JigPlayerAssignmentCreateRequest, JigPlayerAssignmentListPath,
+            JigPlayerAssignmentListResponse,
+        },
+        error::EmptyError,
+    };
+
+    /// Create an assignment, returning it along with the code students enter to play it.
+    ///
+    /// # Authorization
+    /// * TokenUser
+    ///
+    /// # Errors
+    ///
+    /// * [`400 - BadRequest`](http::StatusCode::BAD_REQUEST) if neither or both of `items` and `playlistId` are given,
+    ///   the assignment closes before it opens or has already closed, the playlist has no JIGs, or the duration is zero or
+    ///   longer than sessions can be valid for.
+    /// * [`401 - Unauthorized`](http::StatusCode::UNAUTHORIZED) if authorization is not valid.
+    /// * ['404 - NotFound'](http::StatusCode::NOT_FOUND) if a JIG, the playlist or the roster does not exist.
+    /// * [`503 - ServiceUnavailable`](http::StatusCode::SERVICE_UNAVAILABLE) if no session codes are left.
+    pub struct Create;
+    impl ApiEndpoint for Create {
+        type Path = JigPlayerAssignmentCreatePath;
+        type Req = JigPlayerAssignmentCreateRequest;
+        type Res = JigPlayerAssignment;
+        type Err = EmptyError;
+        const METHOD: Method = Method::Post;
+    }
+
+    /// List the assignments created by the requesting user.
+    ///
+    /// # Authorization
+    /// * TokenUser
+    pub struct List;
+    impl ApiEndpoint for List {
+        type Path = JigPlayerAssignmentListPath;
+        type Req = ();
+        type Res = JigPlayerAssignmentListResponse;
+        type Err = EmptyError;
+        const METHOD: Method = Method::Get;
+    }
+
+    /// Close an assignment now, so that students can no longer start playing it.
+    ///
+    /// Students who already started can still finish.
+    ///
+    /// # Authorization
+    /// * The creator of the assignment, or an admin.
+    ///
+    /// # Errors
+    ///
+    /// * [`401 - Unauthorized`](http::StatusCode::UNAUTHORIZED) if authorization is not valid.
+    /// * [`403 - Forbidden`](http::StatusCode::FORBIDDEN) if the user did not create the assignment.
+    /// * ['404 - NotFound'](http::StatusCode::NOT_FOUND) if the assignment does not exist.
+    pub struct Close;
+    impl ApiEndpoint for Close {
+        type Path = JigPlayerAssignmentClosePath;
+        type Req = ();
+        type Res = JigPlayerAssignment;
+        type Err = EmptyError;
+        const METHOD: Method = Method::Post;
+    }
+}
+
 /// Endpoints for unauthed users to access jig player sessions.
 pub mod instance {
     use crate::{
@@ -86,11 +254,30 @@ pub mod instance {
         domain::jig::player::instance::{
             PlayerSessionInstanceCompletePath, PlayerSessionInstanceCompleteRequest,
             PlayerSessionInstanceCreatePath, PlayerSessionInstanceCreateRequest,
-            PlayerSessionInstanceResponse,
+            PlayerSessionInstancePointsPath, PlayerSessionInstancePointsRequest,
+            PlayerSessionInstanceResponse, PlayerSessionInstanceResult,
+            PlayerSessionInstanceRosterPath, PlayerSessionInstanceRosterResponse,
         },
         error::EmptyError,
     };
 
+    /// List the students who can join a session
+    ///
+    /// # Auth
+    /// * No auth
+    ///
+    /// # Errors
+    ///
+    /// * ['404 - NotFound'](http::StatusCode::NOT_FOUND) if the jig player session does not exist or has expired.
+    pub struct Roster;
+    impl ApiEndpoint for Roster {
+        type Path = PlayerSessionInstanceRosterPath;
+        type Req = ();
+        type Res = PlayerSessionInstanceRosterResponse;
+        type Err = EmptyError;
+        const METHOD: Method = Method::Get;
+    }
+
     /// Create a session instance
     ///
     /// # Auth
@@ -99,8 +286,11 @@ pub mod instance {
     ///
     /// # Errors
     ///
-    /// * [`400 - BadRequest`](http::StatusCode::BAD_REQUEST) if the request is malformed.
-    /// * ['404 - NotFound'](http::StatusCode::NOT_FOUND) if the jig player session does not exist.
+    /// * [`400 - BadRequest`](http::StatusCode::BAD_REQUEST) if the request is malformed, the display name is empty or too long,
+    ///   or the student is missing or not on the session's roster.
+    /// * [`403 - Forbidden`](http::StatusCode::FORBIDDEN) if the student picked the wrong picture password, the assignment
+    ///   isn't open or the student has no attempts left.
+    /// * ['404 - NotFound'](http::StatusCode::NOT_FOUND) if the jig player session does not exist or has expired.
     pub struct Create;
     impl ApiEndpoint for Create {
         type Path = PlayerSessionInstanceCreatePath;
@@ -110,8 +300,31 @@ pub mod instance {
         const METHOD: Method = Method::Post;
     }
 
+    /// Report the points scored in a module of a session instance
+    ///
+    /// # Auth
+    /// * Requires the token returned in [`Create`](Create)
+    ///
+    /// # Errors
+    ///
+    /// * [`400 - BadRequest`](http::StatusCode::BAD_REQUEST) if the module isn't part of the JIG.
+    /// * ['404 - NotFound'](http::StatusCode::NOT_FOUND) if the jig player session instance stored in the token does not exist.
+    /// * ['409 - Conflict'](http::StatusCode::CONFLICT) if the instance was already completed.
+    /// * ['410 - Gone'](http::StatusCode::GONE) if the player session expired or was revoked.
+    pub struct Points;
+    impl ApiEndpoint for Points {
+        type Path = PlayerSessionInstancePointsPath;
+        type Req = PlayerSessionInstancePointsRequest;
+        type Res = PlayerSessionInstanceResult;
+        type Err = EmptyError;
+        const METHOD: Method = Method::Post;
+    }
+
     /// Complete a session instance and update the jig play count
     ///
+    /// Returns the instance's results. Completing an instance again returns the same results
+    /// without counting another play.
+    ///
     /// # Auth
     /// * Requires the token returned in [`Create`](Create)
     ///
@@ -119,11 +332,12 @@ pub mod instance {
     ///
     /// * [`400 - BadRequest`](http::StatusCode::BAD_REQUEST) if the request is malformed.
     /// * ['404 - NotFound'](http::StatusCode::NOT_FOUND) if the jig player session instance stored in the token does not exist.
+    /// * ['410 - Gone'](http::StatusCode::GONE) if the player session expired or was revoked.
     pub struct Complete;
     impl ApiEndpoint for Complete {
         type Path = PlayerSessionInstanceCompletePath;
         type Req = PlayerSessionInstanceCompleteRequest;
-        type Res = ();
+        type Res = PlayerSessionInstanceResult;
         type Err = EmptyError;
         const METHOD: Method = Method::Post;
     }
@@ -4,7 +4,9 @@ use crate::{
     api::{ApiEndpoint, Method},
     domain::{
         jig::report::{
-            CreateJigReport, CreateJigReportPath, GetJigReportPath, JigReport, ReportId,
+            AssignJigReportPath, AssignJigReportRequest, CreateJigReport, CreateJigReportPath,
+            GetJigReportPath, JigReport, ListJigReportsPath, ListJigReportsQuery,
+            ListJigReportsResponse, ReportId, ResolveJigReportPath, ResolveJigReportRequest,
         },
         CreateResponse,
     },
@@ -32,7 +34,7 @@ impl ApiEndpoint for Create {
 ///
 /// # Authorization
 ///
-/// * Admin
+/// * One of `Admin` or `AdminAsset`
 ///
 /// # Errors
 ///
@@ -44,3 +46,53 @@ impl ApiEndpoint for Get {
     type Err = EmptyError;
     const METHOD: Method = Method::Get;
 }
+
+/// List Jig reports in the moderation queue
+///
+/// # Authorization
+///
+/// * One of `Admin` or `AdminAsset`
+pub struct List;
+impl ApiEndpoint for List {
+    type Path = ListJigReportsPath;
+    type Req = ListJigReportsQuery;
+    type Res = ListJigReportsResponse;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Get;
+}
+
+/// Assign a Jig report to a curator
+///
+/// # Authorization
+///
+/// * One of `Admin` or `AdminAsset`
+///
+/// # Errors
+///
+/// * [`BadRequest`](http::StatusCode::BAD_REQUEST) if the assignee isn't a curator or the report is resolved.
+pub struct Assign;
+impl ApiEndpoint for Assign {
+    type Path = AssignJigReportPath;
+    type Req = AssignJigReportRequest;
+    type Res = ();
+    type Err = EmptyError;
+    const METHOD: Method = Method::Patch;
+}
+
+/// Resolve a Jig report and notify the reporter
+///
+/// # Authorization
+///
+/// * One of `Admin` or `AdminAsset`
+///
+/// # Errors
+///
+/// * [`BadRequest`](http::StatusCode::BAD_REQUEST) if the report is already resolved.
+pub struct Resolve;
+impl ApiEndpoint for Resolve {
+    type Path = ResolveJigReportPath;
+    type Req = ResolveJigReportRequest;
+    type Res = ();
+    type Err = EmptyError;
+    const METHOD: Method = Method::Post;
+}
@@ -0,0 +1,81 @@
+use crate::{
+    api::Method,
+    domain::notification::{
+        NotificationListPath, NotificationListQuery, NotificationListResponse,
+        NotificationMarkReadPath, NotificationMarkReadRequest, NotificationPreferences,
+        NotificationPreferencesPath, NotificationUnreadCountPath, NotificationUnreadCountResponse,
+    },
+    error::EmptyError,
+};
+
+use super::ApiEndpoint;
+
+/// List the notifications of the requesting user, most recent first.
+///
+/// # Authorization
+/// * TokenUser
+///
+/// # Errors
+/// * [`400 - BadRequest`](http::StatusCode::BAD_REQUEST) if the page limit isn't within 1-100.
+pub struct List;
+impl ApiEndpoint for List {
+    type Req = NotificationListQuery;
+    type Res = NotificationListResponse;
+    type Path = NotificationListPath;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Get;
+}
+
+/// Mark notifications of the requesting user as read.
+///
+/// # Authorization
+/// * TokenUser
+pub struct MarkRead;
+impl ApiEndpoint for MarkRead {
+    type Req = NotificationMarkReadRequest;
+    type Res = ();
+    type Path = NotificationMarkReadPath;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Post;
+}
+
+/// Count the unread notifications of the requesting user.
+///
+/// # Authorization
+/// * TokenUser
+pub struct UnreadCount;
+impl ApiEndpoint for UnreadCount {
+    type Req = ();
+    type Res = NotificationUnreadCountResponse;
+    type Path = NotificationUnreadCountPath;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Get;
+}
+
+/// Get the notification preferences of the requesting user.
+///
+/// # Authorization
+/// * TokenUser
+pub struct GetPreferences;
+impl ApiEndpoint for GetPreferences {
+    type Req = ();
+    type Res = NotificationPreferences;
+    type Path = NotificationPreferencesPath;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Get;
+}
+
+/// Update the notification preferences of the requesting user.
+///
+/// Notifications of disabled kinds aren't created, existing notifications are kept.
+///
+/// # Authorization
+/// * TokenUser
+pub struct UpdatePreferences;
+impl ApiEndpoint for UpdatePreferences {
+    type Req = NotificationPreferences;
+    type Res = ();
+    type Path = NotificationPreferencesPath;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Put;
+}
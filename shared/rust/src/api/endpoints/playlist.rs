@@ -16,6 +16,9 @@ use crate::{
 
 use super::ApiEndpoint;
 
+/// Endpoints for playlist curation.
+pub mod curation;
+
 /// Create a Playlist and it's draft and live data copies.
 ///
 /// * New Playlists are all set to `PrivacyLevel::Unlisted` by default
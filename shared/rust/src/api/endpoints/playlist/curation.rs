@@ -0,0 +1,58 @@
+//! routes for the playlist curation by admin
+
+use crate::{
+    api::Method,
+    domain::{
+        playlist::curation::{
+            CommentId, PlaylistCurationCommentCreatePath, PlaylistCurationCommentGetPath,
+            PlaylistCurationCommentRequest, PlaylistCurationCommentResponse, PlaylistCurationData,
+            PlaylistCurationPath, PlaylistCurationUpdatePath, PlaylistCurationUpdateRequest,
+        },
+        CreateResponse,
+    },
+    error::EmptyError,
+};
+
+use super::ApiEndpoint;
+
+/// Get a curation data by Playlist ID.
+pub struct GetCuration;
+impl ApiEndpoint for GetCuration {
+    type Req = ();
+    type Res = PlaylistCurationData;
+    type Path = PlaylistCurationPath;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Get;
+}
+
+/// Update a curation data by Playlist ID.
+///
+/// Status changes must follow [`CurationStatus::can_transition_to`](crate::domain::asset::CurationStatus::can_transition_to).
+pub struct UpdateCuration;
+impl ApiEndpoint for UpdateCuration {
+    type Req = PlaylistCurationUpdateRequest;
+    type Res = ();
+    type Path = PlaylistCurationUpdatePath;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Patch;
+}
+
+/// Submit a comment by Playlist ID.
+pub struct CreateComment;
+impl ApiEndpoint for CreateComment {
+    type Req = PlaylistCurationCommentRequest;
+    type Res = CreateResponse<CommentId>;
+    type Path = PlaylistCurationCommentCreatePath;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Post;
+}
+
+/// Get a comment by comment ID.
+pub struct GetComment;
+impl ApiEndpoint for GetComment {
+    type Req = ();
+    type Res = PlaylistCurationCommentResponse;
+    type Path = PlaylistCurationCommentGetPath;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Get;
+}
@@ -4,8 +4,10 @@ use crate::{
     api::Method,
     domain::{
         resource::report::{
-            CreateResourceReport, CreateResourceReportPath, GetResourceReportPath, ReportId,
-            ResourceReport,
+            AssignResourceReportPath, AssignResourceReportRequest, CreateResourceReport,
+            CreateResourceReportPath, GetResourceReportPath, ListResourceReportsPath,
+            ListResourceReportsQuery, ListResourceReportsResponse, ReportId,
+            ResolveResourceReportPath, ResolveResourceReportRequest, ResourceReport,
         },
         CreateResponse,
     },
@@ -35,7 +37,7 @@ impl ApiEndpoint for Create {
 ///
 /// # Authorization
 ///
-/// * Admin
+/// * One of `Admin` or `AdminAsset`
 ///
 /// # Errors
 ///
@@ -47,3 +49,53 @@ impl ApiEndpoint for Get {
     type Err = EmptyError;
     const METHOD: Method = Method::Get;
 }
+
+/// List Resource reports in the moderation queue
+///
+/// # Authorization
+///
+/// * One of `Admin` or `AdminAsset`
+pub struct List;
+impl ApiEndpoint for List {
+    type Path = ListResourceReportsPath;
+    type Req = ListResourceReportsQuery;
+    type Res = ListResourceReportsResponse;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Get;
+}
+
+/// Assign a Resource report to a curator
+///
+/// # Authorization
+///
+/// * One of `Admin` or `AdminAsset`
+///
+/// # Errors
+///
+/// * [`BadRequest`](http::StatusCode::BAD_REQUEST) if the assignee isn't a curator or the report is resolved.
+pub struct Assign;
+impl ApiEndpoint for Assign {
+    type Path = AssignResourceReportPath;
+    type Req = AssignResourceReportRequest;
+    type Res = ();
+    type Err = EmptyError;
+    const METHOD: Method = Method::Patch;
+}
+
+/// Resolve a Resource report and notify the reporter
+///
+/// # Authorization
+///
+/// * One of `Admin` or `AdminAsset`
+///
+/// # Errors
+///
+/// * [`BadRequest`](http::StatusCode::BAD_REQUEST) if the report is already resolved.
+pub struct Resolve;
+impl ApiEndpoint for Resolve {
+    type Path = ResolveResourceReportPath;
+    type Req = ResolveResourceReportRequest;
+    type Res = ();
+    type Err = EmptyError;
+    const METHOD: Method = Method::Post;
+}
@@ -0,0 +1,149 @@
+use crate::{
+    api::{ApiEndpoint, Method},
+    domain::{
+        review::{
+            ReviewDeletePath, ReviewId, ReviewListPath, ReviewListQuery, ReviewListResponse,
+            ReviewReplyPath, ReviewReplyRequest, ReviewReportListPath, ReviewReportListQuery,
+            ReviewReportListResponse, ReviewReportPath, ReviewReportRequest,
+            ReviewReportResolvePath, ReviewReportResolveRequest, ReviewUpsertPath,
+            ReviewUpsertRequest,
+        },
+        CreateResponse,
+    },
+    error::EmptyError,
+};
+
+/// List the reviews of a live, public JIG, resource or playlist, most recent first.
+///
+/// Hidden reviews (see [`ReportResolve`]) aren't listed, and don't count towards the rating.
+///
+/// # Authorization
+/// * None
+///
+/// # Errors
+/// * [`400 - BadRequest`](http::StatusCode::BAD_REQUEST) if the asset type can't be reviewed.
+/// * [`404 - NotFound`](http::StatusCode::NOT_FOUND) if the asset does not exist or isn't live and public.
+pub struct List;
+impl ApiEndpoint for List {
+    type Req = ReviewListQuery;
+    type Res = ReviewListResponse;
+    type Path = ReviewListPath;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Get;
+}
+
+/// Review a live, public JIG, resource or playlist, or edit the requesting user's review of it.
+///
+/// Editing a review keeps the reply of the author of the asset.
+///
+/// # Authorization
+/// * TokenUser
+///
+/// # Errors
+/// * [`400 - BadRequest`](http::StatusCode::BAD_REQUEST) if the asset type can't be reviewed, the rating is out of range or the text is too long.
+/// * [`401 - Unauthorized`](http::StatusCode::UNAUTHORIZED) if authorization is not valid.
+/// * [`403 - Forbidden`](http::StatusCode::FORBIDDEN) if the user authored the asset.
+/// * [`404 - NotFound`](http::StatusCode::NOT_FOUND) if the asset does not exist or isn't live and public.
+pub struct Upsert;
+impl ApiEndpoint for Upsert {
+    type Req = ReviewUpsertRequest;
+    type Res = CreateResponse<ReviewId>;
+    type Path = ReviewUpsertPath;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Put;
+}
+
+/// Delete a review.
+///
+/// # Authorization
+/// * The user who wrote the review
+/// * Admin, to moderate reported reviews
+///
+/// # Errors
+/// * [`401 - Unauthorized`](http::StatusCode::UNAUTHORIZED) if authorization is not valid.
+/// * [`403 - Forbidden`](http::StatusCode::FORBIDDEN) if the user can't delete the review.
+/// * [`404 - NotFound`](http::StatusCode::NOT_FOUND) if the review does not exist.
+pub struct Delete;
+impl ApiEndpoint for Delete {
+    type Req = ();
+    type Res = ();
+    type Path = ReviewDeletePath;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Delete;
+}
+
+/// Reply to a review, replacing any previous reply.
+///
+/// # Authorization
+/// * The author of the reviewed asset
+///
+/// # Errors
+/// * [`400 - BadRequest`](http::StatusCode::BAD_REQUEST) if the text is too long.
+/// * [`401 - Unauthorized`](http::StatusCode::UNAUTHORIZED) if authorization is not valid.
+/// * [`403 - Forbidden`](http::StatusCode::FORBIDDEN) if the user isn't the author of the asset.
+/// * [`404 - NotFound`](http::StatusCode::NOT_FOUND) if the review does not exist.
+pub struct Reply;
+impl ApiEndpoint for Reply {
+    type Req = ReviewReplyRequest;
+    type Res = ();
+    type Path = ReviewReplyPath;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Put;
+}
+
+/// Report a review for abuse.
+///
+/// The review is hidden once it has [`REVIEW_REPORT_HIDE_THRESHOLD`](crate::domain::review::REVIEW_REPORT_HIDE_THRESHOLD)
+/// pending reports, until a curator resolves them.
+///
+/// # Authorization
+/// * TokenUser
+///
+/// # Errors
+/// * [`401 - Unauthorized`](http::StatusCode::UNAUTHORIZED) if authorization is not valid.
+/// * [`404 - NotFound`](http::StatusCode::NOT_FOUND) if the review does not exist.
+/// * [`409 - Conflict`](http::StatusCode::CONFLICT) if the user already reported the review.
+pub struct Report;
+impl ApiEndpoint for Report {
+    type Req = ReviewReportRequest;
+    type Res = ();
+    type Path = ReviewReportPath;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Post;
+}
+
+/// List reports of reviews in the moderation queue, oldest first.
+///
+/// # Authorization
+/// * One of `Admin` or `AdminAsset`
+///
+/// # Errors
+/// * [`401 - Unauthorized`](http::StatusCode::UNAUTHORIZED) if authorization is not valid.
+/// * [`403 - Forbidden`](http::StatusCode::FORBIDDEN) if the user does not have sufficient permission to perform the action.
+pub struct ReportList;
+impl ApiEndpoint for ReportList {
+    type Req = ReviewReportListQuery;
+    type Res = ReviewReportListResponse;
+    type Path = ReviewReportListPath;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Get;
+}
+
+/// Resolve the open reports of a review, either showing the review again or hiding it for good.
+///
+/// # Authorization
+/// * One of `Admin` or `AdminAsset`
+///
+/// # Errors
+/// * [`400 - BadRequest`](http::StatusCode::BAD_REQUEST) if the action is to contact the author.
+/// * [`401 - Unauthorized`](http::StatusCode::UNAUTHORIZED) if authorization is not valid.
+/// * [`403 - Forbidden`](http::StatusCode::FORBIDDEN) if the user does not have sufficient permission to perform the action.
+/// * [`404 - NotFound`](http::StatusCode::NOT_FOUND) if the review does not exist or has no open reports.
+pub struct ReportResolve;
+impl ApiEndpoint for ReportResolve {
+    type Req = ReviewReportResolveRequest;
+    type Res = ();
+    type Path = ReviewReportResolvePath;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Post;
+}
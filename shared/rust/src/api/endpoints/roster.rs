@@ -0,0 +1,167 @@
+use crate::{
+    api::Method,
+    domain::{
+        roster::{
+            Roster, RosterCreatePath, RosterCreateRequest, RosterDeletePath, RosterGetPath,
+            RosterId, RosterListPath, RosterListResponse, RosterUpdatePath, RosterUpdateRequest,
+        },
+        CreateResponse,
+    },
+    error::EmptyError,
+};
+
+use super::ApiEndpoint;
+
+/// Create a roster.
+///
+/// # Authorization
+/// * TokenUser
+///
+/// # Errors
+/// * [`400 - BadRequest`](http::StatusCode::BAD_REQUEST) if a name is empty or too long, or nicknames are repeated.
+/// * [`401 - Unauthorized`](http::StatusCode::UNAUTHORIZED) if authorization is not valid.
+pub struct Create;
+impl ApiEndpoint for Create {
+    type Req = RosterCreateRequest;
+    type Res = CreateResponse<RosterId>;
+    type Path = RosterCreatePath;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Post;
+}
+
+/// List the rosters of the requesting user.
+///
+/// # Authorization
+/// * TokenUser
+pub struct List;
+impl ApiEndpoint for List {
+    type Req = ();
+    type Res = RosterListResponse;
+    type Path = RosterListPath;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Get;
+}
+
+/// Get a roster by ID.
+///
+/// # Authorization
+/// * The creator of the roster
+///
+/// # Errors
+/// * [`401 - Unauthorized`](http::StatusCode::UNAUTHORIZED) if authorization is not valid.
+/// * [`404 - NotFound`](http::StatusCode::NOT_FOUND) if the roster does not exist or belongs to another user.
+pub struct Get;
+impl ApiEndpoint for Get {
+    type Req = ();
+    type Res = Roster;
+    type Path = RosterGetPath;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Get;
+}
+
+/// Update a roster.
+///
+/// # Authorization
+/// * The creator of the roster
+///
+/// # Errors
+/// * [`400 - BadRequest`](http::StatusCode::BAD_REQUEST) if the name is empty or too long.
+/// * [`401 - Unauthorized`](http::StatusCode::UNAUTHORIZED) if authorization is not valid.
+/// * [`404 - NotFound`](http::StatusCode::NOT_FOUND) if the roster does not exist or belongs to another user.
+pub struct Update;
+impl ApiEndpoint for Update {
+    type Req = RosterUpdateRequest;
+    type Res = ();
+    type Path = RosterUpdatePath;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Patch;
+}
+
+/// Delete a roster.
+///
+/// Player sessions which used the roster are kept, along with the names students joined them with.
+///
+/// # Authorization
+/// * The creator of the roster
+///
+/// # Errors
+/// * [`401 - Unauthorized`](http::StatusCode::UNAUTHORIZED) if authorization is not valid.
+/// * [`404 - NotFound`](http::StatusCode::NOT_FOUND) if the roster does not exist or belongs to another user.
+pub struct Delete;
+impl ApiEndpoint for Delete {
+    type Req = ();
+    type Res = ();
+    type Path = RosterDeletePath;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Delete;
+}
+
+/// Endpoints for the students on a roster.
+pub mod student {
+    use crate::{
+        api::{ApiEndpoint, Method},
+        domain::{
+            roster::{
+                RosterStudentCreatePath, RosterStudentCreateRequest, RosterStudentDeletePath,
+                RosterStudentId, RosterStudentUpdatePath, RosterStudentUpdateRequest,
+            },
+            CreateResponse,
+        },
+        error::EmptyError,
+    };
+
+    /// Add a student to a roster.
+    ///
+    /// # Authorization
+    /// * The creator of the roster
+    ///
+    /// # Errors
+    /// * [`400 - BadRequest`](http::StatusCode::BAD_REQUEST) if the nickname is empty, too long or already on the roster.
+    /// * [`401 - Unauthorized`](http::StatusCode::UNAUTHORIZED) if authorization is not valid.
+    /// * [`404 - NotFound`](http::StatusCode::NOT_FOUND) if the roster does not exist or belongs to another user.
+    pub struct Create;
+    impl ApiEndpoint for Create {
+        type Req = RosterStudentCreateRequest;
+        type Res = CreateResponse<RosterStudentId>;
+        type Path = RosterStudentCreatePath;
+        type Err = EmptyError;
+        const METHOD: Method = Method::Post;
+    }
+
+    /// Update a student on a roster.
+    ///
+    /// # Authorization
+    /// * The creator of the roster
+    ///
+    /// # Errors
+    /// * [`400 - BadRequest`](http::StatusCode::BAD_REQUEST) if the nickname is empty, too long or already on the roster.
+    /// * [`401 - Unauthorized`](http::StatusCode::UNAUTHORIZED) if authorization is not valid.
+    /// * [`404 - NotFound`](http::StatusCode::NOT_FOUND) if the roster or student does not exist.
+    pub struct Update;
+    impl ApiEndpoint for Update {
+        type Req = RosterStudentUpdateRequest;
+        type Res = ();
+        type Path = RosterStudentUpdatePath;
+        type Err = EmptyError;
+        const METHOD: Method = Method::Patch;
+    }
+
+    /// Remove a student from a roster.
+    ///
+    /// Results of player sessions the student joined are kept under the name they joined with.
+    ///
+    /// # Authorization
+    /// * The creator of the roster
+    ///
+    /// # Errors
+    /// * [`401 - Unauthorized`](http::StatusCode::UNAUTHORIZED) if authorization is not valid.
+    /// * [`404 - NotFound`](http::StatusCode::NOT_FOUND) if the roster or student does not exist.
+    pub struct Delete;
+    impl ApiEndpoint for Delete {
+        type Req = ();
+        type Res = ();
+        type Path = RosterStudentDeletePath;
+        type Err = EmptyError;
+        const METHOD: Method = Method::Delete;
+    }
+}
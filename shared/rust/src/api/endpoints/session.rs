@@ -2,7 +2,9 @@ use crate::{
     api::Method,
     domain::session::{
         CreateSessionOAuthPath, CreateSessionOAuthRequest, CreateSessionPath,
-        CreateSessionResponse, DeleteSessionPath, GetOAuthPath, GetOAuthUrlResponse,
+        CreateSessionResponse, DeleteSessionPath, GetOAuthPath, GetOAuthUrlResponse, GetSsoUrlPath,
+        GetSsoUrlQuery, ListActiveSessionsPath, ListActiveSessionsResponse, NewSessionResponse,
+        RevokeOtherSessionsPath, RevokeSessionPath, VerifyTotpPath, VerifyTotpRequest,
     },
     error::EmptyError,
 };
@@ -24,12 +26,20 @@ impl ApiEndpoint for Create {
 
 /// Sign in via oauth.
 ///
+/// Supports Google, Microsoft, Apple and schools' single sign-on.
+///
+/// Teachers signing in with their school's single sign-on for the first time are added to the school's account.
+///
 /// Note: If the account doesn't exist, but the oauth token is valid, it'll return a token that can be used to create an account.
 ///
+/// If a user already has the oauth user's (verified) email, the oauth account gets linked to them and they're signed in.
+///
 /// # Errors
 /// (non exhaustive list)
-/// If there is already a user with the oauth user's email,
-/// and it isn't them - [`409 - Conflict`](http::StatusCode::CONFLICT)
+/// If the user with the oauth user's email already signs in with another account from the
+/// same provider - [`409 - Conflict`](http::StatusCode::CONFLICT)
+///
+/// If the provider didn't verify the email - [`401 - Unauthorized`](http::StatusCode::UNAUTHORIZED)
 ///
 /// # Flow (login)
 /// 1. [`GET /v1/session/oauth/url/{service}/{kind}`](GetOAuthUrl)
@@ -51,8 +61,30 @@ impl ApiEndpoint for CreateOAuth {
     const METHOD: Method = Method::Post;
 }
 
+/// Finish signing in with a code from an authenticator app, or one of the user's recovery codes.
+///
+/// Replaces the session returned by [`Create`] or [`CreateOAuth`] as
+/// [`TwoFactor`](CreateSessionResponse::TwoFactor) with a standard session.
+///
+/// Note: This response *also* includes a cookie.
+///
+/// # Authorization
+/// The session returned as [`TwoFactor`](CreateSessionResponse::TwoFactor), for a user who has set up two-factor authentication
+/// # Errors
+/// [`Unauthorized`](http::StatusCode::UNAUTHORIZED) if the code is wrong, or after too many wrong codes
+pub struct VerifyTotp;
+impl ApiEndpoint for VerifyTotp {
+    type Path = VerifyTotpPath;
+    type Req = VerifyTotpRequest;
+    type Res = NewSessionResponse;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Post;
+}
+
 /// Get URL for oauth callback
 ///
+/// Apple posts the code to the API instead, which then redirects to the same page as the other providers.
+///
 /// # Flow (login/register)
 /// 1. `GET /v1/session/oauth/url/{service}/{kind}` (this route)
 /// 2. Continue from [`CreateOAuth`]
@@ -65,6 +97,23 @@ impl ApiEndpoint for GetOAuthUrl {
     const METHOD: Method = Method::Get;
 }
 
+/// Get the URL to sign in with the single sign-on of the school the email belongs to.
+///
+/// # Flow (login/register)
+/// 1. `GET /v1/session/sso/url/{kind}?email=...` (this route)
+/// 2. Continue from [`CreateOAuth`], with the school from the `state` the identity provider passes back
+///
+/// # Errors
+/// * [`404 - NotFound`](http::StatusCode::NOT_FOUND) if there's no school with single sign-on for the email's domain.
+pub struct GetSsoUrl;
+impl ApiEndpoint for GetSsoUrl {
+    type Path = GetSsoUrlPath;
+    type Req = GetSsoUrlQuery;
+    type Res = GetOAuthUrlResponse;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Get;
+}
+
 /// Delete a session (logout)
 ///
 /// # Authorization
@@ -79,3 +128,51 @@ impl ApiEndpoint for Delete {
     type Err = EmptyError;
     const METHOD: Method = Method::Delete;
 }
+
+/// List the sessions the user is signed in with, e.g. on other computers.
+///
+/// Sessions an admin uses to impersonate the user aren't listed.
+///
+/// # Authorization
+/// standard
+/// # Errors
+/// [`Unauthorized`](http::StatusCode::UNAUTHORIZED) if the authorization is invalid
+pub struct ListActive;
+impl ApiEndpoint for ListActive {
+    type Path = ListActiveSessionsPath;
+    type Req = ();
+    type Res = ListActiveSessionsResponse;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Get;
+}
+
+/// Sign out a session the user is signed in with.
+///
+/// # Authorization
+/// standard
+/// # Errors
+/// [`Unauthorized`](http::StatusCode::UNAUTHORIZED) if the authorization is invalid
+/// [`NotFound`](http::StatusCode::NOT_FOUND) if the session doesn't exist or belongs to another user
+pub struct Revoke;
+impl ApiEndpoint for Revoke {
+    type Path = RevokeSessionPath;
+    type Req = ();
+    type Res = ();
+    type Err = EmptyError;
+    const METHOD: Method = Method::Delete;
+}
+
+/// Sign out every session the user is signed in with, except the one making the request.
+///
+/// # Authorization
+/// standard
+/// # Errors
+/// [`Unauthorized`](http::StatusCode::UNAUTHORIZED) if the authorization is invalid
+pub struct RevokeOthers;
+impl ApiEndpoint for RevokeOthers {
+    type Path = RevokeOtherSessionsPath;
+    type Req = ();
+    type Res = ();
+    type Err = EmptyError;
+    const METHOD: Method = Method::Delete;
+}
@@ -0,0 +1,27 @@
+use crate::{
+    api::{ApiEndpoint, Method},
+    domain::sitemap::{
+        SitemapIndexPath, SitemapIndexResponse, SitemapPagePath, SitemapPageResponse,
+    },
+    error::EmptyError,
+};
+
+/// Get the number of sitemap pages for each kind of public content.
+pub struct Index;
+impl ApiEndpoint for Index {
+    type Path = SitemapIndexPath;
+    type Req = ();
+    type Res = SitemapIndexResponse;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Get;
+}
+
+/// Get a page of sitemap entries for a kind of public content.
+pub struct Page;
+impl ApiEndpoint for Page {
+    type Path = SitemapPagePath;
+    type Req = ();
+    type Res = SitemapPageResponse;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Get;
+}
@@ -16,10 +16,14 @@ use crate::{
     error::EmptyError,
 };
 
+mod api_token;
 mod colors;
 mod fonts;
 mod profile;
 mod public_user;
+mod totp;
+
+pub use api_token::{Create as CreateApiToken, List as ListApiTokens, Revoke as RevokeApiToken};
 
 pub use colors::{
     Create as CreateColor, Delete as DeleteColor, Get as GetColors, Update as UpdateColor,
@@ -33,9 +37,14 @@ pub use profile::{
     Create as CreateProfile, Get as Profile, Patch as PatchProfile, PatchProfileAdminData,
 };
 
+pub use totp::{
+    Confirm as ConfirmTotp, Disable as DisableTotp, Enroll as EnrollTotp, Get as GetTotp,
+    RegenerateRecoveryCodes,
+};
+
 pub use public_user::{
     BrowseFollowers, BrowseFollowing, BrowsePublicUser, BrowseUserJigs,
-    BrowseUserPlaylists as BrowsePlaylists, BrowseUserResources as BrowseResources, Follow,
+    BrowseUserPlaylists as BrowsePlaylists, BrowseUserResources as BrowseResources, Feed, Follow,
     Get as GetPublicUser, SearchPublicUser as Search, Unfollow,
 };
 
@@ -0,0 +1,53 @@
+use super::ApiEndpoint;
+
+use crate::{
+    api::method::Method,
+    domain::user::{
+        ApiTokenCreatePath, ApiTokenCreateRequest, ApiTokenCreateResponse, ApiTokenListPath,
+        ApiTokenListResponse, ApiTokenRevokePath,
+    },
+    error::EmptyError,
+};
+
+/// Create a personal API token.
+///
+/// The token is only returned once, and is used as `Authorization: Bearer <token>`.
+///
+/// # Authorization
+/// * Standard. API tokens can't be used to create more tokens.
+///
+/// # Errors
+/// * [`400 - BadRequest`](http::StatusCode::BAD_REQUEST) if the name is empty or the token would already be expired.
+/// * [`403 - Forbidden`](http::StatusCode::FORBIDDEN) if one of the scopes isn't the user's,
+/// or the request was authorized with an API token.
+pub struct Create;
+impl ApiEndpoint for Create {
+    type Req = ApiTokenCreateRequest;
+    type Res = ApiTokenCreateResponse;
+    type Path = ApiTokenCreatePath;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Post;
+}
+
+/// List the user's personal API tokens.
+pub struct List;
+impl ApiEndpoint for List {
+    type Req = ();
+    type Res = ApiTokenListResponse;
+    type Path = ApiTokenListPath;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Get;
+}
+
+/// Revoke one of the user's personal API tokens.
+///
+/// # Errors
+/// * [`404 - NotFound`](http::StatusCode::NOT_FOUND) if the token doesn't exist or belongs to another user.
+pub struct Revoke;
+impl ApiEndpoint for Revoke {
+    type Req = ();
+    type Res = ();
+    type Path = ApiTokenRevokePath;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Delete;
+}
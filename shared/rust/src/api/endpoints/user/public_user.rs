@@ -13,7 +13,8 @@ use crate::{
             BrowsePublicUserResourcesQuery, BrowsePublicUserResourcesResponse,
             BrowsePublicUserResponse, PublicUser, PublicUserBrowsePath, PublicUserFollowPath,
             PublicUserGetPath, PublicUserSearchPath, PublicUserUnfollowPath, SearchPublicUserQuery,
-            SearchPublicUserResponse, UserBrowseQuery,
+            SearchPublicUserResponse, UserBrowseQuery, UserFeedPath, UserFeedQuery,
+            UserFeedResponse,
         },
     },
     error::EmptyError,
@@ -118,3 +119,19 @@ impl ApiEndpoint for Unfollow {
     type Err = EmptyError;
     const METHOD: Method = Method::Delete;
 }
+
+/// Activity of the users the requesting user follows: public JIGs, resources, playlists and courses
+/// they published, and circles they joined. Most recent first.
+///
+/// Pass the returned `nextCursor` as `cursor` to get the next page.
+///
+/// # Authorization
+/// * TokenUser
+pub struct Feed;
+impl ApiEndpoint for Feed {
+    type Req = UserFeedQuery;
+    type Res = UserFeedResponse;
+    type Path = UserFeedPath;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Get;
+}
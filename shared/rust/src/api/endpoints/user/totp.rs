@@ -0,0 +1,89 @@
+use super::ApiEndpoint;
+
+use crate::{
+    api::method::Method,
+    domain::user::{
+        TotpCodeRequest, TotpConfirmPath, TotpConfirmResponse, TotpDisablePath, TotpEnrollPath,
+        TotpEnrollResponse, TotpGetPath, TotpRecoveryCodesPath, TotpRecoveryCodesResponse,
+        TotpStatusResponse,
+    },
+    error::EmptyError,
+};
+
+/// Get whether the user has two-factor authentication enabled.
+pub struct Get;
+impl ApiEndpoint for Get {
+    type Req = ();
+    type Res = TotpStatusResponse;
+    type Path = TotpGetPath;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Get;
+}
+
+/// Start setting up two-factor authentication, by adding the returned secret to an authenticator app.
+///
+/// Two-factor authentication is only enabled once a code has been [confirmed](Confirm).
+/// Calling this again before then replaces the secret.
+///
+/// # Authorization
+/// * Standard, or the session returned when signing in if two-factor authentication is mandatory but not set up yet.
+///
+/// # Errors
+/// * [`409 - Conflict`](http::StatusCode::CONFLICT) if two-factor authentication is already enabled.
+pub struct Enroll;
+impl ApiEndpoint for Enroll {
+    type Req = ();
+    type Res = TotpEnrollResponse;
+    type Path = TotpEnrollPath;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Post;
+}
+
+/// Enable two-factor authentication with a code from the authenticator app.
+///
+/// Signs the user in with a new session, and returns recovery codes which must be shown to the user.
+///
+/// # Authorization
+/// * Same as [`Enroll`]
+///
+/// # Errors
+/// * [`400 - BadRequest`](http::StatusCode::BAD_REQUEST) if the code is wrong.
+/// * [`404 - NotFound`](http::StatusCode::NOT_FOUND) if [`Enroll`] wasn't called first.
+/// * [`409 - Conflict`](http::StatusCode::CONFLICT) if two-factor authentication is already enabled.
+pub struct Confirm;
+impl ApiEndpoint for Confirm {
+    type Req = TotpCodeRequest;
+    type Res = TotpConfirmResponse;
+    type Path = TotpConfirmPath;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Post;
+}
+
+/// Disable two-factor authentication.
+///
+/// # Errors
+/// * [`400 - BadRequest`](http::StatusCode::BAD_REQUEST) if the code is wrong.
+/// * [`403 - Forbidden`](http::StatusCode::FORBIDDEN) if two-factor authentication is mandatory for the user.
+/// * [`404 - NotFound`](http::StatusCode::NOT_FOUND) if two-factor authentication isn't enabled.
+pub struct Disable;
+impl ApiEndpoint for Disable {
+    type Req = TotpCodeRequest;
+    type Res = ();
+    type Path = TotpDisablePath;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Delete;
+}
+
+/// Replace the user's recovery codes with new ones.
+///
+/// # Errors
+/// * [`400 - BadRequest`](http::StatusCode::BAD_REQUEST) if the code is wrong.
+/// * [`404 - NotFound`](http::StatusCode::NOT_FOUND) if two-factor authentication isn't enabled.
+pub struct RegenerateRecoveryCodes;
+impl ApiEndpoint for RegenerateRecoveryCodes {
+    type Req = TotpCodeRequest;
+    type Res = TotpRecoveryCodesResponse;
+    type Path = TotpRecoveryCodesPath;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Post;
+}
@@ -0,0 +1,91 @@
+use crate::{
+    api::Method,
+    domain::webhook::{
+        WebhookCreatePath, WebhookCreateRequest, WebhookCreateResponse, WebhookDeletePath,
+        WebhookDeliveryListPath, WebhookDeliveryListResponse, WebhookListPath, WebhookListResponse,
+        WebhookUpdatePath, WebhookUpdateRequest,
+    },
+    error::EmptyError,
+};
+
+use super::ApiEndpoint;
+
+/// Register a webhook.
+///
+/// # Authorization
+/// * TokenUser, or [`Admin`](crate::domain::user::UserScope::Admin) to receive every user's events
+///
+/// # Errors
+/// * [`400 - BadRequest`](http::StatusCode::BAD_REQUEST) if the URL isn't `https` or resolves to an internal address, or no events are given.
+/// * [`401 - Unauthorized`](http::StatusCode::UNAUTHORIZED) if authorization is not valid.
+/// * [`403 - Forbidden`](http::StatusCode::FORBIDDEN) if `allUsers` is set by a user who isn't an admin.
+pub struct Create;
+impl ApiEndpoint for Create {
+    type Req = WebhookCreateRequest;
+    type Res = WebhookCreateResponse;
+    type Path = WebhookCreatePath;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Post;
+}
+
+/// List the webhooks of the requesting user.
+///
+/// # Authorization
+/// * TokenUser
+pub struct List;
+impl ApiEndpoint for List {
+    type Req = ();
+    type Res = WebhookListResponse;
+    type Path = WebhookListPath;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Get;
+}
+
+/// Update a webhook.
+///
+/// # Authorization
+/// * The creator of the webhook
+///
+/// # Errors
+/// * [`400 - BadRequest`](http::StatusCode::BAD_REQUEST) if the URL isn't `https` or resolves to an internal address, or no events are given.
+/// * [`404 - NotFound`](http::StatusCode::NOT_FOUND) if the webhook does not exist or belongs to another user.
+pub struct Update;
+impl ApiEndpoint for Update {
+    type Req = WebhookUpdateRequest;
+    type Res = ();
+    type Path = WebhookUpdatePath;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Patch;
+}
+
+/// Delete a webhook, along with its delivery log.
+///
+/// # Authorization
+/// * The creator of the webhook
+///
+/// # Errors
+/// * [`404 - NotFound`](http::StatusCode::NOT_FOUND) if the webhook does not exist or belongs to another user.
+pub struct Delete;
+impl ApiEndpoint for Delete {
+    type Req = ();
+    type Res = ();
+    type Path = WebhookDeletePath;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Delete;
+}
+
+/// List the most recent deliveries to a webhook.
+///
+/// # Authorization
+/// * The creator of the webhook
+///
+/// # Errors
+/// * [`404 - NotFound`](http::StatusCode::NOT_FOUND) if the webhook does not exist or belongs to another user.
+pub struct ListDeliveries;
+impl ApiEndpoint for ListDeliveries {
+    type Req = ();
+    type Res = WebhookDeliveryListResponse;
+    type Path = WebhookDeliveryListPath;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Get;
+}
@@ -4,13 +4,18 @@
 
 use std::env::VarError;
 
-/// currently set to 14 days
+/// How long a JIG player session is valid for by default, and the longest it can be valid for
+/// from when it's created or extended. Currently set to 365 days.
 pub const JIG_PLAYER_SESSION_VALID_DURATION_SECS: u32 = 60 * 60 * 24 * 365;
 
 /// Defines the range of possible values for JIG player session sharing codes
 /// means 0-9999 are possible. If this is changed then the DB's check constraint must also be updated.
 pub const JIG_PLAYER_SESSION_CODE_MAX: i32 = 999999;
 
+/// Maximum length of the name a player chooses when joining a JIG player session.
+/// If this is changed then the DB's check constraint must also be updated.
+pub const JIG_PLAYER_SESSION_DISPLAY_NAME_MAX_LEN: usize = 50;
+
 /// Minimum amount of words which should be added to a list for a game.
 pub const MIN_LIST_WORDS: usize = 2;
 
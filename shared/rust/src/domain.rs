@@ -89,13 +89,18 @@ pub mod locale;
 pub mod media;
 pub mod meta;
 pub mod module;
+pub mod notification;
 pub mod pdf;
 pub mod playlist;
 pub mod resource;
+pub mod review;
+pub mod roster;
 pub mod search;
 pub mod ser;
 pub mod session;
+pub mod sitemap;
 pub mod user;
+pub mod webhook;
 
 #[deprecated]
 /// auth types (deprecated)
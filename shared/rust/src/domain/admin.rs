@@ -1,11 +1,15 @@
 //! Types for admin routes.
+use crate::api::endpoints::PathPart;
 use crate::domain::{
+    asset::{AssetId, AssetType, CurationStatus},
     billing::{School, SchoolId, SchoolName, SchoolNameId},
+    user::UserId,
     ItemCount, Page, PageLimit,
 };
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use macros::make_path_parts;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use strum_macros::{Display, EnumIter};
 
 /// Type of data export to perform
@@ -161,3 +165,185 @@ pub enum InviteFailedReason {
     #[strum(serialize = "Not found")]
     UserNotFound,
 }
+
+make_path_parts!(CurationDashboardPath => "/v1/admin/curation");
+
+/// Query for the curation dashboard.
+#[derive(Default, Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CurationDashboardQuery {
+    /// Only list assets of this type
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub asset_type: Option<AssetType>,
+
+    /// Only list assets in this status. If `None`, lists all outstanding work, i.e. every asset
+    /// which isn't [`CurationStatus::Done`]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<CurationStatus>,
+
+    /// Only list assets assigned to this curator
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assignee_id: Option<UserId>,
+
+    /// The page number of the assets to get.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page: Option<u32>,
+
+    /// The hits per page to be returned
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page_limit: Option<u32>,
+}
+
+/// An asset in the curation queue.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CurationQueueItem {
+    /// The asset being curated
+    pub asset_id: AssetId,
+
+    /// Display name of the asset's live data
+    pub display_name: String,
+
+    /// Status for curation
+    pub curation_status: CurationStatus,
+
+    /// Curator assigned to the asset
+    pub assignee_id: Option<UserId>,
+
+    /// When the asset entered the curation queue
+    pub created_at: DateTime<Utc>,
+
+    /// When the curation status last changed. The age of the item in its current status is
+    /// measured from this.
+    pub status_updated_at: DateTime<Utc>,
+
+    /// When the current curator was assigned
+    pub assigned_at: Option<DateTime<Utc>>,
+
+    /// When curation was last completed
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+/// Number of assets in each curation status, across all assets matching the asset type and assignee filters.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct CurationStatusCounts {
+    /// Assets in [`CurationStatus::New`]
+    pub new: u64,
+
+    /// Assets in [`CurationStatus::NewVersion`]
+    pub new_version: u64,
+
+    /// Assets in [`CurationStatus::InProgress`]
+    pub in_progress: u64,
+
+    /// Assets in [`CurationStatus::Done`]
+    pub done: u64,
+}
+
+/// Response for the curation dashboard, oldest status change first.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CurationDashboardResponse {
+    /// Assets in the curation queue
+    pub items: Vec<CurationQueueItem>,
+
+    /// Number of pages
+    pub pages: u32,
+
+    /// Total number of assets matching the query
+    pub total_count: u64,
+
+    /// Number of assets in each status
+    pub status_counts: CurationStatusCounts,
+}
+
+wrap_uuid! {
+    /// Wrapper type around [`Uuid`], represents the ID of a translation glossary term.
+    pub struct GlossaryTermId
+}
+
+make_path_parts!(GlossaryTermPath => "/v1/admin/translation/glossary");
+
+/// A term which the description translation job must not translate, or must translate to a fixed
+/// rendering.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GlossaryTerm {
+    /// ID of the term
+    pub id: GlossaryTermId,
+
+    /// The term as it appears in source text, e.g. a holiday name. Matched case-sensitively on
+    /// whole words.
+    pub term: String,
+
+    /// Fixed renderings of the term, keyed by target language code. The term is left untranslated
+    /// for any language without a rendering.
+    pub renderings: HashMap<String, String>,
+
+    /// When the term was created
+    pub created_at: DateTime<Utc>,
+
+    /// When the term was last updated
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// Request to create a glossary term
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateGlossaryTermRequest {
+    /// The term as it appears in source text
+    pub term: String,
+
+    /// Fixed renderings of the term, keyed by target language code
+    #[serde(default)]
+    pub renderings: HashMap<String, String>,
+}
+
+/// List of all glossary terms
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ListGlossaryTermsResponse {
+    /// Glossary terms, ordered by term
+    pub terms: Vec<GlossaryTerm>,
+}
+
+make_path_parts!(UpdateGlossaryTermPath => "/v1/admin/translation/glossary/{}" => GlossaryTermId);
+
+/// Request to update a glossary term
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateGlossaryTermRequest {
+    /// The term as it appears in source text
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub term: Option<String>,
+
+    /// Replaces the fixed renderings of the term
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub renderings: Option<HashMap<String, String>>,
+}
+
+make_path_parts!(DeleteGlossaryTermPath => "/v1/admin/translation/glossary/{}" => GlossaryTermId);
+
+make_path_parts!(OverrideTranslatedDescriptionPath => "/v1/admin/translation/description");
+
+/// Request to override entries of an asset's `translated_description`.
+///
+/// Overrides are kept when the description is re-translated, until they are removed.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OverrideTranslatedDescriptionRequest {
+    /// The asset to override translations for
+    pub asset_id: AssetId,
+
+    /// Translations keyed by language code. A `None` value removes the override for that language,
+    /// leaving it to be translated automatically.
+    pub translations: HashMap<String, Option<String>>,
+}
@@ -645,6 +645,17 @@ pub enum OrderBy {
     PublishedAt = 1,
 }
 
+/// Sort asset search results
+#[derive(Serialize, Deserialize, Copy, Clone, Eq, PartialEq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub enum SearchSortBy {
+    /// Most relevant results first
+    Relevance,
+
+    /// Highest average review rating first
+    Rating,
+}
+
 /// Access level for the jig.
 #[derive(Serialize, Deserialize, Copy, Clone, Eq, PartialEq, Debug)]
 #[cfg_attr(feature = "backend", derive(sqlx::Type))]
@@ -691,6 +702,103 @@ impl Default for PrivacyLevel {
     }
 }
 
+/// Status of an asset in the curation workflow.
+#[derive(Serialize, Deserialize, Copy, Clone, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "backend", derive(sqlx::Type))]
+#[serde(rename_all = "camelCase")]
+#[repr(i16)]
+pub enum CurationStatus {
+    /// Asset hasn't been curated yet
+    New = 0,
+
+    /// Asset was curated and has since been republished by its author
+    NewVersion = 1,
+
+    /// A curator is reviewing the asset
+    InProgress = 2,
+
+    /// Curation of the asset completed
+    Done = 3,
+}
+
+impl Default for CurationStatus {
+    fn default() -> Self {
+        Self::New
+    }
+}
+
+impl CurationStatus {
+    /// Whether a curator is allowed to move an asset from this status to `next`.
+    ///
+    /// [`Self::NewVersion`] is never set by a curator, assets only enter it when a curated asset
+    /// is republished.
+    ///
+    /// ```
+    /// # use shared::domain::asset::CurationStatus;
+    /// assert!(CurationStatus::New.can_transition_to(CurationStatus::InProgress));
+    /// assert!(!CurationStatus::New.can_transition_to(CurationStatus::Done));
+    /// assert!(!CurationStatus::Done.can_transition_to(CurationStatus::NewVersion));
+    /// ```
+    pub fn can_transition_to(&self, next: Self) -> bool {
+        *self == next
+            || matches!(
+                (self, next),
+                (Self::New | Self::NewVersion, Self::InProgress)
+                    | (Self::InProgress, Self::Done)
+                    | (Self::Done, Self::InProgress)
+            )
+    }
+}
+
+/// Moderation status of a reported asset.
+#[derive(Serialize, Deserialize, Copy, Clone, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "backend", derive(sqlx::Type))]
+#[serde(rename_all = "camelCase")]
+#[repr(i16)]
+pub enum ReportStatus {
+    /// The report hasn't been looked at yet.
+    Pending = 0,
+
+    /// The report has been assigned to a curator.
+    InReview = 1,
+
+    /// The report has been resolved with a [`ReportAction`].
+    Resolved = 2,
+}
+
+impl Default for ReportStatus {
+    fn default() -> Self {
+        Self::Pending
+    }
+}
+
+/// Action taken by a curator when resolving a report.
+#[derive(Serialize, Deserialize, Copy, Clone, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "backend", derive(sqlx::Type))]
+#[serde(rename_all = "camelCase")]
+#[repr(i16)]
+pub enum ReportAction {
+    /// The report was not actionable.
+    Dismiss = 0,
+
+    /// The asset was blocked so that it no longer appears in search.
+    Block = 1,
+
+    /// The author of the asset was contacted.
+    ContactAuthor = 2,
+}
+
+impl ReportAction {
+    /// Represents the action as a `str`, as shown to the reporter.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Dismiss => "reviewed and no action was needed",
+            Self::Block => "removed from Jigzi search",
+            Self::ContactAuthor => "reviewed and the author has been contacted",
+        }
+    }
+}
+
 /// Whether the data is draft or live.
 #[derive(Serialize, Deserialize, Clone, Copy, Debug)]
 #[cfg_attr(feature = "backend", derive(sqlx::Type))]
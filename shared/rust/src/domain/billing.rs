@@ -214,6 +214,12 @@ impl From<&stripe::InvoiceId> for StripeInvoiceId {
 #[cfg_attr(feature = "backend", derive(sqlx::Type), sqlx(transparent))]
 pub struct StripeProductId(String);
 
+impl From<StripeProductId> for String {
+    fn from(value: StripeProductId) -> Self {
+        value.0
+    }
+}
+
 /// Stripe price ID
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "backend", derive(sqlx::Type), sqlx(transparent))]
@@ -237,7 +243,7 @@ pub enum BillingInterval {
 }
 
 /// Status of a subscription
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "backend", derive(sqlx::Type))]
 #[repr(i16)]
 pub enum SubscriptionStatus {
@@ -306,6 +312,10 @@ pub struct Subscription {
     pub status: SubscriptionStatus,
     /// When the subscriptions current period ends/expires
     pub current_period_end: DateTime<Utc>,
+    /// When the subscriptions trial period ends, if it was created with one
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trial_end: Option<DateTime<Utc>>,
     /// Account ID to associate this subscription with.
     pub account_id: AccountId,
     /// ID of the latest unpaid invoice generated for this subscription
@@ -336,6 +346,8 @@ pub struct CreateSubscriptionRecord {
     pub status: SubscriptionStatus,
     /// When the subscriptions current period ends/expires
     pub current_period_end: DateTime<Utc>,
+    /// When the subscriptions trial period ends, if it was created with one
+    pub trial_end: Option<DateTime<Utc>>,
     /// Account ID to associate this subscription with
     /// User ID to associate this subscription with
     pub account_id: AccountId,
@@ -357,6 +369,8 @@ pub struct UpdateSubscriptionRecord {
     pub status: Option<SubscriptionStatus>,
     /// When the subscriptions current period ends/expires
     pub current_period_end: Option<DateTime<Utc>>,
+    /// When the subscriptions trial period ends
+    pub trial_end: Option<DateTime<Utc>>,
     /// ID of the latest unpaid invoice generated for this subscription
     pub latest_invoice_id: Option<StripeInvoiceId>,
 }
@@ -370,6 +384,7 @@ impl UpdateSubscriptionRecord {
             auto_renew: None,
             status: None,
             current_period_end: None,
+            trial_end: None,
             latest_invoice_id: None,
         }
     }
@@ -403,6 +418,9 @@ impl TryFrom<stripe::Subscription> for UpdateSubscriptionRecord {
                     .latest()
                     .ok_or(anyhow::anyhow!("Invalid timestamp"))?,
             ),
+            trial_end: value
+                .trial_end
+                .and_then(|trial_end| Utc.timestamp_opt(trial_end, 0).latest()),
             latest_invoice_id,
         })
     }
@@ -507,6 +525,64 @@ impl TrialPeriod {
     }
 }
 
+/// The kind of billing notification email sent to account administrators
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "backend", derive(sqlx::Type))]
+#[repr(i16)]
+pub enum BillingNotificationKind {
+    /// The subscriptions trial period is about to end
+    TrialEnding = 0,
+    /// A payment for the subscription failed and the account is overdue
+    PaymentFailed = 1,
+    /// The subscription has expired
+    SubscriptionExpired = 2,
+}
+
+impl BillingNotificationKind {
+    /// Represents the notification kind as a `str`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::TrialEnding => "trial_ending",
+            Self::PaymentFailed => "payment_failed",
+            Self::SubscriptionExpired => "subscription_expired",
+        }
+    }
+}
+
+make_path_parts!(BillingNotificationPreferencesPath => "/v1/user/me/billing-notifications");
+
+/// A users preferences for billing notification emails. Every notification is enabled by default.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BillingNotificationPreferences {
+    /// Whether to receive an email before a trial period ends
+    pub trial_ending: bool,
+    /// Whether to receive an email when a payment fails
+    pub payment_failed: bool,
+    /// Whether to receive an email when a subscription expires
+    pub subscription_expired: bool,
+}
+
+impl Default for BillingNotificationPreferences {
+    fn default() -> Self {
+        Self {
+            trial_ending: true,
+            payment_failed: true,
+            subscription_expired: true,
+        }
+    }
+}
+
+impl BillingNotificationPreferences {
+    /// Whether the user wants to receive notifications of `kind`
+    pub fn is_enabled(&self, kind: BillingNotificationKind) -> bool {
+        match kind {
+            BillingNotificationKind::TrialEnding => self.trial_ending,
+            BillingNotificationKind::PaymentFailed => self.payment_failed,
+            BillingNotificationKind::SubscriptionExpired => self.subscription_expired,
+        }
+    }
+}
+
 wrap_uuid! {
     /// Local charge ID
     pub struct ChargeId
@@ -781,6 +857,10 @@ pub struct CreateSubscriptionRequest {
     pub setup_intent_id: Option<String>,
     /// Plan ID to create the subscription for
     pub plan_id: PlanId,
+    /// Optional promotion code to apply a discount to the subscription
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub promotion_code: Option<String>,
 }
 
 make_path_parts!(CreateSubscriptionPath => "/v1/subscribe");
@@ -809,6 +889,160 @@ pub struct CreateSetupIntentRequest {
 
 make_path_parts!(CreateSetupIntentPath => "/v1/billing/payment-method");
 
+wrap_uuid! {
+    /// Local promotion code ID
+    pub struct PromotionCodeId
+}
+
+/// The discount a promotion code applies to a subscription
+#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub enum Discount {
+    /// A percentage, between 1 and 100, taken off the price
+    PercentOff(u8),
+    /// A fixed amount, in cents, taken off the price
+    AmountOff(i64),
+}
+
+impl Discount {
+    /// Apply the discount to an amount, never discounting below zero
+    pub fn apply(&self, amount: &AmountInCents) -> AmountInCents {
+        let discounted = match self {
+            Self::PercentOff(percent) => amount.0 - (amount.0 * i64::from(*percent)) / 100,
+            Self::AmountOff(amount_off) => amount.0 - amount_off,
+        };
+
+        AmountInCents::new(discounted.max(0))
+    }
+}
+
+/// How long a promotion code discount applies to a subscription
+#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub enum DiscountDuration {
+    /// Only the first invoice is discounted
+    Once,
+    /// Invoices are discounted for a number of months
+    Repeating(u32),
+    /// Every invoice is discounted
+    Forever,
+}
+
+/// An admin managed promotion code which maps to a Stripe coupon
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PromotionCode {
+    /// Local ID of the promotion code
+    pub id: PromotionCodeId,
+    /// The code entered by customers
+    pub code: String,
+    /// The discount applied
+    pub discount: Discount,
+    /// How long the discount applies
+    pub duration: DiscountDuration,
+    /// The maximum number of times the code can be redeemed. [None] means unlimited.
+    pub max_redemptions: Option<u32>,
+    /// The number of times the code has been redeemed
+    pub redemption_count: u32,
+    /// The plans the code can be applied to. An empty list means every plan.
+    pub plan_ids: Vec<PlanId>,
+    /// When the code can no longer be redeemed, if ever
+    pub expires_at: Option<DateTime<Utc>>,
+    /// Whether the code can currently be redeemed
+    pub active: bool,
+    /// When the promotion code was created
+    pub created_at: DateTime<Utc>,
+    /// When the promotion code was last updated
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl PromotionCode {
+    /// Whether the code can be redeemed for `plan_id` at this moment
+    pub fn is_redeemable(&self, plan_id: &PlanId) -> bool {
+        self.active
+            && self
+                .expires_at
+                .map_or(true, |expires_at| expires_at > Utc::now())
+            && self
+                .max_redemptions
+                .map_or(true, |max| self.redemption_count < max)
+            && (self.plan_ids.is_empty() || self.plan_ids.contains(plan_id))
+    }
+}
+
+make_path_parts!(PromotionCodePath => "/v1/admin/promotion-code");
+
+/// Request to create a new promotion code
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CreatePromotionCodeRequest {
+    /// The code entered by customers
+    pub code: String,
+    /// The discount applied
+    pub discount: Discount,
+    /// Three-letter ISO currency code, in lowercase, of an [`AmountOff`](Discount::AmountOff)
+    /// discount. Required for, and only used by, amount off discounts.
+    #[serde(default)]
+    pub currency: Option<String>,
+    /// How long the discount applies
+    pub duration: DiscountDuration,
+    /// The maximum number of times the code can be redeemed. [None] means unlimited.
+    #[serde(default)]
+    pub max_redemptions: Option<u32>,
+    /// The plans the code can be applied to. An empty list means every plan.
+    #[serde(default)]
+    pub plan_ids: Vec<PlanId>,
+    /// When the code can no longer be redeemed, if ever
+    #[serde(default)]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+make_path_parts!(UpdatePromotionCodePath => "/v1/admin/promotion-code/{}" => PromotionCodeId);
+
+/// Request to update a promotion code.
+///
+/// The discount, duration and expiry can't be changed once a code has been created because the
+/// Stripe coupon is immutable.
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct UpdatePromotionCodeRequest {
+    /// Whether the code can be redeemed
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub active: Option<bool>,
+}
+
+/// List of promotion codes
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ListPromotionCodesResponse {
+    /// Promotion codes, newest first
+    pub promotion_codes: Vec<PromotionCode>,
+}
+
+make_path_parts!(ValidatePromotionCodePath => "/v1/billing/promotion-code/validate");
+
+/// Query to validate a promotion code against a subscription plan
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidatePromotionCodeQuery {
+    /// The code entered by the customer
+    pub code: String,
+    /// The plan the customer intends to subscribe to
+    pub plan_id: PlanId,
+}
+
+/// Preview of a plan's price with a promotion code applied
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ValidatePromotionCodeResponse {
+    /// The plan details, including the undiscounted price
+    pub plan: SubscriptionPlanDetailsResponse,
+    /// The discount applied
+    pub discount: Discount,
+    /// How long the discount applies
+    pub duration: DiscountDuration,
+    /// The price of the plan after the discount has been applied
+    pub discounted_amount_in_cents: AmountInCents,
+}
+
 wrap_uuid! {
     /// Account ID
     pub struct AccountId
@@ -979,3 +1213,70 @@ pub struct GetSchoolAccountResponse {
     /// School location
     pub users: Vec<AccountUser>,
 }
+
+make_path_parts!(SchoolSsoPath => "/v1/school/{}/sso" => SchoolId);
+
+make_path_parts!(VerifySchoolSsoPath => "/v1/school/{}/sso/verify" => SchoolId);
+
+/// A school's single sign-on, through the school's OpenID Connect identity provider.
+///
+/// Teachers with an email in one of the verified domains sign in through the identity provider, and are
+/// added to the school's account the first time they do.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SchoolSsoConfig {
+    /// The identity provider's issuer URL, its configuration is discovered from
+    /// `{issuer}/.well-known/openid-configuration`.
+    pub issuer: String,
+    /// The client ID the school registered for Jigzi with the identity provider.
+    pub client_id: String,
+    /// The email domains the identity provider signs in.
+    pub email_domains: Vec<SchoolSsoDomain>,
+    /// The DNS TXT record to add to each email domain, to prove that the school owns it.
+    pub verification_record: String,
+    /// Whether teachers can sign in with it.
+    pub enabled: bool,
+    /// When single sign-on was set up.
+    pub created_at: DateTime<Utc>,
+    /// When single sign-on was last updated.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// An email domain of a school's single sign-on.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SchoolSsoDomain {
+    /// The domain, e.g. `school.edu`.
+    pub domain: String,
+    /// Whether the school proved that it owns the domain.
+    ///
+    /// Teachers can only sign in with single sign-on once it's verified.
+    pub verified: bool,
+}
+
+/// Request to set up or update a school's single sign-on.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct UpsertSchoolSsoRequest {
+    /// The identity provider's issuer URL.
+    pub issuer: String,
+    /// The client ID registered with the identity provider.
+    pub client_id: String,
+    /// The client secret registered with the identity provider.
+    ///
+    /// Required when setting up single sign-on, keeps the current secret if omitted when updating.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_secret: Option<String>,
+    /// The email domains the identity provider signs in.
+    ///
+    /// New domains have to be verified, and each domain can only be verified by one school.
+    /// Public email domains (e.g. `gmail.com`) can't be used.
+    pub email_domains: Vec<String>,
+    /// Whether teachers can sign in with it.
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
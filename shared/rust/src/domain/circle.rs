@@ -1,5 +1,8 @@
 //! Types for Circles.
 
+pub mod library;
+pub mod post;
+
 use chrono::{DateTime, Utc};
 use macros::make_path_parts;
 use serde::{Deserialize, Serialize};
@@ -191,6 +194,8 @@ make_path_parts!(CircleRemoveMemberPath => "/v1/circle/{}/members/{}" => CircleI
 
 make_path_parts!(CircleBrowseMembersPath => "/v1/circle/{}/members" => CircleId);
 
+make_path_parts!(CircleInvitePath => "/v1/circle/{}/invite/{}" => CircleId, UserId);
+
 /// Members associated with Circle
 #[derive(Serialize, Deserialize)]
 pub struct BrowseMembersResponse {
@@ -0,0 +1,115 @@
+//! Types for the shared libraries of Circles.
+//!
+//! Members pin JIGs, resources, playlists and courses into the library of a Circle, where every
+//! member can browse them and clone them with the clone endpoint of the asset.
+
+use chrono::{DateTime, Utc};
+use macros::make_path_parts;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    api::endpoints::PathPart,
+    domain::{
+        asset::{AssetId, AssetType},
+        circle::CircleId,
+        user::UserId,
+    },
+};
+
+wrap_uuid! {
+    /// Wrapper type around [`Uuid`], represents [`CircleLibraryItem::id`].
+    pub struct CircleLibraryItemId
+}
+
+/// Maximum length of the note of a library item.
+/// If this is changed then the DB's check constraint must also be updated.
+pub const CIRCLE_LIBRARY_NOTE_MAX_LEN: usize = 1000;
+
+/// Over-the-wire representation of an asset pinned into the library of a Circle.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CircleLibraryItem {
+    /// ID of the library item
+    pub id: CircleLibraryItemId,
+
+    /// The pinned asset
+    pub asset_id: AssetId,
+
+    /// Note explaining why the asset was pinned. Defaults to empty string.
+    pub note: String,
+
+    /// Member who pinned the asset
+    pub pinned_by: UserId,
+
+    /// Name of the member who pinned the asset
+    pub pinned_by_name: Option<String>,
+
+    /// When the asset was pinned
+    pub pinned_at: DateTime<Utc>,
+
+    /// When the note was last edited
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+make_path_parts!(CircleLibraryPinPath => "/v1/circle/{}/library" => CircleId);
+
+/// Request to pin an asset into the library of a Circle.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CircleLibraryPinRequest {
+    /// The asset to pin
+    pub asset_id: AssetId,
+
+    /// Note explaining why the asset was pinned
+    #[serde(default)]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub note: String,
+}
+
+make_path_parts!(CircleLibraryListPath => "/v1/circle/{}/library" => CircleId);
+
+/// Query for browsing the library of a Circle.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct CircleLibraryListQuery {
+    /// Optionally only list assets of this type
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub asset_type: Option<AssetType>,
+
+    /// The page number to get.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page: Option<u32>,
+
+    /// The hits per page to be returned
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page_limit: Option<u32>,
+}
+
+/// Response for browsing the library of a Circle.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CircleLibraryListResponse {
+    /// The library items returned, most recently pinned first.
+    pub items: Vec<CircleLibraryItem>,
+
+    /// The number of pages found.
+    pub pages: u32,
+
+    /// The total number of library items found
+    pub total_item_count: u64,
+}
+
+make_path_parts!(CircleLibraryUpdatePath => "/v1/circle/{}/library/{}" => CircleId, CircleLibraryItemId);
+
+/// Request to edit the note of a library item.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CircleLibraryUpdateRequest {
+    /// Note explaining why the asset was pinned
+    pub note: String,
+}
+
+make_path_parts!(CircleLibraryUnpinPath => "/v1/circle/{}/library/{}" => CircleId, CircleLibraryItemId);
@@ -0,0 +1,174 @@
+//! Types for discussions in Circles.
+//!
+//! Posts are either threads, which start a discussion, or replies to a thread.
+
+use chrono::{DateTime, Utc};
+use macros::make_path_parts;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    api::endpoints::PathPart,
+    domain::{asset::AssetId, circle::CircleId, user::UserId},
+};
+
+wrap_uuid! {
+    /// Wrapper type around [`Uuid`], represents [`CirclePost::id`].
+    pub struct CirclePostId
+}
+
+/// Maximum length of the text of a post.
+/// If this is changed then the DB's check constraint must also be updated.
+pub const CIRCLE_POST_MAX_LEN: usize = 5000;
+
+/// Maximum number of assets which can be attached to a post.
+pub const CIRCLE_POST_MAX_ASSETS: usize = 10;
+
+/// Reaction of a member to a post.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "backend", derive(sqlx::Type))]
+#[serde(rename_all = "camelCase")]
+#[repr(i16)]
+pub enum CirclePostReaction {
+    /// Like
+    Like = 0,
+    /// Love
+    Love = 1,
+    /// Celebrate
+    Celebrate = 2,
+    /// Insightful
+    Insightful = 3,
+    /// Funny
+    Funny = 4,
+}
+
+/// Number of members who reacted to a post in the same way.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CirclePostReactionCount {
+    /// The reaction
+    pub reaction: CirclePostReaction,
+
+    /// Number of members who reacted this way
+    pub count: u32,
+}
+
+/// Over-the-wire representation of a post.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CirclePost {
+    /// ID of the post
+    pub id: CirclePostId,
+
+    /// Thread the post replies to, if it's a reply
+    pub parent_id: Option<CirclePostId>,
+
+    /// Member who wrote the post
+    pub author_id: UserId,
+
+    /// Name of the member who wrote the post
+    pub author_name: Option<String>,
+
+    /// Text of the post
+    pub body: String,
+
+    /// JIGs, resources, playlists and courses attached to the post, in order
+    pub assets: Vec<AssetId>,
+
+    /// Number of replies to a thread. Always zero for replies.
+    pub reply_count: u32,
+
+    /// Reactions to the post, most common first
+    pub reactions: Vec<CirclePostReactionCount>,
+
+    /// Reaction of the requesting user, if any
+    pub my_reaction: Option<CirclePostReaction>,
+
+    /// When the post was written
+    pub created_at: DateTime<Utc>,
+
+    /// When the post was last edited
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+make_path_parts!(CirclePostCreatePath => "/v1/circle/{}/post" => CircleId);
+
+/// Request to write a post.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CirclePostCreateRequest {
+    /// Text of the post
+    pub body: String,
+
+    /// JIGs, resources, playlists and courses to attach to the post
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub assets: Vec<AssetId>,
+
+    /// Thread to reply to. Starts a new thread if not set.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<CirclePostId>,
+}
+
+make_path_parts!(CirclePostListPath => "/v1/circle/{}/post" => CircleId);
+
+/// Query for listing the threads of a Circle, or the replies to a thread.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct CirclePostListQuery {
+    /// The page number to get.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page: Option<u32>,
+
+    /// The hits per page to be returned
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page_limit: Option<u32>,
+}
+
+/// Response for listing the threads of a Circle, or the replies to a thread.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CirclePostListResponse {
+    /// The posts returned. Threads are newest first, replies oldest first.
+    pub posts: Vec<CirclePost>,
+
+    /// The number of pages found.
+    pub pages: u32,
+
+    /// The total number of posts found
+    pub total_post_count: u64,
+}
+
+make_path_parts!(CirclePostRepliesPath => "/v1/circle/{}/post/{}/replies" => CircleId, CirclePostId);
+
+make_path_parts!(CirclePostUpdatePath => "/v1/circle/{}/post/{}" => CircleId, CirclePostId);
+
+/// Request to edit a post.
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct CirclePostUpdateRequest {
+    /// Text of the post
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+
+    /// Assets attached to the post, replacing the current ones
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assets: Option<Vec<AssetId>>,
+}
+
+make_path_parts!(CirclePostDeletePath => "/v1/circle/{}/post/{}" => CircleId, CirclePostId);
+
+make_path_parts!(CirclePostReactPath => "/v1/circle/{}/post/{}/reaction" => CircleId, CirclePostId);
+
+/// Request to react to a post.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CirclePostReactRequest {
+    /// The reaction, replacing any earlier one. Removes the reaction if not set.
+    #[serde(default)]
+    pub reaction: Option<CirclePostReaction>,
+}
@@ -17,6 +17,7 @@ use super::{
     user::UserId,
 };
 
+pub mod curation;
 pub mod unit;
 
 wrap_uuid! {
@@ -0,0 +1,175 @@
+//! Types for Course curation
+use chrono::{DateTime, Utc};
+use macros::make_path_parts;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    api::endpoints::PathPart,
+    domain::{asset::CurationStatus, user::UserId},
+};
+
+use super::CourseId;
+
+wrap_uuid! {
+    /// Wrapper type around [`Uuid`](Uuid), represents the ID of a curation comment.
+    pub struct CommentId
+}
+
+make_path_parts!(CourseCurationPath => "/v1/course/{}/curation" => CourseId);
+
+/// Curation data for Courses
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CourseCurationData {
+    /// Course ID for curation
+    pub course_id: CourseId,
+
+    /// Fields curated by Admin
+    pub fields_done: CourseCurationFieldsDone,
+
+    /// Status for curation
+    pub curation_status: CourseCurationStatus,
+
+    /// Curator assigned to this Course
+    pub assignee_id: Option<UserId>,
+
+    /// When the Course entered the curation queue
+    pub created_at: DateTime<Utc>,
+
+    /// When the curation status last changed
+    pub status_updated_at: DateTime<Utc>,
+
+    /// When the current curator was assigned
+    pub assigned_at: Option<DateTime<Utc>>,
+
+    /// When curation was last completed
+    pub completed_at: Option<DateTime<Utc>>,
+
+    /// Comments from curator (not updatable)
+    pub comments: Vec<CourseCurationComment>,
+}
+
+/// Curation fields that have been completed
+///
+/// Authorization:
+/// Admin
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct CourseCurationFieldsDone {
+    /// Display name of Course
+    pub display_name: bool,
+
+    /// Language of Course
+    pub language: bool,
+
+    /// Categories of Course
+    pub categories: bool,
+
+    /// Descriptions of Course
+    pub description: bool,
+
+    /// Addtional resources of Course
+    pub additional_resources: bool,
+}
+
+/// Status of Curation
+pub type CourseCurationStatus = CurationStatus;
+
+make_path_parts!(CourseCurationUpdatePath => "/v1/course/{}/curation" => CourseId);
+
+/// Curation data for Courses
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct CourseCurationUpdateRequest {
+    /// Display name of Course
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<bool>,
+
+    /// Language of Course
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<bool>,
+
+    /// Categories of Course
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub categories: Option<bool>,
+
+    /// Descriptions of Course
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<bool>,
+
+    /// Addtional resources of Course
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub additional_resources: Option<bool>,
+
+    /// Curation status of Course
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub curation_status: Option<CourseCurationStatus>,
+
+    /// Curator assigned to the Course, `Some(None)` unassigns the current curator
+    #[serde(default)]
+    #[serde(deserialize_with = "crate::domain::deserialize_optional_field")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assignee_id: Option<Option<UserId>>,
+}
+
+make_path_parts!(CourseCurationCommentCreatePath => "/v1/course/{}/curation/comment" => CourseId);
+
+/// Curation comment for Courses
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "backend", derive(sqlx::Type))]
+#[serde(rename_all = "camelCase")]
+pub struct CourseCurationComment {
+    /// Comment ID
+    pub id: CommentId,
+
+    /// Course ID for comment
+    pub course_id: CourseId,
+
+    /// Comment
+    pub value: String,
+
+    /// When comment was submitted
+    pub created_at: DateTime<Utc>,
+
+    /// ID of commenter
+    pub author_id: UserId,
+}
+
+/// Request to comment on Course
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CourseCurationCommentRequest {
+    /// Comment
+    pub value: String,
+}
+
+make_path_parts!(CourseCurationCommentGetPath => "/v1/course/{}/curation/comment/{}" => CourseId, CommentId);
+
+/// Curation comment for Courses, with the author's name
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CourseCurationCommentResponse {
+    /// ID of comment
+    pub id: CommentId,
+
+    /// ID of Course
+    pub course_id: CourseId,
+
+    /// Curator comment
+    pub value: String,
+
+    /// When comment was submitted
+    pub created_at: Option<DateTime<Utc>>,
+
+    /// ID of commenter
+    pub author_id: UserId,
+
+    /// Name of commenter
+    pub author_name: String,
+}
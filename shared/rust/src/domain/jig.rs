@@ -17,8 +17,9 @@ use std::{
 
 use super::{
     additional_resource::AdditionalResource,
-    asset::{DraftOrLive, OrderBy, PrivacyLevel, UserOrMe},
+    asset::{DraftOrLive, OrderBy, PrivacyLevel, SearchSortBy, UserOrMe},
     category::CategoryId,
+    circle::CircleId,
     meta::{AffiliationId, AgeRangeId, ResourceTypeId},
     module::LiteModule,
     playlist::PlaylistResponse,
@@ -487,6 +488,14 @@ pub struct JigResponse {
     /// Number of plays Jig
     pub plays: i64,
 
+    /// Average star rating of the JIG in reviews, [`None`] if it hasn't been reviewed
+    #[serde(default)]
+    pub average_rating: Option<f32>,
+
+    /// Number of reviews of the JIG
+    #[serde(default)]
+    pub review_count: i64,
+
     /// Live is current to Draft
     pub live_up_to_date: bool,
 
@@ -748,6 +757,18 @@ pub struct JigSearchQuery {
     #[serde(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub is_rated: Option<bool>,
+
+    /// How to sort the results, by relevance if [`None`]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort_by: Option<SearchSortBy>,
+
+    /// Optionally filter by the `circles` whose library the JIG is pinned into
+    #[serde(default)]
+    #[serde(serialize_with = "super::csv_encode_uuids")]
+    #[serde(deserialize_with = "super::from_csv")]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub circles: Vec<CircleId>,
 }
 
 /// Response for successful search.
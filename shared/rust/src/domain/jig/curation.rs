@@ -4,7 +4,10 @@ use macros::make_path_parts;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::api::endpoints::PathPart;
+use crate::{
+    api::endpoints::PathPart,
+    domain::{asset::CurationStatus, user::UserId},
+};
 
 use super::{report::JigReport, JigId};
 
@@ -28,6 +31,21 @@ pub struct JigCurationData {
     /// Status for curation
     pub curation_status: JigCurationStatus,
 
+    /// Curator assigned to this JIG
+    pub assignee_id: Option<UserId>,
+
+    /// When the JIG entered the curation queue
+    pub created_at: DateTime<Utc>,
+
+    /// When the curation status last changed
+    pub status_updated_at: DateTime<Utc>,
+
+    /// When the current curator was assigned
+    pub assigned_at: Option<DateTime<Utc>>,
+
+    /// When curation was last completed
+    pub completed_at: Option<DateTime<Utc>>,
+
     /// Comments from curator (not updatable)
     pub comments: Vec<JigCurationComment>,
 
@@ -79,29 +97,7 @@ impl Default for JigCurationFieldsDone {
 }
 
 /// Status of Curation
-#[derive(Serialize, Deserialize, Copy, Clone, Eq, PartialEq, Debug)]
-#[cfg_attr(feature = "backend", derive(sqlx::Type))]
-#[serde(rename_all = "camelCase")]
-#[repr(i16)]
-pub enum JigCurationStatus {
-    /// JIGs first curation
-    New = 0,
-
-    /// JIG has been updated by user
-    NewVersion = 1,
-
-    /// Admin is reviewing JIG
-    InProgress = 2,
-
-    /// Curation of JIG completed
-    Done = 3,
-}
-
-impl Default for JigCurationStatus {
-    fn default() -> Self {
-        Self::New
-    }
-}
+pub type JigCurationStatus = CurationStatus;
 
 make_path_parts!(JigCurationUpdatePath => "/v1/jig/{}/curation" => JigId);
 
@@ -153,6 +149,12 @@ pub struct JigCurationUpdateRequest {
     #[serde(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub curation_status: Option<JigCurationStatus>,
+
+    /// Curator assigned to the JIG, `Some(None)` unassigns the current curator
+    #[serde(default)]
+    #[serde(deserialize_with = "crate::domain::deserialize_optional_field")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assignee_id: Option<Option<UserId>>,
 }
 
 make_path_parts!(JigCurationCommentCreatePath => "/v1/jig/{}/curation/comment" => JigId);
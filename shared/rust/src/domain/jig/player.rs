@@ -6,7 +6,7 @@ use chrono::{DateTime, Utc};
 use macros::make_path_parts;
 use serde::{Deserialize, Serialize};
 
-use crate::api::endpoints::PathPart;
+use crate::{api::endpoints::PathPart, domain::roster::RosterId};
 
 use super::JigId;
 
@@ -88,6 +88,17 @@ pub struct JigPlayerSessionCreateRequest {
 
     /// Settings for the session
     pub settings: JigPlayerSettings,
+
+    /// Roster of the class the session is for. Students pick their name from it when joining.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub roster_id: Option<RosterId>,
+
+    /// How long the session is valid for, in seconds. Defaults to, and can't be longer than,
+    /// [`JIG_PLAYER_SESSION_VALID_DURATION_SECS`](crate::config::JIG_PLAYER_SESSION_VALID_DURATION_SECS).
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration: Option<u32>,
 }
 
 /// Request to create a player session for a jig.
@@ -109,6 +120,11 @@ pub struct JigPlayerSession {
 
     /// When the player session expires
     pub expires_at: DateTime<Utc>,
+
+    /// Roster of the class the session is for, if any
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub roster_id: Option<RosterId>,
 }
 
 make_path_parts!(JigPlayerSessionListPath => "/v1/jig/{}/player" => JigPlayerSessionIndex);
@@ -120,6 +136,26 @@ pub struct JigPlayerSessionListResponse {
     pub sessions: Vec<JigPlayerSession>,
 }
 
+make_path_parts!(JigPlayerSessionExtendPath => "/v1/jig/player/{}/extend" => JigPlayerSessionIndex);
+
+/// Request to extend a jig player session.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct JigPlayerSessionExtendRequest {
+    /// Number of seconds to add to the session's validity
+    pub duration: u32,
+}
+
+/// Response for extending a jig player session.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct JigPlayerSessionExtendResponse {
+    /// When the player session now expires
+    pub expires_at: DateTime<Utc>,
+}
+
+make_path_parts!(JigPlayerSessionRevokePath => "/v1/jig/player/{}/revoke" => JigPlayerSessionIndex);
+
 make_path_parts!(JigPlayCountPath => "/v1/jig/{}/play-count" => JigPlayerSessionIndex);
 
 /// Response for completing a session for a jig play as a player and updating the jig play count
@@ -130,32 +166,283 @@ pub struct JigPlayCountResponse {
     pub play_count: i64,
 }
 
+make_path_parts!(JigPlayerSessionLeaderboardPath => "/v1/jig/player/{}/leaderboard" => JigPlayerSessionIndex);
+
+/// Query for the leaderboard of a player session.
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct JigPlayerSessionLeaderboardQuery {
+    /// Only return the leaderboard if any entry changed after this cursor, as returned in
+    /// [`JigPlayerSessionLeaderboardResponse::cursor`].
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub since: Option<DateTime<Utc>>,
+}
+
+/// A player's position on the leaderboard of a player session.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct JigPlayerSessionLeaderboardEntry {
+    /// Position on the leaderboard, starting at 1. Players with the same points and completion
+    /// time share a rank.
+    pub rank: u32,
+
+    /// ID of the session instance
+    pub instance_id: instance::PlayerSessionInstanceId,
+
+    /// Name the player chose when joining the session
+    pub display_name: Option<String>,
+
+    /// Total points scored across all modules
+    pub points: u32,
+
+    /// When (if at all) the JIG was played all the way through
+    pub completed_at: Option<DateTime<Utc>>,
+
+    /// When the player last scored or completed the JIG
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Leaderboard of a player session.
+///
+/// Players are ranked by points, then by how quickly they completed the JIG. Players who haven't
+/// completed it are ranked after those who have the same points.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct JigPlayerSessionLeaderboardResponse {
+    /// Every entry in rank order, or none if nothing changed since the requested cursor.
+    pub entries: Vec<JigPlayerSessionLeaderboardEntry>,
+
+    /// Total number of players in the session
+    pub total_count: u32,
+
+    /// Cursor to pass as [`JigPlayerSessionLeaderboardQuery::since`] when polling for changes
+    pub cursor: DateTime<Utc>,
+}
+
+/// A JIG of a player session.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct JigPlayerSessionItem {
+    /// ID of the JIG
+    pub jig_id: JigId,
+
+    /// Settings for this JIG, overriding those of the session
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub settings: Option<JigPlayerSettings>,
+}
+
+/// Types for assignments: player sessions made up of several JIGs, which students can only play
+/// while they are open.
+pub mod assignment {
+    use chrono::{DateTime, Utc};
+    use macros::make_path_parts;
+    use serde::{Deserialize, Serialize};
+
+    use crate::api::endpoints::PathPart;
+    use crate::domain::{
+        jig::player::{JigPlayerSessionIndex, JigPlayerSessionItem, JigPlayerSettings},
+        playlist::PlaylistId,
+        roster::RosterId,
+    };
+
+    make_path_parts!(JigPlayerAssignmentCreatePath => "/v1/jig/player/assignment");
+
+    /// Request to create an assignment.
+    ///
+    /// Exactly one of `items` and `playlist_id` must be given.
+    #[derive(Serialize, Deserialize, Debug, Default)]
+    #[serde(rename_all = "camelCase")]
+    pub struct JigPlayerAssignmentCreateRequest {
+        /// JIGs to be played, in order
+        #[serde(default)]
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        pub items: Vec<JigPlayerSessionItem>,
+
+        /// Playlist whose JIGs are to be played. The JIGs are copied from the published playlist
+        /// when the assignment is created.
+        #[serde(default)]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub playlist_id: Option<PlaylistId>,
+
+        /// Settings for every JIG which doesn't override them
+        #[serde(default)]
+        pub settings: JigPlayerSettings,
+
+        /// When students can start playing. Immediately if not set.
+        #[serde(default)]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub opens_at: Option<DateTime<Utc>>,
+
+        /// When students can no longer start playing, i.e. the due date
+        #[serde(default)]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub closes_at: Option<DateTime<Utc>>,
+
+        /// How many times each student can play the assignment. Unlimited if not set.
+        ///
+        /// Students are counted by their name on the roster, or by device for assignments without
+        /// a roster.
+        #[serde(default)]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub max_attempts: Option<u16>,
+
+        /// Roster of the class the assignment is for
+        #[serde(default)]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub roster_id: Option<RosterId>,
+
+        /// How long the assignment stays valid after it closes, in seconds. Defaults to, and can't
+        /// be longer than,
+        /// [`JIG_PLAYER_SESSION_VALID_DURATION_SECS`](crate::config::JIG_PLAYER_SESSION_VALID_DURATION_SECS).
+        #[serde(default)]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub duration: Option<u32>,
+    }
+
+    /// Over-the-wire representation of an assignment.
+    #[derive(Serialize, Deserialize, Clone, Debug)]
+    #[serde(rename_all = "camelCase")]
+    pub struct JigPlayerAssignment {
+        /// Code students enter to play the assignment
+        pub index: JigPlayerSessionIndex,
+
+        /// JIGs to be played, in order
+        pub items: Vec<JigPlayerSessionItem>,
+
+        /// Playlist the JIGs were copied from, if any
+        pub playlist_id: Option<PlaylistId>,
+
+        /// Settings for every JIG which doesn't override them
+        pub settings: JigPlayerSettings,
+
+        /// When students can start playing
+        pub opens_at: Option<DateTime<Utc>>,
+
+        /// When students can no longer start playing
+        pub closes_at: Option<DateTime<Utc>>,
+
+        /// How many times each student can play the assignment
+        pub max_attempts: Option<u16>,
+
+        /// Roster of the class the assignment is for
+        pub roster_id: Option<RosterId>,
+
+        /// When the assignment was created
+        pub created_at: DateTime<Utc>,
+
+        /// When the code expires
+        pub expires_at: DateTime<Utc>,
+    }
+
+    make_path_parts!(JigPlayerAssignmentListPath => "/v1/jig/player/assignment");
+
+    /// Assignments created by the requesting user.
+    #[derive(Serialize, Deserialize, Debug)]
+    #[serde(rename_all = "camelCase")]
+    pub struct JigPlayerAssignmentListResponse {
+        /// The assignments, newest first
+        pub assignments: Vec<JigPlayerAssignment>,
+    }
+
+    make_path_parts!(JigPlayerAssignmentClosePath => "/v1/jig/player/assignment/{}/close" => JigPlayerSessionIndex);
+}
+
 /// Types for Jig session instance endpoints
 pub mod instance {
+    use chrono::{DateTime, Utc};
     use macros::make_path_parts;
     use serde::{Deserialize, Serialize};
 
-    use crate::domain::jig::{player::JigPlayerSessionIndex, JigId, JigPlayerSettings};
+    use crate::api::endpoints::PathPart;
+    use crate::domain::{
+        jig::{
+            player::{JigPlayerSessionIndex, JigPlayerSessionItem},
+            JigId, JigPlayerSettings,
+        },
+        module::ModuleId,
+        roster::{PicturePassword, RosterStudentId},
+    };
+
+    wrap_uuid! {
+        /// Wrapper type around [`Uuid`], represents [`PlayerSessionInstanceResult::id`].
+        pub struct PlayerSessionInstanceId
+    }
 
     make_path_parts!(PlayerSessionInstanceCreatePath => "/v1/jig/player/instance");
 
     /// Request to create a player (who is not the author) session for a JIG.
     #[derive(Serialize, Deserialize, Debug)]
+    #[serde(rename_all = "camelCase")]
     pub struct PlayerSessionInstanceCreateRequest {
         /// Four-digit code identifying a JIG player session
         pub index: JigPlayerSessionIndex,
+
+        /// Name shown for the player on the session's leaderboard. Ignored for sessions with a
+        /// roster, where the student's nickname is used.
+        #[serde(default)]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub display_name: Option<String>,
+
+        /// Student joining the session, required for sessions with a roster.
+        #[serde(default)]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub student: Option<PlayerSessionInstanceStudent>,
     }
 
-    /// Response for successfully creating an instance of a JIG player session. contains the token
+    /// Student picked from the roster when joining a player session.
+    #[derive(Serialize, Deserialize, Clone, Debug)]
+    #[serde(rename_all = "camelCase")]
+    pub struct PlayerSessionInstanceStudent {
+        /// ID of the student on the roster
+        pub id: RosterStudentId,
+
+        /// Picture the student picked, required if they have a picture password
+        #[serde(default)]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub picture_password: Option<PicturePassword>,
+    }
+
+    make_path_parts!(PlayerSessionInstanceRosterPath => "/v1/jig/player/{}/roster" => JigPlayerSessionIndex);
+
+    /// A student students can pick when joining a player session.
+    #[derive(Serialize, Deserialize, Clone, Debug)]
+    #[serde(rename_all = "camelCase")]
+    pub struct PlayerSessionInstanceRosterStudent {
+        /// ID of the student on the roster
+        pub id: RosterStudentId,
+
+        /// Name the student is known by in class
+        pub nickname: String,
+
+        /// Whether the student has to pick their picture password
+        pub has_picture_password: bool,
+    }
+
+    /// Students who can join a player session.
     #[derive(Serialize, Deserialize, Debug)]
     #[serde(rename_all = "camelCase")]
+    pub struct PlayerSessionInstanceRosterResponse {
+        /// Students on the session's roster, ordered by nickname. `None` if anyone can join the
+        /// session.
+        pub students: Option<Vec<PlayerSessionInstanceRosterStudent>>,
+    }
+
+    /// Response for successfully creating an instance of a JIG player session. contains the token
+    #[derive(Serialize, Deserialize, Debug, Clone)]
+    #[serde(rename_all = "camelCase")]
     pub struct PlayerSessionInstanceResponse {
-        /// ID of the JIG that the session is for
+        /// ID of the (first) JIG that the session is for
         pub jig_id: JigId,
 
         /// Settings for the player session.
         pub settings: JigPlayerSettings,
 
+        /// Every JIG to be played, in order, with the settings to play it with
+        #[serde(default)]
+        pub items: Vec<JigPlayerSessionItem>,
+
         /// Token that will be passed to confirm a JIG was played all the way through
         pub token: String,
     }
@@ -169,6 +456,71 @@ pub mod instance {
         /// Token that will be passed to confirm a JIG was played all the way through
         pub token: String,
     }
+
+    /// Outcome of a single module played in a player session instance.
+    #[derive(Serialize, Deserialize, Clone, Debug)]
+    #[serde(rename_all = "camelCase")]
+    pub struct PlayerSessionInstanceModuleResult {
+        /// ID of the (live) module
+        pub module_id: ModuleId,
+
+        /// Points scored in the module so far
+        pub points: u32,
+
+        /// Whether the module was played all the way through
+        pub completed: bool,
+    }
+
+    make_path_parts!(PlayerSessionInstancePointsPath => "/v1/jig/player/instance/points");
+
+    /// Request to report the points scored in a module of a player session instance.
+    ///
+    /// Reports replace earlier reports for the same module, so they can be safely retried.
+    #[derive(Serialize, Deserialize, Debug)]
+    #[serde(rename_all = "camelCase")]
+    pub struct PlayerSessionInstancePointsRequest {
+        /// Token returned when the instance was created
+        pub token: String,
+
+        /// The module's outcome
+        pub module: PlayerSessionInstanceModuleResult,
+    }
+
+    /// Results of a player session instance.
+    #[derive(Serialize, Deserialize, Clone, Debug)]
+    #[serde(rename_all = "camelCase")]
+    pub struct PlayerSessionInstanceResult {
+        /// ID of the instance
+        pub id: PlayerSessionInstanceId,
+
+        /// Name the player chose when joining the session
+        pub display_name: Option<String>,
+
+        /// Student on the session's roster who played, if the session has a roster
+        pub student_id: Option<RosterStudentId>,
+
+        /// Total points scored across all modules
+        pub points: u32,
+
+        /// Outcome of each module played, in the order they were first reported
+        pub modules: Vec<PlayerSessionInstanceModuleResult>,
+
+        /// When the instance was created
+        pub created_at: DateTime<Utc>,
+
+        /// When (if at all) the JIG was played all the way through
+        pub completed_at: Option<DateTime<Utc>>,
+    }
+
+    make_path_parts!(JigPlayerSessionInstancesPath => "/v1/jig/player/{}/instances" => JigPlayerSessionIndex);
+
+    /// Results of every instance of a player session.
+    #[derive(Serialize, Deserialize, Debug)]
+    #[serde(rename_all = "camelCase")]
+    pub struct JigPlayerSessionInstancesResponse {
+        /// Instance results, oldest first
+        pub instances: Vec<PlayerSessionInstanceResult>,
+    }
 }
 
 #[derive(Clone, Default, Serialize, Deserialize, Debug, Eq, PartialEq)]
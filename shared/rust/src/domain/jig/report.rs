@@ -5,7 +5,13 @@ use serde::{Deserialize, Serialize};
 use strum_macros::EnumIter;
 use uuid::Uuid;
 
-use crate::api::endpoints::PathPart;
+use crate::{
+    api::endpoints::PathPart,
+    domain::{
+        asset::{ReportAction, ReportStatus},
+        user::UserId,
+    },
+};
 
 use super::JigId;
 
@@ -44,6 +50,101 @@ pub struct JigReport {
 
     /// When report was submitted
     pub created_at: DateTime<Utc>,
+
+    /// Moderation status of the report
+    #[serde(default)]
+    pub status: ReportStatus,
+
+    /// Curator the report is assigned to
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assignee_id: Option<UserId>,
+
+    /// Action taken when the report was resolved
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub action: Option<ReportAction>,
+
+    /// Note left by the curator when resolving the report
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resolution_note: Option<String>,
+
+    /// When the report was resolved
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+make_path_parts!(ListJigReportsPath => "/v1/jig/admin/report");
+
+/// Query for listing the jig report moderation queue
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ListJigReportsQuery {
+    /// Filter by report type
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub report_type: Option<JigReportType>,
+
+    /// Filter by moderation status
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<ReportStatus>,
+
+    /// Filter by assigned curator
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assignee_id: Option<UserId>,
+
+    /// The page number of the reports to get.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page: Option<u32>,
+
+    /// The hits per page to be returned
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page_limit: Option<u32>,
+}
+
+/// Response for listing the jig report moderation queue, oldest first
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ListJigReportsResponse {
+    /// the reports returned.
+    pub reports: Vec<JigReport>,
+
+    /// The number of pages found.
+    pub pages: u32,
+
+    /// The total number of reports found
+    pub total_report_count: u64,
+}
+
+make_path_parts!(AssignJigReportPath => "/v1/jig/{}/report/{}/assign" => JigId, ReportId);
+
+/// Request for assigning a report to a curator
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct AssignJigReportRequest {
+    /// The curator to assign the report to. `None` unassigns the report.
+    pub assignee_id: Option<UserId>,
+}
+
+make_path_parts!(ResolveJigReportPath => "/v1/jig/{}/report/{}/resolve" => JigId, ReportId);
+
+/// Request for resolving a report
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ResolveJigReportRequest {
+    /// Action to take
+    pub action: ReportAction,
+
+    /// Note on the resolution. Required when contacting the author, as it is the message sent to them.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
 }
 
 make_path_parts!(CreateJigReportPath => "/v1/jig/{}/report" => JigId);
@@ -0,0 +1,194 @@
+//! Types for in-app notifications.
+
+use chrono::{DateTime, Utc};
+use macros::make_path_parts;
+use serde::{Deserialize, Serialize};
+
+use crate::api::endpoints::PathPart;
+
+wrap_uuid! {
+    /// Wrapper type around [`Uuid`], represents [`Notification::id`].
+    pub struct NotificationId
+}
+
+/// What a notification is about.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "backend", derive(sqlx::Type))]
+#[serde(rename_all = "camelCase")]
+#[repr(i16)]
+pub enum NotificationKind {
+    /// Another user followed the user.
+    ///
+    /// `data`: `{ "followerId": <UserId> }`
+    NewFollower = 0,
+
+    /// Another user liked one of the user's assets.
+    ///
+    /// `data`: `{ "assetId": <AssetId>, "likedBy": <UserId> }`
+    AssetLiked = 1,
+
+    /// A curator commented on one of the user's JIGs or resources.
+    ///
+    /// `data`: `{ "jigId": <JigId>, "commentId": <CommentId> }`, with `resourceId` instead of `jigId` for resources.
+    CurationComment = 2,
+
+    /// A report the user made was resolved.
+    ///
+    /// `data`: `{ "jigId": <JigId>, "reportId": <ReportId>, "action": <ReportAction> }`, with `resourceId` instead of `jigId` for resources.
+    ReportResolved = 3,
+
+    /// Another user invited the user to a circle.
+    ///
+    /// `data`: `{ "circleId": <CircleId>, "invitedBy": <UserId> }`
+    CircleInvite = 4,
+
+    /// The subscription of an account the user administers changed.
+    ///
+    /// `data`: `{ "accountId": <AccountId>, "status": <SubscriptionStatus> }`
+    SubscriptionChanged = 5,
+}
+
+impl TryFrom<i16> for NotificationKind {
+    type Error = anyhow::Error;
+
+    fn try_from(i: i16) -> Result<Self, Self::Error> {
+        match i {
+            0 => Ok(Self::NewFollower),
+            1 => Ok(Self::AssetLiked),
+            2 => Ok(Self::CurationComment),
+            3 => Ok(Self::ReportResolved),
+            4 => Ok(Self::CircleInvite),
+            5 => Ok(Self::SubscriptionChanged),
+            _ => anyhow::bail!("Notification kind {} is invalid", i),
+        }
+    }
+}
+
+/// A notification.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Notification {
+    /// ID of the notification
+    pub id: NotificationId,
+
+    /// What the notification is about
+    pub kind: NotificationKind,
+
+    /// Details of the notification, see [`NotificationKind`]
+    pub data: serde_json::Value,
+
+    /// When the notification was created
+    pub created_at: DateTime<Utc>,
+
+    /// When the notification was marked as read, if it was
+    pub read_at: Option<DateTime<Utc>>,
+}
+
+make_path_parts!(NotificationListPath => "/v1/notification");
+
+/// Query for listing the notifications of the requesting user.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationListQuery {
+    /// Only return notifications which haven't been read.
+    #[serde(default)]
+    pub unread_only: bool,
+
+    /// The page number of the notifications to fetch.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page: Option<u32>,
+
+    /// The hits per page to be returned
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page_limit: Option<u32>,
+}
+
+/// Response for listing the notifications of the requesting user.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationListResponse {
+    /// The notifications, most recent first
+    pub notifications: Vec<Notification>,
+
+    /// The number of pages found.
+    pub pages: u32,
+
+    /// The total number of notifications found
+    pub total_count: u64,
+}
+
+make_path_parts!(NotificationMarkReadPath => "/v1/notification/read");
+
+/// Request to mark notifications as read.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationMarkReadRequest {
+    /// Notifications to mark as read, or every notification if `None`.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ids: Option<Vec<NotificationId>>,
+}
+
+make_path_parts!(NotificationUnreadCountPath => "/v1/notification/unread-count");
+
+/// Response for counting the unread notifications of the requesting user.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationUnreadCountResponse {
+    /// The number of unread notifications
+    pub count: u64,
+}
+
+make_path_parts!(NotificationPreferencesPath => "/v1/notification/preferences");
+
+/// A user's preferences for which notifications they receive. Every notification is enabled by default.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationPreferences {
+    /// Whether to be notified of new followers
+    pub new_follower: bool,
+
+    /// Whether to be notified when an asset is liked
+    pub asset_liked: bool,
+
+    /// Whether to be notified of curation comments
+    pub curation_comment: bool,
+
+    /// Whether to be notified when a report is resolved
+    pub report_resolved: bool,
+
+    /// Whether to be notified of circle invites
+    pub circle_invite: bool,
+
+    /// Whether to be notified of subscription changes
+    pub subscription_changed: bool,
+}
+
+impl Default for NotificationPreferences {
+    fn default() -> Self {
+        Self {
+            new_follower: true,
+            asset_liked: true,
+            curation_comment: true,
+            report_resolved: true,
+            circle_invite: true,
+            subscription_changed: true,
+        }
+    }
+}
+
+impl NotificationPreferences {
+    /// Whether the user wants to receive notifications of `kind`
+    pub fn is_enabled(&self, kind: NotificationKind) -> bool {
+        match kind {
+            NotificationKind::NewFollower => self.new_follower,
+            NotificationKind::AssetLiked => self.asset_liked,
+            NotificationKind::CurationComment => self.curation_comment,
+            NotificationKind::ReportResolved => self.report_resolved,
+            NotificationKind::CircleInvite => self.circle_invite,
+            NotificationKind::SubscriptionChanged => self.subscription_changed,
+        }
+    }
+}
@@ -1,4 +1,5 @@
 //! Types for Playlists.
+pub mod curation;
 
 use chrono::{DateTime, Utc};
 use macros::make_path_parts;
@@ -137,6 +138,14 @@ pub struct PlaylistResponse {
     /// Number of plays Playlist
     pub plays: i64,
 
+    /// Average star rating of the Playlist in reviews, [`None`] if it hasn't been reviewed
+    #[serde(default)]
+    pub average_rating: Option<f32>,
+
+    /// Number of reviews of the Playlist
+    #[serde(default)]
+    pub review_count: i64,
+
     /// Live is current to Draft
     pub live_up_to_date: bool,
 
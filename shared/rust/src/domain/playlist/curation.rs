@@ -0,0 +1,191 @@
+//! Types for Playlist curation
+use chrono::{DateTime, Utc};
+use macros::make_path_parts;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    api::endpoints::PathPart,
+    domain::{asset::CurationStatus, user::UserId},
+};
+
+use super::PlaylistId;
+
+wrap_uuid! {
+    /// Wrapper type around [`Uuid`](Uuid), represents the ID of a curation comment.
+    pub struct CommentId
+}
+
+make_path_parts!(PlaylistCurationPath => "/v1/playlist/{}/curation" => PlaylistId);
+
+/// Curation data for Playlists
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PlaylistCurationData {
+    /// Playlist ID for curation
+    pub playlist_id: PlaylistId,
+
+    /// Fields curated by Admin
+    pub fields_done: PlaylistCurationFieldsDone,
+
+    /// Status for curation
+    pub curation_status: PlaylistCurationStatus,
+
+    /// Curator assigned to this Playlist
+    pub assignee_id: Option<UserId>,
+
+    /// When the Playlist entered the curation queue
+    pub created_at: DateTime<Utc>,
+
+    /// When the curation status last changed
+    pub status_updated_at: DateTime<Utc>,
+
+    /// When the current curator was assigned
+    pub assigned_at: Option<DateTime<Utc>>,
+
+    /// When curation was last completed
+    pub completed_at: Option<DateTime<Utc>>,
+
+    /// Comments from curator (not updatable)
+    pub comments: Vec<PlaylistCurationComment>,
+}
+
+/// Curation fields that have been completed
+///
+/// Authorization:
+/// Admin
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PlaylistCurationFieldsDone {
+    /// Display name of Playlist
+    pub display_name: bool,
+
+    /// Language of Playlist
+    pub language: bool,
+
+    /// Categories of Playlist
+    pub categories: bool,
+
+    /// Descriptions of Playlist
+    pub description: bool,
+
+    /// Age ranges of Playlist
+    pub age_ranges: bool,
+
+    /// Affiliations of Playlist
+    pub affiliations: bool,
+
+    /// Addtional resources of Playlist
+    pub additional_resources: bool,
+}
+
+/// Status of Curation
+pub type PlaylistCurationStatus = CurationStatus;
+
+make_path_parts!(PlaylistCurationUpdatePath => "/v1/playlist/{}/curation" => PlaylistId);
+
+/// Curation data for Playlists
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PlaylistCurationUpdateRequest {
+    /// Display name of Playlist
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<bool>,
+
+    /// Language of Playlist
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<bool>,
+
+    /// Categories of Playlist
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub categories: Option<bool>,
+
+    /// Descriptions of Playlist
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<bool>,
+
+    /// Age ranges of Playlist
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub age_ranges: Option<bool>,
+
+    /// Affiliations of Playlist
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub affiliations: Option<bool>,
+
+    /// Addtional resources of Playlist
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub additional_resources: Option<bool>,
+
+    /// Curation status of Playlist
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub curation_status: Option<PlaylistCurationStatus>,
+
+    /// Curator assigned to the Playlist, `Some(None)` unassigns the current curator
+    #[serde(default)]
+    #[serde(deserialize_with = "crate::domain::deserialize_optional_field")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assignee_id: Option<Option<UserId>>,
+}
+
+make_path_parts!(PlaylistCurationCommentCreatePath => "/v1/playlist/{}/curation/comment" => PlaylistId);
+
+/// Curation comment for Playlists
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "backend", derive(sqlx::Type))]
+#[serde(rename_all = "camelCase")]
+pub struct PlaylistCurationComment {
+    /// Comment ID
+    pub id: CommentId,
+
+    /// Playlist ID for comment
+    pub playlist_id: PlaylistId,
+
+    /// Comment
+    pub value: String,
+
+    /// When comment was submitted
+    pub created_at: DateTime<Utc>,
+
+    /// ID of commenter
+    pub author_id: UserId,
+}
+
+/// Request to comment on Playlist
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PlaylistCurationCommentRequest {
+    /// Comment
+    pub value: String,
+}
+
+make_path_parts!(PlaylistCurationCommentGetPath => "/v1/playlist/{}/curation/comment/{}" => PlaylistId, CommentId);
+
+/// Curation comment for Playlists, with the author's name
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PlaylistCurationCommentResponse {
+    /// ID of comment
+    pub id: CommentId,
+
+    /// ID of Playlist
+    pub playlist_id: PlaylistId,
+
+    /// Curator comment
+    pub value: String,
+
+    /// When comment was submitted
+    pub created_at: Option<DateTime<Utc>>,
+
+    /// ID of commenter
+    pub author_id: UserId,
+
+    /// Name of commenter
+    pub author_name: String,
+}
@@ -13,7 +13,7 @@ use crate::api::endpoints::PathPart;
 
 use super::{
     additional_resource::AdditionalResource,
-    asset::{DraftOrLive, OrderBy, PrivacyLevel, UserOrMe},
+    asset::{DraftOrLive, OrderBy, PrivacyLevel, SearchSortBy, UserOrMe},
     category::CategoryId,
     meta::{AffiliationId, AgeRangeId, ResourceTypeId},
     module::LiteModule,
@@ -53,6 +53,14 @@ pub struct ResourceResponse {
     /// Number of views for a Resource
     pub views: i64,
 
+    /// Average star rating of the Resource in reviews, [`None`] if it hasn't been reviewed
+    #[serde(default)]
+    pub average_rating: Option<f32>,
+
+    /// Number of reviews of the Resource
+    #[serde(default)]
+    pub review_count: i64,
+
     /// Live is current to Draft
     pub live_up_to_date: bool,
 
@@ -395,6 +403,11 @@ pub struct ResourceSearchQuery {
     #[serde(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub is_rated: Option<bool>,
+
+    /// How to sort the results, by relevance if [`None`]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort_by: Option<SearchSortBy>,
 }
 
 /// Response for successful search.
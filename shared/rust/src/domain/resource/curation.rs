@@ -3,7 +3,7 @@ use chrono::{DateTime, Utc};
 use macros::make_path_parts;
 use serde::{Deserialize, Serialize};
 
-use crate::api::endpoints::PathPart;
+use crate::{api::endpoints::PathPart, domain::asset::CurationStatus};
 
 use super::{report::ResourceReport, ResourceId, UserId};
 
@@ -27,6 +27,21 @@ pub struct ResourceCurationData {
     /// Status for curation
     pub curation_status: ResourceCurationStatus,
 
+    /// Curator assigned to this Resource
+    pub assignee_id: Option<UserId>,
+
+    /// When the Resource entered the curation queue
+    pub created_at: DateTime<Utc>,
+
+    /// When the curation status last changed
+    pub status_updated_at: DateTime<Utc>,
+
+    /// When the current curator was assigned
+    pub assigned_at: Option<DateTime<Utc>>,
+
+    /// When curation was last completed
+    pub completed_at: Option<DateTime<Utc>>,
+
     /// Comments from curator (not updatable)
     pub comments: Vec<ResourceCurationComment>,
 
@@ -78,29 +93,7 @@ impl Default for ResourceCurationFieldsDone {
 }
 
 /// Status of Curation
-#[derive(Serialize, Deserialize, Copy, Clone, Eq, PartialEq, Debug)]
-#[cfg_attr(feature = "backend", derive(sqlx::Type))]
-#[serde(rename_all = "camelCase")]
-#[repr(i16)]
-pub enum ResourceCurationStatus {
-    /// Resources first curation
-    New = 0,
-
-    /// Resource has been updated by user
-    NewVersion = 1,
-
-    /// Admin is reviewing Resource
-    InProgress = 2,
-
-    /// Curation of Resource completed
-    Done = 3,
-}
-
-impl Default for ResourceCurationStatus {
-    fn default() -> Self {
-        Self::New
-    }
-}
+pub type ResourceCurationStatus = CurationStatus;
 
 make_path_parts!(ResourceCurationUpdatePath => "/v1/resource/{}/curation" => ResourceId);
 
@@ -147,6 +140,12 @@ pub struct ResourceCurationUpdateRequest {
     #[serde(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub curation_status: Option<ResourceCurationStatus>,
+
+    /// Curator assigned to the Resource, `Some(None)` unassigns the current curator
+    #[serde(default)]
+    #[serde(deserialize_with = "crate::domain::deserialize_optional_field")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assignee_id: Option<Option<UserId>>,
 }
 
 make_path_parts!(ResourceCurationCommentCreatePath => "/v1/resource/{}/curation/comment" => ResourceId);
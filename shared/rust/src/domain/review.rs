@@ -0,0 +1,267 @@
+//! Types for star ratings and reviews of public assets.
+//!
+//! Signed-in users can review live, public JIGs, resources and playlists they didn't author, once
+//! per asset. The author of the asset can reply to each review, and anyone signed in can report a
+//! review for abuse. Reported reviews are moderated by curators, like reported assets.
+
+use chrono::{DateTime, Utc};
+use macros::make_path_parts;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    api::endpoints::PathPart,
+    domain::{
+        asset::{AssetId, AssetType, ReportAction, ReportStatus},
+        user::UserId,
+    },
+};
+
+wrap_uuid! {
+    /// Wrapper type around [`Uuid`], represents [`Review::id`].
+    pub struct ReviewId
+}
+
+/// Lowest star rating of a review.
+/// If this is changed then the DB's check constraint must also be updated.
+pub const REVIEW_RATING_MIN: u8 = 1;
+
+/// Highest star rating of a review.
+/// If this is changed then the DB's check constraint must also be updated.
+pub const REVIEW_RATING_MAX: u8 = 5;
+
+/// Maximum length of the text of a review or of a reply to it.
+/// If this is changed then the DB's check constraints must also be updated.
+pub const REVIEW_TEXT_MAX_LEN: usize = 1000;
+
+/// Number of pending reports after which a review is hidden until a curator resolves them.
+pub const REVIEW_REPORT_HIDE_THRESHOLD: u32 = 3;
+
+/// Over-the-wire representation of a review of an asset.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Review {
+    /// ID of the review
+    pub id: ReviewId,
+
+    /// The reviewed asset
+    pub asset_id: AssetId,
+
+    /// User who wrote the review
+    pub author_id: UserId,
+
+    /// Name of the user who wrote the review
+    pub author_name: Option<String>,
+
+    /// Star rating, from [`REVIEW_RATING_MIN`] to [`REVIEW_RATING_MAX`]
+    pub rating: u8,
+
+    /// Text of the review. Defaults to empty string.
+    pub text: String,
+
+    /// Reply of the author of the asset, if they replied
+    pub reply: Option<String>,
+
+    /// When the author of the asset last replied
+    pub replied_at: Option<DateTime<Utc>>,
+
+    /// When the review was written
+    pub created_at: DateTime<Utc>,
+
+    /// When the review was last edited
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+make_path_parts!(ReviewListPath => "/v1/{}/{}/review" => AssetType, Uuid);
+
+/// Query for listing the reviews of an asset.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewListQuery {
+    /// The page number to get.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page: Option<u32>,
+
+    /// The hits per page to be returned
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page_limit: Option<u32>,
+}
+
+/// Response for listing the reviews of an asset.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewListResponse {
+    /// The reviews returned, most recent first.
+    pub reviews: Vec<Review>,
+
+    /// The number of pages found.
+    pub pages: u32,
+
+    /// The total number of reviews found
+    pub total_review_count: u64,
+
+    /// Average star rating of the asset, [`None`] if it hasn't been reviewed
+    pub average_rating: Option<f32>,
+}
+
+make_path_parts!(ReviewUpsertPath => "/v1/{}/{}/review" => AssetType, Uuid);
+
+/// Request to review an asset, or to edit the requesting user's review of it.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewUpsertRequest {
+    /// Star rating, from [`REVIEW_RATING_MIN`] to [`REVIEW_RATING_MAX`]
+    pub rating: u8,
+
+    /// Text of the review
+    #[serde(default)]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub text: String,
+}
+
+make_path_parts!(ReviewDeletePath => "/v1/review/{}" => ReviewId);
+
+make_path_parts!(ReviewReplyPath => "/v1/review/{}/reply" => ReviewId);
+
+/// Request to reply to a review of one of the requesting user's assets.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewReplyRequest {
+    /// Text of the reply. An empty reply removes the existing one.
+    pub text: String,
+}
+
+make_path_parts!(ReviewReportPath => "/v1/review/{}/report" => ReviewId);
+
+/// Why a review was reported.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "backend", derive(sqlx::Type))]
+#[serde(rename_all = "camelCase")]
+#[repr(i16)]
+pub enum ReviewReportType {
+    /// Spam or advertising
+    Spam = 0,
+
+    /// Offensive or abusive language
+    Offensive = 1,
+
+    /// Unrelated to the asset
+    OffTopic = 2,
+
+    /// Any other reason
+    Other = 3,
+}
+
+impl TryFrom<i16> for ReviewReportType {
+    type Error = anyhow::Error;
+
+    fn try_from(i: i16) -> Result<Self, Self::Error> {
+        match i {
+            0 => Ok(Self::Spam),
+            1 => Ok(Self::Offensive),
+            2 => Ok(Self::OffTopic),
+            3 => Ok(Self::Other),
+            _ => anyhow::bail!("Review report type {} is invalid", i),
+        }
+    }
+}
+
+/// Request to report a review for abuse.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewReportRequest {
+    /// Why the review is reported
+    pub report_type: ReviewReportType,
+}
+
+/// A report of a review, as seen by curators.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewReport {
+    /// The reported review
+    pub review: Review,
+
+    /// Whether the review is hidden, either because of pending reports or because it was blocked
+    pub hidden: bool,
+
+    /// User who reported the review
+    pub reporter_id: UserId,
+
+    /// Why the review was reported
+    pub report_type: ReviewReportType,
+
+    /// When the review was reported
+    pub created_at: DateTime<Utc>,
+
+    /// Moderation status of the report
+    pub status: ReportStatus,
+
+    /// Action taken when the report was resolved
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub action: Option<ReportAction>,
+
+    /// Note left by the curator when resolving the report
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resolution_note: Option<String>,
+
+    /// When the report was resolved
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+make_path_parts!(ReviewReportListPath => "/v1/review/admin/report");
+
+/// Query for listing the review report moderation queue.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewReportListQuery {
+    /// Filter by moderation status
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<ReportStatus>,
+
+    /// The page number of the reports to get.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page: Option<u32>,
+
+    /// The hits per page to be returned
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page_limit: Option<u32>,
+}
+
+/// Response for listing the review report moderation queue, oldest first.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewReportListResponse {
+    /// The reports returned.
+    pub reports: Vec<ReviewReport>,
+
+    /// The number of pages found.
+    pub pages: u32,
+
+    /// The total number of reports found
+    pub total_report_count: u64,
+}
+
+make_path_parts!(ReviewReportResolvePath => "/v1/review/{}/report/resolve" => ReviewId);
+
+/// Request to resolve the open reports of a review.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewReportResolveRequest {
+    /// Action to take. [`ReportAction::Dismiss`] shows the review again and
+    /// [`ReportAction::Block`] hides it for good. Reviewers can't be contacted.
+    pub action: ReportAction,
+
+    /// Note on the resolution
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
@@ -0,0 +1,203 @@
+//! Types for class rosters.
+//!
+//! A roster lists the students of a teacher's class by nickname, so that students can pick their
+//! name when joining a JIG player session without any personal data being collected.
+
+use chrono::{DateTime, Utc};
+use macros::make_path_parts;
+use serde::{Deserialize, Serialize};
+
+use crate::api::endpoints::PathPart;
+
+wrap_uuid! {
+    /// Wrapper type around [`Uuid`], represents [`Roster::id`].
+    pub struct RosterId
+}
+
+wrap_uuid! {
+    /// Wrapper type around [`Uuid`], represents [`RosterStudent::id`].
+    pub struct RosterStudentId
+}
+
+/// Maximum length of a roster's name or a student's nickname.
+/// If this is changed then the DB's check constraints must also be updated.
+pub const ROSTER_NAME_MAX_LEN: usize = 50;
+
+/// Picture a student picks to confirm their name when joining a player session.
+///
+/// Young students can't be expected to type a password, so they pick a picture instead.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "backend", derive(sqlx::Type))]
+#[serde(rename_all = "camelCase")]
+#[repr(i16)]
+pub enum PicturePassword {
+    /// Apple
+    Apple = 0,
+    /// Ball
+    Ball = 1,
+    /// Car
+    Car = 2,
+    /// Cat
+    Cat = 3,
+    /// Dog
+    Dog = 4,
+    /// Fish
+    Fish = 5,
+    /// Flower
+    Flower = 6,
+    /// House
+    House = 7,
+    /// Moon
+    Moon = 8,
+    /// Star
+    Star = 9,
+    /// Sun
+    Sun = 10,
+    /// Tree
+    Tree = 11,
+}
+
+impl PicturePassword {
+    /// Every picture, in the order they are shown to students.
+    pub const ALL: [Self; 12] = [
+        Self::Apple,
+        Self::Ball,
+        Self::Car,
+        Self::Cat,
+        Self::Dog,
+        Self::Fish,
+        Self::Flower,
+        Self::House,
+        Self::Moon,
+        Self::Star,
+        Self::Sun,
+        Self::Tree,
+    ];
+
+    /// Emoji representing the picture.
+    pub fn emoji(&self) -> &'static str {
+        match self {
+            Self::Apple => "🍎",
+            Self::Ball => "⚽",
+            Self::Car => "🚗",
+            Self::Cat => "🐱",
+            Self::Dog => "🐶",
+            Self::Fish => "🐟",
+            Self::Flower => "🌸",
+            Self::House => "🏠",
+            Self::Moon => "🌙",
+            Self::Star => "⭐",
+            Self::Sun => "☀️",
+            Self::Tree => "🌳",
+        }
+    }
+}
+
+/// A student on a roster, as seen by the teacher.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RosterStudent {
+    /// ID of the student
+    pub id: RosterStudentId,
+
+    /// Name the student is known by in class
+    pub nickname: String,
+
+    /// Picture the student picks to confirm their name, if any
+    pub picture_password: Option<PicturePassword>,
+}
+
+/// A class roster.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Roster {
+    /// ID of the roster
+    pub id: RosterId,
+
+    /// Name of the roster, e.g. the name of the class
+    pub display_name: String,
+
+    /// Students on the roster, ordered by nickname
+    pub students: Vec<RosterStudent>,
+
+    /// When the roster was created
+    pub created_at: DateTime<Utc>,
+
+    /// When the roster was last edited
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// Request to add a student to a roster.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RosterStudentCreateRequest {
+    /// Name the student is known by in class
+    pub nickname: String,
+
+    /// Picture the student picks to confirm their name, if any
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub picture_password: Option<PicturePassword>,
+}
+
+make_path_parts!(RosterCreatePath => "/v1/roster");
+
+/// Request to create a roster.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RosterCreateRequest {
+    /// Name of the roster
+    pub display_name: String,
+
+    /// Students to add to the roster
+    #[serde(default)]
+    pub students: Vec<RosterStudentCreateRequest>,
+}
+
+make_path_parts!(RosterListPath => "/v1/roster");
+
+/// Response for listing the rosters of the requesting user.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RosterListResponse {
+    /// The user's rosters, ordered by name
+    pub rosters: Vec<Roster>,
+}
+
+make_path_parts!(RosterGetPath => "/v1/roster/{}" => RosterId);
+
+make_path_parts!(RosterUpdatePath => "/v1/roster/{}" => RosterId);
+
+/// Request to update a roster.
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct RosterUpdateRequest {
+    /// Name of the roster
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+}
+
+make_path_parts!(RosterDeletePath => "/v1/roster/{}" => RosterId);
+
+make_path_parts!(RosterStudentCreatePath => "/v1/roster/{}/student" => RosterId);
+
+make_path_parts!(RosterStudentUpdatePath => "/v1/roster/{}/student/{}" => RosterId, RosterStudentId);
+
+/// Request to update a student on a roster.
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct RosterStudentUpdateRequest {
+    /// Name the student is known by in class
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nickname: Option<String>,
+
+    /// Picture the student picks to confirm their name. `Some(None)` removes it.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(deserialize_with = "super::deserialize_optional_field")]
+    pub picture_password: Option<Option<PicturePassword>>,
+}
+
+make_path_parts!(RosterStudentDeletePath => "/v1/roster/{}/student/{}" => RosterId, RosterStudentId);
@@ -2,8 +2,9 @@
 
 use std::fmt;
 
-use super::user::UserId;
+use super::{billing::SchoolId, user::UserId};
 use crate::api::endpoints::PathPart;
+use chrono::{DateTime, Utc};
 use macros::make_path_parts;
 use serde::{Deserialize, Serialize};
 
@@ -77,6 +78,29 @@ pub enum CreateSessionResponse {
         #[serde(skip_serializing_if = "Option::is_none")]
         oauth_profile: Option<OAuthUserProfile>,
     },
+
+    /// The user has to verify a code from their authenticator app before the session can be used.
+    ///
+    /// The returned session can only be used to [verify a code](crate::api::endpoints::session::VerifyTotp),
+    /// or if the user hasn't set up two-factor authentication yet (`enrolled` is `false`), to
+    /// [set it up](crate::api::endpoints::user::EnrollTotp).
+    ///
+    /// ## Json response:
+    /// ```json
+    /// {
+    ///     "twoFactor": {
+    ///         "csrf": <CSRF_TOKEN>,
+    ///         "enrolled": true
+    ///     }
+    /// }
+    /// ```
+    TwoFactor {
+        /// Csrf token. Note that this field is "flattened" into it's contents when (de)serialized. See example above.
+        #[serde(flatten)]
+        response: NewSessionResponse,
+        /// Whether the user has already set up two-factor authentication.
+        enrolled: bool,
+    },
 }
 
 /// User's profile info fetched from the OAuth service. Returned as part of the identity claims
@@ -118,6 +142,16 @@ pub struct NewSessionResponse {
     pub csrf: String,
 }
 
+make_path_parts!(VerifyTotpPath => "/v1/session/totp");
+
+/// Request to finish signing in with a code from an authenticator app, or a recovery code.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifyTotpRequest {
+    /// The 6 digit code from the authenticator app, or one of the user's recovery codes.
+    pub code: String,
+}
+
 /// Which URL to use for OAuth callback.
 #[derive(Serialize, Deserialize, Debug, Copy, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -143,11 +177,17 @@ impl PathPart for OAuthUrlKind {
 pub enum GetOAuthUrlServiceKind {
     /// Google OAuth v2
     Google,
+    /// Microsoft identity platform, for both Microsoft 365 (e.g. school) and personal accounts
+    Microsoft,
+    /// Sign in with Apple
+    Apple,
 }
 impl PathPart for GetOAuthUrlServiceKind {
     fn get_path_string(&self) -> String {
         match self {
             Self::Google => String::from("google"),
+            Self::Microsoft => String::from("microsoft"),
+            Self::Apple => String::from("apple"),
         }
     }
 }
@@ -158,6 +198,12 @@ impl PathPart for GetOAuthUrlServiceKind {
 pub enum OAuthProvider {
     /// Google OAuth v2
     Google,
+    /// Microsoft identity platform
+    Microsoft,
+    /// Sign in with Apple
+    Apple,
+    /// A school's single sign-on
+    Sso,
 }
 
 impl OAuthProvider {
@@ -165,6 +211,9 @@ impl OAuthProvider {
     pub fn as_str(&self) -> &'static str {
         match self {
             Self::Google => "Google",
+            Self::Microsoft => "Microsoft",
+            Self::Apple => "Apple",
+            Self::Sso => "your school's single sign-on",
         }
     }
 }
@@ -179,6 +228,16 @@ pub struct GetOAuthUrlResponse {
     pub url: String,
 }
 
+make_path_parts!(GetSsoUrlPath => "/v1/session/sso/url/{}" => OAuthUrlKind);
+
+/// Query for the URL to sign in with single sign-on.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GetSsoUrlQuery {
+    /// The user's email, which decides the school whose identity provider is used.
+    pub email: String,
+}
+
 make_path_parts!(CreateSessionOAuthPath => "/v1/session/oauth");
 
 /// Request for Creating a Session / signing in via oauth.
@@ -195,6 +254,36 @@ pub enum CreateSessionOAuthRequest {
         /// Not sure if this is needed anymore
         redirect_kind: OAuthUrlKind,
     },
+
+    /// OAuth with Microsoft
+    Microsoft {
+        /// The Microsoft OAuth Code
+        code: String,
+
+        /// Which OAuth url was used
+        redirect_kind: OAuthUrlKind,
+    },
+
+    /// Sign in with Apple
+    Apple {
+        /// The Apple OAuth Code
+        code: String,
+
+        /// Which OAuth url was used
+        redirect_kind: OAuthUrlKind,
+    },
+
+    /// A school's single sign-on
+    Sso {
+        /// The school whose identity provider was used
+        school_id: SchoolId,
+
+        /// The identity provider's OAuth Code
+        code: String,
+
+        /// Which OAuth url was used
+        redirect_kind: OAuthUrlKind,
+    },
 }
 
 /// Optional query used as the first option for authentication with the API
@@ -209,8 +298,61 @@ impl fmt::Debug for CreateSessionOAuthRequest {
         match self {
             // todo: replace with `finish_non_exhaustive`
             Self::Google { .. } => f.debug_struct("Google").finish(),
+            Self::Microsoft { .. } => f.debug_struct("Microsoft").finish(),
+            Self::Apple { .. } => f.debug_struct("Apple").finish(),
+            Self::Sso { school_id, .. } => {
+                f.debug_struct("Sso").field("school_id", school_id).finish()
+            }
         }
     }
 }
 
 make_path_parts!(DeleteSessionPath => "/v1/session");
+
+wrap_uuid! {
+    /// Wrapper type around [`Uuid`], represents [`ActiveSession::id`].
+    pub struct SessionId
+}
+
+/// A session a user is signed in with.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ActiveSession {
+    /// ID of the session. Unlike the session's token, this can be shared safely.
+    pub id: SessionId,
+
+    /// When the user signed in
+    pub created_at: DateTime<Utc>,
+
+    /// When the session was last used, if it has been used since signing in
+    pub last_used_at: Option<DateTime<Utc>>,
+
+    /// When the session expires, if ever
+    pub expires_at: Option<DateTime<Utc>>,
+
+    /// User agent of the browser which last used the session
+    pub user_agent: Option<String>,
+
+    /// Network the session was last used from, e.g. `203.0.113.0/24`
+    pub ip_hint: Option<String>,
+
+    /// Bitmask of what the session can be used for
+    pub scope_mask: i16,
+
+    /// Whether this is the session making the request
+    pub current: bool,
+}
+
+make_path_parts!(ListActiveSessionsPath => "/v1/session/active");
+
+/// Response for listing the sessions a user is signed in with.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ListActiveSessionsResponse {
+    /// The sessions, most recently created first.
+    pub sessions: Vec<ActiveSession>,
+}
+
+make_path_parts!(RevokeSessionPath => "/v1/session/active/{}" => SessionId);
+
+make_path_parts!(RevokeOtherSessionsPath => "/v1/session/active");
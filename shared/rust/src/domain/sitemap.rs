@@ -0,0 +1,108 @@
+//! Types for the sitemap of public content.
+
+use chrono::{DateTime, Utc};
+use macros::make_path_parts;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::api::endpoints::PathPart;
+
+/// Maximum number of entries in a single sitemap page.
+///
+/// Sitemaps are limited to 50,000 urls each, this leaves some headroom.
+pub const SITEMAP_PAGE_SIZE: u32 = 10_000;
+
+/// Kind of public content listed in the sitemap.
+///
+/// Resources aren't listed, they don't have a page of their own and open their content directly.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub enum SitemapKind {
+    /// Published public JIGs
+    Jig,
+
+    /// Published public playlists
+    Playlist,
+
+    /// Published public courses
+    Course,
+
+    /// Circles
+    Circle,
+
+    /// Profiles of users with published public content
+    User,
+}
+
+impl SitemapKind {
+    /// Every kind of content in the sitemap.
+    pub const ALL: [Self; 5] = [
+        Self::Jig,
+        Self::Playlist,
+        Self::Course,
+        Self::Circle,
+        Self::User,
+    ];
+
+    /// Represents the kind as a `str`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Jig => "jig",
+            Self::Playlist => "playlist",
+            Self::Course => "course",
+            Self::Circle => "circle",
+            Self::User => "user",
+        }
+    }
+}
+
+impl PathPart for SitemapKind {
+    fn get_path_string(&self) -> String {
+        self.as_str().to_string()
+    }
+}
+
+make_path_parts!(SitemapIndexPath => "/v1/sitemap");
+
+/// Summary of the sitemap entries of one kind of content.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SitemapSummary {
+    /// The kind of content.
+    pub kind: SitemapKind,
+
+    /// Number of pages of entries, see [`SITEMAP_PAGE_SIZE`].
+    pub pages: u32,
+
+    /// When the most recently edited entry was last edited.
+    pub last_edited: Option<DateTime<Utc>>,
+}
+
+/// Response for the sitemap index.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SitemapIndexResponse {
+    /// Summaries for every kind of content.
+    pub sitemaps: Vec<SitemapSummary>,
+}
+
+make_path_parts!(SitemapPagePath => "/v1/sitemap/{}/{}" => SitemapKind, u32);
+
+/// A single public item in the sitemap.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SitemapEntry {
+    /// The item's ID.
+    pub id: Uuid,
+
+    /// When the item was last edited.
+    pub last_edited: Option<DateTime<Utc>>,
+}
+
+/// Response for a page of sitemap entries.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SitemapPageResponse {
+    /// The entries, ordered by ID.
+    pub entries: Vec<SitemapEntry>,
+}
@@ -691,6 +691,157 @@ pub enum ChangePasswordRequest {
 
 make_path_parts!(UserDeletePath => "/v1/user/me");
 
+// Two-factor authentication
+
+make_path_parts!(TotpGetPath => "/v1/user/me/totp");
+
+/// Response for [`GetTotp`](crate::api::endpoints::user::GetTotp)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TotpStatusResponse {
+    /// Whether the user has to enter a code from their authenticator app when signing in.
+    pub enabled: bool,
+
+    /// Whether two-factor authentication is mandatory for the user, i.e. it can't be disabled.
+    pub required: bool,
+
+    /// Number of recovery codes which haven't been used yet.
+    pub recovery_codes_left: u32,
+}
+
+make_path_parts!(TotpEnrollPath => "/v1/user/me/totp");
+
+/// Response for [`EnrollTotp`](crate::api::endpoints::user::EnrollTotp)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TotpEnrollResponse {
+    /// The base32 encoded secret, for typing into an authenticator app.
+    pub secret: String,
+
+    /// `otpauth://` URI of the secret, to show as a QR code for scanning with an authenticator app.
+    pub provisioning_uri: String,
+}
+
+/// Request containing a code from an authenticator app.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TotpCodeRequest {
+    /// The 6 digit code.
+    pub code: String,
+}
+
+make_path_parts!(TotpConfirmPath => "/v1/user/me/totp/confirm");
+
+/// Response for [`ConfirmTotp`](crate::api::endpoints::user::ConfirmTotp)
+///
+/// Note: This response *also* includes a cookie.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TotpConfirmResponse {
+    /// A transparent CSRF token to use for the new session.
+    pub csrf: String,
+
+    /// Single use codes to sign in with when the authenticator app isn't available.
+    pub recovery_codes: Vec<String>,
+}
+
+make_path_parts!(TotpDisablePath => "/v1/user/me/totp");
+
+make_path_parts!(TotpRecoveryCodesPath => "/v1/user/me/totp/recovery-codes");
+
+/// Response for [`RegenerateRecoveryCodes`](crate::api::endpoints::user::RegenerateRecoveryCodes)
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TotpRecoveryCodesResponse {
+    /// Single use codes to sign in with when the authenticator app isn't available.
+    /// Replaces all earlier recovery codes.
+    pub recovery_codes: Vec<String>,
+}
+
+// Personal API tokens
+
+/// Prefix of personal API tokens, which tells them apart from session tokens.
+pub const API_TOKEN_PREFIX: &str = "jpat_";
+
+wrap_uuid! {
+    /// Wrapper type around [`Uuid`], represents [`ApiToken::id`].
+    pub struct ApiTokenId
+}
+
+/// A long-lived token for using the API from scripts, without signing in.
+///
+/// Passed in the request header as `Authorization: Bearer <token>`. Only endpoints which limit
+/// the user's scopes to the token's accept it, e.g. creating, updating and publishing jigs.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiToken {
+    /// ID of the token. Unlike the token itself, this can be shared safely.
+    pub id: ApiTokenId,
+
+    /// Name to recognize the token by, e.g. what script it's used in.
+    pub name: String,
+
+    /// The user's scopes the token can use. A token with [`UserScope::Admin`] can use all of them.
+    pub scopes: Vec<UserScope>,
+
+    /// Whether the token can only be used for requests which don't change anything, like `GET`.
+    pub read_only: bool,
+
+    /// When the token was created.
+    pub created_at: DateTime<Utc>,
+
+    /// When the token was last used, if ever.
+    pub last_used_at: Option<DateTime<Utc>>,
+
+    /// When the token expires, if ever.
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+make_path_parts!(ApiTokenCreatePath => "/v1/user/me/api-token");
+
+/// Request for [`CreateApiToken`](crate::api::endpoints::user::CreateApiToken)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiTokenCreateRequest {
+    /// Name to recognize the token by.
+    pub name: String,
+
+    /// Scopes the token can be used for. Each must be one of the user's own scopes.
+    #[serde(default)]
+    pub scopes: Vec<UserScope>,
+
+    /// Whether the token can only be used for requests which don't change anything.
+    pub read_only: bool,
+
+    /// When the token expires, if ever.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// Response for [`CreateApiToken`](crate::api::endpoints::user::CreateApiToken)
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiTokenCreateResponse {
+    /// ID of the created token.
+    pub id: ApiTokenId,
+
+    /// The secret token, starting with [`API_TOKEN_PREFIX`]. It can't be retrieved again.
+    pub token: String,
+}
+
+make_path_parts!(ApiTokenListPath => "/v1/user/me/api-token");
+
+/// Response for [`ListApiTokens`](crate::api::endpoints::user::ListApiTokens)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiTokenListResponse {
+    /// The user's tokens, most recently created first.
+    pub tokens: Vec<ApiToken>,
+}
+
+make_path_parts!(ApiTokenRevokePath => "/v1/user/me/api-token/{}" => ApiTokenId);
+
 // Colors
 
 make_path_parts!(UserColorCreatePath => "/v1/user/me/color");
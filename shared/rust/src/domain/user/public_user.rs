@@ -3,10 +3,15 @@
 use crate::{
     api::endpoints::PathPart,
     domain::{
-        additional_resource::AdditionalResource, asset::UserOrMe, circle::CircleId,
-        csv_encode_uuids, from_csv, image::ImageId, user::UserId,
+        additional_resource::AdditionalResource,
+        asset::{AssetId, UserOrMe},
+        circle::CircleId,
+        csv_encode_uuids, from_csv,
+        image::ImageId,
+        user::UserId,
     },
 };
+use chrono::{DateTime, Utc};
 use macros::make_path_parts;
 use serde::{Deserialize, Serialize};
 use strum_macros::Display;
@@ -335,6 +340,59 @@ make_path_parts!(PublicUserFollowPath => "/v1/user/{}/follow" => UserId);
 
 make_path_parts!(PublicUserUnfollowPath => "/v1/user/{}/unfollow" => UserId);
 
+make_path_parts!(UserFeedPath => "/v1/user/me/feed");
+
+/// Query for [`Feed`](crate::api::endpoints::user::Feed).
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct UserFeedQuery {
+    /// Only return activity from after this cursor, as returned in [`UserFeedResponse::next_cursor`].
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+
+    /// The number of items to return.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page_limit: Option<u32>,
+}
+
+/// Something a followed user did.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum UserFeedActivity {
+    /// Published a public JIG, resource, playlist or course.
+    Published(AssetId),
+
+    /// Joined a circle.
+    JoinedCircle(CircleId),
+}
+
+/// An item in the activity feed.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UserFeedItem {
+    /// The followed user
+    pub user_id: UserId,
+
+    /// What they did
+    pub activity: UserFeedActivity,
+
+    /// When they did it
+    pub created_at: DateTime<Utc>,
+}
+
+/// Response for [`Feed`](crate::api::endpoints::user::Feed).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UserFeedResponse {
+    /// Activity of the users the requesting user follows, most recent first
+    pub items: Vec<UserFeedItem>,
+
+    /// Pass as [`UserFeedQuery::cursor`] to get the next page, or `None` if there are no more items.
+    pub next_cursor: Option<String>,
+}
+
 /// Sort browse results by timestamp
 #[derive(Serialize, Deserialize, Copy, Clone, Eq, PartialEq, Debug, Display)]
 #[cfg_attr(feature = "backend", derive(sqlx::Type))]
@@ -0,0 +1,251 @@
+//! Types for outbound webhooks.
+//!
+//! A webhook is a URL which Jigzi `POST`s a [`WebhookPayload`] to when an event it's subscribed to
+//! happens, so that integrations can react without polling the API.
+//!
+//! # Signatures
+//! Every delivery has a [`WEBHOOK_SIGNATURE_HEADER`] header of the form `t=<timestamp>,v1=<signature>`,
+//! where `timestamp` is the unix time the delivery was attempted at and `signature` is the hex encoded
+//! HMAC-SHA256 of `<timestamp>.<body>`, keyed with the webhook's secret.
+//! Receivers should compare the signature in constant time, and reject old timestamps to prevent replays.
+//!
+//! # Retries
+//! A delivery succeeds if the URL responds with a `2xx` status within 10 seconds.
+//! Failed deliveries are retried with exponential backoff, for about a day.
+
+use chrono::{DateTime, Utc};
+use macros::make_path_parts;
+use serde::{Deserialize, Serialize};
+
+use crate::api::endpoints::PathPart;
+
+wrap_uuid! {
+    /// Wrapper type around [`Uuid`], represents [`Webhook::id`].
+    pub struct WebhookId
+}
+
+wrap_uuid! {
+    /// Wrapper type around [`Uuid`], represents [`WebhookDelivery::id`].
+    pub struct WebhookDeliveryId
+}
+
+/// Name of the header containing the signature of a delivery.
+pub const WEBHOOK_SIGNATURE_HEADER: &str = "X-Jigzi-Signature";
+
+/// Name of the header containing the [`WebhookEvent`] of a delivery.
+pub const WEBHOOK_EVENT_HEADER: &str = "X-Jigzi-Event";
+
+/// Something which happened that a webhook can subscribe to.
+///
+/// Events are sent to webhooks of the user the asset or account belongs to,
+/// and to admins' webhooks which subscribe to every user's events.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "backend", derive(sqlx::Type))]
+#[serde(rename_all = "camelCase")]
+#[repr(i16)]
+pub enum WebhookEvent {
+    /// A JIG was published.
+    ///
+    /// `data`: `{ "jigId": <JigId> }`
+    JigPublished = 0,
+
+    /// A resource was published.
+    ///
+    /// `data`: `{ "resourceId": <ResourceId> }`
+    ResourcePublished = 1,
+
+    /// A curator changed the curation status of a JIG or resource.
+    ///
+    /// `data`: `{ "jigId": <JigId>, "curationStatus": <CurationStatus> }`, with `resourceId` instead of `jigId` for resources.
+    CurationStatusChanged = 2,
+
+    /// A student finished playing a JIG in a player session.
+    ///
+    /// `data`: `{ "sessionIndex": <JigPlayerSessionIndex>, "result": <PlayerSessionInstanceResult> }`
+    PlayerSessionCompleted = 3,
+
+    /// The subscription of an account changed, e.g. it was renewed or canceled.
+    ///
+    /// Sent to the account's admins.
+    ///
+    /// `data`: `{ "accountId": <AccountId>, "status": <SubscriptionStatus> }`
+    SubscriptionChanged = 4,
+}
+
+impl TryFrom<i16> for WebhookEvent {
+    type Error = anyhow::Error;
+
+    fn try_from(i: i16) -> Result<Self, Self::Error> {
+        match i {
+            0 => Ok(Self::JigPublished),
+            1 => Ok(Self::ResourcePublished),
+            2 => Ok(Self::CurationStatusChanged),
+            3 => Ok(Self::PlayerSessionCompleted),
+            4 => Ok(Self::SubscriptionChanged),
+            _ => anyhow::bail!("Webhook event {} is invalid", i),
+        }
+    }
+}
+
+/// Body of a webhook delivery.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookPayload {
+    /// ID of the delivery. Retries of a delivery have the same ID.
+    pub id: WebhookDeliveryId,
+
+    /// What happened
+    pub event: WebhookEvent,
+
+    /// When it happened
+    pub created_at: DateTime<Utc>,
+
+    /// Details of the event, see [`WebhookEvent`]
+    pub data: serde_json::Value,
+}
+
+/// A registered webhook.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Webhook {
+    /// ID of the webhook
+    pub id: WebhookId,
+
+    /// URL events are `POST`ed to
+    pub url: String,
+
+    /// Events the webhook is subscribed to
+    pub events: Vec<WebhookEvent>,
+
+    /// Whether the webhook receives every user's events, rather than only the owner's. Admins only.
+    pub all_users: bool,
+
+    /// Whether events are sent to the webhook
+    pub enabled: bool,
+
+    /// When the webhook was created
+    pub created_at: DateTime<Utc>,
+
+    /// When the webhook was last edited
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+make_path_parts!(WebhookCreatePath => "/v1/webhook");
+
+/// Request to register a webhook.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookCreateRequest {
+    /// URL to `POST` events to. Must be `https`.
+    pub url: String,
+
+    /// Events to subscribe to
+    pub events: Vec<WebhookEvent>,
+
+    /// Whether to receive every user's events, rather than only the requesting user's. Admins only.
+    #[serde(default)]
+    pub all_users: bool,
+}
+
+/// Response for registering a webhook.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookCreateResponse {
+    /// ID of the webhook
+    pub id: WebhookId,
+
+    /// Secret deliveries are signed with. It can't be retrieved again.
+    pub secret: String,
+}
+
+make_path_parts!(WebhookListPath => "/v1/webhook");
+
+/// Response for listing the webhooks of the requesting user.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookListResponse {
+    /// The user's webhooks, most recently created first
+    pub webhooks: Vec<Webhook>,
+}
+
+make_path_parts!(WebhookUpdatePath => "/v1/webhook/{}" => WebhookId);
+
+/// Request to update a webhook.
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookUpdateRequest {
+    /// URL to `POST` events to
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+
+    /// Events to subscribe to
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub events: Option<Vec<WebhookEvent>>,
+
+    /// Whether events are sent to the webhook
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+}
+
+make_path_parts!(WebhookDeletePath => "/v1/webhook/{}" => WebhookId);
+
+/// Status of a webhook delivery.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "backend", derive(sqlx::Type))]
+#[serde(rename_all = "camelCase")]
+#[repr(i16)]
+pub enum WebhookDeliveryStatus {
+    /// Not delivered yet, it will be (re)tried
+    Pending = 0,
+
+    /// The URL accepted the delivery
+    Delivered = 1,
+
+    /// Every attempt failed, it won't be retried
+    Failed = 2,
+}
+
+/// A delivery of an event to a webhook.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookDelivery {
+    /// ID of the delivery, as in [`WebhookPayload::id`]
+    pub id: WebhookDeliveryId,
+
+    /// Event that was delivered
+    pub event: WebhookEvent,
+
+    /// Whether the event was delivered
+    pub status: WebhookDeliveryStatus,
+
+    /// How many times delivery was attempted
+    pub attempts: u32,
+
+    /// HTTP status the URL responded with on the last attempt, if it responded
+    pub response_status: Option<u16>,
+
+    /// Why the last attempt failed, if it did
+    pub error: Option<String>,
+
+    /// When the event happened
+    pub created_at: DateTime<Utc>,
+
+    /// When delivery was last attempted
+    pub last_attempt_at: Option<DateTime<Utc>>,
+
+    /// When delivery will be retried, if it's pending
+    pub next_attempt_at: Option<DateTime<Utc>>,
+}
+
+make_path_parts!(WebhookDeliveryListPath => "/v1/webhook/{}/delivery" => WebhookId);
+
+/// Response for listing the deliveries of a webhook.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookDeliveryListResponse {
+    /// The 100 most recent deliveries, most recent first
+    pub deliveries: Vec<WebhookDelivery>,
+}